@@ -20,8 +20,19 @@ pub trait SpotAgent: Send + Sync {
     fn available_tools(&self) -> Vec<&str>;
 
     /// Get the agent's capabilities.
+    ///
+    /// Agents that don't declare capabilities have all of them.
     fn capabilities(&self) -> AgentCapabilities {
-        AgentCapabilities::default()
+        AgentCapabilities::full()
+    }
+
+    /// Whether the executor enforces [`capabilities`](Self::capabilities) when
+    /// registering tools.
+    ///
+    /// Declared capabilities are always enforced; override this only for
+    /// agents whose capabilities are informational.
+    fn enforces_capabilities(&self) -> bool {
+        true
     }
 
     /// Get the visibility level for this agent.
@@ -100,24 +111,30 @@ mod tests {
     // =========================================================================
 
     #[test]
-    fn test_default_capabilities_all_false() {
+    fn test_default_capabilities_allow_everything() {
         let agent = MockAgent::new();
         let caps = agent.capabilities();
 
-        assert!(!caps.shell, "Default capabilities should have shell=false");
+        assert!(caps.shell, "Default capabilities should have shell=true");
         assert!(
-            !caps.file_write,
-            "Default capabilities should have file_write=false"
+            caps.file_write,
+            "Default capabilities should have file_write=true"
         );
         assert!(
-            !caps.file_read,
-            "Default capabilities should have file_read=false"
+            caps.file_read,
+            "Default capabilities should have file_read=true"
         );
         assert!(
-            !caps.sub_agents,
-            "Default capabilities should have sub_agents=false"
+            caps.sub_agents,
+            "Default capabilities should have sub_agents=true"
         );
-        assert!(!caps.mcp, "Default capabilities should have mcp=false");
+        assert!(caps.mcp, "Default capabilities should have mcp=true");
+    }
+
+    #[test]
+    fn test_default_enforces_capabilities() {
+        let agent = MockAgent::new();
+        assert!(agent.enforces_capabilities());
     }
 
     #[test]
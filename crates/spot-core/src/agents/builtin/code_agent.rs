@@ -36,6 +36,14 @@ impl SpotAgent for CodeAgent {
             "list_processes",
             "read_process_output",
             "kill_process",
+            "write_process_input",
+            "wait_for_output",
+            // Git tools (read-only)
+            "git_status",
+            "git_diff",
+            "git_log",
+            "git_blame",
+            "git_show",
         ]
     }
 
@@ -125,6 +133,20 @@ mod tests {
         assert!(tools.contains(&"list_processes"));
         assert!(tools.contains(&"read_process_output"));
         assert!(tools.contains(&"kill_process"));
+        assert!(tools.contains(&"write_process_input"));
+        assert!(tools.contains(&"wait_for_output"));
+    }
+
+    #[test]
+    fn test_code_agent_has_git_tools() {
+        let agent = CodeAgent;
+        let tools = agent.available_tools();
+
+        assert!(tools.contains(&"git_status"));
+        assert!(tools.contains(&"git_diff"));
+        assert!(tools.contains(&"git_log"));
+        assert!(tools.contains(&"git_blame"));
+        assert!(tools.contains(&"git_show"));
     }
 
     #[test]
@@ -133,8 +155,8 @@ mod tests {
         let tools = agent.available_tools();
         assert_eq!(
             tools.len(),
-            11,
-            "Code Agent should have exactly 11 tools: {:?}",
+            18,
+            "Code Agent should have exactly 18 tools: {:?}",
             tools
         );
     }
@@ -23,7 +23,14 @@ impl SpotAgent for ExploreAgent {
     }
 
     fn available_tools(&self) -> Vec<&str> {
-        vec!["list_files", "read_file", "grep"]
+        vec![
+            "list_files",
+            "read_file",
+            "grep",
+            "outline_file",
+            "find_definition",
+            "find_references",
+        ]
     }
 
     fn visibility(&self) -> AgentVisibility {
@@ -72,7 +79,10 @@ mod tests {
         assert!(tools.contains(&"list_files"));
         assert!(tools.contains(&"read_file"));
         assert!(tools.contains(&"grep"));
-        assert_eq!(tools.len(), 3);
+        assert!(tools.contains(&"outline_file"));
+        assert!(tools.contains(&"find_definition"));
+        assert!(tools.contains(&"find_references"));
+        assert_eq!(tools.len(), 6);
     }
 
     #[test]
@@ -24,7 +24,19 @@ macro_rules! define_reviewer {
             }
 
             fn available_tools(&self) -> Vec<&str> {
-                vec!["list_files", "read_file", "grep"]
+                vec![
+                    "list_files",
+                    "read_file",
+                    "grep",
+                    "outline_file",
+                    "find_definition",
+                    "find_references",
+                    "git_status",
+                    "git_diff",
+                    "git_log",
+                    "git_blame",
+                    "git_show",
+                ]
             }
 
             fn capabilities(&self) -> AgentCapabilities {
@@ -169,7 +181,7 @@ mod tests {
         let agent = CodeReviewerAgent;
         let tools = agent.available_tools();
         // Reviewers should have minimal tools
-        assert_eq!(tools.len(), 3, "Reviewer should have exactly 3 tools");
+        assert_eq!(tools.len(), 11, "Reviewer should have exactly 11 tools");
     }
 
     // =========================================================================
@@ -197,8 +209,8 @@ mod tests {
         let tools = agent.available_tools();
         assert_eq!(
             tools.len(),
-            3,
-            "Reviewer should have exactly 3 tools: {:?}",
+            11,
+            "Reviewer should have exactly 11 tools: {:?}",
             tools
         );
     }
@@ -214,6 +226,8 @@ mod tests {
         assert!(tools.contains(&"read_file"));
         assert!(tools.contains(&"list_files"));
         assert!(tools.contains(&"grep"));
+        assert!(tools.contains(&"find_references"));
+        assert!(tools.contains(&"git_diff"));
 
         // no shell capability = no shell tool
         assert!(!caps.shell);
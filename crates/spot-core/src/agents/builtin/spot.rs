@@ -33,7 +33,11 @@ impl SpotAgent for SpotMainAgent {
     }
 
     fn capabilities(&self) -> AgentCapabilities {
-        AgentCapabilities::planning()
+        // The orchestrator is where users attach MCP servers
+        AgentCapabilities {
+            mcp: true,
+            ..AgentCapabilities::planning()
+        }
     }
 }
 
@@ -127,7 +131,8 @@ mod tests {
         );
         assert!(caps.file_read, "Spot should have file_read capability");
         assert!(caps.sub_agents, "Spot should have sub_agents capability");
-        assert!(!caps.mcp, "Spot should not have mcp capability");
+        assert!(caps.mcp, "Spot should keep MCP tools");
+        assert!(agent.enforces_capabilities());
     }
 
     #[test]
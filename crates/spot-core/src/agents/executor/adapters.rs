@@ -2,7 +2,9 @@
 //!
 //! Contains wrapper types that bridge our implementations to serdesAI's interfaces:
 //! - `ArcModel`: Wraps `Arc<dyn Model>` to implement `Model` trait
-//! - `ToolExecutorAdapter`: Adapts `Arc<dyn Tool>` to `ToolExecutor<()>`
+//! - `ToolExecutorAdapter`: Adapts `Arc<dyn Tool>` to `ToolExecutor<()>`,
+//!   checkpointing and publishing diffs of files changed by file tools and
+//!   recording the processes tools start
 //! - `RecordingToolExecutor`: Records tool returns during streaming
 
 use async_trait::async_trait;
@@ -14,6 +16,11 @@ use serdes_ai_core::{ModelRequest, ModelResponse, ModelSettings, ToolReturnPart}
 use serdes_ai_models::{Model, ModelError, ModelProfile, ModelRequestParameters, StreamedResponse};
 use serdes_ai_tools::{RunContext, Tool, ToolError, ToolReturn};
 
+use crate::checkpoints::CheckpointRecorder;
+use crate::messaging::{DiffMessage, Message, MessageSender};
+use crate::tools::diff::UnifiedDiff;
+use crate::tools::ProcessScope;
+
 /// Wrapper to make `Arc<dyn Model>` implement `Model`.
 ///
 /// This allows us to use dynamically dispatched models with serdesAI's
@@ -67,11 +74,59 @@ impl Model for ArcModel {
 /// serdesAI's executor interface (which uses `execute()`).
 pub(super) struct ToolExecutorAdapter {
     tool: Arc<dyn Tool + Send + Sync>,
+    /// Bus and agent name for publishing file diffs
+    diffs: Option<(MessageSender, String)>,
+    /// Records the previous content of changed files
+    checkpoints: Option<CheckpointRecorder>,
+    /// Records the processes the tool starts
+    processes: Option<ProcessScope>,
 }
 
 impl ToolExecutorAdapter {
     pub fn new(tool: Arc<dyn Tool + Send + Sync>) -> Self {
-        Self { tool }
+        Self {
+            tool,
+            diffs: None,
+            checkpoints: None,
+            processes: None,
+        }
+    }
+
+    /// Publish a [`DiffMessage`] whenever the tool changes a file.
+    pub fn with_diffs(mut self, bus: Option<MessageSender>, agent_name: &str) -> Self {
+        self.diffs = bus.map(|sender| (sender, agent_name.to_string()));
+        self
+    }
+
+    /// Checkpoint files before the tool changes them.
+    pub fn with_checkpoints(mut self, checkpoints: Option<CheckpointRecorder>) -> Self {
+        self.checkpoints = checkpoints;
+        self
+    }
+
+    /// Record the processes the tool starts, so cancelling the run kills them.
+    pub fn with_processes(mut self, processes: &ProcessScope) -> Self {
+        self.processes = Some(processes.clone());
+        self
+    }
+
+    async fn call(&self, ctx: &RunContext, args: JsonValue) -> Result<ToolReturn, ToolError> {
+        let call = self.tool.call(ctx, args);
+        match &self.processes {
+            Some(processes) => processes.scope(call).await,
+            None => call.await,
+        }
+    }
+}
+
+/// Path of the file a tool call modifies, for tools that modify files.
+fn modified_file(tool_name: Option<&str>, args: &JsonValue) -> Option<String> {
+    match tool_name? {
+        "edit_file" | "delete_file" => args
+            .get("file_path")
+            .and_then(|v| v.as_str())
+            .map(str::to_string),
+        _ => None,
     }
 }
 
@@ -84,7 +139,46 @@ impl serdes_ai_agent::ToolExecutor<()> for ToolExecutorAdapter {
     ) -> Result<ToolReturn, ToolError> {
         // Convert serdes_ai_agent::RunContext to serdes_ai_tools::RunContext
         let tool_ctx = RunContext::minimal(&ctx.model_name);
-        self.tool.call(&tool_ctx, args).await
+
+        let tracked = self.diffs.is_some() || self.checkpoints.is_some();
+        let Some(path) = modified_file(ctx.tool_name.as_deref(), &args).filter(|_| tracked) else {
+            return self.call(&tool_ctx, args).await;
+        };
+
+        let before = std::fs::read(&path).ok();
+        let result = self.call(&tool_ctx, args).await;
+        if !matches!(&result, Ok(ret) if !ret.is_error()) {
+            return result;
+        }
+
+        let after = std::fs::read(&path).ok();
+        if before == after {
+            return result;
+        }
+
+        let checkpoint = self.checkpoints.as_ref().map(|recorder| {
+            recorder.record(
+                ctx.tool_call_id.as_deref(),
+                &path,
+                before.clone(),
+                after.clone(),
+            )
+        });
+
+        if let Some((sender, agent_name)) = &self.diffs {
+            let old = before.as_deref().map(String::from_utf8_lossy);
+            let new = after.as_deref().map(String::from_utf8_lossy);
+            let diff = UnifiedDiff::between(&path, old.as_deref(), new.as_deref());
+            if !diff.is_empty() {
+                let _ = sender.send(Message::Diff(
+                    DiffMessage::from_unified(&diff)
+                        .with_agent(agent_name.as_str())
+                        .with_checkpoint(checkpoint),
+                ));
+            }
+        }
+
+        result
     }
 }
 
@@ -323,6 +417,91 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[tokio::test]
+    async fn tool_executor_adapter_publishes_file_diffs() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("notes.txt");
+        std::fs::write(&path, "one\ntwo\n").unwrap();
+        let path = path.to_str().unwrap().to_string();
+
+        let bus = crate::messaging::MessageBus::new();
+        let mut rx = bus.subscribe();
+        let tool = crate::tools::SpotToolRegistry::new()
+            .tools_by_name(&["edit_file"])
+            .remove(0);
+        let adapter = ToolExecutorAdapter::new(tool).with_diffs(Some(bus.sender()), "code-agent");
+
+        let ctx = make_test_ctx("test-model", Some("edit_file"), Some("call-1"));
+        let result = adapter
+            .execute(
+                serde_json::json!({
+                    "file_path": path,
+                    "edits": [{ "search": "two", "replace": "three" }]
+                }),
+                &ctx,
+            )
+            .await
+            .unwrap();
+        assert!(!result.is_error());
+
+        let Some(Message::Diff(diff)) = rx.try_recv().unwrap() else {
+            panic!("expected a diff message");
+        };
+        assert_eq!(diff.path, path);
+        assert_eq!(diff.agent_name.as_deref(), Some("code-agent"));
+        assert_eq!((diff.additions(), diff.deletions()), (1, 1));
+    }
+
+    #[tokio::test]
+    async fn tool_executor_adapter_checkpoints_file_changes() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("notes.txt");
+        std::fs::write(&path, "one\ntwo\n").unwrap();
+
+        let bus = crate::messaging::MessageBus::new();
+        let mut rx = bus.subscribe();
+        let store = crate::checkpoints::CheckpointStore::new();
+        let recorder = store.recorder();
+        let tool = crate::tools::SpotToolRegistry::new()
+            .tools_by_name(&["delete_file"])
+            .remove(0);
+        let adapter = ToolExecutorAdapter::new(tool)
+            .with_diffs(Some(bus.sender()), "code-agent")
+            .with_checkpoints(Some(recorder.clone()));
+
+        let ctx = make_test_ctx("test-model", Some("delete_file"), Some("call-7"));
+        adapter
+            .execute(serde_json::json!({ "file_path": path }), &ctx)
+            .await
+            .unwrap();
+        assert!(!path.exists());
+
+        let Some(Message::Diff(diff)) = rx.try_recv().unwrap() else {
+            panic!("expected a diff message");
+        };
+        let key = diff.checkpoint.expect("diff should link its checkpoint");
+        assert_eq!(key.run_id, recorder.run_id());
+        assert_eq!(key.tool_call_id, "call-7");
+
+        store.revert(&key).unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "one\ntwo\n");
+    }
+
+    #[tokio::test]
+    async fn tool_executor_adapter_ignores_other_tools() {
+        let bus = crate::messaging::MessageBus::new();
+        let mut rx = bus.subscribe();
+        let tool: Arc<dyn Tool + Send + Sync> = Arc::new(MockTool::new("grep", "match"));
+        let adapter = ToolExecutorAdapter::new(tool).with_diffs(Some(bus.sender()), "code-agent");
+
+        let ctx = make_test_ctx("test-model", Some("grep"), None);
+        adapter
+            .execute(serde_json::json!({ "file_path": "x" }), &ctx)
+            .await
+            .unwrap();
+        assert!(rx.try_recv().unwrap().is_none());
+    }
+
     #[test]
     fn recording_tool_executor_new() {
         let tool: Arc<dyn Tool + Send + Sync> = Arc::new(MockTool::new("test", "result"));
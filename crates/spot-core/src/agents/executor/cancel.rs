@@ -0,0 +1,167 @@
+//! Cooperative cancellation of agent runs.
+//!
+//! A [`CancelHandle`] is shared between a frontend and a running agent.
+//! Cancelling it stops the model stream, drops in-flight tool calls, kills
+//! shell processes the run started and propagates into nested
+//! `invoke_agent` runs.
+
+use std::time::Duration;
+
+use tokio_util::sync::CancellationToken;
+use tracing::{info, warn};
+
+use super::{AgentExecutor, ExecutorError};
+use crate::tools::{get_global_context, ProcessScope};
+
+/// Marker appended to the message history of a cancelled run.
+pub const CANCELLED_MARKER: &str = "[Cancelled by user]";
+
+/// Handle for cancelling an agent run.
+///
+/// Cheap to clone; all clones cancel the same run. Also records the
+/// processes the run's tool calls start, so cancelling kills only those.
+#[derive(Debug, Clone, Default)]
+pub struct CancelHandle {
+    token: CancellationToken,
+    processes: ProcessScope,
+}
+
+impl CancelHandle {
+    /// Create a new, uncancelled handle.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Request cancellation of the run (and any nested runs).
+    pub fn cancel(&self) {
+        self.token.cancel();
+    }
+
+    /// Check if cancellation was requested.
+    pub fn is_cancelled(&self) -> bool {
+        self.token.is_cancelled()
+    }
+
+    /// Wait until cancellation is requested.
+    pub async fn cancelled(&self) {
+        self.token.cancelled().await
+    }
+
+    /// Create a handle that is cancelled with this one but can also be
+    /// cancelled on its own (used for nested runs and single streams).
+    pub(crate) fn child(&self) -> Self {
+        Self {
+            token: self.token.child_token(),
+            processes: self.processes.child(),
+        }
+    }
+
+    /// Processes started by this run, including those of nested runs.
+    pub(crate) fn processes(&self) -> &ProcessScope {
+        &self.processes
+    }
+}
+
+impl<'a> AgentExecutor<'a> {
+    /// Sleep before a retry, returning early if the run is cancelled.
+    pub(super) async fn sleep_unless_cancelled(
+        &self,
+        delay: Duration,
+    ) -> Result<(), ExecutorError> {
+        tokio::select! {
+            _ = self.cancel.cancelled() => Err(ExecutorError::Cancelled),
+            _ = tokio::time::sleep(delay) => Ok(()),
+        }
+    }
+}
+
+/// Kill the shell processes started by a cancelled run that are still running.
+///
+/// Kills go through the tool context so the frontend owning the processes
+/// can update its views.
+pub(super) async fn kill_run_processes(cancel: &CancelHandle) {
+    let Some(ctx) = get_global_context() else {
+        return;
+    };
+
+    for process_id in cancel.processes().process_ids() {
+        let running = ctx
+            .store
+            .snapshot(&process_id)
+            .is_some_and(|snapshot| snapshot.exit_code.is_none());
+        if !running {
+            continue;
+        }
+        info!(process_id = %process_id, "Killing process of cancelled run");
+        if let Err(e) = ctx.kill_process(process_id.clone()).await {
+            warn!(process_id = %process_id, error = %e, "Failed to kill process");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::Database;
+    use crate::models::ModelRegistry;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_cancel_handle_clones_share_state() {
+        let handle = CancelHandle::new();
+        let clone = handle.clone();
+        assert!(!clone.is_cancelled());
+
+        handle.cancel();
+        assert!(clone.is_cancelled());
+    }
+
+    #[test]
+    fn test_cancel_handle_child_follows_parent() {
+        let parent = CancelHandle::new();
+        let child = parent.child();
+
+        // Cancelling a child leaves the parent running
+        let sibling = parent.child();
+        sibling.cancel();
+        assert!(!parent.is_cancelled());
+        assert!(!child.is_cancelled());
+
+        parent.cancel();
+        assert!(child.is_cancelled());
+    }
+
+    #[tokio::test]
+    async fn test_cancel_handle_wakes_waiters() {
+        let handle = CancelHandle::new();
+        let waiter = {
+            let handle = handle.clone();
+            tokio::spawn(async move { handle.cancelled().await })
+        };
+
+        handle.cancel();
+        tokio::time::timeout(Duration::from_secs(1), waiter)
+            .await
+            .expect("waiter should wake up")
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_sleep_unless_cancelled_returns_early() {
+        let temp = TempDir::new().unwrap();
+        let db = Database::open_at(temp.path().join("test.db")).unwrap();
+        db.migrate().unwrap();
+        let registry = ModelRegistry::new();
+        let cancel = CancelHandle::new();
+        let executor = AgentExecutor::new(&db, &registry).with_cancel(cancel.clone());
+
+        cancel.cancel();
+        let result = tokio::time::timeout(
+            Duration::from_secs(1),
+            executor.sleep_unless_cancelled(Duration::from_secs(60)),
+        )
+        .await
+        .expect("sleep should stop on cancel");
+        assert!(matches!(result, Err(ExecutorError::Cancelled)));
+    }
+}
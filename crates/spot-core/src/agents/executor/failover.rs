@@ -0,0 +1,645 @@
+//! Transient-error retries, circuit breaking and model fallback.
+//!
+//! [`FailoverModel`] wraps the model of an agent run so that, for every
+//! model request:
+//! - 5xx and network errors are retried with a [`RetryPolicy`]
+//! - repeated failures open the provider's [`CircuitBreaker`]
+//! - once retries are exhausted (or the circuit is open) the request goes to
+//!   the fallback model from `SpotConfig.resilience.fallbacks`
+//!
+//! Only the failed request is repeated, so tool calls that already ran are
+//! never replayed. Fallback models get request settings built from their own
+//! `model_settings.<model>.*` and provider, not the primary model's. Every retry and fallback is published as a
+//! `Message::Retry` event.
+
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use tracing::{info, warn};
+
+use serdes_ai_core::{ModelRequest, ModelResponse, ModelSettings};
+use serdes_ai_models::{Model, ModelError, ModelProfile, ModelRequestParameters, StreamedResponse};
+
+use super::model_factory::get_model_with_bus;
+use super::request_settings::RequestSettings;
+use super::resilience::{shared_circuit_breaker, CircuitBreaker, FallbackChain, RetryPolicy};
+use super::{AgentExecutor, ExecutorError};
+use crate::config::{ResilienceConfig, Settings, SpotConfig};
+use crate::messaging::{Message, MessageSender, RetryMessage};
+use crate::models::settings::ModelSettings as SpotModelSettings;
+use crate::models::{ModelRegistry, ModelType};
+
+impl<'a> AgentExecutor<'a> {
+    /// Wrap `model` so its requests are retried and fall back to the models
+    /// configured for `model_name` in `[resilience.fallbacks]`.
+    ///
+    /// Fallback models are built up front; one that cannot be built is
+    /// skipped with a warning.
+    pub(super) async fn with_failover(
+        &self,
+        model_name: &str,
+        model: Arc<dyn Model>,
+    ) -> Arc<dyn Model> {
+        let config = SpotConfig::load(&Settings::new(self.db)).resilience;
+        let policy = retry_policy(&config);
+        let fallbacks = FallbackChain::from_mappings(config.fallbacks);
+
+        let mut chain = vec![Candidate::new(self.registry, model_name, model)];
+        let mut tried = vec![model_name.to_string()];
+        let mut next = fallbacks.get_fallback(model_name);
+
+        while let Some(name) = next {
+            if tried.iter().any(|t| t == name) {
+                break;
+            }
+            tried.push(name.to_string());
+
+            let settings = SpotModelSettings::load(self.db, name).ok();
+            match get_model_with_bus(
+                self.db,
+                name,
+                self.registry,
+                settings.as_ref(),
+                self.bus.as_ref(),
+            )
+            .await
+            {
+                Ok(fallback) => chain.push(Candidate::fallback(
+                    self.registry,
+                    name,
+                    fallback,
+                    &settings.unwrap_or_default(),
+                )),
+                Err(e) => self.publish_warning(format!(
+                    "Skipping fallback model '{}' for '{}': {}",
+                    name, model_name, e
+                )),
+            }
+            next = fallbacks.get_fallback(name);
+        }
+
+        Arc::new(FailoverModel::new(chain, policy, self.bus.clone()))
+    }
+
+    /// Publish a `Message::Retry` event for a retry or fallback.
+    pub(super) fn publish_retry(
+        &self,
+        provider: &str,
+        attempt: u32,
+        reason: &str,
+        wait: Option<Duration>,
+    ) {
+        send_retry(
+            self.bus.as_ref(),
+            retry_message(provider, attempt, reason, wait),
+        );
+    }
+
+    /// Publish a `Message::Retry` event for an API key rotation.
+    pub(super) fn publish_rotation(&self, provider: &str, attempt: u32) {
+        send_retry(
+            self.bus.as_ref(),
+            Message::retry_rotation(provider, attempt, "rate limited, rotating API key"),
+        );
+    }
+}
+
+/// Provider of `model_name`, used to key circuit breakers and in
+/// `Message::Retry` events.
+///
+/// Custom endpoints ("cerebras:llama-3") use their prefix, built-in providers
+/// their lowercase name ("openai", "anthropic", "google"). Unknown models are
+/// keyed by their own name.
+pub(super) fn provider_id(registry: &ModelRegistry, model_name: &str) -> String {
+    if let Some(config) = registry.get(model_name) {
+        match config.model_type {
+            ModelType::Openai | ModelType::ChatgptOauth => return "openai".to_string(),
+            ModelType::Anthropic | ModelType::ClaudeCode => return "anthropic".to_string(),
+            ModelType::Gemini | ModelType::GoogleVertex => return "google".to_string(),
+            ModelType::CustomOpenai | ModelType::CustomAnthropic => {
+                if let Some((prefix, _)) = model_name.split_once(':') {
+                    return prefix.to_lowercase();
+                }
+            }
+            ModelType::AzureOpenai | ModelType::Openrouter => {
+                return config.model_type.to_string();
+            }
+            ModelType::RoundRobin => {}
+        }
+    }
+
+    if model_name.starts_with("gpt-") || model_name.starts_with("o1-") {
+        return "openai".to_string();
+    }
+    if model_name.starts_with("claude-") {
+        return "anthropic".to_string();
+    }
+    if model_name.starts_with("gemini-") {
+        return "google".to_string();
+    }
+
+    model_name.to_string()
+}
+
+/// Build the retry policy from configuration.
+fn retry_policy(config: &ResilienceConfig) -> RetryPolicy {
+    RetryPolicy::new()
+        .with_max_retries(config.max_retries)
+        .with_base_delay(Duration::from_millis(config.base_delay_ms))
+}
+
+/// Build a `Message::Retry` event for a retry (with `wait`) or a fallback.
+fn retry_message(provider: &str, attempt: u32, reason: &str, wait: Option<Duration>) -> Message {
+    match wait {
+        Some(wait) => Message::retry_cooldown(
+            provider,
+            attempt,
+            reason,
+            wait.as_millis().div_ceil(1000) as u64,
+        ),
+        None => Message::Retry(RetryMessage {
+            provider: provider.to_string(),
+            attempt,
+            reason: reason.to_string(),
+            wait_secs: None,
+            is_rotation: false,
+        }),
+    }
+}
+
+fn send_retry(bus: Option<&MessageSender>, message: Message) {
+    if let Some(bus) = bus {
+        let _ = bus.send(message);
+    }
+}
+
+/// Whether a model error is worth retrying (5xx, overloaded, network).
+fn is_transient(error: &ModelError) -> bool {
+    ExecutorError::Model(error.to_string()).is_transient()
+}
+
+/// A model in a failover chain.
+struct Candidate {
+    name: String,
+    provider: String,
+    model: Arc<dyn Model>,
+    breaker: Arc<CircuitBreaker>,
+    /// Request settings for this model; `None` uses the caller's settings.
+    settings: Option<ModelSettings>,
+}
+
+impl Candidate {
+    fn new(registry: &ModelRegistry, name: &str, model: Arc<dyn Model>) -> Self {
+        let provider = provider_id(registry, name);
+        Self {
+            name: name.to_string(),
+            breaker: shared_circuit_breaker(&provider),
+            provider,
+            model,
+            settings: None,
+        }
+    }
+
+    /// A fallback model, sent request settings built from its own saved
+    /// settings and provider rather than the primary model's.
+    fn fallback(
+        registry: &ModelRegistry,
+        name: &str,
+        model: Arc<dyn Model>,
+        settings: &SpotModelSettings,
+    ) -> Self {
+        let request = RequestSettings::new(settings, registry.get(name));
+        Self {
+            settings: Some(request.core),
+            ..Self::new(registry, name, model)
+        }
+    }
+
+    /// The settings to send to this model for a request made with `settings`.
+    fn settings<'s>(&'s self, settings: &'s ModelSettings) -> &'s ModelSettings {
+        self.settings.as_ref().unwrap_or(settings)
+    }
+}
+
+/// A model that retries transient errors and falls back to other models.
+///
+/// The first candidate is the model the run asked for; the rest are its
+/// fallbacks in order.
+pub(super) struct FailoverModel {
+    candidates: Vec<Candidate>,
+    policy: RetryPolicy,
+    bus: Option<MessageSender>,
+}
+
+impl FailoverModel {
+    /// `candidates` must not be empty.
+    fn new(candidates: Vec<Candidate>, policy: RetryPolicy, bus: Option<MessageSender>) -> Self {
+        debug_assert!(!candidates.is_empty(), "failover model needs a model");
+        Self {
+            candidates,
+            policy,
+            bus,
+        }
+    }
+
+    /// Send one request through the chain.
+    ///
+    /// `call` is invoked once per attempt with the model to use and the
+    /// settings to send it. Errors that are not transient are returned
+    /// straight away.
+    async fn run<'s, T, F, Fut>(
+        &'s self,
+        settings: &'s ModelSettings,
+        mut call: F,
+    ) -> Result<T, ModelError>
+    where
+        F: FnMut(&'s dyn Model, &'s ModelSettings) -> Fut,
+        Fut: Future<Output = Result<T, ModelError>>,
+    {
+        let mut index = 0;
+
+        loop {
+            let candidate = &self.candidates[index];
+            let mut attempt = 0;
+
+            // Retry on this model until it succeeds or gives up
+            let error = loop {
+                if let Err(reason) = candidate.breaker.check() {
+                    break ModelError::Api {
+                        message: reason,
+                        code: None,
+                    };
+                }
+
+                match call(candidate.model.as_ref(), candidate.settings(settings)).await {
+                    Ok(value) => {
+                        candidate.breaker.record_success();
+                        return Ok(value);
+                    }
+                    Err(e) if is_transient(&e) => {
+                        candidate.breaker.record_failure();
+                        if !self.policy.should_retry(attempt) {
+                            break e;
+                        }
+
+                        let delay = self.policy.delay_for_attempt(attempt);
+                        attempt += 1;
+                        warn!(
+                            model = %candidate.name,
+                            provider = %candidate.provider,
+                            attempt,
+                            delay_ms = delay.as_millis() as u64,
+                            error = %e,
+                            "Transient error, retrying"
+                        );
+                        send_retry(
+                            self.bus.as_ref(),
+                            retry_message(
+                                &candidate.provider,
+                                attempt,
+                                &e.to_string(),
+                                Some(delay),
+                            ),
+                        );
+                        tokio::time::sleep(delay).await;
+                    }
+                    Err(e) => return Err(e),
+                }
+            };
+
+            // Give up on this model; switch to its fallback if there is one
+            index += 1;
+            let Some(next) = self.candidates.get(index) else {
+                return Err(error);
+            };
+            info!(from = %candidate.name, to = %next.name, error = %error, "Falling back to another model");
+            send_retry(
+                self.bus.as_ref(),
+                retry_message(
+                    &candidate.provider,
+                    attempt,
+                    &format!("{} - falling back to {}", error, next.name),
+                    None,
+                ),
+            );
+        }
+    }
+}
+
+#[async_trait]
+impl Model for FailoverModel {
+    fn name(&self) -> &str {
+        self.candidates[0].model.name()
+    }
+
+    fn system(&self) -> &str {
+        self.candidates[0].model.system()
+    }
+
+    fn identifier(&self) -> String {
+        self.candidates[0].model.identifier()
+    }
+
+    async fn request(
+        &self,
+        messages: &[ModelRequest],
+        settings: &ModelSettings,
+        params: &ModelRequestParameters,
+    ) -> Result<ModelResponse, ModelError> {
+        self.run(settings, |model, settings| {
+            model.request(messages, settings, params)
+        })
+        .await
+    }
+
+    async fn request_stream(
+        &self,
+        messages: &[ModelRequest],
+        settings: &ModelSettings,
+        params: &ModelRequestParameters,
+    ) -> Result<StreamedResponse, ModelError> {
+        self.run(settings, |model, settings| {
+            model.request_stream(messages, settings, params)
+        })
+        .await
+    }
+
+    fn profile(&self) -> &ModelProfile {
+        self.candidates[0].model.profile()
+    }
+
+    async fn count_tokens(&self, messages: &[ModelRequest]) -> Result<u64, ModelError> {
+        self.candidates[0].model.count_tokens(messages).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::messaging::MessageBus;
+    use crate::models::ModelConfig;
+    use crate::test_utils::{MockBehavior, MockModel};
+
+    #[test]
+    fn test_retry_policy_from_config() {
+        let config = ResilienceConfig {
+            max_retries: 7,
+            base_delay_ms: 250,
+            ..Default::default()
+        };
+        let policy = retry_policy(&config);
+        assert_eq!(policy.max_retries, 7);
+        assert_eq!(policy.base_delay, Duration::from_millis(250));
+    }
+
+    #[test]
+    fn test_provider_id() {
+        let mut registry = ModelRegistry::new();
+        registry.add(ModelConfig {
+            name: "cerebras:llama-3".to_string(),
+            model_type: ModelType::CustomOpenai,
+            ..Default::default()
+        });
+        registry.add(ModelConfig {
+            name: "claude-code-sonnet".to_string(),
+            model_type: ModelType::ClaudeCode,
+            ..Default::default()
+        });
+
+        assert_eq!(provider_id(&registry, "cerebras:llama-3"), "cerebras");
+        assert_eq!(provider_id(&registry, "claude-code-sonnet"), "anthropic");
+        assert_eq!(provider_id(&registry, "gpt-4o"), "openai");
+        assert_eq!(provider_id(&registry, "gemini-2.5-pro"), "google");
+        assert_eq!(
+            provider_id(&registry, "some-local-model"),
+            "some-local-model"
+        );
+    }
+
+    /// Policy that retries immediately, for fast tests.
+    fn instant_policy(max_retries: u32) -> RetryPolicy {
+        RetryPolicy::new()
+            .with_max_retries(max_retries)
+            .with_base_delay(Duration::ZERO)
+            .with_jitter(false)
+    }
+
+    fn transient() -> MockBehavior {
+        MockBehavior::Error("HTTP error status: 503".to_string())
+    }
+
+    fn text(reply: &str) -> MockBehavior {
+        MockBehavior::Text(reply.to_string())
+    }
+
+    /// A failover chain over mock models; the provider of each is its name.
+    fn failover(
+        models: Vec<(&str, MockBehavior)>,
+        policy: RetryPolicy,
+        bus: Option<MessageSender>,
+    ) -> (FailoverModel, Vec<Arc<MockModel>>) {
+        let registry = ModelRegistry::new();
+        let mocks: Vec<_> = models
+            .into_iter()
+            .map(|(name, behavior)| Arc::new(MockModel::new(name).with_behavior(behavior)))
+            .collect();
+        let candidates = mocks
+            .iter()
+            .map(|mock| Candidate::new(&registry, mock.name(), Arc::clone(mock) as Arc<dyn Model>))
+            .collect();
+        (FailoverModel::new(candidates, policy, bus), mocks)
+    }
+
+    async fn reply(model: &FailoverModel) -> Result<String, ModelError> {
+        let settings = ModelSettings::default();
+        let params = ModelRequestParameters::default();
+        model
+            .request(&[], &settings, &params)
+            .await
+            .map(|r| r.text_content())
+    }
+
+    #[tokio::test]
+    async fn test_failover_returns_first_success() {
+        let (model, mocks) = failover(vec![("failover-ok", text("ok"))], instant_policy(3), None);
+
+        assert_eq!(reply(&model).await.unwrap(), "ok");
+        assert_eq!(mocks[0].request_count(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_failover_does_not_retry_permanent_errors() {
+        let (model, mocks) = failover(
+            vec![
+                (
+                    "failover-permanent",
+                    MockBehavior::Error("invalid api key".to_string()),
+                ),
+                ("failover-permanent-backup", text("backup")),
+            ],
+            instant_policy(3),
+            None,
+        );
+
+        let err = reply(&model).await.unwrap_err();
+        assert!(err.to_string().contains("invalid api key"));
+        assert_eq!(mocks[0].request_count(), 1);
+        assert_eq!(mocks[1].request_count(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_failover_retries_transient_errors_and_publishes() {
+        let bus = MessageBus::new();
+        let mut rx = bus.subscribe();
+        let (model, mocks) = failover(
+            vec![(
+                "failover-transient",
+                MockBehavior::Queue(vec![transient(), text("recovered")]),
+            )],
+            instant_policy(3),
+            Some(bus.sender()),
+        );
+
+        assert_eq!(reply(&model).await.unwrap(), "recovered");
+        assert_eq!(mocks[0].request_count(), 2);
+
+        match rx.try_recv().unwrap() {
+            Some(Message::Retry(retry)) => {
+                assert_eq!(retry.provider, "failover-transient");
+                assert_eq!(retry.attempt, 1);
+                assert!(!retry.is_rotation);
+                assert!(retry.reason.contains("503"));
+            }
+            other => panic!("expected a retry message, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_failover_switches_to_fallback_model() {
+        let (model, mocks) = failover(
+            vec![
+                ("failover-primary", transient()),
+                ("failover-backup", text("backup")),
+            ],
+            instant_policy(1),
+            None,
+        );
+
+        assert_eq!(reply(&model).await.unwrap(), "backup");
+        assert_eq!(mocks[0].request_count(), 2);
+        assert_eq!(mocks[1].request_count(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_fallback_uses_its_own_request_settings() {
+        let mut registry = ModelRegistry::new();
+        registry.add(ModelConfig {
+            name: "failover-gpt".to_string(),
+            model_type: ModelType::CustomOpenai,
+            context_length: 400_000,
+            ..Default::default()
+        });
+        registry.add(ModelConfig {
+            name: "failover-claude".to_string(),
+            model_type: ModelType::CustomAnthropic,
+            context_length: 200_000,
+            ..Default::default()
+        });
+
+        let primary = Arc::new(MockModel::new("failover-gpt").with_behavior(transient()));
+        let backup = Arc::new(MockModel::new("failover-claude").with_behavior(text("backup")));
+        let backup_settings = SpotModelSettings {
+            max_tokens: Some(4096),
+            ..Default::default()
+        };
+        let model = FailoverModel::new(
+            vec![
+                Candidate::new(
+                    &registry,
+                    "failover-gpt",
+                    Arc::clone(&primary) as Arc<dyn Model>,
+                ),
+                Candidate::fallback(
+                    &registry,
+                    "failover-claude",
+                    Arc::clone(&backup) as Arc<dyn Model>,
+                    &backup_settings,
+                ),
+            ],
+            instant_policy(0),
+            None,
+        );
+
+        // The run's settings carry OpenAI-only extras and the primary's limit
+        let primary_settings = SpotModelSettings {
+            max_tokens: Some(32_000),
+            reasoning_effort: Some("high".to_string()),
+            ..Default::default()
+        };
+        let settings = RequestSettings::new(&primary_settings, registry.get("failover-gpt")).core;
+        let params = ModelRequestParameters::default();
+        let response = model.request(&[], &settings, &params).await.unwrap();
+        assert_eq!(response.text_content(), "backup");
+
+        assert!(primary.settings_log()[0].contains("reasoning_effort"));
+        let expected = RequestSettings::new(&backup_settings, registry.get("failover-claude")).core;
+        assert_eq!(backup.settings_log(), vec![format!("{:?}", expected)]);
+        assert!(!backup.settings_log()[0].contains("reasoning_effort"));
+    }
+
+    #[tokio::test]
+    async fn test_failover_retries_only_the_failed_request() {
+        let (model, mocks) = failover(
+            vec![(
+                "failover-per-request",
+                MockBehavior::Queue(vec![text("first"), transient(), text("second")]),
+            )],
+            instant_policy(3),
+            None,
+        );
+
+        // A failure on the second request of a run repeats only that request
+        assert_eq!(reply(&model).await.unwrap(), "first");
+        assert_eq!(reply(&model).await.unwrap(), "second");
+        assert_eq!(mocks[0].request_count(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_failover_open_circuit_skips_to_fallback() {
+        let breaker = shared_circuit_breaker("failover-tripped");
+        for _ in 0..5 {
+            breaker.record_failure();
+        }
+
+        let (model, mocks) = failover(
+            vec![
+                ("failover-tripped", text("tripped")),
+                ("failover-healthy", text("healthy")),
+            ],
+            instant_policy(3),
+            None,
+        );
+
+        assert_eq!(reply(&model).await.unwrap(), "healthy");
+        assert_eq!(mocks[0].request_count(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_failover_gives_up_after_last_fallback() {
+        let (model, mocks) = failover(
+            vec![
+                ("failover-last-a", transient()),
+                ("failover-last-b", transient()),
+            ],
+            instant_policy(0),
+            None,
+        );
+
+        let err = reply(&model).await.unwrap_err();
+        assert!(is_transient(&err));
+        assert_eq!(mocks[0].request_count(), 1);
+        assert_eq!(mocks[1].request_count(), 1);
+    }
+}
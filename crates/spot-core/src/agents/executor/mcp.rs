@@ -1,14 +1,16 @@
 //! MCP (Model Context Protocol) tool executor.
 //!
 //! Provides `McpToolExecutor` which wraps MCP tools to work with
-//! serdesAI's tool execution interface.
+//! serdesAI's tool execution interface. Tools are exposed under their
+//! namespaced `server__tool` name; the executor keeps the original server
+//! and tool names to route calls back.
 
 use async_trait::async_trait;
 use serde_json::Value as JsonValue;
 
 use serdes_ai_tools::{RunContext, Tool, ToolDefinition, ToolError, ToolReturn};
 
-use crate::mcp::McpManager;
+use crate::mcp::{namespaced_tool_name, McpManager};
 
 /// Tool executor that calls MCP server tools.
 ///
@@ -16,11 +18,26 @@ use crate::mcp::McpManager;
 /// share Arc across async boundaries here. The pointer is valid for
 /// the duration of the executor run.
 pub(super) struct McpToolExecutor {
+    /// Name the tool is exposed under (`server__tool`)
+    pub name: String,
     pub server_name: String,
     pub tool_name: String,
     pub mcp_manager_ptr: *const McpManager,
 }
 
+impl McpToolExecutor {
+    /// Create an executor for `tool_name` on `server_name`, exposed under its
+    /// namespaced name.
+    pub fn new(server_name: &str, tool_name: &str, mcp_manager: &McpManager) -> Self {
+        Self {
+            name: namespaced_tool_name(server_name, tool_name),
+            server_name: server_name.to_string(),
+            tool_name: tool_name.to_string(),
+            mcp_manager_ptr: mcp_manager as *const McpManager,
+        }
+    }
+}
+
 // Safety: The pointer is only used during a single executor run
 // where the McpManager is guaranteed to outlive the tool executor.
 unsafe impl Send for McpToolExecutor {}
@@ -30,7 +47,7 @@ unsafe impl Sync for McpToolExecutor {}
 impl Tool for McpToolExecutor {
     fn definition(&self) -> ToolDefinition {
         ToolDefinition::new(
-            self.tool_name.clone(),
+            self.name.clone(),
             format!("MCP tool from {}", self.server_name),
         )
     }
@@ -39,6 +56,7 @@ impl Tool for McpToolExecutor {
         // Safety: The McpManager outlives this executor
         let manager = unsafe { &*self.mcp_manager_ptr };
 
+        // Call with the server's own tool name, not the exposed one
         match manager
             .call_tool(&self.server_name, &self.tool_name, args)
             .await
@@ -85,18 +103,20 @@ mod tests {
     fn mcp_tool_executor_definition_name() {
         // Use null pointer - safe as long as we don't call()
         let executor = McpToolExecutor {
+            name: "filesystem__read_file".to_string(),
             server_name: "filesystem".to_string(),
             tool_name: "read_file".to_string(),
             mcp_manager_ptr: ptr::null(),
         };
 
         let def = executor.definition();
-        assert_eq!(def.name, "read_file");
+        assert_eq!(def.name, "filesystem__read_file");
     }
 
     #[test]
     fn mcp_tool_executor_definition_description() {
         let executor = McpToolExecutor {
+            name: "github-mcp__list_issues".to_string(),
             server_name: "github-mcp".to_string(),
             tool_name: "list_issues".to_string(),
             mcp_manager_ptr: ptr::null(),
@@ -109,6 +129,7 @@ mod tests {
     #[test]
     fn mcp_tool_executor_stores_server_and_tool_names() {
         let executor = McpToolExecutor {
+            name: "my-server__my-tool".to_string(),
             server_name: "my-server".to_string(),
             tool_name: "my-tool".to_string(),
             mcp_manager_ptr: ptr::null(),
@@ -118,6 +139,16 @@ mod tests {
         assert_eq!(executor.tool_name, "my-tool");
     }
 
+    #[test]
+    fn mcp_tool_executor_new_maps_exposed_name_back() {
+        let manager = McpManager::new();
+        let executor = McpToolExecutor::new("team.tools", "search", &manager);
+
+        assert_eq!(executor.name, "team_tools__search");
+        assert_eq!(executor.server_name, "team.tools");
+        assert_eq!(executor.tool_name, "search");
+    }
+
     // Note: call() tests require a running MCP server, skipped for unit tests.
     // Integration tests should cover MCP tool execution.
 }
@@ -5,25 +5,33 @@
 //!
 //! ## Submodules
 //! - `adapters`: Model and tool adapters for serdesAI integration
+//! - `cancel`: Cancellation handle for stopping runs
 //! - `sub_agents`: Executors for invoke_agent and list_agents tools
 //! - `mcp`: MCP tool executor
 //! - `types`: Result types and errors
 //! - `model_factory`: Model resolution and creation
 
 mod adapters;
+mod cancel;
+mod failover;
 mod mcp;
 mod model_factory;
+mod request_settings;
 pub mod resilience;
 mod retry;
+mod round_robin;
 mod sub_agents;
 mod types;
 
 // Re-export public API
+pub use cancel::CancelHandle;
 pub use model_factory::get_model;
+use model_factory::get_model_with_bus;
 pub use retry::{RetryDecision, RetryHandler};
 pub use types::{ExecuteContext, ExecutorError, ExecutorResult, ExecutorStreamReceiver};
 
 use crate::agents::SpotAgent;
+use crate::checkpoints::CheckpointStore;
 use crate::config::Settings;
 use crate::db::Database;
 use crate::mcp::McpManager;
@@ -33,7 +41,9 @@ use crate::models::ModelRegistry;
 use crate::tools::SpotToolRegistry;
 
 use adapters::{ArcModel, ToolExecutorAdapter};
+use failover::provider_id;
 use mcp::McpToolExecutor;
+use request_settings::RequestSettings;
 use sub_agents::{InvokeAgentExecutor, ListAgentsExecutor};
 
 use serdes_ai_agent::{agent, RunOptions};
@@ -41,6 +51,7 @@ use serdes_ai_core::messages::{ImageMediaType, UserContent, UserContentPart};
 use serdes_ai_core::{ModelRequest, ToolReturnPart};
 use serdes_ai_tools::{Tool, ToolDefinition};
 
+use std::collections::HashSet;
 use std::sync::Arc;
 use tokio::sync::Mutex;
 use tracing::{debug, error, info, instrument, warn};
@@ -62,6 +73,10 @@ pub struct AgentExecutor<'a> {
     bus: Option<MessageSender>,
     /// Optional retry handler for API key rotation on rate limits.
     retry_handler: Option<RetryHandler>,
+    /// Handle for cancelling runs started by this executor.
+    cancel: CancelHandle,
+    /// Optional store for checkpointing files before tools change them.
+    checkpoints: Option<CheckpointStore>,
 }
 
 impl<'a> AgentExecutor<'a> {
@@ -72,6 +87,8 @@ impl<'a> AgentExecutor<'a> {
             registry,
             bus: None,
             retry_handler: None,
+            cancel: CancelHandle::new(),
+            checkpoints: None,
         }
     }
 
@@ -107,6 +124,40 @@ impl<'a> AgentExecutor<'a> {
         self.retry_handler.is_some()
     }
 
+    /// Use `cancel` to stop runs started by this executor.
+    ///
+    /// Cancelling stops the model stream, drops in-flight tool calls, kills
+    /// shell processes started during the run and cancels nested
+    /// `invoke_agent` runs. The run then returns an [`ExecutorResult`] with
+    /// `cancelled` set, or [`ExecutorError::Cancelled`] if nothing was
+    /// streamed yet.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let cancel = CancelHandle::new();
+    /// let executor = AgentExecutor::new(&db, &registry).with_cancel(cancel.clone());
+    /// // From the UI, e.g. on Ctrl-C:
+    /// cancel.cancel();
+    /// ```
+    pub fn with_cancel(mut self, cancel: CancelHandle) -> Self {
+        self.cancel = cancel;
+        self
+    }
+
+    /// Handle that cancels runs started by this executor.
+    pub fn cancel_handle(&self) -> CancelHandle {
+        self.cancel.clone()
+    }
+
+    /// Record checkpoints in `store` before tools write or delete files.
+    ///
+    /// Sub-agent runs record into the same store, so undoing a turn also
+    /// reverts edits made by agents it invoked.
+    pub fn with_checkpoints(mut self, store: CheckpointStore) -> Self {
+        self.checkpoints = Some(store);
+        self
+    }
+
     /// Filter tool names based on settings.
     ///
     /// Filters out:
@@ -124,6 +175,83 @@ impl<'a> AgentExecutor<'a> {
             .collect()
     }
 
+    /// Drop tools that contradict the agent's declared capabilities.
+    ///
+    /// Only applies to agents that enforce capabilities. Each dropped tool is
+    /// reported as a warning on the bus.
+    fn apply_capabilities<'b>(
+        &self,
+        spot_agent: &dyn SpotAgent,
+        tool_names: Vec<&'b str>,
+    ) -> Vec<&'b str> {
+        if !spot_agent.enforces_capabilities() {
+            return tool_names;
+        }
+
+        let capabilities = spot_agent.capabilities();
+        tool_names
+            .into_iter()
+            .filter(|name| match capabilities.denied_capability(name) {
+                Some(capability) => {
+                    self.publish_warning(format!(
+                        "{} lacks the {} capability; tool '{}' was not registered",
+                        spot_agent.display_name(),
+                        capability,
+                        name
+                    ));
+                    false
+                }
+                None => true,
+            })
+            .collect()
+    }
+
+    /// Collect MCP tools for an agent, dropping them if it lacks the `mcp` capability.
+    async fn agent_mcp_tools(
+        &self,
+        spot_agent: &dyn SpotAgent,
+        mcp_manager: &McpManager,
+        tool_registry: &SpotToolRegistry,
+    ) -> Vec<(ToolDefinition, Arc<dyn Tool + Send + Sync>)> {
+        let reserved = Self::reserved_tool_names(tool_registry);
+        let tools = self
+            .collect_mcp_tools(mcp_manager, Some(spot_agent.name()), &reserved)
+            .await;
+
+        let mcp_denied = spot_agent.enforces_capabilities() && !spot_agent.capabilities().mcp;
+        if tools.is_empty() || !mcp_denied {
+            return tools;
+        }
+
+        self.publish_warning(format!(
+            "{} lacks the mcp capability; {} MCP tool(s) were not registered",
+            spot_agent.display_name(),
+            tools.len()
+        ));
+        Vec::new()
+    }
+
+    /// Names MCP tools must not take: built-in tools and the sub-agent tools.
+    fn reserved_tool_names(tool_registry: &SpotToolRegistry) -> HashSet<String> {
+        tool_registry
+            .definitions()
+            .into_iter()
+            .chain([
+                InvokeAgentExecutor::definition(),
+                ListAgentsExecutor::definition(),
+            ])
+            .map(|def| def.name)
+            .collect()
+    }
+
+    /// Log a warning (capability violation, tool collision) and publish it on the bus.
+    fn publish_warning(&self, text: String) {
+        warn!("{}", text);
+        if let Some(ref bus) = self.bus {
+            bus.warning(text);
+        }
+    }
+
     /// Check if agent wants invoke_agent tool.
     fn wants_invoke_agent(&self, tool_names: &[&str]) -> bool {
         tool_names.contains(&"invoke_agent")
@@ -152,11 +280,19 @@ impl<'a> AgentExecutor<'a> {
         let spot_settings = SpotModelSettings::load(self.db, model_name).ok();
 
         // Get the model (handles OAuth models and custom endpoints)
-        let model = get_model(self.db, model_name, self.registry, spot_settings.as_ref()).await?;
+        let model = get_model_with_bus(
+            self.db,
+            model_name,
+            self.registry,
+            spot_settings.as_ref(),
+            self.bus.as_ref(),
+        )
+        .await?;
+        let model = self.with_failover(model_name, model).await;
         let wrapped_model = ArcModel(model);
 
         // Get original tool list (before filtering) to check for special tools
-        let original_tools = spot_agent.available_tools();
+        let original_tools = self.apply_capabilities(spot_agent, spot_agent.available_tools());
         let wants_invoke = self.wants_invoke_agent(&original_tools);
         let wants_list = self.wants_list_agents(&original_tools);
 
@@ -164,16 +300,29 @@ impl<'a> AgentExecutor<'a> {
         let tool_names = self.filter_tools(original_tools);
         let tools = tool_registry.tools_by_name(&tool_names);
 
+        // Per-model request settings (temperature, output limit, seed, reasoning)
+        let request = RequestSettings::new(
+            &spot_settings.unwrap_or_default(),
+            self.registry.get(model_name),
+        );
+
         // Build the serdesAI agent
         let mut builder = agent(wrapped_model)
             .system_prompt(spot_agent.system_prompt())
             .temperature(1.0)
-            .max_tokens(30000);
+            .max_tokens(request.max_tokens);
 
         // Register built-in tools with real executors
+        let recorder = self.checkpoints.as_ref().map(CheckpointStore::recorder);
         for tool in tools {
             let def = tool.definition();
-            builder = builder.tool_with_executor(def, ToolExecutorAdapter::new(Arc::clone(&tool)));
+            builder = builder.tool_with_executor(
+                def,
+                ToolExecutorAdapter::new(Arc::clone(&tool))
+                    .with_diffs(self.bus.clone(), spot_agent.name())
+                    .with_checkpoints(recorder.clone())
+                    .with_processes(self.cancel.processes()),
+            );
         }
 
         // Add invoke_agent with custom executor (has database access)
@@ -182,7 +331,9 @@ impl<'a> AgentExecutor<'a> {
                 InvokeAgentExecutor::new(self.db, model_name, bus.clone())
             } else {
                 InvokeAgentExecutor::new_legacy(self.db, model_name)
-            };
+            }
+            .with_cancel(self.cancel.child())
+            .with_checkpoints(self.checkpoints.clone());
             builder =
                 builder.tool_with_executor(InvokeAgentExecutor::definition(), invoke_executor);
         }
@@ -195,9 +346,9 @@ impl<'a> AgentExecutor<'a> {
             );
         }
 
-        // Add MCP tools (filtered by agent attachments)
+        // Add MCP tools (filtered by agent attachments and capabilities)
         let mcp_tools = self
-            .collect_mcp_tools(mcp_manager, Some(spot_agent.name()))
+            .agent_mcp_tools(spot_agent, mcp_manager, tool_registry)
             .await;
         for (def, tool) in mcp_tools {
             builder = builder.tool_with_executor(def, ToolExecutorAdapter::new(tool));
@@ -205,49 +356,31 @@ impl<'a> AgentExecutor<'a> {
 
         let serdes_agent = builder.build();
 
-        // Load per-model settings from database
-        let spot_settings = SpotModelSettings::load(self.db, model_name).unwrap_or_default();
-
-        // Check if this model has thinking enabled (supports it and not explicitly disabled)
-        let model_supports_thinking = self
-            .registry
-            .get(model_name)
-            .map(|c| c.supports_thinking)
-            .unwrap_or(false);
-        let thinking_explicitly_disabled = spot_settings.extended_thinking == Some(false);
-        let thinking_enabled = model_supports_thinking && !thinking_explicitly_disabled;
-
-        // Convert to serdes_ai_core::ModelSettings
-        // When thinking is enabled, temperature MUST be 1.0 per Claude API requirements
-        let effective_temp = if thinking_enabled {
-            1.0
-        } else {
-            spot_settings.effective_temperature() as f64
-        };
-
-        let core_settings = serdes_ai_core::ModelSettings::new()
-            .temperature(effective_temp)
-            .top_p(spot_settings.effective_top_p() as f64)
-            .max_tokens(30000);
-
         // Set up run options with message history if provided
         let options = match message_history {
             Some(history) => RunOptions::new()
-                .model_settings(core_settings)
+                .model_settings(request.core)
                 .message_history(history),
-            None => RunOptions::new().model_settings(core_settings),
+            None => RunOptions::new().model_settings(request.core),
         };
 
-        // Run the agent
-        let result = serdes_agent
-            .run_with_options(prompt, (), options)
-            .await
-            .map_err(|e| ExecutorError::Execution(e.to_string()))?;
+        // Run the agent (dropping the run future aborts in-flight tool calls)
+        let result = tokio::select! {
+            biased;
+            _ = self.cancel.cancelled() => {
+                cancel::kill_run_processes(&self.cancel).await;
+                return Err(ExecutorError::Cancelled);
+            }
+            result = serdes_agent.run_with_options(prompt, (), options) => {
+                result.map_err(|e| ExecutorError::Execution(e.to_string()))?
+            }
+        };
 
         Ok(ExecutorResult {
             output: result.output.clone(),
             messages: result.messages,
             run_id: result.run_id,
+            cancelled: false,
         })
     }
 
@@ -259,6 +392,9 @@ impl<'a> AgentExecutor<'a> {
     ///
     /// When a retry handler is configured (via `with_retry_handler`), this method
     /// will automatically rotate API keys on rate limit (429) errors and retry.
+    /// Model requests that fail with transient 5xx and network errors are
+    /// retried with backoff and, once the provider keeps failing, sent to the
+    /// model configured in `[resilience.fallbacks]` (see `failover.rs`).
     ///
     /// # Errors
     ///
@@ -445,20 +581,7 @@ impl<'a> AgentExecutor<'a> {
 
         // Check for rate limit during streaming
         match process_result {
-            Ok((accumulated_text, final_run_id, messages)) => {
-                // Get the run_id (from RunComplete event)
-                let run_id = final_run_id.ok_or_else(|| {
-                    ExecutorError::Execution("Stream ended without RunComplete event".into())
-                })?;
-
-                bridge.agent_completed(&run_id);
-
-                Ok(ExecutorResult {
-                    output: accumulated_text,
-                    messages,
-                    run_id,
-                })
-            }
+            Ok(outcome) => outcome.into_result(&bridge),
             Err(e) if e.is_rate_limit() => {
                 error!(
                     model = %model_name,
@@ -501,6 +624,9 @@ impl<'a> AgentExecutor<'a> {
             handler.get_current_key(provider).ok_or_else(|| {
                 ExecutorError::Config(format!("No pool keys available for provider: {}", provider))
             })?;
+        // Key pools are named by env var; retry events use the provider id
+        let provider_name = provider_id(self.registry, model_name);
+        let mut attempt = 0;
 
         loop {
             // Create event bridge for this attempt
@@ -549,13 +675,22 @@ impl<'a> AgentExecutor<'a> {
                     match handler.handle_rate_limit(provider, current_key_id).await {
                         RetryDecision::RetryWithKey { key, key_id } => {
                             info!(new_key_id = key_id, "Rotated to new key, retrying");
+                            attempt += 1;
+                            self.publish_rotation(&provider_name, attempt);
                             current_key = key;
                             current_key_id = key_id;
                             continue;
                         }
                         RetryDecision::WaitAndRetry { wait_duration } => {
                             info!(wait_secs = ?wait_duration, "All keys exhausted, waiting");
-                            tokio::time::sleep(wait_duration).await;
+                            attempt += 1;
+                            self.publish_retry(
+                                &provider_name,
+                                attempt,
+                                "rate limited on all API keys",
+                                Some(wait_duration),
+                            );
+                            self.sleep_unless_cancelled(wait_duration).await?;
                             continue;
                         }
                         RetryDecision::GiveUp { reason } => {
@@ -581,21 +716,10 @@ impl<'a> AgentExecutor<'a> {
                 .await;
 
             match process_result {
-                Ok((accumulated_text, final_run_id, messages)) => {
+                Ok(outcome) => {
                     // Success! Mark the key as working
                     handler.mark_success(provider, current_key_id);
-
-                    let run_id = final_run_id.ok_or_else(|| {
-                        ExecutorError::Execution("Stream ended without RunComplete event".into())
-                    })?;
-
-                    bridge.agent_completed(&run_id);
-
-                    return Ok(ExecutorResult {
-                        output: accumulated_text,
-                        messages,
-                        run_id,
-                    });
+                    return outcome.into_result(&bridge);
                 }
                 Err(e) if e.is_rate_limit() => {
                     // Rate limit during streaming - rotate and retry
@@ -608,13 +732,22 @@ impl<'a> AgentExecutor<'a> {
                     match handler.handle_rate_limit(provider, current_key_id).await {
                         RetryDecision::RetryWithKey { key, key_id } => {
                             info!(new_key_id = key_id, "Rotated to new key, retrying");
+                            attempt += 1;
+                            self.publish_rotation(&provider_name, attempt);
                             current_key = key;
                             current_key_id = key_id;
                             continue;
                         }
                         RetryDecision::WaitAndRetry { wait_duration } => {
                             info!(wait_secs = ?wait_duration, "All keys exhausted, waiting");
-                            tokio::time::sleep(wait_duration).await;
+                            attempt += 1;
+                            self.publish_retry(
+                                &provider_name,
+                                attempt,
+                                "rate limited on all API keys",
+                                Some(wait_duration),
+                            );
+                            self.sleep_unless_cancelled(wait_duration).await?;
                             continue;
                         }
                         RetryDecision::GiveUp { reason } => {
@@ -633,7 +766,8 @@ impl<'a> AgentExecutor<'a> {
     /// Extract provider name from model configuration.
     ///
     /// Returns the FULL env var name (e.g., "CEREBRAS_API_KEY") to match
-    /// how keys are stored in the api_key_pools table.
+    /// how keys are stored in the api_key_pools table. Only used for key
+    /// pools; circuit breakers and retry events use [`provider_id`].
     fn extract_provider_for_model(&self, model_name: &str) -> Option<String> {
         // Check registry for custom config
         if let Some(config) = self.registry.get(model_name) {
@@ -660,7 +794,8 @@ impl<'a> AgentExecutor<'a> {
                 crate::models::ModelType::GoogleVertex => {
                     return Some("GOOGLE_API_KEY".to_string())
                 }
-                crate::models::ModelType::CustomOpenai => {
+                crate::models::ModelType::CustomOpenai
+                | crate::models::ModelType::CustomAnthropic => {
                     // For custom models like "cerebras:model", construct env var name
                     if let Some(pos) = model_name.find(':') {
                         let provider = &model_name[..pos];
@@ -773,7 +908,9 @@ impl<'a> AgentExecutor<'a> {
     /// publishes events to a message bus that renderers can subscribe to.
     /// This method is useful when you need direct control over event handling.
     ///
-    /// Returns a stream receiver for consuming events in real-time.
+    /// Returns a stream receiver for consuming events in real-time. Transient
+    /// errors on model requests are retried and may fall back to another
+    /// model, as in [`execute_with_bus`].
     pub async fn execute_stream(
         &self,
         spot_agent: &dyn SpotAgent,
@@ -803,16 +940,21 @@ impl<'a> AgentExecutor<'a> {
     /// Only returns tools from MCP servers that are attached to the given agent.
     /// If no agent_name is provided or the agent has no attachments, returns tools
     /// from ALL running servers (for backwards compatibility).
+    ///
+    /// Tools are exposed as `server__tool` and filtered by the agent's MCP tool
+    /// allow/deny lists. A tool whose exposed name is in `reserved` or already
+    /// taken by another MCP tool is skipped with a warning.
     async fn collect_mcp_tools(
         &self,
         mcp_manager: &McpManager,
         agent_name: Option<&str>,
+        reserved: &HashSet<String>,
     ) -> Vec<(ToolDefinition, Arc<dyn Tool + Send + Sync>)> {
         let mut tools = Vec::new();
 
-        // Get agent's MCP attachments from settings
+        // Get agent's MCP attachments and per-tool filter from settings
+        let settings = Settings::new(self.db);
         let attached_mcps: Option<Vec<String>> = agent_name.and_then(|name| {
-            let settings = Settings::new(self.db);
             let mcps = settings.get_agent_mcps(name);
             if mcps.is_empty() {
                 None // No attachments = use all MCPs
@@ -820,10 +962,16 @@ impl<'a> AgentExecutor<'a> {
                 Some(mcps)
             }
         });
+        let tool_filter = agent_name
+            .map(|name| settings.get_agent_mcp_tool_filter(name))
+            .unwrap_or_default();
 
-        // Get all tools from running MCP servers
-        let all_mcp_tools = mcp_manager.list_all_tools().await;
+        // Get all tools from running MCP servers, in a stable order so
+        // collisions always resolve the same way
+        let mut all_mcp_tools: Vec<_> = mcp_manager.list_all_tools().await.into_iter().collect();
+        all_mcp_tools.sort_by(|a, b| a.0.cmp(&b.0));
 
+        let mut taken = reserved.clone();
         for (server_name, server_tools) in all_mcp_tools {
             // Filter by agent attachments if specified
             if let Some(ref attached) = attached_mcps {
@@ -845,20 +993,33 @@ impl<'a> AgentExecutor<'a> {
             );
 
             for mcp_tool in server_tools {
+                // Create an MCP tool executor (maps the exposed name back)
+                let executor = McpToolExecutor::new(&server_name, &mcp_tool.name, mcp_manager);
+
+                if !tool_filter.permits(&executor.name) {
+                    debug!(
+                        agent = agent_name.unwrap_or("unknown"),
+                        tool = %executor.name,
+                        "Skipping MCP tool - not allowed for agent"
+                    );
+                    continue;
+                }
+
+                if !taken.insert(executor.name.clone()) {
+                    self.publish_warning(format!(
+                        "MCP tool '{}' from {} was not registered: the name '{}' is already taken",
+                        mcp_tool.name, server_name, executor.name
+                    ));
+                    continue;
+                }
+
                 // Create a tool definition from MCP tool
                 let def = ToolDefinition::new(
-                    mcp_tool.name.clone(),
+                    executor.name.clone(),
                     mcp_tool.description.clone().unwrap_or_default(),
                 )
                 .with_parameters(mcp_tool.input_schema.clone());
 
-                // Create an MCP tool executor
-                let executor = McpToolExecutor {
-                    server_name: server_name.clone(),
-                    tool_name: mcp_tool.name.clone(),
-                    mcp_manager_ptr: mcp_manager as *const McpManager,
-                };
-
                 tools.push((def, Arc::new(executor) as Arc<dyn Tool + Send + Sync>));
             }
         }
@@ -880,7 +1041,8 @@ mod streaming;
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::messaging::MessageBus;
+    use crate::agents::AgentCapabilities;
+    use crate::messaging::{Message, MessageBus, MessageLevel};
     use tempfile::TempDir;
 
     fn setup_test_db() -> (TempDir, Database) {
@@ -1072,6 +1234,195 @@ mod tests {
         }
     }
 
+    struct RestrictedAgent;
+
+    impl SpotAgent for RestrictedAgent {
+        fn name(&self) -> &str {
+            "restricted"
+        }
+
+        fn display_name(&self) -> &str {
+            "Restricted"
+        }
+
+        fn description(&self) -> &str {
+            "Read-only agent that lists write tools"
+        }
+
+        fn system_prompt(&self) -> String {
+            "You are a test agent.".to_string()
+        }
+
+        fn available_tools(&self) -> Vec<&str> {
+            vec![
+                "read_file",
+                "run_shell_command",
+                "edit_file",
+                "invoke_agent",
+            ]
+        }
+
+        fn capabilities(&self) -> AgentCapabilities {
+            AgentCapabilities::read_only()
+        }
+
+        fn enforces_capabilities(&self) -> bool {
+            true
+        }
+    }
+
+    #[test]
+    fn test_apply_capabilities_drops_denied_tools() {
+        let (_temp, db) = setup_test_db();
+        let registry = ModelRegistry::new();
+        let bus = MessageBus::new();
+        let mut receiver = bus.subscribe();
+        let executor = AgentExecutor::new(&db, &registry).with_bus(bus.sender());
+        let agent = RestrictedAgent;
+
+        let tools = executor.apply_capabilities(&agent, agent.available_tools());
+        assert_eq!(tools, vec!["read_file"]);
+
+        // One warning per dropped tool
+        let mut warnings = Vec::new();
+        while let Ok(Some(msg)) = receiver.try_recv() {
+            if let Message::Text(text) = msg {
+                assert_eq!(text.level, MessageLevel::Warning);
+                warnings.push(text.text);
+            }
+        }
+        assert_eq!(warnings.len(), 3);
+        assert!(warnings[0].contains("run_shell_command"));
+        assert!(warnings[0].contains("shell"));
+    }
+
+    #[test]
+    fn test_apply_capabilities_skipped_when_not_enforced() {
+        let (_temp, db) = setup_test_db();
+        let registry = ModelRegistry::new();
+        let executor = AgentExecutor::new(&db, &registry);
+        // MockAgent has default (all false) capabilities but doesn't enforce them
+        let agent = MockAgent { name: "test" };
+
+        let tools = executor.apply_capabilities(&agent, vec!["read_file", "run_shell_command"]);
+        assert_eq!(tools, vec!["read_file", "run_shell_command"]);
+    }
+
+    #[tokio::test]
+    async fn test_agent_mcp_tools_without_servers() {
+        let (_temp, db) = setup_test_db();
+        let registry = ModelRegistry::new();
+        let executor = AgentExecutor::new(&db, &registry);
+        let mcp_manager = McpManager::new();
+
+        let tools = executor
+            .agent_mcp_tools(&RestrictedAgent, &mcp_manager, &SpotToolRegistry::new())
+            .await;
+        assert!(tools.is_empty());
+    }
+
+    /// Start an MCP manager with one stand-in server (exposing `echo`) per name.
+    async fn stand_in_mcp_manager(
+        server: &crate::mcp::test_server::StandInServer,
+        names: &[&str],
+    ) -> McpManager {
+        use crate::mcp::{McpConfig, McpServerEntry};
+
+        let mut config = McpConfig::new();
+        for name in names {
+            config.add_server(
+                *name,
+                McpServerEntry::remote(server.url("/mcp")).with_bearer_token("t0ken"),
+            );
+        }
+        let manager = McpManager::with_config(config);
+        manager.start_all().await.unwrap();
+        manager
+    }
+
+    fn tool_names(tools: &[(ToolDefinition, Arc<dyn Tool + Send + Sync>)]) -> Vec<&str> {
+        tools.iter().map(|(def, _)| def.name.as_str()).collect()
+    }
+
+    #[tokio::test]
+    async fn test_collect_mcp_tools_namespaces_and_skips_collisions() {
+        use crate::mcp::test_server::{StandInMode, StandInServer};
+
+        let (_temp, db) = setup_test_db();
+        let registry = ModelRegistry::new();
+        let bus = MessageBus::new();
+        let mut receiver = bus.subscribe();
+        let executor = AgentExecutor::new(&db, &registry).with_bus(bus.sender());
+        let server = StandInServer::start(StandInMode::Json).await;
+        let manager =
+            stand_in_mcp_manager(&server, &["alpha", "beta", "team.tools", "team_tools"]).await;
+
+        // "beta__echo" stands in for a built-in tool of the same name
+        let reserved = HashSet::from(["beta__echo".to_string()]);
+        let tools = executor.collect_mcp_tools(&manager, None, &reserved).await;
+        assert_eq!(tool_names(&tools), vec!["alpha__echo", "team_tools__echo"]);
+
+        // One warning per skipped tool
+        let mut warnings = Vec::new();
+        while let Ok(Some(msg)) = receiver.try_recv() {
+            if let Message::Text(text) = msg {
+                assert_eq!(text.level, MessageLevel::Warning);
+                warnings.push(text.text);
+            }
+        }
+        assert_eq!(warnings.len(), 2);
+        assert!(warnings[0].contains("'beta__echo'"));
+        assert!(warnings[1].contains("from team_tools"));
+
+        manager.stop_all().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_collect_mcp_tools_applies_agent_tool_filter() {
+        use crate::mcp::test_server::{StandInMode, StandInServer};
+
+        let (_temp, db) = setup_test_db();
+        let registry = ModelRegistry::new();
+        let executor = AgentExecutor::new(&db, &registry);
+        let server = StandInServer::start(StandInMode::Json).await;
+        let manager = stand_in_mcp_manager(&server, &["alpha", "beta", "gamma"]).await;
+
+        let settings = Settings::new(&db);
+        settings
+            .set_agent_mcp_allowed_tools(
+                "test",
+                &["alpha__*".to_string(), "beta__echo".to_string()],
+            )
+            .unwrap();
+        settings
+            .set_agent_mcp_denied_tools("test", &["beta__echo".to_string()])
+            .unwrap();
+
+        let tools = executor
+            .collect_mcp_tools(&manager, Some("test"), &HashSet::new())
+            .await;
+        assert_eq!(tool_names(&tools), vec!["alpha__echo"]);
+
+        // Other agents are unaffected
+        let tools = executor
+            .collect_mcp_tools(&manager, Some("other"), &HashSet::new())
+            .await;
+        assert_eq!(
+            tool_names(&tools),
+            vec!["alpha__echo", "beta__echo", "gamma__echo"]
+        );
+
+        manager.stop_all().await.unwrap();
+    }
+
+    #[test]
+    fn test_reserved_tool_names_include_builtins_and_sub_agents() {
+        let reserved = AgentExecutor::reserved_tool_names(&SpotToolRegistry::new());
+        assert!(reserved.contains("read_file"));
+        assert!(reserved.contains("invoke_agent"));
+        assert!(reserved.contains("list_agents"));
+    }
+
     #[tokio::test]
     async fn test_execute_with_bus_without_bus_returns_config_error() {
         let (_temp, db) = setup_test_db();
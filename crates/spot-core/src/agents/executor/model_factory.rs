@@ -6,13 +6,17 @@
 use std::sync::Arc;
 use tracing::{debug, error, info, warn};
 
-use serdes_ai_models::{infer_model, openai::OpenAIChatModel, Model};
+use serdes_ai_models::{anthropic::AnthropicModel, infer_model, openai::OpenAIChatModel, Model};
 
 use crate::auth;
 use crate::db::Database;
+use crate::messaging::MessageSender;
 use crate::models::settings::ModelSettings as SpotModelSettings;
-use crate::models::{resolve_api_key, ModelRegistry, ModelType};
+use crate::models::utils::resolve_endpoint_env_vars;
+use crate::models::{resolve_api_key, CustomEndpoint, ModelConfig, ModelRegistry, ModelType};
 
+use super::failover::provider_id;
+use super::round_robin::{Member, RoundRobinModel};
 use super::ExecutorError;
 
 /// Get a model by name, handling custom endpoints, OAuth models, and standard models.
@@ -22,15 +26,29 @@ use super::ExecutorError;
 /// API key-based models.
 ///
 /// # Model Resolution Order
-/// 1. Custom endpoint models (from `/add-model`)
-/// 2. OAuth models by config type (ClaudeCode, ChatgptOauth)
-/// 3. OAuth models by prefix (legacy: `chatgpt-*`, `claude-code-*`)
-/// 4. Standard models via `infer_model()` (uses environment API keys)
+/// 1. Round-robin models (built from their member models)
+/// 2. Custom endpoint models (from `/add-model`)
+/// 3. OAuth models by config type (ClaudeCode, ChatgptOauth)
+/// 4. OAuth models by prefix (legacy: `chatgpt-*`, `claude-code-*`)
+/// 5. Standard models via `infer_model()` (uses environment API keys)
 pub async fn get_model(
     db: &Database,
     model_name: &str,
     registry: &ModelRegistry,
     model_settings: Option<&SpotModelSettings>,
+) -> Result<Arc<dyn Model>, ExecutorError> {
+    get_model_with_bus(db, model_name, registry, model_settings, None).await
+}
+
+/// Like [`get_model`], but composite models report on `bus`.
+///
+/// Round-robin models announce which member serves each request.
+pub(super) async fn get_model_with_bus(
+    db: &Database,
+    model_name: &str,
+    registry: &ModelRegistry,
+    model_settings: Option<&SpotModelSettings>,
+    bus: Option<&MessageSender>,
 ) -> Result<Arc<dyn Model>, ExecutorError> {
     debug!(model_name = %model_name, ?model_settings, "get_model called");
 
@@ -71,6 +89,11 @@ pub async fn get_model(
             "Found model in registry"
         );
 
+        // Round-robin models are built from their members
+        if config.model_type == ModelType::RoundRobin {
+            return round_robin_model(db, config, registry, bus).await;
+        }
+
         // Handle custom endpoint models (e.g., from /add-model)
         if let Some(endpoint) = &config.custom_endpoint {
             debug!(
@@ -108,15 +131,17 @@ pub async fn get_model(
             // Get the actual model ID to send to the API
             let model_id = config.model_id.as_deref().unwrap_or(model_name);
 
-            // Create OpenAI-compatible model with custom endpoint
-            let model = OpenAIChatModel::new(model_id, api_key).with_base_url(&endpoint.url);
+            // Create the model with the endpoint's protocol, headers and CA bundle
+            let model =
+                custom_endpoint_model(model_id, config, endpoint, api_key, thinking_budget)?;
 
             info!(
                 model_name = %model_name,
                 endpoint = %endpoint.url,
+                model_type = %config.model_type,
                 "Custom endpoint model ready"
             );
-            return Ok(Arc::new(model));
+            return Ok(model);
         }
 
         // Handle based on model type for non-custom-endpoint models
@@ -205,6 +230,168 @@ pub async fn get_model(
     Ok(model)
 }
 
+/// Build a round-robin model from its configured members.
+///
+/// Members are resolved like any other model, using their own saved settings.
+/// Members that can't be built (e.g. missing API key) are skipped with a
+/// warning; nested round-robin members are rejected.
+async fn round_robin_model(
+    db: &Database,
+    config: &ModelConfig,
+    registry: &ModelRegistry,
+    bus: Option<&MessageSender>,
+) -> Result<Arc<dyn Model>, ExecutorError> {
+    let mut members = Vec::new();
+
+    for member_name in &config.round_robin_models {
+        if registry
+            .get(member_name)
+            .is_some_and(|c| c.model_type == ModelType::RoundRobin)
+        {
+            return Err(ExecutorError::Config(format!(
+                "Round-robin model '{}' cannot contain another round-robin model ('{}')",
+                config.name, member_name
+            )));
+        }
+
+        let member_settings = SpotModelSettings::load(db, member_name).ok();
+        match Box::pin(get_model(
+            db,
+            member_name,
+            registry,
+            member_settings.as_ref(),
+        ))
+        .await
+        {
+            Ok(model) => members.push(Member::new(
+                member_name,
+                &provider_id(registry, member_name),
+                model,
+            )),
+            Err(e) => {
+                warn!(
+                    round_robin = %config.name,
+                    member = %member_name,
+                    error = %e,
+                    "Skipping round-robin member"
+                );
+                if let Some(bus) = bus {
+                    bus.warning(format!(
+                        "Round-robin '{}': skipping '{}': {}",
+                        config.name, member_name, e
+                    ));
+                }
+            }
+        }
+    }
+
+    if members.is_empty() {
+        return Err(ExecutorError::Config(format!(
+            "Round-robin model '{}' has no usable member models",
+            config.name
+        )));
+    }
+
+    info!(
+        model_name = %config.name,
+        members = members.len(),
+        "Round-robin model ready"
+    );
+    Ok(Arc::new(RoundRobinModel::new(
+        &config.name,
+        members,
+        bus.cloned(),
+    )))
+}
+
+/// Build a model for a custom endpoint.
+///
+/// `ModelType::CustomAnthropic` endpoints get an Anthropic Messages API client;
+/// everything else is treated as OpenAI-compatible. Configured headers and CA
+/// certificates are applied to the underlying HTTP client. `$VAR` and
+/// `${VAR}` in the URL, header values and CA path are expanded from the
+/// environment; `api_key` is already resolved.
+fn custom_endpoint_model(
+    model_id: &str,
+    config: &ModelConfig,
+    endpoint: &CustomEndpoint,
+    api_key: String,
+    thinking_budget: Option<u64>,
+) -> Result<Arc<dyn Model>, ExecutorError> {
+    let endpoint = resolve_endpoint_env_vars(&CustomEndpoint {
+        api_key: None,
+        ..endpoint.clone()
+    })
+    .map_err(|e| ExecutorError::Config(format!("Custom endpoint for {}: {}", model_id, e)))?;
+    let client = endpoint_http_client(&endpoint)?;
+
+    match config.model_type {
+        ModelType::CustomAnthropic => {
+            let mut model = AnthropicModel::new(model_id, api_key).with_base_url(&endpoint.url);
+            if let Some(budget) = thinking_budget {
+                model = model.with_thinking(Some(budget));
+            }
+            if let Some(client) = client {
+                model = model.with_client(client);
+            }
+            Ok(Arc::new(model))
+        }
+        _ => {
+            let mut model = OpenAIChatModel::new(model_id, api_key).with_base_url(&endpoint.url);
+            if let Some(client) = client {
+                model = model.with_client(client);
+            }
+            Ok(Arc::new(model))
+        }
+    }
+}
+
+/// Build an HTTP client with the endpoint's extra headers and CA certificates.
+///
+/// Returns `None` when neither is configured, so the model keeps its default client.
+fn endpoint_http_client(
+    endpoint: &CustomEndpoint,
+) -> Result<Option<reqwest::Client>, ExecutorError> {
+    if endpoint.headers.is_empty() && endpoint.ca_certs_path.is_none() {
+        return Ok(None);
+    }
+
+    let mut headers = reqwest::header::HeaderMap::new();
+    for (name, value) in &endpoint.headers {
+        let header_name = reqwest::header::HeaderName::from_bytes(name.as_bytes())
+            .map_err(|_| ExecutorError::Config(format!("Invalid header name '{}'", name)))?;
+        let header_value = reqwest::header::HeaderValue::from_str(value)
+            .map_err(|_| ExecutorError::Config(format!("Invalid value for header '{}'", name)))?;
+        headers.insert(header_name, header_value);
+    }
+
+    let mut builder = reqwest::Client::builder().default_headers(headers);
+
+    if let Some(ref path) = endpoint.ca_certs_path {
+        let pem = std::fs::read(path).map_err(|e| {
+            ExecutorError::Config(format!("Failed to read CA certificates {}: {}", path, e))
+        })?;
+        let certs = reqwest::Certificate::from_pem_bundle(&pem).map_err(|e| {
+            ExecutorError::Config(format!("Invalid CA certificates in {}: {}", path, e))
+        })?;
+        if certs.is_empty() {
+            return Err(ExecutorError::Config(format!(
+                "No CA certificates found in {}",
+                path
+            )));
+        }
+        for cert in certs {
+            builder = builder.add_root_certificate(cert);
+        }
+        debug!(ca_certs_path = %path, "Trusting custom CA certificates");
+    }
+
+    builder
+        .build()
+        .map(Some)
+        .map_err(|e| ExecutorError::Config(format!("Failed to build HTTP client: {}", e)))
+}
+
 /// Information about the current API key being used
 #[allow(dead_code)]
 #[derive(Debug, Clone)]
@@ -253,7 +440,7 @@ pub fn create_model_with_key(
     let model_id = config.model_id.as_deref().unwrap_or(model_name);
 
     // Create the model with the rotated key
-    let model = OpenAIChatModel::new(model_id, api_key).with_base_url(&endpoint.url);
+    let model = custom_endpoint_model(model_id, config, endpoint, api_key.to_string(), None)?;
 
     info!(
         model_name = %model_name,
@@ -261,7 +448,7 @@ pub fn create_model_with_key(
         "Created model with rotated API key"
     );
 
-    Ok(Arc::new(model))
+    Ok(model)
 }
 
 /// Get a model with optional retry handler for rate limit handling.
@@ -317,10 +504,10 @@ pub async fn get_model_with_retry(
 
                             // Create the model with the pool key
                             let model_id = config.model_id.as_deref().unwrap_or(model_name);
-                            let model = OpenAIChatModel::new(model_id, pool_key)
-                                .with_base_url(&endpoint.url);
+                            let model =
+                                custom_endpoint_model(model_id, config, endpoint, pool_key, None)?;
 
-                            return Ok((Arc::new(model), Some(KeyInfo { provider, key_id })));
+                            return Ok((model, Some(KeyInfo { provider, key_id })));
                         }
                     }
                 }
@@ -381,7 +568,7 @@ fn extract_provider_from_config(model_name: &str, config: &crate::models::ModelC
         ModelType::ClaudeCode => provider_to_env_var("anthropic"),
         ModelType::ChatgptOauth => provider_to_env_var("openai"),
         ModelType::GoogleVertex => provider_to_env_var("google"),
-        ModelType::CustomOpenai => {
+        ModelType::CustomOpenai | ModelType::CustomAnthropic => {
             // Try to extract from model name (e.g., "cerebras:model" -> "CEREBRAS_API_KEY")
             if let Some(pos) = model_name.find(':') {
                 return provider_to_env_var(&model_name[..pos]);
@@ -407,6 +594,10 @@ mod tests {
         (temp_dir, db)
     }
 
+    /// A variable cargo sets for every test run. Env lookups are tested
+    /// against it instead of `set_var`, which races tests on other threads.
+    const SET_ENV_VAR: &str = "CARGO_MANIFEST_DIR";
+
     fn create_custom_model(name: &str, url: &str, api_key: Option<&str>) -> ModelConfig {
         ModelConfig {
             name: name.to_string(),
@@ -449,16 +640,13 @@ mod tests {
     async fn test_custom_endpoint_with_env_var_key() {
         let (_temp, db) = setup_test_db();
         let mut registry = ModelRegistry::new();
-        let env_key = "TEST_CUSTOM_API_KEY_FOR_MODEL_FACTORY";
-        std::env::set_var(env_key, "sk-from-env-var");
         let model = create_custom_model(
             "custom-env",
             "https://api.example.com/v1",
-            Some(&format!("${}", env_key)),
+            Some(&format!("${}", SET_ENV_VAR)),
         );
         registry.add(model);
         let result = get_model(&db, "custom-env", &registry, None).await;
-        std::env::remove_var(env_key);
         assert!(result.is_ok(), "Should succeed with env var API key");
     }
 
@@ -515,6 +703,209 @@ mod tests {
         }
     }
 
+    fn create_round_robin_model(name: &str, members: &[&str]) -> ModelConfig {
+        ModelConfig {
+            name: name.to_string(),
+            model_type: ModelType::RoundRobin,
+            round_robin_models: members.iter().map(|m| m.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_round_robin_model_built_from_members() {
+        let (_temp, db) = setup_test_db();
+        let mut registry = ModelRegistry::new();
+        registry.add(create_custom_model(
+            "rr-member-a",
+            "https://a.example.com/v1",
+            Some("sk-a"),
+        ));
+        registry.add(create_custom_model(
+            "rr-member-b",
+            "https://b.example.com/v1",
+            Some("sk-b"),
+        ));
+        registry.add(create_round_robin_model(
+            "rr-pool",
+            &["rr-member-a", "rr-member-b"],
+        ));
+
+        let model = get_model(&db, "rr-pool", &registry, None).await.unwrap();
+        assert_eq!(model.identifier(), "round_robin:rr-pool");
+    }
+
+    #[tokio::test]
+    async fn test_round_robin_skips_unusable_members() {
+        let (_temp, db) = setup_test_db();
+        let mut registry = ModelRegistry::new();
+        registry.add(create_custom_model(
+            "rr-usable",
+            "https://a.example.com/v1",
+            Some("sk-a"),
+        ));
+        registry.add(create_custom_model(
+            "rr-no-key",
+            "https://b.example.com/v1",
+            None,
+        ));
+        registry.add(create_round_robin_model(
+            "rr-partial",
+            &["rr-no-key", "rr-usable"],
+        ));
+
+        assert!(get_model(&db, "rr-partial", &registry, None).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_round_robin_without_usable_members_returns_error() {
+        let (_temp, db) = setup_test_db();
+        let mut registry = ModelRegistry::new();
+        registry.add(create_round_robin_model("rr-empty", &[]));
+
+        match get_model(&db, "rr-empty", &registry, None).await {
+            Err(ExecutorError::Config(msg)) => assert!(msg.contains("no usable member")),
+            _ => panic!("Expected ExecutorError::Config for empty round-robin"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_round_robin_rejects_nested_round_robin() {
+        let (_temp, db) = setup_test_db();
+        let mut registry = ModelRegistry::new();
+        registry.add(create_round_robin_model("rr-outer", &["rr-inner"]));
+        registry.add(create_round_robin_model("rr-inner", &["rr-outer"]));
+
+        match get_model(&db, "rr-outer", &registry, None).await {
+            Err(ExecutorError::Config(msg)) => assert!(msg.contains("rr-inner")),
+            _ => panic!("Expected ExecutorError::Config for nested round-robin"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_custom_anthropic_endpoint() {
+        let (_temp, db) = setup_test_db();
+        let mut registry = ModelRegistry::new();
+        let mut model = create_custom_model(
+            "gateway-claude",
+            "https://gateway.example.com",
+            Some("sk-gateway"),
+        );
+        model.model_type = ModelType::CustomAnthropic;
+        registry.add(model);
+        let result = get_model(&db, "gateway-claude", &registry, None).await;
+        assert!(result.is_ok(), "Should build an Anthropic-compatible model");
+        assert!(result.unwrap().identifier().contains("test-model-id"));
+    }
+
+    #[tokio::test]
+    async fn test_custom_endpoint_with_headers() {
+        let (_temp, db) = setup_test_db();
+        let mut registry = ModelRegistry::new();
+        let mut model =
+            create_custom_model("custom-headers", "https://api.example.com/v1", Some("sk"));
+        if let Some(endpoint) = model.custom_endpoint.as_mut() {
+            endpoint
+                .headers
+                .insert("X-Gateway-Auth".to_string(), "secret".to_string());
+        }
+        registry.add(model);
+        let result = get_model(&db, "custom-headers", &registry, None).await;
+        assert!(result.is_ok(), "Should succeed with extra headers");
+    }
+
+    #[tokio::test]
+    async fn test_custom_endpoint_expands_env_vars_in_headers_and_ca_path() {
+        let (_temp, db) = setup_test_db();
+        let mut registry = ModelRegistry::new();
+        let mut model = create_custom_model("custom-env", "https://api.example.com/v1", Some("sk"));
+        if let Some(endpoint) = model.custom_endpoint.as_mut() {
+            endpoint.ca_certs_path = Some(format!("${{{}}}/missing.pem", SET_ENV_VAR));
+        }
+        registry.add(model);
+        let expected = format!("{}/missing.pem", std::env::var(SET_ENV_VAR).unwrap());
+        match get_model(&db, "custom-env", &registry, None).await {
+            Err(ExecutorError::Config(msg)) => assert!(msg.contains(&expected)),
+            _ => panic!("Expected ExecutorError::Config for missing CA file"),
+        }
+
+        let mut model = create_custom_model(
+            "custom-env-header",
+            "https://api.example.com/v1",
+            Some("sk"),
+        );
+        if let Some(endpoint) = model.custom_endpoint.as_mut() {
+            endpoint.headers.insert(
+                "X-Gateway-Auth".to_string(),
+                "${SPOT_TEST_UNSET_GATEWAY_TOKEN}".to_string(),
+            );
+        }
+        registry.add(model);
+        match get_model(&db, "custom-env-header", &registry, None).await {
+            Err(ExecutorError::Config(msg)) => {
+                assert!(msg.contains("SPOT_TEST_UNSET_GATEWAY_TOKEN"))
+            }
+            _ => panic!("Expected ExecutorError::Config for an unset variable"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_custom_endpoint_missing_ca_file_returns_error() {
+        let (_temp, db) = setup_test_db();
+        let mut registry = ModelRegistry::new();
+        let mut model = create_custom_model("custom-ca", "https://api.example.com/v1", Some("sk"));
+        if let Some(endpoint) = model.custom_endpoint.as_mut() {
+            endpoint.ca_certs_path = Some("/nonexistent/ca.pem".to_string());
+        }
+        registry.add(model);
+        let result = get_model(&db, "custom-ca", &registry, None).await;
+        match result {
+            Err(ExecutorError::Config(msg)) => assert!(msg.contains("/nonexistent/ca.pem")),
+            _ => panic!("Expected ExecutorError::Config for missing CA file"),
+        }
+    }
+
+    #[test]
+    fn test_endpoint_http_client_none_without_overrides() {
+        let endpoint = CustomEndpoint {
+            url: "https://api.example.com".to_string(),
+            api_key: None,
+            headers: HashMap::new(),
+            ca_certs_path: None,
+        };
+        assert!(endpoint_http_client(&endpoint).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_endpoint_http_client_rejects_invalid_header() {
+        let mut headers = HashMap::new();
+        headers.insert("Bad Header".to_string(), "value".to_string());
+        let endpoint = CustomEndpoint {
+            url: "https://api.example.com".to_string(),
+            api_key: None,
+            headers,
+            ca_certs_path: None,
+        };
+        match endpoint_http_client(&endpoint) {
+            Err(ExecutorError::Config(msg)) => assert!(msg.contains("Bad Header")),
+            _ => panic!("Expected ExecutorError::Config for invalid header name"),
+        }
+    }
+
+    #[test]
+    fn test_endpoint_http_client_rejects_empty_ca_bundle() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("empty.pem");
+        std::fs::write(&path, "").unwrap();
+        let endpoint = CustomEndpoint {
+            url: "https://api.example.com".to_string(),
+            api_key: None,
+            headers: HashMap::new(),
+            ca_certs_path: Some(path.to_string_lossy().into_owned()),
+        };
+        assert!(endpoint_http_client(&endpoint).is_err());
+    }
+
     #[tokio::test]
     async fn test_legacy_chatgpt_prefix_detected() {
         let (_temp, db) = setup_test_db();
@@ -642,16 +1033,13 @@ mod tests {
     async fn test_env_var_with_braces() {
         let (_temp, db) = setup_test_db();
         let mut registry = ModelRegistry::new();
-        let env_key = "TEST_BRACED_KEY_MODEL_FACTORY";
-        std::env::set_var(env_key, "sk-braced-key");
         let model = create_custom_model(
             "custom-braced",
             "https://api.example.com/v1",
-            Some(&format!("${{{}}}", env_key)),
+            Some(&format!("${{{}}}", SET_ENV_VAR)),
         );
         registry.add(model);
         let result = get_model(&db, "custom-braced", &registry, None).await;
-        std::env::remove_var(env_key);
         assert!(result.is_ok(), "Should resolve braced env var syntax");
     }
 
@@ -0,0 +1,179 @@
+//! Per-model request settings.
+//!
+//! Converts the settings saved for a model (`model_settings.<model>.*`) into
+//! the `serdes_ai_core::ModelSettings` sent with every request.
+
+use serde_json::{Map, Value};
+
+use crate::models::settings::ModelSettings as SpotModelSettings;
+use crate::models::{ModelConfig, ModelType};
+
+/// Settings for a single agent run.
+pub(super) struct RequestSettings {
+    /// Output token limit, already clamped to the model's context window.
+    pub max_tokens: u64,
+    /// Settings passed to the model via `RunOptions`.
+    pub core: serdes_ai_core::ModelSettings,
+}
+
+impl RequestSettings {
+    /// Build request settings from saved model settings and the model's config.
+    pub fn new(settings: &SpotModelSettings, config: Option<&ModelConfig>) -> Self {
+        let supports_thinking = config.map(|c| c.supports_thinking).unwrap_or(false);
+        let thinking_enabled = supports_thinking && settings.extended_thinking != Some(false);
+
+        // When thinking is enabled, temperature MUST be 1.0 per Claude API requirements
+        let temperature = if thinking_enabled {
+            1.0
+        } else {
+            settings.effective_temperature() as f64
+        };
+
+        let max_tokens = clamp_max_tokens(
+            settings.effective_max_tokens(),
+            config.map(|c| c.context_length),
+        );
+
+        let mut core = serdes_ai_core::ModelSettings::new()
+            .temperature(temperature)
+            .top_p(settings.effective_top_p() as f64)
+            .max_tokens(max_tokens);
+
+        if let Some(seed) = settings.seed {
+            core = core.seed(seed as u64);
+        }
+
+        let extra = provider_extras(settings);
+        if !extra.is_empty() && accepts_extras(config) {
+            core = core.extra(Value::Object(extra));
+        }
+
+        Self { max_tokens, core }
+    }
+}
+
+/// Limit the requested output tokens to the model's context window.
+///
+/// A context length of 0 means "unknown" and leaves the request as is.
+fn clamp_max_tokens(requested: i32, context_length: Option<usize>) -> u64 {
+    let requested = requested.max(1) as u64;
+    match context_length {
+        Some(limit) if limit > 0 => requested.min(limit as u64),
+        _ => requested,
+    }
+}
+
+/// Whether the model's provider takes the fields from [`provider_extras`].
+///
+/// Other providers (Anthropic in particular) reject unknown body fields with
+/// a 400, so the settings are not sent to them at all. A custom
+/// OpenAI-compatible endpoint that doesn't support a field fails the request
+/// the same way; clear the setting for such models.
+fn accepts_extras(config: Option<&ModelConfig>) -> bool {
+    config.is_some_and(|c| {
+        matches!(
+            c.model_type,
+            ModelType::Openai
+                | ModelType::AzureOpenai
+                | ModelType::CustomOpenai
+                | ModelType::Openrouter
+        )
+    })
+}
+
+/// Request body fields for settings that have no typed equivalent.
+///
+/// `reasoning_effort` and `verbosity` use the OpenAI Chat Completions field
+/// names; see [`accepts_extras`] for the providers they are sent to.
+fn provider_extras(settings: &SpotModelSettings) -> Map<String, Value> {
+    let mut extra = Map::new();
+    if let Some(effort) = &settings.reasoning_effort {
+        extra.insert("reasoning_effort".into(), Value::String(effort.clone()));
+    }
+    if let Some(level) = settings.verbosity {
+        extra.insert(
+            "verbosity".into(),
+            Value::String(verbosity_name(level).to_string()),
+        );
+    }
+    extra
+}
+
+/// Map the 0-3 verbosity setting onto the API's low/medium/high levels.
+fn verbosity_name(level: i32) -> &'static str {
+    match level {
+        i32::MIN..=1 => "low",
+        2 => "medium",
+        _ => "high",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clamp_max_tokens_within_context() {
+        assert_eq!(clamp_max_tokens(4096, Some(128_000)), 4096);
+    }
+
+    #[test]
+    fn test_clamp_max_tokens_to_context_length() {
+        assert_eq!(clamp_max_tokens(16384, Some(8192)), 8192);
+    }
+
+    #[test]
+    fn test_clamp_max_tokens_unknown_context() {
+        assert_eq!(clamp_max_tokens(16384, None), 16384);
+        assert_eq!(clamp_max_tokens(16384, Some(0)), 16384);
+    }
+
+    #[test]
+    fn test_clamp_max_tokens_non_positive() {
+        assert_eq!(clamp_max_tokens(0, Some(8192)), 1);
+        assert_eq!(clamp_max_tokens(-5, None), 1);
+    }
+
+    #[test]
+    fn test_provider_extras_empty_by_default() {
+        assert!(provider_extras(&SpotModelSettings::new()).is_empty());
+    }
+
+    #[test]
+    fn test_provider_extras_forwards_reasoning_and_verbosity() {
+        let mut settings = SpotModelSettings::new();
+        settings.reasoning_effort = Some("high".to_string());
+        settings.verbosity = Some(2);
+
+        let extra = provider_extras(&settings);
+        assert_eq!(extra["reasoning_effort"], "high");
+        assert_eq!(extra["verbosity"], "medium");
+    }
+
+    #[test]
+    fn test_extras_only_sent_to_openai_compatible_providers() {
+        let accepts = |model_type| {
+            let config = ModelConfig {
+                model_type,
+                ..ModelConfig::default()
+            };
+            accepts_extras(Some(&config))
+        };
+
+        assert!(accepts(ModelType::Openai));
+        assert!(accepts(ModelType::CustomOpenai));
+        assert!(accepts(ModelType::Openrouter));
+        assert!(!accepts(ModelType::Anthropic));
+        assert!(!accepts(ModelType::CustomAnthropic));
+        assert!(!accepts(ModelType::Gemini));
+        assert!(!accepts_extras(None));
+    }
+
+    #[test]
+    fn test_verbosity_name() {
+        assert_eq!(verbosity_name(0), "low");
+        assert_eq!(verbosity_name(1), "low");
+        assert_eq!(verbosity_name(2), "medium");
+        assert_eq!(verbosity_name(3), "high");
+    }
+}
@@ -2,13 +2,14 @@
 //!
 //! Provides:
 //! - `CircuitBreaker`: Prevents cascading failures by stopping requests to failing providers
+//! - `shared_circuit_breaker()`: Process-wide breakers that outlive a single agent run
 //! - `RetryPolicy`: Configurable retry with exponential backoff and jitter
 //! - `FallbackChain`: Maps models to fallback alternatives when a provider is unavailable
 
 #![allow(dead_code)]
 
 use std::collections::HashMap;
-use std::sync::Mutex;
+use std::sync::{Arc, Mutex, OnceLock};
 use std::time::{Duration, Instant};
 
 use tracing::{info, warn};
@@ -195,6 +196,24 @@ impl CircuitBreaker {
     }
 }
 
+/// Get the process-wide circuit breaker for `name`, creating it on first use.
+///
+/// Models are rebuilt for every agent run, so breakers that should remember
+/// failures across runs live here instead of on the model.
+pub fn shared_circuit_breaker(name: &str) -> Arc<CircuitBreaker> {
+    static BREAKERS: OnceLock<Mutex<HashMap<String, Arc<CircuitBreaker>>>> = OnceLock::new();
+
+    let mut breakers = BREAKERS
+        .get_or_init(|| Mutex::new(HashMap::new()))
+        .lock()
+        .unwrap();
+    Arc::clone(
+        breakers
+            .entry(name.to_string())
+            .or_insert_with(|| Arc::new(CircuitBreaker::with_defaults(name))),
+    )
+}
+
 // =========================================================================
 // Retry Policy
 // =========================================================================
@@ -576,6 +595,17 @@ mod tests {
         assert_ne!(CircuitState::Open, CircuitState::HalfOpen);
     }
 
+    #[test]
+    fn test_shared_circuit_breaker_is_reused() {
+        let first = shared_circuit_breaker("shared-test-provider");
+        let second = shared_circuit_breaker("shared-test-provider");
+        let other = shared_circuit_breaker("shared-test-other");
+
+        assert!(Arc::ptr_eq(&first, &second));
+        assert!(!Arc::ptr_eq(&first, &other));
+        assert_eq!(first.provider(), "shared-test-provider");
+    }
+
     // =====================================================================
     // RetryPolicy Tests
     // =====================================================================
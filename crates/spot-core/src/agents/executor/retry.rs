@@ -214,7 +214,6 @@ impl RetryHandler {
 }
 
 /// Extract HTTP status code from an error string
-#[allow(dead_code)]
 pub fn extract_status_code(error: &str) -> Option<u16> {
     // Look for patterns like "status: 429" or "status:429"
     let patterns = ["status: ", "status:"];
@@ -0,0 +1,337 @@
+//! Round-robin composite model.
+//!
+//! `RoundRobinModel` spreads requests across the members listed in
+//! `ModelConfig.round_robin_models`. Each request goes to the next member
+//! whose circuit breaker allows it; a member listed more than once gets a
+//! proportionally larger share of the requests.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, OnceLock};
+
+use async_trait::async_trait;
+use tracing::{debug, warn};
+
+use serdes_ai_core::{ModelRequest, ModelResponse, ModelSettings};
+use serdes_ai_models::{Model, ModelError, ModelProfile, ModelRequestParameters, StreamedResponse};
+
+use super::resilience::{shared_circuit_breaker, CircuitBreaker};
+use crate::messaging::MessageSender;
+
+/// A member model of a round-robin group.
+pub(super) struct Member {
+    name: String,
+    model: Arc<dyn Model>,
+    breaker: Arc<CircuitBreaker>,
+}
+
+impl Member {
+    /// A member whose failures count against the circuit breaker `circuit`,
+    /// normally the member's provider id.
+    pub fn new(name: &str, circuit: &str, model: Arc<dyn Model>) -> Self {
+        Self {
+            name: name.to_string(),
+            model,
+            breaker: shared_circuit_breaker(circuit),
+        }
+    }
+}
+
+/// A model that cycles through its member models on every request.
+pub(super) struct RoundRobinModel {
+    name: String,
+    members: Vec<Member>,
+    /// Index of the next member to try, shared by all instances with this name.
+    cursor: Arc<Mutex<usize>>,
+    bus: Option<MessageSender>,
+}
+
+impl RoundRobinModel {
+    /// Create a round-robin model over already-built members.
+    ///
+    /// `members` must not be empty. The rotation position is kept per `name`,
+    /// so successive agent runs continue where the last one stopped.
+    pub fn new(name: &str, members: Vec<Member>, bus: Option<MessageSender>) -> Self {
+        debug_assert!(!members.is_empty(), "round-robin model needs members");
+        Self {
+            name: name.to_string(),
+            members,
+            cursor: shared_cursor(name),
+            bus,
+        }
+    }
+
+    /// Pick the next member whose circuit is not open and advance the cursor.
+    fn next_member(&self) -> Result<&Member, ModelError> {
+        let mut cursor = self.cursor.lock().unwrap();
+        let len = self.members.len();
+
+        for offset in 0..len {
+            let index = (*cursor + offset) % len;
+            let member = &self.members[index];
+            match member.breaker.check() {
+                Ok(()) => {
+                    *cursor = (index + 1) % len;
+                    return Ok(member);
+                }
+                Err(reason) => debug!(member = %member.name, %reason, "Skipping member"),
+            }
+        }
+
+        Err(ModelError::Api {
+            message: format!(
+                "All models in round-robin '{}' are unavailable (circuit open)",
+                self.name
+            ),
+            code: None,
+        })
+    }
+
+    /// Report which member is serving the current request.
+    fn announce(&self, member: &Member) {
+        debug!(round_robin = %self.name, member = %member.name, "Round-robin request");
+        if let Some(ref bus) = self.bus {
+            bus.info(format!("🔁 {} → {}", self.name, member.name));
+        }
+    }
+
+    /// Update the member's circuit breaker with the request outcome.
+    fn record<T>(&self, member: &Member, result: &Result<T, ModelError>) {
+        match result {
+            Ok(_) => member.breaker.record_success(),
+            Err(e) => {
+                warn!(
+                    round_robin = %self.name,
+                    member = %member.name,
+                    error = %e,
+                    "Member request failed"
+                );
+                member.breaker.record_failure();
+            }
+        }
+    }
+}
+
+/// Rotation position for the round-robin model `name`.
+fn shared_cursor(name: &str) -> Arc<Mutex<usize>> {
+    static CURSORS: OnceLock<Mutex<HashMap<String, Arc<Mutex<usize>>>>> = OnceLock::new();
+
+    let mut cursors = CURSORS
+        .get_or_init(|| Mutex::new(HashMap::new()))
+        .lock()
+        .unwrap();
+    Arc::clone(cursors.entry(name.to_string()).or_default())
+}
+
+#[async_trait]
+impl Model for RoundRobinModel {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn system(&self) -> &str {
+        "round_robin"
+    }
+
+    fn identifier(&self) -> String {
+        format!("round_robin:{}", self.name)
+    }
+
+    async fn request(
+        &self,
+        messages: &[ModelRequest],
+        settings: &ModelSettings,
+        params: &ModelRequestParameters,
+    ) -> Result<ModelResponse, ModelError> {
+        let member = self.next_member()?;
+        self.announce(member);
+        let result = member.model.request(messages, settings, params).await;
+        self.record(member, &result);
+        result
+    }
+
+    async fn request_stream(
+        &self,
+        messages: &[ModelRequest],
+        settings: &ModelSettings,
+        params: &ModelRequestParameters,
+    ) -> Result<StreamedResponse, ModelError> {
+        let member = self.next_member()?;
+        self.announce(member);
+        let result = member
+            .model
+            .request_stream(messages, settings, params)
+            .await;
+        self.record(member, &result);
+        result
+    }
+
+    fn profile(&self) -> &ModelProfile {
+        self.members[0].model.profile()
+    }
+
+    async fn count_tokens(&self, messages: &[ModelRequest]) -> Result<u64, ModelError> {
+        self.members[0].model.count_tokens(messages).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::messaging::{Message, MessageBus};
+    use crate::test_utils::{MockBehavior, MockModel};
+
+    /// A mock member whose circuit breaker is keyed by its own name.
+    fn mock(name: &str, reply: &str) -> Member {
+        let model = MockModel::new(name).with_behavior(MockBehavior::Text(reply.to_string()));
+        Member::new(name, name, Arc::new(model))
+    }
+
+    async fn reply(model: &RoundRobinModel) -> Result<String, ModelError> {
+        let settings = ModelSettings::default();
+        let params = ModelRequestParameters::default();
+        model
+            .request(&[], &settings, &params)
+            .await
+            .map(|r| r.text_content())
+    }
+
+    #[tokio::test]
+    async fn test_round_robin_cycles_members() {
+        let model = RoundRobinModel::new(
+            "rr-cycle",
+            vec![mock("rr-cycle-a", "a"), mock("rr-cycle-b", "b")],
+            None,
+        );
+
+        assert_eq!(reply(&model).await.unwrap(), "a");
+        assert_eq!(reply(&model).await.unwrap(), "b");
+        assert_eq!(reply(&model).await.unwrap(), "a");
+    }
+
+    #[tokio::test]
+    async fn test_round_robin_repeated_member_is_weighted() {
+        let model = RoundRobinModel::new(
+            "rr-weighted",
+            vec![
+                mock("rr-weighted-a", "a"),
+                mock("rr-weighted-a", "a"),
+                mock("rr-weighted-b", "b"),
+            ],
+            None,
+        );
+
+        let mut replies = Vec::new();
+        for _ in 0..6 {
+            replies.push(reply(&model).await.unwrap());
+        }
+        assert_eq!(replies.iter().filter(|r| *r == "a").count(), 4);
+        assert_eq!(replies.iter().filter(|r| *r == "b").count(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_round_robin_continues_across_instances() {
+        let members = || vec![mock("rr-resume-a", "a"), mock("rr-resume-b", "b")];
+
+        let first = RoundRobinModel::new("rr-resume", members(), None);
+        assert_eq!(reply(&first).await.unwrap(), "a");
+
+        let second = RoundRobinModel::new("rr-resume", members(), None);
+        assert_eq!(reply(&second).await.unwrap(), "b");
+    }
+
+    #[tokio::test]
+    async fn test_round_robin_skips_open_circuit() {
+        let breaker = shared_circuit_breaker("rr-skip-a");
+        for _ in 0..5 {
+            breaker.record_failure();
+        }
+
+        let model = RoundRobinModel::new(
+            "rr-skip",
+            vec![mock("rr-skip-a", "a"), mock("rr-skip-b", "b")],
+            None,
+        );
+
+        assert_eq!(reply(&model).await.unwrap(), "b");
+        assert_eq!(reply(&model).await.unwrap(), "b");
+    }
+
+    #[tokio::test]
+    async fn test_round_robin_all_circuits_open() {
+        for name in ["rr-down-a", "rr-down-b"] {
+            let breaker = shared_circuit_breaker(name);
+            for _ in 0..5 {
+                breaker.record_failure();
+            }
+        }
+
+        let model = RoundRobinModel::new(
+            "rr-down",
+            vec![mock("rr-down-a", "a"), mock("rr-down-b", "b")],
+            None,
+        );
+
+        let err = reply(&model).await.unwrap_err();
+        assert!(err.to_string().contains("rr-down"));
+    }
+
+    #[tokio::test]
+    async fn test_round_robin_failure_trips_member_breaker() {
+        let failing =
+            MockModel::new("rr-fail-a").with_behavior(MockBehavior::Error("boom".to_string()));
+        let model = RoundRobinModel::new(
+            "rr-fail",
+            vec![
+                Member::new("rr-fail-a", "rr-fail-a", Arc::new(failing)),
+                mock("rr-fail-b", "b"),
+            ],
+            None,
+        );
+
+        assert!(reply(&model).await.is_err());
+        assert_eq!(shared_circuit_breaker("rr-fail-a").failure_count(), 1);
+        assert_eq!(reply(&model).await.unwrap(), "b");
+    }
+
+    #[tokio::test]
+    async fn test_round_robin_members_share_provider_circuit() {
+        let failing =
+            MockModel::new("rr-shared-a").with_behavior(MockBehavior::Error("boom".to_string()));
+        let model = RoundRobinModel::new(
+            "rr-shared",
+            vec![
+                Member::new("rr-shared-a", "rr-shared-provider", Arc::new(failing)),
+                Member::new(
+                    "rr-shared-b",
+                    "rr-shared-provider",
+                    Arc::new(MockModel::new("rr-shared-b")),
+                ),
+            ],
+            None,
+        );
+
+        assert!(reply(&model).await.is_err());
+        assert_eq!(
+            shared_circuit_breaker("rr-shared-provider").failure_count(),
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn test_round_robin_reports_member_on_bus() {
+        let bus = MessageBus::new();
+        let mut rx = bus.subscribe();
+        let model = RoundRobinModel::new(
+            "rr-report",
+            vec![mock("rr-report-a", "a")],
+            Some(bus.sender()),
+        );
+
+        reply(&model).await.unwrap();
+
+        match rx.try_recv().unwrap() {
+            Some(Message::Text(msg)) => assert!(msg.text.contains("rr-report-a")),
+            other => panic!("expected a text message, got {:?}", other),
+        }
+    }
+}
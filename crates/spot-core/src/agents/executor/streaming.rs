@@ -21,13 +21,16 @@ use serdes_ai_core::{
 };
 use serdes_ai_tools::{Tool, ToolDefinition};
 
+use crate::checkpoints::CheckpointStore;
 use crate::messaging::EventBridge;
 use crate::models::settings::ModelSettings as SpotModelSettings;
 
 use super::adapters::{ArcModel, RecordingToolExecutor, ToolExecutorAdapter};
-use super::model_factory::{create_model_with_key, get_model};
+use super::cancel::{kill_run_processes, CANCELLED_MARKER};
+use super::model_factory::{create_model_with_key, get_model_with_bus};
+use super::request_settings::RequestSettings;
 use super::sub_agents::{InvokeAgentExecutor, ListAgentsExecutor};
-use super::types::{ExecuteContext, ExecutorError, ExecutorStreamReceiver};
+use super::types::{ExecuteContext, ExecutorError, ExecutorResult, ExecutorStreamReceiver};
 use super::{AgentExecutor, SpotAgent, StreamEvent};
 
 /// Safely truncate a string to at most `max_bytes` bytes, respecting UTF-8 character boundaries.
@@ -183,10 +186,94 @@ struct RawToolCall {
     args_buffer: String,
 }
 
+/// What [`AgentExecutor::process_stream`] accumulated from a stream.
+pub(super) struct StreamOutcome {
+    /// Text streamed by the model.
+    text: String,
+    /// Run ID from the `RunComplete` event.
+    run_id: Option<String>,
+    /// Message history including the prompt and everything after it.
+    messages: Vec<ModelRequest>,
+    /// Whether the stream ended because the run was cancelled.
+    cancelled: bool,
+}
+
+impl StreamOutcome {
+    /// Report the end of the run on `bridge` and build the executor result.
+    pub(super) fn into_result(self, bridge: &EventBridge) -> Result<ExecutorResult, ExecutorError> {
+        if self.cancelled {
+            bridge.agent_cancelled();
+            return Ok(ExecutorResult {
+                output: self.text,
+                messages: self.messages,
+                run_id: self.run_id.unwrap_or_default(),
+                cancelled: true,
+            });
+        }
+
+        // Get the run_id (from RunComplete event)
+        let run_id = self.run_id.ok_or_else(|| {
+            ExecutorError::Execution("Stream ended without RunComplete event".into())
+        })?;
+
+        bridge.agent_completed(&run_id);
+
+        Ok(ExecutorResult {
+            output: self.text,
+            messages: self.messages,
+            run_id,
+            cancelled: false,
+        })
+    }
+}
+
+/// Close the message history of a cancelled run.
+///
+/// Keeps the partial response text, answers tool calls that never returned
+/// with an error and appends a [`CANCELLED_MARKER`] response, so the next
+/// prompt continues from a well-formed history.
+fn close_cancelled_history(
+    messages: &mut Vec<ModelRequest>,
+    model_name: &str,
+    partial_text: &str,
+    unanswered_tool_calls: impl IntoIterator<Item = (String, Option<String>)>,
+    mut tool_returns: Vec<ToolReturnPart>,
+) {
+    for (tool_name, tool_call_id) in unanswered_tool_calls {
+        let mut part = ToolReturnPart::error(&tool_name, "Cancelled by user".to_string());
+        if let Some(id) = tool_call_id {
+            part = part.with_tool_call_id(id);
+        }
+        tool_returns.push(part);
+    }
+
+    if !tool_returns.is_empty() {
+        let mut tool_req = ModelRequest::new();
+        for part in tool_returns {
+            tool_req.parts.push(ModelRequestPart::ToolReturn(part));
+        }
+        messages.push(tool_req);
+    }
+
+    let text = if partial_text.is_empty() {
+        CANCELLED_MARKER.to_string()
+    } else {
+        format!("{}\n\n{}", partial_text, CANCELLED_MARKER)
+    };
+    let response = ModelResponse::with_parts(vec![ModelResponsePart::Text(TextPart::new(text))])
+        .with_model_name(model_name.to_string());
+    let mut response_req = ModelRequest::new();
+    response_req
+        .parts
+        .push(ModelRequestPart::ModelResponse(Box::new(response)));
+    messages.push(response_req);
+}
+
 impl<'a> AgentExecutor<'a> {
     /// Process a stream of events and accumulate results.
     ///
-    /// Returns (accumulated_text, final_run_id, messages).
+    /// A stream ending in [`ExecutorError::Cancelled`] yields a cancelled
+    /// outcome whose history is closed with a cancellation marker.
     pub(super) async fn process_stream(
         &self,
         stream: &mut ExecutorStreamReceiver,
@@ -194,7 +281,7 @@ impl<'a> AgentExecutor<'a> {
         mut messages: Vec<ModelRequest>,
         model_name: &str,
         tool_return_recorder: &Arc<Mutex<Vec<ToolReturnPart>>>,
-    ) -> Result<(String, Option<String>, Vec<ModelRequest>), ExecutorError> {
+    ) -> Result<StreamOutcome, ExecutorError> {
         // Accumulate text for the final output
         let mut accumulated_text = String::new();
         let mut final_run_id: Option<String> = None;
@@ -375,6 +462,27 @@ impl<'a> AgentExecutor<'a> {
 
                     bridge.process(event);
                 }
+                Err(ExecutorError::Cancelled) => {
+                    info!("Run cancelled, closing message history");
+                    let unanswered = if expected_tool_returns > 0 {
+                        std::mem::take(&mut pending_tool_calls)
+                    } else {
+                        VecDeque::new()
+                    };
+                    close_cancelled_history(
+                        &mut messages,
+                        model_name,
+                        &current_response_text,
+                        unanswered,
+                        pending_tool_returns,
+                    );
+                    return Ok(StreamOutcome {
+                        text: accumulated_text,
+                        run_id: final_run_id,
+                        messages,
+                        cancelled: true,
+                    });
+                }
                 Err(e) => {
                     bridge.agent_error(&e.to_string());
                     return Err(e);
@@ -391,7 +499,12 @@ impl<'a> AgentExecutor<'a> {
             messages.push(tool_req);
         }
 
-        Ok((accumulated_text, final_run_id, messages))
+        Ok(StreamOutcome {
+            text: accumulated_text,
+            run_id: final_run_id,
+            messages,
+            cancelled: false,
+        })
     }
 
     /// Internal streaming execution with full control over user content.
@@ -408,10 +521,18 @@ impl<'a> AgentExecutor<'a> {
         let spot_settings = SpotModelSettings::load(self.db, model_name).ok();
 
         // Get the model (handles OAuth models and custom endpoints)
-        let model = get_model(self.db, model_name, self.registry, spot_settings.as_ref()).await?;
+        let model = get_model_with_bus(
+            self.db,
+            model_name,
+            self.registry,
+            spot_settings.as_ref(),
+            self.bus.as_ref(),
+        )
+        .await?;
+        let model = self.with_failover(model_name, model).await;
 
         // Get original tool list (before filtering) to check for special tools
-        let original_tools = spot_agent.available_tools();
+        let original_tools = self.apply_capabilities(spot_agent, spot_agent.available_tools());
         let wants_invoke = self.wants_invoke_agent(&original_tools);
         let wants_list = self.wants_list_agents(&original_tools);
 
@@ -423,44 +544,31 @@ impl<'a> AgentExecutor<'a> {
         let mut tool_data: Vec<(ToolDefinition, Arc<dyn Tool + Send + Sync>)> =
             tools.into_iter().map(|t| (t.definition(), t)).collect();
 
-        // Collect MCP tools from running servers (filtered by agent attachments)
+        // Collect MCP tools from running servers (filtered by attachments and capabilities)
         let mcp_tool_calls = self
-            .collect_mcp_tools(context.mcp_manager, Some(spot_agent.name()))
+            .agent_mcp_tools(spot_agent, context.mcp_manager, context.tool_registry)
             .await;
         tool_data.extend(mcp_tool_calls);
 
-        // Load per-model settings from database
+        // Per-model request settings (temperature, output limit, seed, reasoning)
         let spot_settings = SpotModelSettings::load(self.db, model_name).unwrap_or_default();
-
-        // Check if this model has thinking enabled (supports it and not explicitly disabled)
-        let model_supports_thinking = self
-            .registry
-            .get(model_name)
-            .map(|c| c.supports_thinking)
-            .unwrap_or(false);
-        let thinking_explicitly_disabled = spot_settings.extended_thinking == Some(false);
-        let thinking_enabled = model_supports_thinking && !thinking_explicitly_disabled;
-
-        // Convert to serdes_ai_core::ModelSettings
-        // When thinking is enabled, temperature MUST be 1.0 per Claude API requirements
-        let effective_temp = if thinking_enabled {
-            1.0
-        } else {
-            spot_settings.effective_temperature() as f64
-        };
-
-        let core_settings = serdes_ai_core::ModelSettings::new()
-            .temperature(effective_temp)
-            .top_p(spot_settings.effective_top_p() as f64)
-            .max_tokens(30000);
+        let RequestSettings {
+            max_tokens,
+            core: core_settings,
+        } = RequestSettings::new(&spot_settings, self.registry.get(model_name));
 
         // Prepare data for the spawned task
         let system_prompt = spot_agent.system_prompt();
         let model_name_owned = model_name.to_string();
         let db_path = self.db.path().to_path_buf();
         let bus = self.bus.clone();
+        let agent_name = spot_agent.name().to_string();
+        let checkpoints = self.checkpoints.clone();
+        let recorder = checkpoints.as_ref().map(CheckpointStore::recorder);
         let tool_return_recorder = tool_return_recorder.clone();
         let (tx, rx) = mpsc::channel(32);
+        let cancel = self.cancel.child();
+        let task_cancel = cancel.clone();
 
         // Build compression config from settings (loaded before spawn)
         let compression_config = {
@@ -510,7 +618,7 @@ impl<'a> AgentExecutor<'a> {
             let mut builder = agent(wrapped_model)
                 .system_prompt(system_prompt)
                 .temperature(1.0)
-                .max_tokens(30000);
+                .max_tokens(max_tokens);
 
             match tool_return_recorder {
                 Some(recorder) => {
@@ -520,7 +628,10 @@ impl<'a> AgentExecutor<'a> {
                         builder = builder.tool_with_executor(
                             def,
                             RecordingToolExecutor::new(
-                                ToolExecutorAdapter::new(tool),
+                                ToolExecutorAdapter::new(tool)
+                                    .with_diffs(bus.clone(), &agent_name)
+                                    .with_checkpoints(recorder.clone())
+                                    .with_processes(task_cancel.processes()),
                                 recorder.clone(),
                             ),
                         );
@@ -532,7 +643,9 @@ impl<'a> AgentExecutor<'a> {
                             db_path.clone(),
                             &model_name_owned,
                             bus.clone(),
-                        );
+                        )
+                        .with_cancel(task_cancel.child())
+                        .with_checkpoints(checkpoints.clone());
                         builder = builder.tool_with_executor(
                             InvokeAgentExecutor::definition(),
                             RecordingToolExecutor::new(invoke_executor, recorder.clone()),
@@ -554,7 +667,13 @@ impl<'a> AgentExecutor<'a> {
                     // Register tools with real executors
                     for (def, tool) in tool_data {
                         debug!(tool_name = %def.name, "Registering tool");
-                        builder = builder.tool_with_executor(def, ToolExecutorAdapter::new(tool));
+                        builder = builder.tool_with_executor(
+                            def,
+                            ToolExecutorAdapter::new(tool)
+                                .with_diffs(bus.clone(), &agent_name)
+                                .with_checkpoints(recorder.clone())
+                                .with_processes(task_cancel.processes()),
+                        );
                     }
 
                     // Add invoke_agent with custom executor (has database access)
@@ -563,7 +682,9 @@ impl<'a> AgentExecutor<'a> {
                             db_path.clone(),
                             &model_name_owned,
                             bus.clone(),
-                        );
+                        )
+                        .with_cancel(task_cancel.child())
+                        .with_checkpoints(checkpoints.clone());
                         builder = builder
                             .tool_with_executor(InvokeAgentExecutor::definition(), invoke_executor);
                     }
@@ -607,60 +728,72 @@ impl<'a> AgentExecutor<'a> {
             // Use real streaming from serdesAI
             debug!("Calling run_stream_with_options");
 
-            match serdes_agent
-                .run_stream_with_options(prompt, (), options)
-                .await
-            {
-                Ok(mut stream) => {
-                    debug!("Stream started, forwarding events");
-                    let mut event_count = 0u32;
-
-                    // Forward all events from the stream
-                    while let Some(event_result) = stream.next().await {
-                        event_count += 1;
-                        match event_result {
-                            Ok(event) => {
-                                debug!(event_num = event_count, "Received stream event");
-                                if tx.send(Ok(event)).await.is_err() {
-                                    warn!("Receiver dropped, stopping stream");
+            // Stop on cancellation; dropping the stream aborts in-flight tool calls
+            let run = async {
+                match serdes_agent
+                    .run_stream_with_options(prompt, (), options)
+                    .await
+                {
+                    Ok(mut stream) => {
+                        debug!("Stream started, forwarding events");
+                        let mut event_count = 0u32;
+
+                        // Forward all events from the stream
+                        while let Some(event_result) = stream.next().await {
+                            event_count += 1;
+                            match event_result {
+                                Ok(event) => {
+                                    debug!(event_num = event_count, "Received stream event");
+                                    if tx.send(Ok(event)).await.is_err() {
+                                        warn!("Receiver dropped, stopping stream");
+                                        break;
+                                    }
+                                }
+                                Err(e) => {
+                                    let error_str = e.to_string();
+                                    error!(error = %error_str, "Stream error");
+                                    log_http_error(&error_str);
+
+                                    let _ = tx
+                                        .send(Ok(StreamEvent::Error {
+                                            message: error_str.clone(),
+                                        }))
+                                        .await;
+                                    let _ = tx.send(Err(ExecutorError::Execution(error_str))).await;
                                     break;
                                 }
                             }
-                            Err(e) => {
-                                let error_str = e.to_string();
-                                error!(error = %error_str, "Stream error");
-                                log_http_error(&error_str);
-
-                                let _ = tx
-                                    .send(Ok(StreamEvent::Error {
-                                        message: error_str.clone(),
-                                    }))
-                                    .await;
-                                let _ = tx.send(Err(ExecutorError::Execution(error_str))).await;
-                                break;
-                            }
                         }
+                        debug!(total_events = event_count, "Stream completed");
+                    }
+                    Err(e) => {
+                        let error_str = e.to_string();
+                        error!(error = %error_str, "Failed to start stream");
+                        log_http_error(&error_str);
+
+                        // Send error event
+                        let _ = tx
+                            .send(Ok(StreamEvent::Error {
+                                message: error_str.clone(),
+                            }))
+                            .await;
+                        let _ = tx.send(Err(ExecutorError::Execution(error_str))).await;
                     }
-                    debug!(total_events = event_count, "Stream completed");
                 }
-                Err(e) => {
-                    let error_str = e.to_string();
-                    error!(error = %error_str, "Failed to start stream");
-                    log_http_error(&error_str);
-
-                    // Send error event
-                    let _ = tx
-                        .send(Ok(StreamEvent::Error {
-                            message: error_str.clone(),
-                        }))
-                        .await;
-                    let _ = tx.send(Err(ExecutorError::Execution(error_str))).await;
+            };
+            tokio::select! {
+                biased;
+                _ = task_cancel.cancelled() => {
+                    info!("Run cancelled, stopping stream");
+                    kill_run_processes(&task_cancel).await;
+                    let _ = tx.send(Err(ExecutorError::Cancelled)).await;
                 }
+                _ = run => {}
             }
             debug!("Streaming task exiting");
         });
 
-        Ok(ExecutorStreamReceiver::new(rx))
+        Ok(ExecutorStreamReceiver::new(rx, cancel))
     }
 
     /// Internal streaming execution with explicit API key for retry scenarios.
@@ -680,9 +813,10 @@ impl<'a> AgentExecutor<'a> {
     ) -> Result<ExecutorStreamReceiver, ExecutorError> {
         // Create model with the explicit API key
         let model = create_model_with_key(model_name, self.registry, api_key)?;
+        let model = self.with_failover(model_name, model).await;
 
         // Get original tool list (before filtering) to check for special tools
-        let original_tools = spot_agent.available_tools();
+        let original_tools = self.apply_capabilities(spot_agent, spot_agent.available_tools());
         let wants_invoke = self.wants_invoke_agent(&original_tools);
         let wants_list = self.wants_list_agents(&original_tools);
 
@@ -694,43 +828,31 @@ impl<'a> AgentExecutor<'a> {
         let mut tool_data: Vec<(ToolDefinition, Arc<dyn Tool + Send + Sync>)> =
             tools.into_iter().map(|t| (t.definition(), t)).collect();
 
-        // Collect MCP tools from running servers (filtered by agent attachments)
+        // Collect MCP tools from running servers (filtered by attachments and capabilities)
         let mcp_tool_calls = self
-            .collect_mcp_tools(context.mcp_manager, Some(spot_agent.name()))
+            .agent_mcp_tools(spot_agent, context.mcp_manager, context.tool_registry)
             .await;
         tool_data.extend(mcp_tool_calls);
 
-        // Load per-model settings from database
+        // Per-model request settings (temperature, output limit, seed, reasoning)
         let spot_settings = SpotModelSettings::load(self.db, model_name).unwrap_or_default();
-
-        // Check if this model has thinking enabled (supports it and not explicitly disabled)
-        let model_supports_thinking = self
-            .registry
-            .get(model_name)
-            .map(|c| c.supports_thinking)
-            .unwrap_or(false);
-        let thinking_explicitly_disabled = spot_settings.extended_thinking == Some(false);
-        let thinking_enabled = model_supports_thinking && !thinking_explicitly_disabled;
-
-        // Convert to serdes_ai_core::ModelSettings
-        let effective_temp = if thinking_enabled {
-            1.0
-        } else {
-            spot_settings.effective_temperature() as f64
-        };
-
-        let core_settings = serdes_ai_core::ModelSettings::new()
-            .temperature(effective_temp)
-            .top_p(spot_settings.effective_top_p() as f64)
-            .max_tokens(30000);
+        let RequestSettings {
+            max_tokens,
+            core: core_settings,
+        } = RequestSettings::new(&spot_settings, self.registry.get(model_name));
 
         // Prepare data for the spawned task
         let system_prompt = spot_agent.system_prompt();
         let model_name_owned = model_name.to_string();
         let db_path = self.db.path().to_path_buf();
         let bus = self.bus.clone();
+        let agent_name = spot_agent.name().to_string();
+        let checkpoints = self.checkpoints.clone();
+        let recorder = checkpoints.as_ref().map(CheckpointStore::recorder);
         let tool_return_recorder = tool_return_recorder.clone();
         let (tx, rx) = mpsc::channel(32);
+        let cancel = self.cancel.child();
+        let task_cancel = cancel.clone();
 
         // Build compression config from settings (loaded before spawn)
         let compression_config = {
@@ -766,7 +888,7 @@ impl<'a> AgentExecutor<'a> {
             let mut builder = agent(wrapped_model)
                 .system_prompt(system_prompt)
                 .temperature(1.0)
-                .max_tokens(30000);
+                .max_tokens(max_tokens);
 
             match tool_return_recorder {
                 Some(recorder) => {
@@ -775,7 +897,10 @@ impl<'a> AgentExecutor<'a> {
                         builder = builder.tool_with_executor(
                             def,
                             RecordingToolExecutor::new(
-                                ToolExecutorAdapter::new(tool),
+                                ToolExecutorAdapter::new(tool)
+                                    .with_diffs(bus.clone(), &agent_name)
+                                    .with_checkpoints(recorder.clone())
+                                    .with_processes(task_cancel.processes()),
                                 recorder.clone(),
                             ),
                         );
@@ -786,7 +911,9 @@ impl<'a> AgentExecutor<'a> {
                             db_path.clone(),
                             &model_name_owned,
                             bus.clone(),
-                        );
+                        )
+                        .with_cancel(task_cancel.child())
+                        .with_checkpoints(checkpoints.clone());
                         builder = builder.tool_with_executor(
                             InvokeAgentExecutor::definition(),
                             RecordingToolExecutor::new(invoke_executor, recorder.clone()),
@@ -805,7 +932,13 @@ impl<'a> AgentExecutor<'a> {
                 }
                 None => {
                     for (def, tool) in tool_data {
-                        builder = builder.tool_with_executor(def, ToolExecutorAdapter::new(tool));
+                        builder = builder.tool_with_executor(
+                            def,
+                            ToolExecutorAdapter::new(tool)
+                                .with_diffs(bus.clone(), &agent_name)
+                                .with_checkpoints(recorder.clone())
+                                .with_processes(task_cancel.processes()),
+                        );
                     }
 
                     if wants_invoke {
@@ -813,7 +946,9 @@ impl<'a> AgentExecutor<'a> {
                             db_path.clone(),
                             &model_name_owned,
                             bus.clone(),
-                        );
+                        )
+                        .with_cancel(task_cancel.child())
+                        .with_checkpoints(checkpoints.clone());
                         builder = builder
                             .tool_with_executor(InvokeAgentExecutor::definition(), invoke_executor);
                     }
@@ -851,57 +986,69 @@ impl<'a> AgentExecutor<'a> {
 
             debug!("Calling run_stream_with_options");
 
-            match serdes_agent
-                .run_stream_with_options(prompt, (), options)
-                .await
-            {
-                Ok(mut stream) => {
-                    debug!("Stream started, forwarding events");
-                    let mut event_count = 0u32;
-
-                    while let Some(event_result) = stream.next().await {
-                        event_count += 1;
-                        match event_result {
-                            Ok(event) => {
-                                if tx.send(Ok(event)).await.is_err() {
-                                    warn!("Receiver dropped, stopping stream");
+            // Stop on cancellation; dropping the stream aborts in-flight tool calls
+            let run = async {
+                match serdes_agent
+                    .run_stream_with_options(prompt, (), options)
+                    .await
+                {
+                    Ok(mut stream) => {
+                        debug!("Stream started, forwarding events");
+                        let mut event_count = 0u32;
+
+                        while let Some(event_result) = stream.next().await {
+                            event_count += 1;
+                            match event_result {
+                                Ok(event) => {
+                                    if tx.send(Ok(event)).await.is_err() {
+                                        warn!("Receiver dropped, stopping stream");
+                                        break;
+                                    }
+                                }
+                                Err(e) => {
+                                    let error_str = e.to_string();
+                                    error!(error = %error_str, "Stream error");
+                                    log_http_error(&error_str);
+
+                                    let _ = tx
+                                        .send(Ok(StreamEvent::Error {
+                                            message: error_str.clone(),
+                                        }))
+                                        .await;
+                                    let _ = tx.send(Err(ExecutorError::Execution(error_str))).await;
                                     break;
                                 }
                             }
-                            Err(e) => {
-                                let error_str = e.to_string();
-                                error!(error = %error_str, "Stream error");
-                                log_http_error(&error_str);
-
-                                let _ = tx
-                                    .send(Ok(StreamEvent::Error {
-                                        message: error_str.clone(),
-                                    }))
-                                    .await;
-                                let _ = tx.send(Err(ExecutorError::Execution(error_str))).await;
-                                break;
-                            }
                         }
+                        debug!(total_events = event_count, "Stream completed");
+                    }
+                    Err(e) => {
+                        let error_str = e.to_string();
+                        error!(error = %error_str, "Failed to start stream");
+                        log_http_error(&error_str);
+
+                        let _ = tx
+                            .send(Ok(StreamEvent::Error {
+                                message: error_str.clone(),
+                            }))
+                            .await;
+                        let _ = tx.send(Err(ExecutorError::Execution(error_str))).await;
                     }
-                    debug!(total_events = event_count, "Stream completed");
                 }
-                Err(e) => {
-                    let error_str = e.to_string();
-                    error!(error = %error_str, "Failed to start stream");
-                    log_http_error(&error_str);
-
-                    let _ = tx
-                        .send(Ok(StreamEvent::Error {
-                            message: error_str.clone(),
-                        }))
-                        .await;
-                    let _ = tx.send(Err(ExecutorError::Execution(error_str))).await;
+            };
+            tokio::select! {
+                biased;
+                _ = task_cancel.cancelled() => {
+                    info!("Run cancelled, stopping stream");
+                    kill_run_processes(&task_cancel).await;
+                    let _ = tx.send(Err(ExecutorError::Cancelled)).await;
                 }
+                _ = run => {}
             }
             debug!("Streaming task exiting");
         });
 
-        Ok(ExecutorStreamReceiver::new(rx))
+        Ok(ExecutorStreamReceiver::new(rx, cancel))
     }
 }
 
@@ -1042,4 +1189,57 @@ mod tests {
 
         assert_eq!(tc.args_buffer, "{\"key\": \"value\"}");
     }
+
+    // =========================================================================
+    // close_cancelled_history Tests
+    // =========================================================================
+
+    fn last_response_text(messages: &[ModelRequest]) -> String {
+        let Some(ModelRequestPart::ModelResponse(response)) =
+            messages.last().and_then(|req| req.parts.first())
+        else {
+            panic!("expected a model response at the end of history");
+        };
+        match response.parts.first() {
+            Some(ModelResponsePart::Text(text)) => text.content.clone(),
+            other => panic!("expected text part, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_close_cancelled_history_keeps_partial_text() {
+        let mut messages = Vec::new();
+        close_cancelled_history(
+            &mut messages,
+            "test-model",
+            "Partial answer",
+            [],
+            Vec::new(),
+        );
+
+        assert_eq!(messages.len(), 1);
+        assert_eq!(
+            last_response_text(&messages),
+            format!("Partial answer\n\n{}", CANCELLED_MARKER)
+        );
+    }
+
+    #[test]
+    fn test_close_cancelled_history_answers_pending_tool_calls() {
+        let mut messages = Vec::new();
+        close_cancelled_history(
+            &mut messages,
+            "test-model",
+            "",
+            [("read_file".to_string(), Some("call_1".to_string()))],
+            Vec::new(),
+        );
+
+        assert_eq!(messages.len(), 2);
+        assert!(matches!(
+            messages[0].parts.as_slice(),
+            [ModelRequestPart::ToolReturn(_)]
+        ));
+        assert_eq!(last_response_text(&messages), CANCELLED_MARKER);
+    }
 }
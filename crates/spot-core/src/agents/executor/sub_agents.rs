@@ -12,6 +12,7 @@ use tracing::{debug, warn};
 use serdes_ai_tools::{Tool, ToolDefinition, ToolError, ToolReturn};
 
 use crate::agents::AgentManager;
+use crate::checkpoints::CheckpointStore;
 use crate::config::Settings;
 use crate::db::Database;
 use crate::mcp::McpManager;
@@ -21,7 +22,7 @@ use crate::session::SessionManager;
 use crate::tools::agent_tools::InvokeAgentTool;
 use crate::tools::SpotToolRegistry;
 
-use super::{AgentExecutor, RetryHandler};
+use super::{AgentExecutor, CancelHandle, RetryHandler};
 
 /// Executor for invoke_agent that has access to all required dependencies.
 pub(super) struct InvokeAgentExecutor {
@@ -29,6 +30,10 @@ pub(super) struct InvokeAgentExecutor {
     current_model: String,
     /// Optional message bus for sub-agent event publishing.
     bus: Option<MessageSender>,
+    /// Cancels the sub-agent run when the invoking run is cancelled.
+    cancel: CancelHandle,
+    /// Store for checkpoints of files the sub-agent changes.
+    checkpoints: Option<CheckpointStore>,
 }
 
 impl InvokeAgentExecutor {
@@ -38,6 +43,8 @@ impl InvokeAgentExecutor {
             db_path: db.path().to_path_buf(),
             current_model: current_model.to_string(),
             bus: Some(bus),
+            cancel: CancelHandle::new(),
+            checkpoints: None,
         }
     }
 
@@ -47,6 +54,8 @@ impl InvokeAgentExecutor {
             db_path: db.path().to_path_buf(),
             current_model: current_model.to_string(),
             bus: None,
+            cancel: CancelHandle::new(),
+            checkpoints: None,
         }
     }
 
@@ -60,9 +69,23 @@ impl InvokeAgentExecutor {
             db_path,
             current_model: current_model.to_string(),
             bus,
+            cancel: CancelHandle::new(),
+            checkpoints: None,
         }
     }
 
+    /// Cancel sub-agent runs through `cancel`.
+    pub fn with_cancel(mut self, cancel: CancelHandle) -> Self {
+        self.cancel = cancel;
+        self
+    }
+
+    /// Record sub-agent file changes in the invoking run's checkpoint store.
+    pub fn with_checkpoints(mut self, checkpoints: Option<CheckpointStore>) -> Self {
+        self.checkpoints = checkpoints;
+        self
+    }
+
     pub fn definition() -> ToolDefinition {
         InvokeAgentTool.definition()
     }
@@ -95,6 +118,8 @@ impl serdes_ai_agent::ToolExecutor<()> for InvokeAgentExecutor {
         let prompt = args.prompt.clone();
         let session_id = args.session_id.clone();
         let bus = self.bus.clone();
+        let cancel = self.cancel.clone();
+        let checkpoints = self.checkpoints.clone();
 
         // Run the agent in a blocking context to handle the non-Send Database
         let result = tokio::task::spawn_blocking(move || {
@@ -129,7 +154,7 @@ impl serdes_ai_agent::ToolExecutor<()> for InvokeAgentExecutor {
                 };
 
                 // Load session history if session_id provided
-                let session_manager = SessionManager::new();
+                let session_manager = SessionManager::new(&db);
                 let message_history = session_id.as_ref().and_then(|sid| {
                     match session_manager.load(sid) {
                         Ok(data) => {
@@ -150,8 +175,12 @@ impl serdes_ai_agent::ToolExecutor<()> for InvokeAgentExecutor {
                 ));
 
                 // Create executor - with bus if available for visible sub-agent output
-                let executor = AgentExecutor::new(&db, &model_registry)
-                    .with_retry_handler(retry_handler);
+                let mut executor = AgentExecutor::new(&db, &model_registry)
+                    .with_retry_handler(retry_handler)
+                    .with_cancel(cancel);
+                if let Some(store) = checkpoints {
+                    executor = executor.with_checkpoints(store);
+                }
 
                 let result = if let Some(bus) = bus {
                     // Use execute_with_bus - events flow to the same bus!
@@ -201,6 +230,10 @@ impl serdes_ai_agent::ToolExecutor<()> for InvokeAgentExecutor {
                     }
                 }
 
+                if result.cancelled {
+                    return Err("Sub-agent run was cancelled".to_string());
+                }
+
                 Ok::<_, String>((result.output, final_session_id))
             })
         })
@@ -282,6 +315,31 @@ mod tests {
         assert_eq!(executor.db_path, db_path);
         assert_eq!(executor.current_model, "gpt-4");
         assert!(executor.bus.is_none());
+        assert!(!executor.cancel.is_cancelled());
+    }
+
+    #[test]
+    fn test_invoke_agent_executor_with_cancel() {
+        let parent = CancelHandle::new();
+        let executor =
+            InvokeAgentExecutor::new_with_path(PathBuf::from("/tmp/test.db"), "gpt-4", None)
+                .with_cancel(parent.child());
+
+        parent.cancel();
+        assert!(executor.cancel.is_cancelled());
+    }
+
+    #[test]
+    fn test_invoke_agent_executor_shares_checkpoint_store() {
+        let store = CheckpointStore::new();
+        let executor =
+            InvokeAgentExecutor::new_with_path(PathBuf::from("/tmp/test.db"), "gpt-4", None)
+                .with_checkpoints(Some(store.clone()));
+
+        // Turns begun by the frontend are visible through the sub-agent's store
+        store.begin_turn();
+        let shared = executor.checkpoints.expect("store should be set");
+        assert_eq!(shared.current_turn(), Some(1));
     }
 
     #[test]
@@ -11,6 +11,7 @@ use serdes_ai_core::ModelRequest;
 use thiserror::Error;
 use tokio::sync::mpsc;
 
+use super::cancel::CancelHandle;
 use super::StreamEvent;
 
 /// Execution context containing tool registry and MCP manager.
@@ -32,6 +33,11 @@ pub struct ExecutorResult {
     pub messages: Vec<ModelRequest>,
     /// Unique run ID for tracing.
     pub run_id: String,
+    /// Whether the run was cancelled before completing.
+    ///
+    /// `messages` then ends with a cancellation marker, and tool calls that
+    /// never returned are answered with an error.
+    pub cancelled: bool,
 }
 
 /// Receiver for streaming events from agent execution.
@@ -40,12 +46,23 @@ pub struct ExecutorResult {
 /// for consuming streaming events.
 pub struct ExecutorStreamReceiver {
     rx: mpsc::Receiver<Result<StreamEvent, ExecutorError>>,
+    cancel: CancelHandle,
 }
 
 impl ExecutorStreamReceiver {
     /// Create a new stream receiver from a channel.
-    pub(super) fn new(rx: mpsc::Receiver<Result<StreamEvent, ExecutorError>>) -> Self {
-        Self { rx }
+    pub(super) fn new(
+        rx: mpsc::Receiver<Result<StreamEvent, ExecutorError>>,
+        cancel: CancelHandle,
+    ) -> Self {
+        Self { rx, cancel }
+    }
+
+    /// Handle that cancels the run producing this stream.
+    ///
+    /// After cancelling, the stream ends with `Err(ExecutorError::Cancelled)`.
+    pub fn cancel_handle(&self) -> CancelHandle {
+        self.cancel.clone()
     }
 
     /// Receive the next event from the stream.
@@ -71,9 +88,16 @@ pub enum ExecutorError {
     Config(String),
     #[error("Rate limit exceeded: {0}")]
     RateLimit(String),
+    #[error("Cancelled by user")]
+    Cancelled,
 }
 
 impl ExecutorError {
+    /// Check if this error is a user cancellation.
+    pub fn is_cancelled(&self) -> bool {
+        matches!(self, ExecutorError::Cancelled)
+    }
+
     /// Check if this error indicates a rate limit.
     pub fn is_rate_limit(&self) -> bool {
         match self {
@@ -94,6 +118,34 @@ impl ExecutorError {
             _ => false,
         }
     }
+
+    /// Check if this error is transient and worth retrying on the same provider.
+    ///
+    /// Covers 5xx server errors, overloaded providers and network failures.
+    /// Rate limits are handled separately by key rotation and are not transient.
+    pub fn is_transient(&self) -> bool {
+        if self.is_rate_limit() {
+            return false;
+        }
+        match self {
+            ExecutorError::Execution(msg) | ExecutorError::Model(msg) => {
+                if let Some(code) = super::retry::extract_status_code(msg) {
+                    return matches!(code, 500 | 502 | 503 | 504 | 529);
+                }
+                let lower = msg.to_lowercase();
+                lower.contains("overloaded")
+                    || lower.contains("timed out")
+                    || lower.contains("timeout")
+                    || lower.contains("connection reset")
+                    || lower.contains("connection refused")
+                    || lower.contains("connection closed")
+                    || lower.contains("error sending request")
+                    || lower.contains("service unavailable")
+                    || lower.contains("bad gateway")
+            }
+            _ => false,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -136,6 +188,16 @@ mod tests {
         assert_eq!(err.to_string(), "Rate limit exceeded: All keys exhausted");
     }
 
+    #[test]
+    fn executor_error_cancelled() {
+        let err = ExecutorError::Cancelled;
+        assert_eq!(err.to_string(), "Cancelled by user");
+        assert!(err.is_cancelled());
+        assert!(!err.is_transient());
+        assert!(!err.is_rate_limit());
+        assert!(!ExecutorError::Execution("cancelled".into()).is_cancelled());
+    }
+
     #[test]
     fn executor_error_is_rate_limit_direct() {
         let err = ExecutorError::RateLimit("test".into());
@@ -186,12 +248,37 @@ mod tests {
         assert!(!ExecutorError::Execution("timeout".into()).is_rate_limit());
     }
 
+    #[test]
+    fn executor_error_is_transient_server_errors() {
+        for code in [500, 502, 503, 504, 529] {
+            let err = ExecutorError::Model(format!("HTTP error status: {}", code));
+            assert!(err.is_transient(), "status {} should be transient", code);
+        }
+    }
+
+    #[test]
+    fn executor_error_is_transient_network_errors() {
+        assert!(ExecutorError::Execution("error sending request for url".into()).is_transient());
+        assert!(ExecutorError::Model("operation timed out".into()).is_transient());
+        assert!(ExecutorError::Model("Overloaded".into()).is_transient());
+    }
+
+    #[test]
+    fn executor_error_is_transient_false_for_others() {
+        assert!(!ExecutorError::Model("HTTP error status: 400".into()).is_transient());
+        assert!(!ExecutorError::Model("status: 429 rate limited".into()).is_transient());
+        assert!(!ExecutorError::RateLimit("slow down".into()).is_transient());
+        assert!(!ExecutorError::Auth("timeout".into()).is_transient());
+        assert!(!ExecutorError::Config("bad".into()).is_transient());
+    }
+
     #[test]
     fn executor_result_fields() {
         let result = ExecutorResult {
             output: "Hello world".to_string(),
             messages: vec![],
             run_id: "run-123".to_string(),
+            cancelled: false,
         };
         assert_eq!(result.output, "Hello world");
         assert!(result.messages.is_empty());
@@ -207,6 +294,7 @@ mod tests {
             output: "response".to_string(),
             messages: vec![msg],
             run_id: "run-456".to_string(),
+            cancelled: false,
         };
         assert_eq!(result.messages.len(), 1);
     }
@@ -214,7 +302,7 @@ mod tests {
     #[tokio::test]
     async fn executor_stream_receiver_new_and_recv() {
         let (tx, rx) = mpsc::channel(1);
-        let mut receiver = ExecutorStreamReceiver::new(rx);
+        let mut receiver = ExecutorStreamReceiver::new(rx, CancelHandle::new());
 
         // Send an event
         tx.send(Err(ExecutorError::Model("test".into())))
@@ -234,7 +322,7 @@ mod tests {
     #[tokio::test]
     async fn executor_stream_receiver_empty_channel() {
         let (_tx, rx) = mpsc::channel::<Result<StreamEvent, ExecutorError>>(1);
-        let mut receiver = ExecutorStreamReceiver::new(rx);
+        let mut receiver = ExecutorStreamReceiver::new(rx, CancelHandle::new());
 
         // Drop sender immediately
         drop(_tx);
@@ -152,6 +152,11 @@ impl SpotAgent for JsonAgent {
         self.capabilities.clone()
     }
 
+    /// Only agents with a `capabilities` block are restricted.
+    fn enforces_capabilities(&self) -> bool {
+        self.def.capabilities.is_some()
+    }
+
     fn visibility(&self) -> AgentVisibility {
         self.def.visibility.unwrap_or_default()
     }
@@ -593,6 +598,35 @@ mod tests {
         assert!(caps.mcp); // default
     }
 
+    #[test]
+    fn test_enforces_capabilities_only_when_declared() {
+        let mut def = JsonAgentDef {
+            name: "declared".to_string(),
+            display_name: None,
+            description: None,
+            system_prompt: "Test.".to_string(),
+            tools: vec!["run_shell_command".to_string()],
+            model: None,
+            capabilities: None,
+            visibility: None,
+        };
+        assert!(!JsonAgent::new(def.clone()).enforces_capabilities());
+
+        def.capabilities = Some(JsonCapabilities {
+            file_read: None,
+            file_write: None,
+            shell: Some(false),
+            sub_agents: None,
+            mcp: None,
+        });
+        let agent = JsonAgent::new(def);
+        assert!(agent.enforces_capabilities());
+        assert_eq!(
+            agent.capabilities().denied_capability("run_shell_command"),
+            Some("shell")
+        );
+    }
+
     #[test]
     fn test_capabilities_default_when_none() {
         let def = JsonAgentDef {
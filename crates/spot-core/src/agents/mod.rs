@@ -18,7 +18,7 @@ pub mod json_agent;
 mod manager;
 
 pub use base::SpotAgent;
-pub use executor::{AgentExecutor, ExecuteContext, RetryHandler};
+pub use executor::{AgentExecutor, CancelHandle, ExecuteContext, RetryHandler};
 pub use manager::{AgentInfo, AgentManager};
 
 /// Agent capability flags.
@@ -120,4 +120,80 @@ impl AgentCapabilities {
             mcp: false,
         }
     }
+
+    /// Name of the capability `tool_name` needs but these capabilities lack.
+    ///
+    /// Returns `None` when the tool is allowed. MCP tools are not covered here;
+    /// they are gated as a whole by [`mcp`](Self::mcp).
+    pub fn denied_capability(&self, tool_name: &str) -> Option<&'static str> {
+        let (capability, allowed) = match tool_name {
+            "run_shell_command"
+            | "list_processes"
+            | "read_process_output"
+            | "kill_process"
+            | "write_process_input"
+            | "wait_for_output" => ("shell", self.shell),
+            "edit_file" | "delete_file" => ("file_write", self.file_write),
+            "list_files" | "read_file" | "grep" | "outline_file" | "find_definition"
+            | "find_references" | "git_status" | "git_diff" | "git_log" | "git_blame"
+            | "git_show" => ("file_read", self.file_read),
+            "invoke_agent" | "list_agents" => ("sub_agents", self.sub_agents),
+            _ => return None,
+        };
+        (!allowed).then_some(capability)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_denied_capability_full_allows_everything() {
+        let caps = AgentCapabilities::full();
+        for tool in [
+            "run_shell_command",
+            "kill_process",
+            "edit_file",
+            "delete_file",
+            "read_file",
+            "invoke_agent",
+        ] {
+            assert_eq!(
+                caps.denied_capability(tool),
+                None,
+                "{tool} should be allowed"
+            );
+        }
+    }
+
+    #[test]
+    fn test_denied_capability_read_only() {
+        let caps = AgentCapabilities::read_only();
+        assert_eq!(caps.denied_capability("run_shell_command"), Some("shell"));
+        assert_eq!(caps.denied_capability("read_process_output"), Some("shell"));
+        assert_eq!(caps.denied_capability("write_process_input"), Some("shell"));
+        assert_eq!(caps.denied_capability("edit_file"), Some("file_write"));
+        assert_eq!(caps.denied_capability("delete_file"), Some("file_write"));
+        assert_eq!(caps.denied_capability("invoke_agent"), Some("sub_agents"));
+        assert_eq!(caps.denied_capability("read_file"), None);
+        assert_eq!(caps.denied_capability("grep"), None);
+    }
+
+    #[test]
+    fn test_denied_capability_no_file_read() {
+        let caps = AgentCapabilities {
+            file_read: false,
+            ..AgentCapabilities::full()
+        };
+        assert_eq!(caps.denied_capability("list_files"), Some("file_read"));
+        assert_eq!(caps.denied_capability("find_definition"), Some("file_read"));
+        assert_eq!(caps.denied_capability("git_diff"), Some("file_read"));
+    }
+
+    #[test]
+    fn test_denied_capability_ignores_unknown_tools() {
+        let caps = AgentCapabilities::default();
+        assert_eq!(caps.denied_capability("some_plugin_tool"), None);
+    }
 }
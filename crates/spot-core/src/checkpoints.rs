@@ -0,0 +1,733 @@
+//! File-change checkpoints for undoing agent edits.
+//!
+//! Before a file tool writes or deletes a file, the executor snapshots the
+//! previous content into a [`CheckpointStore`]. Checkpoints are keyed by run
+//! and tool call and grouped into turns (one per user message), so frontends
+//! can revert a single edit or roll back whole turns without relying on git.
+
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// Identifies the tool call that made a change.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct CheckpointKey {
+    pub run_id: String,
+    pub tool_call_id: String,
+}
+
+/// Errors from reverting checkpoints.
+#[derive(Debug, Error)]
+pub enum CheckpointError {
+    #[error("Nothing to undo")]
+    NothingToUndo,
+
+    #[error("Turn {0} has no recorded file changes")]
+    UnknownTurn(usize),
+
+    #[error("No checkpoint recorded for this edit")]
+    UnknownCheckpoint,
+
+    #[error("This edit was already reverted")]
+    AlreadyReverted,
+
+    #[error("{} changed after the agent edited it; revert later edits first", .0.display())]
+    Conflict(PathBuf),
+
+    #[error("Failed to restore {}: {source}", path.display())]
+    Io {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+}
+
+/// Snapshot of a file around one tool call.
+#[derive(Debug, Clone)]
+pub struct Checkpoint {
+    pub key: CheckpointKey,
+    pub path: PathBuf,
+    /// Content before the change (`None` if the file did not exist)
+    pub before: Option<Vec<u8>>,
+    /// Content after the change (`None` if the file was deleted)
+    pub after: Option<Vec<u8>>,
+    /// Whether this change was reverted on its own
+    pub reverted: bool,
+}
+
+/// How a turn changed a file overall.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileChange {
+    Created,
+    Modified,
+    Deleted,
+}
+
+impl fmt::Display for FileChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FileChange::Created => write!(f, "created"),
+            FileChange::Modified => write!(f, "modified"),
+            FileChange::Deleted => write!(f, "deleted"),
+        }
+    }
+}
+
+/// A file touched during a turn.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TouchedFile {
+    pub path: PathBuf,
+    pub change: FileChange,
+    /// Number of tool calls that changed the file
+    pub edits: usize,
+}
+
+/// Files touched during one turn.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TurnSummary {
+    pub turn: usize,
+    pub files: Vec<TouchedFile>,
+}
+
+impl fmt::Display for TurnSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let noun = if self.files.len() == 1 {
+            "file"
+        } else {
+            "files"
+        };
+        write!(
+            f,
+            "📝 Turn {} changed {} {} (`/undo` to revert):",
+            self.turn,
+            self.files.len(),
+            noun
+        )?;
+        for file in &self.files {
+            write!(f, "\n- {} `{}`", file.change, file.path.display())?;
+            if file.edits > 1 {
+                write!(f, " ({} edits)", file.edits)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// An edit that has not been reverted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EditEntry {
+    /// Short id, `<turn>.<n>` for the n-th edit of a turn
+    pub id: String,
+    pub key: CheckpointKey,
+    pub path: PathBuf,
+    pub change: FileChange,
+}
+
+impl fmt::Display for EditEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}  {} {}", self.id, self.change, self.path.display())
+    }
+}
+
+/// Result of undoing one or more turns.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UndoReport {
+    /// Turns that were rolled back, oldest first
+    pub turns: Vec<usize>,
+    /// Files restored to their earlier content
+    pub files: Vec<PathBuf>,
+}
+
+impl fmt::Display for UndoReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.turns.first(), self.turns.last()) {
+            (Some(first), Some(last)) if first != last => {
+                write!(f, "Undid turns {}–{}", first, last)?
+            }
+            (Some(turn), _) => write!(f, "Undid turn {}", turn)?,
+            _ => write!(f, "Undid changes")?,
+        }
+        let noun = if self.files.len() == 1 {
+            "file"
+        } else {
+            "files"
+        };
+        write!(f, ": restored {} {}", self.files.len(), noun)
+    }
+}
+
+/// Checkpoints recorded during one turn.
+#[derive(Debug)]
+struct Turn {
+    number: usize,
+    checkpoints: Vec<Checkpoint>,
+}
+
+impl Turn {
+    fn active(&self) -> impl DoubleEndedIterator<Item = &Checkpoint> {
+        self.checkpoints.iter().filter(|c| !c.reverted)
+    }
+}
+
+#[derive(Default)]
+struct Inner {
+    turns: Vec<Turn>,
+    last_turn: usize,
+    last_run: u64,
+    last_call: u64,
+}
+
+impl Inner {
+    fn begin_turn(&mut self) -> usize {
+        self.last_turn += 1;
+        self.turns.push(Turn {
+            number: self.last_turn,
+            checkpoints: Vec::new(),
+        });
+        self.last_turn
+    }
+}
+
+/// In-memory store of file checkpoints for a session.
+///
+/// Cheap to clone; all clones share the same checkpoints.
+#[derive(Clone, Default)]
+pub struct CheckpointStore {
+    inner: Arc<Mutex<Inner>>,
+}
+
+impl CheckpointStore {
+    /// Create an empty store.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start a new turn and return its number (starting at 1).
+    ///
+    /// Checkpoints recorded afterwards belong to this turn.
+    pub fn begin_turn(&self) -> usize {
+        self.lock().begin_turn()
+    }
+
+    /// Number of the most recent turn, if any.
+    pub fn current_turn(&self) -> Option<usize> {
+        self.lock().turns.last().map(|t| t.number)
+    }
+
+    /// Create a recorder for one agent run, with a fresh run id.
+    pub fn recorder(&self) -> CheckpointRecorder {
+        let run_id = {
+            let mut inner = self.lock();
+            inner.last_run += 1;
+            format!("run-{}", inner.last_run)
+        };
+        CheckpointRecorder {
+            store: self.clone(),
+            run_id,
+        }
+    }
+
+    /// Summarize the files a turn changed, or `None` if it changed nothing.
+    pub fn turn_summary(&self, turn: usize) -> Option<TurnSummary> {
+        let inner = self.lock();
+        let turn = inner.turns.iter().find(|t| t.number == turn)?;
+
+        // (path, created, deleted, edits) in first-touched order
+        let mut touched: Vec<(&Path, bool, bool, usize)> = Vec::new();
+        for checkpoint in turn.active() {
+            match touched.iter_mut().find(|(p, ..)| *p == checkpoint.path) {
+                Some(entry) => {
+                    entry.2 = checkpoint.after.is_none();
+                    entry.3 += 1;
+                }
+                None => touched.push((
+                    &checkpoint.path,
+                    checkpoint.before.is_none(),
+                    checkpoint.after.is_none(),
+                    1,
+                )),
+            }
+        }
+
+        let files: Vec<TouchedFile> = touched
+            .into_iter()
+            .filter_map(|(path, created, deleted, edits)| {
+                let change = match (created, deleted) {
+                    // Created and deleted again within the turn
+                    (true, true) => return None,
+                    (true, false) => FileChange::Created,
+                    (false, true) => FileChange::Deleted,
+                    (false, false) => FileChange::Modified,
+                };
+                Some(TouchedFile {
+                    path: path.to_path_buf(),
+                    change,
+                    edits,
+                })
+            })
+            .collect();
+
+        (!files.is_empty()).then_some(TurnSummary {
+            turn: turn.number,
+            files,
+        })
+    }
+
+    /// Most recent edit that has not been reverted, optionally limited to a path.
+    pub fn last_edit(&self, path: Option<&Path>) -> Option<CheckpointKey> {
+        let inner = self.lock();
+        for turn in inner.turns.iter().rev() {
+            let edit = turn
+                .active()
+                .rev()
+                .find(|c| path.is_none_or(|p| c.path == p || c.path.ends_with(p)));
+            if let Some(checkpoint) = edit {
+                return Some(checkpoint.key.clone());
+            }
+        }
+        None
+    }
+
+    /// Edits that can still be reverted, oldest first.
+    pub fn edits(&self) -> Vec<EditEntry> {
+        let inner = self.lock();
+        inner
+            .turns
+            .iter()
+            .flat_map(|turn| {
+                turn.checkpoints
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, c)| !c.reverted)
+                    .map(|(i, c)| EditEntry {
+                        id: format!("{}.{}", turn.number, i + 1),
+                        key: c.key.clone(),
+                        path: c.path.clone(),
+                        change: match (&c.before, &c.after) {
+                            (None, _) => FileChange::Created,
+                            (_, None) => FileChange::Deleted,
+                            _ => FileChange::Modified,
+                        },
+                    })
+            })
+            .collect()
+    }
+
+    /// Key of the edit with the short id (`<turn>.<n>`) shown by [`edits`](Self::edits).
+    pub fn edit_by_id(&self, id: &str) -> Option<CheckpointKey> {
+        let (turn, n) = id.split_once('.')?;
+        let (turn, n) = (turn.parse::<usize>().ok()?, n.parse::<usize>().ok()?);
+        let inner = self.lock();
+        let turn = inner.turns.iter().find(|t| t.number == turn)?;
+        turn.checkpoints
+            .get(n.checked_sub(1)?)
+            .map(|c| c.key.clone())
+    }
+
+    /// Revert a single edit, restoring the file as it was before the tool call.
+    ///
+    /// Fails with [`CheckpointError::Conflict`] if the file changed after the
+    /// edit, so later edits are never silently discarded.
+    pub fn revert(&self, key: &CheckpointKey) -> Result<PathBuf, CheckpointError> {
+        let mut inner = self.lock();
+        let checkpoint = inner
+            .turns
+            .iter_mut()
+            .flat_map(|t| t.checkpoints.iter_mut())
+            .find(|c| c.key == *key)
+            .ok_or(CheckpointError::UnknownCheckpoint)?;
+
+        if checkpoint.reverted {
+            return Err(CheckpointError::AlreadyReverted);
+        }
+        if std::fs::read(&checkpoint.path).ok() != checkpoint.after {
+            return Err(CheckpointError::Conflict(checkpoint.path.clone()));
+        }
+
+        restore(&checkpoint.path, checkpoint.before.as_deref())?;
+        checkpoint.reverted = true;
+        Ok(checkpoint.path.clone())
+    }
+
+    /// Undo file changes, rewinding the workspace to before a turn.
+    ///
+    /// With `None`, undoes the most recent turn that changed files. With
+    /// `Some(n)`, undoes turn `n` and every turn after it. Undone turns are
+    /// removed from the store. Nothing is restored if any file changed after
+    /// the agent last edited it.
+    pub fn undo(&self, turn: Option<usize>) -> Result<UndoReport, CheckpointError> {
+        let mut inner = self.lock();
+        let start = match turn {
+            Some(number) => inner
+                .turns
+                .iter()
+                .position(|t| t.number == number)
+                .ok_or(CheckpointError::UnknownTurn(number))?,
+            None => inner
+                .turns
+                .iter()
+                .rposition(|t| t.active().next().is_some())
+                .ok_or(CheckpointError::NothingToUndo)?,
+        };
+
+        let undone = &inner.turns[start..];
+        let checkpoints: Vec<&Checkpoint> = undone.iter().flat_map(|t| t.active()).collect();
+        if checkpoints.is_empty() {
+            return Err(CheckpointError::NothingToUndo);
+        }
+
+        // Check every file is still as the agent left it before touching any
+        let mut files: Vec<PathBuf> = Vec::new();
+        for checkpoint in checkpoints.iter().rev() {
+            if files.contains(&checkpoint.path) {
+                continue;
+            }
+            if std::fs::read(&checkpoint.path).ok() != checkpoint.after {
+                return Err(CheckpointError::Conflict(checkpoint.path.clone()));
+            }
+            files.push(checkpoint.path.clone());
+        }
+
+        for checkpoint in checkpoints.iter().rev() {
+            restore(&checkpoint.path, checkpoint.before.as_deref())?;
+        }
+
+        files.reverse();
+        let turns = undone.iter().map(|t| t.number).collect();
+        inner.turns.truncate(start);
+        Ok(UndoReport { turns, files })
+    }
+
+    fn record(
+        &self,
+        run_id: &str,
+        tool_call_id: Option<&str>,
+        path: PathBuf,
+        before: Option<Vec<u8>>,
+        after: Option<Vec<u8>>,
+    ) -> CheckpointKey {
+        let mut inner = self.lock();
+        if inner.turns.is_empty() {
+            inner.begin_turn();
+        }
+
+        let tool_call_id = match tool_call_id {
+            Some(id) => id.to_string(),
+            None => {
+                inner.last_call += 1;
+                format!("call-{}", inner.last_call)
+            }
+        };
+        let key = CheckpointKey {
+            run_id: run_id.to_string(),
+            tool_call_id,
+        };
+
+        if let Some(turn) = inner.turns.last_mut() {
+            turn.checkpoints.push(Checkpoint {
+                key: key.clone(),
+                path,
+                before,
+                after,
+                reverted: false,
+            });
+        }
+        key
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Inner> {
+        // Checkpoints stay usable even if a recording thread panicked
+        self.inner.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl fmt::Debug for CheckpointStore {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let inner = self.lock();
+        f.debug_struct("CheckpointStore")
+            .field("turns", &inner.turns.len())
+            .finish_non_exhaustive()
+    }
+}
+
+/// Records checkpoints for one agent run.
+#[derive(Debug, Clone)]
+pub struct CheckpointRecorder {
+    store: CheckpointStore,
+    run_id: String,
+}
+
+impl CheckpointRecorder {
+    /// Id of the run this recorder belongs to.
+    pub fn run_id(&self) -> &str {
+        &self.run_id
+    }
+
+    /// Record that a tool call changed `path` from `before` to `after`.
+    ///
+    /// Calls without an id get a generated one.
+    pub fn record(
+        &self,
+        tool_call_id: Option<&str>,
+        path: impl Into<PathBuf>,
+        before: Option<Vec<u8>>,
+        after: Option<Vec<u8>>,
+    ) -> CheckpointKey {
+        self.store
+            .record(&self.run_id, tool_call_id, path.into(), before, after)
+    }
+}
+
+/// Write `content` back to `path`, or remove the file if it did not exist.
+fn restore(path: &Path, content: Option<&[u8]>) -> Result<(), CheckpointError> {
+    let result = match content {
+        Some(bytes) => path
+            .parent()
+            .filter(|parent| !parent.as_os_str().is_empty())
+            .map_or(Ok(()), std::fs::create_dir_all)
+            .and_then(|()| std::fs::write(path, bytes)),
+        None => match std::fs::remove_file(path) {
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            other => other,
+        },
+    };
+    result.map_err(|source| CheckpointError::Io {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    /// Write `after` to `path` and record the change like the executor does.
+    fn edit(recorder: &CheckpointRecorder, call: &str, path: &Path, after: Option<&str>) {
+        let before = std::fs::read(path).ok();
+        match after {
+            Some(content) => std::fs::write(path, content).unwrap(),
+            None => std::fs::remove_file(path).unwrap(),
+        }
+        recorder.record(
+            Some(call),
+            path,
+            before,
+            after.map(|s| s.as_bytes().to_vec()),
+        );
+    }
+
+    fn read(path: &Path) -> Option<String> {
+        std::fs::read_to_string(path).ok()
+    }
+
+    #[test]
+    fn test_revert_single_edit() {
+        let temp = TempDir::new().unwrap();
+        let file = temp.path().join("a.txt");
+        std::fs::write(&file, "one\n").unwrap();
+
+        let store = CheckpointStore::new();
+        store.begin_turn();
+        let recorder = store.recorder();
+        edit(&recorder, "call-a", &file, Some("two\n"));
+
+        let key = store.last_edit(None).unwrap();
+        assert_eq!(key.run_id, recorder.run_id());
+        assert_eq!(key.tool_call_id, "call-a");
+
+        assert_eq!(store.revert(&key).unwrap(), file);
+        assert_eq!(read(&file).as_deref(), Some("one\n"));
+        assert!(matches!(
+            store.revert(&key),
+            Err(CheckpointError::AlreadyReverted)
+        ));
+        assert!(store.last_edit(None).is_none());
+    }
+
+    #[test]
+    fn test_revert_refuses_to_discard_later_edits() {
+        let temp = TempDir::new().unwrap();
+        let file = temp.path().join("a.txt");
+        std::fs::write(&file, "v0").unwrap();
+
+        let store = CheckpointStore::new();
+        store.begin_turn();
+        let recorder = store.recorder();
+        edit(&recorder, "first", &file, Some("v1"));
+        edit(&recorder, "second", &file, Some("v2"));
+
+        let first = CheckpointKey {
+            run_id: recorder.run_id().to_string(),
+            tool_call_id: "first".to_string(),
+        };
+        assert!(matches!(
+            store.revert(&first),
+            Err(CheckpointError::Conflict(_))
+        ));
+        assert_eq!(read(&file).as_deref(), Some("v2"));
+
+        // Reverting in reverse order works
+        store
+            .revert(&store.last_edit(Some(&file)).unwrap())
+            .unwrap();
+        store.revert(&first).unwrap();
+        assert_eq!(read(&file).as_deref(), Some("v0"));
+    }
+
+    #[test]
+    fn test_edits_are_listed_by_short_id() {
+        let temp = TempDir::new().unwrap();
+        let a = temp.path().join("a.txt");
+        let b = temp.path().join("b.txt");
+        std::fs::write(&a, "v0").unwrap();
+
+        let store = CheckpointStore::new();
+        store.begin_turn();
+        let recorder = store.recorder();
+        edit(&recorder, "first", &a, Some("v1"));
+        store.begin_turn();
+        let recorder = store.recorder();
+        edit(&recorder, "second", &b, Some("new"));
+        edit(&recorder, "third", &a, None);
+
+        let edits = store.edits();
+        let ids: Vec<&str> = edits.iter().map(|e| e.id.as_str()).collect();
+        assert_eq!(ids, ["1.1", "2.1", "2.2"]);
+        assert_eq!(edits[1].change, FileChange::Created);
+        assert_eq!(edits[2].change, FileChange::Deleted);
+        assert_eq!(
+            edits[0].to_string(),
+            format!("1.1  modified {}", a.display())
+        );
+
+        // Ids stay the same after an edit is reverted
+        let key = store.edit_by_id("2.2").unwrap();
+        assert_eq!(key.tool_call_id, "third");
+        store.revert(&key).unwrap();
+        let ids: Vec<String> = store.edits().into_iter().map(|e| e.id).collect();
+        assert_eq!(ids, ["1.1", "2.1"]);
+        assert_eq!(
+            store.edit_by_id("2.1").map(|k| k.tool_call_id),
+            Some("second".to_string())
+        );
+
+        assert!(store.edit_by_id("2.3").is_none());
+        assert!(store.edit_by_id("2.0").is_none());
+        assert!(store.edit_by_id("a.txt").is_none());
+    }
+
+    #[test]
+    fn test_undo_last_turn_restores_created_modified_and_deleted_files() {
+        let temp = TempDir::new().unwrap();
+        let modified = temp.path().join("modified.txt");
+        let created = temp.path().join("created.txt");
+        let deleted = temp.path().join("deleted.txt");
+        std::fs::write(&modified, "old").unwrap();
+        std::fs::write(&deleted, "keep me").unwrap();
+
+        let store = CheckpointStore::new();
+        let turn = store.begin_turn();
+        let recorder = store.recorder();
+        edit(&recorder, "1", &modified, Some("new"));
+        edit(&recorder, "2", &created, Some("fresh"));
+        edit(&recorder, "3", &deleted, None);
+        edit(&recorder, "4", &modified, Some("newer"));
+
+        let summary = store.turn_summary(turn).unwrap();
+        assert_eq!(
+            summary.files,
+            vec![
+                TouchedFile {
+                    path: modified.clone(),
+                    change: FileChange::Modified,
+                    edits: 2,
+                },
+                TouchedFile {
+                    path: created.clone(),
+                    change: FileChange::Created,
+                    edits: 1,
+                },
+                TouchedFile {
+                    path: deleted.clone(),
+                    change: FileChange::Deleted,
+                    edits: 1,
+                },
+            ]
+        );
+        assert!(summary.to_string().starts_with("📝 Turn 1 changed 3 files"));
+
+        let report = store.undo(None).unwrap();
+        assert_eq!(report.turns, vec![turn]);
+        assert_eq!(report.files.len(), 3);
+        assert_eq!(read(&modified).as_deref(), Some("old"));
+        assert_eq!(read(&created), None);
+        assert_eq!(read(&deleted).as_deref(), Some("keep me"));
+
+        assert!(store.turn_summary(turn).is_none());
+        assert!(matches!(
+            store.undo(None),
+            Err(CheckpointError::NothingToUndo)
+        ));
+    }
+
+    #[test]
+    fn test_undo_turn_rewinds_later_turns() {
+        let temp = TempDir::new().unwrap();
+        let file = temp.path().join("a.txt");
+        std::fs::write(&file, "v0").unwrap();
+
+        let store = CheckpointStore::new();
+        for (turn, content) in ["v1", "v2", "v3"].iter().enumerate() {
+            store.begin_turn();
+            edit(&store.recorder(), &turn.to_string(), &file, Some(content));
+        }
+
+        let report = store.undo(Some(2)).unwrap();
+        assert_eq!(report.turns, vec![2, 3]);
+        assert_eq!(report.to_string(), "Undid turns 2–3: restored 1 file");
+        assert_eq!(read(&file).as_deref(), Some("v1"));
+        assert_eq!(store.current_turn(), Some(1));
+
+        assert!(matches!(
+            store.undo(Some(3)),
+            Err(CheckpointError::UnknownTurn(3))
+        ));
+        // Turn numbers keep increasing after an undo
+        assert_eq!(store.begin_turn(), 4);
+    }
+
+    #[test]
+    fn test_undo_refuses_when_file_changed_outside_the_agent() {
+        let temp = TempDir::new().unwrap();
+        let file = temp.path().join("a.txt");
+        std::fs::write(&file, "v0").unwrap();
+
+        let store = CheckpointStore::new();
+        store.begin_turn();
+        edit(&store.recorder(), "1", &file, Some("v1"));
+        std::fs::write(&file, "hand edit").unwrap();
+
+        assert!(matches!(
+            store.undo(None),
+            Err(CheckpointError::Conflict(path)) if path == file
+        ));
+        assert_eq!(read(&file).as_deref(), Some("hand edit"));
+    }
+
+    #[test]
+    fn test_record_without_turn_or_call_id() {
+        let store = CheckpointStore::new();
+        let recorder = store.recorder();
+        let first = recorder.record(None, "a.txt", None, Some(b"x".to_vec()));
+        let second = recorder.record(None, "a.txt", Some(b"x".to_vec()), None);
+
+        assert_eq!(store.current_turn(), Some(1));
+        assert_ne!(first, second);
+        // Created then deleted within the turn: nothing to report
+        assert!(store.turn_summary(1).is_none());
+    }
+}
@@ -22,6 +22,9 @@ pub const COMPRESSION_TARGET_TOKENS: &str = "compression.target_tokens";
 pub const VDI_MODE: &str = "vdi.mode";
 pub const VDI_FRAME_INTERVAL_MS: &str = "vdi.frame_interval_ms";
 
+// Command permission settings
+pub const ALLOWED_COMMANDS: &str = "permissions.allowed_commands";
+
 // Update check settings
 pub const UPDATE_CHECK_ENABLED: &str = "update_check.enabled";
 pub const UPDATE_CHECK_LAST_CHECK: &str = "update_check.last_check";
@@ -36,6 +39,8 @@ pub const LOG_FILE_ENABLED: &str = "log.file_enabled";
 // Agent-scoped key prefixes
 const AGENT_PIN_PREFIX: &str = "agent_pin.";
 const AGENT_MCP_PREFIX: &str = "agent_mcp.";
+const AGENT_MCP_ALLOW_PREFIX: &str = "agent_mcp_allow.";
+const AGENT_MCP_DENY_PREFIX: &str = "agent_mcp_deny.";
 
 /// Build the settings key for an agent's pinned model.
 pub fn agent_pin_key(agent_name: &str) -> String {
@@ -47,6 +52,16 @@ pub fn agent_mcp_key(agent_name: &str) -> String {
     format!("{}{}", AGENT_MCP_PREFIX, agent_name)
 }
 
+/// Build the settings key for an agent's allowed MCP tools.
+pub fn agent_mcp_allow_key(agent_name: &str) -> String {
+    format!("{}{}", AGENT_MCP_ALLOW_PREFIX, agent_name)
+}
+
+/// Build the settings key for an agent's denied MCP tools.
+pub fn agent_mcp_deny_key(agent_name: &str) -> String {
+    format!("{}{}", AGENT_MCP_DENY_PREFIX, agent_name)
+}
+
 /// Get the prefix used for agent pin keys (for LIKE queries).
 pub fn agent_pin_prefix() -> &'static str {
     AGENT_PIN_PREFIX
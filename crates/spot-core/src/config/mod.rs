@@ -7,7 +7,10 @@ mod settings;
 pub mod typed_config;
 mod vdi;
 
-pub use settings::{PdfMode, Settings};
+pub use settings::{PdfMode, Settings, SettingsError};
 pub use typed_config::{project_config_path, user_config_path};
-pub use typed_config::{CompressionConfig, ConfigValidationError, SpotConfig, VdiConfig};
+pub use typed_config::{
+    CommandAction, CommandRule, CompressionConfig, ConfigValidationError, FileRule, FilesConfig,
+    PermissionsConfig, ResilienceConfig, SpotConfig, VdiConfig,
+};
 pub use vdi::{detect_vdi_environment, is_vdi_mode_active};
@@ -5,6 +5,7 @@ use std::collections::HashMap;
 use super::keys;
 use crate::agents::UserMode;
 use crate::db::Database;
+use crate::mcp::McpToolFilter;
 use thiserror::Error;
 
 /// PDF processing mode for attachments
@@ -141,6 +142,44 @@ impl<'a> Settings<'a> {
         self.set(keys::YOLO_MODE, if enabled { "true" } else { "false" })
     }
 
+    /// Get the shell commands the user chose to always allow.
+    ///
+    /// Stored as a JSON array so commands may contain any character.
+    pub fn allowed_commands(&self) -> Vec<String> {
+        self.get(keys::ALLOWED_COMMANDS)
+            .ok()
+            .flatten()
+            .and_then(|v| serde_json::from_str(&v).ok())
+            .unwrap_or_default()
+    }
+
+    /// Always allow a shell command (matched exactly, ignoring surrounding whitespace).
+    pub fn add_allowed_command(&self, command: &str) -> Result<(), SettingsError> {
+        let command = command.trim().to_string();
+        let mut commands = self.allowed_commands();
+        if !commands.contains(&command) {
+            commands.push(command);
+            self.set_allowed_commands(&commands)?;
+        }
+        Ok(())
+    }
+
+    /// Stop always allowing a shell command.
+    pub fn remove_allowed_command(&self, command: &str) -> Result<(), SettingsError> {
+        let command = command.trim();
+        let commands: Vec<String> = self
+            .allowed_commands()
+            .into_iter()
+            .filter(|c| c != command)
+            .collect();
+        self.set_allowed_commands(&commands)
+    }
+
+    fn set_allowed_commands(&self, commands: &[String]) -> Result<(), SettingsError> {
+        let value = serde_json::to_string(commands).expect("string list serializes");
+        self.set(keys::ALLOWED_COMMANDS, &value)
+    }
+
     /// Get the assistant name.
     pub fn assistant_name(&self) -> String {
         self.get_or(keys::ASSISTANT_NAME, "Spot")
@@ -263,16 +302,7 @@ impl<'a> Settings<'a> {
 
     /// Get the MCPs attached to an agent (comma-separated list stored as single value).
     pub fn get_agent_mcps(&self, agent_name: &str) -> Vec<String> {
-        self.get(&Self::agent_mcp_key(agent_name))
-            .ok()
-            .flatten()
-            .map(|s| {
-                s.split(',')
-                    .map(|m| m.trim().to_string())
-                    .filter(|m| !m.is_empty())
-                    .collect()
-            })
-            .unwrap_or_default()
+        self.get_list(&Self::agent_mcp_key(agent_name))
     }
 
     /// Set the MCPs attached to an agent.
@@ -310,6 +340,57 @@ impl<'a> Settings<'a> {
         self.delete(&Self::agent_mcp_key(agent_name))
     }
 
+    /// Get the per-tool MCP allow/deny lists for an agent.
+    ///
+    /// Entries are exposed tool names (`server__tool`, or `server__*`).
+    pub fn get_agent_mcp_tool_filter(&self, agent_name: &str) -> McpToolFilter {
+        McpToolFilter {
+            allow: self.get_list(&keys::agent_mcp_allow_key(agent_name)),
+            deny: self.get_list(&keys::agent_mcp_deny_key(agent_name)),
+        }
+    }
+
+    /// Set the MCP tools an agent may use; an empty list allows all tools.
+    pub fn set_agent_mcp_allowed_tools(
+        &self,
+        agent_name: &str,
+        tool_names: &[String],
+    ) -> Result<(), SettingsError> {
+        self.set_list(&keys::agent_mcp_allow_key(agent_name), tool_names)
+    }
+
+    /// Set the MCP tools an agent may never use.
+    pub fn set_agent_mcp_denied_tools(
+        &self,
+        agent_name: &str,
+        tool_names: &[String],
+    ) -> Result<(), SettingsError> {
+        self.set_list(&keys::agent_mcp_deny_key(agent_name), tool_names)
+    }
+
+    /// Read a comma-separated list setting.
+    fn get_list(&self, key: &str) -> Vec<String> {
+        self.get(key)
+            .ok()
+            .flatten()
+            .map(|s| {
+                s.split(',')
+                    .map(|m| m.trim().to_string())
+                    .filter(|m| !m.is_empty())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Write a comma-separated list setting, deleting it when empty.
+    fn set_list(&self, key: &str, values: &[String]) -> Result<(), SettingsError> {
+        if values.is_empty() {
+            self.delete(key)
+        } else {
+            self.set(key, &values.join(","))
+        }
+    }
+
     /// Get all agent->MCPs mappings.
     pub fn get_all_agent_mcps(&self) -> Result<HashMap<String, Vec<String>>, SettingsError> {
         let prefix = keys::agent_mcp_prefix();
@@ -674,6 +755,21 @@ mod tests {
         assert!(settings.yolo_mode());
     }
 
+    #[test]
+    fn test_allowed_commands_roundtrip() {
+        let (_temp, db) = setup_test_db();
+        let settings = Settings::new(&db);
+
+        assert!(settings.allowed_commands().is_empty());
+        settings.add_allowed_command("  cargo test ").unwrap();
+        settings.add_allowed_command("cargo test").unwrap();
+        settings.add_allowed_command("echo a,b").unwrap();
+        assert_eq!(settings.allowed_commands(), vec!["cargo test", "echo a,b"]);
+
+        settings.remove_allowed_command("cargo test").unwrap();
+        assert_eq!(settings.allowed_commands(), vec!["echo a,b"]);
+    }
+
     #[test]
     fn test_assistant_name_default() {
         let (_temp, db) = setup_test_db();
@@ -1300,6 +1396,49 @@ mod tests {
         assert_eq!(settings.get_agent_mcps("agent_b"), vec!["mcp_b"]);
     }
 
+    #[test]
+    fn test_agent_mcp_tool_filter_roundtrip() {
+        let (_temp, db) = setup_test_db();
+        let settings = Settings::new(&db);
+
+        assert!(settings.get_agent_mcp_tool_filter("spot").is_empty());
+
+        settings
+            .set_agent_mcp_allowed_tools("spot", &["github__*".to_string()])
+            .unwrap();
+        settings
+            .set_agent_mcp_denied_tools(
+                "spot",
+                &[
+                    "github__delete_repo".to_string(),
+                    "fs__write_file".to_string(),
+                ],
+            )
+            .unwrap();
+
+        let filter = settings.get_agent_mcp_tool_filter("spot");
+        assert_eq!(filter.allow, vec!["github__*"]);
+        assert_eq!(filter.deny, vec!["github__delete_repo", "fs__write_file"]);
+        assert!(settings.get_agent_mcp_tool_filter("other").is_empty());
+
+        // Clearing a list removes the setting
+        settings.set_agent_mcp_allowed_tools("spot", &[]).unwrap();
+        assert!(settings.get_agent_mcp_tool_filter("spot").allow.is_empty());
+    }
+
+    #[test]
+    fn test_agent_mcp_tool_filter_separate_from_attachments() {
+        let (_temp, db) = setup_test_db();
+        let settings = Settings::new(&db);
+
+        settings
+            .set_agent_mcp_denied_tools("spot", &["fs__write_file".to_string()])
+            .unwrap();
+
+        assert!(settings.get_agent_mcps("spot").is_empty());
+        assert!(settings.get_all_agent_mcps().unwrap().is_empty());
+    }
+
     #[test]
     fn test_overwrite_agent_pinned_model() {
         let (_temp, db) = setup_test_db();
@@ -11,12 +11,14 @@
 //! Later layers override earlier ones, but only for fields that are explicitly set.
 //! The existing SQLite-based `Settings` struct continues to work unchanged.
 
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 
 use serde::{Deserialize, Serialize};
 
 use super::settings::{PdfMode, Settings};
 use crate::agents::UserMode;
+use crate::terminal::{FileOperation, RiskLevel};
 
 // ─────────────────────────────────────────────────────────────────────────────
 // Core config structs
@@ -33,7 +35,8 @@ pub struct SpotConfig {
     /// User experience level (normal / expert / developer).
     pub user_mode: UserMode,
 
-    /// Auto-accept shell commands without confirmation.
+    /// Auto-accept shell commands without confirmation (high-risk commands
+    /// are still denied).
     pub yolo_mode: bool,
 
     /// PDF processing mode (image / text).
@@ -59,6 +62,15 @@ pub struct SpotConfig {
 
     /// VDI (Virtual Desktop Infrastructure) settings.
     pub vdi: VdiConfig,
+
+    /// Retry, circuit breaker and fallback settings for model requests.
+    pub resilience: ResilienceConfig,
+
+    /// Allow / ask / deny rules for shell commands requested by agents.
+    pub permissions: PermissionsConfig,
+
+    /// File listing and search settings.
+    pub files: FilesConfig,
 }
 
 /// Context-compression configuration.
@@ -89,6 +101,110 @@ pub struct VdiConfig {
     pub frame_interval_ms: u64,
 }
 
+/// Retry and fallback configuration for model requests.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ResilienceConfig {
+    /// Retries for transient (5xx / network) errors before giving up on a model.
+    pub max_retries: u32,
+
+    /// Delay before the first retry in milliseconds; doubles on each attempt.
+    pub base_delay_ms: u64,
+
+    /// Fallback model to use when a model's provider keeps failing
+    /// (model name -> fallback model name).
+    pub fallbacks: HashMap<String, String>,
+}
+
+/// Settings for the `list_files` and `grep` tools.
+///
+/// Both honor `.gitignore` and `.ignore` files; `ignore` adds patterns on top:
+///
+/// ```toml
+/// [files]
+/// ignore = ["*.generated.ts", "fixtures/"]
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FilesConfig {
+    /// Extra `.gitignore`-style patterns, matched in the listed or searched
+    /// directory. Patterns from all layers are combined.
+    pub ignore: Vec<String>,
+}
+
+/// Shell command and file-change permission rules.
+///
+/// ```toml
+/// [[permissions.rules]]
+/// action = "allow"
+/// command = "cargo *"
+///
+/// [[permissions.rules]]
+/// action = "deny"
+/// command = "git push*"
+///
+/// [[permissions.file_rules]]
+/// action = "deny"
+/// path = "**/.env"
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PermissionsConfig {
+    /// Rules in evaluation order; the first matching rule decides.
+    /// Project rules come before user rules; `allow` rules in project config
+    /// are ignored.
+    pub rules: Vec<CommandRule>,
+
+    /// Rules for file writes and deletes, in the same order.
+    pub file_rules: Vec<FileRule>,
+}
+
+/// What to do with a shell command that matches a rule.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CommandAction {
+    /// Run without asking.
+    Allow,
+    /// Ask the user first.
+    Ask,
+    /// Refuse to run.
+    Deny,
+}
+
+/// A single permission rule. Unset criteria match any command.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CommandRule {
+    /// Action to take when the rule matches.
+    pub action: CommandAction,
+
+    /// Glob matched against the whole command (e.g. `"cargo *"`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub command: Option<String>,
+
+    /// Glob matched against the working directory (e.g. `"/home/me/work/**"`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cwd: Option<String>,
+
+    /// Risk level the command must have (`low` / `medium` / `high`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub risk: Option<RiskLevel>,
+}
+
+/// A permission rule for file writes and deletes. Unset criteria match any change.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FileRule {
+    /// Action to take when the rule matches.
+    pub action: CommandAction,
+
+    /// Glob matched against the absolute file path (e.g. `"/home/me/work/**"`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+
+    /// Operation the change must be (`write` / `delete`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub operation: Option<FileOperation>,
+}
+
 // ─────────────────────────────────────────────────────────────────────────────
 // Serde helpers for PdfMode
 // ─────────────────────────────────────────────────────────────────────────────
@@ -123,6 +239,9 @@ impl Default for SpotConfig {
             update_check_enabled: true,
             compression: CompressionConfig::default(),
             vdi: VdiConfig::default(),
+            resilience: ResilienceConfig::default(),
+            permissions: PermissionsConfig::default(),
+            files: FilesConfig::default(),
         }
     }
 }
@@ -147,6 +266,16 @@ impl Default for VdiConfig {
     }
 }
 
+impl Default for ResilienceConfig {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            base_delay_ms: 1000,
+            fallbacks: HashMap::new(),
+        }
+    }
+}
+
 // ─────────────────────────────────────────────────────────────────────────────
 // Overlay (partial config for layered merging)
 // ─────────────────────────────────────────────────────────────────────────────
@@ -167,6 +296,9 @@ struct SpotConfigOverlay {
     pub update_check_enabled: Option<bool>,
     pub compression: Option<CompressionOverlay>,
     pub vdi: Option<VdiOverlay>,
+    pub resilience: Option<ResilienceOverlay>,
+    pub permissions: Option<PermissionsOverlay>,
+    pub files: Option<FilesOverlay>,
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
@@ -185,6 +317,27 @@ struct VdiOverlay {
     pub frame_interval_ms: Option<u64>,
 }
 
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+struct ResilienceOverlay {
+    pub max_retries: Option<u32>,
+    pub base_delay_ms: Option<u64>,
+    pub fallbacks: Option<HashMap<String, String>>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+struct PermissionsOverlay {
+    pub rules: Option<Vec<CommandRule>>,
+    pub file_rules: Option<Vec<FileRule>>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+struct FilesOverlay {
+    pub ignore: Option<Vec<String>>,
+}
+
 impl SpotConfigOverlay {
     /// Apply this overlay on top of an existing config, returning a new config.
     fn apply_to(self, mut base: SpotConfig) -> SpotConfig {
@@ -234,6 +387,39 @@ impl SpotConfigOverlay {
                 base.vdi.frame_interval_ms = v;
             }
         }
+        if let Some(overlay) = self.resilience {
+            if let Some(v) = overlay.max_retries {
+                base.resilience.max_retries = v;
+            }
+            if let Some(v) = overlay.base_delay_ms {
+                base.resilience.base_delay_ms = v;
+            }
+            if let Some(v) = overlay.fallbacks {
+                // Later layers add to (and override) earlier fallback mappings
+                base.resilience.fallbacks.extend(v);
+            }
+        }
+        if let Some(overlay) = self.permissions {
+            if let Some(mut v) = overlay.rules {
+                // Later (more specific) layers are evaluated first
+                v.append(&mut base.permissions.rules);
+                base.permissions.rules = v;
+            }
+            if let Some(mut v) = overlay.file_rules {
+                v.append(&mut base.permissions.file_rules);
+                base.permissions.file_rules = v;
+            }
+        }
+        if let Some(overlay) = self.files {
+            if let Some(v) = overlay.ignore {
+                // Later layers add to earlier ignore patterns
+                for pattern in v {
+                    if !base.files.ignore.contains(&pattern) {
+                        base.files.ignore.push(pattern);
+                    }
+                }
+            }
+        }
         base
     }
 }
@@ -309,6 +495,48 @@ impl SpotConfig {
             });
         }
 
+        for (model, fallback) in &self.resilience.fallbacks {
+            if model == fallback {
+                errors.push(ConfigValidationError {
+                    field: "resilience.fallbacks".into(),
+                    message: format!("model {:?} cannot fall back to itself", model),
+                });
+            }
+        }
+
+        for (i, rule) in self.permissions.rules.iter().enumerate() {
+            for (name, pattern) in [("command", &rule.command), ("cwd", &rule.cwd)] {
+                if let Some(pattern) = pattern {
+                    if let Err(e) = globset::Glob::new(pattern) {
+                        errors.push(ConfigValidationError {
+                            field: format!("permissions.rules[{}].{}", i, name),
+                            message: format!("invalid glob {:?}: {}", pattern, e),
+                        });
+                    }
+                }
+            }
+        }
+
+        for (i, rule) in self.permissions.file_rules.iter().enumerate() {
+            if let Some(ref pattern) = rule.path {
+                if let Err(e) = globset::Glob::new(pattern) {
+                    errors.push(ConfigValidationError {
+                        field: format!("permissions.file_rules[{}].path", i),
+                        message: format!("invalid glob {:?}: {}", pattern, e),
+                    });
+                }
+            }
+        }
+
+        for (i, pattern) in self.files.ignore.iter().enumerate() {
+            if let Err(e) = globset::Glob::new(pattern) {
+                errors.push(ConfigValidationError {
+                    field: format!("files.ignore[{}]", i),
+                    message: format!("invalid pattern {:?}: {}", pattern, e),
+                });
+            }
+        }
+
         if !(16..=500).contains(&self.vdi.frame_interval_ms) {
             errors.push(ConfigValidationError {
                 field: "vdi.frame_interval_ms".into(),
@@ -353,6 +581,45 @@ fn load_overlay_from_file(path: &Path) -> Result<Option<SpotConfigOverlay>, Stri
     Ok(Some(overlay))
 }
 
+/// Parse the project config file into an overlay.
+///
+/// Like [`load_overlay_from_file`], but `allow` permission rules and
+/// `yolo_mode` are dropped: a checked-out repository may add `ask` and `deny`
+/// rules, which are evaluated before the user's, but must not approve
+/// commands or file changes on the user's behalf.
+fn load_project_overlay(path: &Path) -> Result<Option<SpotConfigOverlay>, String> {
+    let Some(mut overlay) = load_overlay_from_file(path)? else {
+        return Ok(None);
+    };
+    if overlay.yolo_mode.take().is_some() {
+        tracing::warn!(
+            "Ignoring yolo_mode in {}; only user config may enable it",
+            path.display()
+        );
+    }
+    if let Some(ref mut permissions) = overlay.permissions {
+        let mut dropped = 0;
+        if let Some(ref mut rules) = permissions.rules {
+            let len = rules.len();
+            rules.retain(|rule| rule.action != CommandAction::Allow);
+            dropped += len - rules.len();
+        }
+        if let Some(ref mut rules) = permissions.file_rules {
+            let len = rules.len();
+            rules.retain(|rule| rule.action != CommandAction::Allow);
+            dropped += len - rules.len();
+        }
+        if dropped > 0 {
+            tracing::warn!(
+                "Ignoring {} allow rule(s) in {}; project config may only ask or deny",
+                dropped,
+                path.display()
+            );
+        }
+    }
+    Ok(Some(overlay))
+}
+
 /// Build an overlay from environment variables.
 ///
 /// Supported variables:
@@ -370,6 +637,7 @@ fn load_overlay_from_file(path: &Path) -> Result<Option<SpotConfigOverlay>, Stri
 /// - `SPOT_COMPRESSION_TARGET_TOKENS` (integer)
 /// - `SPOT_VDI_MODE` (true / false)
 /// - `SPOT_VDI_FRAME_INTERVAL_MS` (integer)
+/// - `SPOT_RESILIENCE_MAX_RETRIES` (integer)
 fn load_overlay_from_env() -> SpotConfigOverlay {
     let mut overlay = SpotConfigOverlay::default();
 
@@ -444,6 +712,16 @@ fn load_overlay_from_env() -> SpotConfigOverlay {
         overlay.vdi = Some(vdi);
     }
 
+    // Resilience env vars
+    if let Ok(v) = std::env::var("SPOT_RESILIENCE_MAX_RETRIES") {
+        if let Ok(n) = v.parse::<u32>() {
+            overlay.resilience = Some(ResilienceOverlay {
+                max_retries: Some(n),
+                ..Default::default()
+            });
+        }
+    }
+
     overlay
 }
 
@@ -566,7 +844,7 @@ impl SpotConfig {
 
         // Layer 3: project-level TOML
         if let Some(path) = project_config_path() {
-            match load_overlay_from_file(&path) {
+            match load_project_overlay(&path) {
                 Ok(Some(overlay)) => {
                     tracing::debug!("Loaded project config from {}", path.display());
                     config = overlay.apply_to(config);
@@ -608,7 +886,7 @@ impl SpotConfig {
         }
 
         if let Some(path) = project_config_path() {
-            match load_overlay_from_file(&path) {
+            match load_project_overlay(&path) {
                 Ok(Some(overlay)) => config = overlay.apply_to(config),
                 Ok(None) => {}
                 Err(e) => tracing::warn!("Skipping project config: {}", e),
@@ -782,6 +1060,242 @@ frame_interval_ms = 100
         assert_eq!(loaded.vdi.frame_interval_ms, 100);
     }
 
+    #[test]
+    fn test_resilience_toml_override() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("config.toml");
+        std::fs::write(
+            &path,
+            r#"
+[resilience]
+max_retries = 5
+
+[resilience.fallbacks]
+"gpt-4o" = "claude-sonnet-4"
+"#,
+        )
+        .unwrap();
+
+        let loaded = SpotConfig::load_from_file(&path).unwrap();
+        assert_eq!(loaded.resilience.max_retries, 5);
+        // Unspecified resilience fields keep defaults
+        assert_eq!(loaded.resilience.base_delay_ms, 1000);
+        assert_eq!(
+            loaded
+                .resilience
+                .fallbacks
+                .get("gpt-4o")
+                .map(String::as_str),
+            Some("claude-sonnet-4")
+        );
+    }
+
+    #[test]
+    fn test_permissions_toml_rules() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("config.toml");
+        std::fs::write(
+            &path,
+            r#"
+[[permissions.rules]]
+action = "allow"
+command = "cargo *"
+
+[[permissions.rules]]
+action = "ask"
+risk = "medium"
+cwd = "/srv/**"
+"#,
+        )
+        .unwrap();
+
+        let loaded = SpotConfig::load_from_file(&path).unwrap();
+        assert_eq!(
+            loaded.permissions.rules,
+            vec![
+                CommandRule {
+                    action: CommandAction::Allow,
+                    command: Some("cargo *".into()),
+                    cwd: None,
+                    risk: None,
+                },
+                CommandRule {
+                    action: CommandAction::Ask,
+                    command: None,
+                    cwd: Some("/srv/**".into()),
+                    risk: Some(RiskLevel::Medium),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_permissions_toml_file_rules() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("config.toml");
+        std::fs::write(
+            &path,
+            r#"
+[[permissions.file_rules]]
+action = "deny"
+path = "**/.env"
+
+[[permissions.file_rules]]
+action = "ask"
+operation = "delete"
+"#,
+        )
+        .unwrap();
+
+        let loaded = SpotConfig::load_from_file(&path).unwrap();
+        assert!(loaded.permissions.rules.is_empty());
+        assert_eq!(
+            loaded.permissions.file_rules,
+            vec![
+                FileRule {
+                    action: CommandAction::Deny,
+                    path: Some("**/.env".into()),
+                    operation: None,
+                },
+                FileRule {
+                    action: CommandAction::Ask,
+                    path: None,
+                    operation: Some(FileOperation::Delete),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_permissions_later_layer_rules_come_first() {
+        let rule = |action, command: &str| CommandRule {
+            action,
+            command: Some(command.into()),
+            cwd: None,
+            risk: None,
+        };
+        let user = SpotConfigOverlay {
+            permissions: Some(PermissionsOverlay {
+                rules: Some(vec![rule(CommandAction::Allow, "git *")]),
+                file_rules: None,
+            }),
+            ..Default::default()
+        };
+        let project = SpotConfigOverlay {
+            permissions: Some(PermissionsOverlay {
+                rules: Some(vec![rule(CommandAction::Deny, "git push*")]),
+                file_rules: None,
+            }),
+            ..Default::default()
+        };
+
+        let config = project.apply_to(user.apply_to(SpotConfig::default()));
+        assert_eq!(
+            config.permissions.rules,
+            vec![
+                rule(CommandAction::Deny, "git push*"),
+                rule(CommandAction::Allow, "git *")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_project_allow_rules_do_not_override_user_rules() {
+        let dir = TempDir::new().unwrap();
+        let user_path = dir.path().join("user.toml");
+        let project_path = dir.path().join("project.toml");
+        std::fs::write(
+            &user_path,
+            r#"
+[[permissions.rules]]
+action = "ask"
+command = "git push*"
+"#,
+        )
+        .unwrap();
+        std::fs::write(
+            &project_path,
+            r#"
+[[permissions.rules]]
+action = "allow"
+command = "git push*"
+
+[[permissions.rules]]
+action = "deny"
+command = "rm -rf*"
+
+[[permissions.file_rules]]
+action = "allow"
+path = "**"
+"#,
+        )
+        .unwrap();
+
+        let user = load_overlay_from_file(&user_path).unwrap().unwrap();
+        let project = load_project_overlay(&project_path).unwrap().unwrap();
+        let config = project.apply_to(user.apply_to(SpotConfig::default()));
+
+        let rule = |action, command: &str| CommandRule {
+            action,
+            command: Some(command.into()),
+            cwd: None,
+            risk: None,
+        };
+        assert_eq!(
+            config.permissions.rules,
+            vec![
+                rule(CommandAction::Deny, "rm -rf*"),
+                rule(CommandAction::Ask, "git push*")
+            ]
+        );
+        assert!(config.permissions.file_rules.is_empty());
+    }
+
+    #[test]
+    fn test_project_cannot_enable_yolo_mode() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("project.toml");
+        std::fs::write(&path, "yolo_mode = true\n").unwrap();
+
+        let project = load_project_overlay(&path).unwrap().unwrap();
+        assert!(project.yolo_mode.is_none());
+        let config = project.apply_to(SpotConfig::default());
+        assert!(!config.yolo_mode);
+
+        // The same setting in user config still applies
+        let user = load_overlay_from_file(&path).unwrap().unwrap();
+        assert!(user.apply_to(SpotConfig::default()).yolo_mode);
+    }
+
+    #[test]
+    fn test_files_ignore_layers_are_combined() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("config.toml");
+        std::fs::write(
+            &path,
+            r#"
+[files]
+ignore = ["*.generated.ts", "fixtures/"]
+"#,
+        )
+        .unwrap();
+
+        let loaded = SpotConfig::load_from_file(&path).unwrap();
+        assert_eq!(loaded.files.ignore, vec!["*.generated.ts", "fixtures/"]);
+
+        let project = SpotConfigOverlay {
+            files: Some(FilesOverlay {
+                ignore: Some(vec!["fixtures/".into(), "snapshots/".into()]),
+            }),
+            ..Default::default()
+        };
+        let config = project.apply_to(loaded);
+        assert_eq!(
+            config.files.ignore,
+            vec!["*.generated.ts", "fixtures/", "snapshots/"]
+        );
+    }
+
     // =====================================================================
     // Validation
     // =====================================================================
@@ -840,6 +1354,40 @@ frame_interval_ms = 100
         assert!(errors.iter().any(|e| e.field == "vdi.frame_interval_ms"));
     }
 
+    #[test]
+    fn test_validation_self_fallback() {
+        let mut cfg = SpotConfig::default();
+        cfg.resilience
+            .fallbacks
+            .insert("gpt-4o".into(), "gpt-4o".into());
+        let errors = cfg.validate();
+        assert!(errors.iter().any(|e| e.field == "resilience.fallbacks"));
+    }
+
+    #[test]
+    fn test_validation_bad_permission_glob() {
+        let mut cfg = SpotConfig::default();
+        cfg.permissions.rules.push(CommandRule {
+            action: CommandAction::Allow,
+            command: Some("cargo [".into()),
+            cwd: None,
+            risk: None,
+        });
+        let errors = cfg.validate();
+        assert!(errors
+            .iter()
+            .any(|e| e.field == "permissions.rules[0].command"));
+    }
+
+    #[test]
+    fn test_validation_bad_ignore_pattern() {
+        let mut cfg = SpotConfig::default();
+        cfg.files.ignore = vec!["fixtures/".into(), "src/[".into()];
+        let errs = cfg.validate();
+        assert_eq!(errs.len(), 1);
+        assert_eq!(errs[0].field, "files.ignore[1]");
+    }
+
     #[test]
     fn test_validation_empty_names() {
         let cfg = SpotConfig {
@@ -1003,6 +1551,7 @@ frame_interval_ms = 100
         assert!(toml_str.contains("user_mode"));
         assert!(toml_str.contains("[compression]"));
         assert!(toml_str.contains("[vdi]"));
+        assert!(toml_str.contains("[resilience]"));
     }
 
     // =====================================================================
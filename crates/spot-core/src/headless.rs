@@ -0,0 +1,554 @@
+//! Headless (non-interactive) execution mode.
+//!
+//! Runs a single prompt through an agent, streams message bus events to
+//! stdout, and exits. This is what `spot -p "..."` uses so Spot can be
+//! driven from scripts, git hooks and CI jobs.
+//!
+//! Two output formats are supported:
+//! - [`OutputFormat::Text`]: the main agent's response text on stdout, tool
+//!   activity and errors on stderr
+//! - [`OutputFormat::Json`]: every bus [`Message`] as one NDJSON line on
+//!   stdout, followed by a final `result` or `error` line
+//!
+//! Shell commands go through the same [`CommandPolicy`] as the UIs. There is
+//! nobody to ask, so commands the policy would ask about are refused.
+
+use std::fmt;
+use std::io::{self, Read, Write};
+use std::str::FromStr;
+use std::sync::Arc;
+
+use anyhow::{anyhow, Context, Result};
+use tokio::sync::mpsc;
+use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt, EnvFilter};
+
+use crate::agents::{AgentExecutor, AgentManager, RetryHandler};
+use crate::config::{CommandAction, Settings, SpotConfig};
+use crate::db::Database;
+use crate::mcp::McpManager;
+use crate::messaging::{AgentEvent, BusError, Message, MessageBus, MessageLevel, ToolStatus};
+use crate::models::ModelRegistry;
+use crate::runner::AppConfig;
+use crate::terminal::{spawn_llm_command, SystemExecRequest, SystemExecResponse, SystemExecStore};
+use crate::tools::{
+    set_global_context, CommandPolicy, FileDecision, PolicyDecision, SpotToolRegistry, ToolContext,
+};
+
+/// Exit code for a successful run.
+pub const EXIT_SUCCESS: i32 = 0;
+/// Exit code when the agent run fails (`ExecutorError`).
+pub const EXIT_EXECUTION_FAILED: i32 = 1;
+
+/// Output format for headless mode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OutputFormat {
+    /// Plain response text on stdout.
+    #[default]
+    Text,
+    /// Newline-delimited JSON messages on stdout.
+    Json,
+}
+
+impl FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "text" => Ok(Self::Text),
+            "json" | "ndjson" => Ok(Self::Json),
+            other => Err(format!(
+                "invalid output format '{}' (expected 'text' or 'json')",
+                other
+            )),
+        }
+    }
+}
+
+impl fmt::Display for OutputFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Text => write!(f, "text"),
+            Self::Json => write!(f, "json"),
+        }
+    }
+}
+
+/// Options for a single headless run.
+#[derive(Debug, Clone, Default)]
+pub struct HeadlessOptions {
+    /// The prompt to send. `-` reads the prompt from stdin.
+    pub prompt: String,
+    /// Agent to run (defaults to the current agent).
+    pub agent: Option<String>,
+    /// Model to use (defaults to the agent's pinned model, then the default model).
+    pub model: Option<String>,
+    /// How to write output to stdout.
+    pub output: OutputFormat,
+}
+
+/// Run a single prompt without a UI.
+///
+/// Returns the process exit code: [`EXIT_SUCCESS`] when the agent completes,
+/// [`EXIT_EXECUTION_FAILED`] when execution returns an `ExecutorError`.
+///
+/// # Errors
+///
+/// Returns an error for setup failures (database, unknown agent, unreadable stdin).
+pub fn run_headless(config: AppConfig, options: HeadlessOptions) -> Result<i32> {
+    // Logs go to stderr so stdout stays clean for the response
+    let default_filter = if config.verbose {
+        "trace"
+    } else if config.debug {
+        "debug"
+    } else {
+        "warn"
+    };
+    let filter =
+        EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(default_filter));
+    tracing_subscriber::registry()
+        .with(filter)
+        .with(
+            tracing_subscriber::fmt::layer()
+                .with_target(true)
+                .with_writer(io::stderr),
+        )
+        .init();
+
+    if config.debug {
+        crate::enable_debug_stream_events();
+    }
+
+    let prompt = resolve_prompt(&options.prompt)?;
+
+    let runtime = tokio::runtime::Runtime::new().context("Failed to create Tokio runtime")?;
+    runtime.block_on(run_prompt(prompt, options))
+}
+
+/// Read the prompt from stdin when `-` is given.
+fn resolve_prompt(prompt: &str) -> Result<String> {
+    let prompt = if prompt == "-" {
+        let mut buf = String::new();
+        io::stdin()
+            .read_to_string(&mut buf)
+            .context("Failed to read prompt from stdin")?;
+        buf
+    } else {
+        prompt.to_string()
+    };
+
+    if prompt.trim().is_empty() {
+        return Err(anyhow!("Prompt is empty"));
+    }
+    Ok(prompt)
+}
+
+async fn run_prompt(prompt: String, options: HeadlessOptions) -> Result<i32> {
+    #[allow(clippy::arc_with_non_send_sync)]
+    let db = Arc::new(Database::open()?);
+    db.migrate()?;
+
+    let agents = AgentManager::new();
+    let agent_name = options
+        .agent
+        .clone()
+        .unwrap_or_else(|| agents.current_name());
+    let agent = agents
+        .get(&agent_name)
+        .ok_or_else(|| anyhow!("Agent not found: {}", agent_name))?;
+
+    let model_name = {
+        let settings = Settings::new(&db);
+        options.model.clone().unwrap_or_else(|| {
+            settings
+                .get_agent_pinned_model(&agent_name)
+                .unwrap_or_else(|| settings.model())
+        })
+    };
+
+    install_tool_context(
+        CommandPolicy::load(&db),
+        SpotConfig::load(&Settings::new(&db)).files.ignore,
+    );
+
+    let model_registry = ModelRegistry::load_from_db(&db).unwrap_or_default();
+    let tool_registry = SpotToolRegistry::new();
+    let bus = MessageBus::new();
+    let mut receiver = bus.subscribe();
+    let mcp_manager = McpManager::new().with_bus(bus.sender());
+    if let Err(e) = mcp_manager.start_all().await {
+        tracing::warn!("Failed to start MCP servers: {}", e);
+    }
+
+    // Run `/server:prompt` commands and attach `@server:resource` mentions
+    let prompt = match mcp_manager.expand_input(&prompt).await {
+        Ok(prompt) => prompt,
+        Err(e) => {
+            let _ = mcp_manager.stop_all().await;
+            return Err(anyhow!("MCP: {}", e));
+        }
+    };
+
+    let mut printer = HeadlessPrinter::new(options.output, agent.name());
+
+    let executor = AgentExecutor::new(&db, &model_registry)
+        .with_retry_handler(RetryHandler::new(Arc::clone(&db)))
+        .with_bus(bus.sender());
+
+    let execution = executor.execute_with_bus(
+        agent,
+        &model_name,
+        &prompt,
+        None,
+        &tool_registry,
+        &mcp_manager,
+    );
+    tokio::pin!(execution);
+
+    // Print bus messages while the agent runs. Falling behind only skips the
+    // missed messages; stop listening once the bus is closed.
+    let mut bus_open = true;
+    let result = loop {
+        tokio::select! {
+            biased;
+
+            msg = receiver.recv(), if bus_open => match msg {
+                Ok(msg) => printer.print(&msg),
+                Err(BusError::Lagged(skipped)) => {
+                    tracing::warn!(skipped, "Headless output fell behind the message bus");
+                }
+                Err(BusError::Closed) => bus_open = false,
+            },
+            result = &mut execution => break result,
+        }
+    };
+
+    // Drain anything published after the executor returned
+    loop {
+        match receiver.try_recv() {
+            Ok(Some(msg)) => printer.print(&msg),
+            Err(BusError::Lagged(skipped)) => {
+                tracing::warn!(skipped, "Headless output fell behind the message bus");
+            }
+            Ok(None) | Err(BusError::Closed) => break,
+        }
+    }
+
+    let exit_code = match result {
+        Ok(exec_result) => {
+            printer.finish_success(&exec_result.run_id, &exec_result.output);
+            EXIT_SUCCESS
+        }
+        Err(e) => {
+            printer.finish_error(&e.to_string());
+            EXIT_EXECUTION_FAILED
+        }
+    };
+
+    if let Err(e) = mcp_manager.stop_all().await {
+        tracing::warn!("Failed to stop MCP servers: {}", e);
+    }
+    Ok(exit_code)
+}
+
+/// Install the tool context for headless runs.
+///
+/// `run_shell_command`, the process tools (including input typed into
+/// processes) and file-change approvals go through `policy`; `list_files`
+/// and `grep` skip `ignore_patterns`.
+fn install_tool_context(policy: CommandPolicy, ignore_patterns: Vec<String>) {
+    let store = Arc::new(SystemExecStore::new());
+    let (request_tx, mut request_rx) = mpsc::unbounded_channel();
+
+    let tool_ctx =
+        ToolContext::new(store.clone(), request_tx).with_ignore_patterns(ignore_patterns);
+    if set_global_context(tool_ctx).is_err() {
+        tracing::warn!("Tool context already initialized");
+        return;
+    }
+
+    tokio::spawn(async move {
+        let mut next_process_id = 1u64;
+
+        while let Some(request) = request_rx.recv().await {
+            match request {
+                SystemExecRequest::ExecuteShell {
+                    request_id,
+                    command,
+                    cwd,
+                } => {
+                    let decision = policy.evaluate(&command, cwd.as_deref());
+                    if let Some(message) = refusal_message(&decision) {
+                        tracing::info!(request_id, reason = %decision.reason, "Refusing command");
+                        store.respond(request_id, SystemExecResponse::Error { message });
+                        continue;
+                    }
+
+                    let process_id = format!("proc-{}", next_process_id);
+                    next_process_id += 1;
+                    spawn_llm_command(store.clone(), process_id.clone(), command, cwd);
+                    store.respond(request_id, SystemExecResponse::Started { process_id });
+                }
+                SystemExecRequest::KillProcess {
+                    request_id,
+                    process_id,
+                } => {
+                    store.kill(&process_id);
+                    store.respond(request_id, SystemExecResponse::Killed { process_id });
+                }
+                SystemExecRequest::ApproveFileChange { request_id, change } => {
+                    let decision = policy.evaluate_file_change(&change.path, change.operation());
+                    let response = match file_refusal_message(&decision) {
+                        None => SystemExecResponse::FileChangeApproved { edited: None },
+                        Some(message) => {
+                            tracing::info!(request_id, reason = %decision.reason, "Refusing file change");
+                            SystemExecResponse::Error { message }
+                        }
+                    };
+                    store.respond(request_id, response);
+                }
+                SystemExecRequest::ApproveProcessInput {
+                    request_id, input, ..
+                } => {
+                    let decision = policy.evaluate_input(&input);
+                    let response = match refusal_message(&decision) {
+                        None => SystemExecResponse::InputApproved,
+                        Some(message) => {
+                            tracing::info!(request_id, reason = %decision.reason, "Refusing process input");
+                            SystemExecResponse::Error { message }
+                        }
+                    };
+                    store.respond(request_id, response);
+                }
+            }
+        }
+    });
+}
+
+/// Error returned to the tool when headless mode won't run a command.
+fn refusal_message(decision: &PolicyDecision) -> Option<String> {
+    match decision.action {
+        CommandAction::Allow => None,
+        CommandAction::Deny => Some(format!("Command denied by policy: {}", decision.reason)),
+        CommandAction::Ask => Some(
+            "Command needs approval, which headless mode cannot ask for. \
+             Add an allow rule under [[permissions.rules]] in ~/.spot/config.toml \
+             or enable YOLO mode."
+                .to_string(),
+        ),
+    }
+}
+
+/// Error returned to a file tool when headless mode won't apply its change.
+fn file_refusal_message(decision: &FileDecision) -> Option<String> {
+    match decision.action {
+        CommandAction::Allow => None,
+        CommandAction::Deny => Some(format!("File change denied by policy: {}", decision.reason)),
+        CommandAction::Ask => Some(
+            "File change needs approval, which headless mode cannot ask for. \
+             Add an allow rule under [[permissions.file_rules]] in ~/.spot/config.toml \
+             or enable YOLO mode."
+                .to_string(),
+        ),
+    }
+}
+
+/// Writes bus messages to stdout/stderr in the selected format.
+struct HeadlessPrinter {
+    format: OutputFormat,
+    agent_name: String,
+    /// Whether the last text written to stdout ended with a newline.
+    at_line_start: bool,
+}
+
+impl HeadlessPrinter {
+    fn new(format: OutputFormat, agent_name: &str) -> Self {
+        Self {
+            format,
+            agent_name: agent_name.to_string(),
+            at_line_start: true,
+        }
+    }
+
+    fn print(&mut self, msg: &Message) {
+        match self.format {
+            OutputFormat::Json => {
+                if let Ok(line) = serde_json::to_string(msg) {
+                    self.write_stdout(&format!("{}\n", line));
+                }
+            }
+            OutputFormat::Text => self.print_text(msg),
+        }
+    }
+
+    fn print_text(&mut self, msg: &Message) {
+        match msg {
+            // Only the main agent's text is the answer; sub-agent output is progress
+            Message::TextDelta(delta) => {
+                let from = delta.agent_name.as_deref().unwrap_or(&self.agent_name);
+                if from == self.agent_name {
+                    self.write_stdout(&delta.text);
+                }
+            }
+            Message::Tool(tool) if tool.status == ToolStatus::Executing => {
+                eprintln!("→ {}", tool.tool_name);
+            }
+            Message::Tool(tool) if tool.status == ToolStatus::Failed => {
+                eprintln!(
+                    "✗ {}: {}",
+                    tool.tool_name,
+                    tool.error.as_deref().unwrap_or("failed")
+                );
+            }
+            Message::Agent(agent) => match &agent.event {
+                AgentEvent::Started if agent.agent_name != self.agent_name => {
+                    eprintln!("↳ {} started", agent.display_name);
+                }
+                AgentEvent::Error { message } => {
+                    eprintln!("{} error: {}", agent.display_name, message);
+                }
+                _ => {}
+            },
+            Message::Text(text) => match text.level {
+                MessageLevel::Error => eprintln!("error: {}", text.text),
+                MessageLevel::Warning => eprintln!("warning: {}", text.text),
+                _ => {}
+            },
+            Message::Retry(retry) => {
+                eprintln!("retry ({}): {}", retry.provider, retry.reason);
+            }
+            Message::McpStatus(status) if status.state.is_down() => {
+                eprintln!(
+                    "mcp ({}): {}",
+                    status.server,
+                    status.error.as_deref().unwrap_or(status.state.label())
+                );
+            }
+            _ => {}
+        }
+    }
+
+    fn finish_success(&mut self, run_id: &str, output: &str) {
+        match self.format {
+            OutputFormat::Json => {
+                let line = serde_json::json!({
+                    "type": "result",
+                    "run_id": run_id,
+                    "output": output,
+                });
+                self.write_stdout(&format!("{}\n", line));
+            }
+            OutputFormat::Text => {
+                if !self.at_line_start {
+                    self.write_stdout("\n");
+                }
+            }
+        }
+    }
+
+    fn finish_error(&mut self, error: &str) {
+        match self.format {
+            OutputFormat::Json => {
+                let line = serde_json::json!({
+                    "type": "error",
+                    "message": error,
+                });
+                self.write_stdout(&format!("{}\n", line));
+            }
+            OutputFormat::Text => {
+                if !self.at_line_start {
+                    self.write_stdout("\n");
+                }
+            }
+        }
+        eprintln!("Execution failed: {}", error);
+    }
+
+    fn write_stdout(&mut self, text: &str) {
+        if text.is_empty() {
+            return;
+        }
+        let mut stdout = io::stdout().lock();
+        let _ = stdout.write_all(text.as_bytes());
+        let _ = stdout.flush();
+        self.at_line_start = text.ends_with('\n');
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_output_format_from_str() {
+        assert_eq!("text".parse::<OutputFormat>(), Ok(OutputFormat::Text));
+        assert_eq!("json".parse::<OutputFormat>(), Ok(OutputFormat::Json));
+        assert_eq!("JSON".parse::<OutputFormat>(), Ok(OutputFormat::Json));
+        assert_eq!("ndjson".parse::<OutputFormat>(), Ok(OutputFormat::Json));
+        assert!("yaml".parse::<OutputFormat>().is_err());
+    }
+
+    #[test]
+    fn test_output_format_display_roundtrip() {
+        for format in [OutputFormat::Text, OutputFormat::Json] {
+            assert_eq!(format.to_string().parse::<OutputFormat>(), Ok(format));
+        }
+    }
+
+    #[test]
+    fn test_output_format_default_is_text() {
+        assert_eq!(OutputFormat::default(), OutputFormat::Text);
+    }
+
+    #[test]
+    fn test_resolve_prompt_passthrough() {
+        assert_eq!(resolve_prompt("Explain this").unwrap(), "Explain this");
+    }
+
+    #[test]
+    fn test_resolve_prompt_rejects_empty() {
+        assert!(resolve_prompt("   ").is_err());
+    }
+
+    #[test]
+    fn test_refusal_message_follows_policy() {
+        let policy = CommandPolicy::new(vec![], vec!["cargo check".to_string()], false);
+
+        assert!(refusal_message(&policy.evaluate("cargo check", None)).is_none());
+        let ask = refusal_message(&policy.evaluate("cargo build", None)).unwrap();
+        assert!(ask.contains("permissions.rules"));
+        let deny = refusal_message(&policy.evaluate("rm -rf /", None)).unwrap();
+        assert!(deny.starts_with("Command denied by policy"));
+    }
+
+    #[test]
+    fn test_file_refusal_message_follows_policy() {
+        use crate::terminal::FileOperation;
+
+        let policy = CommandPolicy::new(vec![], vec![], false);
+        let ask = policy.evaluate_file_change("src/lib.rs", FileOperation::Write);
+        assert!(file_refusal_message(&ask)
+            .unwrap()
+            .contains("permissions.file_rules"));
+
+        let yolo = CommandPolicy::new(vec![], vec![], true);
+        let allow = yolo.evaluate_file_change("src/lib.rs", FileOperation::Delete);
+        assert!(file_refusal_message(&allow).is_none());
+    }
+
+    #[test]
+    fn test_printer_tracks_line_start() {
+        let mut printer = HeadlessPrinter::new(OutputFormat::Text, "spot");
+        assert!(printer.at_line_start);
+        printer.print(&Message::text_delta_from("partial", "spot"));
+        assert!(!printer.at_line_start);
+        printer.print(&Message::text_delta_from(" line\n", "spot"));
+        assert!(printer.at_line_start);
+    }
+
+    #[test]
+    fn test_printer_ignores_sub_agent_text() {
+        let mut printer = HeadlessPrinter::new(OutputFormat::Text, "spot");
+        printer.print(&Message::text_delta_from("nested output", "explore"));
+        assert!(printer.at_line_start);
+    }
+}
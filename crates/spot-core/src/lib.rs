@@ -20,8 +20,10 @@ pub use spot_tools::terminal;
 
 // Modules that remain in spot-core
 pub mod agents;
+pub mod checkpoints;
 pub mod config;
 pub mod display_detect;
+pub mod headless;
 pub mod mcp;
 pub mod messaging;
 pub mod metrics;
@@ -19,12 +19,40 @@ pub enum McpConfigError {
 
     #[error("Config file not found: {0}")]
     NotFound(PathBuf),
+
+    #[error("Invalid server: {0}")]
+    InvalidServer(String),
+}
+
+/// How Spot talks to an MCP server.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum McpTransportKind {
+    /// Spawn `command` and speak JSON-RPC over its stdin/stdout.
+    Stdio,
+    /// Streamable HTTP: POST each message to `url`.
+    #[serde(alias = "streamable-http", alias = "streamableHttp")]
+    Http,
+    /// Legacy HTTP+SSE: GET `url` for an event stream, POST to the endpoint it announces.
+    Sse,
+}
+
+impl McpTransportKind {
+    /// Short label for settings pages.
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Stdio => "stdio",
+            Self::Http => "http",
+            Self::Sse => "sse",
+        }
+    }
 }
 
 /// MCP server entry in the configuration file.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct McpServerEntry {
-    /// Command to run the MCP server.
+    /// Command to run the MCP server (local servers).
+    #[serde(default, skip_serializing_if = "String::is_empty")]
     pub command: String,
 
     /// Arguments to pass to the command.
@@ -35,6 +63,15 @@ pub struct McpServerEntry {
     #[serde(default)]
     pub env: HashMap<String, String>,
 
+    /// Start from a minimal environment (`PATH`, `HOME`, locale, temp dirs)
+    /// instead of inheriting Spot's, then apply `env`.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub clean_env: bool,
+
+    /// Working directory for the server process.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cwd: Option<String>,
+
     /// Whether this server is enabled.
     #[serde(default = "default_enabled")]
     pub enabled: bool,
@@ -42,6 +79,24 @@ pub struct McpServerEntry {
     /// Optional description of the server.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
+
+    /// URL of a remote server. Remote servers ignore `command`, `args`,
+    /// `env`, `clean_env` and `cwd`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+
+    /// Transport to use. Inferred when omitted: `sse` for URLs ending in
+    /// `/sse`, `http` for other URLs, `stdio` otherwise.
+    #[serde(default, rename = "type", skip_serializing_if = "Option::is_none")]
+    pub transport: Option<McpTransportKind>,
+
+    /// Extra HTTP headers sent to a remote server.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub headers: HashMap<String, String>,
+
+    /// Token sent as `Authorization: Bearer <token>` to a remote server.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bearer_token: Option<String>,
 }
 
 fn default_enabled() -> bool {
@@ -55,8 +110,76 @@ impl McpServerEntry {
             command: command.into(),
             args: Vec::new(),
             env: HashMap::new(),
+            clean_env: false,
+            cwd: None,
             enabled: true,
             description: None,
+            url: None,
+            transport: None,
+            headers: HashMap::new(),
+            bearer_token: None,
+        }
+    }
+
+    /// Create an entry for a remote server at `url`.
+    pub fn remote(url: impl Into<String>) -> Self {
+        Self {
+            url: Some(url.into()),
+            ..Self::new("")
+        }
+    }
+
+    /// Set the transport explicitly.
+    pub fn with_transport(mut self, transport: McpTransportKind) -> Self {
+        self.transport = Some(transport);
+        self
+    }
+
+    /// Add an HTTP header.
+    pub fn with_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.insert(name.into(), value.into());
+        self
+    }
+
+    /// Set the bearer token.
+    pub fn with_bearer_token(mut self, token: impl Into<String>) -> Self {
+        self.bearer_token = Some(token.into());
+        self
+    }
+
+    /// The transport this entry uses, explicit or inferred.
+    pub fn transport_kind(&self) -> McpTransportKind {
+        if let Some(transport) = self.transport {
+            return transport;
+        }
+        match &self.url {
+            Some(url)
+                if url
+                    .split(['?', '#'])
+                    .next()
+                    .unwrap_or("")
+                    .trim_end_matches('/')
+                    .ends_with("/sse") =>
+            {
+                McpTransportKind::Sse
+            }
+            Some(_) => McpTransportKind::Http,
+            None => McpTransportKind::Stdio,
+        }
+    }
+
+    /// Whether this entry connects to a remote server over HTTP.
+    pub fn is_remote(&self) -> bool {
+        self.transport_kind() != McpTransportKind::Stdio
+    }
+
+    /// One-line summary for settings pages: the URL of a remote server or
+    /// the command line of a local one.
+    pub fn display_target(&self) -> String {
+        match (&self.url, self.is_remote()) {
+            (Some(url), true) => format!("[{}] {}", self.transport_kind().label(), url),
+            _ if self.args.is_empty() => self.command.clone(),
+            _ => format!("{} {}", self.command, self.args.join(" ")),
         }
     }
 
@@ -72,6 +195,18 @@ impl McpServerEntry {
         self
     }
 
+    /// Set the working directory.
+    pub fn with_cwd(mut self, cwd: impl Into<String>) -> Self {
+        self.cwd = Some(cwd.into());
+        self
+    }
+
+    /// Don't inherit Spot's environment.
+    pub fn with_clean_env(mut self) -> Self {
+        self.clean_env = true;
+        self
+    }
+
     /// Set description.
     pub fn with_description(mut self, desc: impl Into<String>) -> Self {
         self.description = Some(desc.into());
@@ -94,6 +229,20 @@ impl McpServerEntry {
             .map(|(k, v)| (k.clone(), expand_env_var(v)))
             .collect();
         self.env = expanded;
+
+        if let Some(cwd) = &mut self.cwd {
+            *cwd = expand_env_var(cwd);
+        }
+
+        if let Some(url) = &mut self.url {
+            *url = expand_env_var(url);
+        }
+        for value in self.headers.values_mut() {
+            *value = expand_env_var(value);
+        }
+        if let Some(token) = &mut self.bearer_token {
+            *token = expand_env_var(token);
+        }
     }
 }
 
@@ -146,12 +295,7 @@ impl McpConfig {
 
     /// Load configuration from a specific path.
     pub fn load_from_path(path: &Path) -> Result<Self, McpConfigError> {
-        if !path.exists() {
-            return Err(McpConfigError::NotFound(path.to_path_buf()));
-        }
-
-        let content = fs::read_to_string(path)?;
-        let mut config: McpConfig = serde_json::from_str(&content)?;
+        let mut config = Self::load_unexpanded_from_path(path)?;
 
         // Expand environment variables in all entries
         for entry in config.servers.values_mut() {
@@ -161,11 +305,31 @@ impl McpConfig {
         Ok(config)
     }
 
+    /// Load configuration from a specific path as written, keeping `${VAR}`
+    /// references.
+    pub fn load_unexpanded_from_path(path: &Path) -> Result<Self, McpConfigError> {
+        if !path.exists() {
+            return Err(McpConfigError::NotFound(path.to_path_buf()));
+        }
+
+        let content = fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&content)?)
+    }
+
     /// Try to load configuration, returning empty config if not found.
     pub fn load_or_default() -> Self {
         Self::load_default().unwrap_or_default()
     }
 
+    /// Load the default configuration for editing.
+    ///
+    /// Like [`load_or_default`](Self::load_or_default) but leaves `${VAR}`
+    /// references unexpanded, so saving it back doesn't write secrets from
+    /// the environment into the file.
+    pub fn load_for_edit() -> Self {
+        Self::load_unexpanded_from_path(&Self::default_config_path()).unwrap_or_default()
+    }
+
     /// Save configuration to the default path.
     pub fn save_default(&self) -> Result<(), McpConfigError> {
         let path = Self::default_config_path();
@@ -192,6 +356,28 @@ impl McpConfig {
             .join("mcp_servers.json")
     }
 
+    /// Get the file a server's stderr is logged to.
+    ///
+    /// Path: `~/.spot/logs/mcp/<name>.log`
+    pub fn server_log_path(name: &str) -> PathBuf {
+        let file_name: String = name
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+        dirs::home_dir()
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join(".spot")
+            .join("logs")
+            .join("mcp")
+            .join(format!("{}.log", file_name))
+    }
+
     /// Get enabled servers.
     pub fn enabled_servers(&self) -> impl Iterator<Item = (&String, &McpServerEntry)> {
         self.servers.iter().filter(|(_, entry)| entry.enabled)
@@ -248,6 +434,114 @@ impl McpConfig {
     }
 }
 
+/// The remote server form on the GUI and TUI MCP settings pages.
+///
+/// Headers are edited as one line of `Name: value` pairs separated by `;`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RemoteServerForm {
+    pub name: String,
+    pub url: String,
+    pub transport: McpTransportKind,
+    pub bearer_token: String,
+    pub headers: String,
+}
+
+impl Default for RemoteServerForm {
+    fn default() -> Self {
+        Self {
+            name: String::new(),
+            url: String::new(),
+            transport: McpTransportKind::Http,
+            bearer_token: String::new(),
+            headers: String::new(),
+        }
+    }
+}
+
+impl RemoteServerForm {
+    /// Fill the form from an existing entry.
+    pub fn from_entry(name: &str, entry: &McpServerEntry) -> Self {
+        let mut headers: Vec<_> = entry.headers.iter().collect();
+        headers.sort();
+        Self {
+            name: name.to_string(),
+            url: entry.url.clone().unwrap_or_default(),
+            transport: match entry.transport_kind() {
+                McpTransportKind::Stdio => McpTransportKind::Http,
+                kind => kind,
+            },
+            bearer_token: entry.bearer_token.clone().unwrap_or_default(),
+            headers: headers
+                .iter()
+                .map(|(k, v)| format!("{}: {}", k, v))
+                .collect::<Vec<_>>()
+                .join("; "),
+        }
+    }
+
+    /// Parse the headers line.
+    fn parse_headers(&self) -> Result<HashMap<String, String>, McpConfigError> {
+        let mut headers = HashMap::new();
+        for pair in self
+            .headers
+            .split(';')
+            .map(str::trim)
+            .filter(|p| !p.is_empty())
+        {
+            match pair.split_once(':') {
+                Some((name, value)) if !name.trim().is_empty() => {
+                    headers.insert(name.trim().to_string(), value.trim().to_string());
+                }
+                _ => {
+                    return Err(McpConfigError::InvalidServer(format!(
+                        "header '{}' should look like 'Name: value'",
+                        pair
+                    )))
+                }
+            }
+        }
+        Ok(headers)
+    }
+
+    /// Validate the form and write it into `config`.
+    ///
+    /// `previous` is the name the server had when the form was opened, if
+    /// it is being edited; renaming it moves the entry and keeps fields the
+    /// form doesn't show, like `enabled` and `description`.
+    pub fn save_into(
+        &self,
+        config: &mut McpConfig,
+        previous: Option<&str>,
+    ) -> Result<(), McpConfigError> {
+        let invalid = |msg: &str| Err(McpConfigError::InvalidServer(msg.to_string()));
+        let name = self.name.trim();
+        let url = self.url.trim();
+        if name.is_empty() {
+            return invalid("name is required");
+        }
+        if !(url.starts_with("http://") || url.starts_with("https://") || url.starts_with("${")) {
+            return invalid("URL must start with http:// or https://");
+        }
+        if previous != Some(name) && config.has_server(name) {
+            return Err(McpConfigError::InvalidServer(format!(
+                "a server named '{}' already exists",
+                name
+            )));
+        }
+        let headers = self.parse_headers()?;
+
+        let mut entry = previous
+            .and_then(|p| config.remove_server(p))
+            .unwrap_or_else(|| McpServerEntry::remote(url));
+        entry.url = Some(url.to_string());
+        entry.transport = Some(self.transport);
+        entry.headers = headers;
+        entry.bearer_token = Some(self.bearer_token.trim().to_string()).filter(|t| !t.is_empty());
+        config.add_server(name, entry);
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -266,6 +560,8 @@ mod tests {
         assert!(entry.env.is_empty());
         assert!(entry.enabled);
         assert!(entry.description.is_none());
+        assert!(!entry.clean_env);
+        assert!(entry.cwd.is_none());
     }
 
     #[test]
@@ -322,6 +618,65 @@ mod tests {
         assert_eq!(cloned.env, entry.env);
     }
 
+    #[test]
+    fn test_server_entry_remote() {
+        let entry = McpServerEntry::remote("https://mcp.example.com/mcp")
+            .with_header("X-Team", "platform")
+            .with_bearer_token("secret");
+
+        assert_eq!(entry.command, "");
+        assert_eq!(entry.url.as_deref(), Some("https://mcp.example.com/mcp"));
+        assert_eq!(entry.headers.get("X-Team"), Some(&"platform".to_string()));
+        assert_eq!(entry.bearer_token.as_deref(), Some("secret"));
+        assert!(entry.is_remote());
+    }
+
+    #[test]
+    fn test_transport_kind_inference() {
+        assert_eq!(
+            McpServerEntry::new("npx").transport_kind(),
+            McpTransportKind::Stdio
+        );
+        assert_eq!(
+            McpServerEntry::remote("https://host/mcp").transport_kind(),
+            McpTransportKind::Http
+        );
+        assert_eq!(
+            McpServerEntry::remote("https://host/sse").transport_kind(),
+            McpTransportKind::Sse
+        );
+        assert_eq!(
+            McpServerEntry::remote("https://host/sse/?team=a").transport_kind(),
+            McpTransportKind::Sse
+        );
+        assert_eq!(
+            McpServerEntry::remote("https://host/sse")
+                .with_transport(McpTransportKind::Http)
+                .transport_kind(),
+            McpTransportKind::Http
+        );
+    }
+
+    #[test]
+    fn test_display_target() {
+        let local = McpServerEntry::new("npx").with_args(vec!["-y".to_string(), "pkg".to_string()]);
+        assert_eq!(local.display_target(), "npx -y pkg");
+        assert_eq!(McpServerEntry::new("server").display_target(), "server");
+
+        let remote = McpServerEntry::remote("https://host/sse");
+        assert_eq!(remote.display_target(), "[sse] https://host/sse");
+    }
+
+    #[test]
+    fn test_server_entry_with_cwd_and_clean_env() {
+        let entry = McpServerEntry::new("node")
+            .with_cwd("/srv/mcp")
+            .with_clean_env();
+
+        assert_eq!(entry.cwd.as_deref(), Some("/srv/mcp"));
+        assert!(entry.clean_env);
+    }
+
     // =========================================================================
     // expand_env_var Tests
     // =========================================================================
@@ -390,6 +745,16 @@ mod tests {
         assert_eq!(entry.env.get("AUTH"), Some(&"secret123".to_string()));
     }
 
+    #[test]
+    fn test_server_entry_expand_env_vars_in_cwd() {
+        std::env::set_var("MCP_TEST_CWD_ROOT", "/opt/servers");
+
+        let mut entry = McpServerEntry::new("cmd").with_cwd("${MCP_TEST_CWD_ROOT}/github");
+        entry.expand_env_vars();
+
+        assert_eq!(entry.cwd.as_deref(), Some("/opt/servers/github"));
+    }
+
     // =========================================================================
     // McpConfig Tests
     // =========================================================================
@@ -673,6 +1038,13 @@ mod tests {
         assert!(path.to_string_lossy().contains(".spot"));
     }
 
+    #[test]
+    fn test_server_log_path_sanitizes_name() {
+        let path = McpConfig::server_log_path("my server/../x");
+        assert_eq!(path.file_name().unwrap(), "my_server____x.log");
+        assert!(path.parent().unwrap().ends_with("logs/mcp"));
+    }
+
     // =========================================================================
     // Error Type Tests
     // =========================================================================
@@ -790,10 +1162,12 @@ mod tests {
     }
 
     #[test]
-    fn test_parse_fails_missing_command() {
+    fn test_parse_missing_command_defaults_empty() {
+        // Remote servers have no command
         let json = r#"{"args": ["test"]}"#;
-        let result: Result<McpServerEntry, _> = serde_json::from_str(json);
-        assert!(result.is_err());
+        let entry: McpServerEntry = serde_json::from_str(json).unwrap();
+        assert_eq!(entry.command, "");
+        assert!(!entry.is_remote());
     }
 
     #[test]
@@ -911,6 +1285,22 @@ mod tests {
         assert_eq!(entry.args[2], "val2");
     }
 
+    #[test]
+    fn test_server_entry_expand_remote_fields() {
+        std::env::set_var("MCP_REMOTE_HOST", "mcp.internal");
+        std::env::set_var("MCP_REMOTE_TOKEN", "tok-123");
+
+        let mut entry = McpServerEntry::remote("https://${MCP_REMOTE_HOST}/mcp")
+            .with_header("X-Token", "${MCP_REMOTE_TOKEN}")
+            .with_bearer_token("${MCP_REMOTE_TOKEN}");
+
+        entry.expand_env_vars();
+
+        assert_eq!(entry.url.as_deref(), Some("https://mcp.internal/mcp"));
+        assert_eq!(entry.headers.get("X-Token"), Some(&"tok-123".to_string()));
+        assert_eq!(entry.bearer_token.as_deref(), Some("tok-123"));
+    }
+
     #[test]
     fn test_server_entry_expand_multiple_env_keys() {
         std::env::set_var("ENV_KEY_VAR", "expanded");
@@ -1044,6 +1434,35 @@ mod tests {
         assert!(content.contains("${ANOTHER_VAR}"));
     }
 
+    #[test]
+    fn test_load_unexpanded_keeps_env_vars() {
+        std::env::set_var("UNEXPANDED_TEST_TOKEN", "secret");
+
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("config.json");
+        fs::write(
+            &path,
+            r#"{"servers": {"remote": {"url": "https://host/mcp", "bearer_token": "${UNEXPANDED_TEST_TOKEN}"}}}"#,
+        )
+        .unwrap();
+
+        let raw = McpConfig::load_unexpanded_from_path(&path).unwrap();
+        assert_eq!(
+            raw.get_server("remote").unwrap().bearer_token.as_deref(),
+            Some("${UNEXPANDED_TEST_TOKEN}")
+        );
+
+        let expanded = McpConfig::load_from_path(&path).unwrap();
+        assert_eq!(
+            expanded
+                .get_server("remote")
+                .unwrap()
+                .bearer_token
+                .as_deref(),
+            Some("secret")
+        );
+    }
+
     #[test]
     fn test_load_empty_file() {
         let temp = TempDir::new().unwrap();
@@ -1116,6 +1535,51 @@ mod tests {
 
         // skip_serializing_if = "Option::is_none" should exclude description
         assert!(!json.contains("description"));
+        assert!(!json.contains("cwd"));
+        assert!(!json.contains("clean_env"));
+    }
+
+    #[test]
+    fn test_cwd_and_clean_env_roundtrip() {
+        let entry = McpServerEntry::new("cmd")
+            .with_cwd("/work")
+            .with_clean_env();
+        let json = serde_json::to_string(&entry).unwrap();
+        let parsed: McpServerEntry = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(parsed.cwd.as_deref(), Some("/work"));
+        assert!(parsed.clean_env);
+    }
+
+    #[test]
+    fn test_parse_remote_entry() {
+        let json = r#"{
+            "type": "streamable-http",
+            "url": "https://mcp.example.com/v1",
+            "headers": {"X-Team": "platform"},
+            "bearer_token": "${TEAM_TOKEN}"
+        }"#;
+        let entry: McpServerEntry = serde_json::from_str(json).unwrap();
+
+        assert_eq!(entry.transport, Some(McpTransportKind::Http));
+        assert_eq!(entry.url.as_deref(), Some("https://mcp.example.com/v1"));
+        assert_eq!(entry.headers.len(), 1);
+        assert!(entry.enabled);
+    }
+
+    #[test]
+    fn test_remote_entry_roundtrip_omits_local_fields() {
+        let entry = McpServerEntry::remote("https://host/sse")
+            .with_transport(McpTransportKind::Sse)
+            .with_bearer_token("${TOKEN}");
+        let json = serde_json::to_string(&entry).unwrap();
+
+        assert!(!json.contains("command"));
+        assert!(json.contains(r#""type":"sse""#));
+
+        let parsed: McpServerEntry = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.transport_kind(), McpTransportKind::Sse);
+        assert_eq!(parsed.bearer_token.as_deref(), Some("${TOKEN}"));
     }
 
     #[test]
@@ -1183,4 +1647,87 @@ mod tests {
         let msg = format!("{}", config_err);
         assert!(msg.contains("parse config file"));
     }
+
+    // =========================================================================
+    // RemoteServerForm Tests
+    // =========================================================================
+
+    fn form(name: &str, url: &str) -> RemoteServerForm {
+        RemoteServerForm {
+            name: name.to_string(),
+            url: url.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_remote_form_adds_server() {
+        let mut config = McpConfig::new();
+        let form = RemoteServerForm {
+            transport: McpTransportKind::Sse,
+            bearer_token: "${TEAM_TOKEN}".to_string(),
+            headers: "X-Team: platform; X-Env:prod;".to_string(),
+            ..form(" team ", "https://host/events")
+        };
+
+        form.save_into(&mut config, None).unwrap();
+
+        let entry = config.get_server("team").unwrap();
+        assert_eq!(entry.url.as_deref(), Some("https://host/events"));
+        assert_eq!(entry.transport_kind(), McpTransportKind::Sse);
+        assert_eq!(entry.bearer_token.as_deref(), Some("${TEAM_TOKEN}"));
+        assert_eq!(entry.headers.get("X-Env"), Some(&"prod".to_string()));
+        assert!(entry.enabled);
+    }
+
+    #[test]
+    fn test_remote_form_edit_renames_and_keeps_other_fields() {
+        let mut config = McpConfig::new();
+        let mut entry = McpServerEntry::remote("https://old/mcp")
+            .with_description("Shared tools")
+            .with_bearer_token("old")
+            .with_header("B", "2")
+            .with_header("A", "1");
+        entry.enabled = false;
+        config.add_server("old", entry);
+
+        let mut form = RemoteServerForm::from_entry("old", config.get_server("old").unwrap());
+        assert_eq!(form.headers, "A: 1; B: 2");
+        assert_eq!(form.transport, McpTransportKind::Http);
+        form.name = "new".to_string();
+        form.bearer_token.clear();
+        form.save_into(&mut config, Some("old")).unwrap();
+
+        assert!(!config.has_server("old"));
+        let entry = config.get_server("new").unwrap();
+        assert_eq!(entry.description.as_deref(), Some("Shared tools"));
+        assert!(!entry.enabled);
+        assert!(entry.bearer_token.is_none());
+    }
+
+    #[test]
+    fn test_remote_form_validation() {
+        let mut config = McpConfig::new();
+        config.add_server("taken", McpServerEntry::new("cmd"));
+
+        let err = form("", "https://host").save_into(&mut config, None);
+        assert!(err.unwrap_err().to_string().contains("name is required"));
+        let err = form("a", "host:8080").save_into(&mut config, None);
+        assert!(err.unwrap_err().to_string().contains("http://"));
+        let err = form("taken", "https://host").save_into(&mut config, None);
+        assert!(err.unwrap_err().to_string().contains("already exists"));
+
+        let bad_headers = RemoteServerForm {
+            headers: "X-Team platform".to_string(),
+            ..form("a", "https://host")
+        };
+        assert!(bad_headers.save_into(&mut config, None).is_err());
+        assert_eq!(config.servers.len(), 1);
+
+        // Saving an edited server under its own name is fine
+        form("taken", "https://host")
+            .save_into(&mut config, Some("taken"))
+            .unwrap();
+        assert!(config.get_server("taken").unwrap().is_remote());
+    }
 }
@@ -0,0 +1,537 @@
+//! MCP resources and prompts as chat context.
+//!
+//! Resources are attached to a message by mentioning them as
+//! `@server:resource`, where `resource` is the resource's name or URI.
+//! Prompts are run as slash commands: `/server:prompt [args]`.
+//!
+//! Responses from servers are converted into the types here so frontends
+//! don't depend on serdes-ai-mcp.
+
+use serde::Deserialize;
+use serde_json::Value as JsonValue;
+use std::collections::HashMap;
+
+/// A resource a server exposes.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct McpResource {
+    pub uri: String,
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub mime_type: Option<String>,
+}
+
+/// Contents of a resource, as text or base64 `blob`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct McpResourceContents {
+    pub uri: String,
+    #[serde(default)]
+    pub mime_type: Option<String>,
+    #[serde(default)]
+    pub text: Option<String>,
+    #[serde(default)]
+    pub blob: Option<String>,
+}
+
+/// Result of reading a resource.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub(super) struct ReadResourceResult {
+    #[serde(default)]
+    pub contents: Vec<McpResourceContents>,
+}
+
+/// A prompt template a server exposes.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct McpPrompt {
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub arguments: Vec<McpPromptArgument>,
+}
+
+/// An argument of a prompt template.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct McpPromptArgument {
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub required: bool,
+}
+
+/// A rendered prompt.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct McpPromptResult {
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub messages: Vec<McpPromptMessage>,
+}
+
+/// One message of a rendered prompt.
+#[derive(Debug, Clone, Deserialize)]
+pub struct McpPromptMessage {
+    pub role: String,
+    pub content: JsonValue,
+}
+
+impl McpPromptMessage {
+    /// Text of the message; embedded text resources are included, other
+    /// content (images, audio) is skipped.
+    pub fn text(&self) -> Option<&str> {
+        match self.content.get("type").and_then(JsonValue::as_str) {
+            Some("text") => self.content.get("text").and_then(JsonValue::as_str),
+            Some("resource") => self.content["resource"]
+                .get("text")
+                .and_then(JsonValue::as_str),
+            _ => None,
+        }
+    }
+}
+
+impl McpPromptResult {
+    /// The prompt as a single chat message.
+    pub fn to_text(&self) -> String {
+        self.messages
+            .iter()
+            .filter_map(McpPromptMessage::text)
+            .collect::<Vec<_>>()
+            .join("\n\n")
+    }
+}
+
+/// An `@server:resource` mention in user input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResourceMention {
+    pub server: String,
+    /// Resource name or URI
+    pub resource: String,
+}
+
+/// Find `@server:resource` mentions in `input`.
+///
+/// A mention starts at an `@` at the beginning of the input or after
+/// whitespace; trailing punctuation is not part of the resource.
+pub fn parse_resource_mentions(input: &str) -> Vec<ResourceMention> {
+    let mut mentions: Vec<ResourceMention> = Vec::new();
+    for word in input.split_whitespace() {
+        let Some(rest) = word.strip_prefix('@') else {
+            continue;
+        };
+        let Some((server, resource)) = rest.split_once(':') else {
+            continue;
+        };
+        let resource = resource.trim_end_matches(['.', ',', ';', '!', '?', ')', '"', '\'']);
+        if !is_server_name(server) || resource.is_empty() {
+            continue;
+        }
+
+        let mention = ResourceMention {
+            server: server.to_string(),
+            resource: resource.to_string(),
+        };
+        if !mentions.contains(&mention) {
+            mentions.push(mention);
+        }
+    }
+    mentions
+}
+
+/// A `/server:prompt [args]` command.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PromptCommand {
+    pub server: String,
+    pub prompt: String,
+    /// Everything after the command name
+    pub args: String,
+}
+
+/// Parse `input` as a `/server:prompt [args]` command.
+///
+/// Returns `None` for anything else, including built-in commands such as
+/// `/save`, which have no `:`.
+pub fn parse_prompt_command(input: &str) -> Option<PromptCommand> {
+    let rest = input.trim_start().strip_prefix('/')?;
+    let (command, args) = match rest.split_once(char::is_whitespace) {
+        Some((command, args)) => (command, args.trim()),
+        None => (rest.trim_end(), ""),
+    };
+    let (server, prompt) = command.split_once(':')?;
+    if !is_server_name(server) || prompt.is_empty() {
+        return None;
+    }
+
+    Some(PromptCommand {
+        server: server.to_string(),
+        prompt: prompt.to_string(),
+        args: args.to_string(),
+    })
+}
+
+impl PromptCommand {
+    /// Map the command's arguments onto `prompt`'s declared arguments.
+    ///
+    /// `name=value` words set arguments by name. Other words fill the
+    /// remaining arguments in order, and the last one takes the rest of the
+    /// line, so `/docs:summarize the release notes` works for a prompt with
+    /// one argument. Fails if a required argument is missing.
+    pub fn arguments(&self, prompt: &McpPrompt) -> Result<HashMap<String, String>, String> {
+        let mut values = HashMap::new();
+        let mut positional = Vec::new();
+        for word in self.args.split_whitespace() {
+            match word.split_once('=') {
+                Some((name, value)) if prompt.arguments.iter().any(|a| a.name == name) => {
+                    values.insert(name.to_string(), value.to_string());
+                }
+                _ => positional.push(word),
+            }
+        }
+
+        let open: Vec<&McpPromptArgument> = prompt
+            .arguments
+            .iter()
+            .filter(|a| !values.contains_key(&a.name))
+            .collect();
+        let mut words = positional.into_iter();
+        for (i, argument) in open.iter().enumerate() {
+            let value = if i + 1 == open.len() {
+                words.by_ref().collect::<Vec<_>>().join(" ")
+            } else {
+                words.next().unwrap_or_default().to_string()
+            };
+            if !value.is_empty() {
+                values.insert(argument.name.clone(), value);
+            }
+        }
+        if words.next().is_some() {
+            return Err(format!(
+                "/{}:{} takes no arguments",
+                self.server, self.prompt
+            ));
+        }
+
+        if let Some(missing) = prompt
+            .arguments
+            .iter()
+            .find(|a| a.required && !values.contains_key(&a.name))
+        {
+            return Err(format!(
+                "/{}:{} needs a value for '{}'",
+                self.server, self.prompt, missing.name
+            ));
+        }
+        Ok(values)
+    }
+}
+
+/// Server names in mentions and commands: letters, digits, `_`, `-` and `.`.
+fn is_server_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.'))
+}
+
+/// Render read resources as an attachment block for the prompt.
+pub fn render_resources(resources: &[(ResourceMention, Vec<McpResourceContents>)]) -> String {
+    let mut out = String::from("\n\n--- MCP Resources ---\n");
+    for (mention, contents) in resources {
+        for content in contents {
+            let mime = content
+                .mime_type
+                .as_deref()
+                .map(|m| format!(" ({})", m))
+                .unwrap_or_default();
+            out.push_str(&format!(
+                "Resource: {}:{}{}\n",
+                mention.server, content.uri, mime
+            ));
+            match (&content.text, &content.blob) {
+                (Some(text), _) => out.push_str(&format!("```\n{}\n```\n\n", text)),
+                (None, Some(blob)) => out.push_str(&format!(
+                    "[binary content, {} bytes base64, not included]\n\n",
+                    blob.len()
+                )),
+                (None, None) => out.push_str("[empty]\n\n"),
+            }
+        }
+    }
+    out
+}
+
+impl McpPrompt {
+    /// The slash command that runs this prompt on `server`, e.g.
+    /// `/docs:summarize <topic> [lang]`.
+    pub fn usage(&self, server: &str) -> String {
+        let mut usage = format!("/{}:{}", server, self.name);
+        for argument in &self.arguments {
+            if argument.required {
+                usage.push_str(&format!(" <{}>", argument.name));
+            } else {
+                usage.push_str(&format!(" [{}]", argument.name));
+            }
+        }
+        usage
+    }
+}
+
+impl McpResource {
+    /// The mention that attaches this resource from `server`. Names with
+    /// whitespace can't be mentioned, so the URI is used instead.
+    pub fn mention(&self, server: &str) -> String {
+        if self.name.is_empty() || self.name.contains(char::is_whitespace) {
+            format!("@{}:{}", server, self.uri)
+        } else {
+            format!("@{}:{}", server, self.name)
+        }
+    }
+}
+
+/// Prompts of all servers as `(usage, description)`, sorted by usage.
+pub fn prompt_catalog(prompts: &HashMap<String, Vec<McpPrompt>>) -> Vec<(String, Option<String>)> {
+    let mut catalog: Vec<_> = prompts
+        .iter()
+        .flat_map(|(server, prompts)| {
+            prompts
+                .iter()
+                .map(move |p| (p.usage(server), p.description.clone()))
+        })
+        .collect();
+    catalog.sort();
+    catalog
+}
+
+/// Resources of all servers as `(mention, description)`, sorted by mention.
+/// Resources without a description are described by their URI.
+pub fn resource_catalog(
+    resources: &HashMap<String, Vec<McpResource>>,
+) -> Vec<(String, Option<String>)> {
+    let mut catalog: Vec<_> = resources
+        .iter()
+        .flat_map(|(server, resources)| {
+            resources.iter().map(move |r| {
+                let description = r.description.clone().unwrap_or_else(|| r.uri.clone());
+                (r.mention(server), Some(description))
+            })
+        })
+        .collect();
+    catalog.sort();
+    catalog
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn prompt(arguments: &[(&str, bool)]) -> McpPrompt {
+        McpPrompt {
+            name: "summarize".to_string(),
+            description: None,
+            arguments: arguments
+                .iter()
+                .map(|(name, required)| McpPromptArgument {
+                    name: name.to_string(),
+                    description: None,
+                    required: *required,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_parse_resource_mentions() {
+        let mentions = parse_resource_mentions(
+            "Compare @docs:guide.md and @jira:PROJ-12, then @docs:guide.md",
+        );
+        assert_eq!(
+            mentions,
+            vec![
+                ResourceMention {
+                    server: "docs".to_string(),
+                    resource: "guide.md".to_string(),
+                },
+                ResourceMention {
+                    server: "jira".to_string(),
+                    resource: "PROJ-12".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_resource_mentions_with_uri() {
+        let mentions = parse_resource_mentions("see @docs:file:///srv/guide.md.");
+        assert_eq!(mentions.len(), 1);
+        assert_eq!(mentions[0].resource, "file:///srv/guide.md");
+    }
+
+    #[test]
+    fn test_parse_resource_mentions_ignores_non_mentions() {
+        assert!(parse_resource_mentions("mail me@example.com or @alice").is_empty());
+        assert!(parse_resource_mentions("@:nothing @docs:").is_empty());
+        assert!(parse_resource_mentions("email@docs:guide.md").is_empty());
+    }
+
+    #[test]
+    fn test_parse_prompt_command() {
+        assert_eq!(
+            parse_prompt_command("/docs:summarize the release notes"),
+            Some(PromptCommand {
+                server: "docs".to_string(),
+                prompt: "summarize".to_string(),
+                args: "the release notes".to_string(),
+            })
+        );
+        let command = parse_prompt_command("/jira:triage").unwrap();
+        assert_eq!(command.prompt, "triage");
+        assert!(command.args.is_empty());
+    }
+
+    #[test]
+    fn test_parse_prompt_command_rejects_others() {
+        assert_eq!(parse_prompt_command("/save notes"), None);
+        assert_eq!(parse_prompt_command("/docs:"), None);
+        assert_eq!(parse_prompt_command("hello /docs:summarize"), None);
+        assert_eq!(parse_prompt_command("/a/b:c"), None);
+    }
+
+    #[test]
+    fn test_prompt_arguments_last_takes_rest() {
+        let command = parse_prompt_command("/docs:summarize en the release notes").unwrap();
+        let args = command
+            .arguments(&prompt(&[("lang", false), ("topic", true)]))
+            .unwrap();
+        assert_eq!(args["lang"], "en");
+        assert_eq!(args["topic"], "the release notes");
+    }
+
+    #[test]
+    fn test_prompt_arguments_by_name() {
+        let command = parse_prompt_command("/docs:summarize topic=api release notes").unwrap();
+        let args = command
+            .arguments(&prompt(&[("lang", false), ("topic", true)]))
+            .unwrap();
+        assert_eq!(args["topic"], "api");
+        assert_eq!(args["lang"], "release notes");
+    }
+
+    #[test]
+    fn test_prompt_arguments_missing_required() {
+        let command = parse_prompt_command("/docs:summarize").unwrap();
+        let err = command.arguments(&prompt(&[("topic", true)])).unwrap_err();
+        assert!(err.contains("'topic'"));
+    }
+
+    #[test]
+    fn test_prompt_arguments_rejects_extra_words() {
+        let command = parse_prompt_command("/docs:summarize now").unwrap();
+        assert!(command.arguments(&prompt(&[])).is_err());
+        let command = parse_prompt_command("/docs:summarize").unwrap();
+        assert!(command.arguments(&prompt(&[])).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_prompt_result_to_text() {
+        let result: McpPromptResult = serde_json::from_value(json!({
+            "description": "Summarize",
+            "messages": [
+                { "role": "user", "content": { "type": "text", "text": "Summarize this:" } },
+                { "role": "user", "content": {
+                    "type": "resource",
+                    "resource": { "uri": "file:///a.md", "text": "# A" }
+                } },
+                { "role": "user", "content": { "type": "image", "data": "", "mimeType": "image/png" } }
+            ]
+        }))
+        .unwrap();
+        assert_eq!(result.to_text(), "Summarize this:\n\n# A");
+    }
+
+    #[test]
+    fn test_resource_deserializes_camel_case() {
+        let resource: McpResource = serde_json::from_value(json!({
+            "uri": "file:///guide.md",
+            "name": "guide.md",
+            "mimeType": "text/markdown"
+        }))
+        .unwrap();
+        assert_eq!(resource.mime_type.as_deref(), Some("text/markdown"));
+        assert_eq!(resource.description, None);
+    }
+
+    #[test]
+    fn test_prompt_catalog() {
+        let mut summarize = prompt(&[("topic", true), ("lang", false)]);
+        summarize.description = Some("Summarize a topic".to_string());
+        let mut triage = prompt(&[]);
+        triage.name = "triage".to_string();
+        let prompts = HashMap::from([
+            ("jira".to_string(), vec![triage]),
+            ("docs".to_string(), vec![summarize]),
+        ]);
+
+        assert_eq!(
+            prompt_catalog(&prompts),
+            vec![
+                (
+                    "/docs:summarize <topic> [lang]".to_string(),
+                    Some("Summarize a topic".to_string())
+                ),
+                ("/jira:triage".to_string(), None),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_resource_catalog_mentions() {
+        let resource = |uri: &str, name: &str| McpResource {
+            uri: uri.to_string(),
+            name: name.to_string(),
+            description: None,
+            mime_type: None,
+        };
+        let resources = HashMap::from([(
+            "docs".to_string(),
+            vec![
+                resource("file:///guide.md", "guide.md"),
+                resource("file:///release%20notes.md", "Release notes"),
+            ],
+        )]);
+
+        let catalog = resource_catalog(&resources);
+        assert_eq!(catalog[0].0, "@docs:file:///release%20notes.md");
+        assert_eq!(catalog[1].0, "@docs:guide.md");
+        assert_eq!(catalog[1].1.as_deref(), Some("file:///guide.md"));
+    }
+
+    #[test]
+    fn test_render_resources() {
+        let mention = ResourceMention {
+            server: "docs".to_string(),
+            resource: "guide.md".to_string(),
+        };
+        let contents = vec![
+            McpResourceContents {
+                uri: "file:///guide.md".to_string(),
+                mime_type: Some("text/markdown".to_string()),
+                text: Some("# Guide".to_string()),
+                blob: None,
+            },
+            McpResourceContents {
+                uri: "file:///logo.png".to_string(),
+                mime_type: None,
+                text: None,
+                blob: Some("aGVsbG8=".to_string()),
+            },
+        ];
+
+        let out = render_resources(&[(mention, contents)]);
+        assert!(out.contains("Resource: docs:file:///guide.md (text/markdown)\n```\n# Guide\n```"));
+        assert!(out.contains("[binary content, 8 bytes base64, not included]"));
+    }
+}
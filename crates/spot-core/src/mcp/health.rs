@@ -0,0 +1,180 @@
+//! Health of running MCP servers.
+//!
+//! [`McpManager`](super::McpManager) records an [`McpServerStatus`] for every
+//! server it starts. Its supervisor (see `McpManager::supervise`)
+//! periodically checks running servers, marks unresponsive ones unhealthy
+//! and restarts them with exponential backoff.
+
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+
+/// Lifecycle state of an MCP server.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum McpServerState {
+    /// Launching or connecting
+    Starting,
+    /// Connected and answering requests
+    Ready,
+    /// Stopped answering; waiting to be restarted
+    Unhealthy,
+    /// Failed to start; waiting to be retried
+    Failed,
+    /// Stopped on request
+    Stopped,
+}
+
+impl McpServerState {
+    /// Short lowercase label for display.
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Starting => "starting",
+            Self::Ready => "ready",
+            Self::Unhealthy => "unhealthy",
+            Self::Failed => "failed",
+            Self::Stopped => "stopped",
+        }
+    }
+
+    /// Whether the server should be running but isn't.
+    pub fn is_down(&self) -> bool {
+        matches!(self, Self::Unhealthy | Self::Failed)
+    }
+}
+
+/// Current status of an MCP server.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct McpServerStatus {
+    pub state: McpServerState,
+    /// Most recent start or health check error
+    pub last_error: Option<String>,
+    /// Number of tools from the last successful listing
+    pub tool_count: Option<usize>,
+    /// Times the supervisor has restarted the server
+    pub restarts: u32,
+    /// Consecutive failed starts and health checks
+    pub failures: u32,
+    /// When the supervisor will next try to restart the server
+    pub next_restart: Option<Instant>,
+}
+
+impl McpServerStatus {
+    pub(super) fn new(state: McpServerState) -> Self {
+        Self {
+            state,
+            last_error: None,
+            tool_count: None,
+            restarts: 0,
+            failures: 0,
+            next_restart: None,
+        }
+    }
+
+    /// One-line description, e.g. `ready · 3 tools` or `failed: <error>`.
+    pub fn summary(&self) -> String {
+        match (self.state, &self.last_error, self.tool_count) {
+            (state, Some(error), _) if state.is_down() => {
+                format!("{}: {}", state.label(), error)
+            }
+            (McpServerState::Ready, _, Some(count)) => {
+                format!(
+                    "ready · {} tool{}",
+                    count,
+                    if count == 1 { "" } else { "s" }
+                )
+            }
+            (state, _, _) => state.label().to_string(),
+        }
+    }
+
+    /// Seconds until the next restart attempt, if one is scheduled.
+    pub fn restart_in_secs(&self) -> Option<u64> {
+        self.next_restart
+            .map(|at| at.saturating_duration_since(Instant::now()).as_secs())
+    }
+}
+
+/// Timing of the MCP server supervisor.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct McpSupervisorConfig {
+    /// How often running servers are checked
+    pub check_interval: Duration,
+    /// How long a server may take to answer a health check
+    pub check_timeout: Duration,
+    /// Delay before the first restart attempt
+    pub initial_backoff: Duration,
+    /// Longest delay between restart attempts
+    pub max_backoff: Duration,
+}
+
+impl Default for McpSupervisorConfig {
+    fn default() -> Self {
+        Self {
+            check_interval: Duration::from_secs(30),
+            check_timeout: Duration::from_secs(5),
+            initial_backoff: Duration::from_secs(2),
+            max_backoff: Duration::from_secs(300),
+        }
+    }
+}
+
+impl McpSupervisorConfig {
+    /// Delay before restarting after `failures` consecutive failures.
+    ///
+    /// Doubles from `initial_backoff` with each failure, up to `max_backoff`.
+    pub fn backoff(&self, failures: u32) -> Duration {
+        let exponent = failures.saturating_sub(1).min(16);
+        self.initial_backoff
+            .saturating_mul(1 << exponent)
+            .min(self.max_backoff)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_state_labels() {
+        assert_eq!(McpServerState::Ready.label(), "ready");
+        assert_eq!(McpServerState::Unhealthy.label(), "unhealthy");
+        assert!(McpServerState::Failed.is_down());
+        assert!(McpServerState::Unhealthy.is_down());
+        assert!(!McpServerState::Stopped.is_down());
+        assert!(!McpServerState::Starting.is_down());
+    }
+
+    #[test]
+    fn test_state_serializes_snake_case() {
+        let json = serde_json::to_string(&McpServerState::Unhealthy).unwrap();
+        assert_eq!(json, "\"unhealthy\"");
+    }
+
+    #[test]
+    fn test_status_summary() {
+        let mut status = McpServerStatus::new(McpServerState::Ready);
+        assert_eq!(status.summary(), "ready");
+        status.tool_count = Some(1);
+        assert_eq!(status.summary(), "ready · 1 tool");
+        status.tool_count = Some(3);
+        assert_eq!(status.summary(), "ready · 3 tools");
+
+        status.state = McpServerState::Failed;
+        status.last_error = Some("connection refused".to_string());
+        assert_eq!(status.summary(), "failed: connection refused");
+
+        status.state = McpServerState::Starting;
+        assert_eq!(status.summary(), "starting");
+    }
+
+    #[test]
+    fn test_backoff_doubles_up_to_max() {
+        let config = McpSupervisorConfig::default();
+        assert_eq!(config.backoff(0), Duration::from_secs(2));
+        assert_eq!(config.backoff(1), Duration::from_secs(2));
+        assert_eq!(config.backoff(2), Duration::from_secs(4));
+        assert_eq!(config.backoff(5), Duration::from_secs(32));
+        assert_eq!(config.backoff(9), Duration::from_secs(300));
+        assert_eq!(config.backoff(u32::MAX), Duration::from_secs(300));
+    }
+}
@@ -1,14 +1,64 @@
 //! MCP server lifecycle management.
 //!
-//! Handles starting, stopping, and managing MCP server connections.
-
-use super::config::{McpConfig, McpServerEntry};
+//! Handles starting, stopping, and managing MCP server connections, and
+//! tracks each server's health (see [`McpManager::supervise`]).
+
+use super::config::{McpConfig, McpServerEntry, McpTransportKind};
+use super::context::{
+    parse_prompt_command, parse_resource_mentions, render_resources, McpPrompt, McpPromptResult,
+    McpResource, McpResourceContents, PromptCommand, ReadResourceResult, ResourceMention,
+};
+use super::health::{McpServerState, McpServerStatus, McpSupervisorConfig};
+use super::transport::{remote_headers, HttpTransport, SseTransport};
+use serdes_ai_mcp::transport::StdioTransport;
 use serdes_ai_mcp::{McpClient, McpError};
 use std::collections::HashMap;
+use std::path::Path;
+use std::process::Stdio;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 use thiserror::Error;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::process::{ChildStderr, Command};
 use tokio::sync::RwLock;
-use tracing::{error, info, warn};
+use tokio::task::JoinHandle;
+use tracing::{debug, error, info, warn};
+
+use crate::messaging::{McpStatusMessage, Message, MessageSender};
+
+/// Variables kept from Spot's environment for servers with `clean_env`.
+const BASELINE_ENV_VARS: &[&str] = &[
+    "PATH",
+    "HOME",
+    "USER",
+    "LOGNAME",
+    "SHELL",
+    "LANG",
+    "LC_ALL",
+    "TMPDIR",
+    "TEMP",
+    "TMP",
+    // Needed by most programs on Windows
+    "SYSTEMROOT",
+    "SYSTEMDRIVE",
+    "USERPROFILE",
+    "APPDATA",
+    "LOCALAPPDATA",
+    "PATHEXT",
+    "COMSPEC",
+];
+
+/// Server logs larger than this are rotated to `<name>.log.old` on start.
+const MAX_LOG_BYTES: u64 = 1024 * 1024;
+
+/// How long listing a server's tools may take before it counts as unresponsive.
+const LIST_TOOLS_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// How long reading a resource mentioned in user input may take.
+const READ_MENTION_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// How long closing a connection may take before it is abandoned.
+const CLOSE_TIMEOUT: Duration = Duration::from_secs(5);
 
 /// Error type for MCP manager operations.
 #[derive(Debug, Error)]
@@ -27,6 +77,28 @@ pub enum McpManagerError {
 
     #[error("Server not running: {0}")]
     NotRunning(String),
+
+    #[error("Failed to launch {name}: {source}")]
+    Launch {
+        name: String,
+        #[source]
+        source: std::io::Error,
+    },
+
+    #[error("Remote server {0} has no url")]
+    MissingUrl(String),
+
+    #[error("Resource not found: {0}")]
+    ResourceNotFound(String),
+
+    #[error("Prompt not found: {0}")]
+    PromptNotFound(String),
+
+    #[error("Invalid prompt arguments: {0}")]
+    InvalidPromptArguments(String),
+
+    #[error("Unexpected response from MCP server: {0}")]
+    InvalidResponse(String),
 }
 
 /// Handle to a running MCP server.
@@ -42,15 +114,17 @@ pub struct McpServerHandle {
 pub struct McpManager {
     config: McpConfig,
     servers: RwLock<HashMap<String, McpServerHandle>>,
+    /// Status of every server started so far (a std lock, so UIs can read
+    /// it while rendering)
+    statuses: std::sync::RwLock<HashMap<String, McpServerStatus>>,
+    /// Where status changes are published
+    bus: Option<MessageSender>,
 }
 
 impl McpManager {
     /// Create a new MCP manager with default configuration.
     pub fn new() -> Self {
-        Self {
-            config: McpConfig::load_or_default(),
-            servers: RwLock::new(HashMap::new()),
-        }
+        Self::with_config(McpConfig::load_or_default())
     }
 
     /// Create a manager with a specific configuration.
@@ -58,9 +132,17 @@ impl McpManager {
         Self {
             config,
             servers: RwLock::new(HashMap::new()),
+            statuses: std::sync::RwLock::new(HashMap::new()),
+            bus: None,
         }
     }
 
+    /// Publish server status changes on the message bus.
+    pub fn with_bus(mut self, bus: MessageSender) -> Self {
+        self.bus = Some(bus);
+        self
+    }
+
     /// Load configuration from the default path.
     pub fn load_config(&mut self) -> Result<(), McpManagerError> {
         self.config = McpConfig::load_default()?;
@@ -88,13 +170,23 @@ impl McpManager {
         }
 
         info!(server = %name, "Starting MCP server");
+        self.update_status(name, |status| {
+            status.state = McpServerState::Starting;
+            status.next_restart = None;
+        });
 
         // Start the server
-        let handle = self.connect_server(name, entry).await?;
+        let (handle, tool_count) = match self.connect_server(name, entry).await {
+            Ok(connected) => connected,
+            Err(e) => {
+                self.mark_down(name, McpServerState::Failed, e.to_string());
+                return Err(e);
+            }
+        };
 
         // Store the handle
-        let mut servers = self.servers.write().await;
-        servers.insert(name.to_string(), handle);
+        self.servers.write().await.insert(name.to_string(), handle);
+        self.mark_ready(name, tool_count);
 
         info!(server = %name, "MCP server started");
         Ok(())
@@ -102,16 +194,34 @@ impl McpManager {
 
     /// Stop a specific MCP server.
     pub async fn stop_server(&self, name: &str) -> Result<(), McpManagerError> {
-        let mut servers = self.servers.write().await;
+        if !self.disconnect(name).await {
+            return Err(McpManagerError::NotRunning(name.to_string()));
+        }
+        self.update_status(name, |status| {
+            status.state = McpServerState::Stopped;
+            status.next_restart = None;
+        });
+        Ok(())
+    }
 
-        if let Some(handle) = servers.remove(name) {
-            info!("Stopping MCP server: {}", name);
-            if let Err(e) = handle.client.close().await {
-                warn!("Error closing MCP server {}: {}", name, e);
-            }
-            Ok(())
-        } else {
-            Err(McpManagerError::NotRunning(name.to_string()))
+    /// Remove a server's handle and close its connection.
+    ///
+    /// Returns whether the server was running.
+    async fn disconnect(&self, name: &str) -> bool {
+        let Some(handle) = self.servers.write().await.remove(name) else {
+            return false;
+        };
+        Self::close(name, handle).await;
+        true
+    }
+
+    /// Close a server connection whose handle has been removed.
+    async fn close(name: &str, handle: McpServerHandle) {
+        info!("Stopping MCP server: {}", name);
+        match tokio::time::timeout(CLOSE_TIMEOUT, handle.client.close()).await {
+            Ok(Ok(_)) => {}
+            Ok(Err(e)) => warn!("Error closing MCP server {}: {}", name, e),
+            Err(_) => warn!("Timeout closing MCP server {}", name),
         }
     }
 
@@ -198,31 +308,408 @@ impl McpManager {
     }
 
     /// List tools from all running servers.
+    ///
+    /// Servers that fail or time out are marked unhealthy and disconnected,
+    /// so later calls don't wait on them again; the supervisor restarts them.
     pub async fn list_all_tools(&self) -> HashMap<String, Vec<serdes_ai_mcp::McpTool>> {
-        let servers = self.servers.read().await;
         let mut all_tools = HashMap::new();
 
-        for (name, handle) in servers.iter() {
-            // Use timeout to avoid hanging forever on unresponsive servers
+        for (name, client) in self.running_clients().await {
+            match self.check_server(&name, &client, LIST_TOOLS_TIMEOUT).await {
+                Ok(tools) => {
+                    all_tools.insert(name, tools);
+                }
+                Err(e) => {
+                    warn!(server = %name, error = %e, "Failed to list tools from MCP server");
+                    self.mark_unhealthy(&name, &client, e).await;
+                }
+            }
+        }
+
+        all_tools
+    }
+
+    /// Status of a server, if it has been started.
+    pub fn server_status(&self, name: &str) -> Option<McpServerStatus> {
+        self.statuses.read().ok()?.get(name).cloned()
+    }
+
+    /// Status of every server started so far.
+    pub fn server_statuses(&self) -> HashMap<String, McpServerStatus> {
+        self.statuses
+            .read()
+            .map(|statuses| statuses.clone())
+            .unwrap_or_default()
+    }
+
+    /// Spawn a task that keeps servers running.
+    ///
+    /// Every `check_interval` each running server is asked for its tools.
+    /// Servers that fail or time out are marked unhealthy and disconnected,
+    /// then restarted (like servers that failed to start) with exponential
+    /// backoff. Abort the returned handle to stop supervising.
+    pub fn supervise(self: Arc<Self>, config: McpSupervisorConfig) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut next_check = Instant::now() + config.check_interval;
+            loop {
+                self.restart_due(&config).await;
+
+                let wake = self
+                    .next_restart()
+                    .map_or(next_check, |at| at.min(next_check));
+                tokio::time::sleep_until(tokio::time::Instant::from_std(wake)).await;
+
+                if Instant::now() >= next_check {
+                    self.check_health(&config).await;
+                    next_check = Instant::now() + config.check_interval;
+                }
+            }
+        })
+    }
+
+    /// Check every running server once, marking unresponsive ones unhealthy.
+    pub async fn check_health(&self, config: &McpSupervisorConfig) {
+        for (name, client) in self.running_clients().await {
+            if let Err(e) = self
+                .check_server(&name, &client, config.check_timeout)
+                .await
+            {
+                warn!(server = %name, error = %e, "MCP server failed health check");
+                self.mark_unhealthy(&name, &client, e).await;
+            }
+        }
+    }
+
+    /// Restart down servers whose backoff has elapsed, and schedule a restart
+    /// for the ones that just went down.
+    pub async fn restart_due(&self, config: &McpSupervisorConfig) {
+        let now = Instant::now();
+        let due: Vec<String> = self
+            .server_statuses()
+            .into_iter()
+            .filter(|(_, status)| {
+                status.state.is_down() && status.next_restart.is_some_and(|at| at <= now)
+            })
+            .map(|(name, _)| name)
+            .collect();
+
+        for name in due {
+            info!(server = %name, "Restarting MCP server");
+            match self.start_server(&name).await {
+                Ok(()) => self.update_status(&name, |status| status.restarts += 1),
+                Err(e) => warn!(server = %name, error = %e, "Failed to restart MCP server"),
+            }
+        }
+
+        // Includes servers whose restart just failed
+        if let Ok(mut statuses) = self.statuses.write() {
+            for status in statuses.values_mut() {
+                if status.state.is_down() && status.next_restart.is_none() {
+                    status.next_restart = Some(Instant::now() + config.backoff(status.failures));
+                }
+            }
+        }
+    }
+
+    /// Earliest scheduled restart, if any.
+    fn next_restart(&self) -> Option<Instant> {
+        self.statuses
+            .read()
+            .ok()?
+            .values()
+            .filter_map(|status| status.next_restart)
+            .min()
+    }
+
+    /// Clients of all running servers.
+    async fn running_clients(&self) -> Vec<(String, Arc<McpClient>)> {
+        let servers = self.servers.read().await;
+        servers
+            .iter()
+            .map(|(name, handle)| (name.clone(), Arc::clone(&handle.client)))
+            .collect()
+    }
+
+    /// List a server's tools as a health check, recording the tool count.
+    ///
+    /// The count is only recorded if `client` is still the server's client;
+    /// the server may have been stopped or restarted during the check.
+    async fn check_server(
+        &self,
+        name: &str,
+        client: &Arc<McpClient>,
+        timeout: Duration,
+    ) -> Result<Vec<serdes_ai_mcp::McpTool>, String> {
+        let tools = match tokio::time::timeout(timeout, client.list_tools()).await {
+            Ok(Ok(tools)) => tools,
+            Ok(Err(e)) => return Err(e.to_string()),
+            Err(_) => return Err(format!("no response within {}s", timeout.as_secs())),
+        };
+        // Holding the lock keeps the server from being stopped meanwhile
+        let servers = self.servers.read().await;
+        if is_client_of(&servers, name, client) {
+            self.mark_ready(name, Some(tools.len()));
+        }
+        Ok(tools)
+    }
+
+    /// Mark a running server unhealthy and disconnect it.
+    ///
+    /// Does nothing if `client` is no longer the server's client, so a late
+    /// result can't mark a stopped or restarted server unhealthy.
+    async fn mark_unhealthy(&self, name: &str, client: &Arc<McpClient>, error: String) {
+        let handle = {
+            let mut servers = self.servers.write().await;
+            if !is_client_of(&servers, name, client) {
+                debug!(server = %name, "Ignoring health result for a replaced connection");
+                return;
+            }
+            self.mark_down(name, McpServerState::Unhealthy, error);
+            servers.remove(name)
+        };
+        if let Some(handle) = handle {
+            Self::close(name, handle).await;
+        }
+    }
+
+    fn mark_ready(&self, name: &str, tool_count: Option<usize>) {
+        self.update_status(name, |status| {
+            status.state = McpServerState::Ready;
+            status.last_error = None;
+            status.tool_count = tool_count.or(status.tool_count);
+            status.failures = 0;
+            status.next_restart = None;
+        });
+    }
+
+    fn mark_down(&self, name: &str, state: McpServerState, error: String) {
+        self.update_status(name, |status| {
+            status.state = state;
+            status.last_error = Some(error);
+            status.failures += 1;
+            status.next_restart = None;
+        });
+    }
+
+    /// Update a server's status, publishing the change if its state changed.
+    fn update_status(&self, name: &str, update: impl FnOnce(&mut McpServerStatus)) {
+        let message = {
+            let Ok(mut statuses) = self.statuses.write() else {
+                return;
+            };
+            let status = statuses
+                .entry(name.to_string())
+                .or_insert_with(|| McpServerStatus::new(McpServerState::Stopped));
+            let previous = status.state;
+            update(status);
+            if status.state == previous {
+                return;
+            }
+            McpStatusMessage {
+                server: name.to_string(),
+                state: status.state,
+                error: status.last_error.clone().filter(|_| status.state.is_down()),
+                tool_count: status.tool_count,
+            }
+        };
+
+        debug!(server = %name, state = message.state.label(), "MCP server status changed");
+        if let Some(bus) = &self.bus {
+            // No subscribers is fine
+            let _ = bus.send(Message::McpStatus(message));
+        }
+    }
+
+    /// Get the client of a running server.
+    async fn running_client(&self, server_name: &str) -> Result<Arc<McpClient>, McpManagerError> {
+        self.get_handle(server_name)
+            .await
+            .ok_or_else(|| McpManagerError::NotRunning(server_name.to_string()))
+    }
+
+    /// List resources from a specific server.
+    pub async fn list_resources(
+        &self,
+        server_name: &str,
+    ) -> Result<Vec<McpResource>, McpManagerError> {
+        let client = self.running_client(server_name).await?;
+        from_wire(client.list_resources().await?)
+    }
+
+    /// List resources from all running servers.
+    ///
+    /// Servers that don't offer resources are left out.
+    pub async fn list_all_resources(&self) -> HashMap<String, Vec<McpResource>> {
+        let mut all_resources = HashMap::new();
+        for name in self.running_servers().await {
             match tokio::time::timeout(
                 std::time::Duration::from_secs(5),
-                handle.client.list_tools(),
+                self.list_resources(&name),
             )
             .await
             {
-                Ok(Ok(tools)) => {
-                    all_tools.insert(name.clone(), tools);
+                Ok(Ok(resources)) => {
+                    all_resources.insert(name, resources);
                 }
                 Ok(Err(e)) => {
-                    warn!(server = %name, error = %e, "Failed to list tools from MCP server");
+                    debug!(server = %name, error = %e, "No resources from MCP server");
                 }
                 Err(_) => {
-                    warn!(server = %name, "Timeout listing tools from MCP server");
+                    warn!(server = %name, "Timeout listing resources from MCP server");
                 }
             }
         }
+        all_resources
+    }
 
-        all_tools
+    /// Read a resource from a specific server by URI.
+    pub async fn read_resource(
+        &self,
+        server_name: &str,
+        uri: &str,
+    ) -> Result<Vec<McpResourceContents>, McpManagerError> {
+        let client = self.running_client(server_name).await?;
+        let result: ReadResourceResult = from_wire(client.read_resource(uri).await?)?;
+        Ok(result.contents)
+    }
+
+    /// List prompts from a specific server.
+    pub async fn list_prompts(&self, server_name: &str) -> Result<Vec<McpPrompt>, McpManagerError> {
+        let client = self.running_client(server_name).await?;
+        from_wire(client.list_prompts().await?)
+    }
+
+    /// List prompts from all running servers.
+    ///
+    /// Servers that don't offer prompts are left out.
+    pub async fn list_all_prompts(&self) -> HashMap<String, Vec<McpPrompt>> {
+        let mut all_prompts = HashMap::new();
+        for name in self.running_servers().await {
+            match tokio::time::timeout(std::time::Duration::from_secs(5), self.list_prompts(&name))
+                .await
+            {
+                Ok(Ok(prompts)) => {
+                    all_prompts.insert(name, prompts);
+                }
+                Ok(Err(e)) => {
+                    debug!(server = %name, error = %e, "No prompts from MCP server");
+                }
+                Err(_) => {
+                    warn!(server = %name, "Timeout listing prompts from MCP server");
+                }
+            }
+        }
+        all_prompts
+    }
+
+    /// Render a prompt from a specific server.
+    pub async fn get_prompt(
+        &self,
+        server_name: &str,
+        prompt_name: &str,
+        arguments: HashMap<String, String>,
+    ) -> Result<McpPromptResult, McpManagerError> {
+        let client = self.running_client(server_name).await?;
+        from_wire(
+            client
+                .get_prompt(prompt_name, serde_json::json!(arguments))
+                .await?,
+        )
+    }
+
+    /// Resolve MCP prompt commands and resource mentions in user input.
+    ///
+    /// A `/server:prompt [args]` input is replaced by the rendered prompt,
+    /// and resources mentioned as `@server:resource` are read and appended
+    /// as an attachment block. Commands and mentions naming a server that
+    /// isn't configured are left as they are. Mentions that can't be read
+    /// (the server isn't running, the resource is missing or the read times
+    /// out) are left out with a warning on the bus.
+    pub async fn expand_input(&self, input: &str) -> Result<String, McpManagerError> {
+        let mut text = match parse_prompt_command(input) {
+            Some(command) if self.config.get_server(&command.server).is_some() => {
+                self.run_prompt_command(&command).await?
+            }
+            _ => input.to_string(),
+        };
+
+        let mut resources = Vec::new();
+        for mention in parse_resource_mentions(input) {
+            if self.config.get_server(&mention.server).is_none() {
+                continue;
+            }
+            match tokio::time::timeout(READ_MENTION_TIMEOUT, self.read_mention(&mention)).await {
+                Ok(Ok(contents)) => resources.push((mention, contents)),
+                Ok(Err(e)) => self.warn_unattached(&mention, &e.to_string()),
+                Err(_) => self.warn_unattached(
+                    &mention,
+                    &format!("no response within {}s", READ_MENTION_TIMEOUT.as_secs()),
+                ),
+            }
+        }
+        if !resources.is_empty() {
+            text.push_str(&render_resources(&resources));
+        }
+
+        Ok(text)
+    }
+
+    /// Report a mentioned resource that was left out of the message.
+    fn warn_unattached(&self, mention: &ResourceMention, error: &str) {
+        let text = format!(
+            "MCP: @{}:{} was not attached: {}",
+            mention.server, mention.resource, error
+        );
+        warn!("{}", text);
+        if let Some(bus) = &self.bus {
+            bus.warning(text);
+        }
+    }
+
+    /// Render the prompt a `/server:prompt` command names.
+    async fn run_prompt_command(&self, command: &PromptCommand) -> Result<String, McpManagerError> {
+        let prompts = self.list_prompts(&command.server).await?;
+        let prompt = prompts
+            .iter()
+            .find(|p| p.name == command.prompt)
+            .ok_or_else(|| {
+                McpManagerError::PromptNotFound(format!("{}:{}", command.server, command.prompt))
+            })?;
+        let arguments = command
+            .arguments(prompt)
+            .map_err(McpManagerError::InvalidPromptArguments)?;
+
+        let result = self
+            .get_prompt(&command.server, &command.prompt, arguments)
+            .await?;
+        Ok(result.to_text())
+    }
+
+    /// Read the resource a mention names, by resource name or URI.
+    async fn read_mention(
+        &self,
+        mention: &ResourceMention,
+    ) -> Result<Vec<McpResourceContents>, McpManagerError> {
+        let resources = self.list_resources(&mention.server).await?;
+        let uri = resources
+            .iter()
+            .find(|r| r.name == mention.resource || r.uri == mention.resource)
+            .map(|r| r.uri.clone())
+            // Unlisted URIs may still match one of the server's resource templates
+            .or_else(|| {
+                mention
+                    .resource
+                    .contains("://")
+                    .then(|| mention.resource.clone())
+            })
+            .ok_or_else(|| {
+                McpManagerError::ResourceNotFound(format!(
+                    "{}:{}",
+                    mention.server, mention.resource
+                ))
+            })?;
+
+        self.read_resource(&mention.server, &uri).await
     }
 
     /// Connect to a server and create a handle.
@@ -230,16 +717,21 @@ impl McpManager {
         &self,
         name: &str,
         entry: &McpServerEntry,
-    ) -> Result<McpServerHandle, McpManagerError> {
-        // Build args with env vars
-        let args: Vec<&str> = entry.args.iter().map(|s| s.as_str()).collect();
-
-        // Create the client
-        let client = match McpClient::stdio(&entry.command, &args).await {
-            Ok(c) => c,
-            Err(e) => {
-                error!(server = %name, error = %e, "Failed to spawn MCP server process");
-                return Err(e.into());
+    ) -> Result<(McpServerHandle, Option<usize>), McpManagerError> {
+        let client = match entry.transport_kind() {
+            McpTransportKind::Stdio => Self::launch_stdio(name, entry)?,
+            kind => {
+                let url = entry
+                    .url
+                    .as_deref()
+                    .ok_or_else(|| McpManagerError::MissingUrl(name.to_string()))?;
+                let headers = remote_headers(&entry.headers, entry.bearer_token.as_deref())?;
+                info!(server = %name, url = %url, transport = kind.label(), "Connecting to remote MCP server");
+                if kind == McpTransportKind::Sse {
+                    McpClient::new(SseTransport::connect(url, headers).await?)
+                } else {
+                    McpClient::new(HttpTransport::new(url, headers))
+                }
             }
         };
 
@@ -260,26 +752,150 @@ impl McpManager {
         }
 
         // List tools to verify connection - with timeout since some servers are slow
-        match tokio::time::timeout(std::time::Duration::from_secs(5), client.list_tools()).await {
+        let tool_count = match tokio::time::timeout(LIST_TOOLS_TIMEOUT, client.list_tools()).await {
             Ok(Ok(tools)) => {
                 info!(
                     server = %name,
                     tool_count = tools.len(),
                     "MCP server ready with {} tools", tools.len()
                 );
+                Some(tools.len())
             }
             Ok(Err(e)) => {
                 warn!(server = %name, error = %e, "Failed to list MCP tools");
+                None
             }
             Err(_) => {
                 warn!(server = %name, "Timeout listing MCP tools");
+                None
             }
-        }
+        };
 
-        Ok(McpServerHandle {
+        let handle = McpServerHandle {
             client: Arc::new(client),
-        })
+        };
+        Ok((handle, tool_count))
     }
+
+    /// Spawn a stdio server and wrap it in a client.
+    fn launch_stdio(name: &str, entry: &McpServerEntry) -> Result<McpClient, McpManagerError> {
+        let launch_error = |source| McpManagerError::Launch {
+            name: name.to_string(),
+            source,
+        };
+
+        if let Some(cwd) = &entry.cwd {
+            if !Path::new(cwd).is_dir() {
+                return Err(launch_error(std::io::Error::new(
+                    std::io::ErrorKind::NotFound,
+                    format!("working directory {} does not exist", cwd),
+                )));
+            }
+        }
+
+        let mut child = match stdio_command(entry).spawn() {
+            Ok(child) => child,
+            Err(e) => {
+                error!(server = %name, error = %e, "Failed to spawn MCP server process");
+                return Err(launch_error(e));
+            }
+        };
+        if let Some(stderr) = child.stderr.take() {
+            spawn_stderr_logger(name, stderr);
+        }
+
+        Ok(McpClient::new(StdioTransport::from_child(child)?))
+    }
+}
+
+/// Build the command that launches a stdio server.
+///
+/// Applies the entry's environment (on top of a minimal baseline when
+/// `clean_env` is set) and working directory. Stdin and stdout carry the
+/// protocol; stderr is piped so it can be logged.
+fn stdio_command(entry: &McpServerEntry) -> Command {
+    let mut command = Command::new(&entry.command);
+    command
+        .args(&entry.args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true);
+
+    if entry.clean_env {
+        command.env_clear();
+        for var in BASELINE_ENV_VARS {
+            if let Some(value) = std::env::var_os(var) {
+                command.env(var, value);
+            }
+        }
+    }
+    command.envs(&entry.env);
+
+    if let Some(cwd) = &entry.cwd {
+        command.current_dir(cwd);
+    }
+
+    command
+}
+
+/// Copy a server's stderr to its log file (see [`McpConfig::server_log_path`]).
+fn spawn_stderr_logger(name: &str, stderr: ChildStderr) {
+    let name = name.to_string();
+    let path = McpConfig::server_log_path(&name);
+
+    tokio::spawn(async move {
+        let mut file = match open_server_log(&path).await {
+            Ok(file) => Some(file),
+            Err(e) => {
+                warn!(server = %name, path = %path.display(), error = %e, "Failed to open MCP server log");
+                None
+            }
+        };
+
+        let mut lines = BufReader::new(stderr).lines();
+        while let Ok(Some(line)) = lines.next_line().await {
+            debug!(server = %name, "{}", line);
+            if let Some(f) = file.as_mut() {
+                if f.write_all(format!("{}\n", line).as_bytes()).await.is_err() {
+                    file = None;
+                }
+            }
+        }
+    });
+}
+
+/// Open a server log for appending, rotating it first if it has grown large.
+/// Whether `client` is the connection of the running server `name`.
+fn is_client_of(
+    servers: &HashMap<String, McpServerHandle>,
+    name: &str,
+    client: &Arc<McpClient>,
+) -> bool {
+    servers
+        .get(name)
+        .is_some_and(|handle| Arc::ptr_eq(&handle.client, client))
+}
+
+async fn open_server_log(path: &Path) -> std::io::Result<tokio::fs::File> {
+    if let Some(parent) = path.parent() {
+        tokio::fs::create_dir_all(parent).await?;
+    }
+    if let Ok(meta) = tokio::fs::metadata(path).await {
+        if meta.len() > MAX_LOG_BYTES {
+            tokio::fs::rename(path, path.with_extension("log.old")).await?;
+        }
+    }
+
+    let mut file = tokio::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .await?;
+    let started = chrono::Local::now().format("%Y-%m-%d %H:%M:%S");
+    file.write_all(format!("--- started {} ---\n", started).as_bytes())
+        .await?;
+    Ok(file)
 }
 
 impl Default for McpManager {
@@ -288,6 +904,17 @@ impl Default for McpManager {
     }
 }
 
+/// Convert a serdes-ai-mcp response into Spot's own type.
+fn from_wire<T, U>(value: T) -> Result<U, McpManagerError>
+where
+    T: serde::Serialize,
+    U: serde::de::DeserializeOwned,
+{
+    serde_json::to_value(value)
+        .and_then(serde_json::from_value)
+        .map_err(|e| McpManagerError::InvalidResponse(e.to_string()))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1137,12 +1764,27 @@ mod tests {
                 McpManagerError::ServerNotFound(_) => {}
                 McpManagerError::AlreadyRunning(_) => {}
                 McpManagerError::NotRunning(_) => {}
+                McpManagerError::Launch { .. } => {}
+                McpManagerError::MissingUrl(_) => {}
+                McpManagerError::ResourceNotFound(_) => {}
+                McpManagerError::PromptNotFound(_) => {}
+                McpManagerError::InvalidPromptArguments(_) => {}
+                McpManagerError::InvalidResponse(_) => {}
             }
         }
 
         check_variant(&McpManagerError::ServerNotFound("x".into()));
         check_variant(&McpManagerError::AlreadyRunning("x".into()));
         check_variant(&McpManagerError::NotRunning("x".into()));
+        check_variant(&McpManagerError::Launch {
+            name: "x".into(),
+            source: std::io::Error::other("boom"),
+        });
+        check_variant(&McpManagerError::MissingUrl("x".into()));
+        check_variant(&McpManagerError::ResourceNotFound("x".into()));
+        check_variant(&McpManagerError::PromptNotFound("x".into()));
+        check_variant(&McpManagerError::InvalidPromptArguments("x".into()));
+        check_variant(&McpManagerError::InvalidResponse("x".into()));
 
         // Config variant
         use super::super::config::McpConfigError;
@@ -1447,4 +2089,425 @@ mod tests {
         // GitHub should not be in enabled list
         assert!(enabled.iter().all(|(name, _)| *name != "github"));
     }
+
+    // =========================================================================
+    // Launch Command Tests
+    // =========================================================================
+
+    #[test]
+    fn test_stdio_command_applies_args_env_and_cwd() {
+        let entry = McpServerEntry::new("github-server")
+            .with_args(vec!["--stdio".to_string()])
+            .with_env("GITHUB_TOKEN", "ghp_test")
+            .with_cwd("/tmp");
+
+        let command = stdio_command(&entry);
+        let command = command.as_std();
+        assert_eq!(command.get_program(), "github-server");
+        assert_eq!(command.get_args().collect::<Vec<_>>(), vec!["--stdio"]);
+        let envs: HashMap<_, _> = command.get_envs().collect();
+        assert_eq!(
+            envs.get(std::ffi::OsStr::new("GITHUB_TOKEN")),
+            Some(&Some(std::ffi::OsStr::new("ghp_test")))
+        );
+        assert_eq!(command.get_current_dir(), Some(Path::new("/tmp")));
+    }
+
+    #[test]
+    fn test_stdio_command_clean_env_keeps_only_baseline() {
+        std::env::set_var("MCP_TEST_NOT_PASSED", "1");
+        let entry = McpServerEntry::new("server")
+            .with_clean_env()
+            .with_env("API_KEY", "k");
+
+        let command = stdio_command(&entry);
+        let names: Vec<String> = command
+            .as_std()
+            .get_envs()
+            .map(|(k, _)| k.to_string_lossy().into_owned())
+            .collect();
+        assert!(names.contains(&"API_KEY".to_string()));
+        assert!(!names.contains(&"MCP_TEST_NOT_PASSED".to_string()));
+        if std::env::var_os("PATH").is_some() {
+            assert!(names.contains(&"PATH".to_string()));
+        }
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_stdio_command_process_sees_env_and_cwd() {
+        let dir = tempfile::tempdir().unwrap();
+        let entry = McpServerEntry::new("sh")
+            .with_args(vec![
+                "-c".to_string(),
+                "printf '%s %s' \"$MCP_TEST_TOKEN\" \"$(pwd)\"".to_string(),
+            ])
+            .with_env("MCP_TEST_TOKEN", "secret")
+            .with_cwd(dir.path().to_string_lossy())
+            .with_clean_env();
+
+        let output = stdio_command(&entry).output().await.unwrap();
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let cwd = dir.path().canonicalize().unwrap();
+        assert_eq!(stdout, format!("secret {}", cwd.display()));
+    }
+
+    #[tokio::test]
+    async fn test_start_server_missing_cwd() {
+        let mut config = McpConfig::new();
+        config.add_server(
+            "scoped",
+            McpServerEntry::new("server").with_cwd("/nonexistent/mcp/dir/xyz"),
+        );
+        let manager = McpManager::with_config(config);
+
+        let err = manager.start_server("scoped").await.unwrap_err();
+        assert!(matches!(err, McpManagerError::Launch { .. }));
+        assert!(err.to_string().contains("does not exist"));
+        assert!(!manager.is_running("scoped").await);
+    }
+
+    #[tokio::test]
+    async fn test_start_remote_servers() {
+        use super::super::test_server::{StandInMode, StandInServer};
+
+        let http = StandInServer::start(StandInMode::Json).await;
+        let sse = StandInServer::start(StandInMode::Sse).await;
+        let mut config = McpConfig::new();
+        config.add_server(
+            "team-http",
+            McpServerEntry::remote(http.url("/mcp")).with_bearer_token("t0ken"),
+        );
+        config.add_server(
+            "team-sse",
+            McpServerEntry::remote(sse.url("/sse")).with_bearer_token("t0ken"),
+        );
+        let manager = McpManager::with_config(config);
+
+        manager.start_all().await.unwrap();
+        assert!(manager.is_running("team-http").await);
+        assert!(manager.is_running("team-sse").await);
+
+        let all_tools = manager.list_all_tools().await;
+        assert_eq!(all_tools["team-http"][0].name, "echo");
+        assert_eq!(all_tools["team-sse"][0].name, "echo");
+
+        manager.stop_all().await.unwrap();
+        assert!(manager.running_servers().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_start_remote_server_without_url() {
+        let mut config = McpConfig::new();
+        config.add_server(
+            "remote",
+            McpServerEntry::new("").with_transport(McpTransportKind::Http),
+        );
+        let manager = McpManager::with_config(config);
+
+        let err = manager.start_server("remote").await.unwrap_err();
+        assert!(matches!(err, McpManagerError::MissingUrl(_)));
+    }
+
+    async fn start_stand_in(server: &super::super::test_server::StandInServer) -> McpManager {
+        let mut config = McpConfig::new();
+        config.add_server(
+            "team",
+            McpServerEntry::remote(server.url("/mcp")).with_bearer_token("t0ken"),
+        );
+        let manager = McpManager::with_config(config);
+        manager.start_server("team").await.unwrap();
+        manager
+    }
+
+    #[tokio::test]
+    async fn test_resources_and_prompts() {
+        use super::super::test_server::{StandInMode, StandInServer};
+
+        let server = StandInServer::start(StandInMode::Json).await;
+        let manager = start_stand_in(&server).await;
+
+        let resources = manager.list_resources("team").await.unwrap();
+        assert_eq!(resources[0].name, "guide.md");
+        assert_eq!(resources[0].mime_type.as_deref(), Some("text/markdown"));
+        assert_eq!(manager.list_all_resources().await["team"], resources);
+
+        let contents = manager
+            .read_resource("team", "file:///guide.md")
+            .await
+            .unwrap();
+        assert_eq!(contents[0].text.as_deref(), Some("# Guide"));
+
+        let prompts = manager.list_prompts("team").await.unwrap();
+        assert_eq!(prompts[0].name, "summarize");
+        assert!(prompts[0].arguments[0].required);
+
+        let arguments = HashMap::from([("topic".to_string(), "releases".to_string())]);
+        let prompt = manager
+            .get_prompt("team", "summarize", arguments)
+            .await
+            .unwrap();
+        assert_eq!(prompt.to_text(), "Summarize releases");
+
+        let err = manager.list_prompts("other").await.unwrap_err();
+        assert!(matches!(err, McpManagerError::NotRunning(_)));
+
+        manager.stop_all().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_expand_input() {
+        use super::super::test_server::{StandInMode, StandInServer};
+
+        let server = StandInServer::start(StandInMode::Json).await;
+        let manager = start_stand_in(&server).await;
+
+        // Prompt commands are replaced by the rendered prompt
+        let text = manager
+            .expand_input("/team:summarize the release notes")
+            .await
+            .unwrap();
+        assert_eq!(text, "Summarize the release notes");
+
+        // Mentioned resources are appended
+        let text = manager
+            .expand_input("Check @team:guide.md, please")
+            .await
+            .unwrap();
+        assert!(text.starts_with("Check @team:guide.md, please\n\n--- MCP Resources ---"));
+        assert!(text.contains("# Guide"));
+
+        // Unknown servers and plain input pass through
+        for input in ["/elsewhere:thing", "mail @bob:tomorrow", "plain text"] {
+            assert_eq!(manager.expand_input(input).await.unwrap(), input);
+        }
+
+        let err = manager.expand_input("/team:translate").await.unwrap_err();
+        assert!(matches!(err, McpManagerError::PromptNotFound(_)));
+        let err = manager.expand_input("/team:summarize").await.unwrap_err();
+        assert!(matches!(err, McpManagerError::InvalidPromptArguments(_)));
+
+        manager.stop_all().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_expand_input_warns_about_unreadable_mentions() {
+        use super::super::test_server::{StandInMode, StandInServer};
+
+        let server = StandInServer::start(StandInMode::Json).await;
+        let bus = crate::messaging::MessageBus::new();
+        let mut receiver = bus.subscribe();
+        let mut config = McpConfig::new();
+        config.add_server(
+            "team",
+            McpServerEntry::remote(server.url("/mcp")).with_bearer_token("t0ken"),
+        );
+        config.add_server("idle", McpServerEntry::new("spot-test-idle-server"));
+        let manager = McpManager::with_config(config).with_bus(bus.sender());
+        manager.start_server("team").await.unwrap();
+
+        // The message is still sent, with the readable mention attached
+        let input = "Check @team:guide.md, @team:missing.md and @idle:notes.md";
+        let text = manager.expand_input(input).await.unwrap();
+        assert!(text.starts_with(input));
+        assert!(text.contains("# Guide"));
+
+        let mut warnings = Vec::new();
+        while let Ok(Some(message)) = receiver.try_recv() {
+            if let Message::Text(warning) = message {
+                warnings.push(warning.text);
+            }
+        }
+        assert_eq!(warnings.len(), 2);
+        assert!(warnings[0].starts_with("MCP: @team:missing.md was not attached"));
+        assert!(warnings[1].starts_with("MCP: @idle:notes.md was not attached"));
+
+        manager.stop_all().await.unwrap();
+    }
+
+    // =========================================================================
+    // Health and Supervision Tests
+    // =========================================================================
+
+    fn immediate_restarts() -> McpSupervisorConfig {
+        McpSupervisorConfig {
+            initial_backoff: Duration::ZERO,
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_status_follows_start_and_stop() {
+        use super::super::test_server::{StandInMode, StandInServer};
+
+        let server = StandInServer::start(StandInMode::Json).await;
+        let bus = crate::messaging::MessageBus::new();
+        let mut receiver = bus.subscribe();
+        let mut config = McpConfig::new();
+        config.add_server(
+            "team",
+            McpServerEntry::remote(server.url("/mcp")).with_bearer_token("t0ken"),
+        );
+        let manager = McpManager::with_config(config).with_bus(bus.sender());
+        assert!(manager.server_status("team").is_none());
+
+        manager.start_server("team").await.unwrap();
+        let status = manager.server_status("team").unwrap();
+        assert_eq!(status.state, McpServerState::Ready);
+        assert_eq!(status.tool_count, Some(1));
+        assert!(status.last_error.is_none());
+
+        manager.stop_server("team").await.unwrap();
+        assert_eq!(
+            manager.server_status("team").unwrap().state,
+            McpServerState::Stopped
+        );
+
+        let mut published = Vec::new();
+        while let Ok(Some(message)) = receiver.try_recv() {
+            if let Message::McpStatus(status) = message {
+                published.push(status.state);
+            }
+        }
+        assert_eq!(
+            published,
+            [
+                McpServerState::Starting,
+                McpServerState::Ready,
+                McpServerState::Stopped
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_failed_start_is_recorded_and_retried() {
+        let mut config = McpConfig::new();
+        config.add_server("broken", McpServerEntry::new("spot-test-no-such-command"));
+        let manager = McpManager::with_config(config);
+
+        assert!(manager.start_server("broken").await.is_err());
+        let status = manager.server_status("broken").unwrap();
+        assert_eq!(status.state, McpServerState::Failed);
+        assert!(status.last_error.unwrap().contains("Failed to launch"));
+        assert_eq!(status.failures, 1);
+
+        // The first pass schedules the restart, the next one attempts it
+        let supervisor = immediate_restarts();
+        manager.restart_due(&supervisor).await;
+        assert!(manager
+            .server_status("broken")
+            .unwrap()
+            .next_restart
+            .is_some());
+        manager.restart_due(&supervisor).await;
+
+        let status = manager.server_status("broken").unwrap();
+        assert_eq!(status.state, McpServerState::Failed);
+        assert_eq!(status.failures, 2);
+        assert_eq!(status.restarts, 0);
+        assert!(status.next_restart.is_some());
+        assert!(manager.server_statuses().contains_key("broken"));
+    }
+
+    #[tokio::test]
+    async fn test_health_check_marks_unresponsive_server_unhealthy() {
+        use super::super::test_server::{StandInMode, StandInServer};
+
+        let server = StandInServer::start(StandInMode::Json).await;
+        let manager = start_stand_in(&server).await;
+        let supervisor = McpSupervisorConfig {
+            check_timeout: Duration::from_secs(1),
+            ..immediate_restarts()
+        };
+
+        manager.check_health(&supervisor).await;
+        assert_eq!(
+            manager.server_status("team").unwrap().state,
+            McpServerState::Ready
+        );
+
+        drop(server);
+        manager.check_health(&supervisor).await;
+        let status = manager.server_status("team").unwrap();
+        assert_eq!(status.state, McpServerState::Unhealthy);
+        assert!(status.last_error.is_some());
+        assert!(!manager.is_running("team").await);
+    }
+
+    #[tokio::test]
+    async fn test_unhealthy_server_is_restarted() {
+        use super::super::test_server::{StandInMode, StandInServer};
+
+        let server = StandInServer::start(StandInMode::Json).await;
+        let manager = start_stand_in(&server).await;
+        let client = manager.get_handle("team").await.unwrap();
+        manager
+            .mark_unhealthy("team", &client, "stopped answering".to_string())
+            .await;
+        assert!(!manager.is_running("team").await);
+
+        let supervisor = immediate_restarts();
+        manager.restart_due(&supervisor).await;
+        manager.restart_due(&supervisor).await;
+
+        let status = manager.server_status("team").unwrap();
+        assert_eq!(status.state, McpServerState::Ready);
+        assert_eq!(status.restarts, 1);
+        assert_eq!(status.failures, 0);
+        assert!(status.last_error.is_none());
+        assert!(manager.is_running("team").await);
+
+        manager.stop_all().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_health_results_ignored_after_stop() {
+        use super::super::test_server::{StandInMode, StandInServer};
+
+        let server = StandInServer::start(StandInMode::Json).await;
+        let manager = start_stand_in(&server).await;
+        let client = manager.get_handle("team").await.unwrap();
+
+        // A check that was in flight when the server was stopped
+        manager.stop_server("team").await.unwrap();
+        let _ = manager
+            .check_server("team", &client, Duration::from_secs(1))
+            .await;
+        manager
+            .mark_unhealthy("team", &client, "stopped answering".to_string())
+            .await;
+        let status = manager.server_status("team").unwrap();
+        assert_eq!(status.state, McpServerState::Stopped);
+        assert_eq!(status.failures, 0);
+        assert!(status.next_restart.is_none());
+
+        // Nor does it disconnect a server started again since
+        manager.start_server("team").await.unwrap();
+        manager
+            .mark_unhealthy("team", &client, "stopped answering".to_string())
+            .await;
+        assert_eq!(
+            manager.server_status("team").unwrap().state,
+            McpServerState::Ready
+        );
+        assert!(manager.is_running("team").await);
+
+        manager.stop_all().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_list_all_tools_marks_failing_server_unhealthy() {
+        use super::super::test_server::{StandInMode, StandInServer};
+
+        let server = StandInServer::start(StandInMode::Json).await;
+        let manager = start_stand_in(&server).await;
+        drop(server);
+
+        assert!(manager.list_all_tools().await.is_empty());
+        assert_eq!(
+            manager.server_status("team").unwrap().state,
+            McpServerState::Unhealthy
+        );
+        assert!(manager.running_servers().await.is_empty());
+    }
 }
@@ -3,6 +3,9 @@
 //! This module provides:
 //! - Configuration loading from `~/.spot/mcp_servers.json`
 //! - MCP server lifecycle management (start/stop)
+//! - Health checks and automatic restarts of failed servers
+//! - Local (stdio) and remote (streamable HTTP, SSE) servers
+//! - Resources (`@server:resource` mentions) and prompts (`/server:prompt`)
 //! - Integration with the agent executor via McpToolset
 //!
 //! ## Configuration File Format
@@ -20,13 +23,40 @@
 //!       "command": "npx",
 //!       "args": ["-y", "@modelcontextprotocol/server-github"],
 //!       "env": {
-//!         "GITHUB_TOKEN": "${GITHUB_TOKEN}"
-//!       }
+//!         "GITHUB_PERSONAL_ACCESS_TOKEN": "${GITHUB_TOKEN}"
+//!       },
+//!       "clean_env": true,
+//!       "cwd": "${HOME}/projects"
+//!     },
+//!     "team-tools": {
+//!       "type": "http",
+//!       "url": "https://mcp.example.com/mcp",
+//!       "headers": { "X-Team": "platform" },
+//!       "bearer_token": "${TEAM_MCP_TOKEN}"
 //!     }
 //!   }
 //! }
 //! ```
 //!
+//! `${VAR}` references in `args`, `env`, `cwd`, `url`, `headers` and
+//! `bearer_token` are expanded from Spot's environment. Servers inherit
+//! Spot's environment plus `env`; with `clean_env` they only get `PATH`,
+//! `HOME`, locale and temp-dir variables plus `env`. Each server's stderr is
+//! appended to `~/.spot/logs/mcp/<name>.log`.
+//!
+//! Entries with a `url` are remote servers, reached over streamable HTTP
+//! (`"type": "http"`) or the older HTTP+SSE transport (`"type": "sse"`).
+//! Without a `type`, URLs ending in `/sse` use SSE and others use HTTP.
+//!
+//! Agents see MCP tools as `server__tool` (see [`namespaced_tool_name`]).
+//! Besides attaching whole servers, individual tools can be allowed or
+//! denied per agent with an [`McpToolFilter`].
+//!
+//! Every started server has an [`McpServerStatus`]. [`McpManager::supervise`]
+//! checks running servers every 30 seconds; servers that stop answering, or
+//! that failed to start, are restarted with exponential backoff. Status
+//! changes are published on the message bus as `Message::McpStatus`.
+//!
 //! ## Usage
 //!
 //! ```ignore
@@ -43,7 +73,20 @@
 //! ```
 
 mod config;
+mod context;
+mod health;
 mod manager;
+mod naming;
+#[cfg(test)]
+pub(crate) mod test_server;
+mod transport;
 
-pub use config::{McpConfig, McpServerEntry};
+pub use config::{McpConfig, McpServerEntry, McpTransportKind, RemoteServerForm};
+pub use context::{
+    parse_prompt_command, parse_resource_mentions, prompt_catalog, resource_catalog, McpPrompt,
+    McpPromptArgument, McpPromptMessage, McpPromptResult, McpResource, McpResourceContents,
+    PromptCommand, ResourceMention,
+};
+pub use health::{McpServerState, McpServerStatus, McpSupervisorConfig};
 pub use manager::McpManager;
+pub use naming::{namespaced_tool_name, McpToolFilter, MCP_TOOL_SEPARATOR};
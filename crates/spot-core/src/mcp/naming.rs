@@ -0,0 +1,180 @@
+//! Names under which MCP tools are exposed to models.
+//!
+//! MCP tools are registered as `server__tool`, so two servers exposing
+//! `search`, or a server exposing `read_file`, don't collide with each
+//! other or with built-in tools.
+
+/// Separator between the server and tool parts of an exposed name.
+pub const MCP_TOOL_SEPARATOR: &str = "__";
+
+/// Longest tool name accepted by all supported providers.
+const MAX_TOOL_NAME_LEN: usize = 64;
+
+/// Build the exposed name for `tool` on `server`.
+///
+/// Characters providers reject in tool names are replaced with `_`. Names
+/// longer than 64 characters are cut short and end in a hash of the full
+/// name, so long names sharing a prefix stay distinct. Names that still
+/// collide (`a.b` and `a_b`) are skipped when tools are registered.
+pub fn namespaced_tool_name(server: &str, tool: &str) -> String {
+    let name = format!(
+        "{}{}{}",
+        sanitize_part(server),
+        MCP_TOOL_SEPARATOR,
+        sanitize_part(tool)
+    );
+    if name.len() <= MAX_TOOL_NAME_LEN {
+        return name;
+    }
+
+    let suffix = format!("_{:08x}", fnv1a(server, tool));
+    let mut short: String = name
+        .chars()
+        .take(MAX_TOOL_NAME_LEN - suffix.len())
+        .collect();
+    short.push_str(&suffix);
+    short
+}
+
+/// 32-bit FNV-1a hash of `server` and `tool`; stable across releases, so
+/// truncated names in saved tool filters keep matching.
+fn fnv1a(server: &str, tool: &str) -> u32 {
+    let bytes = server.bytes().chain([0]).chain(tool.bytes());
+    bytes.fold(0x811c_9dc5, |hash, byte| {
+        (hash ^ u32::from(byte)).wrapping_mul(0x0100_0193)
+    })
+}
+
+/// Replace anything outside `[A-Za-z0-9_-]` with `_`.
+fn sanitize_part(part: &str) -> String {
+    part.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '_' || c == '-' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+/// Per-agent allow/deny lists of individual MCP tools.
+///
+/// Entries are exposed names (`server__tool`); a trailing `*` matches by
+/// prefix, so `github__*` covers every tool of the `github` server.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct McpToolFilter {
+    /// If non-empty, only matching tools are registered
+    pub allow: Vec<String>,
+    /// Matching tools are never registered (takes precedence over `allow`)
+    pub deny: Vec<String>,
+}
+
+impl McpToolFilter {
+    /// Whether the tool exposed as `name` may be registered.
+    pub fn permits(&self, name: &str) -> bool {
+        let matches = |entry: &String| match entry.strip_suffix('*') {
+            Some(prefix) => name.starts_with(prefix),
+            None => entry == name,
+        };
+
+        if self.deny.iter().any(matches) {
+            return false;
+        }
+        self.allow.is_empty() || self.allow.iter().any(matches)
+    }
+
+    /// Whether neither list has entries.
+    pub fn is_empty(&self) -> bool {
+        self.allow.is_empty() && self.deny.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_namespaced_tool_name() {
+        assert_eq!(namespaced_tool_name("github", "search"), "github__search");
+        assert_eq!(
+            namespaced_tool_name("my-server", "read_file"),
+            "my-server__read_file"
+        );
+    }
+
+    #[test]
+    fn test_namespaced_tool_name_sanitizes() {
+        assert_eq!(
+            namespaced_tool_name("team.tools", "files/list"),
+            "team_tools__files_list"
+        );
+        assert_eq!(namespaced_tool_name("café", "a b"), "caf___a_b");
+    }
+
+    #[test]
+    fn test_namespaced_tool_name_truncates() {
+        let name = namespaced_tool_name("server", &"x".repeat(100));
+        assert_eq!(name.len(), 64);
+        assert!(name.starts_with("server__xxx"));
+    }
+
+    #[test]
+    fn test_namespaced_tool_name_truncated_names_stay_distinct() {
+        let prefix = "x".repeat(70);
+        let a = namespaced_tool_name("server", &format!("{}_a", prefix));
+        let b = namespaced_tool_name("server", &format!("{}_b", prefix));
+        assert_ne!(a, b);
+        assert_eq!(a.len(), 64);
+        assert_eq!(b.len(), 64);
+
+        // Stable, so saved filters keep matching
+        assert_eq!(a, namespaced_tool_name("server", &format!("{}_a", prefix)));
+        // Names that fit are left alone
+        let fits = "y".repeat(64 - "server__".len());
+        assert_eq!(
+            namespaced_tool_name("server", &fits),
+            format!("server__{}", fits)
+        );
+    }
+
+    #[test]
+    fn test_filter_empty_permits_everything() {
+        let filter = McpToolFilter::default();
+        assert!(filter.is_empty());
+        assert!(filter.permits("github__search"));
+    }
+
+    #[test]
+    fn test_filter_allow_list() {
+        let filter = McpToolFilter {
+            allow: vec!["github__search".to_string(), "fs__*".to_string()],
+            deny: vec![],
+        };
+        assert!(filter.permits("github__search"));
+        assert!(filter.permits("fs__read_file"));
+        assert!(!filter.permits("github__create_issue"));
+    }
+
+    #[test]
+    fn test_filter_deny_wins_over_allow() {
+        let filter = McpToolFilter {
+            allow: vec!["github__*".to_string()],
+            deny: vec!["github__delete_repo".to_string()],
+        };
+        assert!(filter.permits("github__search"));
+        assert!(!filter.permits("github__delete_repo"));
+        assert!(!filter.permits("fs__read_file"));
+    }
+
+    #[test]
+    fn test_filter_deny_only() {
+        let filter = McpToolFilter {
+            allow: vec![],
+            deny: vec!["fs__*".to_string()],
+        };
+        assert!(!filter.is_empty());
+        assert!(!filter.permits("fs__write_file"));
+        assert!(filter.permits("github__search"));
+    }
+}
@@ -0,0 +1,297 @@
+//! In-process stand-in for a remote MCP server, for transport tests.
+//!
+//! Speaks just enough HTTP/1.1 and MCP to answer `initialize`,
+//! `tools/list`, `tools/call` (an `echo` tool), resources (`guide.md`),
+//! prompts (`summarize`) and `ping`, and records every request it receives. Requests without `Authorization: Bearer
+//! t0ken` are rejected with 401.
+
+use serde_json::{json, Value as JsonValue};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::mpsc;
+
+const TOKEN: &str = "Bearer t0ken";
+const SESSION_ID: &str = "stand-in-session";
+
+/// How the stand-in replies.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StandInMode {
+    /// Streamable HTTP with `application/json` replies.
+    Json,
+    /// Streamable HTTP with `text/event-stream` replies.
+    EventStream,
+    /// HTTP+SSE: `GET /sse` streams replies to `POST /messages`.
+    Sse,
+}
+
+/// A request the stand-in received.
+#[derive(Debug, Clone)]
+pub struct RecordedRequest {
+    pub method: String,
+    pub path: String,
+    /// Header names are lowercased.
+    pub headers: HashMap<String, String>,
+    pub body: String,
+}
+
+impl RecordedRequest {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.get(&name.to_lowercase()).map(String::as_str)
+    }
+}
+
+struct Shared {
+    mode: StandInMode,
+    requests: Mutex<Vec<RecordedRequest>>,
+    events_tx: mpsc::UnboundedSender<String>,
+    events_rx: tokio::sync::Mutex<Option<mpsc::UnboundedReceiver<String>>>,
+}
+
+/// A running stand-in server; stops when dropped.
+pub struct StandInServer {
+    addr: std::net::SocketAddr,
+    shared: Arc<Shared>,
+    task: tokio::task::JoinHandle<()>,
+}
+
+impl StandInServer {
+    pub async fn start(mode: StandInMode) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let (events_tx, events_rx) = mpsc::unbounded_channel();
+        let shared = Arc::new(Shared {
+            mode,
+            requests: Mutex::new(Vec::new()),
+            events_tx,
+            events_rx: tokio::sync::Mutex::new(Some(events_rx)),
+        });
+
+        let task = {
+            let shared = Arc::clone(&shared);
+            tokio::spawn(async move {
+                while let Ok((stream, _)) = listener.accept().await {
+                    tokio::spawn(handle_connection(stream, Arc::clone(&shared)));
+                }
+            })
+        };
+
+        Self { addr, shared, task }
+    }
+
+    pub fn url(&self, path: &str) -> String {
+        format!("http://{}{}", self.addr, path)
+    }
+
+    pub fn requests(&self) -> Vec<RecordedRequest> {
+        self.shared.requests.lock().unwrap().clone()
+    }
+}
+
+impl Drop for StandInServer {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+async fn read_request(stream: &mut TcpStream) -> Option<RecordedRequest> {
+    let mut buf = Vec::new();
+    let mut chunk = [0u8; 4096];
+    let header_end = loop {
+        if let Some(pos) = buf.windows(4).position(|w| w == b"\r\n\r\n") {
+            break pos;
+        }
+        let n = stream.read(&mut chunk).await.ok()?;
+        if n == 0 {
+            return None;
+        }
+        buf.extend_from_slice(&chunk[..n]);
+    };
+
+    let head = String::from_utf8_lossy(&buf[..header_end]).to_string();
+    let mut lines = head.split("\r\n");
+    let mut request_line = lines.next()?.split(' ');
+    let method = request_line.next()?.to_string();
+    let path = request_line.next()?.to_string();
+    let headers: HashMap<String, String> = lines
+        .filter_map(|line| line.split_once(':'))
+        .map(|(k, v)| (k.trim().to_lowercase(), v.trim().to_string()))
+        .collect();
+
+    let length: usize = headers
+        .get("content-length")
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(0);
+    let mut body = buf[header_end + 4..].to_vec();
+    while body.len() < length {
+        let n = stream.read(&mut chunk).await.ok()?;
+        if n == 0 {
+            break;
+        }
+        body.extend_from_slice(&chunk[..n]);
+    }
+
+    Some(RecordedRequest {
+        method,
+        path,
+        headers,
+        body: String::from_utf8_lossy(&body).to_string(),
+    })
+}
+
+async fn respond(stream: &mut TcpStream, status: &str, headers: &[(&str, &str)], body: &str) {
+    let mut response = format!(
+        "HTTP/1.1 {}\r\nContent-Length: {}\r\nConnection: close\r\n",
+        status,
+        body.len()
+    );
+    for (name, value) in headers {
+        response.push_str(&format!("{}: {}\r\n", name, value));
+    }
+    response.push_str("\r\n");
+    response.push_str(body);
+    let _ = stream.write_all(response.as_bytes()).await;
+    let _ = stream.shutdown().await;
+}
+
+/// The JSON-RPC reply to `message`, or `None` for notifications.
+fn reply(message: &JsonValue) -> Option<JsonValue> {
+    let id = message.get("id")?.clone();
+    let method = message.get("method").and_then(JsonValue::as_str)?;
+    let result = match method {
+        "initialize" => json!({
+            "protocolVersion": "2025-03-26",
+            "capabilities": { "tools": {}, "resources": {}, "prompts": {} },
+            "serverInfo": { "name": "stand-in", "version": "1.0.0" }
+        }),
+        "tools/list" => json!({
+            "tools": [{
+                "name": "echo",
+                "description": "Echo the text argument",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": { "text": { "type": "string" } }
+                }
+            }]
+        }),
+        "tools/call" => {
+            let text = message["params"]["arguments"]["text"]
+                .as_str()
+                .unwrap_or_default();
+            json!({ "content": [{ "type": "text", "text": text }] })
+        }
+        "resources/list" => json!({
+            "resources": [{
+                "uri": "file:///guide.md",
+                "name": "guide.md",
+                "mimeType": "text/markdown"
+            }]
+        }),
+        "resources/read" if message["params"]["uri"] == "file:///guide.md" => json!({
+            "contents": [{
+                "uri": "file:///guide.md",
+                "mimeType": "text/markdown",
+                "text": "# Guide"
+            }]
+        }),
+        "prompts/list" => json!({
+            "prompts": [{
+                "name": "summarize",
+                "description": "Summarize a topic",
+                "arguments": [{ "name": "topic", "required": true }]
+            }]
+        }),
+        "prompts/get" => {
+            let topic = message["params"]["arguments"]["topic"]
+                .as_str()
+                .unwrap_or_default();
+            json!({
+                "messages": [{
+                    "role": "user",
+                    "content": { "type": "text", "text": format!("Summarize {}", topic) }
+                }]
+            })
+        }
+        "ping" => json!({}),
+        _ => {
+            return Some(json!({
+                "jsonrpc": "2.0",
+                "id": id,
+                "error": { "code": -32601, "message": "Method not found" }
+            }))
+        }
+    };
+    Some(json!({ "jsonrpc": "2.0", "id": id, "result": result }))
+}
+
+async fn handle_connection(mut stream: TcpStream, shared: Arc<Shared>) {
+    let Some(request) = read_request(&mut stream).await else {
+        return;
+    };
+    shared.requests.lock().unwrap().push(request.clone());
+
+    if request.header("authorization") != Some(TOKEN) {
+        respond(
+            &mut stream,
+            "401 Unauthorized",
+            &[],
+            "missing or invalid token",
+        )
+        .await;
+        return;
+    }
+
+    match (shared.mode, request.method.as_str()) {
+        (StandInMode::Sse, "GET") => {
+            let Some(mut events) = shared.events_rx.lock().await.take() else {
+                respond(&mut stream, "409 Conflict", &[], "stream already open").await;
+                return;
+            };
+            let head = "HTTP/1.1 200 OK\r\nContent-Type: text/event-stream\r\n\
+                        Cache-Control: no-cache\r\nConnection: close\r\n\r\n\
+                        event: endpoint\ndata: /messages?session=1\n\n";
+            if stream.write_all(head.as_bytes()).await.is_err() {
+                return;
+            }
+            while let Some(data) = events.recv().await {
+                let event = format!("event: message\ndata: {}\n\n", data);
+                if stream.write_all(event.as_bytes()).await.is_err() {
+                    return;
+                }
+            }
+        }
+        (StandInMode::Sse, "POST") => {
+            if let Some(reply) = serde_json::from_str::<JsonValue>(&request.body)
+                .ok()
+                .and_then(|m| reply(&m))
+            {
+                let _ = shared.events_tx.send(reply.to_string());
+            }
+            respond(&mut stream, "202 Accepted", &[], "").await;
+        }
+        (_, "POST") => {
+            let message: JsonValue = serde_json::from_str(&request.body).unwrap_or_default();
+            let session = [("Mcp-Session-Id", SESSION_ID)];
+            match reply(&message) {
+                None => respond(&mut stream, "202 Accepted", &session, "").await,
+                Some(reply) if shared.mode == StandInMode::EventStream => {
+                    // Lead with a notification the client has to skip
+                    let body = format!(
+                        "event: message\ndata: {}\n\nevent: message\ndata: {}\n\n",
+                        json!({ "jsonrpc": "2.0", "method": "notifications/message", "params": {} }),
+                        reply
+                    );
+                    let headers = [session[0], ("Content-Type", "text/event-stream")];
+                    respond(&mut stream, "200 OK", &headers, &body).await;
+                }
+                Some(reply) => {
+                    let headers = [session[0], ("Content-Type", "application/json")];
+                    respond(&mut stream, "200 OK", &headers, &reply.to_string()).await;
+                }
+            }
+        }
+        (_, "DELETE") => respond(&mut stream, "200 OK", &[], "").await,
+        _ => respond(&mut stream, "405 Method Not Allowed", &[], "").await,
+    }
+}
@@ -0,0 +1,576 @@
+//! HTTP transports for remote MCP servers.
+//!
+//! - [`HttpTransport`] speaks streamable HTTP: every message is POSTed to
+//!   the server URL, and the reply comes back as JSON or as an event stream.
+//! - [`SseTransport`] speaks the older HTTP+SSE protocol: a GET opens an
+//!   event stream that announces a POST endpoint and carries all replies.
+//!
+//! Both send the entry's headers and bearer token with every request.
+
+use async_trait::async_trait;
+use futures::StreamExt;
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue, ACCEPT, AUTHORIZATION, CONTENT_TYPE};
+use serde_json::Value as JsonValue;
+use serdes_ai_mcp::transport::McpTransport;
+use serdes_ai_mcp::{JsonRpcNotification, JsonRpcRequest, JsonRpcResponse, McpError, McpResult};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::oneshot;
+use tokio::task::JoinHandle;
+use tracing::debug;
+
+/// Header carrying the streamable HTTP session ID.
+const SESSION_HEADER: &str = "mcp-session-id";
+
+/// How long an SSE server gets to announce its POST endpoint.
+const ENDPOINT_TIMEOUT: Duration = Duration::from_secs(10);
+
+fn transport_error(message: impl Into<String>) -> McpError {
+    McpError::Transport(message.into())
+}
+
+/// Build the headers sent with every request to a remote server.
+pub fn remote_headers(
+    headers: &HashMap<String, String>,
+    bearer_token: Option<&str>,
+) -> McpResult<HeaderMap> {
+    let mut map = HeaderMap::new();
+    for (name, value) in headers {
+        let name = HeaderName::from_bytes(name.as_bytes())
+            .map_err(|_| transport_error(format!("invalid header name '{}'", name)))?;
+        let value = HeaderValue::from_str(value)
+            .map_err(|_| transport_error(format!("invalid value for header '{}'", name)))?;
+        map.insert(name, value);
+    }
+    if let Some(token) = bearer_token.filter(|t| !t.is_empty()) {
+        let mut value = HeaderValue::from_str(&format!("Bearer {}", token))
+            .map_err(|_| transport_error("invalid bearer token"))?;
+        value.set_sensitive(true);
+        map.insert(AUTHORIZATION, value);
+    }
+    Ok(map)
+}
+
+/// Serialize a request, returning it along with its ID.
+fn encode_request(request: &JsonRpcRequest) -> McpResult<(JsonValue, JsonValue)> {
+    let body = serde_json::to_value(request).map_err(|e| transport_error(e.to_string()))?;
+    let id = body.get("id").cloned().unwrap_or(JsonValue::Null);
+    Ok((body, id))
+}
+
+/// Parse `data` as the response to request `id`, if it is one.
+fn decode_response(data: &str, id: &JsonValue) -> Option<JsonRpcResponse> {
+    let value: JsonValue = serde_json::from_str(data).ok()?;
+    if value.get("id") != Some(id)
+        || !(value.get("result").is_some() || value.get("error").is_some())
+    {
+        return None;
+    }
+    serde_json::from_value(value).ok()
+}
+
+/// Turn a non-success HTTP response into an error.
+async fn check_status(response: reqwest::Response) -> McpResult<reqwest::Response> {
+    let status = response.status();
+    if status.is_success() {
+        return Ok(response);
+    }
+    let body = response.text().await.unwrap_or_default();
+    let body = body.trim();
+    Err(transport_error(if body.is_empty() {
+        format!("HTTP {}", status)
+    } else {
+        format!("HTTP {}: {}", status, body)
+    }))
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Server-sent events
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// One server-sent event.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct SseEvent {
+    event: String,
+    data: String,
+}
+
+/// Incremental parser for a `text/event-stream` body.
+#[derive(Debug, Default)]
+struct SseParser {
+    buffer: Vec<u8>,
+    event: Option<String>,
+    data: Vec<String>,
+}
+
+impl SseParser {
+    /// Feed a chunk of the body, returning the events it completes.
+    fn push(&mut self, chunk: &[u8]) -> Vec<SseEvent> {
+        self.buffer.extend_from_slice(chunk);
+        let mut events = Vec::new();
+
+        while let Some(pos) = self.buffer.iter().position(|&b| b == b'\n') {
+            let line: Vec<u8> = self.buffer.drain(..=pos).collect();
+            let line = String::from_utf8_lossy(&line);
+            let line = line.trim_end_matches(['\n', '\r']);
+
+            if line.is_empty() {
+                if !self.data.is_empty() {
+                    events.push(SseEvent {
+                        event: self.event.take().unwrap_or_else(|| "message".to_string()),
+                        data: self.data.join("\n"),
+                    });
+                }
+                self.event = None;
+                self.data.clear();
+                continue;
+            }
+            if line.starts_with(':') {
+                continue;
+            }
+
+            let (field, value) = line.split_once(':').unwrap_or((line, ""));
+            let value = value.strip_prefix(' ').unwrap_or(value);
+            match field {
+                "event" => self.event = Some(value.to_string()),
+                "data" => self.data.push(value.to_string()),
+                _ => {}
+            }
+        }
+
+        events
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Streamable HTTP
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Streamable HTTP transport.
+pub struct HttpTransport {
+    client: reqwest::Client,
+    url: String,
+    headers: HeaderMap,
+    session_id: Mutex<Option<String>>,
+    connected: AtomicBool,
+}
+
+impl HttpTransport {
+    /// Create a transport that POSTs to `url` with `headers`.
+    pub fn new(url: impl Into<String>, headers: HeaderMap) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            url: url.into(),
+            headers,
+            session_id: Mutex::new(None),
+            connected: AtomicBool::new(true),
+        }
+    }
+
+    fn session_id(&self) -> Option<String> {
+        self.session_id.lock().ok().and_then(|s| s.clone())
+    }
+
+    async fn post(&self, body: &JsonValue) -> McpResult<reqwest::Response> {
+        let mut request = self
+            .client
+            .post(&self.url)
+            .headers(self.headers.clone())
+            .header(ACCEPT, "application/json, text/event-stream")
+            .json(body);
+        if let Some(session_id) = self.session_id() {
+            request = request.header(SESSION_HEADER, session_id);
+        }
+
+        let response = request
+            .send()
+            .await
+            .map_err(|e| transport_error(format!("POST {} failed: {}", self.url, e)))?;
+        let response = check_status(response).await?;
+
+        if let Some(session_id) = response
+            .headers()
+            .get(SESSION_HEADER)
+            .and_then(|v| v.to_str().ok())
+        {
+            if let Ok(mut current) = self.session_id.lock() {
+                *current = Some(session_id.to_string());
+            }
+        }
+        Ok(response)
+    }
+}
+
+#[async_trait]
+impl McpTransport for HttpTransport {
+    async fn request(&self, request: &JsonRpcRequest) -> McpResult<JsonRpcResponse> {
+        let (body, id) = encode_request(request)?;
+        let response = self.post(&body).await?;
+
+        let is_stream = response
+            .headers()
+            .get(CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .is_some_and(|v| v.starts_with("text/event-stream"));
+        if !is_stream {
+            let text = response
+                .text()
+                .await
+                .map_err(|e| transport_error(e.to_string()))?;
+            return decode_response(&text, &id)
+                .ok_or_else(|| transport_error(format!("unexpected response: {}", text)));
+        }
+
+        // The server may send notifications before the response
+        let mut parser = SseParser::default();
+        let mut stream = response.bytes_stream();
+        while let Some(chunk) = stream.next().await {
+            let chunk = chunk.map_err(|e| transport_error(e.to_string()))?;
+            for event in parser.push(&chunk) {
+                match decode_response(&event.data, &id) {
+                    Some(response) => return Ok(response),
+                    None => debug!(url = %self.url, data = %event.data, "Skipping MCP event"),
+                }
+            }
+        }
+        Err(transport_error("event stream ended without a response"))
+    }
+
+    async fn notify(&self, notification: &JsonRpcNotification) -> McpResult<()> {
+        let body =
+            serde_json::to_value(notification).map_err(|e| transport_error(e.to_string()))?;
+        self.post(&body).await?;
+        Ok(())
+    }
+
+    async fn close(&self) -> McpResult<()> {
+        self.connected.store(false, Ordering::SeqCst);
+        // Ending the session is a courtesy; servers may not support it
+        if let Some(session_id) = self.session_id() {
+            let _ = self
+                .client
+                .delete(&self.url)
+                .headers(self.headers.clone())
+                .header(SESSION_HEADER, session_id)
+                .send()
+                .await;
+        }
+        Ok(())
+    }
+
+    fn is_connected(&self) -> bool {
+        self.connected.load(Ordering::SeqCst)
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// HTTP+SSE
+// ─────────────────────────────────────────────────────────────────────────────
+
+type PendingRequests = Arc<Mutex<HashMap<String, oneshot::Sender<JsonRpcResponse>>>>;
+
+/// HTTP+SSE transport.
+pub struct SseTransport {
+    client: reqwest::Client,
+    endpoint: reqwest::Url,
+    headers: HeaderMap,
+    pending: PendingRequests,
+    connected: Arc<AtomicBool>,
+    reader: JoinHandle<()>,
+}
+
+impl SseTransport {
+    /// Open the event stream at `url` and wait for the server to announce
+    /// its POST endpoint.
+    pub async fn connect(url: &str, headers: HeaderMap) -> McpResult<Self> {
+        let base = reqwest::Url::parse(url)
+            .map_err(|e| transport_error(format!("invalid URL {}: {}", url, e)))?;
+        let client = reqwest::Client::new();
+
+        let response = client
+            .get(base.clone())
+            .headers(headers.clone())
+            .header(ACCEPT, "text/event-stream")
+            .send()
+            .await
+            .map_err(|e| transport_error(format!("GET {} failed: {}", url, e)))?;
+        let response = check_status(response).await?;
+
+        let mut parser = SseParser::default();
+        let mut stream = response.bytes_stream();
+        let announced = tokio::time::timeout(ENDPOINT_TIMEOUT, async {
+            while let Some(Ok(chunk)) = stream.next().await {
+                if let Some(event) = parser
+                    .push(&chunk)
+                    .into_iter()
+                    .find(|e| e.event == "endpoint")
+                {
+                    return Some(event.data);
+                }
+            }
+            None
+        })
+        .await
+        .map_err(|_| transport_error("timed out waiting for the endpoint event"))?
+        .ok_or_else(|| transport_error("event stream ended before the endpoint event"))?;
+        let endpoint = base
+            .join(announced.trim())
+            .map_err(|e| transport_error(format!("invalid endpoint: {}", e)))?;
+        debug!(url = %url, endpoint = %endpoint, "MCP SSE endpoint announced");
+
+        let pending: PendingRequests = Arc::new(Mutex::new(HashMap::new()));
+        let connected = Arc::new(AtomicBool::new(true));
+        let reader = {
+            let pending = Arc::clone(&pending);
+            let connected = Arc::clone(&connected);
+            tokio::spawn(async move {
+                while let Some(Ok(chunk)) = stream.next().await {
+                    for event in parser.push(&chunk) {
+                        if event.event == "message" {
+                            deliver(&pending, &event.data);
+                        }
+                    }
+                }
+                connected.store(false, Ordering::SeqCst);
+                // Dropping the senders fails any requests still waiting
+                if let Ok(mut pending) = pending.lock() {
+                    pending.clear();
+                }
+            })
+        };
+
+        Ok(Self {
+            client,
+            endpoint,
+            headers,
+            pending,
+            connected,
+            reader,
+        })
+    }
+
+    async fn post(&self, body: &JsonValue) -> McpResult<()> {
+        let response = self
+            .client
+            .post(self.endpoint.clone())
+            .headers(self.headers.clone())
+            .json(body)
+            .send()
+            .await
+            .map_err(|e| transport_error(format!("POST {} failed: {}", self.endpoint, e)))?;
+        check_status(response).await?;
+        Ok(())
+    }
+}
+
+/// Hand a `message` event to the request waiting for it.
+fn deliver(pending: &PendingRequests, data: &str) {
+    let Ok(value) = serde_json::from_str::<JsonValue>(data) else {
+        debug!(data = %data, "Skipping non-JSON MCP event");
+        return;
+    };
+    let Some(id) = value.get("id") else {
+        debug!(data = %data, "Skipping MCP notification");
+        return;
+    };
+    let sender = pending
+        .lock()
+        .ok()
+        .and_then(|mut p| p.remove(&id.to_string()));
+    if let (Some(sender), Some(response)) = (sender, decode_response(data, id)) {
+        let _ = sender.send(response);
+    }
+}
+
+#[async_trait]
+impl McpTransport for SseTransport {
+    async fn request(&self, request: &JsonRpcRequest) -> McpResult<JsonRpcResponse> {
+        if !self.is_connected() {
+            return Err(transport_error("event stream closed"));
+        }
+
+        let (body, id) = encode_request(request)?;
+        let (tx, rx) = oneshot::channel();
+        if let Ok(mut pending) = self.pending.lock() {
+            pending.insert(id.to_string(), tx);
+        }
+
+        if let Err(e) = self.post(&body).await {
+            if let Ok(mut pending) = self.pending.lock() {
+                pending.remove(&id.to_string());
+            }
+            return Err(e);
+        }
+
+        rx.await
+            .map_err(|_| transport_error("event stream closed before the response arrived"))
+    }
+
+    async fn notify(&self, notification: &JsonRpcNotification) -> McpResult<()> {
+        let body =
+            serde_json::to_value(notification).map_err(|e| transport_error(e.to_string()))?;
+        self.post(&body).await
+    }
+
+    async fn close(&self) -> McpResult<()> {
+        self.connected.store(false, Ordering::SeqCst);
+        self.reader.abort();
+        Ok(())
+    }
+
+    fn is_connected(&self) -> bool {
+        self.connected.load(Ordering::SeqCst)
+    }
+}
+
+impl Drop for SseTransport {
+    fn drop(&mut self) {
+        self.reader.abort();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mcp::test_server::{StandInMode, StandInServer};
+    use serdes_ai_mcp::McpClient;
+
+    fn headers() -> HeaderMap {
+        let mut extra = HashMap::new();
+        extra.insert("X-Team".to_string(), "platform".to_string());
+        remote_headers(&extra, Some("t0ken")).unwrap()
+    }
+
+    #[test]
+    fn test_sse_parser_events() {
+        let mut parser = SseParser::default();
+        let events = parser.push(b": keep-alive\n\nevent: endpoint\ndata: /messages?id=1\n\n");
+        assert_eq!(
+            events,
+            vec![SseEvent {
+                event: "endpoint".to_string(),
+                data: "/messages?id=1".to_string()
+            }]
+        );
+    }
+
+    #[test]
+    fn test_sse_parser_split_chunks_and_multiline_data() {
+        let mut parser = SseParser::default();
+        assert!(parser.push(b"data: {\"a\":\r\n").is_empty());
+        assert!(parser.push(b"data: 1}\r").is_empty());
+        let events = parser.push(b"\n\r\n");
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].event, "message");
+        assert_eq!(events[0].data, "{\"a\":\n1}");
+    }
+
+    #[test]
+    fn test_sse_parser_multibyte_split() {
+        let mut parser = SseParser::default();
+        let bytes = "data: héllo\n\n".as_bytes();
+        assert!(parser.push(&bytes[..8]).is_empty());
+        let events = parser.push(&bytes[8..]);
+        assert_eq!(events[0].data, "héllo");
+    }
+
+    #[test]
+    fn test_remote_headers() {
+        let map = headers();
+        assert_eq!(map.get("x-team").unwrap(), "platform");
+        assert_eq!(map.get(AUTHORIZATION).unwrap(), "Bearer t0ken");
+        assert!(map.get(AUTHORIZATION).unwrap().is_sensitive());
+
+        let mut bad = HashMap::new();
+        bad.insert("Bad Header".to_string(), "x".to_string());
+        assert!(remote_headers(&bad, None).is_err());
+        assert!(remote_headers(&HashMap::new(), Some(""))
+            .unwrap()
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_http_transport_json_responses() {
+        let server = StandInServer::start(StandInMode::Json).await;
+        let client = McpClient::new(HttpTransport::new(server.url("/mcp"), headers()));
+
+        let init = client.initialize().await.unwrap();
+        assert_eq!(init.server_info.name, "stand-in");
+        let tools = client.list_tools().await.unwrap();
+        assert_eq!(tools[0].name, "echo");
+
+        let requests = server.requests();
+        assert!(requests
+            .iter()
+            .all(|r| r.header("authorization") == Some("Bearer t0ken")));
+        assert!(requests
+            .iter()
+            .all(|r| r.header("x-team") == Some("platform")));
+        // The session ID from initialize is sent back afterwards
+        assert_eq!(requests[0].header(SESSION_HEADER), None);
+        assert!(requests[1..]
+            .iter()
+            .all(|r| r.header(SESSION_HEADER) == Some("stand-in-session")));
+
+        client.close().await.unwrap();
+        assert!(server.requests().iter().any(|r| r.method == "DELETE"));
+    }
+
+    #[tokio::test]
+    async fn test_http_transport_event_stream_responses() {
+        let server = StandInServer::start(StandInMode::EventStream).await;
+        let client = McpClient::new(HttpTransport::new(server.url("/mcp"), headers()));
+
+        client.initialize().await.unwrap();
+        let result = client
+            .call_tool("echo", serde_json::json!({ "text": "over sse" }))
+            .await
+            .unwrap();
+        assert!(matches!(
+            result.content.first(),
+            Some(serdes_ai_mcp::ToolResultContent::Text { text }) if text == "over sse"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_http_transport_rejected_token() {
+        let server = StandInServer::start(StandInMode::Json).await;
+        let client = McpClient::new(HttpTransport::new(
+            server.url("/mcp"),
+            remote_headers(&HashMap::new(), Some("wrong")).unwrap(),
+        ));
+
+        let err = client.initialize().await.unwrap_err();
+        assert!(err.to_string().contains("401"));
+    }
+
+    #[tokio::test]
+    async fn test_sse_transport() {
+        let server = StandInServer::start(StandInMode::Sse).await;
+        let transport = SseTransport::connect(&server.url("/sse"), headers())
+            .await
+            .unwrap();
+        let client = McpClient::new(transport);
+
+        let init = client.initialize().await.unwrap();
+        assert_eq!(init.server_info.name, "stand-in");
+        let tools = client.list_tools().await.unwrap();
+        assert_eq!(tools.len(), 1);
+
+        let requests = server.requests();
+        assert_eq!(requests[0].method, "GET");
+        assert!(requests
+            .iter()
+            .filter(|r| r.method == "POST")
+            .all(|r| r.path == "/messages?session=1"
+                && r.header("authorization") == Some("Bearer t0ken")));
+    }
+
+    #[tokio::test]
+    async fn test_sse_transport_unreachable() {
+        let result = SseTransport::connect("http://127.0.0.1:9/sse", HeaderMap::new()).await;
+        assert!(result.is_err());
+    }
+}
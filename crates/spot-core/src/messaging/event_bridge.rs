@@ -72,6 +72,14 @@ impl EventBridge {
         ));
     }
 
+    /// Signal that the agent run was cancelled by the user.
+    pub fn agent_cancelled(&self) {
+        let _ = self.sender.send(Message::agent_cancelled(
+            &self.agent_name,
+            &self.agent_display_name,
+        ));
+    }
+
     /// Send context size information.
     pub fn context_info(
         &self,
@@ -316,7 +324,7 @@ impl EventBridge {
             }
 
             StreamEvent::Cancelled { .. } => {
-                self.agent_error("Operation cancelled");
+                self.agent_cancelled();
             }
         }
     }
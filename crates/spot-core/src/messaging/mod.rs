@@ -74,6 +74,6 @@ mod bus;
 mod event_bridge;
 mod types;
 
-pub use bus::{MessageBus, MessageSender};
+pub use bus::{BusError, MessageBus, MessageSender};
 pub use event_bridge::EventBridge;
 pub use types::*;
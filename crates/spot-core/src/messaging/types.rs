@@ -1,6 +1,10 @@
 //! Message types for agent-UI communication.
 
 use serde::{Deserialize, Serialize};
+use spot_tools::tools::diff::{self, UnifiedDiff};
+
+use crate::checkpoints::CheckpointKey;
+use crate::mcp::McpServerState;
 
 /// Message levels for styling.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -67,6 +71,99 @@ pub enum FileOperation {
 pub struct DiffMessage {
     pub path: String,
     pub lines: Vec<DiffLine>,
+    /// Agent whose tool made the change (for nested agent routing)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub agent_name: Option<String>,
+    /// Checkpoint that can revert the change
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub checkpoint: Option<CheckpointKey>,
+}
+
+impl DiffMessage {
+    /// Build a diff message from a computed unified diff.
+    ///
+    /// Line contents keep their `+`/`-`/` ` prefix. Added and context lines
+    /// are numbered in the new file, removed lines in the old one.
+    pub fn from_unified(diff: &UnifiedDiff) -> Self {
+        let path = diff
+            .new_path
+            .clone()
+            .or_else(|| diff.old_path.clone())
+            .unwrap_or_default();
+
+        let mut lines = Vec::new();
+        for hunk in &diff.hunks {
+            lines.push(DiffLine {
+                content: format!(
+                    "@@ -{},{} +{},{} @@",
+                    hunk.old_start, hunk.old_count, hunk.new_start, hunk.new_count
+                ),
+                line_type: DiffLineType::Header,
+                line_number: None,
+            });
+
+            let mut old_line = hunk.old_start.max(1) as u32;
+            let mut new_line = hunk.new_start.max(1) as u32;
+            for line in &hunk.lines {
+                let (content, line_type, line_number) = match line {
+                    diff::DiffLine::Context(s) => {
+                        old_line += 1;
+                        new_line += 1;
+                        (format!(" {}", s), DiffLineType::Context, new_line - 1)
+                    }
+                    diff::DiffLine::Add(s) => {
+                        new_line += 1;
+                        (format!("+{}", s), DiffLineType::Added, new_line - 1)
+                    }
+                    diff::DiffLine::Remove(s) => {
+                        old_line += 1;
+                        (format!("-{}", s), DiffLineType::Removed, old_line - 1)
+                    }
+                };
+                lines.push(DiffLine {
+                    content,
+                    line_type,
+                    line_number: Some(line_number),
+                });
+            }
+        }
+
+        Self {
+            path,
+            lines,
+            agent_name: None,
+            checkpoint: None,
+        }
+    }
+
+    /// Attribute the diff to an agent.
+    pub fn with_agent(mut self, agent_name: impl Into<String>) -> Self {
+        self.agent_name = Some(agent_name.into());
+        self
+    }
+
+    /// Link the diff to the checkpoint that reverts it.
+    pub fn with_checkpoint(mut self, checkpoint: Option<CheckpointKey>) -> Self {
+        self.checkpoint = checkpoint;
+        self
+    }
+
+    /// Number of added lines.
+    pub fn additions(&self) -> usize {
+        self.count(DiffLineType::Added)
+    }
+
+    /// Number of removed lines.
+    pub fn deletions(&self) -> usize {
+        self.count(DiffLineType::Removed)
+    }
+
+    fn count(&self, line_type: DiffLineType) -> usize {
+        self.lines
+            .iter()
+            .filter(|l| l.line_type == line_type)
+            .count()
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -108,7 +205,7 @@ pub enum InputType {
     Selection,
 }
 
-/// Agent lifecycle events (start, complete, error).
+/// Agent lifecycle events (start, complete, error, cancel).
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AgentMessage {
     pub agent_name: String,
@@ -121,8 +218,14 @@ pub struct AgentMessage {
 #[serde(tag = "event_type", rename_all = "snake_case")]
 pub enum AgentEvent {
     Started,
-    Completed { run_id: String },
-    Error { message: String },
+    Completed {
+        run_id: String,
+    },
+    Error {
+        message: String,
+    },
+    /// The run was stopped by the user before completing.
+    Cancelled,
 }
 
 /// Tool execution lifecycle.
@@ -213,6 +316,51 @@ pub struct RetryMessage {
     pub is_rotation: bool,
 }
 
+impl RetryMessage {
+    /// One-line description for status bars and notifications.
+    pub fn summary(&self) -> String {
+        if self.is_rotation {
+            return format!("🔑 {}: {}", self.provider, self.reason);
+        }
+        let mut summary = format!(
+            "⏳ {} (attempt {}): {}",
+            self.provider, self.attempt, self.reason
+        );
+        if let Some(secs) = self.wait_secs {
+            summary.push_str(&format!(", retrying in {}s", secs));
+        }
+        summary
+    }
+}
+
+/// MCP server status change.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct McpStatusMessage {
+    pub server: String,
+    pub state: McpServerState,
+    /// Why the server failed or became unhealthy
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Number of tools the server offers (once ready)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tool_count: Option<usize>,
+}
+
+impl McpStatusMessage {
+    /// One-line description for status bars and notifications.
+    pub fn summary(&self) -> String {
+        match (&self.error, self.tool_count) {
+            (Some(error), _) if self.state.is_down() => {
+                format!("🔌 {} {}: {}", self.server, self.state.label(), error)
+            }
+            (_, Some(count)) if self.state == McpServerState::Ready => {
+                format!("🔌 {} ready ({} tools)", self.server, count)
+            }
+            _ => format!("🔌 {} {}", self.server, self.state.label()),
+        }
+    }
+}
+
 /// Any message type (for serialization).
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
@@ -231,6 +379,7 @@ pub enum Message {
     Thinking(ThinkingMessage),
     HistoryUpdate(HistoryUpdateMessage),
     Retry(RetryMessage),
+    McpStatus(McpStatusMessage),
     ContextInfo(ContextInfoMessage),
     ContextCompressed(ContextCompressedMessage),
     Divider,
@@ -309,6 +458,15 @@ impl Message {
         })
     }
 
+    /// Create an agent cancelled message.
+    pub fn agent_cancelled(name: &str, display_name: &str) -> Self {
+        Self::Agent(AgentMessage {
+            agent_name: name.to_string(),
+            display_name: display_name.to_string(),
+            event: AgentEvent::Cancelled,
+        })
+    }
+
     /// Create a tool started message.
     pub fn tool_started(tool_name: &str) -> Self {
         Self::Tool(ToolMessage {
@@ -838,6 +996,17 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_message_agent_cancelled() {
+        let msg = Message::agent_cancelled("spot", "Spot");
+        if let Message::Agent(agent_msg) = msg {
+            assert_eq!(agent_msg.display_name, "Spot");
+            assert!(matches!(agent_msg.event, AgentEvent::Cancelled));
+        } else {
+            panic!("Expected Agent variant");
+        }
+    }
+
     #[test]
     fn test_message_tool_started() {
         let msg = Message::tool_started("read_file");
@@ -1219,6 +1388,8 @@ mod tests {
                     line_number: Some(2),
                 },
             ],
+            agent_name: None,
+            checkpoint: None,
         };
         let json = serde_json::to_string(&msg).unwrap();
         let parsed: DiffMessage = serde_json::from_str(&json).unwrap();
@@ -1226,6 +1397,36 @@ mod tests {
         assert_eq!(parsed.lines.len(), 4);
         assert_eq!(parsed.lines[0].line_type, DiffLineType::Header);
         assert_eq!(parsed.lines[2].line_type, DiffLineType::Removed);
+        assert!(!json.contains("agent_name"));
+        assert!(!json.contains("checkpoint"));
+    }
+
+    #[test]
+    fn test_diff_message_from_unified() {
+        let diff = UnifiedDiff::between("src/lib.rs", Some("a\nb\nc\n"), Some("a\nB\nc\nd\n"));
+        let msg = DiffMessage::from_unified(&diff).with_agent("code-agent");
+
+        assert_eq!(msg.path, "src/lib.rs");
+        assert_eq!(msg.agent_name.as_deref(), Some("code-agent"));
+        assert_eq!(msg.additions(), 2);
+        assert_eq!(msg.deletions(), 1);
+
+        let rendered: Vec<(&str, Option<u32>)> = msg
+            .lines
+            .iter()
+            .map(|l| (l.content.as_str(), l.line_number))
+            .collect();
+        assert_eq!(
+            rendered,
+            vec![
+                ("@@ -1,3 +1,4 @@", None),
+                (" a", Some(1)),
+                ("-b", Some(2)),
+                ("+B", Some(2)),
+                (" c", Some(3)),
+                ("+d", Some(4)),
+            ]
+        );
     }
 
     #[test]
@@ -1465,6 +1666,22 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_retry_message_summary() {
+        let Message::Retry(cooldown) = Message::retry_cooldown("openai", 2, "HTTP 503", 4) else {
+            unreachable!()
+        };
+        assert_eq!(
+            cooldown.summary(),
+            "⏳ openai (attempt 2): HTTP 503, retrying in 4s"
+        );
+
+        let Message::Retry(rotation) = Message::retry_rotation("openai", 1, "rate limited") else {
+            unreachable!()
+        };
+        assert_eq!(rotation.summary(), "🔑 openai: rate limited");
+    }
+
     #[test]
     fn test_message_enum_retry_serde() {
         let msg = Message::Retry(RetryMessage {
@@ -1481,4 +1698,46 @@ mod tests {
         let parsed: Message = serde_json::from_str(&json).unwrap();
         matches!(parsed, Message::Retry(_));
     }
+
+    // =========================================================================
+    // McpStatusMessage Tests
+    // =========================================================================
+
+    #[test]
+    fn test_mcp_status_message_serde() {
+        let msg = Message::McpStatus(McpStatusMessage {
+            server: "github".to_string(),
+            state: McpServerState::Ready,
+            error: None,
+            tool_count: Some(12),
+        });
+        let json = serde_json::to_string(&msg).unwrap();
+        assert!(json.contains("\"type\":\"mcp_status\""));
+        assert!(json.contains("\"state\":\"ready\""));
+        assert!(!json.contains("error"));
+
+        let Message::McpStatus(parsed) = serde_json::from_str::<Message>(&json).unwrap() else {
+            panic!("Expected Message::McpStatus");
+        };
+        assert_eq!(parsed.server, "github");
+        assert_eq!(parsed.tool_count, Some(12));
+    }
+
+    #[test]
+    fn test_mcp_status_message_summary() {
+        let mut msg = McpStatusMessage {
+            server: "github".to_string(),
+            state: McpServerState::Ready,
+            error: None,
+            tool_count: Some(12),
+        };
+        assert_eq!(msg.summary(), "🔌 github ready (12 tools)");
+
+        msg.state = McpServerState::Unhealthy;
+        msg.error = Some("health check timed out".to_string());
+        assert_eq!(msg.summary(), "🔌 github unhealthy: health check timed out");
+
+        msg.state = McpServerState::Starting;
+        assert_eq!(msg.summary(), "🔌 github starting");
+    }
 }
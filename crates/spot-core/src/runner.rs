@@ -15,4 +15,7 @@ pub struct AppConfig {
     pub verbose: bool,
     /// Skip the automatic update check on startup
     pub skip_update_check: bool,
+    /// Session to resume on startup: `Some(None)` resumes the most recent
+    /// session, `Some(Some(name))` a named one.
+    pub resume: Option<Option<String>>,
 }
@@ -5,16 +5,19 @@
 //!
 //! ## Storage Format
 //!
-//! Sessions are stored in `~/.spot/sessions/` as:
-//! - `{name}.json` - Serialized message history
-//! - `{name}_meta.json` - Session metadata
+//! Sessions live in the SQLite database (see [`crate::db::repositories::SessionRepository`]):
+//! - `sessions` - One row per session with agent, model and timestamps
+//! - `messages` - One row per `ModelRequest`, serialized as JSON, with a token estimate
+//!
+//! Sessions saved by older versions as `~/.spot/sessions/{name}.json` are
+//! imported once by [`SessionManager::import_legacy_sessions`].
 //!
 //! ## Usage
 //!
 //! ```ignore
 //! use spot_core::session::SessionManager;
 //!
-//! let manager = SessionManager::new();
+//! let manager = SessionManager::new(&db);
 //!
 //! // Save a session
 //! manager.save("my-project", &messages, "spot", "gpt-4o")?;
@@ -25,16 +28,22 @@
 //! }
 //!
 //! // Load a session
-//! let (messages, meta) = manager.load("my-project")?;
+//! let data = manager.load("my-project")?;
 //! ```
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use serdes_ai_core::ModelRequest;
+use serdes_ai_core::messages::{UserContent, UserContentPart};
+use serdes_ai_core::{ModelRequest, ModelRequestPart, ModelResponsePart};
 use std::fs;
 use std::path::{Path, PathBuf};
 use thiserror::Error;
 
+use crate::db::{Database, NewMessage, SessionSummary};
+
+/// Settings key recording that legacy JSON sessions were imported.
+const LEGACY_IMPORT_KEY: &str = "sessions.legacy_json_imported";
+
 /// Error type for session operations.
 #[derive(Debug, Error)]
 pub enum SessionError {
@@ -47,6 +56,9 @@ pub enum SessionError {
     #[error("Serialization error: {0}")]
     Serialization(#[from] serde_json::Error),
 
+    #[error("Database error: {0}")]
+    Database(#[from] rusqlite::Error),
+
     #[error("Invalid session name: {0}")]
     InvalidName(String),
 }
@@ -104,7 +116,22 @@ impl SessionMeta {
     }
 }
 
-/// Session data stored on disk.
+impl From<SessionSummary> for SessionMeta {
+    fn from(summary: SessionSummary) -> Self {
+        Self {
+            name: summary.name,
+            created_at: from_millis(summary.created_at),
+            updated_at: from_millis(summary.updated_at),
+            message_count: summary.message_count.max(0) as usize,
+            token_estimate: summary.token_count.max(0) as usize,
+            agent: summary.agent_name,
+            model: summary.model,
+            description: summary.description,
+        }
+    }
+}
+
+/// A saved session: metadata plus message history.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SessionData {
     /// Session metadata.
@@ -131,34 +158,35 @@ impl SessionData {
 }
 
 /// Session manager for saving and loading sessions.
-pub struct SessionManager {
-    /// Base directory for sessions.
-    sessions_dir: PathBuf,
+pub struct SessionManager<'a> {
+    db: &'a Database,
+
+    /// Directory holding sessions saved as JSON by older versions.
+    legacy_dir: PathBuf,
 
     /// Maximum number of sessions to keep (0 = unlimited).
     max_sessions: usize,
 }
 
-impl SessionManager {
+impl<'a> SessionManager<'a> {
     /// Create a new session manager with default settings.
-    pub fn new() -> Self {
-        let sessions_dir = dirs::home_dir()
+    pub fn new(db: &'a Database) -> Self {
+        let legacy_dir = dirs::home_dir()
             .unwrap_or_else(|| PathBuf::from("."))
             .join(".spot")
             .join("sessions");
 
         Self {
-            sessions_dir,
+            db,
+            legacy_dir,
             max_sessions: 50, // Keep last 50 sessions by default
         }
     }
 
-    /// Create with custom directory.
-    pub fn with_dir(dir: impl AsRef<Path>) -> Self {
-        Self {
-            sessions_dir: dir.as_ref().to_path_buf(),
-            max_sessions: 50,
-        }
+    /// Set the directory scanned for legacy JSON sessions.
+    pub fn with_legacy_dir(mut self, dir: impl AsRef<Path>) -> Self {
+        self.legacy_dir = dir.as_ref().to_path_buf();
+        self
     }
 
     /// Set maximum number of sessions to keep.
@@ -167,17 +195,6 @@ impl SessionManager {
         self
     }
 
-    /// Ensure the sessions directory exists.
-    fn ensure_dir(&self) -> Result<(), SessionError> {
-        fs::create_dir_all(&self.sessions_dir)?;
-        Ok(())
-    }
-
-    /// Get path for a session file.
-    fn session_path(&self, name: &str) -> PathBuf {
-        self.sessions_dir.join(format!("{}.json", name))
-    }
-
     /// Validate session name.
     fn validate_name(name: &str) -> Result<(), SessionError> {
         if name.is_empty() {
@@ -200,6 +217,9 @@ impl SessionManager {
     }
 
     /// Save a session.
+    ///
+    /// Messages already stored for the session are kept; only new or changed
+    /// messages are written.
     pub fn save(
         &self,
         name: &str,
@@ -208,100 +228,97 @@ impl SessionManager {
         model: &str,
     ) -> Result<SessionMeta, SessionError> {
         Self::validate_name(name)?;
-        self.ensure_dir()?;
-
-        let path = self.session_path(name);
-
-        // Load existing or create new
-        let mut session = if path.exists() {
-            let content = fs::read_to_string(&path)?;
-            serde_json::from_str::<SessionData>(&content)?
-        } else {
-            SessionData::new(name, agent, model)
-        };
 
-        // Update with new messages
-        session.update(messages.to_vec());
-        session.meta.agent = agent.to_string();
-        session.meta.model = model.to_string();
-
-        // Write to disk
-        let content = serde_json::to_string_pretty(&session)?;
-        fs::write(&path, content)?;
+        let meta = self.write(name, messages, agent, model)?;
 
         // Cleanup old sessions if needed
         self.cleanup()?;
 
-        Ok(session.meta)
+        Ok(meta)
+    }
+
+    /// Serialize messages to rows and store them.
+    fn write(
+        &self,
+        name: &str,
+        messages: &[ModelRequest],
+        agent: &str,
+        model: &str,
+    ) -> Result<SessionMeta, SessionError> {
+        let rows = messages
+            .iter()
+            .map(|msg| -> Result<NewMessage, SessionError> {
+                let content = serde_json::to_string(msg)?;
+                Ok(NewMessage {
+                    role: message_role(msg).to_string(),
+                    token_count: Some((content.len() / 4) as i64),
+                    content,
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let summary = self.db.sessions().save(name, agent, model, &rows)?;
+        Ok(summary.into())
     }
 
     /// Load a session.
     pub fn load(&self, name: &str) -> Result<SessionData, SessionError> {
         Self::validate_name(name)?;
 
-        let path = self.session_path(name);
+        let repo = self.db.sessions();
+        let summary = repo
+            .get(name)?
+            .ok_or_else(|| SessionError::NotFound(name.to_string()))?;
 
-        if !path.exists() {
-            return Err(SessionError::NotFound(name.to_string()));
-        }
+        let messages = repo
+            .messages(summary.id)?
+            .iter()
+            .map(|row| serde_json::from_str::<ModelRequest>(&row.content))
+            .collect::<Result<Vec<_>, _>>()?;
 
-        let content = fs::read_to_string(&path)?;
-        let session: SessionData = serde_json::from_str(&content)?;
-
-        Ok(session)
+        Ok(SessionData {
+            meta: summary.into(),
+            messages,
+        })
     }
 
-    /// List all sessions.
-    pub fn list(&self) -> Result<Vec<SessionMeta>, SessionError> {
-        self.ensure_dir()?;
-
-        let mut sessions = Vec::new();
+    /// Load the most recently updated session.
+    pub fn load_latest(&self) -> Result<SessionData, SessionError> {
+        let latest = self
+            .list()?
+            .into_iter()
+            .next()
+            .ok_or_else(|| SessionError::NotFound("no saved sessions".to_string()))?;
 
-        for entry in fs::read_dir(&self.sessions_dir)? {
-            let entry = entry?;
-            let path = entry.path();
-
-            if path.extension().map(|e| e == "json").unwrap_or(false) {
-                // Skip metadata files (we read from main file)
-                if let Some(stem) = path.file_stem() {
-                    let name = stem.to_string_lossy();
-                    if name.ends_with("_meta") {
-                        continue;
-                    }
-                }
-
-                if let Ok(content) = fs::read_to_string(&path) {
-                    if let Ok(session) = serde_json::from_str::<SessionData>(&content) {
-                        sessions.push(session.meta);
-                    }
-                }
-            }
-        }
+        self.load(&latest.name)
+    }
 
-        // Sort by updated_at descending (most recent first)
-        sessions.sort_by(|a, b| b.updated_at.cmp(&a.updated_at));
+    /// List all sessions, most recently updated first.
+    pub fn list(&self) -> Result<Vec<SessionMeta>, SessionError> {
+        let sessions = self.db.sessions().list()?;
+        Ok(sessions.into_iter().map(SessionMeta::from).collect())
+    }
 
-        Ok(sessions)
+    /// Find sessions whose name or messages contain `query`.
+    pub fn search(&self, query: &str) -> Result<Vec<SessionMeta>, SessionError> {
+        let sessions = self.db.sessions().search(query)?;
+        Ok(sessions.into_iter().map(SessionMeta::from).collect())
     }
 
     /// Delete a session.
     pub fn delete(&self, name: &str) -> Result<(), SessionError> {
         Self::validate_name(name)?;
 
-        let path = self.session_path(name);
-
-        if !path.exists() {
+        if !self.db.sessions().delete(name)? {
             return Err(SessionError::NotFound(name.to_string()));
         }
 
-        fs::remove_file(path)?;
-
         Ok(())
     }
 
     /// Check if a session exists.
     pub fn exists(&self, name: &str) -> bool {
-        Self::validate_name(name).is_ok() && self.session_path(name).exists()
+        Self::validate_name(name).is_ok() && self.db.sessions().exists(name).unwrap_or(false)
     }
 
     /// Generate a unique session name.
@@ -331,28 +348,104 @@ impl SessionManager {
             return Ok(()); // Unlimited
         }
 
-        let sessions = self.list()?;
+        self.db.sessions().prune(self.max_sessions)?;
+        Ok(())
+    }
+
+    /// Import sessions saved as JSON files by older versions.
+    ///
+    /// Runs once per database; later calls return `Ok(0)`. Files that fail to
+    /// parse are skipped, and sessions that already exist are left untouched.
+    /// The JSON files are not removed. Returns the number of imported sessions.
+    pub fn import_legacy_sessions(&self) -> Result<usize, SessionError> {
+        let settings = self.db.settings_repo();
+        if settings.get(LEGACY_IMPORT_KEY)?.is_some() {
+            return Ok(0);
+        }
+
+        let mut imported = 0;
+        if self.legacy_dir.is_dir() {
+            for entry in fs::read_dir(&self.legacy_dir)? {
+                let path = entry?.path();
+                if !is_legacy_session_file(&path) {
+                    continue;
+                }
+
+                let session = match read_legacy_session(&path) {
+                    Ok(session) => session,
+                    Err(e) => {
+                        tracing::warn!("Skipping legacy session {}: {}", path.display(), e);
+                        continue;
+                    }
+                };
+
+                let meta = &session.meta;
+                if Self::validate_name(&meta.name).is_err() || self.exists(&meta.name) {
+                    continue;
+                }
 
-        if sessions.len() > self.max_sessions {
-            // Delete oldest sessions
-            for session in sessions.iter().skip(self.max_sessions) {
-                let _ = self.delete(&session.name);
+                self.write(&meta.name, &session.messages, &meta.agent, &meta.model)?;
+                let repo = self.db.sessions();
+                repo.set_description(&meta.name, meta.description.as_deref())?;
+                repo.set_timestamps(
+                    &meta.name,
+                    meta.created_at.timestamp_millis(),
+                    meta.updated_at.timestamp_millis(),
+                )?;
+                imported += 1;
             }
         }
 
-        Ok(())
+        if imported > 0 {
+            tracing::info!(
+                "Imported {} session(s) from {}",
+                imported,
+                self.legacy_dir.display()
+            );
+        }
+        settings.set(LEGACY_IMPORT_KEY, "true")?;
+
+        Ok(imported)
     }
 
-    /// Get session directory path.
-    pub fn sessions_dir(&self) -> &Path {
-        &self.sessions_dir
+    /// Get the directory scanned for legacy JSON sessions.
+    pub fn legacy_dir(&self) -> &Path {
+        &self.legacy_dir
     }
 }
 
-impl Default for SessionManager {
-    fn default() -> Self {
-        Self::new()
+/// Whether `path` is a `{name}.json` session file (not a `_meta.json` sidecar).
+fn is_legacy_session_file(path: &Path) -> bool {
+    let is_json = path.extension().map(|e| e == "json").unwrap_or(false);
+    let is_meta = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().ends_with("_meta"))
+        .unwrap_or(false);
+    is_json && !is_meta
+}
+
+/// Read a session saved as JSON by older versions.
+fn read_legacy_session(path: &Path) -> Result<SessionData, SessionError> {
+    let content = fs::read_to_string(path)?;
+    Ok(serde_json::from_str(&content)?)
+}
+
+/// Role stored alongside a message row.
+fn message_role(msg: &ModelRequest) -> &'static str {
+    for part in &msg.parts {
+        match part {
+            ModelRequestPart::ModelResponse(_) => return "assistant",
+            ModelRequestPart::UserPrompt(_) => return "user",
+            ModelRequestPart::ToolReturn(_) => return "tool",
+            _ => {}
+        }
     }
+    "system"
+}
+
+/// Convert Unix milliseconds to a UTC timestamp.
+fn from_millis(millis: i64) -> DateTime<Utc> {
+    DateTime::from_timestamp_millis(millis).unwrap_or_default()
 }
 
 /// Estimate token count for messages.
@@ -387,6 +480,63 @@ fn rand_suffix() -> String {
     format!("{:x}", nanos % 0xFFFF)
 }
 
+/// A user or assistant turn recovered from message history.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TranscriptEntry {
+    /// Text the user sent.
+    User(String),
+    /// Text the assistant replied with (tool calls are omitted).
+    Assistant(String),
+}
+
+/// Extract the visible conversation from message history.
+///
+/// Used to redraw a loaded session in the UI. Consecutive assistant
+/// responses (split by tool calls) are merged into one entry.
+pub fn transcript(messages: &[ModelRequest]) -> Vec<TranscriptEntry> {
+    let mut entries: Vec<TranscriptEntry> = Vec::new();
+
+    for msg in messages {
+        for part in &msg.parts {
+            match part {
+                ModelRequestPart::UserPrompt(prompt) => {
+                    let text = match &prompt.content {
+                        UserContent::Text(t) => t.clone(),
+                        UserContent::Parts(parts) => parts
+                            .iter()
+                            .filter_map(|p| match p {
+                                UserContentPart::Text { text, .. } => Some(text.as_str()),
+                                _ => None,
+                            })
+                            .collect::<Vec<_>>()
+                            .join("\n"),
+                    };
+                    entries.push(TranscriptEntry::User(text));
+                }
+                ModelRequestPart::ModelResponse(response) => {
+                    for response_part in &response.parts {
+                        let ModelResponsePart::Text(text_part) = response_part else {
+                            continue;
+                        };
+                        if text_part.content.is_empty() {
+                            continue;
+                        }
+                        if let Some(TranscriptEntry::Assistant(existing)) = entries.last_mut() {
+                            existing.push_str("\n\n");
+                            existing.push_str(&text_part.content);
+                        } else {
+                            entries.push(TranscriptEntry::Assistant(text_part.content.clone()));
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    entries
+}
+
 /// Format a relative time string.
 pub fn format_relative_time(dt: DateTime<Utc>) -> String {
     let now = Utc::now();
@@ -418,6 +568,13 @@ mod tests {
     // Helper functions
     // =========================================================================
 
+    fn setup() -> (TempDir, Database) {
+        let tmp = TempDir::new().unwrap();
+        let db = Database::open_at(tmp.path().join("test.db")).unwrap();
+        db.migrate().unwrap();
+        (tmp, db)
+    }
+
     fn create_test_message(content: &str) -> ModelRequest {
         let mut msg = ModelRequest::new();
         msg.add_user_prompt(content.to_string());
@@ -641,37 +798,32 @@ mod tests {
     // SessionManager Constructor Tests
     // =========================================================================
 
-    #[test]
-    fn test_session_manager_default() {
-        let manager = SessionManager::default();
-        // Should use home dir
-        assert!(manager.sessions_dir().to_string_lossy().contains("spot"));
-    }
-
     #[test]
     fn test_session_manager_new() {
-        let manager = SessionManager::new();
-        assert!(manager.sessions_dir().ends_with("sessions"));
+        let (_tmp, db) = setup();
+        let manager = SessionManager::new(&db);
+        assert!(manager.legacy_dir().ends_with("sessions"));
+        assert_eq!(manager.max_sessions, 50);
     }
 
     #[test]
-    fn test_session_manager_with_dir() {
-        let temp_dir = TempDir::new().unwrap();
-        let manager = SessionManager::with_dir(temp_dir.path());
-        assert_eq!(manager.sessions_dir(), temp_dir.path());
+    fn test_session_manager_with_legacy_dir() {
+        let (tmp, db) = setup();
+        let manager = SessionManager::new(&db).with_legacy_dir(tmp.path());
+        assert_eq!(manager.legacy_dir(), tmp.path());
     }
 
     #[test]
     fn test_session_manager_with_max_sessions() {
-        let temp_dir = TempDir::new().unwrap();
-        let manager = SessionManager::with_dir(temp_dir.path()).with_max_sessions(10);
+        let (_tmp, db) = setup();
+        let manager = SessionManager::new(&db).with_max_sessions(10);
         assert_eq!(manager.max_sessions, 10);
     }
 
     #[test]
     fn test_session_manager_with_max_sessions_zero_means_unlimited() {
-        let temp_dir = TempDir::new().unwrap();
-        let manager = SessionManager::with_dir(temp_dir.path()).with_max_sessions(0);
+        let (_tmp, db) = setup();
+        let manager = SessionManager::new(&db).with_max_sessions(0);
         assert_eq!(manager.max_sessions, 0);
     }
 
@@ -681,8 +833,8 @@ mod tests {
 
     #[test]
     fn test_session_manager_save_load() {
-        let temp_dir = TempDir::new().unwrap();
-        let manager = SessionManager::with_dir(temp_dir.path());
+        let (_tmp, db) = setup();
+        let manager = SessionManager::new(&db);
 
         let messages = vec![];
 
@@ -701,8 +853,8 @@ mod tests {
 
     #[test]
     fn test_session_manager_save_with_messages() {
-        let temp_dir = TempDir::new().unwrap();
-        let manager = SessionManager::with_dir(temp_dir.path());
+        let (_tmp, db) = setup();
+        let manager = SessionManager::new(&db);
 
         let messages = create_test_messages(5);
         let meta = manager
@@ -718,8 +870,8 @@ mod tests {
 
     #[test]
     fn test_session_manager_save_updates_existing() {
-        let temp_dir = TempDir::new().unwrap();
-        let manager = SessionManager::with_dir(temp_dir.path());
+        let (_tmp, db) = setup();
+        let manager = SessionManager::new(&db);
 
         // First save
         manager
@@ -744,8 +896,8 @@ mod tests {
 
     #[test]
     fn test_session_manager_save_invalid_name() {
-        let temp_dir = TempDir::new().unwrap();
-        let manager = SessionManager::with_dir(temp_dir.path());
+        let (_tmp, db) = setup();
+        let manager = SessionManager::new(&db);
 
         let result = manager.save("invalid name", &[], "agent", "model");
         assert!(matches!(result, Err(SessionError::InvalidName(_))));
@@ -753,8 +905,8 @@ mod tests {
 
     #[test]
     fn test_session_manager_load_not_found() {
-        let temp_dir = TempDir::new().unwrap();
-        let manager = SessionManager::with_dir(temp_dir.path());
+        let (_tmp, db) = setup();
+        let manager = SessionManager::new(&db);
 
         let result = manager.load("nonexistent");
         assert!(matches!(result, Err(SessionError::NotFound(_))));
@@ -766,8 +918,8 @@ mod tests {
 
     #[test]
     fn test_session_manager_load_invalid_name() {
-        let temp_dir = TempDir::new().unwrap();
-        let manager = SessionManager::with_dir(temp_dir.path());
+        let (_tmp, db) = setup();
+        let manager = SessionManager::new(&db);
 
         let result = manager.load("invalid name");
         assert!(matches!(result, Err(SessionError::InvalidName(_))));
@@ -779,8 +931,8 @@ mod tests {
 
     #[test]
     fn test_session_manager_list() {
-        let temp_dir = TempDir::new().unwrap();
-        let manager = SessionManager::with_dir(temp_dir.path());
+        let (_tmp, db) = setup();
+        let manager = SessionManager::new(&db);
 
         // Save multiple sessions
         manager.save("session-1", &[], "agent", "model").unwrap();
@@ -793,8 +945,8 @@ mod tests {
 
     #[test]
     fn test_session_manager_list_empty() {
-        let temp_dir = TempDir::new().unwrap();
-        let manager = SessionManager::with_dir(temp_dir.path());
+        let (_tmp, db) = setup();
+        let manager = SessionManager::new(&db);
 
         let sessions = manager.list().unwrap();
         assert!(sessions.is_empty());
@@ -802,8 +954,8 @@ mod tests {
 
     #[test]
     fn test_session_manager_list_sorted_by_updated_at() {
-        let temp_dir = TempDir::new().unwrap();
-        let manager = SessionManager::with_dir(temp_dir.path());
+        let (_tmp, db) = setup();
+        let manager = SessionManager::new(&db);
 
         manager.save("old", &[], "agent", "model").unwrap();
         std::thread::sleep(std::time::Duration::from_millis(20));
@@ -816,61 +968,14 @@ mod tests {
         assert_eq!(sessions[1].name, "old");
     }
 
-    #[test]
-    fn test_session_manager_list_skips_meta_files() {
-        let temp_dir = TempDir::new().unwrap();
-        let manager = SessionManager::with_dir(temp_dir.path());
-
-        // Create a valid session
-        manager.save("valid", &[], "agent", "model").unwrap();
-
-        // Create a file that looks like a meta file (should be skipped)
-        let meta_path = temp_dir.path().join("something_meta.json");
-        fs::write(&meta_path, "{}").unwrap();
-
-        let sessions = manager.list().unwrap();
-        assert_eq!(sessions.len(), 1);
-        assert_eq!(sessions[0].name, "valid");
-    }
-
-    #[test]
-    fn test_session_manager_list_ignores_invalid_json() {
-        let temp_dir = TempDir::new().unwrap();
-        let manager = SessionManager::with_dir(temp_dir.path());
-
-        manager.save("valid", &[], "agent", "model").unwrap();
-
-        // Create invalid JSON file
-        let invalid_path = temp_dir.path().join("invalid.json");
-        fs::write(&invalid_path, "not valid json").unwrap();
-
-        let sessions = manager.list().unwrap();
-        assert_eq!(sessions.len(), 1);
-    }
-
-    #[test]
-    fn test_session_manager_list_ignores_non_json_files() {
-        let temp_dir = TempDir::new().unwrap();
-        let manager = SessionManager::with_dir(temp_dir.path());
-
-        manager.save("valid", &[], "agent", "model").unwrap();
-
-        // Create non-json file
-        let txt_path = temp_dir.path().join("notes.txt");
-        fs::write(&txt_path, "some notes").unwrap();
-
-        let sessions = manager.list().unwrap();
-        assert_eq!(sessions.len(), 1);
-    }
-
     // =========================================================================
     // SessionManager Delete Tests
     // =========================================================================
 
     #[test]
     fn test_session_manager_delete() {
-        let temp_dir = TempDir::new().unwrap();
-        let manager = SessionManager::with_dir(temp_dir.path());
+        let (_tmp, db) = setup();
+        let manager = SessionManager::new(&db);
 
         manager.save("to-delete", &[], "agent", "model").unwrap();
         assert!(manager.exists("to-delete"));
@@ -881,8 +986,8 @@ mod tests {
 
     #[test]
     fn test_session_manager_delete_not_found() {
-        let temp_dir = TempDir::new().unwrap();
-        let manager = SessionManager::with_dir(temp_dir.path());
+        let (_tmp, db) = setup();
+        let manager = SessionManager::new(&db);
 
         let result = manager.delete("nonexistent");
         assert!(matches!(result, Err(SessionError::NotFound(_))));
@@ -890,8 +995,8 @@ mod tests {
 
     #[test]
     fn test_session_manager_delete_invalid_name() {
-        let temp_dir = TempDir::new().unwrap();
-        let manager = SessionManager::with_dir(temp_dir.path());
+        let (_tmp, db) = setup();
+        let manager = SessionManager::new(&db);
 
         let result = manager.delete("invalid name");
         assert!(matches!(result, Err(SessionError::InvalidName(_))));
@@ -903,8 +1008,8 @@ mod tests {
 
     #[test]
     fn test_session_manager_exists() {
-        let temp_dir = TempDir::new().unwrap();
-        let manager = SessionManager::with_dir(temp_dir.path());
+        let (_tmp, db) = setup();
+        let manager = SessionManager::new(&db);
 
         assert!(!manager.exists("new-session"));
 
@@ -914,8 +1019,8 @@ mod tests {
 
     #[test]
     fn test_session_manager_exists_invalid_name() {
-        let temp_dir = TempDir::new().unwrap();
-        let manager = SessionManager::with_dir(temp_dir.path());
+        let (_tmp, db) = setup();
+        let manager = SessionManager::new(&db);
 
         // Invalid name should return false (validation fails)
         assert!(!manager.exists("invalid name"));
@@ -928,8 +1033,8 @@ mod tests {
 
     #[test]
     fn test_generate_name() {
-        let temp_dir = TempDir::new().unwrap();
-        let manager = SessionManager::with_dir(temp_dir.path());
+        let (_tmp, db) = setup();
+        let manager = SessionManager::new(&db);
 
         let name1 = manager.generate_name("chat");
         let name2 = manager.generate_name("chat");
@@ -940,8 +1045,8 @@ mod tests {
 
     #[test]
     fn test_generate_name_with_collision() {
-        let temp_dir = TempDir::new().unwrap();
-        let manager = SessionManager::with_dir(temp_dir.path());
+        let (_tmp, db) = setup();
+        let manager = SessionManager::new(&db);
 
         // Generate a name and save it
         let name1 = manager.generate_name("test");
@@ -955,8 +1060,8 @@ mod tests {
 
     #[test]
     fn test_generate_name_different_prefixes() {
-        let temp_dir = TempDir::new().unwrap();
-        let manager = SessionManager::with_dir(temp_dir.path());
+        let (_tmp, db) = setup();
+        let manager = SessionManager::new(&db);
 
         let chat = manager.generate_name("chat");
         let debug = manager.generate_name("debug");
@@ -971,8 +1076,8 @@ mod tests {
 
     #[test]
     fn test_cleanup_removes_old_sessions() {
-        let temp_dir = TempDir::new().unwrap();
-        let manager = SessionManager::with_dir(temp_dir.path()).with_max_sessions(2);
+        let (_tmp, db) = setup();
+        let manager = SessionManager::new(&db).with_max_sessions(2);
 
         // Create 4 sessions with delays to ensure different timestamps
         for i in 1..=4 {
@@ -994,8 +1099,8 @@ mod tests {
 
     #[test]
     fn test_cleanup_unlimited_sessions() {
-        let temp_dir = TempDir::new().unwrap();
-        let manager = SessionManager::with_dir(temp_dir.path()).with_max_sessions(0);
+        let (_tmp, db) = setup();
+        let manager = SessionManager::new(&db).with_max_sessions(0);
 
         // Create many sessions
         for i in 1..=10 {
@@ -1011,8 +1116,8 @@ mod tests {
 
     #[test]
     fn test_cleanup_no_action_under_limit() {
-        let temp_dir = TempDir::new().unwrap();
-        let manager = SessionManager::with_dir(temp_dir.path()).with_max_sessions(10);
+        let (_tmp, db) = setup();
+        let manager = SessionManager::new(&db).with_max_sessions(10);
 
         manager.save("session-1", &[], "agent", "model").unwrap();
         manager.save("session-2", &[], "agent", "model").unwrap();
@@ -1146,8 +1251,8 @@ mod tests {
 
     #[test]
     fn test_full_session_lifecycle() {
-        let temp_dir = TempDir::new().unwrap();
-        let manager = SessionManager::with_dir(temp_dir.path());
+        let (_tmp, db) = setup();
+        let manager = SessionManager::new(&db);
 
         // Create
         let messages = create_test_messages(3);
@@ -1178,80 +1283,119 @@ mod tests {
     }
 
     #[test]
-    fn test_session_path_generation() {
-        let temp_dir = TempDir::new().unwrap();
-        let manager = SessionManager::with_dir(temp_dir.path());
+    fn test_save_stores_one_row_per_message() {
+        let (_tmp, db) = setup();
+        let manager = SessionManager::new(&db);
 
-        manager.save("test-session", &[], "agent", "model").unwrap();
+        let meta = manager
+            .save("rows", &create_test_messages(3), "agent", "model")
+            .unwrap();
 
-        let expected_path = temp_dir.path().join("test-session.json");
-        assert!(expected_path.exists());
+        let summary = db.sessions().get("rows").unwrap().unwrap();
+        let rows = db.sessions().messages(summary.id).unwrap();
+        assert_eq!(rows.len(), 3);
+        assert!(rows.iter().all(|row| row.role == "user"));
+        let tokens: i64 = rows.iter().filter_map(|row| row.token_count).sum();
+        assert_eq!(meta.token_estimate, tokens as usize);
     }
 
-    // =========================================================================
-    // Additional Edge Case Tests
-    // =========================================================================
-
     #[test]
-    fn test_load_corrupted_json_file() {
-        let temp_dir = TempDir::new().unwrap();
-        let manager = SessionManager::with_dir(temp_dir.path());
+    fn test_save_appends_new_messages() {
+        let (_tmp, db) = setup();
+        let manager = SessionManager::new(&db);
 
-        // Create a valid session first
-        manager.save("test", &[], "agent", "model").unwrap();
+        let mut messages = create_test_messages(2);
+        manager.save("grow", &messages, "agent", "model").unwrap();
+        let summary = db.sessions().get("grow").unwrap().unwrap();
+        let first_ids: Vec<_> = db
+            .sessions()
+            .messages(summary.id)
+            .unwrap()
+            .iter()
+            .map(|row| row.id)
+            .collect();
 
-        // Corrupt the file
-        let path = temp_dir.path().join("test.json");
-        fs::write(&path, "{ invalid json structure").unwrap();
+        messages.push(create_test_message("Another"));
+        manager.save("grow", &messages, "agent", "model").unwrap();
 
-        let result = manager.load("test");
-        assert!(matches!(result, Err(SessionError::Serialization(_))));
+        let rows = db.sessions().messages(summary.id).unwrap();
+        assert_eq!(rows.len(), 3);
+        assert_eq!(rows[0].id, first_ids[0]);
+        assert_eq!(rows[1].id, first_ids[1]);
     }
 
     #[test]
-    fn test_load_wrong_json_schema() {
-        let temp_dir = TempDir::new().unwrap();
-        let manager = SessionManager::with_dir(temp_dir.path());
-        manager.ensure_dir().unwrap();
+    fn test_search_sessions() {
+        let (_tmp, db) = setup();
+        let manager = SessionManager::new(&db);
 
-        // Write valid JSON but wrong schema
-        let path = temp_dir.path().join("wrong-schema.json");
-        fs::write(&path, r#"{"foo": "bar", "baz": 123}"#).unwrap();
+        manager
+            .save("parser", &create_test_messages(1), "agent", "model")
+            .unwrap();
+        manager
+            .save(
+                "chat",
+                &[create_test_message("Why is the tokenizer slow?")],
+                "agent",
+                "model",
+            )
+            .unwrap();
 
-        let result = manager.load("wrong-schema");
-        assert!(matches!(result, Err(SessionError::Serialization(_))));
-    }
+        let by_name = manager.search("parser").unwrap();
+        assert_eq!(by_name.len(), 1);
+        assert_eq!(by_name[0].name, "parser");
 
-    #[test]
-    fn test_save_creates_nested_directories() {
-        let temp_dir = TempDir::new().unwrap();
-        let nested = temp_dir.path().join("a").join("b").join("c");
-        let manager = SessionManager::with_dir(&nested);
+        let by_content = manager.search("tokenizer").unwrap();
+        assert_eq!(by_content.len(), 1);
+        assert_eq!(by_content[0].name, "chat");
 
-        // Should create all dirs and save successfully
-        let result = manager.save("test", &[], "agent", "model");
-        assert!(result.is_ok());
-        assert!(nested.exists());
+        assert!(manager.search("nothing matches").unwrap().is_empty());
     }
 
+    // =========================================================================
+    // Additional Edge Case Tests
+    // =========================================================================
+
     #[test]
-    fn test_ensure_dir_idempotent() {
-        let temp_dir = TempDir::new().unwrap();
-        let manager = SessionManager::with_dir(temp_dir.path());
+    fn test_load_corrupted_message_row() {
+        let (_tmp, db) = setup();
+        let manager = SessionManager::new(&db);
 
-        // Call multiple times - should not error
-        assert!(manager.ensure_dir().is_ok());
-        assert!(manager.ensure_dir().is_ok());
-        assert!(manager.ensure_dir().is_ok());
+        manager
+            .save("test", &create_test_messages(1), "agent", "model")
+            .unwrap();
+
+        // Corrupt the stored message
+        db.conn()
+            .execute(
+                "UPDATE messages SET content = '{ invalid json structure'",
+                [],
+            )
+            .unwrap();
+
+        let result = manager.load("test");
+        assert!(matches!(result, Err(SessionError::Serialization(_))));
     }
 
     #[test]
-    fn test_sessions_dir_accessor() {
-        let temp_dir = TempDir::new().unwrap();
-        let path = temp_dir.path().to_path_buf();
-        let manager = SessionManager::with_dir(&path);
+    fn test_load_wrong_json_schema() {
+        let (_tmp, db) = setup();
+        let manager = SessionManager::new(&db);
+
+        manager
+            .save("wrong-schema", &create_test_messages(1), "agent", "model")
+            .unwrap();
 
-        assert_eq!(manager.sessions_dir(), path);
+        // Valid JSON but wrong schema
+        db.conn()
+            .execute(
+                r#"UPDATE messages SET content = '{"foo": "bar", "baz": 123}'"#,
+                [],
+            )
+            .unwrap();
+
+        let result = manager.load("wrong-schema");
+        assert!(matches!(result, Err(SessionError::Serialization(_))));
     }
 
     #[test]
@@ -1342,8 +1486,8 @@ mod tests {
 
     #[test]
     fn test_save_empty_messages_then_load() {
-        let temp_dir = TempDir::new().unwrap();
-        let manager = SessionManager::with_dir(temp_dir.path());
+        let (_tmp, db) = setup();
+        let manager = SessionManager::new(&db);
 
         manager.save("empty", &[], "agent", "model").unwrap();
         let loaded = manager.load("empty").unwrap();
@@ -1353,25 +1497,10 @@ mod tests {
         assert_eq!(loaded.meta.token_estimate, 0);
     }
 
-    #[test]
-    fn test_list_creates_dir_if_missing() {
-        let temp_dir = TempDir::new().unwrap();
-        let sessions_path = temp_dir.path().join("sessions");
-        let manager = SessionManager::with_dir(&sessions_path);
-
-        // Dir doesn't exist yet
-        assert!(!sessions_path.exists());
-
-        // list() should create it
-        let result = manager.list();
-        assert!(result.is_ok());
-        assert!(sessions_path.exists());
-    }
-
     #[test]
     fn test_multiple_save_same_session_preserves_created_at() {
-        let temp_dir = TempDir::new().unwrap();
-        let manager = SessionManager::with_dir(temp_dir.path());
+        let (_tmp, db) = setup();
+        let manager = SessionManager::new(&db);
 
         // First save
         manager.save("multi", &[], "agent1", "model1").unwrap();
@@ -1392,8 +1521,8 @@ mod tests {
 
     #[test]
     fn test_cleanup_handles_delete_failure_gracefully() {
-        let temp_dir = TempDir::new().unwrap();
-        let manager = SessionManager::with_dir(temp_dir.path()).with_max_sessions(1);
+        let (_tmp, db) = setup();
+        let manager = SessionManager::new(&db).with_max_sessions(1);
 
         // Create sessions
         manager.save("session-1", &[], "agent", "model").unwrap();
@@ -1425,8 +1554,8 @@ mod tests {
 
     #[test]
     fn test_session_with_large_message_content() {
-        let temp_dir = TempDir::new().unwrap();
-        let manager = SessionManager::with_dir(temp_dir.path());
+        let (_tmp, db) = setup();
+        let manager = SessionManager::new(&db);
 
         // Create message with large content
         let large_content = "x".repeat(100_000);
@@ -1442,8 +1571,8 @@ mod tests {
 
     #[test]
     fn test_generate_name_unique_across_rapid_calls() {
-        let temp_dir = TempDir::new().unwrap();
-        let manager = SessionManager::with_dir(temp_dir.path());
+        let (_tmp, db) = setup();
+        let manager = SessionManager::new(&db);
 
         let mut names = std::collections::HashSet::new();
         for _ in 0..10 {
@@ -1493,25 +1622,10 @@ mod tests {
         }
     }
 
-    #[test]
-    fn test_list_handles_empty_file() {
-        let temp_dir = TempDir::new().unwrap();
-        let manager = SessionManager::with_dir(temp_dir.path());
-        manager.ensure_dir().unwrap();
-
-        // Create empty file
-        let empty_path = temp_dir.path().join("empty.json");
-        fs::write(&empty_path, "").unwrap();
-
-        // Should gracefully skip
-        let sessions = manager.list().unwrap();
-        assert!(sessions.is_empty());
-    }
-
     #[test]
     fn test_exists_after_delete() {
-        let temp_dir = TempDir::new().unwrap();
-        let manager = SessionManager::with_dir(temp_dir.path());
+        let (_tmp, db) = setup();
+        let manager = SessionManager::new(&db);
 
         manager.save("transient", &[], "agent", "model").unwrap();
         assert!(manager.exists("transient"));
@@ -1544,4 +1658,174 @@ mod tests {
         assert!(data.messages.is_empty());
         assert_eq!(data.meta.message_count, 0);
     }
+    // =========================================================================
+    // Legacy JSON Import Tests
+    // =========================================================================
+
+    fn write_legacy_session(dir: &Path, name: &str, message_count: usize) -> SessionData {
+        let mut data = SessionData::new(name, "legacy-agent", "legacy-model");
+        data.update(create_test_messages(message_count));
+        data.meta.description = Some("From JSON".to_string());
+        let content = serde_json::to_string_pretty(&data).unwrap();
+        fs::write(dir.join(format!("{}.json", name)), content).unwrap();
+        data
+    }
+
+    #[test]
+    fn test_import_legacy_sessions() {
+        let (tmp, db) = setup();
+        let legacy_dir = tmp.path().join("sessions");
+        fs::create_dir_all(&legacy_dir).unwrap();
+        let original = write_legacy_session(&legacy_dir, "old-chat", 3);
+
+        let manager = SessionManager::new(&db).with_legacy_dir(&legacy_dir);
+        assert_eq!(manager.import_legacy_sessions().unwrap(), 1);
+
+        let loaded = manager.load("old-chat").unwrap();
+        assert_eq!(loaded.messages.len(), 3);
+        assert_eq!(loaded.meta.agent, "legacy-agent");
+        assert_eq!(loaded.meta.model, "legacy-model");
+        assert_eq!(loaded.meta.description.as_deref(), Some("From JSON"));
+        assert_eq!(
+            loaded.meta.created_at.timestamp_millis(),
+            original.meta.created_at.timestamp_millis()
+        );
+    }
+
+    #[test]
+    fn test_import_legacy_sessions_runs_once() {
+        let (tmp, db) = setup();
+        let manager = SessionManager::new(&db).with_legacy_dir(tmp.path());
+
+        write_legacy_session(tmp.path(), "first", 1);
+        assert_eq!(manager.import_legacy_sessions().unwrap(), 1);
+
+        write_legacy_session(tmp.path(), "second", 1);
+        assert_eq!(manager.import_legacy_sessions().unwrap(), 0);
+        assert!(!manager.exists("second"));
+    }
+
+    #[test]
+    fn test_import_legacy_sessions_skips_unrelated_files() {
+        let (tmp, db) = setup();
+        let manager = SessionManager::new(&db).with_legacy_dir(tmp.path());
+
+        write_legacy_session(tmp.path(), "valid", 1);
+        fs::write(tmp.path().join("something_meta.json"), "{}").unwrap();
+        fs::write(tmp.path().join("invalid.json"), "not valid json").unwrap();
+        fs::write(tmp.path().join("empty.json"), "").unwrap();
+        fs::write(tmp.path().join("notes.txt"), "some notes").unwrap();
+
+        assert_eq!(manager.import_legacy_sessions().unwrap(), 1);
+        let sessions = manager.list().unwrap();
+        assert_eq!(sessions.len(), 1);
+        assert_eq!(sessions[0].name, "valid");
+    }
+
+    #[test]
+    fn test_import_legacy_sessions_keeps_existing() {
+        let (tmp, db) = setup();
+        let manager = SessionManager::new(&db).with_legacy_dir(tmp.path());
+
+        manager
+            .save("shared", &create_test_messages(2), "agent", "model")
+            .unwrap();
+        write_legacy_session(tmp.path(), "shared", 5);
+
+        assert_eq!(manager.import_legacy_sessions().unwrap(), 0);
+        assert_eq!(manager.load("shared").unwrap().messages.len(), 2);
+    }
+
+    #[test]
+    fn test_import_legacy_sessions_missing_dir() {
+        let (tmp, db) = setup();
+        let manager = SessionManager::new(&db).with_legacy_dir(tmp.path().join("missing"));
+        assert_eq!(manager.import_legacy_sessions().unwrap(), 0);
+    }
+
+    // =========================================================================
+    // Latest Session / Transcript Tests
+    // =========================================================================
+
+    #[test]
+    fn test_load_latest_returns_most_recent() {
+        let (_tmp, db) = setup();
+        let manager = SessionManager::new(&db);
+
+        manager
+            .save("older", &create_test_messages(1), "agent", "model")
+            .unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(10));
+        manager
+            .save("newer", &create_test_messages(2), "agent", "model")
+            .unwrap();
+
+        let latest = manager.load_latest().unwrap();
+        assert_eq!(latest.meta.name, "newer");
+        assert_eq!(latest.messages.len(), 2);
+    }
+
+    #[test]
+    fn test_load_latest_empty() {
+        let (_tmp, db) = setup();
+        let manager = SessionManager::new(&db);
+
+        let result = manager.load_latest();
+        assert!(matches!(result, Err(SessionError::NotFound(_))));
+    }
+
+    #[test]
+    fn test_transcript_extracts_user_and_assistant_text() {
+        use serdes_ai_core::{ModelResponse, TextPart};
+
+        let mut response_req = ModelRequest::new();
+        response_req
+            .parts
+            .push(ModelRequestPart::ModelResponse(Box::new(
+                ModelResponse::with_parts(vec![ModelResponsePart::Text(TextPart::new(
+                    "Hi there".to_string(),
+                ))]),
+            )));
+
+        let messages = vec![create_test_message("Hello"), response_req];
+        assert_eq!(
+            transcript(&messages),
+            vec![
+                TranscriptEntry::User("Hello".to_string()),
+                TranscriptEntry::Assistant("Hi there".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_transcript_merges_consecutive_responses() {
+        use serdes_ai_core::{ModelResponse, TextPart};
+
+        let response = |text: &str| {
+            let mut req = ModelRequest::new();
+            req.parts.push(ModelRequestPart::ModelResponse(Box::new(
+                ModelResponse::with_parts(vec![ModelResponsePart::Text(TextPart::new(
+                    text.to_string(),
+                ))]),
+            )));
+            req
+        };
+
+        let messages = vec![
+            create_test_message("Fix it"),
+            response("Looking"),
+            response("Done"),
+        ];
+        let entries = transcript(&messages);
+        assert_eq!(entries.len(), 2);
+        assert_eq!(
+            entries[1],
+            TranscriptEntry::Assistant("Looking\n\nDone".to_string())
+        );
+    }
+
+    #[test]
+    fn test_transcript_empty() {
+        assert!(transcript(&[]).is_empty());
+    }
 }
@@ -48,6 +48,8 @@ pub struct MockModel {
     behavior: Mutex<MockBehavior>,
     /// Records all requests made to this model for later assertion.
     request_log: Arc<Mutex<Vec<Vec<ModelRequest>>>>,
+    /// Debug rendering of the settings sent with each request.
+    settings_log: Mutex<Vec<String>>,
 }
 
 impl MockModel {
@@ -59,6 +61,7 @@ impl MockModel {
             profile: ModelProfile::default(),
             behavior: Mutex::new(MockBehavior::default()),
             request_log: Arc::new(Mutex::new(Vec::new())),
+            settings_log: Mutex::new(Vec::new()),
         }
     }
 
@@ -91,6 +94,11 @@ impl MockModel {
         self.request_log.lock().unwrap().len()
     }
 
+    /// Get the settings sent with each request, rendered with `{:?}`.
+    pub fn settings_log(&self) -> Vec<String> {
+        self.settings_log.lock().unwrap().clone()
+    }
+
     fn resolve_behavior(&self) -> MockBehavior {
         let mut behavior = self.behavior.lock().unwrap();
         match &mut *behavior {
@@ -123,11 +131,15 @@ impl Model for MockModel {
     async fn request(
         &self,
         messages: &[ModelRequest],
-        _settings: &CoreModelSettings,
+        settings: &CoreModelSettings,
         _params: &ModelRequestParameters,
     ) -> Result<ModelResponse, ModelError> {
         // Record the request
         self.request_log.lock().unwrap().push(messages.to_vec());
+        self.settings_log
+            .lock()
+            .unwrap()
+            .push(format!("{:?}", settings));
 
         match self.resolve_behavior() {
             MockBehavior::Text(text) => Ok(ModelResponse::text(text)),
@@ -0,0 +1,702 @@
+//! Permission policy for shell commands requested by agents.
+//!
+//! Every frontend (GUI, TUI, headless) asks [`CommandPolicy`] what to do with
+//! a `run_shell_command` request. The decision is made in this order:
+//!
+//! 1. High-risk commands (see `validate_command`) are denied, even in YOLO mode
+//! 2. The first matching `[[permissions.rules]]` entry from the config files
+//!    (for commands chained with `;`, `&&`, `|`, `$(`, redirects etc., `deny`
+//!    rules are checked against every segment and `allow` rules never match)
+//! 3. Commands the user chose to "always allow" are allowed
+//! 4. YOLO mode allows everything else
+//! 5. Otherwise the user is asked
+//!
+//! Text an agent types into a running process (`write_process_input`) is
+//! checked line by line with [`CommandPolicy::evaluate_input`], since a shell
+//! or interpreter reading it will run it.
+//!
+//! File writes and deletes from `edit_file` / `delete_file` go through
+//! [`CommandPolicy::evaluate_file_change`]: the first matching
+//! `[[permissions.file_rules]]` entry decides, then YOLO mode allows, and
+//! otherwise the user is asked to review the diff.
+
+use std::collections::HashSet;
+use std::path::{Component, Path, PathBuf};
+
+use globset::{Glob, GlobMatcher};
+use tracing::warn;
+
+use crate::config::{CommandAction, CommandRule, FileRule, Settings, SettingsError, SpotConfig};
+use crate::db::Database;
+use crate::terminal::{
+    has_shell_metacharacters, split_shell_segments, validate_command, CommandValidation,
+    FileOperation, RiskLevel,
+};
+
+/// Outcome of evaluating a command against the policy.
+#[derive(Debug, Clone)]
+pub struct PolicyDecision {
+    /// Whether to run, ask or refuse.
+    pub action: CommandAction,
+    /// Human-readable explanation of which check decided.
+    pub reason: String,
+    /// Risk assessment of the command.
+    pub validation: CommandValidation,
+}
+
+/// Outcome of evaluating a file change against the policy.
+#[derive(Debug, Clone)]
+pub struct FileDecision {
+    /// Whether to apply, ask or refuse.
+    pub action: CommandAction,
+    /// Human-readable explanation of which check decided.
+    pub reason: String,
+}
+
+/// A rule with its globs compiled.
+struct CompiledRule {
+    rule: CommandRule,
+    command: Option<GlobMatcher>,
+    cwd: Option<GlobMatcher>,
+}
+
+impl CompiledRule {
+    /// Compile a rule. Returns `None` if one of its globs is invalid.
+    fn compile(rule: CommandRule) -> Option<Self> {
+        let matcher = |pattern: &Option<String>| -> Result<Option<GlobMatcher>, globset::Error> {
+            pattern
+                .as_deref()
+                .map(|p| Glob::new(p).map(|g| g.compile_matcher()))
+                .transpose()
+        };
+
+        match (matcher(&rule.command), matcher(&rule.cwd)) {
+            (Ok(command), Ok(cwd)) => Some(Self { rule, command, cwd }),
+            (Err(e), _) | (_, Err(e)) => {
+                warn!(error = %e, "Skipping permission rule with invalid glob");
+                None
+            }
+        }
+    }
+
+    fn matches(&self, command: &str, cwd: &Path, risk: RiskLevel) -> bool {
+        self.command.as_ref().is_none_or(|m| m.is_match(command))
+            && self.cwd.as_ref().is_none_or(|m| m.is_match(cwd))
+            && self.rule.risk.is_none_or(|r| r == risk)
+    }
+
+    fn describe(&self) -> String {
+        let mut parts = Vec::new();
+        if let Some(ref command) = self.rule.command {
+            parts.push(format!("command = {:?}", command));
+        }
+        if let Some(ref cwd) = self.rule.cwd {
+            parts.push(format!("cwd = {:?}", cwd));
+        }
+        if let Some(risk) = self.rule.risk {
+            let risk = match risk {
+                RiskLevel::Low => "low",
+                RiskLevel::Medium => "medium",
+                RiskLevel::High => "high",
+            };
+            parts.push(format!("risk = {}", risk));
+        }
+        if parts.is_empty() {
+            "catch-all rule".to_string()
+        } else {
+            format!("rule {}", parts.join(", "))
+        }
+    }
+}
+
+/// A file rule with its path glob compiled.
+struct CompiledFileRule {
+    rule: FileRule,
+    path: Option<GlobMatcher>,
+}
+
+impl CompiledFileRule {
+    /// Compile a rule. Returns `None` if its glob is invalid.
+    fn compile(rule: FileRule) -> Option<Self> {
+        match rule.path.as_deref().map(Glob::new).transpose() {
+            Ok(glob) => Some(Self {
+                path: glob.map(|g| g.compile_matcher()),
+                rule,
+            }),
+            Err(e) => {
+                warn!(error = %e, "Skipping file rule with invalid glob");
+                None
+            }
+        }
+    }
+
+    fn matches(&self, path: &Path, operation: FileOperation) -> bool {
+        self.path.as_ref().is_none_or(|m| m.is_match(path))
+            && self.rule.operation.is_none_or(|o| o == operation)
+    }
+
+    fn describe(&self) -> String {
+        let mut parts = Vec::new();
+        if let Some(ref path) = self.rule.path {
+            parts.push(format!("path = {:?}", path));
+        }
+        if let Some(operation) = self.rule.operation {
+            let operation = match operation {
+                FileOperation::Write => "write",
+                FileOperation::Delete => "delete",
+            };
+            parts.push(format!("operation = {}", operation));
+        }
+        if parts.is_empty() {
+            "catch-all file rule".to_string()
+        } else {
+            format!("file rule {}", parts.join(", "))
+        }
+    }
+}
+
+/// Decides whether agent shell commands and file changes go ahead, need
+/// approval, or are refused.
+pub struct CommandPolicy {
+    rules: Vec<CompiledRule>,
+    file_rules: Vec<CompiledFileRule>,
+    allowed: HashSet<String>,
+    yolo_mode: bool,
+}
+
+impl CommandPolicy {
+    /// Create a policy from rules, always-allowed commands and the YOLO flag.
+    ///
+    /// Rules with invalid globs are skipped (`SpotConfig::validate` reports them).
+    pub fn new(rules: Vec<CommandRule>, allowed: Vec<String>, yolo_mode: bool) -> Self {
+        Self {
+            rules: rules
+                .into_iter()
+                .filter_map(CompiledRule::compile)
+                .collect(),
+            file_rules: Vec::new(),
+            allowed: allowed.into_iter().map(|c| c.trim().to_string()).collect(),
+            yolo_mode,
+        }
+    }
+
+    /// Load the policy from the layered config and persisted approvals.
+    pub fn load(db: &Database) -> Self {
+        let settings = Settings::new(db);
+        let config = SpotConfig::load(&settings);
+        Self::new(
+            config.permissions.rules,
+            settings.allowed_commands(),
+            config.yolo_mode,
+        )
+        .with_file_rules(config.permissions.file_rules)
+    }
+
+    /// Add rules for file writes and deletes.
+    ///
+    /// Rules with invalid globs are skipped, as in [`CommandPolicy::new`].
+    pub fn with_file_rules(mut self, rules: Vec<FileRule>) -> Self {
+        self.file_rules = rules
+            .into_iter()
+            .filter_map(CompiledFileRule::compile)
+            .collect();
+        self
+    }
+
+    /// Decide what to do with `command` run in `cwd`.
+    ///
+    /// A missing or relative `cwd` is resolved against the current directory,
+    /// and `.` and `..` are resolved before rules see it.
+    pub fn evaluate(&self, command: &str, cwd: Option<&str>) -> PolicyDecision {
+        let validation = validate_command(command);
+        let command = command.trim();
+        let Some(cwd) = resolve_path(cwd) else {
+            return PolicyDecision {
+                action: CommandAction::Deny,
+                reason: "working directory escapes the filesystem root".to_string(),
+                validation,
+            };
+        };
+
+        let chained = has_shell_metacharacters(command);
+
+        let (action, reason) = if validation.risk_level == RiskLevel::High {
+            (
+                CommandAction::Deny,
+                "matches a dangerous command pattern".to_string(),
+            )
+        } else if let Some(rule) = chained
+            .then(|| self.denied_segment(command, &cwd))
+            .flatten()
+        {
+            (
+                CommandAction::Deny,
+                format!("a chained command matched {}", rule.describe()),
+            )
+        } else if let Some(rule) = self.rules.iter().find(|r| {
+            // An allow rule only vouches for a single command
+            !(chained && r.rule.action == CommandAction::Allow)
+                && r.matches(command, &cwd, validation.risk_level)
+        }) {
+            (rule.rule.action, format!("matched {}", rule.describe()))
+        } else if self.allowed.contains(command) {
+            (CommandAction::Allow, "always allowed".to_string())
+        } else if self.yolo_mode {
+            (CommandAction::Allow, "YOLO mode".to_string())
+        } else {
+            (CommandAction::Ask, "no rule matched".to_string())
+        };
+
+        PolicyDecision {
+            action,
+            reason,
+            validation,
+        }
+    }
+
+    /// Decide what to do with text typed into a running process.
+    ///
+    /// Each line is evaluated like a command run in the current directory,
+    /// since a shell or interpreter will run it, and the strictest decision
+    /// wins. Input without any text is allowed.
+    pub fn evaluate_input(&self, input: &str) -> PolicyDecision {
+        let strictness = |action: CommandAction| match action {
+            CommandAction::Allow => 0,
+            CommandAction::Ask => 1,
+            CommandAction::Deny => 2,
+        };
+
+        let mut strictest: Option<PolicyDecision> = None;
+        for line in input.split(['\r', '\n']) {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let decision = self.evaluate(line, None);
+            if strictest
+                .as_ref()
+                .is_none_or(|s| strictness(decision.action) > strictness(s.action))
+            {
+                strictest = Some(decision);
+            }
+        }
+
+        strictest.unwrap_or_else(|| PolicyDecision {
+            action: CommandAction::Allow,
+            reason: "no text input".to_string(),
+            validation: validate_command(""),
+        })
+    }
+
+    /// First deny rule matching one of the segments of a chained command.
+    fn denied_segment(&self, command: &str, cwd: &Path) -> Option<&CompiledRule> {
+        split_shell_segments(command)
+            .into_iter()
+            .find_map(|segment| {
+                let risk = validate_command(segment).risk_level;
+                self.rules
+                    .iter()
+                    .find(|r| r.rule.action == CommandAction::Deny && r.matches(segment, cwd, risk))
+            })
+    }
+
+    /// Decide what to do with a tool writing or deleting `path`.
+    ///
+    /// A relative `path` is resolved against the current directory, and `.`
+    /// and `..` are resolved before rules see it.
+    pub fn evaluate_file_change(&self, path: &str, operation: FileOperation) -> FileDecision {
+        let Some(path) = resolve_path(Some(path)) else {
+            return FileDecision {
+                action: CommandAction::Deny,
+                reason: "path escapes the filesystem root".to_string(),
+            };
+        };
+
+        let (action, reason) =
+            if let Some(rule) = self.file_rules.iter().find(|r| r.matches(&path, operation)) {
+                (rule.rule.action, format!("matched {}", rule.describe()))
+            } else if self.yolo_mode {
+                (CommandAction::Allow, "YOLO mode".to_string())
+            } else {
+                (CommandAction::Ask, "no file rule matched".to_string())
+            };
+
+        FileDecision { action, reason }
+    }
+
+    /// Persist an "always allow" decision for `command`.
+    pub fn remember(db: &Database, command: &str) -> Result<(), SettingsError> {
+        Settings::new(db).add_allowed_command(command)
+    }
+}
+
+/// Resolve a request's working directory or file path to an absolute path.
+///
+/// `.` and `..` are resolved lexically, so `/work/../etc` can't match a rule
+/// for `/work/**`. Returns `None` if `..` climbs above the filesystem root.
+fn resolve_path(path: Option<&str>) -> Option<PathBuf> {
+    let current = std::env::current_dir().unwrap_or_default();
+    let path = match path {
+        Some(path) => current.join(path),
+        None => current,
+    };
+
+    let mut resolved = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                if !resolved.pop() {
+                    return None;
+                }
+            }
+            component => resolved.push(component),
+        }
+    }
+    Some(resolved)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn rule(action: CommandAction, command: &str) -> CommandRule {
+        CommandRule {
+            action,
+            command: Some(command.to_string()),
+            cwd: None,
+            risk: None,
+        }
+    }
+
+    fn action(policy: &CommandPolicy, command: &str) -> CommandAction {
+        policy.evaluate(command, None).action
+    }
+
+    #[test]
+    fn test_default_policy_asks() {
+        let policy = CommandPolicy::new(vec![], vec![], false);
+        assert_eq!(action(&policy, "ls -la"), CommandAction::Ask);
+    }
+
+    #[test]
+    fn test_yolo_allows_but_not_high_risk() {
+        let policy = CommandPolicy::new(vec![], vec![], true);
+        assert_eq!(action(&policy, "./build.sh"), CommandAction::Allow);
+
+        let decision = policy.evaluate("rm -rf /", None);
+        assert_eq!(decision.action, CommandAction::Deny);
+        assert_eq!(decision.validation.risk_level, RiskLevel::High);
+    }
+
+    #[test]
+    fn test_allow_rule_cannot_override_high_risk() {
+        let policy = CommandPolicy::new(vec![rule(CommandAction::Allow, "*")], vec![], false);
+        assert_eq!(action(&policy, "cargo build"), CommandAction::Allow);
+        assert_eq!(action(&policy, "sudo rm -rf /tmp/x"), CommandAction::Deny);
+    }
+
+    #[test]
+    fn test_first_matching_rule_wins() {
+        let policy = CommandPolicy::new(
+            vec![
+                rule(CommandAction::Deny, "git push*"),
+                rule(CommandAction::Allow, "git *"),
+            ],
+            vec![],
+            true,
+        );
+        assert_eq!(action(&policy, "git status"), CommandAction::Allow);
+        assert_eq!(action(&policy, "git push origin main"), CommandAction::Deny);
+        // Rules take precedence over YOLO mode
+        assert_eq!(action(&policy, "git push"), CommandAction::Deny);
+    }
+
+    #[test]
+    fn test_rule_matches_risk_level() {
+        let policy = CommandPolicy::new(
+            vec![CommandRule {
+                action: CommandAction::Allow,
+                command: None,
+                cwd: None,
+                risk: Some(RiskLevel::Low),
+            }],
+            vec![],
+            false,
+        );
+        assert_eq!(action(&policy, "ls"), CommandAction::Allow);
+        assert_eq!(action(&policy, "./deploy.sh"), CommandAction::Ask);
+    }
+
+    #[test]
+    fn test_rule_matches_cwd() {
+        let project = TempDir::new().unwrap();
+        let other = TempDir::new().unwrap();
+        let policy = CommandPolicy::new(
+            vec![CommandRule {
+                action: CommandAction::Allow,
+                command: Some("make *".to_string()),
+                cwd: Some(format!("{}/**", project.path().display())),
+                risk: None,
+            }],
+            vec![],
+            false,
+        );
+
+        let inside = project.path().join("src");
+        let decision = policy.evaluate("make test", inside.to_str());
+        assert_eq!(decision.action, CommandAction::Allow);
+        let decision = policy.evaluate("make test", other.path().to_str());
+        assert_eq!(decision.action, CommandAction::Ask);
+    }
+
+    #[test]
+    fn test_allow_rule_does_not_match_chained_commands() {
+        let policy = CommandPolicy::new(
+            vec![
+                rule(CommandAction::Allow, "cargo *"),
+                CommandRule {
+                    action: CommandAction::Allow,
+                    command: None,
+                    cwd: None,
+                    risk: Some(RiskLevel::Low),
+                },
+            ],
+            vec![],
+            false,
+        );
+        assert_eq!(action(&policy, "cargo test"), CommandAction::Allow);
+
+        for command in [
+            "cargo test; ./deploy.sh",
+            "cargo test && ./deploy.sh",
+            "cargo test || ./deploy.sh",
+            "cargo test | ./deploy.sh",
+            "cargo test & ./deploy.sh",
+            "cargo test $(./deploy.sh)",
+            "cargo test `./deploy.sh`",
+            "cargo test > ~/.bashrc",
+            "cargo test < input.txt",
+            "cargo test\n./deploy.sh",
+        ] {
+            let decision = policy.evaluate(command, None);
+            assert_eq!(decision.action, CommandAction::Ask, "{:?}", command);
+            assert_ne!(decision.validation.risk_level, RiskLevel::Low);
+        }
+    }
+
+    #[test]
+    fn test_deny_rule_matches_chained_segment() {
+        let policy = CommandPolicy::new(
+            vec![
+                rule(CommandAction::Deny, "git push*"),
+                rule(CommandAction::Ask, "npm *"),
+            ],
+            vec![],
+            true,
+        );
+
+        for command in [
+            "cargo build && git push origin main",
+            "true; git push",
+            "echo $(git push)",
+        ] {
+            let decision = policy.evaluate(command, None);
+            assert_eq!(decision.action, CommandAction::Deny, "{:?}", command);
+            assert!(decision.reason.contains("git push*"));
+        }
+        // Ask rules still match the whole command, and YOLO allows the rest
+        assert_eq!(action(&policy, "npm ci && npm test"), CommandAction::Ask);
+        assert_eq!(
+            action(&policy, "cargo build && cargo test"),
+            CommandAction::Allow
+        );
+    }
+
+    #[test]
+    fn test_always_allowed_matches_trimmed_command() {
+        let policy = CommandPolicy::new(vec![], vec!["cargo test".to_string()], false);
+        assert_eq!(action(&policy, "  cargo test "), CommandAction::Allow);
+        assert_eq!(action(&policy, "cargo test --release"), CommandAction::Ask);
+    }
+
+    #[test]
+    fn test_deny_rule_beats_always_allowed() {
+        let policy = CommandPolicy::new(
+            vec![rule(CommandAction::Deny, "curl *")],
+            vec!["curl https://example.com".to_string()],
+            false,
+        );
+        assert_eq!(
+            action(&policy, "curl https://example.com"),
+            CommandAction::Deny
+        );
+    }
+
+    #[test]
+    fn test_process_input_is_evaluated_per_line() {
+        let policy = CommandPolicy::new(
+            vec![
+                rule(CommandAction::Allow, "y"),
+                rule(CommandAction::Deny, "curl *"),
+            ],
+            vec![],
+            false,
+        );
+        assert_eq!(policy.evaluate_input("y\r").action, CommandAction::Allow);
+        assert_eq!(policy.evaluate_input("ls\n").action, CommandAction::Ask);
+        assert_eq!(
+            policy
+                .evaluate_input("y\nls\ncurl https://example.com\n")
+                .action,
+            CommandAction::Deny
+        );
+        assert_eq!(
+            policy.evaluate_input("rm -rf /\n").action,
+            CommandAction::Deny
+        );
+        assert_eq!(policy.evaluate_input("\r\n").action, CommandAction::Allow);
+    }
+
+    #[test]
+    fn test_invalid_rule_is_skipped() {
+        let policy = CommandPolicy::new(
+            vec![
+                rule(CommandAction::Deny, "cargo ["),
+                rule(CommandAction::Allow, "cargo *"),
+            ],
+            vec![],
+            false,
+        );
+        assert_eq!(action(&policy, "cargo check"), CommandAction::Allow);
+    }
+
+    #[test]
+    fn test_decision_reason_names_rule() {
+        let policy = CommandPolicy::new(vec![rule(CommandAction::Ask, "npm *")], vec![], true);
+        let decision = policy.evaluate("npm install", None);
+        assert_eq!(decision.action, CommandAction::Ask);
+        assert!(decision.reason.contains("npm *"));
+    }
+
+    fn file_rule(action: CommandAction, path: &str) -> FileRule {
+        FileRule {
+            action,
+            path: Some(path.to_string()),
+            operation: None,
+        }
+    }
+
+    #[test]
+    fn test_file_changes_ask_unless_yolo() {
+        let policy = CommandPolicy::new(vec![], vec![], false);
+        let decision = policy.evaluate_file_change("src/main.rs", FileOperation::Write);
+        assert_eq!(decision.action, CommandAction::Ask);
+
+        let policy = CommandPolicy::new(vec![], vec![], true);
+        let decision = policy.evaluate_file_change("src/main.rs", FileOperation::Delete);
+        assert_eq!(decision.action, CommandAction::Allow);
+    }
+
+    #[test]
+    fn test_file_rules_take_precedence_over_yolo() {
+        let policy = CommandPolicy::new(vec![], vec![], true).with_file_rules(vec![
+            file_rule(CommandAction::Deny, "**/.env"),
+            FileRule {
+                action: CommandAction::Ask,
+                path: None,
+                operation: Some(FileOperation::Delete),
+            },
+        ]);
+
+        let decision = policy.evaluate_file_change(".env", FileOperation::Write);
+        assert_eq!(decision.action, CommandAction::Deny);
+        assert!(decision.reason.contains("**/.env"));
+        assert_eq!(
+            policy
+                .evaluate_file_change("notes.md", FileOperation::Delete)
+                .action,
+            CommandAction::Ask
+        );
+        assert_eq!(
+            policy
+                .evaluate_file_change("notes.md", FileOperation::Write)
+                .action,
+            CommandAction::Allow
+        );
+    }
+
+    #[test]
+    fn test_file_rule_matches_absolute_path() {
+        let project = TempDir::new().unwrap();
+        let policy = CommandPolicy::new(vec![], vec![], false).with_file_rules(vec![file_rule(
+            CommandAction::Allow,
+            &format!("{}/**", project.path().display()),
+        )]);
+
+        let inside = project.path().join("src/lib.rs");
+        let decision = policy.evaluate_file_change(inside.to_str().unwrap(), FileOperation::Write);
+        assert_eq!(decision.action, CommandAction::Allow);
+        assert_eq!(
+            policy
+                .evaluate_file_change("/elsewhere/lib.rs", FileOperation::Write)
+                .action,
+            CommandAction::Ask
+        );
+    }
+
+    #[test]
+    fn test_paths_are_normalized_before_matching() {
+        let project = TempDir::new().unwrap();
+        let project_glob = format!("{}/**", project.path().display());
+        let policy = CommandPolicy::new(
+            vec![CommandRule {
+                action: CommandAction::Allow,
+                command: Some("make *".to_string()),
+                cwd: Some(project_glob.clone()),
+                risk: None,
+            }],
+            vec![],
+            false,
+        )
+        .with_file_rules(vec![file_rule(CommandAction::Allow, &project_glob)]);
+
+        let write = |path: &Path| {
+            policy
+                .evaluate_file_change(path.to_str().unwrap(), FileOperation::Write)
+                .action
+        };
+        assert_eq!(
+            write(&project.path().join("./src/../lib.rs")),
+            CommandAction::Allow
+        );
+        assert_eq!(
+            write(&project.path().join("src/../../outside.rs")),
+            CommandAction::Ask
+        );
+
+        let escaped = project.path().join("../elsewhere");
+        let decision = policy.evaluate("make test", escaped.to_str());
+        assert_eq!(decision.action, CommandAction::Ask);
+
+        // Climbing above the root is refused outright
+        let too_far = format!("/{}", "../".repeat(64));
+        let decision = policy.evaluate_file_change(&too_far, FileOperation::Write);
+        assert_eq!(decision.action, CommandAction::Deny);
+        assert_eq!(
+            policy.evaluate("make test", Some(&too_far)).action,
+            CommandAction::Deny
+        );
+    }
+
+    #[test]
+    fn test_remember_persists_approval() {
+        let temp = TempDir::new().unwrap();
+        let db = Database::open_at(temp.path().join("test.db")).unwrap();
+        db.migrate().unwrap();
+
+        CommandPolicy::remember(&db, "cargo fmt").unwrap();
+        let policy = CommandPolicy::load(&db);
+        assert_eq!(action(&policy, "cargo fmt"), CommandAction::Allow);
+    }
+}
@@ -1,4 +1,6 @@
 //! Tool re-exports and agent-specific tools.
 
 pub mod agent_tools;
+pub mod command_policy;
+pub use command_policy::{CommandPolicy, FileDecision, PolicyDecision};
 pub use spot_tools::tools::*;
@@ -34,6 +34,10 @@ struct Args {
     /// Skip the automatic update check on startup
     #[arg(long)]
     skip_update_check: bool,
+
+    /// Resume a saved session (the most recent one if no name is given)
+    #[arg(long, value_name = "NAME")]
+    resume: Option<Option<String>>,
 }
 
 fn main() -> Result<()> {
@@ -51,6 +55,7 @@ fn main() -> Result<()> {
             debug: args.debug,
             verbose: args.verbose,
             skip_update_check: args.skip_update_check,
+            resume: args.resume,
         };
         spot_gui::gui::run_gui(config)
     }
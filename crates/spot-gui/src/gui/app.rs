@@ -14,10 +14,11 @@ use gpui_component::input::{InputEvent, InputState};
 use super::components::{ListScrollbarDragState, ScrollbarDragState};
 use super::state::Conversation;
 use super::theme::Theme;
-use spot_core::agents::{AgentManager, UserMode};
-use spot_core::config::{PdfMode, Settings};
+use spot_core::agents::{AgentManager, CancelHandle, UserMode};
+use spot_core::checkpoints::CheckpointStore;
+use spot_core::config::{PdfMode, Settings, SpotConfig};
 use spot_core::db::Database;
-use spot_core::mcp::McpManager;
+use spot_core::mcp::{McpManager, McpSupervisorConfig};
 use spot_core::messaging::MessageBus;
 use spot_core::models::ModelRegistry;
 use spot_core::tools::{set_global_context, SpotToolRegistry, ToolContext};
@@ -40,14 +41,18 @@ actions!(
 mod actions;
 mod agent_dropdown;
 mod attachments;
+mod checkpoints;
 mod error;
 mod execution;
+mod file_approvals;
 mod input;
+mod mcp_catalog;
 mod messages;
 mod metrics;
 mod model_dropdown;
 mod model_management;
 mod scroll_animation;
+mod sessions;
 mod settings;
 mod streaming;
 mod system_executions;
@@ -80,6 +85,10 @@ pub struct ChatApp {
     theme: Theme,
     /// Whether we're currently generating a response
     is_generating: bool,
+    /// Cancel handle of the running agent turn
+    cancel_handle: Option<CancelHandle>,
+    /// File checkpoints for `/undo` and per-edit revert
+    checkpoints: CheckpointStore,
     /// Whether VDI mode is active (reduced frame rate for remote desktop)
     vdi_mode: bool,
     /// Whether a re-render is needed on the next animation tick
@@ -98,6 +107,8 @@ pub struct ChatApp {
     mcp_manager: Arc<McpManager>,
     /// Message history for context
     message_history: Vec<serdes_ai_core::ModelRequest>,
+    /// Name of the session the conversation is auto-saved to
+    current_session: Option<String>,
     /// Estimated tokens currently used in context
     context_tokens_used: usize,
     /// Current model's context window size
@@ -149,6 +160,10 @@ pub struct ChatApp {
     error_message: Option<String>,
     /// Compression notification (shown temporarily when context is compressed)
     pub compression_notification: Option<(std::time::Instant, String)>,
+    /// Retry/fallback notification (shown temporarily when a request is retried)
+    pub retry_notification: Option<(std::time::Instant, String)>,
+    /// Undo/revert notification (shown temporarily after restoring files)
+    pub undo_notification: Option<(std::time::Instant, String)>,
 
     /// Scroll handle for settings content
     settings_scroll_handle: ScrollHandle,
@@ -192,6 +207,11 @@ pub struct ChatApp {
     /// Pending attachments (images and files) waiting to be sent
     pending_attachments: Vec<PendingAttachment>,
 
+    /// Show the saved sessions picker
+    show_session_picker: bool,
+    /// Saved sessions listed in the picker (most recent first)
+    session_picker_entries: Vec<spot_core::session::SessionMeta>,
+
     /// MCP settings: selected agent for MCP attachment
     mcp_settings_selected_agent: String,
     /// MCP settings: show import JSON dialog
@@ -200,6 +220,8 @@ pub struct ChatApp {
     mcp_import_json: String,
     /// MCP settings: import error message
     mcp_import_error: Option<String>,
+    /// MCP settings: open add/edit remote server dialog
+    mcp_remote_dialog: Option<settings::McpRemoteDialog>,
 
     // ── Key Pool Dialog State ──────────────────────────────────────────────────
     /// Whether the key pool management dialog is open
@@ -251,6 +273,9 @@ impl ChatApp {
 
         // Run migrations to ensure schema is up to date
         db.migrate().expect("Failed to run database migrations");
+        if let Err(e) = spot_core::session::SessionManager::new(&db).import_legacy_sessions() {
+            tracing::warn!("Failed to import saved sessions: {}", e);
+        }
 
         // Load settings
         let settings = Settings::new(&db);
@@ -284,7 +309,7 @@ impl ChatApp {
         let tool_registry = Arc::new(SpotToolRegistry::new());
 
         // Initialize MCP manager
-        let mcp_manager = Arc::new(McpManager::new());
+        let mcp_manager = Arc::new(McpManager::new().with_bus(message_bus.sender()));
 
         // Create input state with auto-grow (1-3 lines, then scrollbar)
         let input_state = cx.new(|cx| {
@@ -314,6 +339,8 @@ impl ChatApp {
             show_reasoning,
             theme,
             is_generating: false,
+            cancel_handle: None,
+            checkpoints: CheckpointStore::new(),
             vdi_mode,
             needs_render: false,
             message_bus,
@@ -323,6 +350,7 @@ impl ChatApp {
             tool_registry,
             mcp_manager,
             message_history: Vec::new(),
+            current_session: None,
             context_tokens_used: 0,
             context_window_size: 0,
             throughput_samples: Vec::new(),
@@ -348,6 +376,8 @@ impl ChatApp {
             model_settings_save_success: None,
             error_message: None,
             compression_notification: None,
+            retry_notification: None,
+            undo_notification: None,
 
             settings_scroll_handle: ScrollHandle::new(),
             settings_scrollbar_drag: Rc::new(ScrollbarDragState::default()),
@@ -380,10 +410,14 @@ impl ChatApp {
             api_key_new_value: String::new(),
             pending_attachments: Vec::new(),
 
+            show_session_picker: false,
+            session_picker_entries: Vec::new(),
+
             mcp_settings_selected_agent,
             show_mcp_import_dialog: false,
             mcp_import_json: String::new(),
             mcp_import_error: None,
+            mcp_remote_dialog: None,
 
             // Key pool dialog state
             show_key_pool_dialog: false,
@@ -428,7 +462,8 @@ impl ChatApp {
         let request_tx = self.system_executions.request_tx.clone();
 
         // Create and set global tool context
-        let tool_ctx = ToolContext::new(store, request_tx);
+        let ignore_patterns = SpotConfig::load(&Settings::new(&self.db)).files.ignore;
+        let tool_ctx = ToolContext::new(store, request_tx).with_ignore_patterns(ignore_patterns);
         if let Err(_existing) = set_global_context(tool_ctx) {
             tracing::warn!("Tool context already initialized");
         }
@@ -601,7 +636,7 @@ impl ChatApp {
         }
     }
 
-    /// Start MCP servers
+    /// Start MCP servers, then supervise them
     fn start_mcp_servers(&self, cx: &mut Context<Self>) {
         let mcp = self.mcp_manager.clone();
         cx.spawn(
@@ -618,6 +653,7 @@ impl ChatApp {
                     let running = mcp.running_servers().await;
                     tracing::info!(servers = ?running, "MCP servers started successfully");
                 }
+                mcp.supervise(McpSupervisorConfig::default());
             },
         )
         .detach();
@@ -625,7 +661,7 @@ impl ChatApp {
 
     /// Import MCP servers from JSON (Claude Desktop format).
     pub(super) fn do_mcp_import(&mut self, cx: &mut Context<Self>) {
-        use spot_core::mcp::{McpConfig, McpServerEntry};
+        use spot_core::mcp::{McpConfig, McpServerEntry, McpTransportKind};
 
         let json_str = self.mcp_import_json.trim();
         if json_str.is_empty() {
@@ -664,7 +700,7 @@ impl ChatApp {
         };
 
         // Load existing config and merge
-        let mut config = McpConfig::load_or_default();
+        let mut config = McpConfig::load_for_edit();
         let mut imported_count = 0;
 
         for (name, server_value) in servers_obj {
@@ -673,9 +709,14 @@ impl ChatApp {
                 .get("command")
                 .and_then(|v| v.as_str())
                 .unwrap_or_default();
+            let url = server_value
+                .get("url")
+                .or_else(|| server_value.get("serverUrl"))
+                .and_then(|v| v.as_str())
+                .unwrap_or_default();
 
-            if command.is_empty() {
-                continue; // Skip entries without command
+            if command.is_empty() && url.is_empty() {
+                continue; // Skip entries without command or url
             }
 
             let args: Vec<String> = server_value
@@ -703,10 +744,33 @@ impl ChatApp {
                 .and_then(|v| v.as_str())
                 .map(|s| s.to_string());
 
-            let mut entry = McpServerEntry::new(command).with_args(args);
+            let mut entry = if url.is_empty() {
+                McpServerEntry::new(command).with_args(args)
+            } else {
+                McpServerEntry::remote(url)
+            };
             for (k, v) in env {
                 entry = entry.with_env(k, v);
             }
+            if let Some(cwd) = server_value.get("cwd").and_then(|v| v.as_str()) {
+                entry = entry.with_cwd(cwd);
+            }
+            if let Some(transport) = server_value
+                .get("type")
+                .and_then(|v| serde_json::from_value::<McpTransportKind>(v.clone()).ok())
+            {
+                entry = entry.with_transport(transport);
+            }
+            if let Some(headers) = server_value.get("headers").and_then(|v| v.as_object()) {
+                for (k, v) in headers {
+                    if let Some(v) = v.as_str() {
+                        entry = entry.with_header(k.clone(), v);
+                    }
+                }
+            }
+            if let Some(token) = server_value.get("bearer_token").and_then(|v| v.as_str()) {
+                entry = entry.with_bearer_token(token);
+            }
             if let Some(desc) = description {
                 entry = entry.with_description(desc);
             }
@@ -991,10 +1055,13 @@ impl Render for ChatApp {
             // Overlay dialogs
             .child(self.render_settings(cx))
             .child(self.render_approval_dialog(cx))
+            .child(self.render_file_approval_dialog(cx))
             .child(self.render_add_model_dialog(cx))
             .child(self.render_api_keys_dialog(cx))
             .child(self.render_key_pool_dialog(cx))
             .child(self.render_mcp_import_dialog(cx))
+            .child(self.render_mcp_remote_dialog(cx))
+            .child(self.render_session_picker(cx))
     }
 }
 
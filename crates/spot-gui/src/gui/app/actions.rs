@@ -22,6 +22,7 @@ impl ChatApp {
     ) {
         self.conversation.clear();
         self.message_history.clear();
+        self.current_session = None;
         self.context_tokens_used = 0;
         self.active_agent_stack.clear();
         self.active_section_ids.clear();
@@ -48,7 +49,9 @@ impl ChatApp {
         cx: &mut Context<Self>,
     ) {
         // Close dialogs in order of precedence (most recent first)
-        if self.show_add_model_dialog {
+        if self.show_session_picker {
+            self.close_session_picker();
+        } else if self.show_add_model_dialog {
             self.show_add_model_dialog = false;
             self.add_model_selected_provider = None;
             self.add_model_selected_model = None;
@@ -0,0 +1,70 @@
+//! File checkpoints for ChatApp
+//!
+//! - `undo_turn()` - `/undo [turn]`
+//! - `revert_last_edit()` - `/revert [path]`
+//! - `revert_edit()` - the Revert button under a tool call's diff
+//! - `append_turn_summary()` - list the files a turn touched
+
+use std::path::Path;
+use std::time::Instant;
+
+use spot_core::checkpoints::CheckpointKey;
+
+use super::ChatApp;
+
+impl ChatApp {
+    /// Undo the last turn's file changes, or turn `turn` and everything after it.
+    pub(super) fn undo_turn(&mut self, turn: Option<&str>) {
+        if self.is_generating {
+            self.error_message = Some("Wait for the agent to finish before undoing".to_string());
+            return;
+        }
+        let turn = match turn.map(str::parse::<usize>) {
+            None => None,
+            Some(Ok(turn)) => Some(turn),
+            Some(Err(_)) => {
+                self.error_message = Some("Usage: /undo [turn]".to_string());
+                return;
+            }
+        };
+        match self.checkpoints.undo(turn) {
+            Ok(report) => self.undo_notification = Some((Instant::now(), report.to_string())),
+            Err(e) => self.error_message = Some(e.to_string()),
+        }
+    }
+
+    /// Revert the most recent edit, optionally the most recent one to `path`.
+    pub(super) fn revert_last_edit(&mut self, path: Option<&str>) {
+        match self.checkpoints.last_edit(path.map(Path::new)) {
+            Some(key) => self.revert_edit(&key),
+            None => self.error_message = Some("No edit to revert".to_string()),
+        }
+    }
+
+    /// Revert the single edit recorded under `key`.
+    pub(super) fn revert_edit(&mut self, key: &CheckpointKey) {
+        if self.is_generating {
+            self.error_message = Some("Wait for the agent to finish before reverting".to_string());
+            return;
+        }
+        match self.checkpoints.revert(key) {
+            Ok(path) => {
+                self.undo_notification =
+                    Some((Instant::now(), format!("Reverted {}", path.display())));
+            }
+            Err(e) => self.error_message = Some(e.to_string()),
+        }
+    }
+
+    /// Append a summary of the files the current turn touched, if any.
+    pub(super) fn append_turn_summary(&mut self) {
+        let summary = self
+            .checkpoints
+            .current_turn()
+            .and_then(|turn| self.checkpoints.turn_summary(turn));
+        if let Some(summary) = summary {
+            self.conversation
+                .append_to_main_content(&format!("\n\n{}", summary));
+        }
+    }
+}
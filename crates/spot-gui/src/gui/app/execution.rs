@@ -2,7 +2,8 @@
 //!
 //! This module handles sending messages and executing agents:
 //! - `send_message()` - Prepare and send a user message
-//! - `execute_agent()` - Run an agent with the current context
+//! - `execute_agent()` - Run an agent with the current context, after
+//!   expanding MCP prompt commands and resource mentions
 
 use std::rc::Rc;
 use std::sync::Arc;
@@ -10,7 +11,8 @@ use std::sync::Arc;
 use gpui::{AsyncApp, Context, WeakEntity, Window};
 
 use serdes_ai_core::messages::ImageMediaType;
-use spot_core::agents::{AgentExecutor, AgentManager, ExecuteContext, RetryHandler};
+use spot_core::agents::{AgentExecutor, AgentManager, CancelHandle, ExecuteContext, RetryHandler};
+use spot_core::checkpoints::CheckpointStore;
 use spot_core::config::{PdfMode, Settings};
 use spot_core::db::Database;
 use spot_core::mcp::McpManager;
@@ -35,6 +37,14 @@ impl ChatApp {
             return;
         }
 
+        if text.starts_with('/') && self.handle_slash_command(&text, cx) {
+            self.input_state.update(cx, |state, cx| {
+                state.set_value("", window, cx);
+            });
+            cx.notify();
+            return;
+        }
+
         // Build the message including attachments
         let mut full_message = text.clone();
 
@@ -232,6 +242,41 @@ impl ChatApp {
         cx.notify();
     }
 
+    /// Handle a slash command. Returns `false` if the input isn't a known
+    /// command and should be sent as a regular message.
+    fn handle_slash_command(&mut self, text: &str, cx: &mut Context<Self>) -> bool {
+        let parts: Vec<&str> = text.split_whitespace().collect();
+        match parts[0] {
+            "/sessions" => self.open_session_picker(),
+            "/save" => match parts.get(1) {
+                Some(name) => self.save_session_as(name),
+                None => self.error_message = Some("Usage: /save <name>".to_string()),
+            },
+            "/load" => match parts.get(1) {
+                Some(name) => self.load_session(Some(*name)),
+                None => self.open_session_picker(),
+            },
+            "/undo" => self.undo_turn(parts.get(1).copied()),
+            "/revert" => self.revert_last_edit(parts.get(1).copied()),
+            "/prompts" | "/resources" => self.show_mcp_catalog(parts[0], cx),
+            _ => return false,
+        }
+        true
+    }
+
+    /// Cancel the running agent turn, if any.
+    ///
+    /// Approvals the turn is waiting on are refused. The executor finishes the
+    /// turn on its own; `is_generating` is cleared once it reports back.
+    pub(super) fn cancel_generation(&mut self, cx: &mut Context<Self>) {
+        if let Some(cancel) = self.cancel_handle.take() {
+            tracing::info!("Cancelling agent turn");
+            cancel.cancel();
+            self.system_executions.clear_pending_approvals();
+            cx.notify();
+        }
+    }
+
     /// Execute the agent with the given prompt and optional images
     pub(super) fn execute_agent(
         &mut self,
@@ -253,8 +298,14 @@ impl ChatApp {
             prompt: String,
             images: Vec<(Vec<u8>, ImageMediaType)>,
             history: Option<Vec<serdes_ai_core::ModelRequest>>,
+            cancel: CancelHandle,
+            checkpoints: CheckpointStore,
         }
 
+        let cancel = CancelHandle::new();
+        self.cancel_handle = Some(cancel.clone());
+        self.checkpoints.begin_turn();
+
         let data = ExecuteData {
             agent_name: self.current_agent.clone(),
             db: self.db.clone(),
@@ -271,6 +322,8 @@ impl ChatApp {
             } else {
                 Some(self.message_history.clone())
             },
+            cancel,
+            checkpoints: self.checkpoints.clone(),
         };
 
         // Log BEFORE the spawn to verify data is correct in struct
@@ -297,6 +350,8 @@ impl ChatApp {
                 prompt,
                 images,
                 history,
+                cancel,
+                checkpoints,
             } = data;
 
             // Log images inside async block to verify they survived the move
@@ -317,6 +372,21 @@ impl ChatApp {
                 return;
             };
 
+            // Run `/server:prompt` commands and attach `@server:resource` mentions
+            let prompt = match mcp_manager.expand_input(&prompt).await {
+                Ok(prompt) => prompt,
+                Err(e) => {
+                    this.update(cx, |app, cx| {
+                        app.is_generating = false;
+                        app.cancel_handle = None;
+                        app.error_message = Some(format!("MCP: {}", e));
+                        cx.notify();
+                    })
+                    .ok();
+                    return;
+                }
+            };
+
             // Create retry handler for automatic key rotation on 429s
             // Note: We create a separate Arc<Database> for the retry handler since GPUI uses Rc
             #[allow(clippy::arc_with_non_send_sync)]
@@ -329,7 +399,10 @@ impl ChatApp {
             };
 
             // Create executor with message bus and optional retry handler
-            let mut executor = AgentExecutor::new(&db, &model_registry).with_bus(message_bus_sender);
+            let mut executor = AgentExecutor::new(&db, &model_registry)
+                .with_bus(message_bus_sender)
+                .with_cancel(cancel)
+                .with_checkpoints(checkpoints);
             if let Some(handler) = retry_handler {
                 executor = executor.with_retry_handler(handler);
             }
@@ -380,6 +453,7 @@ impl ChatApp {
             this.update(cx, |app, cx| {
                 tracing::info!("Inside this.update() callback");
                 app.is_generating = false;
+                app.cancel_handle = None;
                 match result {
                     Ok(exec_result) => {
                         tracing::info!(
@@ -388,8 +462,13 @@ impl ChatApp {
                         );
                         if !exec_result.messages.is_empty() {
                             app.message_history = exec_result.messages;
+                            app.autosave_session();
                         }
                     }
+                    Err(e) if e.is_cancelled() => {
+                        app.conversation.append_to_current("\n\n⏹ Cancelled");
+                        app.conversation.finish_current_message();
+                    }
                     Err(e) => {
                         app.error_message = Some(e.to_string());
                         app.conversation
@@ -0,0 +1,339 @@
+//! File change approval for ChatApp
+//!
+//! `edit_file` and `delete_file` ask for approval through the same
+//! `ToolContext` channel as shell commands. Changes the permission policy
+//! allows go ahead, denied ones fail, and the rest are queued for a review
+//! dialog showing the diff, where the proposed content can also be edited:
+//! - `handle_file_change_request()` - Apply the policy or queue for review
+//! - `accept_file_change()` / `reject_file_change()` - Answer the shown change
+//! - `edit_file_change()` - Open the proposed content in an editor
+
+use gpui::{div, prelude::*, px, rgb, rgba, Context, MouseButton, SharedString, Styled, Window};
+use gpui_component::input::{Input, InputState};
+use tracing::info;
+
+use super::ChatApp;
+use spot_core::config::CommandAction;
+use spot_core::terminal::{FileOperation, ProposedFileChange, SystemExecResponse};
+use spot_core::tools::CommandPolicy;
+
+/// File change awaiting user approval
+#[derive(Debug)]
+pub struct PendingFileChange {
+    pub request_id: u64,
+    pub change: ProposedFileChange,
+}
+
+impl ChatApp {
+    /// Handle a file-change approval request from a tool
+    pub(super) fn handle_file_change_request(
+        &mut self,
+        request_id: u64,
+        change: ProposedFileChange,
+        cx: &mut Context<Self>,
+    ) {
+        let decision =
+            CommandPolicy::load(&self.db).evaluate_file_change(&change.path, change.operation());
+        info!(
+            request_id = request_id,
+            path = %change.path,
+            action = ?decision.action,
+            reason = %decision.reason,
+            "Evaluated file change against policy"
+        );
+
+        let store = &self.system_executions.store;
+        match decision.action {
+            CommandAction::Allow => {
+                store.respond(
+                    request_id,
+                    SystemExecResponse::FileChangeApproved { edited: None },
+                );
+            }
+            CommandAction::Deny => {
+                store.respond(
+                    request_id,
+                    SystemExecResponse::Error {
+                        message: format!("File change denied by policy: {}", decision.reason),
+                    },
+                );
+            }
+            CommandAction::Ask => {
+                self.system_executions
+                    .pending_file_changes
+                    .push_back(PendingFileChange { request_id, change });
+                cx.notify();
+            }
+        }
+    }
+
+    /// Accept the shown file change, with the editor's content if it is open
+    pub fn accept_file_change(&mut self, cx: &mut Context<Self>) {
+        let editor = self.system_executions.file_change_editor.take();
+        let Some(pending) = self.system_executions.pending_file_changes.pop_front() else {
+            return;
+        };
+
+        let edited = editor
+            .map(|editor| editor.read(cx).value().to_string())
+            .filter(|edited| pending.change.content.as_deref() != Some(edited.as_str()));
+        info!(
+            request_id = pending.request_id,
+            edited = edited.is_some(),
+            "User accepted file change"
+        );
+        self.system_executions.store.respond(
+            pending.request_id,
+            SystemExecResponse::FileChangeApproved { edited },
+        );
+        cx.notify();
+    }
+
+    /// Reject the shown file change
+    pub fn reject_file_change(&mut self, cx: &mut Context<Self>) {
+        self.system_executions.file_change_editor = None;
+        if let Some(pending) = self.system_executions.pending_file_changes.pop_front() {
+            info!(request_id = pending.request_id, "User rejected file change");
+            self.system_executions.store.respond(
+                pending.request_id,
+                SystemExecResponse::Error {
+                    message: "File change rejected by user".to_string(),
+                },
+            );
+            cx.notify();
+        }
+    }
+
+    /// Open the shown change's proposed content for editing (writes only)
+    pub fn edit_file_change(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(content) = self
+            .system_executions
+            .pending_file_changes
+            .front()
+            .and_then(|pending| pending.change.content.clone())
+        else {
+            return;
+        };
+
+        let editor = cx.new(|cx| InputState::new(window, cx).auto_grow(12, 24));
+        editor.update(cx, |state, cx| {
+            state.set_value(content, window, cx);
+        });
+        self.system_executions.file_change_editor = Some(editor);
+        cx.notify();
+    }
+
+    /// Render the file change review dialog
+    pub(crate) fn render_file_approval_dialog(&self, cx: &Context<Self>) -> impl IntoElement {
+        let theme = self.theme.clone();
+        let pending = self.system_executions.pending_file_changes.front();
+
+        div().when_some(pending, |d, pending| {
+            let change = &pending.change;
+            let operation = change.operation();
+            let editor = self.system_executions.file_change_editor.clone();
+            let is_editing = editor.is_some();
+            let queued = self.system_executions.pending_file_changes.len() - 1;
+
+            let title = match operation {
+                FileOperation::Write => "Write File?",
+                FileOperation::Delete => "Delete File?",
+            };
+            let subtitle = if queued > 0 {
+                format!("{} (+{} queued)", change.path, queued)
+            } else {
+                change.path.clone()
+            };
+
+            // Diff view, or the editor while editing
+            let body = match editor {
+                Some(editor) => div()
+                    .w_full()
+                    .font_family("monospace")
+                    .text_size(px(12.))
+                    .child(Input::new(&editor))
+                    .into_any_element(),
+                None => div()
+                    .id("file-approval-diff")
+                    .w_full()
+                    .max_h(px(400.))
+                    .overflow_y_scroll()
+                    .p(px(12.))
+                    .rounded(px(6.))
+                    .bg(theme.background)
+                    .border_1()
+                    .border_color(theme.border)
+                    .font_family("monospace")
+                    .text_size(px(12.))
+                    .children(change.diff.lines().map(|line| {
+                        let color = if line.starts_with("+++") || line.starts_with("---") {
+                            theme.text_muted
+                        } else if line.starts_with('+') {
+                            theme.success
+                        } else if line.starts_with('-') {
+                            theme.error
+                        } else if line.starts_with("@@") {
+                            theme.accent
+                        } else {
+                            theme.text
+                        };
+                        div()
+                            .text_color(color)
+                            .whitespace_nowrap()
+                            .child(SharedString::from(line.to_string()))
+                    }))
+                    .into_any_element(),
+            };
+
+            d.absolute()
+                .inset_0()
+                .flex()
+                .items_center()
+                .justify_center()
+                .bg(rgba(0x00000088))
+                .occlude()
+                .child(
+                    div()
+                        .w(px(720.))
+                        .max_h(px(640.))
+                        .bg(theme.panel_background)
+                        .border_1()
+                        .border_color(theme.border)
+                        .rounded(px(12.))
+                        .shadow_lg()
+                        .flex()
+                        .flex_col()
+                        .overflow_hidden()
+                        // Header
+                        .child(
+                            div()
+                                .px(px(20.))
+                                .py(px(16.))
+                                .border_b_1()
+                                .border_color(theme.border)
+                                .flex()
+                                .flex_col()
+                                .child(
+                                    div()
+                                        .text_size(px(16.))
+                                        .font_weight(gpui::FontWeight::SEMIBOLD)
+                                        .text_color(theme.text)
+                                        .child(title),
+                                )
+                                .child(
+                                    div()
+                                        .text_size(px(12.))
+                                        .font_family("monospace")
+                                        .text_color(theme.warning)
+                                        .child(subtitle),
+                                ),
+                        )
+                        // Content
+                        .child(div().px(px(20.)).py(px(16.)).child(body))
+                        // Action buttons
+                        .child(
+                            div()
+                                .px(px(20.))
+                                .py(px(16.))
+                                .border_t_1()
+                                .border_color(theme.border)
+                                .flex()
+                                .items_center()
+                                .justify_end()
+                                .gap(px(10.))
+                                // Reject button
+                                .child(
+                                    div()
+                                        .id("reject-file-btn")
+                                        .px(px(16.))
+                                        .py(px(8.))
+                                        .rounded(px(6.))
+                                        .bg(rgba(0xf14c4c33))
+                                        .border_1()
+                                        .border_color(theme.error)
+                                        .text_color(theme.error)
+                                        .text_size(px(13.))
+                                        .font_weight(gpui::FontWeight::MEDIUM)
+                                        .cursor_pointer()
+                                        .hover(|s| s.bg(rgba(0xf14c4c55)))
+                                        .on_mouse_down(MouseButton::Left, |_, _, cx| {
+                                            cx.stop_propagation();
+                                        })
+                                        .on_mouse_up(
+                                            MouseButton::Left,
+                                            cx.listener(|this, _, _, cx| {
+                                                this.reject_file_change(cx);
+                                            }),
+                                        )
+                                        .child("✗ Reject"),
+                                )
+                                // Edit button (writes only) / back to the diff
+                                .when(operation == FileOperation::Write, |d| {
+                                    d.child(
+                                        div()
+                                            .id("edit-file-btn")
+                                            .px(px(16.))
+                                            .py(px(8.))
+                                            .rounded(px(6.))
+                                            .border_1()
+                                            .border_color(theme.accent)
+                                            .text_color(theme.accent)
+                                            .text_size(px(13.))
+                                            .font_weight(gpui::FontWeight::MEDIUM)
+                                            .cursor_pointer()
+                                            .hover(|s| s.opacity(0.8))
+                                            .on_mouse_down(MouseButton::Left, |_, _, cx| {
+                                                cx.stop_propagation();
+                                            })
+                                            .on_mouse_up(
+                                                MouseButton::Left,
+                                                cx.listener(move |this, _, window, cx| {
+                                                    if is_editing {
+                                                        this.system_executions.file_change_editor =
+                                                            None;
+                                                        cx.notify();
+                                                    } else {
+                                                        this.edit_file_change(window, cx);
+                                                    }
+                                                }),
+                                            )
+                                            .child(if is_editing {
+                                                "↩ Show Diff"
+                                            } else {
+                                                "✎ Edit"
+                                            }),
+                                    )
+                                })
+                                // Accept button
+                                .child(
+                                    div()
+                                        .id("accept-file-btn")
+                                        .px(px(16.))
+                                        .py(px(8.))
+                                        .rounded(px(6.))
+                                        .bg(theme.success)
+                                        .text_color(rgb(0xffffff))
+                                        .text_size(px(13.))
+                                        .font_weight(gpui::FontWeight::MEDIUM)
+                                        .cursor_pointer()
+                                        .hover(|s| s.opacity(0.9))
+                                        .on_mouse_down(MouseButton::Left, |_, _, cx| {
+                                            cx.stop_propagation();
+                                        })
+                                        .on_mouse_up(
+                                            MouseButton::Left,
+                                            cx.listener(|this, _, _, cx| {
+                                                this.accept_file_change(cx);
+                                            }),
+                                        )
+                                        .child(if is_editing {
+                                            "✓ Accept Edited"
+                                        } else {
+                                            "✓ Accept"
+                                        }),
+                                ),
+                        ),
+                )
+        })
+    }
+}
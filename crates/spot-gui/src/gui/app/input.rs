@@ -53,7 +53,7 @@ impl ChatApp {
                             .py(px(10.))
                             .rounded(px(8.))
                             .bg(if is_generating {
-                                theme.text_muted
+                                theme.error
                             } else {
                                 theme.accent
                             })
@@ -63,17 +63,19 @@ impl ChatApp {
                             .on_mouse_up(
                                 MouseButton::Left,
                                 cx.listener(|this, _, window, cx| {
-                                    if !this.is_generating {
+                                    if this.is_generating {
+                                        this.cancel_generation(cx);
+                                    } else {
                                         this.send_message(window, cx);
                                     }
                                 }),
                             )
                             .child(if is_generating {
-                                current_spinner_frame()
+                                format!("{} Stop ⏹", current_spinner_frame())
                             } else if attachment_count > 0 {
-                                "Send 📎→"
+                                "Send 📎→".to_string()
                             } else {
-                                "Send →"
+                                "Send →".to_string()
                             }),
                     ),
             )
@@ -0,0 +1,57 @@
+//! `/prompts` and `/resources`: list what running MCP servers offer.
+
+use gpui::{AsyncApp, Context, WeakEntity};
+
+use spot_core::mcp::{prompt_catalog, resource_catalog};
+
+use super::ChatApp;
+
+impl ChatApp {
+    /// Show the MCP prompts (`/prompts`) or resources (`/resources`) that
+    /// can be used from the input.
+    pub(super) fn show_mcp_catalog(&mut self, command: &str, cx: &mut Context<Self>) {
+        let mcp_manager = self.mcp_manager.clone();
+        let prompts = command == "/prompts";
+
+        cx.spawn(async move |this: WeakEntity<ChatApp>, cx: &mut AsyncApp| {
+            let (entries, hint, kind) = if prompts {
+                let prompts = mcp_manager.list_all_prompts().await;
+                (
+                    prompt_catalog(&prompts),
+                    "Run a prompt by sending its command:",
+                    "prompts",
+                )
+            } else {
+                let resources = mcp_manager.list_all_resources().await;
+                (
+                    resource_catalog(&resources),
+                    "Mention a resource in a message to attach it:",
+                    "resources",
+                )
+            };
+
+            let text = if entries.is_empty() {
+                format!("No MCP {} available.", kind)
+            } else {
+                let lines: Vec<String> = entries
+                    .into_iter()
+                    .map(|(usage, description)| match description {
+                        Some(description) => format!("- `{}` — {}", usage, description),
+                        None => format!("- `{}`", usage),
+                    })
+                    .collect();
+                format!("{}\n\n{}", hint, lines.join("\n"))
+            };
+
+            this.update(cx, |app, cx| {
+                app.conversation.start_assistant_message();
+                app.conversation.append_to_current(&text);
+                app.conversation.finish_current_message();
+                app.sync_messages_list_state();
+                cx.notify();
+            })
+            .ok();
+        })
+        .detach();
+    }
+}
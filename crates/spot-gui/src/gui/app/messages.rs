@@ -9,6 +9,10 @@ use crate::gui::components::{collapsible_display, list_scrollbar, CollapsiblePro
 use crate::gui::state::{
     AgentContentItem, MessageRole, MessageSection, ThinkingSection, ToolCallSection,
 };
+use spot_core::messaging::{DiffLineType, DiffMessage};
+
+/// Maximum diff lines shown under a tool call.
+const MAX_DIFF_LINES: usize = 40;
 
 impl ChatApp {
     fn markdown_text_view(
@@ -256,7 +260,7 @@ impl ChatApp {
             }
             MessageSection::ToolCall(tool_section) => {
                 // Tool call sections render as styled inline elements
-                self.render_tool_call_section(tool_section, msg_id, theme)
+                self.render_tool_call_section(tool_section, msg_id, theme, view)
             }
         }
     }
@@ -343,6 +347,7 @@ impl ChatApp {
         tool_section: &ToolCallSection,
         msg_id: &str,
         theme: &crate::gui::theme::Theme,
+        view: &Entity<ChatApp>,
     ) -> AnyElement {
         let element_id = SharedString::from(format!("tool-{}-{}", msg_id, tool_section.id));
 
@@ -355,7 +360,7 @@ impl ChatApp {
             Some(("✗", theme.error))
         };
 
-        div()
+        let row = div()
             .id(element_id)
             .flex()
             .items_center()
@@ -383,8 +388,17 @@ impl ChatApp {
             // Status indicator at end
             .when_some(status, |el, (icon, color)| {
                 el.child(div().text_color(color).child(icon))
-            })
-            .into_any_element()
+            });
+
+        match &tool_section.diff {
+            Some(diff) => div()
+                .flex()
+                .flex_col()
+                .child(row)
+                .child(render_diff(diff, theme, view))
+                .into_any_element(),
+            None => row.into_any_element(),
+        }
     }
 
     /// Render a nested agent section as a collapsible container with click handler.
@@ -440,6 +454,7 @@ impl ChatApp {
                         info,
                         is_running,
                         succeeded,
+                        diff,
                     } => {
                         // Render tool call with same styling as top-level tool calls
                         let element_id = SharedString::from(format!("nested-tool-{}", id));
@@ -451,7 +466,7 @@ impl ChatApp {
                             Some(("✗", theme.error))
                         };
 
-                        div()
+                        let row = div()
                             .id(element_id)
                             .flex()
                             .items_center()
@@ -475,8 +490,17 @@ impl ChatApp {
                             // Status indicator at end
                             .when_some(status, |el, (icon, color)| {
                                 el.child(div().text_color(color).child(icon))
-                            })
-                            .into_any_element()
+                            });
+
+                        match diff {
+                            Some(diff) => div()
+                                .flex()
+                                .flex_col()
+                                .child(row)
+                                .child(render_diff(diff, theme, view))
+                                .into_any_element(),
+                            None => row.into_any_element(),
+                        }
                     }
                     AgentContentItem::Thinking {
                         id,
@@ -560,3 +584,63 @@ impl ChatApp {
             .into_any_element()
     }
 }
+
+/// Render a file diff as colored monospace lines below a tool call, with a
+/// Revert button when the change was checkpointed.
+fn render_diff(
+    diff: &DiffMessage,
+    theme: &crate::gui::theme::Theme,
+    view: &Entity<ChatApp>,
+) -> AnyElement {
+    let hidden = diff.lines.len().saturating_sub(MAX_DIFF_LINES);
+    let revert = diff.checkpoint.clone().map(|key| {
+        let view = view.clone();
+        div()
+            .mt(px(2.))
+            .text_color(theme.accent)
+            .cursor_pointer()
+            .on_mouse_down(MouseButton::Left, move |_event, _window, cx| {
+                // Don't let the click toggle an enclosing agent section
+                cx.stop_propagation();
+                view.update(cx, |app, cx: &mut Context<ChatApp>| {
+                    app.revert_edit(&key);
+                    cx.notify();
+                });
+            })
+            .child("↩ Revert")
+    });
+
+    div()
+        .flex()
+        .flex_col()
+        .ml(px(14.))
+        .mb(px(4.))
+        .px(px(8.))
+        .py(px(4.))
+        .rounded(px(4.))
+        .bg(theme.tool_card)
+        .font_family("monospace")
+        .text_size(px(11.5))
+        .overflow_x_hidden()
+        .children(diff.lines.iter().take(MAX_DIFF_LINES).map(|line| {
+            let color = match line.line_type {
+                DiffLineType::Added => theme.success,
+                DiffLineType::Removed => theme.error,
+                DiffLineType::Header => theme.accent,
+                DiffLineType::Context => theme.text_muted,
+            };
+            div()
+                .text_color(color)
+                .whitespace_nowrap()
+                .child(line.content.clone())
+        }))
+        .when(hidden > 0, |el| {
+            el.child(
+                div()
+                    .text_color(theme.text_muted)
+                    .child(format!("… {} more lines", hidden)),
+            )
+        })
+        .children(revert)
+        .into_any_element()
+}
@@ -0,0 +1,347 @@
+//! Session persistence for ChatApp
+//!
+//! This module handles saving and restoring conversations:
+//! - `autosave_session()` - Save the conversation after each completed turn
+//! - `save_session_as()` / `load_session()` - `/save` and `/load` commands
+//! - `render_session_picker()` - Modal listing saved sessions
+
+use gpui::{div, prelude::*, px, rgb, rgba, Context, MouseButton, SharedString, Styled};
+
+use spot_core::config::Settings;
+use spot_core::session::{
+    format_relative_time, transcript, SessionData, SessionManager, TranscriptEntry,
+};
+
+use super::ChatApp;
+
+/// Prefix for auto-generated session names.
+const AUTO_SESSION_PREFIX: &str = "chat";
+
+impl ChatApp {
+    /// Model the current agent runs with (pinned or default).
+    fn session_model(&self) -> String {
+        Settings::new(&self.db)
+            .get_agent_pinned_model(&self.current_agent)
+            .unwrap_or_else(|| self.current_model.clone())
+    }
+
+    /// Save the conversation to the active session after a completed turn.
+    ///
+    /// The first turn of a new conversation starts an auto-named session.
+    pub(super) fn autosave_session(&mut self) {
+        if self.message_history.is_empty() {
+            return;
+        }
+
+        let manager = SessionManager::new(&self.db);
+        let name = self
+            .current_session
+            .get_or_insert_with(|| manager.generate_name(AUTO_SESSION_PREFIX))
+            .clone();
+        let model = self.session_model();
+
+        if let Err(e) = manager.save(&name, &self.message_history, &self.current_agent, &model) {
+            tracing::warn!("Failed to auto-save session '{}': {}", name, e);
+        }
+    }
+
+    /// Save the conversation under a new name (`/save <name>`).
+    ///
+    /// Later turns are auto-saved under this name.
+    pub(super) fn save_session_as(&mut self, name: &str) {
+        if self.message_history.is_empty() {
+            self.error_message = Some("Nothing to save yet".to_string());
+            return;
+        }
+
+        let model = self.session_model();
+        match SessionManager::new(&self.db).save(
+            name,
+            &self.message_history,
+            &self.current_agent,
+            &model,
+        ) {
+            Ok(meta) => {
+                self.current_session = Some(meta.name);
+                self.error_message = None;
+            }
+            Err(e) => {
+                self.error_message = Some(format!("Failed to save session: {}", e));
+            }
+        }
+    }
+
+    /// Load a saved session, or the most recent one when `name` is `None`.
+    pub fn load_session(&mut self, name: Option<&str>) {
+        if self.is_generating {
+            self.error_message = Some("Cannot load a session while generating".to_string());
+            return;
+        }
+
+        let manager = SessionManager::new(&self.db);
+        let result = match name {
+            Some(name) => manager.load(name),
+            None => manager.load_latest(),
+        };
+
+        match result {
+            Ok(session) => self.restore_session(session),
+            Err(e) => {
+                self.error_message = Some(format!("Failed to load session: {}", e));
+            }
+        }
+    }
+
+    /// Replace the current conversation with a loaded session.
+    fn restore_session(&mut self, session: SessionData) {
+        let SessionData { meta, messages } = session;
+
+        self.conversation.clear();
+        self.active_agent_stack.clear();
+        self.active_section_ids.clear();
+
+        for entry in transcript(&messages) {
+            match entry {
+                TranscriptEntry::User(text) => self.conversation.add_user_message(&text),
+                TranscriptEntry::Assistant(text) => {
+                    self.conversation.start_assistant_message();
+                    self.conversation.append_to_current(&text);
+                    self.conversation.finish_current_message();
+                }
+            }
+        }
+
+        // Restore the agent and model if they still exist. The model is only
+        // applied for this run; the saved default model is left untouched.
+        if self.agents.get(&meta.agent).is_some() {
+            self.current_agent = meta.agent.clone();
+            let _ = self.agents.switch(&meta.agent);
+        }
+        if let Some(model) = self.model_registry.get(&meta.model) {
+            self.current_model = meta.model.clone();
+            self.context_window_size = model.context_length;
+        }
+        self.context_tokens_used = meta.token_estimate;
+
+        self.message_history = messages;
+        self.current_session = Some(meta.name);
+        self.error_message = None;
+        self.user_scrolled_away = false;
+        self.sync_messages_list_state();
+    }
+
+    /// Open the session picker with the saved session list
+    pub(super) fn open_session_picker(&mut self) {
+        match SessionManager::new(&self.db).list() {
+            Ok(sessions) => {
+                self.session_picker_entries = sessions;
+                self.show_session_picker = true;
+                self.show_agent_dropdown = false;
+                self.show_model_dropdown = false;
+                self.show_settings = false;
+            }
+            Err(e) => {
+                self.error_message = Some(format!("Failed to list sessions: {}", e));
+            }
+        }
+    }
+
+    /// Close the session picker
+    pub(super) fn close_session_picker(&mut self) {
+        self.show_session_picker = false;
+        self.session_picker_entries.clear();
+    }
+
+    /// Delete a saved session and refresh the picker
+    fn delete_session(&mut self, name: &str) {
+        if let Err(e) = SessionManager::new(&self.db).delete(name) {
+            self.error_message = Some(format!("Failed to delete session: {}", e));
+            return;
+        }
+        if self.current_session.as_deref() == Some(name) {
+            self.current_session = None;
+        }
+        self.session_picker_entries.retain(|meta| meta.name != name);
+    }
+
+    /// Renders the session picker overlay.
+    pub(super) fn render_session_picker(&self, cx: &Context<Self>) -> impl IntoElement {
+        let theme = self.theme.clone();
+        let show = self.show_session_picker;
+
+        div().when(show, |d| {
+            d.child(
+                div()
+                    .id("session-picker-backdrop")
+                    .absolute()
+                    .top_0()
+                    .left_0()
+                    .size_full()
+                    .bg(rgba(0x000000aa))
+                    .on_mouse_down(
+                        MouseButton::Left,
+                        cx.listener(|this, _, _, cx| {
+                            this.close_session_picker();
+                            cx.notify();
+                        }),
+                    )
+                    .flex()
+                    .items_center()
+                    .justify_center()
+                    .child(
+                        div()
+                            .w(px(520.))
+                            .max_h(px(520.))
+                            .on_mouse_down(MouseButton::Left, |_, _, cx| {
+                                cx.stop_propagation();
+                            })
+                            .bg(theme.panel_background)
+                            .border_1()
+                            .border_color(theme.border)
+                            .rounded(px(12.))
+                            .flex()
+                            .flex_col()
+                            .overflow_hidden()
+                            // Header
+                            .child(
+                                div()
+                                    .flex()
+                                    .items_center()
+                                    .justify_between()
+                                    .px(px(20.))
+                                    .py(px(14.))
+                                    .border_b_1()
+                                    .border_color(theme.border)
+                                    .child(
+                                        div()
+                                            .text_size(px(15.))
+                                            .font_weight(gpui::FontWeight::SEMIBOLD)
+                                            .text_color(theme.text)
+                                            .child("🗂 Sessions"),
+                                    )
+                                    .child(
+                                        div()
+                                            .id("close-session-picker")
+                                            .px(px(8.))
+                                            .py(px(4.))
+                                            .rounded(px(6.))
+                                            .cursor_pointer()
+                                            .hover(|s| s.bg(theme.tool_card))
+                                            .text_color(theme.text_muted)
+                                            .on_mouse_up(
+                                                MouseButton::Left,
+                                                cx.listener(|this, _, _, cx| {
+                                                    this.close_session_picker();
+                                                    cx.notify();
+                                                }),
+                                            )
+                                            .child("✕"),
+                                    ),
+                            )
+                            // Session list
+                            .child(
+                                div()
+                                    .id("session-picker-content")
+                                    .flex_1()
+                                    .overflow_y_scroll()
+                                    .scrollbar_width(px(8.))
+                                    .p(px(12.))
+                                    .flex()
+                                    .flex_col()
+                                    .gap(px(6.))
+                                    .when(self.session_picker_entries.is_empty(), |d| {
+                                        d.child(
+                                            div()
+                                                .text_size(px(12.))
+                                                .text_color(theme.text_muted)
+                                                .p(px(8.))
+                                                .child("No saved sessions yet."),
+                                        )
+                                    })
+                                    .children(self.session_picker_entries.iter().map(|meta| {
+                                        let name_for_load = meta.name.clone();
+                                        let name_for_delete = meta.name.clone();
+                                        let is_current = self.current_session.as_deref()
+                                            == Some(meta.name.as_str());
+                                        let details = format!(
+                                            "{} · {} · {} messages · {}",
+                                            meta.agent,
+                                            meta.model,
+                                            meta.message_count,
+                                            format_relative_time(meta.updated_at)
+                                        );
+
+                                        div()
+                                            .id(SharedString::from(format!(
+                                                "session-{}",
+                                                meta.name
+                                            )))
+                                            .flex()
+                                            .items_center()
+                                            .justify_between()
+                                            .px(px(12.))
+                                            .py(px(8.))
+                                            .rounded(px(6.))
+                                            .bg(theme.tool_card)
+                                            .when(is_current, |d| {
+                                                d.border_1().border_color(theme.accent)
+                                            })
+                                            .cursor_pointer()
+                                            .hover(|s| s.opacity(0.85))
+                                            .on_mouse_up(
+                                                MouseButton::Left,
+                                                cx.listener(move |this, _, _, cx| {
+                                                    this.close_session_picker();
+                                                    this.load_session(Some(name_for_load.as_str()));
+                                                    cx.notify();
+                                                }),
+                                            )
+                                            .child(
+                                                div()
+                                                    .flex()
+                                                    .flex_col()
+                                                    .gap(px(2.))
+                                                    .min_w(px(0.))
+                                                    .child(
+                                                        div()
+                                                            .text_size(px(13.))
+                                                            .text_color(theme.text)
+                                                            .child(meta.name.clone()),
+                                                    )
+                                                    .child(
+                                                        div()
+                                                            .text_size(px(11.))
+                                                            .text_color(theme.text_muted)
+                                                            .child(details),
+                                                    ),
+                                            )
+                                            .child(
+                                                div()
+                                                    .id(SharedString::from(format!(
+                                                        "delete-session-{}",
+                                                        meta.name
+                                                    )))
+                                                    .px(px(8.))
+                                                    .py(px(4.))
+                                                    .rounded(px(4.))
+                                                    .text_size(px(12.))
+                                                    .text_color(rgb(0xff6b6b))
+                                                    .cursor_pointer()
+                                                    .hover(|s| s.bg(theme.background))
+                                                    .on_mouse_up(
+                                                        MouseButton::Left,
+                                                        cx.listener(move |this, _, _, cx| {
+                                                            cx.stop_propagation();
+                                                            this.delete_session(&name_for_delete);
+                                                            cx.notify();
+                                                        }),
+                                                    )
+                                                    .child("Delete"),
+                                            )
+                                    })),
+                            ),
+                    ),
+            )
+        })
+    }
+}
@@ -242,8 +242,8 @@ fn render_mcp_checkboxes(
                 .flex()
                 .flex_col()
                 .gap(px(4.))
-                .children(servers.iter().filter(|(_, enabled, _, _)| *enabled).map(
-                    |(name, _, _, _)| {
+                .children(servers.iter().filter(|(_, enabled, _, _, _)| *enabled).map(
+                    |(name, _, _, _, _)| {
                         render_mcp_checkbox(&theme, cx, name, &selected_agent_owned, agent_mcps)
                     },
                 )),
@@ -1,13 +1,15 @@
 //! Import section component for MCP servers settings.
 //!
-//! Renders the top "Import from JSON" button and description.
+//! Renders the top "Import from JSON" and "Add Remote Server" buttons and
+//! description.
 
 use gpui::{div, prelude::*, px, rgb, Context, MouseButton, Styled};
 
 use crate::gui::app::ChatApp;
 use crate::gui::theme::Theme;
 
-/// Renders the import section with the "Import from JSON" button.
+/// Renders the import section with the "Import from JSON" and "Add Remote
+/// Server" buttons.
 pub fn render_import_section(theme: &Theme, cx: &Context<ChatApp>) -> impl IntoElement {
     div()
         .flex()
@@ -40,10 +42,30 @@ pub fn render_import_section(theme: &Theme, cx: &Context<ChatApp>) -> impl IntoE
                 )
                 .child("📋 Import from JSON"),
         )
+        .child(
+            div()
+                .id("add-mcp-remote")
+                .px(px(16.))
+                .py(px(10.))
+                .rounded(px(8.))
+                .bg(theme.tool_card)
+                .text_color(theme.text)
+                .text_size(px(13.))
+                .font_weight(gpui::FontWeight::MEDIUM)
+                .cursor_pointer()
+                .hover(|s| s.opacity(0.9))
+                .on_mouse_up(
+                    MouseButton::Left,
+                    cx.listener(|this, _, window, cx| {
+                        this.open_mcp_remote_dialog(None, window, cx);
+                    }),
+                )
+                .child("🌐 Add Remote Server"),
+        )
         .child(
             div()
                 .text_size(px(12.))
                 .text_color(theme.text_muted)
-                .child("Paste Claude Desktop / standard MCP config format"),
+                .child("Paste Claude Desktop / standard MCP config format, or connect over HTTP"),
         )
 }
@@ -7,12 +7,16 @@
 //! - `server_list`: Left panel showing MCP servers
 //! - `agent_assignments`: Right panel for agent-to-MCP attachments
 //! - `import_dialog`: Modal dialog for JSON import
+//! - `remote_dialog`: Modal form for adding/editing remote servers
 
 mod agent_assignments;
 mod import_dialog;
 mod import_section;
+mod remote_dialog;
 mod server_list;
 
+pub(crate) use remote_dialog::McpRemoteDialog;
+
 use gpui::{div, prelude::*, px, Context, Styled};
 
 use crate::gui::app::ChatApp;
@@ -22,8 +26,9 @@ impl ChatApp {
     /// Renders the MCP servers settings tab.
     ///
     /// This tab provides:
-    /// - Import button to add servers from JSON
-    /// - Left panel: List of defined MCP servers with controls
+    /// - Import button to add servers from JSON, and a button to add a
+    ///   remote server
+    /// - Left panel: List of defined MCP servers with their state and controls
     /// - Right panel: Agent-to-MCP assignment interface
     pub(crate) fn render_settings_mcp_servers(&self, cx: &Context<Self>) -> impl IntoElement {
         let theme = self.theme.clone();
@@ -32,6 +37,7 @@ impl ChatApp {
 
         // Load server data
         let servers = server_list::load_servers();
+        let statuses = self.mcp_manager.server_statuses();
 
         // Load agent MCP attachments
         let settings = Settings::new(&self.db);
@@ -40,7 +46,7 @@ impl ChatApp {
 
         // Build the UI
         let import_section = import_section::render_import_section(&theme, cx);
-        let left_panel = server_list::render_server_list(&theme, cx, &servers);
+        let left_panel = server_list::render_server_list(&theme, cx, &servers, &statuses);
         let right_panel = agent_assignments::render_agent_assignments(
             &theme,
             cx,
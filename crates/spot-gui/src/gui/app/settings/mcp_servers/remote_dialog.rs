@@ -0,0 +1,310 @@
+//! Remote MCP server dialog component.
+//!
+//! Renders the modal form for adding or editing a server reached over
+//! streamable HTTP or SSE.
+
+use gpui::{div, prelude::*, px, rgb, rgba, Context, Entity, MouseButton, SharedString, Styled};
+use gpui_component::input::{Input, InputState};
+
+use crate::gui::app::ChatApp;
+use crate::gui::theme::Theme;
+use spot_core::mcp::{McpConfig, McpTransportKind, RemoteServerForm};
+
+/// State of the open remote server dialog.
+pub(crate) struct McpRemoteDialog {
+    /// Name of the server being edited, or `None` when adding
+    editing: Option<String>,
+    transport: McpTransportKind,
+    name_input: Entity<InputState>,
+    url_input: Entity<InputState>,
+    token_input: Entity<InputState>,
+    headers_input: Entity<InputState>,
+    error: Option<String>,
+}
+
+impl ChatApp {
+    /// Open the remote server dialog, prefilled from `editing` if given.
+    pub(crate) fn open_mcp_remote_dialog(
+        &mut self,
+        editing: Option<String>,
+        window: &mut gpui::Window,
+        cx: &mut Context<Self>,
+    ) {
+        let form = editing
+            .as_deref()
+            .and_then(|name| {
+                McpConfig::load_for_edit()
+                    .get_server(name)
+                    .map(|entry| RemoteServerForm::from_entry(name, entry))
+            })
+            .unwrap_or_default();
+
+        let mut input = |placeholder: &str, value: String, masked: bool| {
+            cx.new(|cx| {
+                InputState::new(window, cx)
+                    .placeholder(placeholder.to_string())
+                    .masked(masked)
+                    .default_value(value)
+            })
+        };
+        self.mcp_remote_dialog = Some(McpRemoteDialog {
+            transport: form.transport,
+            name_input: input("team-tools", form.name, false),
+            url_input: input("https://mcp.example.com/mcp", form.url, false),
+            token_input: input("Optional, e.g. ${TEAM_MCP_TOKEN}", form.bearer_token, true),
+            headers_input: input(
+                "Optional, e.g. X-Team: platform; X-Env: prod",
+                form.headers,
+                false,
+            ),
+            editing,
+            error: None,
+        });
+        cx.notify();
+    }
+
+    /// Close the remote server dialog without saving.
+    pub(crate) fn close_mcp_remote_dialog(&mut self, cx: &mut Context<Self>) {
+        self.mcp_remote_dialog = None;
+        cx.notify();
+    }
+
+    /// Validate the form and write it to the MCP config.
+    fn save_mcp_remote_dialog(&mut self, cx: &mut Context<Self>) {
+        let Some(dialog) = self.mcp_remote_dialog.as_mut() else {
+            return;
+        };
+        let value = |input: &Entity<InputState>| input.read(cx).value().to_string();
+        let form = RemoteServerForm {
+            name: value(&dialog.name_input),
+            url: value(&dialog.url_input),
+            transport: dialog.transport,
+            bearer_token: value(&dialog.token_input),
+            headers: value(&dialog.headers_input),
+        };
+
+        let mut config = McpConfig::load_for_edit();
+        let result = form
+            .save_into(&mut config, dialog.editing.as_deref())
+            .and_then(|()| config.save_default());
+        match result {
+            Ok(()) => {
+                tracing::info!(server = %form.name.trim(), "Saved remote MCP server");
+                self.mcp_remote_dialog = None;
+            }
+            Err(e) => dialog.error = Some(e.to_string()),
+        }
+        cx.notify();
+    }
+
+    /// Renders the remote server dialog overlay.
+    pub(crate) fn render_mcp_remote_dialog(&self, cx: &Context<Self>) -> impl IntoElement {
+        let theme = self.theme.clone();
+
+        div().when_some(self.mcp_remote_dialog.as_ref(), |d, dialog| {
+            d.absolute()
+                .inset_0()
+                .size_full()
+                .bg(rgba(0x000000aa))
+                .occlude()
+                .on_mouse_down(
+                    MouseButton::Left,
+                    cx.listener(|this, _, _, cx| {
+                        this.close_mcp_remote_dialog(cx);
+                    }),
+                )
+                .flex()
+                .items_center()
+                .justify_center()
+                .child(render_dialog_content(&theme, cx, dialog))
+        })
+    }
+}
+
+/// Renders the dialog card.
+fn render_dialog_content(
+    theme: &Theme,
+    cx: &Context<ChatApp>,
+    dialog: &McpRemoteDialog,
+) -> impl IntoElement {
+    let title = match &dialog.editing {
+        Some(name) => format!("🌐 Edit Remote Server: {}", name),
+        None => "🌐 Add Remote MCP Server".to_string(),
+    };
+
+    div()
+        .w(px(560.))
+        .bg(theme.panel_background)
+        .border_1()
+        .border_color(theme.border)
+        .rounded(px(12.))
+        .shadow_lg()
+        .flex()
+        .flex_col()
+        .overflow_hidden()
+        .on_mouse_down(MouseButton::Left, |_, _, cx| {
+            cx.stop_propagation();
+        })
+        .child(
+            div()
+                .flex()
+                .items_center()
+                .justify_between()
+                .px(px(20.))
+                .py(px(14.))
+                .border_b_1()
+                .border_color(theme.border)
+                .child(
+                    div()
+                        .text_size(px(15.))
+                        .font_weight(gpui::FontWeight::SEMIBOLD)
+                        .text_color(theme.text)
+                        .child(title),
+                )
+                .child(
+                    div()
+                        .id("close-mcp-remote")
+                        .px(px(8.))
+                        .py(px(4.))
+                        .rounded(px(6.))
+                        .cursor_pointer()
+                        .hover(|s| s.bg(theme.tool_card))
+                        .text_color(theme.text_muted)
+                        .on_mouse_up(
+                            MouseButton::Left,
+                            cx.listener(|this, _, _, cx| {
+                                cx.stop_propagation();
+                                this.close_mcp_remote_dialog(cx);
+                            }),
+                        )
+                        .child("✕"),
+                ),
+        )
+        .child(
+            div()
+                .p(px(20.))
+                .flex()
+                .flex_col()
+                .gap(px(10.))
+                .child(render_field(theme, "Name:", &dialog.name_input))
+                .child(render_field(theme, "URL:", &dialog.url_input))
+                .child(render_transport_row(theme, cx, dialog.transport))
+                .child(render_field(theme, "Token:", &dialog.token_input))
+                .child(render_field(theme, "Headers:", &dialog.headers_input))
+                .child(
+                    div().text_size(px(11.)).text_color(theme.text_muted).child(
+                        "${VAR} references are expanded from the environment when connecting.",
+                    ),
+                )
+                .when_some(dialog.error.clone(), |d, err| {
+                    d.child(
+                        div()
+                            .px(px(12.))
+                            .py(px(8.))
+                            .rounded(px(6.))
+                            .bg(rgba(0xff6b6b22))
+                            .text_size(px(12.))
+                            .text_color(rgb(0xff6b6b))
+                            .child(err),
+                    )
+                })
+                .child(
+                    div().flex().justify_end().child(
+                        div()
+                            .id("save-mcp-remote")
+                            .px(px(20.))
+                            .py(px(10.))
+                            .rounded(px(6.))
+                            .bg(theme.accent)
+                            .text_color(rgb(0xffffff))
+                            .text_size(px(13.))
+                            .font_weight(gpui::FontWeight::MEDIUM)
+                            .cursor_pointer()
+                            .hover(|s| s.opacity(0.9))
+                            .on_mouse_up(
+                                MouseButton::Left,
+                                cx.listener(|this, _, _, cx| {
+                                    cx.stop_propagation();
+                                    this.save_mcp_remote_dialog(cx);
+                                }),
+                            )
+                            .child("Save Server"),
+                    ),
+                ),
+        )
+}
+
+/// Renders a labelled input row.
+fn render_field(
+    theme: &Theme,
+    label: &'static str,
+    input: &Entity<InputState>,
+) -> impl IntoElement {
+    div()
+        .flex()
+        .items_center()
+        .gap(px(8.))
+        .child(
+            div()
+                .text_size(px(12.))
+                .text_color(theme.text_muted)
+                .w(px(70.))
+                .child(label),
+        )
+        .child(div().flex_1().child(Input::new(input)))
+}
+
+/// Renders the HTTP / SSE transport choice.
+fn render_transport_row(
+    theme: &Theme,
+    cx: &Context<ChatApp>,
+    selected: McpTransportKind,
+) -> impl IntoElement {
+    let option = |kind: McpTransportKind, label: &'static str| {
+        let is_selected = kind == selected;
+        div()
+            .id(SharedString::from(format!(
+                "mcp-remote-transport-{}",
+                kind.label()
+            )))
+            .px(px(10.))
+            .py(px(4.))
+            .rounded(px(4.))
+            .text_size(px(12.))
+            .cursor_pointer()
+            .bg(if is_selected {
+                theme.accent
+            } else {
+                theme.tool_card
+            })
+            .text_color(if is_selected {
+                rgb(0xffffff)
+            } else {
+                theme.text_muted
+            })
+            .on_mouse_up(
+                MouseButton::Left,
+                cx.listener(move |this, _, _, cx| {
+                    if let Some(dialog) = this.mcp_remote_dialog.as_mut() {
+                        dialog.transport = kind;
+                    }
+                    cx.notify();
+                }),
+            )
+            .child(label)
+    };
+
+    div()
+        .flex()
+        .items_center()
+        .gap(px(8.))
+        .child(
+            div()
+                .text_size(px(12.))
+                .text_color(theme.text_muted)
+                .w(px(70.))
+                .child("Transport:"),
+        )
+        .child(option(McpTransportKind::Http, "Streamable HTTP"))
+        .child(option(McpTransportKind::Sse, "SSE"))
+}
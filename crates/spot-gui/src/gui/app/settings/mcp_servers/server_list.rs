@@ -1,30 +1,33 @@
 //! MCP Server list panel component.
 //!
-//! Renders the left panel showing all defined MCP servers with
-//! enable/disable and delete controls.
+//! Renders the left panel showing all defined MCP servers with their
+//! runtime state and enable/disable, edit (remote servers) and delete
+//! controls.
+
+use std::collections::HashMap;
 
 use gpui::{div, prelude::*, px, rgb, rgba, Context, MouseButton, SharedString, Styled};
 
 use crate::gui::app::ChatApp;
 use crate::gui::theme::Theme;
-use spot_core::mcp::McpConfig;
+use spot_core::mcp::{McpConfig, McpServerState, McpServerStatus};
 
-/// Server info tuple: (name, enabled, description, command_preview)
-pub type ServerInfo = (String, bool, Option<String>, String);
+/// Server info tuple: (name, enabled, description, command_preview, is_remote)
+pub type ServerInfo = (String, bool, Option<String>, String, bool);
 
 /// Loads and returns sorted server info from the MCP config.
 pub fn load_servers() -> Vec<ServerInfo> {
-    let mcp_config = McpConfig::load_or_default();
+    let mcp_config = McpConfig::load_for_edit();
     let mut servers: Vec<ServerInfo> = mcp_config
         .servers
         .iter()
         .map(|(name, entry)| {
-            let cmd_preview = format!("{} {}", entry.command, entry.args.join(" "));
             (
                 name.clone(),
                 entry.enabled,
                 entry.description.clone(),
-                cmd_preview,
+                entry.display_target(),
+                entry.is_remote(),
             )
         })
         .collect();
@@ -37,6 +40,7 @@ pub fn render_server_list(
     theme: &Theme,
     cx: &Context<ChatApp>,
     servers: &[ServerInfo],
+    statuses: &HashMap<String, McpServerStatus>,
 ) -> impl IntoElement {
     div()
         .flex()
@@ -66,7 +70,7 @@ pub fn render_server_list(
                         .child(format!("{} defined", servers.len())),
                 ),
         )
-        .child(render_servers_scroll_area(theme, cx, servers))
+        .child(render_servers_scroll_area(theme, cx, servers, statuses))
 }
 
 /// Renders the scrollable server list area.
@@ -74,6 +78,7 @@ fn render_servers_scroll_area(
     theme: &Theme,
     cx: &Context<ChatApp>,
     servers: &[ServerInfo],
+    statuses: &HashMap<String, McpServerStatus>,
 ) -> impl IntoElement {
     let theme = theme.clone();
 
@@ -97,22 +102,41 @@ fn render_servers_scroll_area(
                     .text_size(px(13.))
                     .text_color(theme.text_muted)
                     .text_center()
-                    .child("No MCP servers defined.\nClick 'Import from JSON' to add servers."),
+                    .child(
+                        "No MCP servers defined.\nClick 'Import from JSON' or 'Add Remote Server' \
+                         to add servers.",
+                    ),
             )
         })
-        .children(servers.iter().map(|(name, enabled, desc, cmd_preview)| {
-            render_server_card(&theme, cx, name, *enabled, desc.clone(), cmd_preview)
-        }))
+        .children(
+            servers
+                .iter()
+                .map(|(name, enabled, desc, cmd_preview, is_remote)| {
+                    render_server_card(
+                        &theme,
+                        cx,
+                        name,
+                        *enabled,
+                        *is_remote,
+                        desc.clone(),
+                        cmd_preview,
+                        statuses.get(name),
+                    )
+                }),
+        )
 }
 
 /// Renders a single server card with controls.
+#[allow(clippy::too_many_arguments)]
 fn render_server_card(
     theme: &Theme,
     cx: &Context<ChatApp>,
     name: &str,
     enabled: bool,
+    is_remote: bool,
     description: Option<String>,
     cmd_preview: &str,
+    status: Option<&McpServerStatus>,
 ) -> impl IntoElement {
     let theme = theme.clone();
     let server_name = name.to_string();
@@ -133,6 +157,7 @@ fn render_server_card(
             cx,
             &name_display,
             enabled,
+            is_remote,
             server_name_toggle,
             server_name_del,
         ))
@@ -157,6 +182,56 @@ fn render_server_card(
                 .overflow_hidden()
                 .child(truncate_text(&cmd, 50)),
         )
+        .when_some(status, |d, status| {
+            d.child(render_server_status(&theme, status))
+        })
+}
+
+/// Renders the runtime state of a server: tool count or last error, and
+/// when it will next be restarted.
+fn render_server_status(theme: &Theme, status: &McpServerStatus) -> impl IntoElement {
+    let color = match status.state {
+        McpServerState::Ready => theme.success,
+        McpServerState::Starting => theme.warning,
+        McpServerState::Unhealthy | McpServerState::Failed => theme.error,
+        McpServerState::Stopped => theme.text_muted,
+    };
+
+    let mut details = Vec::new();
+    if let Some(secs) = status.restart_in_secs() {
+        details.push(format!("retrying in {}s", secs));
+    }
+    if status.restarts > 0 {
+        details.push(format!(
+            "restarted {} time{}",
+            status.restarts,
+            if status.restarts == 1 { "" } else { "s" }
+        ));
+    }
+
+    div()
+        .mt(px(8.))
+        .flex()
+        .flex_col()
+        .gap(px(2.))
+        .child(
+            div()
+                .flex()
+                .items_center()
+                .gap(px(6.))
+                .text_size(px(11.))
+                .child(div().text_color(color).child("●"))
+                .child(div().text_color(theme.text).child(status.summary())),
+        )
+        .when(!details.is_empty(), |d| {
+            d.child(
+                div()
+                    .pl(px(14.))
+                    .text_size(px(11.))
+                    .text_color(theme.text_muted)
+                    .child(details.join(" · ")),
+            )
+        })
 }
 
 /// Renders the header row of a server card with name, status, and controls.
@@ -165,6 +240,7 @@ fn render_server_header(
     cx: &Context<ChatApp>,
     name: &str,
     enabled: bool,
+    is_remote: bool,
     server_name_toggle: String,
     server_name_del: String,
 ) -> impl IntoElement {
@@ -210,27 +286,50 @@ fn render_server_header(
             &theme,
             cx,
             enabled,
+            is_remote,
             server_name_toggle,
             server_name_del,
         ))
 }
 
-/// Renders the toggle and delete buttons for a server.
+/// Renders the toggle, edit and delete buttons for a server.
 fn render_server_controls(
     theme: &Theme,
     cx: &Context<ChatApp>,
     enabled: bool,
+    is_remote: bool,
     server_name_toggle: String,
     server_name_del: String,
 ) -> impl IntoElement {
     let theme = theme.clone();
     let toggle_name = server_name_toggle.clone();
     let del_name = server_name_del.clone();
+    let edit_name = server_name_toggle.clone();
 
     div()
         .flex()
         .items_center()
         .gap(px(4.))
+        .when(is_remote, |d| {
+            d.child(
+                div()
+                    .id(SharedString::from(format!("edit-mcp-{}", edit_name)))
+                    .px(px(8.))
+                    .py(px(4.))
+                    .rounded(px(4.))
+                    .text_size(px(11.))
+                    .text_color(theme.text_muted)
+                    .cursor_pointer()
+                    .hover(|s| s.bg(theme.background).text_color(theme.text))
+                    .on_mouse_up(
+                        MouseButton::Left,
+                        cx.listener(move |this, _, window, cx| {
+                            this.open_mcp_remote_dialog(Some(edit_name.clone()), window, cx);
+                        }),
+                    )
+                    .child("edit"),
+            )
+        })
         .child(
             div()
                 .id(SharedString::from(format!("toggle-mcp-{}", toggle_name)))
@@ -244,7 +343,7 @@ fn render_server_controls(
                 .on_mouse_up(
                     MouseButton::Left,
                     cx.listener(move |_this, _, _, cx| {
-                        let mut config = McpConfig::load_or_default();
+                        let mut config = McpConfig::load_for_edit();
                         if let Some(entry) = config.servers.get_mut(&server_name_toggle) {
                             entry.enabled = !entry.enabled;
                             let _ = config.save_default();
@@ -267,7 +366,7 @@ fn render_server_controls(
                 .on_mouse_up(
                     MouseButton::Left,
                     cx.listener(move |_this, _, _, cx| {
-                        let mut config = McpConfig::load_or_default();
+                        let mut config = McpConfig::load_for_edit();
                         config.remove_server(&server_name_del);
                         let _ = config.save_default();
                         cx.notify();
@@ -15,4 +15,5 @@ mod models;
 mod pinned_agents;
 mod tabs;
 
+pub(crate) use mcp_servers::McpRemoteDialog;
 pub(crate) use tabs::SettingsTab;
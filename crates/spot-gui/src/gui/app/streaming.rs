@@ -374,6 +374,20 @@ impl ChatApp {
                     _ => {}
                 }
             }
+            Message::Diff(diff) => {
+                // Attach file changes to the tool call that made them
+                let section_id = diff
+                    .agent_name
+                    .as_ref()
+                    .and_then(|name| self.active_section_ids.get(name))
+                    .cloned();
+                if let Some(section_id) = section_id {
+                    self.conversation
+                        .attach_diff_in_section(&section_id, diff.clone());
+                } else {
+                    self.conversation.attach_diff(diff.clone());
+                }
+            }
             Message::Agent(agent) => match &agent.event {
                 AgentEvent::Started => {
                     if self.active_agent_stack.is_empty() {
@@ -415,6 +429,7 @@ impl ChatApp {
 
                     // Only finish generating if main agent completed (stack empty)
                     if self.active_agent_stack.is_empty() {
+                        self.append_turn_summary();
                         self.conversation.finish_current_message();
                         self.is_generating = false;
                         self.sync_messages_list_state();
@@ -451,6 +466,7 @@ impl ChatApp {
                     if self.active_agent_stack.is_empty() {
                         self.conversation
                             .append_to_current(&format!("\n\n❌ Error: {}", message));
+                        self.append_turn_summary();
                         self.conversation.finish_current_message();
                         self.is_generating = false;
                         self.error_message = Some(message.clone());
@@ -459,6 +475,32 @@ impl ChatApp {
                         self.active_section_ids.clear();
                     }
                 }
+                AgentEvent::Cancelled => {
+                    // Nested runs may report after their parent already unwound them
+                    if self.active_agent_stack.contains(&agent.agent_name) {
+                        // Pop all agents down to (and including) the cancelled one
+                        while let Some(agent_name) = self.active_agent_stack.pop() {
+                            if let Some(section_id) = self.active_section_ids.get(&agent_name) {
+                                self.conversation
+                                    .append_to_nested_agent(section_id, "\n\n⏹ Cancelled");
+                                self.conversation.finish_nested_agent(section_id);
+                            }
+                            if agent_name == agent.agent_name {
+                                break;
+                            }
+                        }
+                    }
+
+                    if self.active_agent_stack.is_empty() && self.is_generating {
+                        self.conversation.append_to_current("\n\n⏹ Cancelled");
+                        self.append_turn_summary();
+                        self.conversation.finish_current_message();
+                        self.is_generating = false;
+                        self.is_streaming_active = false;
+                        self.sync_messages_list_state();
+                        self.active_section_ids.clear();
+                    }
+                }
             },
             Message::ContextInfo(info) => {
                 // Update context usage with real data from the agent
@@ -500,6 +542,28 @@ impl ChatApp {
                 );
                 self.compression_notification = Some((std::time::Instant::now(), notification));
             }
+            Message::Retry(retry) => {
+                tracing::warn!(
+                    provider = %retry.provider,
+                    attempt = retry.attempt,
+                    reason = %retry.reason,
+                    "Retrying model request"
+                );
+                self.retry_notification = Some((std::time::Instant::now(), retry.summary()));
+            }
+            Message::McpStatus(status) => {
+                tracing::info!(
+                    server = %status.server,
+                    state = status.state.label(),
+                    error = ?status.error,
+                    "MCP server status changed"
+                );
+                // The settings page reads statuses when it redraws; only
+                // failures are worth interrupting the chat for
+                if status.state.is_down() {
+                    self.retry_notification = Some((std::time::Instant::now(), status.summary()));
+                }
+            }
             _ => {}
         }
 
@@ -3,7 +3,7 @@
 //! This module provides a collapsible right-side panel showing all
 //! active terminal processes (both LLM-spawned and user shells).
 
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use std::path::PathBuf;
 use std::sync::Arc;
 
@@ -15,14 +15,16 @@ use gpui_component::input::{Input, InputEvent, InputState};
 use tokio::sync::mpsc;
 use tracing::{debug, error, info};
 
+use super::file_approvals::PendingFileChange;
 use super::ChatApp;
 use crate::gui::components::{scrollbar, ScrollbarDragState, TerminalView};
-use spot_core::config::Settings;
+use spot_core::config::CommandAction;
 use spot_core::terminal::{
-    headless_env, interactive_env, spawn_pty, validate_command, CommandValidation, ProcessKind,
-    ProcessSnapshot, PtyConfig, PtyEvent, RiskLevel, SystemExecRequest, SystemExecResponse,
-    SystemExecStore, TerminalEventBridge, TerminalSize,
+    headless_env, interactive_env, spawn_pty, CommandValidation, ProcessKind, ProcessSnapshot,
+    PtyConfig, PtyEvent, RiskLevel, SystemExecRequest, SystemExecResponse, SystemExecStore,
+    TerminalEventBridge, TerminalSize,
 };
+use spot_core::tools::CommandPolicy;
 
 /// Default width of the system executions sidebar
 const DEFAULT_SIDEBAR_WIDTH: f32 = 320.0;
@@ -45,6 +47,8 @@ pub struct PendingApproval {
     pub request_id: u64,
     pub command: String,
     pub cwd: Option<String>,
+    /// Set when the command is input for this running process
+    pub process_id: Option<String>,
     pub validation: CommandValidation,
 }
 
@@ -70,6 +74,10 @@ pub struct SystemExecutionsState {
     pub request_rx: Option<mpsc::UnboundedReceiver<SystemExecRequest>>,
     /// Pending command awaiting user approval
     pub pending_approval: Option<PendingApproval>,
+    /// File writes and deletes awaiting review; the front one is shown
+    pub pending_file_changes: VecDeque<PendingFileChange>,
+    /// Editor for the shown file change's content, while the user edits it
+    pub file_change_editor: Option<Entity<InputState>>,
     /// Current sidebar width
     pub sidebar_width: f32,
     /// Whether we're currently resizing
@@ -138,6 +146,8 @@ impl SystemExecutionsState {
             request_tx,
             request_rx: Some(request_rx),
             pending_approval: None,
+            pending_file_changes: VecDeque::new(),
+            file_change_editor: None,
             sidebar_width: DEFAULT_SIDEBAR_WIDTH,
             is_resizing: false,
             resize_start_x: None,
@@ -158,6 +168,23 @@ impl SystemExecutionsState {
         self.request_rx.take()
     }
 
+    /// Refuse every command, input and file change still awaiting approval.
+    ///
+    /// Used when the agent run that asked for them is cancelled.
+    pub fn clear_pending_approvals(&mut self) {
+        let commands = self.pending_approval.take().map(|p| p.request_id);
+        let changes = self.pending_file_changes.drain(..).map(|p| p.request_id);
+        for request_id in commands.into_iter().chain(changes) {
+            self.store.respond(
+                request_id,
+                SystemExecResponse::Error {
+                    message: "Agent run was cancelled".to_string(),
+                },
+            );
+        }
+        self.file_change_editor = None;
+    }
+
     /// Toggle sidebar visibility
     pub fn toggle(&mut self) {
         self.visible = !self.visible;
@@ -908,10 +935,8 @@ impl ChatApp {
 
     /// Kill a running terminal and remove it from the UI
     fn kill_terminal(&mut self, process_id: &str, cx: &mut Context<Self>) {
-        // 1. Mark as exited in the store (SIGKILL = 137)
-        self.system_executions
-            .store
-            .mark_finished(process_id, Some(137));
+        // 1. Kill the PTY process and mark it as exited in the store
+        self.system_executions.store.kill(process_id);
 
         // 2. Also remove from UI immediately - user wants kill to close too
         self.system_executions.remove_terminal(process_id);
@@ -921,8 +946,6 @@ impl ChatApp {
         self.system_executions.terminal_inputs.remove(process_id);
         self.system_executions.terminal_heights.remove(process_id);
 
-        // TODO: Actually send signal to PTY process
-        // For now we just mark it as finished and remove from UI
         cx.notify();
     }
 
@@ -1000,6 +1023,7 @@ impl ChatApp {
             kind,
             visible: true,
             output: String::new(),
+            output_total: 0,
             exit_code: None,
             started_at_ms: now_ms,
             finished_at_ms: None,
@@ -1028,6 +1052,15 @@ impl ChatApp {
         match spawn_pty(config) {
             Ok(spawned) => {
                 info!(process_id = %process_id, command = %command, "Terminal spawned");
+                self.system_executions
+                    .store
+                    .register_killer(&process_id, spawned.killer);
+                if kind == ProcessKind::Llm {
+                    // Let agents drive the commands they started
+                    self.system_executions
+                        .store
+                        .register_writer(&process_id, spawned.writer_tx.clone());
+                }
 
                 // Create alacritty terminal for graphical rendering
                 let (event_tx, _event_rx) = tokio::sync::mpsc::unbounded_channel();
@@ -1134,10 +1167,7 @@ impl ChatApp {
 
                                 // Store raw text for tool response
                                 let text = String::from_utf8_lossy(&bytes);
-                                if let Some(mut snapshot) = store.snapshot(&pid) {
-                                    snapshot.output.push_str(&text);
-                                    store.upsert_process(snapshot);
-                                }
+                                store.append_output(&pid, &text);
 
                                 needs_render = true;
                             }
@@ -1208,40 +1238,53 @@ impl ChatApp {
                     "Handling ExecuteShell request"
                 );
 
-                // Validate the command
-                let validation = validate_command(&command);
-
-                // Check YOLO mode from settings
-                let yolo_mode = Settings::new(&self.db).yolo_mode();
-
-                // Decide if we need approval
-                // TRUE YOLO: if enabled, accept EVERYTHING - no exceptions! 🎲
-                let needs_approval = !yolo_mode;
-
-                if needs_approval {
-                    // Store pending approval and show dialog
-                    info!(
-                        request_id = request_id,
-                        risk_level = ?validation.risk_level,
-                        "Command requires user approval"
-                    );
-                    self.system_executions.pending_approval = Some(PendingApproval {
-                        request_id,
-                        command,
-                        cwd,
-                        validation,
-                    });
-                    // Make sidebar visible to show approval dialog
-                    self.system_executions.visible = true;
-                    cx.notify();
-                } else {
-                    // Auto-approve: execute immediately (no window = no interactive input)
-                    info!(
-                        request_id = request_id,
-                        risk_level = ?validation.risk_level,
-                        "Auto-approving command (YOLO mode or low risk)"
-                    );
-                    self.execute_approved_command(request_id, command, cwd, None, cx);
+                // Rules, persisted approvals and YOLO mode decide; high risk is always denied
+                let decision = CommandPolicy::load(&self.db).evaluate(&command, cwd.as_deref());
+
+                match decision.action {
+                    CommandAction::Ask => {
+                        // Store pending approval and show dialog
+                        info!(
+                            request_id = request_id,
+                            risk_level = ?decision.validation.risk_level,
+                            reason = %decision.reason,
+                            "Command requires user approval"
+                        );
+                        self.system_executions.pending_approval = Some(PendingApproval {
+                            request_id,
+                            command,
+                            cwd,
+                            process_id: None,
+                            validation: decision.validation,
+                        });
+                        // Make sidebar visible to show approval dialog
+                        self.system_executions.visible = true;
+                        cx.notify();
+                    }
+                    CommandAction::Allow => {
+                        // Auto-approve: execute immediately (no window = no interactive input)
+                        info!(
+                            request_id = request_id,
+                            risk_level = ?decision.validation.risk_level,
+                            reason = %decision.reason,
+                            "Auto-approving command"
+                        );
+                        self.execute_approved_command(request_id, command, cwd, None, cx);
+                    }
+                    CommandAction::Deny => {
+                        info!(
+                            request_id = request_id,
+                            risk_level = ?decision.validation.risk_level,
+                            reason = %decision.reason,
+                            "Command denied by policy"
+                        );
+                        self.system_executions.store.respond(
+                            request_id,
+                            SystemExecResponse::Error {
+                                message: format!("Command denied by policy: {}", decision.reason),
+                            },
+                        );
+                    }
                 }
             }
             SystemExecRequest::KillProcess {
@@ -1262,6 +1305,51 @@ impl ChatApp {
                     },
                 );
             }
+            SystemExecRequest::ApproveFileChange { request_id, change } => {
+                self.handle_file_change_request(request_id, change, cx);
+            }
+            SystemExecRequest::ApproveProcessInput {
+                request_id,
+                process_id,
+                input,
+            } => {
+                // Input to a shell or REPL runs like a command, so the same policy applies
+                let decision = CommandPolicy::load(&self.db).evaluate_input(&input);
+                info!(
+                    request_id = request_id,
+                    process_id = %process_id,
+                    action = ?decision.action,
+                    reason = %decision.reason,
+                    "Evaluated process input against policy"
+                );
+
+                match decision.action {
+                    CommandAction::Ask => {
+                        self.system_executions.pending_approval = Some(PendingApproval {
+                            request_id,
+                            command: input,
+                            cwd: None,
+                            process_id: Some(process_id),
+                            validation: decision.validation,
+                        });
+                        self.system_executions.visible = true;
+                        cx.notify();
+                    }
+                    CommandAction::Allow => {
+                        self.system_executions
+                            .store
+                            .respond(request_id, SystemExecResponse::InputApproved);
+                    }
+                    CommandAction::Deny => {
+                        self.system_executions.store.respond(
+                            request_id,
+                            SystemExecResponse::Error {
+                                message: format!("Input denied by policy: {}", decision.reason),
+                            },
+                        );
+                    }
+                }
+            }
         }
     }
 
@@ -1303,6 +1391,13 @@ impl ChatApp {
     pub fn approve_pending_command(&mut self, window: &mut Window, cx: &mut Context<Self>) {
         if let Some(pending) = self.system_executions.pending_approval.take() {
             info!(request_id = pending.request_id, "User approved command");
+            if pending.process_id.is_some() {
+                self.system_executions
+                    .store
+                    .respond(pending.request_id, SystemExecResponse::InputApproved);
+                cx.notify();
+                return;
+            }
             self.execute_approved_command(
                 pending.request_id,
                 pending.command,
@@ -1313,6 +1408,19 @@ impl ChatApp {
         }
     }
 
+    /// Approve the pending command and always allow it from now on
+    ///
+    /// Input typed into a running process is approved once, never remembered.
+    pub fn always_allow_pending_command(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let command = self.system_executions.pending_approval.as_ref();
+        if let Some(pending) = command.filter(|p| p.process_id.is_none()) {
+            if let Err(e) = CommandPolicy::remember(&self.db, &pending.command) {
+                tracing::warn!("Failed to save always-allowed command: {}", e);
+            }
+        }
+        self.approve_pending_command(window, cx);
+    }
+
     /// Reject the pending command
     pub fn reject_pending_command(&mut self, cx: &mut Context<Self>) {
         if let Some(pending) = self.system_executions.pending_approval.take() {
@@ -1344,7 +1452,20 @@ impl ChatApp {
             };
 
             let command = pending.command.clone();
-            let cwd = pending.cwd.clone();
+            // Where the command runs: a working directory, or the process it's typed into
+            let (title, location_label, location) = match &pending.process_id {
+                Some(process_id) => (
+                    "Input Approval Required",
+                    "Typed Into Process:",
+                    Some(process_id.clone()),
+                ),
+                None => (
+                    "Command Approval Required",
+                    "Working Directory:",
+                    pending.cwd.clone(),
+                ),
+            };
+            let is_input = pending.process_id.is_some();
             let warnings = validation.warnings.clone();
             let risk_emoji = validation.risk_emoji();
             let risk_desc = validation.risk_description();
@@ -1388,7 +1509,7 @@ impl ChatApp {
                                                 .text_size(px(16.))
                                                 .font_weight(gpui::FontWeight::SEMIBOLD)
                                                 .text_color(theme.text)
-                                                .child("Command Approval Required"),
+                                                .child(title),
                                         )
                                         .child(
                                             div()
@@ -1448,8 +1569,8 @@ impl ChatApp {
                                                 .child(command),
                                         ),
                                 )
-                                // Working directory (if specified)
-                                .when(cwd.is_some(), |d| {
+                                // Working directory or target process (if specified)
+                                .when(location.is_some(), |d| {
                                     d.child(
                                         div()
                                             .flex()
@@ -1459,7 +1580,7 @@ impl ChatApp {
                                                 div()
                                                     .text_size(px(12.))
                                                     .text_color(theme.text_muted)
-                                                    .child("Working Directory:"),
+                                                    .child(location_label),
                                             )
                                             .child(
                                                 div()
@@ -1472,7 +1593,7 @@ impl ChatApp {
                                                     .font_family("monospace")
                                                     .text_size(px(12.))
                                                     .text_color(theme.text_muted)
-                                                    .child(cwd.unwrap_or_default()),
+                                                    .child(location.unwrap_or_default()),
                                             ),
                                     )
                                 })
@@ -1540,6 +1661,33 @@ impl ChatApp {
                                         )
                                         .child("✗ Reject"),
                                 )
+                                // Always allow button (commands only)
+                                .when(!is_input, |d| {
+                                    d.child(
+                                        div()
+                                            .id("always-allow-cmd-btn")
+                                            .px(px(16.))
+                                            .py(px(8.))
+                                            .rounded(px(6.))
+                                            .border_1()
+                                            .border_color(theme.success)
+                                            .text_color(theme.success)
+                                            .text_size(px(13.))
+                                            .font_weight(gpui::FontWeight::MEDIUM)
+                                            .cursor_pointer()
+                                            .hover(|s| s.bg(rgba(0x4ec9b022)))
+                                            .on_mouse_down(MouseButton::Left, |_, _, cx| {
+                                                cx.stop_propagation();
+                                            })
+                                            .on_mouse_up(
+                                                MouseButton::Left,
+                                                cx.listener(|this, _, window, cx| {
+                                                    this.always_allow_pending_command(window, cx);
+                                                }),
+                                            )
+                                            .child("✓ Always Allow"),
+                                    )
+                                })
                                 // Approve button
                                 .child(
                                     div()
@@ -207,6 +207,48 @@ impl ChatApp {
                                     )
                                 },
                             )
+                            // Retry notification (shows for 5 seconds after a retry or fallback)
+                            .when_some(
+                                self.retry_notification
+                                    .as_ref()
+                                    .filter(|(ts, _)| {
+                                        ts.elapsed() < std::time::Duration::from_secs(5)
+                                    })
+                                    .map(|(_, msg)| msg.clone()),
+                                |el, notification| {
+                                    el.child(
+                                        div()
+                                            .px(px(8.))
+                                            .py(px(4.))
+                                            .rounded(px(4.))
+                                            .bg(self.theme.tool_card)
+                                            .text_size(px(11.))
+                                            .text_color(self.theme.warning)
+                                            .child(notification),
+                                    )
+                                },
+                            )
+                            // Undo notification (shows for 5 seconds after files are restored)
+                            .when_some(
+                                self.undo_notification
+                                    .as_ref()
+                                    .filter(|(ts, _)| {
+                                        ts.elapsed() < std::time::Duration::from_secs(5)
+                                    })
+                                    .map(|(_, msg)| msg.clone()),
+                                |el, notification| {
+                                    el.child(
+                                        div()
+                                            .px(px(8.))
+                                            .py(px(4.))
+                                            .rounded(px(4.))
+                                            .bg(self.theme.tool_card)
+                                            .text_size(px(11.))
+                                            .text_color(self.theme.success)
+                                            .child(notification),
+                                    )
+                                },
+                            )
                             // Throughput chart
                             .child(
                                 div()
@@ -270,6 +312,30 @@ impl ChatApp {
                                     )
                                     .child("+ New"),
                             )
+                            // Saved sessions
+                            .child(
+                                div()
+                                    .id("sessions-btn")
+                                    .px(px(12.))
+                                    .py(px(6.))
+                                    .rounded(px(6.))
+                                    .bg(self.theme.tool_card)
+                                    .text_color(self.theme.text)
+                                    .text_size(px(12.))
+                                    .cursor_pointer()
+                                    .hover(|s| s.opacity(0.8))
+                                    .on_mouse_up(
+                                        MouseButton::Left,
+                                        cx.listener(|this, _, _, cx| {
+                                            this.open_session_picker();
+                                            cx.notify();
+                                        }),
+                                    )
+                                    .child(match &self.current_session {
+                                        Some(name) => format!("🗂 {}", name),
+                                        None => "🗂 Sessions".to_string(),
+                                    }),
+                            )
                             // System Executions toggle
                             .child(
                                 div()
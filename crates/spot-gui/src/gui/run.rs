@@ -60,10 +60,12 @@ pub fn run_gui(config: AppConfig) -> Result<()> {
         });
     }
 
+    let resume = config.resume;
+
     Application::new()
         .with_assets(gpui_component_assets::Assets)
         .with_quit_mode(QuitMode::LastWindowClosed)
-        .run(|cx: &mut App| {
+        .run(move |cx: &mut App| {
             gpui_component::init(cx);
             Theme::change(ThemeMode::Dark, None, cx);
             register_keybindings(cx);
@@ -79,8 +81,14 @@ pub fn run_gui(config: AppConfig) -> Result<()> {
                     }),
                     ..Default::default()
                 },
-                |window, cx| {
-                    let app_view = cx.new(|cx| ChatApp::new(window, cx));
+                move |window, cx| {
+                    let app_view = cx.new(|cx| {
+                        let mut app = ChatApp::new(window, cx);
+                        if let Some(name) = &resume {
+                            app.load_session(name.as_deref());
+                        }
+                        app
+                    });
                     cx.new(|cx| Root::new(app_view, window, cx))
                 },
             )
@@ -2,6 +2,8 @@
 //!
 //! Manages chat messages and tool calls for the GUI.
 
+use spot_core::messaging::DiffMessage;
+
 use super::message::{ChatMessage, ToolCall, ToolCallState};
 use super::sections::{MessageSection, ToolCallSection};
 use super::tool_display::get_tool_display_info;
@@ -252,6 +254,27 @@ impl Conversation {
         }
     }
 
+    /// Attach a file diff to the most recent tool call
+    pub fn attach_diff(&mut self, diff: DiffMessage) {
+        if let Some(msg) = self.messages.last_mut() {
+            for section in msg.sections.iter_mut().rev() {
+                if let MessageSection::ToolCall(ref mut tool) = section {
+                    tool.diff = Some(diff);
+                    return;
+                }
+            }
+        }
+    }
+
+    /// Attach a file diff to the most recent tool call in a nested section
+    pub fn attach_diff_in_section(&mut self, section_id: &str, diff: DiffMessage) {
+        if let Some(msg) = self.messages.last_mut() {
+            if let Some(section) = msg.get_nested_section_mut(section_id) {
+                section.attach_diff(diff);
+            }
+        }
+    }
+
     /// Complete a tool call in a specific nested section
     pub fn complete_tool_call_in_section(
         &mut self,
@@ -502,6 +525,32 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_attach_diff_to_latest_tool_call() {
+        let mut conv = Conversation::new();
+        conv.start_assistant_message();
+
+        conv.append_tool_call("read_file", None);
+        conv.append_tool_call("edit_file", None);
+        conv.attach_diff(DiffMessage {
+            path: "src/main.rs".to_string(),
+            lines: vec![],
+            agent_name: None,
+            checkpoint: None,
+        });
+
+        let msg = conv.messages.last().unwrap();
+        let diffs: Vec<_> = msg
+            .sections
+            .iter()
+            .filter_map(|s| match s {
+                MessageSection::ToolCall(tool) => Some(tool.diff.as_ref().map(|d| d.path.as_str())),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(diffs, vec![None, Some("src/main.rs")]);
+    }
+
     #[test]
     fn test_complete_tool_call_finds_most_recent_running() {
         let mut conv = Conversation::new();
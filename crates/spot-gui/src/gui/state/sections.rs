@@ -2,6 +2,8 @@
 //!
 //! Provides section abstractions for collapsible nested agent output.
 
+use spot_core::messaging::DiffMessage;
+
 use super::tool_display::ToolDisplayInfo;
 
 /// Content item within a nested agent section
@@ -15,6 +17,8 @@ pub enum AgentContentItem {
         info: ToolDisplayInfo,
         is_running: bool,
         succeeded: Option<bool>,
+        /// File changes made by the tool
+        diff: Option<DiffMessage>,
     },
     /// A thinking/reasoning section
     Thinking {
@@ -35,6 +39,7 @@ impl AgentContentItem {
             info,
             is_running: true,
             succeeded: None,
+            diff: None,
         }
     }
 
@@ -115,6 +120,16 @@ impl AgentSection {
         }
     }
 
+    /// Attach a diff to the most recent tool call
+    pub fn attach_diff(&mut self, new_diff: DiffMessage) {
+        for item in self.items.iter_mut().rev() {
+            if let AgentContentItem::ToolCall { diff, .. } = item {
+                *diff = Some(new_diff);
+                return;
+            }
+        }
+    }
+
     /// Start a new thinking section, returns the section ID
     pub fn start_thinking(&mut self) -> String {
         let item = AgentContentItem::thinking();
@@ -296,6 +311,8 @@ pub struct ToolCallSection {
     pub is_running: bool,
     /// Whether the tool call succeeded (None if still running)
     pub succeeded: Option<bool>,
+    /// File changes made by the tool
+    pub diff: Option<DiffMessage>,
 }
 
 impl ToolCallSection {
@@ -305,6 +322,7 @@ impl ToolCallSection {
             info,
             is_running: true,
             succeeded: None,
+            diff: None,
         }
     }
 
@@ -42,7 +42,8 @@ impl ModelRegistry {
             .prepare(
                 "SELECT name, model_type, model_id, context_length, supports_thinking,
                         supports_vision, supports_tools, description, api_endpoint,
-                        api_key_env, headers, azure_deployment, azure_api_version
+                        api_key_env, headers, azure_deployment, azure_api_version,
+                        round_robin_models
                  FROM models ORDER BY name",
             )
             .map_err(|e| ModelConfigError::Io(std::io::Error::other(e.to_string())))?;
@@ -51,6 +52,7 @@ impl ModelRegistry {
             .query_map([], |row| {
                 let model_type_str: String = row.get(1)?;
                 let headers_json: Option<String> = row.get(10)?;
+                let round_robin_json: Option<String> = row.get(13)?;
 
                 Ok(ModelConfig {
                     name: row.get(0)?,
@@ -68,7 +70,9 @@ impl ModelRegistry {
                     ),
                     azure_deployment: row.get(11)?,
                     azure_api_version: row.get(12)?,
-                    round_robin_models: Vec::new(),
+                    round_robin_models: round_robin_json
+                        .and_then(|json| serde_json::from_str(&json).ok())
+                        .unwrap_or_default(),
                 })
             })
             .map_err(|e| ModelConfigError::Io(std::io::Error::other(e.to_string())))?;
@@ -126,13 +130,15 @@ impl ModelRegistry {
             .custom_endpoint
             .as_ref()
             .map(|e| serde_json::to_string(&e.headers).unwrap_or_default());
+        let round_robin_json = (!config.round_robin_models.is_empty())
+            .then(|| serde_json::to_string(&config.round_robin_models).unwrap_or_default());
 
         let result = db.conn().execute(
             "INSERT OR REPLACE INTO models (name, model_type, model_id, context_length,
                 supports_thinking, supports_vision, supports_tools, description,
                 api_endpoint, api_key_env, headers, azure_deployment, azure_api_version,
-                is_builtin, source, updated_at)
-             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, 0, ?, unixepoch())",
+                round_robin_models, is_builtin, source, updated_at)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, 0, ?, unixepoch())",
             params![
                 &config.name,
                 config.model_type.to_string(),
@@ -150,6 +156,7 @@ impl ModelRegistry {
                 headers_json,
                 &config.azure_deployment,
                 &config.azure_api_version,
+                round_robin_json,
                 source,
             ],
         );
@@ -935,6 +942,24 @@ mod tests {
         assert!(available.contains(&"round-robin-test".to_string()));
     }
 
+    #[test]
+    fn test_round_robin_members_persisted() {
+        let (_temp, db) = setup_test_db();
+
+        let model = ModelConfig {
+            name: "pool".to_string(),
+            model_type: ModelType::RoundRobin,
+            round_robin_models: vec!["model-a".to_string(), "model-b".to_string()],
+            ..Default::default()
+        };
+        ModelRegistry::add_model_to_db(&db, &model).unwrap();
+
+        let registry = ModelRegistry::load_from_db(&db).unwrap();
+        let loaded = registry.get("pool").unwrap();
+
+        assert_eq!(loaded.round_robin_models, vec!["model-a", "model-b"]);
+    }
+
     #[test]
     fn test_list_available_azure() {
         let (_temp, db) = setup_test_db();
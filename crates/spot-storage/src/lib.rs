@@ -150,6 +150,11 @@ impl Database {
         repositories::SettingsRepository::new(&self.conn)
     }
 
+    /// Get a session repository for the `sessions` and `messages` tables.
+    pub fn sessions(&self) -> repositories::SessionRepository<'_> {
+        repositories::SessionRepository::new(&self.conn)
+    }
+
     // =========================================================================
     // API Key Storage (delegation wrappers for backward compatibility)
     // =========================================================================
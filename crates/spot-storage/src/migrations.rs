@@ -29,6 +29,14 @@ pub fn run_migrations(conn: &Connection) -> anyhow::Result<()> {
             "006_api_key_pools",
             include_str!("sql/006_api_key_pools.sql"),
         ),
+        (
+            "007_session_store",
+            include_str!("sql/007_session_store.sql"),
+        ),
+        (
+            "008_round_robin_models",
+            include_str!("sql/008_round_robin_models.sql"),
+        ),
     ];
 
     for (name, sql) in migrations {
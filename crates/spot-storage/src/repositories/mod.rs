@@ -1,7 +1,9 @@
 mod api_keys;
 mod key_pool;
+mod sessions;
 mod settings;
 
 pub use api_keys::ApiKeyRepository;
 pub use key_pool::KeyPoolRepository;
+pub use sessions::SessionRepository;
 pub use settings::SettingsRepository;
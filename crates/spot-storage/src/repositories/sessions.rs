@@ -0,0 +1,247 @@
+use rusqlite::{params, Connection, OptionalExtension};
+
+use crate::{Message, NewMessage, SessionSummary};
+
+/// Columns selected for a [`SessionSummary`], joined against `messages` for totals.
+const SUMMARY_SELECT: &str = "SELECT s.id, s.name, s.agent_name, s.model, s.description,
+            s.created_at, s.updated_at, COUNT(m.id), COALESCE(SUM(m.token_count), 0)
+     FROM sessions s
+     LEFT JOIN messages m ON m.session_id = s.id";
+
+/// Repository for saved conversations (the `sessions` and `messages` tables).
+///
+/// Each message is stored as its own row, so saving a conversation that grew
+/// by one turn only inserts the new rows.
+pub struct SessionRepository<'a> {
+    conn: &'a Connection,
+}
+
+impl<'a> SessionRepository<'a> {
+    pub fn new(conn: &'a Connection) -> Self {
+        Self { conn }
+    }
+
+    fn map_summary_row(row: &rusqlite::Row) -> Result<SessionSummary, rusqlite::Error> {
+        Ok(SessionSummary {
+            id: row.get(0)?,
+            name: row.get(1)?,
+            agent_name: row.get(2)?,
+            model: row.get(3)?,
+            description: row.get(4)?,
+            created_at: row.get(5)?,
+            updated_at: row.get(6)?,
+            message_count: row.get(7)?,
+            token_count: row.get(8)?,
+        })
+    }
+
+    /// Create or update a session and sync its messages in one transaction.
+    ///
+    /// Stored messages that match the start of `messages` are kept; anything
+    /// after the first difference is replaced.
+    pub fn save(
+        &self,
+        name: &str,
+        agent_name: &str,
+        model: &str,
+        messages: &[NewMessage],
+    ) -> Result<SessionSummary, rusqlite::Error> {
+        let now = now_millis();
+        let tx = self.conn.unchecked_transaction()?;
+
+        tx.execute(
+            "INSERT INTO sessions (name, agent_name, model, created_at, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?4)
+             ON CONFLICT(name) DO UPDATE SET
+                agent_name = excluded.agent_name,
+                model = excluded.model,
+                updated_at = excluded.updated_at",
+            params![name, agent_name, model, now],
+        )?;
+        let session_id: i64 =
+            tx.query_row("SELECT id FROM sessions WHERE name = ?", [name], |row| {
+                row.get(0)
+            })?;
+        Self::sync_messages(&tx, session_id, messages, now)?;
+
+        tx.commit()?;
+
+        self.get(name)?.ok_or(rusqlite::Error::QueryReturnedNoRows)
+    }
+
+    /// Keep the common prefix of stored messages and write the rest.
+    fn sync_messages(
+        conn: &Connection,
+        session_id: i64,
+        messages: &[NewMessage],
+        now: i64,
+    ) -> Result<(), rusqlite::Error> {
+        let existing: Vec<(i64, String)> = {
+            let mut stmt =
+                conn.prepare("SELECT id, content FROM messages WHERE session_id = ? ORDER BY id")?;
+            let rows = stmt.query_map([session_id], |row| Ok((row.get(0)?, row.get(1)?)))?;
+            rows.collect::<Result<_, _>>()?
+        };
+
+        let unchanged = existing
+            .iter()
+            .zip(messages)
+            .take_while(|((_, content), msg)| *content == msg.content)
+            .count();
+
+        if let Some((first_stale, _)) = existing.get(unchanged) {
+            conn.execute(
+                "DELETE FROM messages WHERE session_id = ? AND id >= ?",
+                params![session_id, first_stale],
+            )?;
+        }
+
+        let mut insert = conn.prepare(
+            "INSERT INTO messages (session_id, role, content, token_count, created_at)
+             VALUES (?, ?, ?, ?, ?)",
+        )?;
+        for msg in &messages[unchanged..] {
+            insert.execute(params![
+                session_id,
+                msg.role,
+                msg.content,
+                msg.token_count,
+                now
+            ])?;
+        }
+
+        Ok(())
+    }
+
+    /// Get a session by name.
+    pub fn get(&self, name: &str) -> Result<Option<SessionSummary>, rusqlite::Error> {
+        self.conn
+            .query_row(
+                &format!("{SUMMARY_SELECT} WHERE s.name = ? GROUP BY s.id"),
+                [name],
+                Self::map_summary_row,
+            )
+            .optional()
+    }
+
+    /// Check if a session exists.
+    pub fn exists(&self, name: &str) -> Result<bool, rusqlite::Error> {
+        self.conn.query_row(
+            "SELECT EXISTS(SELECT 1 FROM sessions WHERE name = ?)",
+            [name],
+            |row| row.get(0),
+        )
+    }
+
+    /// List all sessions, most recently updated first.
+    pub fn list(&self) -> Result<Vec<SessionSummary>, rusqlite::Error> {
+        let mut stmt = self.conn.prepare(&format!(
+            "{SUMMARY_SELECT} GROUP BY s.id ORDER BY s.updated_at DESC, s.id DESC"
+        ))?;
+        let rows = stmt.query_map([], Self::map_summary_row)?;
+        rows.collect()
+    }
+
+    /// Find sessions whose name or stored message content contains `query`.
+    ///
+    /// Matching is case-insensitive for ASCII and runs against the serialized
+    /// message content. Results are most recently updated first.
+    pub fn search(&self, query: &str) -> Result<Vec<SessionSummary>, rusqlite::Error> {
+        let mut stmt = self.conn.prepare(&format!(
+            "{SUMMARY_SELECT}
+             WHERE s.name LIKE ?1 ESCAPE '\\'
+                OR EXISTS (
+                    SELECT 1 FROM messages hit
+                    WHERE hit.session_id = s.id AND hit.content LIKE ?1 ESCAPE '\\'
+                )
+             GROUP BY s.id
+             ORDER BY s.updated_at DESC, s.id DESC"
+        ))?;
+        let rows = stmt.query_map([like_pattern(query)], Self::map_summary_row)?;
+        rows.collect()
+    }
+
+    /// Get the messages of a session in the order they were saved.
+    pub fn messages(&self, session_id: i64) -> Result<Vec<Message>, rusqlite::Error> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, session_id, role, content, token_count, created_at
+             FROM messages
+             WHERE session_id = ?
+             ORDER BY id ASC",
+        )?;
+        let rows = stmt.query_map([session_id], |row| {
+            Ok(Message {
+                id: row.get(0)?,
+                session_id: row.get(1)?,
+                role: row.get(2)?,
+                content: row.get(3)?,
+                token_count: row.get(4)?,
+                created_at: row.get(5)?,
+            })
+        })?;
+        rows.collect()
+    }
+
+    /// Set or clear a session's description.
+    pub fn set_description(
+        &self,
+        name: &str,
+        description: Option<&str>,
+    ) -> Result<(), rusqlite::Error> {
+        self.conn.execute(
+            "UPDATE sessions SET description = ? WHERE name = ?",
+            params![description, name],
+        )?;
+        Ok(())
+    }
+
+    /// Overwrite a session's timestamps (Unix milliseconds).
+    ///
+    /// Used when importing sessions that were saved elsewhere.
+    pub fn set_timestamps(
+        &self,
+        name: &str,
+        created_at: i64,
+        updated_at: i64,
+    ) -> Result<(), rusqlite::Error> {
+        self.conn.execute(
+            "UPDATE sessions SET created_at = ?, updated_at = ? WHERE name = ?",
+            params![created_at, updated_at, name],
+        )?;
+        Ok(())
+    }
+
+    /// Delete a session and its messages.
+    /// Returns true if a session was deleted.
+    pub fn delete(&self, name: &str) -> Result<bool, rusqlite::Error> {
+        let deleted = self
+            .conn
+            .execute("DELETE FROM sessions WHERE name = ?", [name])?;
+        Ok(deleted > 0)
+    }
+
+    /// Delete all but the `keep` most recently updated sessions.
+    /// Returns the number of sessions deleted.
+    pub fn prune(&self, keep: usize) -> Result<usize, rusqlite::Error> {
+        self.conn.execute(
+            "DELETE FROM sessions WHERE id NOT IN (
+                SELECT id FROM sessions ORDER BY updated_at DESC, id DESC LIMIT ?
+             )",
+            [keep as i64],
+        )
+    }
+}
+
+/// Current time as Unix milliseconds.
+fn now_millis() -> i64 {
+    chrono::Utc::now().timestamp_millis()
+}
+
+/// Build a `LIKE` pattern matching `query` anywhere, with wildcards escaped.
+fn like_pattern(query: &str) -> String {
+    let escaped = query
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_");
+    format!("%{}%", escaped)
+}
@@ -32,6 +32,30 @@ pub struct Message {
     pub created_at: i64,
 }
 
+/// A session with its metadata and message totals, as returned by listings.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionSummary {
+    pub id: i64,
+    pub name: String,
+    pub agent_name: String,
+    pub model: String,
+    pub description: Option<String>,
+    /// Unix timestamp in milliseconds.
+    pub created_at: i64,
+    /// Unix timestamp in milliseconds.
+    pub updated_at: i64,
+    pub message_count: i64,
+    pub token_count: i64,
+}
+
+/// A message to be written to a session.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NewMessage {
+    pub role: String,
+    pub content: String,
+    pub token_count: Option<i64>,
+}
+
 /// Stored OAuth tokens.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OAuthTokens {
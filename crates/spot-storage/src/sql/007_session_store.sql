@@ -0,0 +1,8 @@
+-- Session store metadata for saved conversations
+-- Rows written by SessionRepository use Unix milliseconds for created_at/updated_at
+-- so consecutive saves within the same second still order correctly.
+ALTER TABLE sessions ADD COLUMN model TEXT NOT NULL DEFAULT '';
+ALTER TABLE sessions ADD COLUMN description TEXT;
+
+-- Index for most-recent-first listing
+CREATE INDEX IF NOT EXISTS idx_sessions_updated ON sessions(updated_at);
@@ -0,0 +1,3 @@
+-- Member list for round-robin models
+-- JSON array of model names; NULL for every other model type.
+ALTER TABLE models ADD COLUMN round_robin_models TEXT;
@@ -4,10 +4,11 @@
 //! - Database creation, migration, and CRUD operations
 //! - PoolKey operations
 //! - Settings repository
+//! - Session repository
 //! - Concurrent access patterns
 //! - Cross-table isolation
 
-use spot_storage::Database;
+use spot_storage::{Database, NewMessage};
 use tempfile::TempDir;
 
 fn setup() -> (TempDir, Database) {
@@ -344,3 +345,206 @@ fn test_settings_repo_accessor() {
     assert_eq!(val1, Some("val1".to_string()));
     assert_eq!(val2, Some("val2".to_string()));
 }
+
+// =========================================================================
+// Session Repository Tests
+// =========================================================================
+
+fn message(role: &str, content: &str) -> NewMessage {
+    NewMessage {
+        role: role.to_string(),
+        content: content.to_string(),
+        token_count: Some(content.len() as i64 / 4),
+    }
+}
+
+#[test]
+fn test_session_save_and_load_messages() {
+    let (_tmp, db) = setup();
+    let messages = vec![
+        message("user", "first question"),
+        message("assistant", "first answer"),
+    ];
+
+    let summary = db
+        .sessions()
+        .save("chat-1", "spot", "gpt-4o", &messages)
+        .unwrap();
+    assert_eq!(summary.name, "chat-1");
+    assert_eq!(summary.agent_name, "spot");
+    assert_eq!(summary.model, "gpt-4o");
+    assert_eq!(summary.message_count, 2);
+    assert_eq!(summary.token_count, 3 + 3);
+
+    let stored = db.sessions().messages(summary.id).unwrap();
+    let contents: Vec<_> = stored.iter().map(|m| m.content.as_str()).collect();
+    assert_eq!(contents, vec!["first question", "first answer"]);
+    assert_eq!(stored[0].role, "user");
+}
+
+#[test]
+fn test_session_save_appends_only_new_messages() {
+    let (_tmp, db) = setup();
+    let mut messages = vec![message("user", "hello")];
+    let first = db.sessions().save("chat", "spot", "m", &messages).unwrap();
+    let first_row = db.sessions().messages(first.id).unwrap()[0].id;
+
+    messages.push(message("assistant", "hi there"));
+    let second = db.sessions().save("chat", "spot", "m", &messages).unwrap();
+
+    assert_eq!(first.id, second.id);
+    let stored = db.sessions().messages(second.id).unwrap();
+    assert_eq!(stored.len(), 2);
+    // The unchanged message keeps its row
+    assert_eq!(stored[0].id, first_row);
+}
+
+#[test]
+fn test_session_save_replaces_diverging_messages() {
+    let (_tmp, db) = setup();
+    let original = vec![
+        message("user", "a"),
+        message("assistant", "b"),
+        message("user", "c"),
+    ];
+    db.sessions().save("chat", "spot", "m", &original).unwrap();
+
+    // e.g. history was compacted
+    let compacted = vec![message("user", "a"), message("assistant", "summary")];
+    let summary = db.sessions().save("chat", "spot", "m", &compacted).unwrap();
+
+    assert_eq!(summary.message_count, 2);
+    let contents: Vec<_> = db
+        .sessions()
+        .messages(summary.id)
+        .unwrap()
+        .into_iter()
+        .map(|m| m.content)
+        .collect();
+    assert_eq!(contents, vec!["a", "summary"]);
+}
+
+#[test]
+fn test_session_save_updates_metadata_and_keeps_created_at() {
+    let (_tmp, db) = setup();
+    let first = db.sessions().save("chat", "agent1", "model1", &[]).unwrap();
+    std::thread::sleep(std::time::Duration::from_millis(5));
+    let second = db.sessions().save("chat", "agent2", "model2", &[]).unwrap();
+
+    assert_eq!(second.agent_name, "agent2");
+    assert_eq!(second.model, "model2");
+    assert_eq!(second.created_at, first.created_at);
+    assert!(second.updated_at > first.updated_at);
+}
+
+#[test]
+fn test_session_list_most_recent_first() {
+    let (_tmp, db) = setup();
+    db.sessions().save("old", "spot", "m", &[]).unwrap();
+    std::thread::sleep(std::time::Duration::from_millis(5));
+    db.sessions().save("new", "spot", "m", &[]).unwrap();
+
+    let names: Vec<_> = db
+        .sessions()
+        .list()
+        .unwrap()
+        .into_iter()
+        .map(|s| s.name)
+        .collect();
+    assert_eq!(names, vec!["new", "old"]);
+}
+
+#[test]
+fn test_session_search_matches_name_and_content() {
+    let (_tmp, db) = setup();
+    db.sessions()
+        .save("refactor-parser", "spot", "m", &[message("user", "hello")])
+        .unwrap();
+    db.sessions()
+        .save(
+            "chat-1",
+            "spot",
+            "m",
+            &[message("user", "why does the Parser panic?")],
+        )
+        .unwrap();
+    db.sessions()
+        .save("chat-2", "spot", "m", &[message("user", "unrelated")])
+        .unwrap();
+
+    let mut names: Vec<_> = db
+        .sessions()
+        .search("parser")
+        .unwrap()
+        .into_iter()
+        .map(|s| s.name)
+        .collect();
+    names.sort();
+    assert_eq!(names, vec!["chat-1", "refactor-parser"]);
+}
+
+#[test]
+fn test_session_search_escapes_wildcards() {
+    let (_tmp, db) = setup();
+    db.sessions()
+        .save("chat", "spot", "m", &[message("user", "100% done")])
+        .unwrap();
+    db.sessions()
+        .save("other", "spot", "m", &[message("user", "100 items")])
+        .unwrap();
+
+    let results = db.sessions().search("100%").unwrap();
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].name, "chat");
+}
+
+#[test]
+fn test_session_delete_cascades_messages() {
+    let (_tmp, db) = setup();
+    let summary = db
+        .sessions()
+        .save("chat", "spot", "m", &[message("user", "hi")])
+        .unwrap();
+
+    assert!(db.sessions().delete("chat").unwrap());
+    assert!(!db.sessions().exists("chat").unwrap());
+    assert!(db.sessions().messages(summary.id).unwrap().is_empty());
+    assert!(!db.sessions().delete("chat").unwrap());
+}
+
+#[test]
+fn test_session_prune_keeps_most_recent() {
+    let (_tmp, db) = setup();
+    for i in 1..=4 {
+        db.sessions()
+            .save(&format!("session-{}", i), "spot", "m", &[])
+            .unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(2));
+    }
+
+    assert_eq!(db.sessions().prune(2).unwrap(), 2);
+    let names: Vec<_> = db
+        .sessions()
+        .list()
+        .unwrap()
+        .into_iter()
+        .map(|s| s.name)
+        .collect();
+    assert_eq!(names, vec!["session-4", "session-3"]);
+}
+
+#[test]
+fn test_session_description_and_timestamps() {
+    let (_tmp, db) = setup();
+    db.sessions().save("chat", "spot", "m", &[]).unwrap();
+    db.sessions()
+        .set_description("chat", Some("Parser work"))
+        .unwrap();
+    db.sessions().set_timestamps("chat", 1_000, 2_000).unwrap();
+
+    let summary = db.sessions().get("chat").unwrap().unwrap();
+    assert_eq!(summary.description.as_deref(), Some("Parser work"));
+    assert_eq!(summary.created_at, 1_000);
+    assert_eq!(summary.updated_at, 2_000);
+    assert!(db.sessions().get("missing").unwrap().is_none());
+}
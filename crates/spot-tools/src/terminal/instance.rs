@@ -12,14 +12,12 @@ use tokio::sync::mpsc;
 use tracing::warn;
 
 use super::pty::{headless_env, interactive_env, spawn_pty, PtyConfig, PtyEvent, SpawnedPty};
+use super::store::{trim_output_start, MAX_OUTPUT_CHARS};
 use super::types::{
     CellContent, CursorState, IndexedCell, ProcessKind, ProcessSnapshot, TerminalContent,
     TerminalEventBridge, TerminalSize,
 };
 
-/// Maximum output buffer size (50KB, ~15K tokens)
-const MAX_OUTPUT_CHARS: usize = 50_000;
-
 /// Terminal instance that manages PTY, emulation, and state
 pub struct Terminal {
     /// Process ID for this terminal
@@ -38,6 +36,8 @@ pub struct Terminal {
     last_content: TerminalContent,
     /// Raw output buffer (for tool response)
     output_buffer: String,
+    /// Bytes of output received, including those trimmed from the buffer
+    output_total: usize,
     /// Exit code (if process has finished)
     exit_code: Option<i32>,
     /// Whether terminal is visible in UI
@@ -115,6 +115,7 @@ impl Terminal {
             resize_tx: spawned.resize_tx,
             last_content: TerminalContent::default(),
             output_buffer: String::new(),
+            output_total: 0,
             exit_code: None,
             visible: true,
             started_at_ms: now_ms,
@@ -128,11 +129,8 @@ impl Terminal {
         // Append to raw output buffer (truncate if needed)
         let text = String::from_utf8_lossy(bytes);
         self.output_buffer.push_str(&text);
-        if self.output_buffer.len() > MAX_OUTPUT_CHARS {
-            // Keep the last MAX_OUTPUT_CHARS
-            let start = self.output_buffer.len() - MAX_OUTPUT_CHARS;
-            self.output_buffer = self.output_buffer[start..].to_string();
-        }
+        self.output_total += text.len();
+        trim_output_start(&mut self.output_buffer, MAX_OUTPUT_CHARS);
 
         // Process through VTE for terminal emulation
         let mut term = self.term.lock();
@@ -237,6 +235,7 @@ impl Terminal {
             kind: self.kind,
             visible: self.visible,
             output: self.output_buffer.clone(),
+            output_total: self.output_total,
             exit_code: self.exit_code,
             started_at_ms: self.started_at_ms,
             finished_at_ms: self.exit_code.map(|_| {
@@ -5,6 +5,7 @@
 
 mod instance;
 mod pty;
+mod runner;
 mod security;
 mod store;
 mod types;
@@ -13,6 +14,10 @@ pub use instance::Terminal;
 pub use pty::{
     headless_env, interactive_env, spawn_pty, spawn_user_shell, PtyConfig, PtyEvent, SpawnedPty,
 };
-pub use security::{validate_command, CommandValidation, RiskLevel, SAFE_COMMAND_PREFIXES};
+pub use runner::spawn_llm_command;
+pub use security::{
+    has_shell_metacharacters, split_shell_segments, validate_command, CommandValidation, RiskLevel,
+    SAFE_COMMAND_PREFIXES,
+};
 pub use store::*;
 pub use types::*;
@@ -5,7 +5,7 @@ use std::io::{Read, Write};
 use std::path::PathBuf;
 use std::thread;
 
-use portable_pty::{native_pty_system, CommandBuilder, PtySize};
+use portable_pty::{native_pty_system, ChildKiller, CommandBuilder, PtySize};
 use tokio::sync::mpsc;
 use tracing::{debug, error, warn};
 
@@ -126,6 +126,8 @@ pub struct SpawnedPty {
     pub output_rx: mpsc::UnboundedReceiver<PtyEvent>,
     /// Handle to resize the PTY
     pub resize_tx: mpsc::UnboundedSender<PtySize>,
+    /// Handle to kill the child process
+    pub killer: Box<dyn ChildKiller + Send + Sync>,
 }
 
 /// Events from the PTY
@@ -177,6 +179,7 @@ pub fn spawn_pty(config: PtyConfig) -> Result<SpawnedPty, String> {
         .slave
         .spawn_command(cmd)
         .map_err(|e| format!("Failed to spawn command: {}", e))?;
+    let killer = child.clone_killer();
 
     // Get reader and writer handles
     let reader = pair
@@ -237,6 +240,7 @@ pub fn spawn_pty(config: PtyConfig) -> Result<SpawnedPty, String> {
         writer_tx,
         output_rx,
         resize_tx,
+        killer,
     })
 }
 
@@ -306,6 +310,7 @@ pub fn spawn_user_shell(cwd: Option<PathBuf>, size: PtySize) -> Result<SpawnedPt
         .slave
         .spawn_command(cmd)
         .map_err(|e| format!("Failed to spawn shell: {}", e))?;
+    let killer = child.clone_killer();
 
     let reader = pair
         .master
@@ -355,5 +360,6 @@ pub fn spawn_user_shell(cwd: Option<PathBuf>, size: PtySize) -> Result<SpawnedPt
         writer_tx,
         output_rx,
         resize_tx,
+        killer,
     })
 }
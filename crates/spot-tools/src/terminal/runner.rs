@@ -0,0 +1,93 @@
+//! Headless runner for LLM-requested shell commands.
+//!
+//! Used by frontends that don't embed a terminal view (TUI, headless mode):
+//! the command runs in a PTY and its output is collected into the
+//! [`SystemExecStore`] so the shell tools can read it.
+
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use tracing::{debug, error, info};
+
+use super::pty::{headless_env, spawn_pty, PtyConfig, PtyEvent};
+use super::store::SystemExecStore;
+use super::types::{ProcessKind, ProcessSnapshot};
+
+/// Register `process_id` in the store and run `command` in a headless PTY.
+///
+/// The snapshot exists when this returns, so callers can respond with
+/// `SystemExecResponse::Started` right after. Spawn failures are recorded as
+/// the process output with exit code -1. Must be called within a Tokio runtime.
+pub fn spawn_llm_command(
+    store: Arc<SystemExecStore>,
+    process_id: String,
+    command: String,
+    cwd: Option<String>,
+) {
+    let now_ms = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64;
+    store.upsert_process(ProcessSnapshot {
+        process_id: process_id.clone(),
+        name: None,
+        kind: ProcessKind::Llm,
+        visible: true,
+        output: String::new(),
+        output_total: 0,
+        exit_code: None,
+        started_at_ms: now_ms,
+        finished_at_ms: None,
+    });
+
+    let config = PtyConfig {
+        command: command.clone(),
+        cwd: cwd.map(PathBuf::from),
+        env: headless_env(),
+        ..Default::default()
+    };
+
+    let spawned = match spawn_pty(config) {
+        Ok(spawned) => spawned,
+        Err(e) => {
+            error!(process_id = %process_id, error = %e, "Failed to spawn terminal");
+            store.set_output(&process_id, format!("Failed to spawn: {}", e));
+            store.mark_finished(&process_id, Some(-1));
+            return;
+        }
+    };
+    info!(process_id = %process_id, command = %command, "Terminal spawned");
+    store.register_killer(&process_id, spawned.killer);
+    store.register_writer(&process_id, spawned.writer_tx.clone());
+
+    tokio::spawn(async move {
+        // Keep the writer alive so the PTY isn't closed under the process
+        let _writer_tx = spawned.writer_tx;
+        let mut output_rx = spawned.output_rx;
+
+        while let Some(event) = output_rx.recv().await {
+            match event {
+                PtyEvent::Output(bytes) => {
+                    let text = String::from_utf8_lossy(&bytes);
+                    if !store.append_output(&process_id, &text) {
+                        // Killed on request - stop collecting output
+                        break;
+                    }
+                }
+                PtyEvent::Exit(code) => {
+                    debug!(process_id = %process_id, exit_code = ?code, "Terminal exited");
+                    store.mark_finished(&process_id, code);
+                    store.set_visible(&process_id, false);
+                    break;
+                }
+                PtyEvent::Error(e) => {
+                    error!(process_id = %process_id, error = %e, "Terminal error");
+                    store.set_output(&process_id, format!("Error: {}", e));
+                    store.mark_finished(&process_id, Some(-1));
+                    break;
+                }
+            }
+        }
+    });
+}
@@ -4,6 +4,7 @@
 //! patterns and classify risk levels for the approval gate.
 
 use regex::Regex;
+use serde::{Deserialize, Serialize};
 use std::sync::LazyLock;
 
 /// Dangerous command patterns that require extra scrutiny
@@ -135,6 +136,29 @@ pub const SAFE_COMMAND_PREFIXES: &[&str] = &[
     "go run",
 ];
 
+/// Whether `command` chains, pipes, substitutes or redirects commands
+/// (`;`, `&`, `&&`, `||`, `|`, `$(`, backticks, `>`, `<` or a newline).
+///
+/// Such a command does more than its first word suggests, so it is never
+/// known-safe.
+pub fn has_shell_metacharacters(command: &str) -> bool {
+    command.contains(|c| matches!(c, ';' | '&' | '|' | '`' | '>' | '<' | '\n'))
+        || command.contains("$(")
+}
+
+/// Split a command at shell metacharacters into the commands it runs.
+///
+/// The split is purely textual (quotes are not parsed), so a segment may be
+/// a redirect target or part of a quoted string; it errs towards more
+/// segments, never fewer.
+pub fn split_shell_segments(command: &str) -> Vec<&str> {
+    command
+        .split(|c| matches!(c, ';' | '&' | '|' | '`' | '>' | '<' | '\n' | '(' | ')'))
+        .map(str::trim)
+        .filter(|segment| !segment.is_empty())
+        .collect()
+}
+
 /// Validate a command and return security assessment
 pub fn validate_command(command: &str) -> CommandValidation {
     let trimmed = command.trim();
@@ -147,10 +171,11 @@ pub fn validate_command(command: &str) -> CommandValidation {
         }
     }
 
-    // Check if it's a known-safe prefix
-    let is_known_safe = SAFE_COMMAND_PREFIXES
-        .iter()
-        .any(|prefix| trimmed.starts_with(prefix) || trimmed == prefix.trim());
+    // Check if it's a known-safe prefix (chained commands never are)
+    let is_known_safe = !has_shell_metacharacters(trimmed)
+        && SAFE_COMMAND_PREFIXES
+            .iter()
+            .any(|prefix| trimmed.starts_with(prefix) || trimmed == prefix.trim());
 
     // Determine risk level
     let risk_level = if !warnings.is_empty() {
@@ -199,13 +224,14 @@ impl CommandValidation {
 }
 
 /// Risk level classification for commands
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum RiskLevel {
-    /// Known safe command, can auto-approve in YOLO mode
+    /// Known safe command
     Low,
     /// Unknown command, requires approval
     Medium,
-    /// Dangerous patterns detected, show warnings even in YOLO mode
+    /// Dangerous patterns detected, denied even in YOLO mode
     High,
 }
 
@@ -280,6 +306,60 @@ mod tests {
         }
     }
 
+    /// A known-safe command chained with another by each metacharacter.
+    const CHAINED_CMDS: &[&str] = &[
+        "ls; ./deploy.sh",
+        "ls && ./deploy.sh",
+        "ls || ./deploy.sh",
+        "ls | ./deploy.sh",
+        "ls & ./deploy.sh",
+        "echo $(./deploy.sh)",
+        "echo `./deploy.sh`",
+        "echo hi > ~/.bashrc",
+        "cat < ~/.ssh/id_rsa",
+        "ls\n./deploy.sh",
+    ];
+
+    #[test]
+    fn test_chained_commands_are_not_low_risk() {
+        for cmd in CHAINED_CMDS {
+            assert!(
+                has_shell_metacharacters(cmd),
+                "'{}' has metacharacters",
+                cmd
+            );
+            let validation = validate_command(cmd);
+            assert!(!validation.is_known_safe, "'{}' is not known-safe", cmd);
+            assert_eq!(
+                validation.risk_level,
+                RiskLevel::Medium,
+                "Command '{}' should be Medium risk",
+                cmd
+            );
+        }
+        assert!(!has_shell_metacharacters("cargo test --release"));
+    }
+
+    #[test]
+    fn test_split_shell_segments() {
+        for cmd in CHAINED_CMDS {
+            let segments = split_shell_segments(cmd);
+            assert!(
+                segments
+                    .iter()
+                    .any(|s| s.starts_with("./deploy.sh") || s.starts_with('~')),
+                "'{}' split into {:?}",
+                cmd,
+                segments
+            );
+        }
+        assert_eq!(
+            split_shell_segments("cargo build && git push origin"),
+            vec!["cargo build", "git push origin"]
+        );
+        assert_eq!(split_shell_segments("cargo test"), vec!["cargo test"]);
+    }
+
     #[test]
     fn test_validation_fields() {
         let validation = validate_command("sudo rm -rf /");
@@ -5,13 +5,34 @@ use std::sync::Arc;
 use std::sync::Mutex;
 use std::time::{SystemTime, UNIX_EPOCH};
 
-use tokio::sync::{oneshot, Notify};
+use portable_pty::ChildKiller;
+use tokio::sync::{mpsc, oneshot, Notify};
+use tracing::warn;
 
 use super::types::{ProcessSnapshot, SystemExecResponse};
 
 #[cfg(test)]
 use super::types::ProcessKind;
 
+/// Exit code recorded for processes killed on request (SIGKILL = 137)
+pub const KILLED_EXIT_CODE: i32 = 137;
+
+/// Maximum output kept per process (50KB, ~15K tokens)
+pub(crate) const MAX_OUTPUT_CHARS: usize = 50_000;
+
+/// Drop the start of `output` so at most `max` bytes remain, cutting at a
+/// char boundary.
+pub(crate) fn trim_output_start(output: &mut String, max: usize) {
+    if output.len() <= max {
+        return;
+    }
+    let mut start = output.len() - max;
+    while !output.is_char_boundary(start) {
+        start += 1;
+    }
+    output.drain(..start);
+}
+
 /// Entry in the process store
 struct ProcessEntry {
     snapshot: ProcessSnapshot,
@@ -23,6 +44,8 @@ struct ProcessEntry {
 struct Inner {
     processes: HashMap<String, ProcessEntry>,
     pending: HashMap<u64, oneshot::Sender<SystemExecResponse>>,
+    killers: HashMap<String, Box<dyn ChildKiller + Send + Sync>>,
+    writers: HashMap<String, mpsc::UnboundedSender<Vec<u8>>>,
 }
 
 /// Store for tracking all terminal processes
@@ -84,24 +107,109 @@ impl SystemExecStore {
         notify.notify_waiters();
     }
 
-    /// Update output for a process
-    pub fn set_output(&self, process_id: &str, output: String) {
+    /// Replace the output of a process
+    ///
+    /// The new output counts as written after the old one, so earlier
+    /// offsets don't point into it.
+    pub fn set_output(&self, process_id: &str, mut output: String) {
         let notify = {
             let mut inner = self.inner.lock().unwrap_or_else(|e| e.into_inner());
             let Some(entry) = inner.processes.get_mut(process_id) else {
                 return;
             };
+            entry.snapshot.output_total += output.len();
+            trim_output_start(&mut output, MAX_OUTPUT_CHARS);
             entry.snapshot.output = output;
             entry.notify.clone()
         };
         notify.notify_waiters();
     }
 
+    /// Append output to a running process, dropping the oldest output past
+    /// 50KB
+    ///
+    /// Returns `false`, without appending, once the process has finished or
+    /// been removed.
+    pub fn append_output(&self, process_id: &str, text: &str) -> bool {
+        let notify = {
+            let mut inner = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+            let Some(entry) = inner.processes.get_mut(process_id) else {
+                return false;
+            };
+            if entry.snapshot.exit_code.is_some() {
+                return false;
+            }
+            let snapshot = &mut entry.snapshot;
+            snapshot.output.push_str(text);
+            snapshot.output_total += text.len();
+            trim_output_start(&mut snapshot.output, MAX_OUTPUT_CHARS);
+            entry.notify.clone()
+        };
+        notify.notify_waiters();
+        true
+    }
+
+    /// Register the handle used by [`kill`](Self::kill) to stop a process
+    pub fn register_killer(&self, process_id: &str, killer: Box<dyn ChildKiller + Send + Sync>) {
+        let mut inner = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+        inner.killers.insert(process_id.to_string(), killer);
+    }
+
+    /// Register the PTY input channel used by [`write_input`](Self::write_input)
+    pub fn register_writer(&self, process_id: &str, writer_tx: mpsc::UnboundedSender<Vec<u8>>) {
+        let mut inner = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+        inner.writers.insert(process_id.to_string(), writer_tx);
+    }
+
+    /// Send raw bytes to a running process's terminal input
+    ///
+    /// Fails if the process has exited or has no registered writer.
+    pub fn write_input(&self, process_id: &str, data: Vec<u8>) -> Result<(), String> {
+        let inner = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+        let Some(entry) = inner.processes.get(process_id) else {
+            return Err(format!("Unknown process: {}", process_id));
+        };
+        if entry.snapshot.exit_code.is_some() {
+            return Err(format!("Process {} has already exited", process_id));
+        }
+        let Some(writer) = inner.writers.get(process_id) else {
+            return Err(format!("Process {} does not accept input", process_id));
+        };
+        writer
+            .send(data)
+            .map_err(|_| format!("Input channel for {} is closed", process_id))
+    }
+
+    /// Kill a running process and mark it finished with [`KILLED_EXIT_CODE`]
+    ///
+    /// Processes without a registered killer are only marked finished.
+    pub fn kill(&self, process_id: &str) {
+        let killer = {
+            let mut inner = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+            if inner
+                .processes
+                .get(process_id)
+                .is_some_and(|p| p.snapshot.exit_code.is_some())
+            {
+                return;
+            }
+            inner.killers.remove(process_id)
+        };
+        if let Some(mut killer) = killer {
+            if let Err(e) = killer.kill() {
+                warn!(process_id = %process_id, error = %e, "Failed to kill process");
+            }
+        }
+        self.mark_finished(process_id, Some(KILLED_EXIT_CODE));
+    }
+
     /// Mark a process as finished
     pub fn mark_finished(&self, process_id: &str, exit_code: Option<i32>) {
         let now_ms = now_ms();
         let notify = {
             let mut inner = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+            inner.killers.remove(process_id);
+            inner.writers.remove(process_id);
             let Some(entry) = inner.processes.get_mut(process_id) else {
                 return;
             };
@@ -129,6 +237,8 @@ impl SystemExecStore {
     pub fn remove_process(&self, process_id: &str) {
         let mut inner = self.inner.lock().unwrap_or_else(|e| e.into_inner());
         inner.processes.remove(process_id);
+        inner.killers.remove(process_id);
+        inner.writers.remove(process_id);
     }
 
     /// List all visible process IDs
@@ -219,6 +329,7 @@ mod tests {
             kind: ProcessKind::Llm,
             visible: true,
             output: String::new(),
+            output_total: 0,
             exit_code: None,
             started_at_ms: 1000,
             finished_at_ms: None,
@@ -232,8 +343,36 @@ mod tests {
         store.set_output("test-1", "Hello, World!".to_string());
         assert_eq!(store.output("test-1"), Some("Hello, World!".to_string()));
 
+        assert!(store.append_output("test-1", " Bye"));
+        let snapshot = store.snapshot("test-1").unwrap();
+        assert_eq!(snapshot.output, "Hello, World! Bye");
+        assert_eq!(snapshot.output_total, 17);
+
         store.mark_finished("test-1", Some(0));
         assert_eq!(store.exit_code("test-1"), Some(Some(0)));
+        assert!(!store.append_output("test-1", "late"));
+    }
+
+    #[test]
+    fn test_store_output_is_trimmed_with_stable_offsets() {
+        let store = SystemExecStore::new();
+        store.upsert_process(llm_process("test-6", 1000));
+
+        let chunk = "é".repeat(1000);
+        for _ in 0..30 {
+            assert!(store.append_output("test-6", &chunk));
+        }
+
+        let snapshot = store.snapshot("test-6").unwrap();
+        assert_eq!(snapshot.output_total, 60_000);
+        assert_eq!(snapshot.output.len(), MAX_OUTPUT_CHARS);
+        assert_eq!(snapshot.output_start(), 10_000);
+        assert!(snapshot.output.chars().all(|c| c == 'é'));
+
+        // Trimming never splits a multi-byte char
+        let mut output = "aé".to_string();
+        trim_output_start(&mut output, 2);
+        assert_eq!(output, "é");
     }
 
     #[test]
@@ -246,6 +385,7 @@ mod tests {
             kind: ProcessKind::User,
             visible: true,
             output: String::new(),
+            output_total: 0,
             exit_code: None,
             started_at_ms: 2000,
             finished_at_ms: None,
@@ -257,4 +397,50 @@ mod tests {
         store.set_visible("test-2", false);
         assert_eq!(store.list_visible().len(), 0);
     }
+
+    fn llm_process(process_id: &str, started_at_ms: u64) -> ProcessSnapshot {
+        ProcessSnapshot {
+            process_id: process_id.to_string(),
+            name: None,
+            kind: ProcessKind::Llm,
+            visible: true,
+            output: String::new(),
+            output_total: 0,
+            exit_code: None,
+            started_at_ms,
+            finished_at_ms: None,
+        }
+    }
+
+    #[test]
+    fn test_store_kill_marks_finished() {
+        let store = SystemExecStore::new();
+        store.upsert_process(llm_process("test-3", 1000));
+
+        store.kill("test-3");
+        assert_eq!(store.exit_code("test-3"), Some(Some(KILLED_EXIT_CODE)));
+
+        // Killing a finished process keeps its exit code
+        store.upsert_process(llm_process("test-4", 1000));
+        store.mark_finished("test-4", Some(0));
+        store.kill("test-4");
+        assert_eq!(store.exit_code("test-4"), Some(Some(0)));
+    }
+
+    #[test]
+    fn test_store_write_input() {
+        let store = SystemExecStore::new();
+        store.upsert_process(llm_process("test-5", 1000));
+        assert!(store.write_input("test-5", b"x".to_vec()).is_err());
+
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        store.register_writer("test-5", tx);
+        store.write_input("test-5", b"hello\r".to_vec()).unwrap();
+        assert_eq!(rx.try_recv().unwrap(), b"hello\r".to_vec());
+
+        // Finished processes drop their writer
+        store.mark_finished("test-5", Some(0));
+        assert!(store.write_input("test-5", b"x".to_vec()).is_err());
+        assert!(store.write_input("missing", b"x".to_vec()).is_err());
+    }
 }
@@ -25,12 +25,26 @@ pub struct ProcessSnapshot {
     pub name: Option<String>, // User-friendly name for the terminal
     pub kind: ProcessKind,
     pub visible: bool,
+    /// The most recent output; older output is dropped past a size limit
     pub output: String,
+    /// Bytes of output written since the process started, including dropped ones
+    #[serde(default)]
+    pub output_total: usize,
     pub exit_code: Option<i32>,
     pub started_at_ms: u64,
     pub finished_at_ms: Option<u64>,
 }
 
+impl ProcessSnapshot {
+    /// Output offset at which `output` starts.
+    ///
+    /// Offsets count bytes from the start of the process, so they stay valid
+    /// when the start of `output` is dropped.
+    pub fn output_start(&self) -> usize {
+        self.output_total.saturating_sub(self.output.len())
+    }
+}
+
 /// Request from tool to UI to execute a shell command
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum SystemExecRequest {
@@ -43,14 +57,69 @@ pub enum SystemExecRequest {
         request_id: u64,
         process_id: String,
     },
+    /// Ask before a file tool writes or deletes a file
+    ApproveFileChange {
+        request_id: u64,
+        change: ProposedFileChange,
+    },
+    /// Ask before typing input into a running process
+    ApproveProcessInput {
+        request_id: u64,
+        process_id: String,
+        input: String,
+    },
 }
 
 /// Response from UI to tool about execution status
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum SystemExecResponse {
-    Started { process_id: String },
-    Killed { process_id: String },
-    Error { message: String },
+    Started {
+        process_id: String,
+    },
+    Killed {
+        process_id: String,
+    },
+    /// The file change may be applied; `edited` replaces the proposed
+    /// content when the user changed it
+    FileChangeApproved {
+        edited: Option<String>,
+    },
+    /// The input may be written to the process
+    InputApproved,
+    Error {
+        message: String,
+    },
+}
+
+/// Kind of change a file tool wants to make
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FileOperation {
+    /// Create or overwrite a file
+    Write,
+    /// Delete a file
+    Delete,
+}
+
+/// A file write or delete awaiting approval
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProposedFileChange {
+    /// Path as given to the tool
+    pub path: String,
+    /// Content to write; `None` deletes the file
+    pub content: Option<String>,
+    /// Unified diff against the current file, for review
+    pub diff: String,
+}
+
+impl ProposedFileChange {
+    /// Whether this writes or deletes the file.
+    pub fn operation(&self) -> FileOperation {
+        match self.content {
+            Some(_) => FileOperation::Write,
+            None => FileOperation::Delete,
+        }
+    }
 }
 
 /// Bridge from alacritty's event system to our async runtime
@@ -1,52 +1,126 @@
 //! Common utilities for tools.
 
+use std::path::Path;
+
+use ignore::overrides::OverrideBuilder;
+use ignore::WalkBuilder;
 use serde::de::DeserializeOwned;
+use serde::{Deserialize, Deserializer};
 use serde_json::Value as JsonValue;
 use serdes_ai_tools::ToolError;
-use tracing::debug;
+use tracing::{debug, warn};
+
+use super::tool_context::get_global_context;
+
+/// Version-control directories that are never walked, even when ignored
+/// files are requested.
+pub static VCS_DIRS: &[&str] = &[".git", ".svn", ".hg"];
 
-/// Directory patterns to ignore.
+/// Directories skipped by default on top of `.gitignore` rules, so that
+/// trees without one (or without a git repository) stay readable.
+///
+/// These use `.gitignore` syntax: the trailing slash matches directories
+/// only, at any depth.
 pub static IGNORE_PATTERNS: &[&str] = &[
-    // Version control
-    ".git",
-    ".svn",
-    ".hg",
     // Dependencies
-    "node_modules",
-    "vendor",
-    ".venv",
-    "venv",
-    "__pycache__",
+    "node_modules/",
+    ".venv/",
+    "venv/",
+    "__pycache__/",
     // Build outputs
-    "target",
-    "dist",
-    "build",
-    ".next",
-    ".nuxt",
+    "target/",
+    "dist/",
+    ".next/",
+    ".nuxt/",
     // IDE/Editor
-    ".idea",
-    ".vscode",
+    ".idea/",
+    ".vscode/",
     // Cache
-    ".cache",
-    ".pytest_cache",
-    ".mypy_cache",
+    ".cache/",
+    ".pytest_cache/",
+    ".mypy_cache/",
     // Package managers
-    ".npm",
-    ".yarn",
-    ".pnpm-store",
+    ".npm/",
+    ".yarn/",
+    ".pnpm-store/",
 ];
 
-/// Check if a path should be ignored.
-pub fn should_ignore(path: &str) -> bool {
-    let path_lower = path.to_lowercase();
+/// Ignore rules for walking a directory tree.
+///
+/// By default a walk honors `.gitignore` (nested ones included, with or
+/// without a git repository), `.ignore`, `.git/info/exclude` and the global
+/// git excludes file, then [`IGNORE_PATTERNS`] and any extra patterns from
+/// the user's config. Hidden files are listed unless a rule ignores them.
+#[derive(Debug, Clone, Default)]
+pub struct IgnoreRules {
+    /// Extra `.gitignore`-style patterns, relative to the walked directory.
+    pub extra_patterns: Vec<String>,
+    /// Walk ignored files too; only [`VCS_DIRS`] are still skipped.
+    pub include_ignored: bool,
+}
 
-    for pattern in IGNORE_PATTERNS {
-        if path_lower.contains(pattern) {
-            return true;
+impl IgnoreRules {
+    /// Rules with extra patterns on top of the defaults.
+    pub fn new(extra_patterns: Vec<String>) -> Self {
+        Self {
+            extra_patterns,
+            include_ignored: false,
         }
     }
 
-    false
+    /// Rules with the extra patterns of the global [`ToolContext`], if set.
+    ///
+    /// [`ToolContext`]: super::ToolContext
+    pub fn from_global_context() -> Self {
+        Self::new(
+            get_global_context()
+                .map(|ctx| ctx.ignore_patterns().to_vec())
+                .unwrap_or_default(),
+        )
+    }
+
+    /// Set whether ignored files are walked too.
+    pub fn with_include_ignored(mut self, include_ignored: bool) -> Self {
+        self.include_ignored = include_ignored;
+        self
+    }
+
+    /// Build a walker for `root` that applies these rules.
+    ///
+    /// Invalid extra patterns are skipped with a warning.
+    pub fn walker(&self, root: &Path) -> WalkBuilder {
+        let mut builder = WalkBuilder::new(root);
+        builder
+            .standard_filters(!self.include_ignored)
+            .hidden(false)
+            .require_git(false)
+            .filter_entry(|entry| {
+                let is_dir = entry.file_type().is_some_and(|ft| ft.is_dir());
+                !(is_dir && VCS_DIRS.iter().any(|vcs| entry.file_name() == *vcs))
+            });
+
+        if !self.include_ignored {
+            let mut overrides = OverrideBuilder::new(root);
+            let patterns = IGNORE_PATTERNS
+                .iter()
+                .copied()
+                .chain(self.extra_patterns.iter().map(String::as_str));
+            for pattern in patterns {
+                // Overrides whitelist by default; `!` turns a glob into an ignore
+                if let Err(e) = overrides.add(&format!("!{}", pattern)) {
+                    warn!(pattern = %pattern, error = %e, "Skipping invalid ignore pattern");
+                }
+            }
+            match overrides.build() {
+                Ok(overrides) => {
+                    builder.overrides(overrides);
+                }
+                Err(e) => warn!(error = %e, "Failed to build ignore patterns"),
+            }
+        }
+
+        builder
+    }
 }
 
 /// Get file extension.
@@ -176,6 +250,7 @@ fn coerce_value(value: &mut JsonValue, schema: &JsonValue, field_name: &str) {
             coerce_json_types(value, schema);
         }
         "array" => {
+            coerce_to_array(value, field_name);
             // Handle array items if schema specifies items
             if let (Some(items_schema), Some(arr)) = (schema.get("items"), value.as_array_mut()) {
                 for item in arr.iter_mut() {
@@ -208,6 +283,16 @@ fn coerce_to_boolean(value: &mut JsonValue, field_name: &str) {
     }
 }
 
+/// Coerces JSON-encoded string values to arrays.
+fn coerce_to_array(value: &mut JsonValue, field_name: &str) {
+    if let Some(s) = value.as_str() {
+        if let Ok(arr @ JsonValue::Array(_)) = serde_json::from_str::<JsonValue>(s) {
+            debug!(field = field_name, "Coerced string to array");
+            *value = arr;
+        }
+    }
+}
+
 /// Coerces string values to integer (i64).
 fn coerce_to_integer(value: &mut JsonValue, field_name: &str) {
     if let Some(s) = value.as_str() {
@@ -287,114 +372,171 @@ pub fn parse_tool_args_lenient<T: DeserializeOwned>(
     })
 }
 
+/// Accept a single string where a list of strings is expected.
+///
+/// Use with `#[serde(default, deserialize_with = "one_or_many")]` on
+/// `Vec<String>` argument fields.
+pub fn one_or_many<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<String>, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum OneOrMany {
+        One(String),
+        Many(Vec<String>),
+    }
+
+    Ok(match Option::<OneOrMany>::deserialize(deserializer)? {
+        Some(OneOrMany::One(value)) => vec![value],
+        Some(OneOrMany::Many(values)) => values,
+        None => Vec::new(),
+    })
+}
+
+/// Strip ANSI escape sequences and control characters from terminal output
+pub fn sanitize_terminal_output(text: &str) -> String {
+    // Strip ANSI escape sequences
+    let bytes = text.as_bytes();
+    let stripped = strip_ansi_escapes::strip(bytes);
+    let clean = String::from_utf8_lossy(&stripped);
+
+    // Also remove other control characters that could break JSON
+    clean
+        .chars()
+        .filter(|c| !c.is_control() || *c == '\n' || *c == '\t')
+        .collect()
+}
+
 #[cfg(test)]
 #[allow(clippy::approx_constant)]
 mod tests {
     use super::*;
 
     // =========================================================================
-    // IGNORE_PATTERNS Tests
+    // IgnoreRules Tests
     // =========================================================================
 
-    #[test]
-    fn test_ignore_patterns_not_empty() {
-        assert!(!IGNORE_PATTERNS.is_empty());
+    /// Walk `root` and return the relative paths of everything found.
+    fn walk(rules: &IgnoreRules, root: &Path) -> Vec<String> {
+        let mut paths: Vec<String> = rules
+            .walker(root)
+            .build()
+            .flatten()
+            .filter(|e| e.depth() > 0)
+            .map(|e| {
+                e.path()
+                    .strip_prefix(root)
+                    .unwrap()
+                    .to_string_lossy()
+                    .replace('\\', "/")
+            })
+            .collect();
+        paths.sort();
+        paths
     }
 
-    #[test]
-    fn test_ignore_patterns_contains_common_dirs() {
-        assert!(IGNORE_PATTERNS.contains(&".git"));
-        assert!(IGNORE_PATTERNS.contains(&"node_modules"));
-        assert!(IGNORE_PATTERNS.contains(&"target"));
-        assert!(IGNORE_PATTERNS.contains(&"__pycache__"));
-    }
-
-    // =========================================================================
-    // should_ignore Tests
-    // =========================================================================
-
-    #[test]
-    fn test_should_ignore_git() {
-        assert!(should_ignore(".git"));
-        assert!(should_ignore(".git/config"));
-        assert!(should_ignore("project/.git/HEAD"));
-        assert!(should_ignore("/home/user/project/.git"));
+    fn write(root: &Path, path: &str, content: &str) {
+        let path = root.join(path);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, content).unwrap();
     }
 
     #[test]
-    fn test_should_ignore_node_modules() {
-        assert!(should_ignore("node_modules"));
-        assert!(should_ignore("node_modules/lodash"));
-        assert!(should_ignore("project/node_modules/react"));
-        assert!(should_ignore("/app/node_modules/package.json"));
-    }
-
-    #[test]
-    fn test_should_ignore_target() {
-        assert!(should_ignore("target"));
-        assert!(should_ignore("target/debug"));
-        assert!(should_ignore("target/release/binary"));
-        assert!(should_ignore("project/target/debug/deps"));
+    fn test_ignore_patterns_contains_common_dirs() {
+        assert!(IGNORE_PATTERNS.contains(&"node_modules/"));
+        assert!(IGNORE_PATTERNS.contains(&"target/"));
+        assert!(IGNORE_PATTERNS.contains(&"__pycache__/"));
+        assert!(VCS_DIRS.contains(&".git"));
     }
 
     #[test]
-    fn test_should_ignore_python_cache() {
-        assert!(should_ignore("__pycache__"));
-        assert!(should_ignore("src/__pycache__/module.pyc"));
-        assert!(should_ignore(".venv"));
-        assert!(should_ignore("venv/lib/python3.9"));
-        assert!(should_ignore(".pytest_cache"));
-        assert!(should_ignore(".mypy_cache"));
+    fn test_ignore_rules_match_whole_components() {
+        let dir = tempfile::tempdir().unwrap();
+        write(dir.path(), "src/builder.rs", "");
+        write(dir.path(), "src/targeting.rs", "");
+        write(dir.path(), "vendor/lib.rs", "");
+        write(dir.path(), "node_modules/react/index.js", "");
+        write(dir.path(), "app/target/out.txt", "");
+        write(dir.path(), ".git/HEAD", "");
+
+        let paths = walk(&IgnoreRules::default(), dir.path());
+        assert_eq!(
+            paths,
+            vec![
+                "app",
+                "src",
+                "src/builder.rs",
+                "src/targeting.rs",
+                "vendor",
+                "vendor/lib.rs"
+            ]
+        );
     }
 
     #[test]
-    fn test_should_ignore_ide_dirs() {
-        assert!(should_ignore(".idea"));
-        assert!(should_ignore(".idea/workspace.xml"));
-        assert!(should_ignore(".vscode"));
-        assert!(should_ignore(".vscode/settings.json"));
+    fn test_ignore_rules_honor_nested_gitignore_and_ignore_files() {
+        let dir = tempfile::tempdir().unwrap();
+        write(dir.path(), ".gitignore", "*.log\n");
+        write(dir.path(), "pkg/.gitignore", "generated/\n");
+        write(dir.path(), "pkg/.ignore", "fixtures.json\n");
+        write(dir.path(), "pkg/generated/api.rs", "");
+        write(dir.path(), "pkg/fixtures.json", "");
+        write(dir.path(), "pkg/lib.rs", "");
+        write(dir.path(), "debug.log", "");
+        write(dir.path(), "generated/keep.rs", "");
+
+        let paths = walk(&IgnoreRules::default(), dir.path());
+        assert_eq!(
+            paths,
+            vec![
+                ".gitignore",
+                "generated",
+                "generated/keep.rs",
+                "pkg",
+                "pkg/.gitignore",
+                "pkg/.ignore",
+                "pkg/lib.rs"
+            ]
+        );
     }
 
     #[test]
-    fn test_should_ignore_build_dirs() {
-        assert!(should_ignore("dist"));
-        assert!(should_ignore("build"));
-        assert!(should_ignore(".next"));
-        assert!(should_ignore(".nuxt"));
+    fn test_ignore_rules_extra_patterns() {
+        let dir = tempfile::tempdir().unwrap();
+        write(dir.path(), "src/lib.rs", "");
+        write(dir.path(), "src/schema.generated.ts", "");
+        write(dir.path(), "snapshots/a.snap", "");
+
+        let rules = IgnoreRules::new(vec!["*.generated.ts".to_string(), "snapshots/".to_string()]);
+        assert_eq!(walk(&rules, dir.path()), vec!["src", "src/lib.rs"]);
     }
 
     #[test]
-    fn test_should_ignore_case_insensitive() {
-        // Should ignore regardless of case
-        assert!(should_ignore("NODE_MODULES"));
-        assert!(should_ignore("Node_Modules"));
-        assert!(should_ignore(".GIT"));
-        assert!(should_ignore("TARGET"));
-    }
+    fn test_ignore_rules_skip_invalid_extra_patterns() {
+        let dir = tempfile::tempdir().unwrap();
+        write(dir.path(), "a.txt", "");
+        write(dir.path(), "b.txt", "");
 
-    #[test]
-    fn test_should_not_ignore_normal_paths() {
-        assert!(!should_ignore("src"));
-        assert!(!should_ignore("lib"));
-        assert!(!should_ignore("src/main.rs"));
-        assert!(!should_ignore("package.json"));
-        assert!(!should_ignore("README.md"));
-        assert!(!should_ignore("tests/test_module.py"));
+        let rules = IgnoreRules::new(vec!["[".to_string(), "b.txt".to_string()]);
+        assert_eq!(walk(&rules, dir.path()), vec!["a.txt"]);
     }
 
     #[test]
-    fn test_should_ignore_substring_matches() {
-        // Note: The current implementation uses `contains` which matches substrings.
-        // This is by design - it's aggressive about ignoring build artifacts.
-        // Words containing patterns like "target" or "build" will be ignored.
-        assert!(should_ignore("src/targeting.rs")); // contains "target"
-        assert!(should_ignore("src/builder.rs")); // contains "build"
-        assert!(should_ignore("rebuild.sh")); // contains "build"
-
-        // These should NOT be ignored (no pattern substring)
-        assert!(!should_ignore("src/main.rs"));
-        assert!(!should_ignore("lib/utils.py"));
-        assert!(!should_ignore("README.md"));
+    fn test_ignore_rules_include_ignored_still_skips_vcs_dirs() {
+        let dir = tempfile::tempdir().unwrap();
+        write(dir.path(), ".gitignore", "*.log\n");
+        write(dir.path(), "debug.log", "");
+        write(dir.path(), "node_modules/x.js", "");
+        write(dir.path(), ".git/HEAD", "");
+
+        let rules = IgnoreRules::new(vec!["*.txt".to_string()]).with_include_ignored(true);
+        assert_eq!(
+            walk(&rules, dir.path()),
+            vec![
+                ".gitignore",
+                "debug.log",
+                "node_modules",
+                "node_modules/x.js"
+            ]
+        );
     }
 
     // =========================================================================
@@ -912,6 +1054,31 @@ mod tests {
         assert_eq!(args["items"][1]["count"], serde_json::json!(2));
     }
 
+    #[test]
+    fn test_coerce_json_string_to_array() {
+        let schema = serde_json::json!({
+            "type": "object",
+            "properties": {
+                "items": {
+                    "type": "array",
+                    "items": {
+                        "type": "object",
+                        "properties": { "count": { "type": "integer" } }
+                    }
+                }
+            }
+        });
+
+        let mut args = serde_json::json!({ "items": "[{\"count\": \"3\"}]" });
+        coerce_json_types(&mut args, &schema);
+        assert_eq!(args["items"], serde_json::json!([{ "count": 3 }]));
+
+        // Strings that aren't JSON arrays are left alone
+        let mut args = serde_json::json!({ "items": "not json" });
+        coerce_json_types(&mut args, &schema);
+        assert_eq!(args["items"], serde_json::json!("not json"));
+    }
+
     // =========================================================================
     // coerce_json_types Tests - Edge Cases
     // =========================================================================
@@ -1,6 +1,8 @@
 //! DeleteFile tool implementation.
 //!
-//! Provides a serdesAI-compatible tool for deleting files.
+//! Provides a serdesAI-compatible tool for deleting files. When a UI is
+//! attached it approves the deletion first. The result includes a diff of
+//! the removed content.
 
 use async_trait::async_trait;
 use serde::Deserialize;
@@ -9,6 +11,10 @@ use tracing::debug;
 
 use serdes_ai_tools::{RunContext, SchemaBuilder, Tool, ToolDefinition, ToolResult, ToolReturn};
 
+use super::diff::{UnifiedDiff, COMPACT_DIFF_LINES};
+use super::tool_context::get_global_context;
+use crate::terminal::ProposedFileChange;
+
 /// Tool for deleting files.
 #[derive(Debug, Clone, Default)]
 pub struct DeleteFileTool;
@@ -58,11 +64,40 @@ impl Tool for DeleteFileTool {
             )));
         }
 
+        // Binary files have no text diff
+        let previous = std::fs::read_to_string(path).ok();
+
+        // The UI applies the permission policy and may ask the user
+        if let Some(tool_ctx) = get_global_context() {
+            let change = ProposedFileChange {
+                path: args.file_path.clone(),
+                content: None,
+                diff: previous
+                    .as_deref()
+                    .map(|p| UnifiedDiff::between(&args.file_path, Some(p), None).to_string())
+                    .unwrap_or_default(),
+            };
+            if let Err(message) = tool_ctx.approve_file_change(change).await {
+                return Ok(ToolReturn::error(format!(
+                    "{} was not deleted: {}",
+                    args.file_path, message
+                )));
+            }
+        }
+
         match std::fs::remove_file(path) {
-            Ok(()) => Ok(ToolReturn::text(format!(
-                "Successfully deleted: {}",
-                args.file_path
-            ))),
+            Ok(()) => {
+                let mut text = format!("Successfully deleted: {}", args.file_path);
+                if let Some(previous) = previous {
+                    let diff = UnifiedDiff::between(&args.file_path, Some(&previous), None);
+                    text.push_str(&format!(
+                        " (-{} lines)\n\n{}",
+                        diff.removed_lines(),
+                        diff.to_compact_string(COMPACT_DIFF_LINES)
+                    ));
+                }
+                Ok(ToolReturn::text(text))
+            }
             Err(e) => Ok(ToolReturn::error(format!("Failed to delete file: {}", e))),
         }
     }
@@ -115,6 +150,9 @@ mod tests {
 
         assert!(result.is_ok());
         assert!(!file_path.exists());
+        let text = result.unwrap().as_text().unwrap().to_string();
+        assert!(text.contains("(-1 lines)"));
+        assert!(text.contains("-content"));
     }
 
     #[tokio::test]
@@ -1,5 +1,11 @@
 //! Unified diff parsing and application.
 //!
+//! [`UnifiedDiff::apply`] is lenient and applies hunks at their stated line
+//! numbers; [`UnifiedDiff::apply_checked`] verifies every hunk against the
+//! file, relocating it when it is only offset. [`apply_search_replace`]
+//! applies exact search/replace blocks instead. [`UnifiedDiff::between`]
+//! computes the diff between two versions of a file.
+//!
 //! Supports standard unified diff format:
 //! ```text
 //! --- a/file.txt
@@ -11,9 +17,20 @@
 //!  more context
 //! ```
 
+use std::fmt;
 use std::str::Lines;
 use thiserror::Error;
 
+/// Unchanged lines kept around each change in computed diffs.
+pub const DEFAULT_CONTEXT_LINES: usize = 3;
+
+/// Diff lines included in tool results before truncating.
+pub const COMPACT_DIFF_LINES: usize = 60;
+
+/// Above this many line pairs, changed regions are diffed as a whole
+/// (all removed, then all added) instead of line by line.
+const MAX_LCS_CELLS: usize = 4_000_000;
+
 /// Diff parsing/application errors.
 #[derive(Debug, Error)]
 pub enum DiffError {
@@ -31,6 +48,17 @@ pub enum DiffError {
     LineOutOfBounds(usize),
     #[error("Patch application failed: {0}")]
     PatchFailed(String),
+    #[error("Hunk {hunk} does not apply: expected '{expected}' at line {line}, got '{actual}'")]
+    HunkMismatch {
+        hunk: usize,
+        line: usize,
+        expected: String,
+        actual: String,
+    },
+    #[error("Search block {block} not found in file")]
+    SearchNotFound { block: usize },
+    #[error("Search block {block} matches {count} times; add surrounding lines to make it unique")]
+    AmbiguousSearch { block: usize, count: usize },
 }
 
 /// A single hunk in a diff.
@@ -156,6 +184,191 @@ impl UnifiedDiff {
 
         Ok(lines.join("\n"))
     }
+
+    /// Apply this diff, verifying that every hunk matches the file.
+    ///
+    /// Hunks are located by their removed and context lines. A hunk whose
+    /// lines are not at its stated position is applied at the nearest place
+    /// they match, and the offset is reported. Context lines keep the file's
+    /// own text, as does a trailing newline.
+    pub fn apply_checked(&self, original: &str) -> Result<AppliedDiff, DiffError> {
+        if self.is_new_file || self.is_delete {
+            return Ok(AppliedDiff {
+                content: self.apply(original)?,
+                hunks: Vec::new(),
+            });
+        }
+
+        let mut lines: Vec<&str> = original.lines().collect();
+        let mut applied = Vec::with_capacity(self.hunks.len());
+        // Shift of later hunks caused by earlier ones, and the first line
+        // the next hunk may touch
+        let mut delta: isize = 0;
+        let mut min_idx = 0;
+
+        for (i, hunk) in self.hunks.iter().enumerate() {
+            let old_lines: Vec<&str> = hunk
+                .lines
+                .iter()
+                .filter_map(|l| match l {
+                    DiffLine::Context(s) | DiffLine::Remove(s) => Some(s.as_str()),
+                    DiffLine::Add(_) => None,
+                })
+                .collect();
+
+            // Pure insertions follow the stated line: "-5,0" inserts after line 5
+            let stated = if old_lines.is_empty() {
+                hunk.old_start
+            } else {
+                hunk.old_start.saturating_sub(1)
+            };
+            let expected = (stated as isize + delta).max(min_idx as isize) as usize;
+
+            let start = if old_lines.is_empty() {
+                expected.min(lines.len())
+            } else {
+                find_hunk(&lines, &old_lines, expected, min_idx)
+                    .ok_or_else(|| hunk_mismatch(i + 1, &lines, &old_lines, expected))?
+            };
+
+            let mut replacement = Vec::with_capacity(hunk.lines.len());
+            let mut idx = start;
+            for line in &hunk.lines {
+                match line {
+                    DiffLine::Context(_) => {
+                        replacement.push(lines[idx]);
+                        idx += 1;
+                    }
+                    DiffLine::Remove(_) => idx += 1,
+                    DiffLine::Add(s) => replacement.push(s.as_str()),
+                }
+            }
+
+            let new_len = replacement.len();
+            lines.splice(start..start + old_lines.len(), replacement);
+
+            applied.push(AppliedHunk {
+                line: start + 1,
+                offset: start as isize - (stated as isize + delta),
+                added: hunk
+                    .lines
+                    .iter()
+                    .filter(|l| matches!(l, DiffLine::Add(_)))
+                    .count(),
+                removed: hunk
+                    .lines
+                    .iter()
+                    .filter(|l| matches!(l, DiffLine::Remove(_)))
+                    .count(),
+            });
+
+            delta += new_len as isize - old_lines.len() as isize;
+            min_idx = start + new_len;
+        }
+
+        Ok(AppliedDiff {
+            content: join_lines(&lines, original.ends_with('\n')),
+            hunks: applied,
+        })
+    }
+}
+
+/// Result of [`UnifiedDiff::apply_checked`].
+#[derive(Debug, Clone)]
+pub struct AppliedDiff {
+    /// The patched content.
+    pub content: String,
+    /// Where each hunk was applied, in diff order.
+    pub hunks: Vec<AppliedHunk>,
+}
+
+/// Placement of one applied hunk.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AppliedHunk {
+    /// Line (1-based) in the patched content where the hunk starts.
+    pub line: usize,
+    /// Lines between the stated and the actual position (0 if exact).
+    pub offset: isize,
+    /// Number of added lines.
+    pub added: usize,
+    /// Number of removed lines.
+    pub removed: usize,
+}
+
+impl AppliedDiff {
+    /// Hunks that were applied away from their stated position.
+    pub fn offset_hunks(&self) -> impl Iterator<Item = (usize, &AppliedHunk)> {
+        self.hunks
+            .iter()
+            .enumerate()
+            .filter(|(_, h)| h.offset != 0)
+            .map(|(i, h)| (i + 1, h))
+    }
+}
+
+/// Find where `old_lines` occur in `lines`, starting no earlier than
+/// `min_idx` and as close to `expected` as possible.
+///
+/// Exact matches (ignoring trailing whitespace) are preferred over matches
+/// that ignore indentation.
+fn find_hunk(lines: &[&str], old_lines: &[&str], expected: usize, min_idx: usize) -> Option<usize> {
+    let exact = |a: &str, b: &str| a.trim_end() == b.trim_end();
+    let loose = |a: &str, b: &str| a.trim() == b.trim();
+
+    for matches in [&exact as &dyn Fn(&str, &str) -> bool, &loose] {
+        let fits = |start: usize| {
+            start >= min_idx
+                && start + old_lines.len() <= lines.len()
+                && old_lines
+                    .iter()
+                    .zip(&lines[start..])
+                    .all(|(want, have)| matches(have, want))
+        };
+
+        let max_distance = expected.max(lines.len());
+        for distance in 0..=max_distance {
+            if fits(expected + distance) {
+                return Some(expected + distance);
+            }
+            if distance > 0 && distance <= expected && fits(expected - distance) {
+                return Some(expected - distance);
+            }
+        }
+    }
+
+    None
+}
+
+/// Build the error for a hunk that matches nowhere, pointing at the first
+/// differing line at its expected position.
+fn hunk_mismatch(hunk: usize, lines: &[&str], old_lines: &[&str], expected: usize) -> DiffError {
+    let (offset, expected_line, actual_line) = old_lines
+        .iter()
+        .enumerate()
+        .map(|(i, old)| (i, *old, lines.get(expected + i).copied()))
+        .find(|(_, old, actual)| actual.map(|a| a.trim() != old.trim()).unwrap_or(true))
+        .map(|(i, old, actual)| (i, old, actual.unwrap_or("<end of file>")))
+        .unwrap_or((
+            0,
+            old_lines[0],
+            lines.get(expected).copied().unwrap_or("<end of file>"),
+        ));
+
+    DiffError::HunkMismatch {
+        hunk,
+        line: expected + offset + 1,
+        expected: expected_line.to_string(),
+        actual: actual_line.to_string(),
+    }
+}
+
+/// Join lines back into content, optionally ending with a newline.
+fn join_lines(lines: &[&str], trailing_newline: bool) -> String {
+    let mut content = lines.join("\n");
+    if trailing_newline && !lines.is_empty() {
+        content.push('\n');
+    }
+    content
 }
 
 /// Parse a file path from a --- or +++ line.
@@ -176,6 +389,10 @@ fn parse_file_path(line: &str, prefix: &str) -> String {
 }
 
 /// Parse a hunk from the diff lines.
+///
+/// Reads exactly as many old and new lines as the header announces, so a
+/// removed line starting with `--` or an added one starting with `++` stays
+/// part of the hunk. Errors if the lines don't add up to the header's counts.
 fn parse_hunk(lines: &mut std::iter::Peekable<Lines>) -> Result<Hunk, DiffError> {
     let header = lines
         .next()
@@ -184,31 +401,57 @@ fn parse_hunk(lines: &mut std::iter::Peekable<Lines>) -> Result<Hunk, DiffError>
     // Parse @@ -old_start,old_count +new_start,new_count @@
     let (old_start, old_count, new_start, new_count) = parse_hunk_header(header)?;
 
-    let mut hunk_lines = Vec::new();
+    let count_mismatch = |old_seen: usize, new_seen: usize| {
+        DiffError::HunkParseError(format!(
+            "'{}' announces {} old and {} new lines, but the hunk has {} and {}",
+            header.trim(),
+            old_count,
+            new_count,
+            old_seen,
+            new_seen
+        ))
+    };
 
-    while let Some(line) = lines.peek() {
-        if line.starts_with("@@") || line.starts_with("---") || line.starts_with("+++") {
-            break;
-        }
+    let mut hunk_lines = Vec::new();
+    let (mut old_seen, mut new_seen) = (0, 0);
 
-        let line = lines.next().unwrap();
+    while old_seen < old_count || new_seen < new_count {
+        let Some(line) = lines.next() else {
+            return Err(count_mismatch(old_seen, new_seen));
+        };
 
-        if line.is_empty() {
+        let diff_line = if line.is_empty() {
             // Empty line is treated as context
-            hunk_lines.push(DiffLine::Context(String::new()));
+            DiffLine::Context(String::new())
         } else if let Some(content) = line.strip_prefix('+') {
-            hunk_lines.push(DiffLine::Add(content.to_string()));
+            DiffLine::Add(content.to_string())
         } else if let Some(content) = line.strip_prefix('-') {
-            hunk_lines.push(DiffLine::Remove(content.to_string()));
+            DiffLine::Remove(content.to_string())
         } else if let Some(content) = line.strip_prefix(' ') {
-            hunk_lines.push(DiffLine::Context(content.to_string()));
+            DiffLine::Context(content.to_string())
         } else if line.starts_with('\\') {
             // "\ No newline at end of file" - ignore
             continue;
+        } else if line.starts_with("@@") {
+            // Next hunk started early
+            return Err(count_mismatch(old_seen, new_seen));
         } else {
             // Treat as context (some diffs don't prefix context with space)
-            hunk_lines.push(DiffLine::Context(line.to_string()));
+            DiffLine::Context(line.to_string())
+        };
+
+        match diff_line {
+            DiffLine::Context(_) => {
+                old_seen += 1;
+                new_seen += 1;
+            }
+            DiffLine::Remove(_) => old_seen += 1,
+            DiffLine::Add(_) => new_seen += 1,
         }
+        if old_seen > old_count || new_seen > new_count {
+            return Err(count_mismatch(old_seen, new_seen));
+        }
+        hunk_lines.push(diff_line);
     }
 
     Ok(Hunk {
@@ -327,6 +570,232 @@ fn apply_hunk_to_lines(lines: Vec<String>, hunk: &Hunk) -> Result<Vec<String>, D
     Ok(new_lines)
 }
 
+impl UnifiedDiff {
+    /// Compute the diff between two versions of a file.
+    ///
+    /// `None` stands for a missing file, giving a creation or deletion diff.
+    /// Differences in the trailing newline alone are not reported.
+    pub fn between(path: &str, old: Option<&str>, new: Option<&str>) -> Self {
+        let old_lines: Vec<&str> = old.unwrap_or_default().lines().collect();
+        let new_lines: Vec<&str> = new.unwrap_or_default().lines().collect();
+        let ops = edit_script(&old_lines, &new_lines);
+
+        UnifiedDiff {
+            old_path: old.map(|_| path.to_string()),
+            new_path: new.map(|_| path.to_string()),
+            is_new_file: old.is_none(),
+            is_delete: new.is_none(),
+            hunks: group_hunks(&ops, &old_lines, &new_lines, DEFAULT_CONTEXT_LINES),
+        }
+    }
+
+    /// Whether the diff contains no changes.
+    pub fn is_empty(&self) -> bool {
+        self.hunks.is_empty()
+    }
+
+    /// Number of added lines across all hunks.
+    pub fn added_lines(&self) -> usize {
+        self.count_lines(|l| matches!(l, DiffLine::Add(_)))
+    }
+
+    /// Number of removed lines across all hunks.
+    pub fn removed_lines(&self) -> usize {
+        self.count_lines(|l| matches!(l, DiffLine::Remove(_)))
+    }
+
+    fn count_lines(&self, pred: impl Fn(&DiffLine) -> bool) -> usize {
+        self.hunks
+            .iter()
+            .flat_map(|h| &h.lines)
+            .filter(|l| pred(l))
+            .count()
+    }
+
+    /// Render the diff, keeping at most `max_lines` lines.
+    ///
+    /// Used where a diff is shown to a model, so large rewrites don't fill
+    /// the context.
+    pub fn to_compact_string(&self, max_lines: usize) -> String {
+        let full = self.to_string();
+        let total = full.lines().count();
+        if total <= max_lines {
+            return full;
+        }
+
+        let mut compact: String = full
+            .lines()
+            .take(max_lines)
+            .flat_map(|l| [l, "\n"])
+            .collect();
+        compact.push_str(&format!("... ({} more diff lines)\n", total - max_lines));
+        compact
+    }
+}
+
+impl fmt::Display for UnifiedDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.old_path {
+            Some(path) if !self.is_new_file => writeln!(f, "--- a/{}", path)?,
+            _ => writeln!(f, "--- /dev/null")?,
+        }
+        match &self.new_path {
+            Some(path) if !self.is_delete => writeln!(f, "+++ b/{}", path)?,
+            _ => writeln!(f, "+++ /dev/null")?,
+        }
+        for hunk in &self.hunks {
+            writeln!(
+                f,
+                "@@ -{},{} +{},{} @@",
+                hunk.old_start, hunk.old_count, hunk.new_start, hunk.new_count
+            )?;
+            for line in &hunk.lines {
+                match line {
+                    DiffLine::Context(s) => writeln!(f, " {}", s)?,
+                    DiffLine::Add(s) => writeln!(f, "+{}", s)?,
+                    DiffLine::Remove(s) => writeln!(f, "-{}", s)?,
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// One step of an edit script, with indices into the old and new lines.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum EditOp {
+    Equal(usize, usize),
+    Delete(usize),
+    Insert(usize),
+}
+
+/// Compute a shortest edit script between two sets of lines.
+///
+/// Common leading and trailing lines are matched directly; the rest is
+/// diffed by longest common subsequence.
+fn edit_script(old: &[&str], new: &[&str]) -> Vec<EditOp> {
+    let prefix = old.iter().zip(new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let a = &old[prefix..old.len() - suffix];
+    let b = &new[prefix..new.len() - suffix];
+
+    let mut ops: Vec<EditOp> = (0..prefix).map(|i| EditOp::Equal(i, i)).collect();
+
+    if a.len().saturating_mul(b.len()) > MAX_LCS_CELLS {
+        ops.extend((0..a.len()).map(|i| EditOp::Delete(prefix + i)));
+        ops.extend((0..b.len()).map(|j| EditOp::Insert(prefix + j)));
+    } else {
+        // lcs[i][j] = length of the LCS of a[i..] and b[j..]
+        let width = b.len() + 1;
+        let mut lcs = vec![0u32; (a.len() + 1) * width];
+        for i in (0..a.len()).rev() {
+            for j in (0..b.len()).rev() {
+                lcs[i * width + j] = if a[i] == b[j] {
+                    lcs[(i + 1) * width + j + 1] + 1
+                } else {
+                    lcs[(i + 1) * width + j].max(lcs[i * width + j + 1])
+                };
+            }
+        }
+
+        let (mut i, mut j) = (0, 0);
+        while i < a.len() || j < b.len() {
+            if i < a.len() && j < b.len() && a[i] == b[j] {
+                ops.push(EditOp::Equal(prefix + i, prefix + j));
+                i += 1;
+                j += 1;
+            } else if i < a.len()
+                && (j == b.len() || lcs[(i + 1) * width + j] >= lcs[i * width + j + 1])
+            {
+                ops.push(EditOp::Delete(prefix + i));
+                i += 1;
+            } else {
+                ops.push(EditOp::Insert(prefix + j));
+                j += 1;
+            }
+        }
+    }
+
+    let old_tail = old.len() - suffix;
+    let new_tail = new.len() - suffix;
+    ops.extend((0..suffix).map(|k| EditOp::Equal(old_tail + k, new_tail + k)));
+    ops
+}
+
+/// Group an edit script into hunks with `context` unchanged lines around
+/// each change.
+fn group_hunks(ops: &[EditOp], old: &[&str], new: &[&str], context: usize) -> Vec<Hunk> {
+    let changes: Vec<usize> = ops
+        .iter()
+        .enumerate()
+        .filter(|(_, op)| !matches!(op, EditOp::Equal(..)))
+        .map(|(i, _)| i)
+        .collect();
+
+    // Merge changes whose context would touch into op ranges
+    let mut ranges: Vec<(usize, usize)> = Vec::new();
+    for &idx in &changes {
+        let start = idx.saturating_sub(context);
+        let end = (idx + context + 1).min(ops.len());
+        match ranges.last_mut() {
+            Some(last) if start <= last.1 => last.1 = end,
+            _ => ranges.push((start, end)),
+        }
+    }
+
+    ranges
+        .into_iter()
+        .map(|(start, end)| {
+            // Lines of each side before the hunk
+            let (old_before, new_before) =
+                ops[..start].iter().fold((0, 0), |(o, n), op| match op {
+                    EditOp::Equal(..) => (o + 1, n + 1),
+                    EditOp::Delete(_) => (o + 1, n),
+                    EditOp::Insert(_) => (o, n + 1),
+                });
+
+            let lines: Vec<DiffLine> = ops[start..end]
+                .iter()
+                .map(|op| match *op {
+                    EditOp::Equal(i, _) => DiffLine::Context(old[i].to_string()),
+                    EditOp::Delete(i) => DiffLine::Remove(old[i].to_string()),
+                    EditOp::Insert(j) => DiffLine::Add(new[j].to_string()),
+                })
+                .collect();
+
+            let old_count = lines
+                .iter()
+                .filter(|l| !matches!(l, DiffLine::Add(_)))
+                .count();
+            let new_count = lines
+                .iter()
+                .filter(|l| !matches!(l, DiffLine::Remove(_)))
+                .count();
+
+            Hunk {
+                old_start: if old_count == 0 {
+                    old_before
+                } else {
+                    old_before + 1
+                },
+                old_count,
+                new_start: if new_count == 0 {
+                    new_before
+                } else {
+                    new_before + 1
+                },
+                new_count,
+                lines,
+            }
+        })
+        .collect()
+}
+
 /// Apply a unified diff to file content.
 pub fn apply_unified_diff(original: &str, diff_text: &str) -> Result<String, DiffError> {
     let diff = UnifiedDiff::parse(diff_text)?;
@@ -338,6 +807,59 @@ pub fn is_unified_diff(text: &str) -> bool {
     text.contains("@@") && (text.contains("---") || text.contains("+++"))
 }
 
+/// An exact search/replace edit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchReplace {
+    /// Text to find; must occur exactly once in the content.
+    pub search: String,
+    /// Text to put in its place.
+    pub replace: String,
+}
+
+impl SearchReplace {
+    /// Create a new search/replace block.
+    pub fn new(search: impl Into<String>, replace: impl Into<String>) -> Self {
+        Self {
+            search: search.into(),
+            replace: replace.into(),
+        }
+    }
+}
+
+/// Apply search/replace blocks in order.
+///
+/// Each block must match exactly once in the content produced by the
+/// blocks before it; otherwise nothing is applied.
+pub fn apply_search_replace(original: &str, edits: &[SearchReplace]) -> Result<String, DiffError> {
+    let mut content = original.to_string();
+
+    for (i, edit) in edits.iter().enumerate() {
+        let block = i + 1;
+        if edit.search.is_empty() {
+            return Err(DiffError::InvalidFormat(format!(
+                "Search block {} is empty",
+                block
+            )));
+        }
+
+        let mut matches = content.match_indices(&edit.search);
+        let Some((pos, _)) = matches.next() else {
+            return Err(DiffError::SearchNotFound { block });
+        };
+        let extra = matches.count();
+        if extra > 0 {
+            return Err(DiffError::AmbiguousSearch {
+                block,
+                count: extra + 1,
+            });
+        }
+
+        content.replace_range(pos..pos + edit.search.len(), &edit.replace);
+    }
+
+    Ok(content)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1449,8 +1971,8 @@ Binary files a/image.png and b/image.png differ
     // ===== Apply with mismatched line counts =====
 
     #[test]
-    fn test_apply_hunk_count_mismatch_extra_context() {
-        // Header says 3 lines but we have fewer - should still work
+    fn test_apply_hunk_count_mismatch_is_rejected() {
+        // Header says 3 lines but the hunk has fewer
         let original = "a\nb";
         let diff = r#"--- a/file.txt
 +++ b/file.txt
@@ -1459,8 +1981,249 @@ Binary files a/image.png and b/image.png differ
 -b
 +B
 "#;
-        // Should handle gracefully
         let result = apply_unified_diff(original, diff);
-        assert!(result.is_ok());
+        assert!(matches!(result, Err(DiffError::HunkParseError(_))));
+
+        // ... or more than it announces
+        let diff = "--- a/f\n+++ b/f\n@@ -1,1 +1,1 @@\n a\n-b\n+B\n";
+        let result = apply_unified_diff("a\nb", diff);
+        assert!(matches!(result, Err(DiffError::HunkParseError(_))));
+    }
+
+    #[test]
+    fn test_parse_hunk_removing_sql_comment() {
+        // "-- comment" removed shows up as "--- comment", which looks like a
+        // file header; the hunk counts say it belongs to the hunk
+        let original = "SELECT 1;\n-- comment\nSELECT 2;\n";
+        let diff =
+            "--- a/q.sql\n+++ b/q.sql\n@@ -1,3 +1,2 @@\n SELECT 1;\n--- comment\n SELECT 2;\n";
+        let parsed = UnifiedDiff::parse(diff).unwrap();
+        assert_eq!(parsed.hunks.len(), 1);
+        assert_eq!(parsed.hunks[0].lines.len(), 3);
+
+        let applied = parsed.apply_checked(original).unwrap();
+        assert_eq!(applied.content, "SELECT 1;\nSELECT 2;\n");
+    }
+
+    // ===== apply_checked =====
+
+    #[test]
+    fn test_apply_checked_exact_position() {
+        let original = "a\nb\nc\n";
+        let diff = "--- a/f\n+++ b/f\n@@ -1,3 +1,3 @@\n a\n-b\n+B\n c\n";
+        let applied = UnifiedDiff::parse(diff)
+            .unwrap()
+            .apply_checked(original)
+            .unwrap();
+        assert_eq!(applied.content, "a\nB\nc\n");
+        assert_eq!(
+            applied.hunks,
+            vec![AppliedHunk {
+                line: 1,
+                offset: 0,
+                added: 1,
+                removed: 1
+            }]
+        );
+        assert_eq!(applied.offset_hunks().count(), 0);
+    }
+
+    #[test]
+    fn test_apply_checked_reports_offset() {
+        let original = "x\ny\nz\na\nb\nc";
+        // Stated at line 1, but the lines start at line 4
+        let diff = "--- a/f\n+++ b/f\n@@ -1,3 +1,3 @@\n a\n-b\n+B\n c\n";
+        let applied = UnifiedDiff::parse(diff)
+            .unwrap()
+            .apply_checked(original)
+            .unwrap();
+        assert_eq!(applied.content, "x\ny\nz\na\nB\nc");
+        let (hunk, placed) = applied.offset_hunks().next().unwrap();
+        assert_eq!(hunk, 1);
+        assert_eq!(placed.line, 4);
+        assert_eq!(placed.offset, 3);
+    }
+
+    #[test]
+    fn test_apply_checked_tracks_earlier_hunks() {
+        let original = "1\n2\n3\n4\n5\n6\n7\n8";
+        let diff = "--- a/f\n+++ b/f\n@@ -1,2 +1,4 @@\n 1\n+1a\n+1b\n 2\n@@ -7,2 +9,1 @@\n-7\n 8\n";
+        let applied = UnifiedDiff::parse(diff)
+            .unwrap()
+            .apply_checked(original)
+            .unwrap();
+        assert_eq!(applied.content, "1\n1a\n1b\n2\n3\n4\n5\n6\n8");
+        assert_eq!(applied.offset_hunks().count(), 0);
+        assert_eq!(applied.hunks[1].line, 9);
+    }
+
+    #[test]
+    fn test_apply_checked_keeps_file_indentation_for_context() {
+        let original = "fn main() {\n    let x = 1;\n}\n";
+        let diff =
+            "--- a/f\n+++ b/f\n@@ -1,3 +1,3 @@\n fn main() {\n-let x = 1;\n+    let x = 2;\n }\n";
+        let applied = UnifiedDiff::parse(diff)
+            .unwrap()
+            .apply_checked(original)
+            .unwrap();
+        assert_eq!(applied.content, "fn main() {\n    let x = 2;\n}\n");
+    }
+
+    #[test]
+    fn test_apply_checked_pure_insertion() {
+        let original = "a\nb";
+        let diff = "--- a/f\n+++ b/f\n@@ -1,0 +2,1 @@\n+inserted\n";
+        let applied = UnifiedDiff::parse(diff)
+            .unwrap()
+            .apply_checked(original)
+            .unwrap();
+        assert_eq!(applied.content, "a\ninserted\nb");
+    }
+
+    #[test]
+    fn test_apply_checked_mismatch_fails() {
+        let original = "a\nb\nc";
+        let diff = "--- a/f\n+++ b/f\n@@ -1,3 +1,3 @@\n a\n-q\n+Q\n c\n";
+        let err = UnifiedDiff::parse(diff)
+            .unwrap()
+            .apply_checked(original)
+            .unwrap_err();
+        match err {
+            DiffError::HunkMismatch {
+                hunk,
+                line,
+                expected,
+                actual,
+            } => {
+                assert_eq!(hunk, 1);
+                assert_eq!(line, 2);
+                assert_eq!(expected, "q");
+                assert_eq!(actual, "b");
+            }
+            other => panic!("unexpected error: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_apply_checked_new_file() {
+        let diff = "--- /dev/null\n+++ b/new.txt\n@@ -0,0 +1,2 @@\n+one\n+two\n";
+        let applied = UnifiedDiff::parse(diff).unwrap().apply_checked("").unwrap();
+        assert_eq!(applied.content, "one\ntwo");
+    }
+
+    // ===== apply_search_replace =====
+
+    #[test]
+    fn test_search_replace_applies_in_order() {
+        let original = "let a = 1;\nlet b = 2;\n";
+        let edits = [
+            SearchReplace::new("let a = 1;", "let a = 10;"),
+            SearchReplace::new("let a = 10;\nlet b", "let a = 10;\nlet c"),
+        ];
+        let result = apply_search_replace(original, &edits).unwrap();
+        assert_eq!(result, "let a = 10;\nlet c = 2;\n");
+    }
+
+    #[test]
+    fn test_search_replace_not_found() {
+        let edits = [
+            SearchReplace::new("a", "b"),
+            SearchReplace::new("missing", "x"),
+        ];
+        let err = apply_search_replace("abc", &edits).unwrap_err();
+        assert!(matches!(err, DiffError::SearchNotFound { block: 2 }));
+    }
+
+    #[test]
+    fn test_search_replace_ambiguous() {
+        let edits = [SearchReplace::new("x", "y")];
+        let err = apply_search_replace("x x x", &edits).unwrap_err();
+        assert!(matches!(
+            err,
+            DiffError::AmbiguousSearch { block: 1, count: 3 }
+        ));
+        assert!(err.to_string().contains("3 times"));
+    }
+
+    #[test]
+    fn test_search_replace_empty_search() {
+        let edits = [SearchReplace::new("", "y")];
+        let err = apply_search_replace("abc", &edits).unwrap_err();
+        assert!(matches!(err, DiffError::InvalidFormat(_)));
+    }
+
+    // ===== UnifiedDiff::between =====
+
+    #[test]
+    fn test_between_single_change() {
+        let old = "1\n2\n3\n4\n5\n6\n7\n8\n9\n";
+        let new = "1\n2\n3\n4\nFIVE\n6\n7\n8\n9\n";
+        let diff = UnifiedDiff::between("f.txt", Some(old), Some(new));
+
+        assert_eq!(diff.hunks.len(), 1);
+        assert_eq!(diff.added_lines(), 1);
+        assert_eq!(diff.removed_lines(), 1);
+        assert_eq!(
+            diff.to_string(),
+            "--- a/f.txt\n+++ b/f.txt\n@@ -2,7 +2,7 @@\n 2\n 3\n 4\n-5\n+FIVE\n 6\n 7\n 8\n"
+        );
+    }
+
+    #[test]
+    fn test_between_separate_hunks() {
+        let old: String = (1..=20).map(|i| format!("{}\n", i)).collect();
+        let new: String = (1..=20)
+            .map(|i| match i {
+                2 => "two\n".to_string(),
+                19 => "nineteen\n".to_string(),
+                _ => format!("{}\n", i),
+            })
+            .collect();
+        let diff = UnifiedDiff::between("f.txt", Some(&old), Some(&new));
+        assert_eq!(diff.hunks.len(), 2);
+        assert_eq!(diff.hunks[1].old_start, 16);
+    }
+
+    #[test]
+    fn test_between_round_trips_through_parse() {
+        let old = "fn main() {\n    let a = 1;\n    let b = 2;\n    println!(\"{}\", a + b);\n}\n";
+        let new = "fn main() {\n    let a = 1;\n    let c = 3;\n    let b = 2;\n    println!(\"{}\", a + b + c);\n}\n";
+        let diff = UnifiedDiff::between("main.rs", Some(old), Some(new));
+
+        let parsed = UnifiedDiff::parse(&diff.to_string()).unwrap();
+        assert_eq!(parsed.old_path, Some("main.rs".to_string()));
+        assert_eq!(parsed.apply_checked(old).unwrap().content, new);
+    }
+
+    #[test]
+    fn test_between_new_and_deleted_file() {
+        let created = UnifiedDiff::between("new.txt", None, Some("a\nb\n"));
+        assert!(created.is_new_file);
+        assert_eq!(
+            created.to_string(),
+            "--- /dev/null\n+++ b/new.txt\n@@ -0,0 +1,2 @@\n+a\n+b\n"
+        );
+
+        let deleted = UnifiedDiff::between("old.txt", Some("a\n"), None);
+        assert!(deleted.is_delete);
+        assert_eq!(deleted.removed_lines(), 1);
+        assert!(deleted.to_string().contains("+++ /dev/null"));
+    }
+
+    #[test]
+    fn test_between_identical_is_empty() {
+        let diff = UnifiedDiff::between("f.txt", Some("same\n"), Some("same\n"));
+        assert!(diff.is_empty());
+    }
+
+    #[test]
+    fn test_to_compact_string_truncates() {
+        let new: String = (0..50).map(|i| format!("line {}\n", i)).collect();
+        let diff = UnifiedDiff::between("big.txt", None, Some(&new));
+
+        let compact = diff.to_compact_string(10);
+        assert_eq!(compact.lines().count(), 11);
+        assert!(compact.ends_with("... (43 more diff lines)\n"));
+        assert_eq!(diff.to_compact_string(100), diff.to_string());
     }
 }
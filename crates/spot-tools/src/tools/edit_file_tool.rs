@@ -1,15 +1,23 @@
 //! EditFile tool implementation.
 //!
 //! Provides a serdesAI-compatible tool for creating or editing files.
+//! Existing files are edited with a unified diff or exact search/replace
+//! blocks; the full content is only written when given explicitly. When a
+//! UI is attached it approves (or edits) the change before it is written;
+//! a file that changed on disk while awaiting approval is left alone.
+//! The result includes a diff of the change.
 
 use async_trait::async_trait;
 use serde::Deserialize;
 use serde_json::Value as JsonValue;
 use tracing::{debug, info_span};
 
-use serdes_ai_tools::{RunContext, SchemaBuilder, Tool, ToolDefinition, ToolResult, ToolReturn};
+use serdes_ai_tools::{RunContext, Tool, ToolDefinition, ToolError, ToolResult, ToolReturn};
 
+use super::diff::{apply_search_replace, SearchReplace, UnifiedDiff, COMPACT_DIFF_LINES};
 use super::file_ops;
+use super::tool_context::get_global_context;
+use crate::terminal::ProposedFileChange;
 
 /// Tool for creating or editing files.
 #[derive(Debug, Clone, Default)]
@@ -18,31 +26,109 @@ pub struct EditFileTool;
 #[derive(Debug, Deserialize)]
 struct EditFileArgs {
     file_path: String,
-    content: String,
+    #[serde(default)]
+    content: Option<String>,
+    #[serde(default)]
+    diff: Option<String>,
+    #[serde(default)]
+    edits: Option<Vec<EditBlock>>,
     #[serde(default)]
     create_directories: bool,
 }
 
+#[derive(Debug, Deserialize)]
+struct EditBlock {
+    search: String,
+    replace: String,
+}
+
+/// How the file is changed.
+enum EditMode {
+    Write(String),
+    Diff(String),
+    Edits(Vec<SearchReplace>),
+}
+
+impl EditFileArgs {
+    fn into_mode(self) -> Result<(String, bool, EditMode), ToolError> {
+        let modes = [
+            self.content.is_some(),
+            self.diff.is_some(),
+            self.edits.is_some(),
+        ];
+        if modes.iter().filter(|set| **set).count() != 1 {
+            return Err(ToolError::execution_failed(
+                "edit_file: provide exactly one of `diff`, `edits` or `content`".to_string(),
+            ));
+        }
+
+        let mode = if let Some(diff) = self.diff {
+            EditMode::Diff(diff)
+        } else if let Some(edits) = self.edits {
+            EditMode::Edits(
+                edits
+                    .into_iter()
+                    .map(|e| SearchReplace::new(e.search, e.replace))
+                    .collect(),
+            )
+        } else {
+            EditMode::Write(self.content.unwrap_or_default())
+        };
+
+        Ok((self.file_path, self.create_directories, mode))
+    }
+}
+
 #[async_trait]
 impl Tool for EditFileTool {
     fn definition(&self) -> ToolDefinition {
         ToolDefinition::new(
             "edit_file",
-            "Create or overwrite a file with the provided content. \
-             Supports creating parent directories if they don't exist.",
-        )
-        .with_parameters(
-            SchemaBuilder::new()
-                .string("file_path", "Path to the file to create or edit.", true)
-                .string("content", "The full content to write to the file.", true)
-                .boolean(
-                    "create_directories",
-                    "Whether to create parent directories if they don't exist. Defaults to false.",
-                    false,
-                )
-                .build()
-                .expect("schema build failed"),
+            "Create or edit a file. To change an existing file, pass either `diff` \
+             (a unified diff) or `edits` (search/replace blocks whose `search` text \
+             occurs exactly once). Pass `content` only to create a file or replace \
+             it entirely. Nothing is written if a hunk or search block does not match.",
         )
+        .with_parameters(serde_json::json!({
+            "type": "object",
+            "properties": {
+                "file_path": {
+                    "type": "string",
+                    "description": "Path to the file to create or edit."
+                },
+                "diff": {
+                    "type": "string",
+                    "description": "Unified diff to apply (`@@ -start,count +start,count @@` hunks with context lines)."
+                },
+                "edits": {
+                    "type": "array",
+                    "description": "Search/replace blocks, applied in order.",
+                    "items": {
+                        "type": "object",
+                        "properties": {
+                            "search": {
+                                "type": "string",
+                                "description": "Exact text to find, including indentation."
+                            },
+                            "replace": {
+                                "type": "string",
+                                "description": "Text to replace it with."
+                            }
+                        },
+                        "required": ["search", "replace"]
+                    }
+                },
+                "content": {
+                    "type": "string",
+                    "description": "The full content to write to the file. Only use for new files or complete rewrites."
+                },
+                "create_directories": {
+                    "type": "boolean",
+                    "description": "Whether to create parent directories if they don't exist. Defaults to false."
+                }
+            },
+            "required": ["file_path"]
+        }))
     }
 
     async fn call(&self, _ctx: &RunContext, args: JsonValue) -> ToolResult {
@@ -53,23 +139,166 @@ impl Tool for EditFileTool {
             args.clone(),
             self.definition().parameters(),
         )?;
-
-        let _span = info_span!("edit_file", file_path = %args.file_path).entered();
-
-        match file_ops::write_file(&args.file_path, &args.content, args.create_directories) {
-            Ok(()) => {
-                let line_count = args.content.lines().count();
-                let byte_count = args.content.len();
-                Ok(ToolReturn::text(format!(
+        let (file_path, create_directories, mode) = args.into_mode()?;
+
+        let _span = info_span!("edit_file", file_path = %file_path).entered();
+
+        let original = std::fs::read_to_string(&file_path);
+        let read_original = || match &original {
+            Ok(content) => Ok(content.as_str()),
+            Err(e) => Err(ToolReturn::error(format!(
+                "Failed to read {}: {}",
+                file_path, e
+            ))),
+        };
+
+        // Hunks applied away from their stated line
+        let mut notes = String::new();
+        let (mut content, summary) = match mode {
+            EditMode::Write(content) => {
+                let summary = format!(
                     "Successfully wrote {} lines ({} bytes) to {}",
-                    line_count, byte_count, args.file_path
-                )))
+                    content.lines().count(),
+                    content.len(),
+                    file_path
+                );
+                (content, summary)
+            }
+            EditMode::Diff(diff_text) => {
+                let diff = match UnifiedDiff::parse(&diff_text) {
+                    Ok(diff) if !diff.hunks.is_empty() => diff,
+                    Ok(_) => return Ok(ToolReturn::error("Diff contains no hunks")),
+                    Err(e) => return Ok(ToolReturn::error(format!("Invalid diff: {}", e))),
+                };
+                if diff.is_delete {
+                    return Ok(ToolReturn::error(
+                        "Diff deletes the file; use delete_file instead",
+                    ));
+                }
+
+                let base = if diff.is_new_file {
+                    // A diff from /dev/null would silently replace the whole file
+                    if std::path::Path::new(&file_path).exists() {
+                        return Ok(ToolReturn::error(format!(
+                            "Diff creates {} but the file already exists and is unchanged. \
+                             Diff against its current content instead.",
+                            file_path
+                        )));
+                    }
+                    ""
+                } else {
+                    match read_original() {
+                        Ok(base) => base,
+                        Err(ret) => return Ok(ret),
+                    }
+                };
+
+                let applied = match diff.apply_checked(base) {
+                    Ok(applied) => applied,
+                    Err(e) => {
+                        return Ok(ToolReturn::error(format!(
+                            "Diff not applied, {} is unchanged: {}. \
+                             Re-read the file and retry with matching context lines.",
+                            file_path, e
+                        )))
+                    }
+                };
+
+                for (hunk, placed) in applied.offset_hunks() {
+                    notes.push_str(&format!(
+                        "\nHunk {} applied at line {} (offset {:+} lines)",
+                        hunk, placed.line, placed.offset
+                    ));
+                }
+                let summary = format!("Applied {} hunk(s) to {}", applied.hunks.len(), file_path);
+                (applied.content, summary)
+            }
+            EditMode::Edits(edits) => {
+                if edits.is_empty() {
+                    return Ok(ToolReturn::error("No edits given"));
+                }
+                let base = match read_original() {
+                    Ok(base) => base,
+                    Err(ret) => return Ok(ret),
+                };
+
+                match apply_search_replace(base, &edits) {
+                    Ok(content) => (
+                        content,
+                        format!("Applied {} edit(s) to {}", edits.len(), file_path),
+                    ),
+                    Err(e) => {
+                        return Ok(ToolReturn::error(format!(
+                            "Edits not applied, {} is unchanged: {}",
+                            file_path, e
+                        )))
+                    }
+                }
+            }
+        };
+
+        // The UI applies the permission policy and may ask the user
+        if let Some(tool_ctx) = get_global_context() {
+            let proposed =
+                UnifiedDiff::between(&file_path, original.as_deref().ok(), Some(&content));
+            if original.is_err() || !proposed.is_empty() {
+                let change = ProposedFileChange {
+                    path: file_path.clone(),
+                    content: Some(content.clone()),
+                    diff: proposed.to_string(),
+                };
+                match tool_ctx.approve_file_change(change).await {
+                    Ok(Some(edited)) => {
+                        content = edited;
+                        notes.push_str("\nThe user edited the change before it was written");
+                    }
+                    Ok(None) => {}
+                    Err(message) => {
+                        return Ok(ToolReturn::error(format!(
+                            "{} was not changed: {}",
+                            file_path, message
+                        )))
+                    }
+                }
+
+                // Approval can take a while; don't overwrite changes made meanwhile
+                if changed_on_disk(&file_path, &original) {
+                    return Ok(ToolReturn::error(format!(
+                        "{} changed while the edit was awaiting approval and was not written. \
+                         Re-read the file and retry.",
+                        file_path
+                    )));
+                }
             }
-            Err(e) => Ok(ToolReturn::error(format!("Failed to write file: {}", e))),
         }
+
+        if let Err(e) = file_ops::write_file(&file_path, &content, create_directories) {
+            return Ok(ToolReturn::error(format!("Failed to write file: {}", e)));
+        }
+
+        let diff = UnifiedDiff::between(&file_path, original.as_deref().ok(), Some(&content));
+        let diff_text = if diff.is_empty() {
+            "(no changes)".to_string()
+        } else {
+            diff.to_compact_string(COMPACT_DIFF_LINES)
+        };
+        Ok(ToolReturn::text(format!(
+            "{} (+{} -{} lines){}\n\n{}",
+            summary,
+            diff.added_lines(),
+            diff.removed_lines(),
+            notes,
+            diff_text
+        )))
     }
 }
 
+/// Whether `path` no longer holds `original`, the content the edit was made
+/// against (a missing file counts as unchanged if it is still missing).
+fn changed_on_disk(path: &str, original: &std::io::Result<String>) -> bool {
+    std::fs::read_to_string(path).ok().as_deref() != original.as_deref().ok()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -98,6 +327,8 @@ mod tests {
         let schema_str = serde_json::to_string(params).unwrap();
         assert!(schema_str.contains("file_path"));
         assert!(schema_str.contains("content"));
+        assert!(schema_str.contains("diff"));
+        assert!(schema_str.contains("edits"));
         assert!(schema_str.contains("create_directories"));
     }
 
@@ -170,6 +401,30 @@ mod tests {
         assert!(text.contains("17 bytes"));
     }
 
+    #[tokio::test]
+    async fn test_call_returns_diff_against_previous_content() {
+        let dir = tempfile::tempdir().expect("tempdir failed");
+        let file_path = dir.path().join("diffed.txt");
+        fs::write(&file_path, "keep\nold\n").expect("write failed");
+
+        let tool = EditFileTool;
+        let ctx = RunContext::minimal("test");
+        let result = tool
+            .call(
+                &ctx,
+                serde_json::json!({
+                    "file_path": file_path.to_str().unwrap(),
+                    "content": "keep\nnew\n"
+                }),
+            )
+            .await
+            .unwrap();
+
+        let text = result.as_text().unwrap();
+        assert!(text.contains("(+1 -1 lines)"));
+        assert!(text.contains("@@ -1,2 +1,2 @@\n keep\n-old\n+new"));
+    }
+
     #[tokio::test]
     async fn test_call_create_directories_true() {
         let dir = tempfile::tempdir().expect("tempdir failed");
@@ -288,6 +543,178 @@ mod tests {
         assert_eq!(fs::read_to_string(&file_path).unwrap(), "");
     }
 
+    #[tokio::test]
+    async fn test_call_rejects_multiple_modes() {
+        let tool = EditFileTool;
+        let ctx = RunContext::minimal("test");
+        let result = tool
+            .call(
+                &ctx,
+                serde_json::json!({
+                    "file_path": "/tmp/test.txt",
+                    "content": "hello",
+                    "diff": "@@ -1 +1 @@\n-a\n+b\n"
+                }),
+            )
+            .await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_call_applies_diff() {
+        let dir = tempfile::tempdir().expect("tempdir failed");
+        let file_path = dir.path().join("patch.txt");
+        fs::write(&file_path, "zero\none\ntwo\nthree\n").expect("write failed");
+
+        let tool = EditFileTool;
+        let ctx = RunContext::minimal("test");
+        // Stated one line too early; should be found at line 2
+        let ret = tool
+            .call(
+                &ctx,
+                serde_json::json!({
+                    "file_path": file_path.to_str().unwrap(),
+                    "diff": "--- a/patch.txt\n+++ b/patch.txt\n@@ -1,3 +1,3 @@\n one\n-two\n+TWO\n three\n"
+                }),
+            )
+            .await
+            .unwrap();
+
+        assert!(!ret.is_error());
+        let text = ret.as_text().unwrap();
+        assert!(text.contains("+1 -1"));
+        assert!(text.contains("offset +1"));
+        assert_eq!(
+            fs::read_to_string(&file_path).unwrap(),
+            "zero\none\nTWO\nthree\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_call_mismatched_diff_leaves_file_unchanged() {
+        let dir = tempfile::tempdir().expect("tempdir failed");
+        let file_path = dir.path().join("patch.txt");
+        fs::write(&file_path, "one\ntwo\n").expect("write failed");
+
+        let tool = EditFileTool;
+        let ctx = RunContext::minimal("test");
+        let ret = tool
+            .call(
+                &ctx,
+                serde_json::json!({
+                    "file_path": file_path.to_str().unwrap(),
+                    "diff": "@@ -1,2 +1,2 @@\n one\n-nope\n+NOPE\n"
+                }),
+            )
+            .await
+            .unwrap();
+
+        assert!(ret.is_error());
+        assert!(ret.as_text().unwrap().contains("Hunk 1 does not apply"));
+        assert_eq!(fs::read_to_string(&file_path).unwrap(), "one\ntwo\n");
+    }
+
+    #[tokio::test]
+    async fn test_call_new_file_diff_refuses_existing_file() {
+        let dir = tempfile::tempdir().expect("tempdir failed");
+        let file_path = dir.path().join("exists.txt");
+        fs::write(&file_path, "keep me\n").expect("write failed");
+
+        let tool = EditFileTool;
+        let ctx = RunContext::minimal("test");
+        let ret = tool
+            .call(
+                &ctx,
+                serde_json::json!({
+                    "file_path": file_path.to_str().unwrap(),
+                    "diff": "--- /dev/null\n+++ b/exists.txt\n@@ -0,0 +1,1 @@\n+replaced\n"
+                }),
+            )
+            .await
+            .unwrap();
+
+        assert!(ret.is_error());
+        assert!(ret.as_text().unwrap().contains("already exists"));
+        assert_eq!(fs::read_to_string(&file_path).unwrap(), "keep me\n");
+    }
+
+    #[tokio::test]
+    async fn test_call_applies_search_replace_edits() {
+        let dir = tempfile::tempdir().expect("tempdir failed");
+        let file_path = dir.path().join("edits.rs");
+        fs::write(&file_path, "fn a() {}\nfn b() {}\n").expect("write failed");
+
+        let tool = EditFileTool;
+        let ctx = RunContext::minimal("test");
+        let ret = tool
+            .call(
+                &ctx,
+                serde_json::json!({
+                    "file_path": file_path.to_str().unwrap(),
+                    "edits": [
+                        { "search": "fn a() {}", "replace": "fn a() { 1 }" },
+                        { "search": "fn b", "replace": "fn c" }
+                    ]
+                }),
+            )
+            .await
+            .unwrap();
+
+        assert!(!ret.is_error());
+        assert!(ret.as_text().unwrap().contains("Applied 2 edit(s)"));
+        assert_eq!(
+            fs::read_to_string(&file_path).unwrap(),
+            "fn a() { 1 }\nfn c() {}\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_call_ambiguous_edit_leaves_file_unchanged() {
+        let dir = tempfile::tempdir().expect("tempdir failed");
+        let file_path = dir.path().join("edits.txt");
+        fs::write(&file_path, "x\nx\n").expect("write failed");
+
+        let tool = EditFileTool;
+        let ctx = RunContext::minimal("test");
+        let ret = tool
+            .call(
+                &ctx,
+                serde_json::json!({
+                    "file_path": file_path.to_str().unwrap(),
+                    // Some models send arrays as JSON strings
+                    "edits": "[{\"search\": \"x\", \"replace\": \"y\"}]"
+                }),
+            )
+            .await
+            .unwrap();
+
+        assert!(ret.is_error());
+        assert!(ret.as_text().unwrap().contains("matches 2 times"));
+        assert_eq!(fs::read_to_string(&file_path).unwrap(), "x\nx\n");
+    }
+
+    #[test]
+    fn test_changed_on_disk() {
+        let dir = tempfile::tempdir().expect("tempdir failed");
+        let file_path = dir.path().join("test.txt");
+        let path = file_path.to_str().unwrap();
+
+        // Missing before and after
+        let original = fs::read_to_string(&file_path);
+        assert!(!changed_on_disk(path, &original));
+
+        // Created meanwhile
+        fs::write(&file_path, "one\n").expect("write failed");
+        assert!(changed_on_disk(path, &original));
+
+        let original = fs::read_to_string(&file_path);
+        assert!(!changed_on_disk(path, &original));
+
+        // Edited meanwhile
+        fs::write(&file_path, "two\n").expect("write failed");
+        assert!(changed_on_disk(path, &original));
+    }
+
     #[test]
     fn test_tool_debug_impl() {
         let tool = EditFileTool;
@@ -1,9 +1,12 @@
 //! File operation tools.
 
-use super::common::{is_text_file, should_ignore};
-use grep_regex::RegexMatcher;
-use grep_searcher::{Searcher, Sink, SinkMatch};
-use ignore::WalkBuilder;
+use super::common::IgnoreRules;
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use grep_regex::RegexMatcherBuilder;
+use grep_searcher::{
+    BinaryDetection, Searcher, SearcherBuilder, Sink, SinkContext, SinkContextKind, SinkMatch,
+};
+use ignore::types::TypesBuilder;
 use serde::{Deserialize, Serialize};
 use std::fs;
 use std::io;
@@ -54,16 +57,6 @@ const LIST_FILES_HARD_MAX_ENTRIES: usize = 10_000;
 const LIST_FILES_DEFAULT_MAX_DEPTH: usize = 10;
 const LIST_FILES_HARD_MAX_DEPTH: usize = 50;
 
-/// Context for recursive file listing.
-struct ListFilesContext<'a> {
-    base: &'a Path,
-    entries: &'a mut Vec<FileEntry>,
-    recursive: bool,
-    max_depth: usize,
-    max_entries: usize,
-    truncated: &'a mut bool,
-}
-
 /// Maximum tokens allowed in a single file read to protect context window
 const READ_FILE_MAX_TOKENS: usize = 10_000;
 /// Approximate characters per token (conservative estimate)
@@ -126,17 +119,20 @@ fn is_project_directory(path: &Path) -> bool {
     false
 }
 
-/// List files in a directory.
+/// List files in a directory, skipping what `rules` exclude.
 pub fn list_files(
     directory: &str,
     recursive: bool,
     max_depth: Option<usize>,
     max_entries: Option<usize>,
+    rules: &IgnoreRules,
 ) -> Result<ListFilesResult, FileError> {
     let path = Path::new(directory);
     if !path.exists() {
         return Err(FileError::NotFound(directory.to_string()));
     }
+    // Surface an unreadable top-level directory as an error
+    fs::read_dir(path)?;
 
     // Auto-disable recursion for home directories (unless it's a project)
     let effective_recursive = if recursive && is_home_directory(path) && !is_project_directory(path)
@@ -150,109 +146,76 @@ pub fn list_files(
         .unwrap_or(LIST_FILES_DEFAULT_MAX_ENTRIES)
         .clamp(1, LIST_FILES_HARD_MAX_ENTRIES);
 
-    let mut entries = Vec::new();
-    let mut total_files = 0;
-    let mut total_dirs = 0;
-    let mut total_size = 0u64;
-    let mut truncated = false;
-
     let max_depth = max_depth
         .unwrap_or(LIST_FILES_DEFAULT_MAX_DEPTH)
         .min(LIST_FILES_HARD_MAX_DEPTH);
 
-    let mut ctx = ListFilesContext {
-        base: path,
-        entries: &mut entries,
-        recursive: effective_recursive,
-        max_depth,
-        max_entries,
-        truncated: &mut truncated,
+    // Walker depth 0 is the directory itself, so entries sit one level deeper
+    let walk_depth = if effective_recursive {
+        max_depth + 1
+    } else {
+        1
     };
-    list_files_recursive(&mut ctx, path, 0)?;
-
-    for entry in &entries {
-        if entry.is_dir {
-            total_dirs += 1;
-        } else {
-            total_files += 1;
-            total_size += entry.size;
-        }
-    }
-
-    Ok(ListFilesResult {
-        entries,
-        total_files,
-        total_dirs,
-        total_size,
-        truncated,
-        max_entries,
-    })
-}
-
-fn list_files_recursive(
-    ctx: &mut ListFilesContext,
-    dir: &Path,
-    depth: usize,
-) -> Result<(), FileError> {
-    if depth > ctx.max_depth {
-        return Ok(());
-    }
-
-    if ctx.entries.len() >= ctx.max_entries {
-        *ctx.truncated = true;
-        return Ok(());
-    }
+    let walker = rules
+        .walker(path)
+        .max_depth(Some(walk_depth))
+        .sort_by_file_name(|a, b| a.cmp(b))
+        .build();
 
-    let read_dir = fs::read_dir(dir);
-    let mut dir_entries: Vec<_> = match read_dir {
-        Ok(read_dir) => read_dir.filter_map(|e| e.ok()).collect(),
-        Err(e) if depth == 0 => return Err(e.into()),
-        Err(_) => return Ok(()),
-    };
+    let mut entries = Vec::new();
+    let mut total_files = 0;
+    let mut total_dirs = 0;
+    let mut total_size = 0u64;
+    let mut truncated = false;
 
-    dir_entries.sort_by_key(|a| a.file_name());
+    for entry in walker.flatten() {
+        if entry.depth() == 0 {
+            continue;
+        }
 
-    for entry in dir_entries {
-        if ctx.entries.len() >= ctx.max_entries {
-            *ctx.truncated = true;
+        if entries.len() >= max_entries {
+            truncated = true;
             break;
         }
 
-        let path = entry.path();
-        let relative = path.strip_prefix(ctx.base).unwrap_or(&path);
-        let relative_str = relative.to_string_lossy().to_string();
-
-        if should_ignore(&relative_str) {
+        let Some(file_type) = entry.file_type() else {
             continue;
-        }
-
-        let file_type = match entry.file_type() {
-            Ok(file_type) => file_type,
-            Err(_) => continue,
         };
-
         let metadata = match entry.metadata() {
             Ok(metadata) => metadata,
             Err(_) => continue,
         };
 
+        let entry_path = entry.path();
+        let relative = entry_path.strip_prefix(path).unwrap_or(entry_path);
         let is_dir = file_type.is_dir();
-        let name = entry.file_name().to_string_lossy().to_string();
 
-        ctx.entries.push(FileEntry {
-            path: relative_str.clone(),
-            name,
+        entries.push(FileEntry {
+            path: relative.to_string_lossy().to_string(),
+            name: entry.file_name().to_string_lossy().to_string(),
             is_dir,
             size: if is_dir { 0 } else { metadata.len() },
-            depth,
+            depth: entry.depth() - 1,
         });
+    }
 
-        if is_dir && ctx.recursive {
-            list_files_recursive(ctx, &path, depth + 1)?;
+    for entry in &entries {
+        if entry.is_dir {
+            total_dirs += 1;
+        } else {
+            total_files += 1;
+            total_size += entry.size;
         }
     }
 
-    Ok(())
+    Ok(ListFilesResult {
+        entries,
+        total_files,
+        total_dirs,
+        total_size,
+        truncated,
+        max_entries,
+    })
 }
 
 /// Read file contents.
@@ -340,25 +303,95 @@ pub fn write_file(path: &str, content: &str, create_dirs: bool) -> Result<(), Fi
     Ok(())
 }
 
+/// A line of file content with its line number.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GrepLine {
+    pub line_number: usize,
+    pub content: String,
+}
+
 /// Grep match result.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GrepMatch {
     pub path: String,
     pub line_number: usize,
     pub content: String,
+    /// Context lines before the match.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub before: Vec<GrepLine>,
+    /// Context lines after the match.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub after: Vec<GrepLine>,
+}
+
+/// Number of matching lines in a file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GrepFileCount {
+    pub path: String,
+    pub count: usize,
 }
 
 /// Grep results.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GrepResult {
+    /// Matching lines (`content` mode only).
     pub matches: Vec<GrepMatch>,
+    /// Files with matches and their match counts (`files_with_matches`
+    /// and `count` modes only).
+    #[serde(default)]
+    pub files: Vec<GrepFileCount>,
     pub total_matches: usize,
+    /// Whether a result limit was hit, so more matches may exist.
+    #[serde(default)]
+    pub truncated: bool,
+}
+
+/// What `grep` reports.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GrepOutputMode {
+    /// Matching lines, with optional context.
+    #[default]
+    Content,
+    /// Only the paths of files with matches.
+    FilesWithMatches,
+    /// The number of matching lines per file.
+    Count,
+}
+
+/// Options for [`grep`].
+#[derive(Debug, Clone, Default)]
+pub struct GrepOptions {
+    /// Maximum matches (`content` mode) or files (other modes) to return.
+    pub max_results: Option<usize>,
+    /// Maximum matches per file (`content` mode).
+    pub max_per_file: Option<usize>,
+    /// Only search files matching one of these globs. Globs without a `/`
+    /// match file names, others match paths relative to the search root.
+    pub include: Vec<String>,
+    /// Skip files matching any of these globs.
+    pub exclude: Vec<String>,
+    /// Only search these file types (e.g. `rust`, `py`), as in ripgrep's `--type`.
+    pub file_types: Vec<String>,
+    /// Context lines to show before each match.
+    pub before_context: usize,
+    /// Context lines to show after each match.
+    pub after_context: usize,
+    pub case_insensitive: bool,
+    /// Only match whole words.
+    pub word: bool,
+    /// Let matches span lines; `.` then also matches newlines.
+    pub multiline: bool,
+    pub output_mode: GrepOutputMode,
+    pub ignore: IgnoreRules,
 }
 
 /// Safety caps to prevent huge context blowups.
 const GREP_HARD_MAX_MATCHES: usize = 50;
 const GREP_DEFAULT_MAX_MATCHES: usize = 50;
+const GREP_HARD_MAX_FILES: usize = 500;
 const GREP_MAX_MATCHES_PER_FILE: usize = 10;
+const GREP_MAX_CONTEXT_LINES: usize = 10;
 const GREP_MAX_LINE_LENGTH: usize = 512;
 const GREP_MAX_FILE_SIZE_BYTES: u64 = 5 * 1024 * 1024;
 const GREP_MAX_DEPTH: usize = 10;
@@ -373,56 +406,122 @@ fn truncate_line(s: &str, max_chars: usize) -> (String, usize) {
     }
 }
 
+/// Decode a matched or context line for display, truncating long lines.
+fn display_line(bytes: &[u8]) -> String {
+    let raw = String::from_utf8_lossy(bytes);
+    let raw = raw.trim_end_matches(&['\r', '\n'][..]);
+    let (mut line, truncated_chars) = truncate_line(raw, GREP_MAX_LINE_LENGTH);
+    if truncated_chars > 0 {
+        line.push_str(&format!(" [...{} more chars]", truncated_chars));
+    }
+    line
+}
+
+fn sink_line_number(line_number: Option<u64>) -> usize {
+    line_number
+        .and_then(|n| usize::try_from(n).ok())
+        .unwrap_or(0)
+}
+
 struct MatchCollector {
+    mode: GrepOutputMode,
     matches: Vec<GrepMatch>,
+    /// Before-context lines waiting for the next match
+    before: Vec<GrepLine>,
     file_path: String,
     max_matches: usize,
     max_per_file: usize,
-    file_match_count: usize,
+    count: usize,
+    limited: bool,
 }
 
 impl Sink for MatchCollector {
     type Error = io::Error;
 
     fn matched(&mut self, _searcher: &Searcher, mat: &SinkMatch<'_>) -> Result<bool, Self::Error> {
-        if self.matches.len() >= self.max_matches {
-            return Ok(false);
+        if self.mode != GrepOutputMode::Content {
+            self.count += 1;
+            return Ok(true);
         }
 
-        if self.file_match_count >= self.max_per_file {
+        if self.matches.len() >= self.max_matches.min(self.max_per_file) {
+            self.limited = true;
             return Ok(false);
         }
 
-        let raw = String::from_utf8_lossy(mat.bytes());
-        let raw = raw.trim_end_matches(&['\r', '\n'][..]);
-        let (mut line_content, truncated_chars) = truncate_line(raw, GREP_MAX_LINE_LENGTH);
-
-        if truncated_chars > 0 {
-            line_content.push_str(&format!(" [...{} more chars]", truncated_chars));
-        }
-
-        let line_number = mat.line_number().unwrap_or(0);
-        let line_number = usize::try_from(line_number).unwrap_or(0);
-
         self.matches.push(GrepMatch {
             path: self.file_path.clone(),
-            line_number,
-            content: line_content,
+            line_number: sink_line_number(mat.line_number()),
+            content: display_line(mat.bytes()),
+            before: std::mem::take(&mut self.before),
+            after: Vec::new(),
         });
+        self.count += 1;
+        Ok(true)
+    }
 
-        self.file_match_count += 1;
+    fn context(
+        &mut self,
+        _searcher: &Searcher,
+        context: &SinkContext<'_>,
+    ) -> Result<bool, Self::Error> {
+        let line = GrepLine {
+            line_number: sink_line_number(context.line_number()),
+            content: display_line(context.bytes()),
+        };
+        match (context.kind(), self.matches.last_mut()) {
+            (SinkContextKind::After, Some(last)) => last.after.push(line),
+            _ => self.before.push(line),
+        }
         Ok(true)
     }
 }
 
+/// Compile globs for `include` / `exclude` filtering.
+fn build_globset(globs: &[String]) -> Result<Option<GlobSet>, FileError> {
+    if globs.is_empty() {
+        return Ok(None);
+    }
+    let mut builder = GlobSetBuilder::new();
+    for glob in globs {
+        let glob = Glob::new(glob)
+            .map_err(|e| FileError::GrepError(format!("Invalid glob {:?}: {}", glob, e)))?;
+        builder.add(glob);
+    }
+    builder
+        .build()
+        .map(Some)
+        .map_err(|e| FileError::GrepError(e.to_string()))
+}
+
+/// Whether `relative` (or its file name) matches `globs`.
+fn glob_matches(globs: &GlobSet, relative: &Path) -> bool {
+    globs.is_match(relative)
+        || relative
+            .file_name()
+            .is_some_and(|name| globs.is_match(name))
+}
+
 /// Search for a pattern in files.
 pub fn grep(
     pattern: &str,
     directory: &str,
-    max_results: Option<usize>,
+    options: &GrepOptions,
 ) -> Result<GrepResult, FileError> {
-    let requested = max_results.unwrap_or(GREP_DEFAULT_MAX_MATCHES);
-    let max_matches = requested.min(GREP_HARD_MAX_MATCHES);
+    let max_results = match options.output_mode {
+        GrepOutputMode::Content => options
+            .max_results
+            .unwrap_or(GREP_DEFAULT_MAX_MATCHES)
+            .min(GREP_HARD_MAX_MATCHES),
+        _ => options
+            .max_results
+            .unwrap_or(GREP_HARD_MAX_FILES)
+            .min(GREP_HARD_MAX_FILES),
+    };
+    let max_per_file = options
+        .max_per_file
+        .unwrap_or(GREP_MAX_MATCHES_PER_FILE)
+        .clamp(1, GREP_HARD_MAX_MATCHES);
 
     if pattern.is_empty() {
         return Err(FileError::GrepError(
@@ -448,96 +547,115 @@ pub fn grep(
         )));
     }
 
-    // Support a tiny subset of common ripgrep-ish flags embedded in the pattern.
-    let (pattern, case_insensitive) = if let Some(rest) = pattern.strip_prefix("--ignore-case ") {
-        (rest, true)
-    } else if let Some(rest) = pattern.strip_prefix("-i ") {
-        (rest, true)
+    let mut matcher_builder = RegexMatcherBuilder::new();
+    matcher_builder
+        .case_insensitive(options.case_insensitive)
+        .word(options.word);
+    if options.multiline {
+        matcher_builder.multi_line(true).dot_matches_new_line(true);
     } else {
-        (pattern, false)
-    };
-
-    let pattern = pattern.trim();
-    if pattern.is_empty() {
-        return Err(FileError::GrepError(
-            "pattern must not be empty".to_string(),
-        ));
+        matcher_builder.line_terminator(Some(b'\n'));
     }
 
-    let regex_pattern = if case_insensitive {
-        format!("(?i){}", pattern)
-    } else {
-        pattern.to_string()
-    };
-
     // Try regex, then fall back to literal (same behavior as ticca-desktop).
-    let matcher = RegexMatcher::new_line_matcher(&regex_pattern)
-        .or_else(|_| {
-            let escaped = regex::escape(pattern);
-            let escaped_pattern = if case_insensitive {
-                format!("(?i){}", escaped)
-            } else {
-                escaped
-            };
-            RegexMatcher::new_line_matcher(&escaped_pattern)
-        })
+    let matcher = matcher_builder
+        .build(pattern)
+        .or_else(|_| matcher_builder.build(&regex::escape(pattern)))
         .map_err(|e| FileError::GrepError(format!("Invalid search pattern: {}", e)))?;
 
-    let walker = WalkBuilder::new(&abs_path)
-        .hidden(false)
-        .git_ignore(true)
-        .git_global(false)
-        .git_exclude(false)
+    let include = build_globset(&options.include)?;
+    let exclude = build_globset(&options.exclude)?;
+
+    let mut walker = options.ignore.walker(&abs_path);
+    walker
         .max_depth(Some(GREP_MAX_DEPTH))
-        .max_filesize(Some(GREP_MAX_FILE_SIZE_BYTES))
-        .filter_entry(|e| !should_ignore(&e.path().to_string_lossy()))
+        .max_filesize(Some(GREP_MAX_FILE_SIZE_BYTES));
+    if !options.file_types.is_empty() {
+        let mut types = TypesBuilder::new();
+        types.add_defaults();
+        for file_type in &options.file_types {
+            types.select(file_type);
+        }
+        let types = types
+            .build()
+            .map_err(|e| FileError::GrepError(e.to_string()))?;
+        walker.types(types);
+    }
+
+    let mut searcher = SearcherBuilder::new()
+        .line_number(true)
+        .multi_line(options.multiline)
+        .before_context(options.before_context.min(GREP_MAX_CONTEXT_LINES))
+        .after_context(options.after_context.min(GREP_MAX_CONTEXT_LINES))
+        .binary_detection(BinaryDetection::quit(b'\x00'))
         .build();
 
-    let mut searcher = Searcher::new();
-    let mut matches: Vec<GrepMatch> = Vec::new();
+    let mut result = GrepResult {
+        matches: Vec::new(),
+        files: Vec::new(),
+        total_matches: 0,
+        truncated: false,
+    };
 
-    for entry in walker.flatten() {
-        if matches.len() >= max_matches {
+    for entry in walker.build().flatten() {
+        let found = match options.output_mode {
+            GrepOutputMode::Content => result.matches.len(),
+            _ => result.files.len(),
+        };
+        if found >= max_results {
+            result.truncated = true;
             break;
         }
 
-        let entry_path = entry.path();
-
         let is_file = entry.file_type().map(|ft| ft.is_file()).unwrap_or(false);
         if !is_file {
             continue;
         }
 
-        let entry_path_str = entry_path.to_string_lossy().to_string();
-        if should_ignore(&entry_path_str) || !is_text_file(&entry_path_str) {
+        let entry_path = entry.path();
+        let relative = entry_path.strip_prefix(&abs_path).unwrap_or(entry_path);
+        if include
+            .as_ref()
+            .is_some_and(|globs| !glob_matches(globs, relative))
+            || exclude
+                .as_ref()
+                .is_some_and(|globs| glob_matches(globs, relative))
+        {
             continue;
         }
 
-        let relative_path = entry_path
-            .strip_prefix(&abs_path)
-            .map(|p| p.to_string_lossy().to_string())
-            .unwrap_or_else(|_| entry_path_str.clone());
-
+        let relative_path = relative.to_string_lossy().to_string();
         let mut collector = MatchCollector {
+            mode: options.output_mode,
             matches: Vec::new(),
-            file_path: relative_path,
-            max_matches: max_matches - matches.len(),
-            max_per_file: GREP_MAX_MATCHES_PER_FILE,
-            file_match_count: 0,
+            before: Vec::new(),
+            file_path: relative_path.clone(),
+            max_matches: max_results - found,
+            max_per_file,
+            count: 0,
+            limited: false,
         };
 
         if searcher
             .search_path(&matcher, entry_path, &mut collector)
-            .is_ok()
+            .is_err()
+            || collector.count == 0
         {
-            matches.extend(collector.matches);
+            continue;
+        }
+
+        result.total_matches += collector.count;
+        result.truncated |= collector.limited;
+        match options.output_mode {
+            GrepOutputMode::Content => result.matches.extend(collector.matches),
+            _ => result.files.push(GrepFileCount {
+                path: relative_path,
+                count: collector.count,
+            }),
         }
     }
 
-    Ok(GrepResult {
-        total_matches: matches.len(),
-        matches,
-    })
+    Ok(result)
 }
 
 #[cfg(test)]
@@ -550,7 +668,8 @@ mod tests {
         let file_path = dir.path().join("a.txt");
         fs::write(&file_path, "foo\nbar\nfoo\n").expect("write failed");
 
-        let result = grep("foo", dir.path().to_str().unwrap(), None).expect("grep failed");
+        let result = grep("foo", dir.path().to_str().unwrap(), &GrepOptions::default())
+            .expect("grep failed");
         assert_eq!(result.total_matches, 2);
 
         assert!(result.matches[0].path.ends_with("a.txt"));
@@ -568,7 +687,15 @@ mod tests {
         let file_path = dir.path().join("a.txt");
         fs::write(&file_path, "foo\nfoo\nfoo\n").expect("write failed");
 
-        let result = grep("foo", dir.path().to_str().unwrap(), Some(1)).expect("grep failed");
+        let result = grep(
+            "foo",
+            dir.path().to_str().unwrap(),
+            &GrepOptions {
+                max_results: Some(1),
+                ..Default::default()
+            },
+        )
+        .expect("grep failed");
         assert_eq!(result.total_matches, 1);
     }
 
@@ -582,18 +709,221 @@ mod tests {
         fs::create_dir_all(&ignored_dir).expect("mkdir failed");
         fs::write(ignored_dir.join("b.txt"), "foo\n").expect("write failed");
 
-        let result = grep("foo", dir.path().to_str().unwrap(), None).expect("grep failed");
+        let result = grep("foo", dir.path().to_str().unwrap(), &GrepOptions::default())
+            .expect("grep failed");
         assert_eq!(result.total_matches, 1);
         assert!(result.matches[0].path.ends_with("a.txt"));
     }
 
+    #[test]
+    fn grep_honors_gitignore_unless_ignored_files_are_included() {
+        let dir = tempfile::tempdir().expect("tempdir failed");
+        fs::write(dir.path().join(".gitignore"), "generated/\n").expect("write failed");
+        fs::write(dir.path().join("a.txt"), "foo\n").expect("write failed");
+        fs::create_dir_all(dir.path().join("generated")).expect("mkdir failed");
+        fs::write(dir.path().join("generated").join("b.txt"), "foo\n").expect("write failed");
+
+        let root = dir.path().to_str().unwrap();
+        let result = grep("foo", root, &GrepOptions::default()).expect("grep failed");
+        assert_eq!(result.total_matches, 1);
+        assert!(result.matches[0].path.ends_with("a.txt"));
+
+        let options = GrepOptions {
+            ignore: IgnoreRules::default().with_include_ignored(true),
+            ..Default::default()
+        };
+        let result = grep("foo", root, &options).expect("grep failed");
+        assert_eq!(result.total_matches, 2);
+    }
+
+    #[test]
+    fn grep_reports_truncation_at_per_file_cap() {
+        let dir = tempfile::tempdir().expect("tempdir failed");
+        fs::write(dir.path().join("a.txt"), "foo\n".repeat(5)).expect("write failed");
+
+        let root = dir.path().to_str().unwrap();
+        let result = grep("foo", root, &GrepOptions::default()).expect("grep failed");
+        assert_eq!(result.total_matches, 5);
+        assert!(!result.truncated);
+
+        let options = GrepOptions {
+            max_per_file: Some(2),
+            ..Default::default()
+        };
+        let result = grep("foo", root, &options).expect("grep failed");
+        assert_eq!(result.total_matches, 2);
+        assert!(result.truncated);
+    }
+
+    #[test]
+    fn grep_collects_context_lines() {
+        let dir = tempfile::tempdir().expect("tempdir failed");
+        fs::write(dir.path().join("a.txt"), "one\ntwo\nfoo\nthree\nfour\n").expect("write failed");
+
+        let options = GrepOptions {
+            before_context: 2,
+            after_context: 1,
+            ..Default::default()
+        };
+        let result = grep("foo", dir.path().to_str().unwrap(), &options).expect("grep failed");
+        let m = &result.matches[0];
+        let before: Vec<(usize, &str)> = m
+            .before
+            .iter()
+            .map(|l| (l.line_number, l.content.as_str()))
+            .collect();
+        let after: Vec<(usize, &str)> = m
+            .after
+            .iter()
+            .map(|l| (l.line_number, l.content.as_str()))
+            .collect();
+        assert_eq!(before, vec![(1, "one"), (2, "two")]);
+        assert_eq!(after, vec![(4, "three")]);
+    }
+
+    #[test]
+    fn grep_include_and_exclude_globs() {
+        let dir = tempfile::tempdir().expect("tempdir failed");
+        fs::create_dir_all(dir.path().join("src")).expect("mkdir failed");
+        fs::create_dir_all(dir.path().join("tests")).expect("mkdir failed");
+        fs::write(dir.path().join("src").join("lib.rs"), "foo\n").expect("write failed");
+        fs::write(dir.path().join("tests").join("it.rs"), "foo\n").expect("write failed");
+        fs::write(dir.path().join("notes.md"), "foo\n").expect("write failed");
+
+        let options = GrepOptions {
+            include: vec!["*.rs".to_string()],
+            exclude: vec!["tests/**".to_string()],
+            ..Default::default()
+        };
+        let result = grep("foo", dir.path().to_str().unwrap(), &options).expect("grep failed");
+        assert_eq!(result.total_matches, 1);
+        assert_eq!(result.matches[0].path, "src/lib.rs");
+    }
+
+    #[test]
+    fn grep_rejects_invalid_glob_and_unknown_file_type() {
+        let dir = tempfile::tempdir().expect("tempdir failed");
+        let root = dir.path().to_str().unwrap();
+
+        let options = GrepOptions {
+            include: vec!["[".to_string()],
+            ..Default::default()
+        };
+        assert!(grep("foo", root, &options).is_err());
+
+        let options = GrepOptions {
+            file_types: vec!["not-a-type".to_string()],
+            ..Default::default()
+        };
+        assert!(grep("foo", root, &options).is_err());
+    }
+
+    #[test]
+    fn grep_filters_by_file_type() {
+        let dir = tempfile::tempdir().expect("tempdir failed");
+        fs::write(dir.path().join("a.rs"), "foo\n").expect("write failed");
+        fs::write(dir.path().join("b.py"), "foo\n").expect("write failed");
+
+        let options = GrepOptions {
+            file_types: vec!["py".to_string()],
+            ..Default::default()
+        };
+        let result = grep("foo", dir.path().to_str().unwrap(), &options).expect("grep failed");
+        assert_eq!(result.total_matches, 1);
+        assert_eq!(result.matches[0].path, "b.py");
+    }
+
+    #[test]
+    fn grep_case_and_word_flags() {
+        let dir = tempfile::tempdir().expect("tempdir failed");
+        fs::write(dir.path().join("a.txt"), "Foo\nfoobar\nfoo bar\n").expect("write failed");
+        let root = dir.path().to_str().unwrap();
+
+        let result = grep("foo", root, &GrepOptions::default()).expect("grep failed");
+        assert_eq!(result.total_matches, 2);
+
+        let options = GrepOptions {
+            case_insensitive: true,
+            ..Default::default()
+        };
+        let result = grep("foo", root, &options).expect("grep failed");
+        assert_eq!(result.total_matches, 3);
+
+        let options = GrepOptions {
+            case_insensitive: true,
+            word: true,
+            ..Default::default()
+        };
+        let result = grep("foo", root, &options).expect("grep failed");
+        let lines: Vec<usize> = result.matches.iter().map(|m| m.line_number).collect();
+        assert_eq!(lines, vec![1, 3]);
+    }
+
+    #[test]
+    fn grep_multiline_matches_span_lines() {
+        let dir = tempfile::tempdir().expect("tempdir failed");
+        fs::write(dir.path().join("a.rs"), "fn main() {\n    run();\n}\n").expect("write failed");
+        let root = dir.path().to_str().unwrap();
+
+        let result = grep(r"main\(\) \{\n\s+run", root, &GrepOptions::default());
+        assert!(result.map(|r| r.total_matches == 0).unwrap_or(true));
+
+        let options = GrepOptions {
+            multiline: true,
+            ..Default::default()
+        };
+        let result = grep(r"main\(\) \{\n\s+run", root, &options).expect("grep failed");
+        assert_eq!(result.total_matches, 1);
+        assert_eq!(result.matches[0].line_number, 1);
+        assert_eq!(result.matches[0].content, "fn main() {\n    run();");
+    }
+
+    #[test]
+    fn grep_files_with_matches_and_count_modes() {
+        let dir = tempfile::tempdir().expect("tempdir failed");
+        fs::write(dir.path().join("a.txt"), "foo\nfoo\nbar\n").expect("write failed");
+        fs::write(dir.path().join("b.txt"), "foo\n").expect("write failed");
+        fs::write(dir.path().join("c.txt"), "bar\n").expect("write failed");
+        let root = dir.path().to_str().unwrap();
+
+        for mode in [GrepOutputMode::FilesWithMatches, GrepOutputMode::Count] {
+            let options = GrepOptions {
+                output_mode: mode,
+                ..Default::default()
+            };
+            let mut result = grep("foo", root, &options).expect("grep failed");
+            result.files.sort_by(|a, b| a.path.cmp(&b.path));
+            assert!(result.matches.is_empty());
+            let files: Vec<(&str, usize)> = result
+                .files
+                .iter()
+                .map(|f| (f.path.as_str(), f.count))
+                .collect();
+            assert_eq!(files, vec![("a.txt", 2), ("b.txt", 1)]);
+            assert_eq!(result.total_matches, 3);
+        }
+    }
+
+    #[test]
+    fn grep_searches_unrecognized_extensions_but_skips_binary_files() {
+        let dir = tempfile::tempdir().expect("tempdir failed");
+        fs::write(dir.path().join("query.prisma"), "foo\n").expect("write failed");
+        fs::write(dir.path().join("blob.bin"), b"foo\n\x00\x01").expect("write failed");
+
+        let result = grep("foo", dir.path().to_str().unwrap(), &GrepOptions::default())
+            .expect("grep failed");
+        assert_eq!(result.total_matches, 1);
+        assert_eq!(result.matches[0].path, "query.prisma");
+    }
+
     #[test]
     fn grep_falls_back_to_literal_on_invalid_regex() {
         let dir = tempfile::tempdir().expect("tempdir failed");
         let file_path = dir.path().join("a.txt");
         fs::write(&file_path, "(paren)\n").expect("write failed");
 
-        let result = grep("(", dir.path().to_str().unwrap(), None).expect("grep failed");
+        let result =
+            grep("(", dir.path().to_str().unwrap(), &GrepOptions::default()).expect("grep failed");
         assert_eq!(result.total_matches, 1);
         assert!(result.matches[0].content.contains("(paren)"));
     }
@@ -604,14 +934,44 @@ mod tests {
         fs::write(dir.path().join("a.txt"), "a").expect("write failed");
         fs::write(dir.path().join("b.txt"), "b").expect("write failed");
 
-        let result = list_files(dir.path().to_str().unwrap(), false, Some(1), Some(1))
-            .expect("list_files failed");
+        let result = list_files(
+            dir.path().to_str().unwrap(),
+            false,
+            Some(1),
+            Some(1),
+            &IgnoreRules::default(),
+        )
+        .expect("list_files failed");
 
         assert_eq!(result.entries.len(), 1);
         assert!(result.truncated);
         assert_eq!(result.max_entries, 1);
     }
 
+    #[test]
+    fn list_files_honors_gitignore_and_extra_patterns() {
+        let dir = tempfile::tempdir().expect("tempdir failed");
+        fs::write(dir.path().join(".gitignore"), "*.log\n").expect("write failed");
+        fs::write(dir.path().join("app.log"), "log").expect("write failed");
+        fs::write(dir.path().join("main.rs"), "fn main() {}").expect("write failed");
+        fs::write(dir.path().join("schema.gen.rs"), "").expect("write failed");
+        fs::create_dir_all(dir.path().join("build")).expect("mkdir failed");
+        fs::write(dir.path().join("build").join("script.rs"), "").expect("write failed");
+
+        let root = dir.path().to_str().unwrap();
+        let rules = IgnoreRules::new(vec!["*.gen.rs".to_string()]);
+        let result = list_files(root, true, None, None, &rules).expect("list_files failed");
+        let paths: Vec<&str> = result.entries.iter().map(|e| e.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            vec![".gitignore", "build", "build/script.rs", "main.rs"]
+        );
+
+        let rules = rules.with_include_ignored(true);
+        let result = list_files(root, true, None, None, &rules).expect("list_files failed");
+        assert_eq!(result.total_files, 5);
+    }
+
     #[test]
     fn read_file_rejects_large_token_count() {
         // Create a temp file with content exceeding token limit
@@ -686,7 +1046,14 @@ mod tests {
         fs::write(dir.path().join("root.txt"), "root").expect("write failed");
         fs::write(dir.path().join("sub").join("child.txt"), "child").expect("write failed");
 
-        let result = list_files(dir.path().to_str().unwrap(), true, None, None).unwrap();
+        let result = list_files(
+            dir.path().to_str().unwrap(),
+            true,
+            None,
+            None,
+            &IgnoreRules::default(),
+        )
+        .unwrap();
 
         let root_entry = result
             .entries
@@ -0,0 +1,221 @@
+//! FindDefinition tool implementation.
+//!
+//! Provides a serdesAI-compatible tool for locating where a symbol is defined.
+
+use async_trait::async_trait;
+use serde::Deserialize;
+use serde_json::Value as JsonValue;
+use tracing::{debug, info_span};
+
+use serdes_ai_tools::{RunContext, SchemaBuilder, Tool, ToolDefinition, ToolResult, ToolReturn};
+
+use super::common::IgnoreRules;
+use super::symbols;
+
+/// Default and maximum number of definitions returned.
+const DEFAULT_MAX_RESULTS: usize = 20;
+const MAX_RESULTS_LIMIT: usize = 50;
+
+/// Tool for finding symbol definitions across a directory.
+#[derive(Debug, Clone, Default)]
+pub struct FindDefinitionTool;
+
+#[derive(Debug, Deserialize)]
+struct FindDefinitionArgs {
+    name: String,
+    directory: Option<String>,
+    kind: Option<String>,
+    max_results: Option<usize>,
+}
+
+#[async_trait]
+impl Tool for FindDefinitionTool {
+    fn definition(&self) -> ToolDefinition {
+        ToolDefinition::new(
+            "find_definition",
+            "Find where a function, method, type, class or other symbol is defined, \
+             using a syntax-aware parse instead of a text search. Returns the file, \
+             line range, kind and signature of each definition. Supports Rust, Python, \
+             JavaScript, TypeScript, Go, Java and C files; respects .gitignore/.ignore rules.",
+        )
+        .with_parameters(
+            SchemaBuilder::new()
+                .string(
+                    "name",
+                    "Symbol name. Qualify with the enclosing type to narrow the search, \
+                     e.g. 'Parser::parse' or 'Client.fetch'.",
+                    true,
+                )
+                .string(
+                    "directory",
+                    "Root directory to search. Defaults to '.'.",
+                    false,
+                )
+                .string(
+                    "kind",
+                    "Only return definitions of this kind, e.g. 'function', 'method', \
+                     'struct', 'class', 'interface', 'trait', 'enum', 'type'.",
+                    false,
+                )
+                .integer(
+                    "max_results",
+                    "Maximum number of definitions to return. Defaults to 20, max 50.",
+                    false,
+                )
+                .build()
+                .expect("schema build failed"),
+        )
+    }
+
+    async fn call(&self, _ctx: &RunContext, args: JsonValue) -> ToolResult {
+        debug!(tool = "find_definition", ?args, "Tool called");
+
+        let args: FindDefinitionArgs = crate::tools::common::parse_tool_args_lenient(
+            "find_definition",
+            args.clone(),
+            self.definition().parameters(),
+        )?;
+
+        let directory = args.directory.as_deref().unwrap_or(".");
+        let _span =
+            info_span!("find_definition", name = %args.name, directory = %directory).entered();
+
+        let max_results = args
+            .max_results
+            .unwrap_or(DEFAULT_MAX_RESULTS)
+            .clamp(1, MAX_RESULTS_LIMIT);
+
+        match symbols::find_definitions(
+            &args.name,
+            args.kind.as_deref(),
+            directory,
+            &IgnoreRules::from_global_context(),
+            max_results,
+        ) {
+            Ok(search) if search.results.is_empty() => Ok(ToolReturn::text(format!(
+                "No definitions of '{}' found in {} ({} source files searched)",
+                args.name, directory, search.files_searched
+            ))),
+            Ok(search) => {
+                debug!(count = search.results.len(), "Find definition completed");
+
+                let mut output = format!(
+                    "Found {} definitions of '{}' in {}:\n",
+                    search.results.len(),
+                    args.name,
+                    directory
+                );
+                for symbol in &search.results {
+                    let container = symbol
+                        .container
+                        .as_ref()
+                        .map(|c| format!(" in {}", c))
+                        .unwrap_or_default();
+                    output.push_str(&format!(
+                        "\n{}:{}-{}: {} {}{}\n    {}",
+                        symbol.path,
+                        symbol.line,
+                        symbol.end_line,
+                        symbol.kind,
+                        symbol.name,
+                        container,
+                        symbol.signature
+                    ));
+                }
+                if search.truncated {
+                    output.push_str(
+                        "\n\n[Results truncated - qualify the name, set kind or narrow the directory]",
+                    );
+                }
+                Ok(ToolReturn::text(output))
+            }
+            Err(e) => Ok(ToolReturn::error(format!("Find definition failed: {}", e))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_definition_returns_correct_name() {
+        let def = FindDefinitionTool.definition();
+        assert_eq!(def.name(), "find_definition");
+        let schema_str = serde_json::to_string(def.parameters()).unwrap();
+        assert!(schema_str.contains("name"));
+        assert!(schema_str.contains("kind"));
+    }
+
+    #[tokio::test]
+    async fn test_call_finds_definition() {
+        let dir = tempfile::tempdir().expect("tempdir failed");
+        fs::write(
+            dir.path().join("server.py"),
+            "class Server:\n    def start(self):\n        pass\n\nserver = Server()\nserver.start()\n",
+        )
+        .unwrap();
+
+        let ctx = RunContext::minimal("test");
+        let result = FindDefinitionTool
+            .call(
+                &ctx,
+                serde_json::json!({
+                    "name": "Server.start",
+                    "directory": dir.path().to_str().unwrap()
+                }),
+            )
+            .await
+            .unwrap();
+
+        let text = result.as_text().unwrap();
+        assert!(text.contains("Found 1 definitions"));
+        assert!(text.contains("server.py:2-3: method start in Server"));
+        assert!(text.contains("    def start(self):"));
+    }
+
+    #[tokio::test]
+    async fn test_call_no_definitions() {
+        let dir = tempfile::tempdir().expect("tempdir failed");
+        fs::write(
+            dir.path().join("main.go"),
+            "package main\n\nfunc main() {}\n",
+        )
+        .unwrap();
+
+        let ctx = RunContext::minimal("test");
+        let result = FindDefinitionTool
+            .call(
+                &ctx,
+                serde_json::json!({
+                    "name": "missing",
+                    "directory": dir.path().to_str().unwrap()
+                }),
+            )
+            .await
+            .unwrap();
+
+        assert!(result
+            .as_text()
+            .unwrap()
+            .contains("No definitions of 'missing' found"));
+    }
+
+    #[tokio::test]
+    async fn test_call_invalid_directory() {
+        let ctx = RunContext::minimal("test");
+        let result = FindDefinitionTool
+            .call(
+                &ctx,
+                serde_json::json!({
+                    "name": "main",
+                    "directory": "/nonexistent/path/xyz123abc"
+                }),
+            )
+            .await
+            .unwrap();
+
+        assert!(result.is_error());
+    }
+}
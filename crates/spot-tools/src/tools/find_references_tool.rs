@@ -0,0 +1,183 @@
+//! FindReferences tool implementation.
+//!
+//! Provides a serdesAI-compatible tool for locating uses of a symbol.
+
+use async_trait::async_trait;
+use serde::Deserialize;
+use serde_json::Value as JsonValue;
+use tracing::{debug, info_span};
+
+use serdes_ai_tools::{RunContext, SchemaBuilder, Tool, ToolDefinition, ToolResult, ToolReturn};
+
+use super::common::IgnoreRules;
+use super::symbols;
+
+/// Default and maximum number of references returned.
+const DEFAULT_MAX_RESULTS: usize = 50;
+const MAX_RESULTS_LIMIT: usize = 200;
+
+/// Tool for finding identifier references across a directory.
+#[derive(Debug, Clone, Default)]
+pub struct FindReferencesTool;
+
+#[derive(Debug, Deserialize)]
+struct FindReferencesArgs {
+    name: String,
+    directory: Option<String>,
+    max_results: Option<usize>,
+}
+
+#[async_trait]
+impl Tool for FindReferencesTool {
+    fn definition(&self) -> ToolDefinition {
+        ToolDefinition::new(
+            "find_references",
+            "Find every identifier with the given name, using a syntax-aware parse: \
+             matches in strings, comments and longer identifiers are skipped, and \
+             definitions are marked. Supports Rust, Python, JavaScript, TypeScript, Go, \
+             Java and C files; respects .gitignore/.ignore rules.",
+        )
+        .with_parameters(
+            SchemaBuilder::new()
+                .string("name", "Exact identifier to look for.", true)
+                .string(
+                    "directory",
+                    "Root directory to search. Defaults to '.'.",
+                    false,
+                )
+                .integer(
+                    "max_results",
+                    "Maximum number of references to return. Defaults to 50, max 200.",
+                    false,
+                )
+                .build()
+                .expect("schema build failed"),
+        )
+    }
+
+    async fn call(&self, _ctx: &RunContext, args: JsonValue) -> ToolResult {
+        debug!(tool = "find_references", ?args, "Tool called");
+
+        let args: FindReferencesArgs = crate::tools::common::parse_tool_args_lenient(
+            "find_references",
+            args.clone(),
+            self.definition().parameters(),
+        )?;
+
+        let directory = args.directory.as_deref().unwrap_or(".");
+        let _span =
+            info_span!("find_references", name = %args.name, directory = %directory).entered();
+
+        let max_results = args
+            .max_results
+            .unwrap_or(DEFAULT_MAX_RESULTS)
+            .clamp(1, MAX_RESULTS_LIMIT);
+
+        match symbols::find_references(
+            &args.name,
+            directory,
+            &IgnoreRules::from_global_context(),
+            max_results,
+        ) {
+            Ok(search) if search.results.is_empty() => Ok(ToolReturn::text(format!(
+                "No references to '{}' found in {} ({} source files searched)",
+                args.name, directory, search.files_searched
+            ))),
+            Ok(search) => {
+                debug!(count = search.results.len(), "Find references completed");
+
+                let mut output = format!(
+                    "Found {} references to '{}' in {}:\n",
+                    search.results.len(),
+                    args.name,
+                    directory
+                );
+                for reference in &search.results {
+                    output.push_str(&format!(
+                        "\n{}:{}:{}:{} {}",
+                        reference.path,
+                        reference.line,
+                        reference.column,
+                        if reference.is_definition {
+                            " [definition]"
+                        } else {
+                            ""
+                        },
+                        reference.content
+                    ));
+                }
+                if search.truncated {
+                    output.push_str(
+                        "\n\n[Results truncated - narrow the directory or raise max_results]",
+                    );
+                }
+                Ok(ToolReturn::text(output))
+            }
+            Err(e) => Ok(ToolReturn::error(format!("Find references failed: {}", e))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_definition_returns_correct_name() {
+        let def = FindReferencesTool.definition();
+        assert_eq!(def.name(), "find_references");
+        let schema_str = serde_json::to_string(def.parameters()).unwrap();
+        assert!(schema_str.contains("max_results"));
+    }
+
+    #[tokio::test]
+    async fn test_call_marks_definitions() {
+        let dir = tempfile::tempdir().expect("tempdir failed");
+        fs::write(
+            dir.path().join("app.js"),
+            "function render() {}\n// render later\nrender();\n",
+        )
+        .unwrap();
+
+        let ctx = RunContext::minimal("test");
+        let result = FindReferencesTool
+            .call(
+                &ctx,
+                serde_json::json!({
+                    "name": "render",
+                    "directory": dir.path().to_str().unwrap()
+                }),
+            )
+            .await
+            .unwrap();
+
+        let text = result.as_text().unwrap();
+        assert!(text.contains("Found 2 references"));
+        assert!(text.contains("app.js:1:10: [definition] function render() {}"));
+        assert!(text.contains("app.js:3:1: render();"));
+    }
+
+    #[tokio::test]
+    async fn test_call_truncates() {
+        let dir = tempfile::tempdir().expect("tempdir failed");
+        fs::write(dir.path().join("a.py"), "x = 1\nx = x + 1\n").unwrap();
+
+        let ctx = RunContext::minimal("test");
+        let result = FindReferencesTool
+            .call(
+                &ctx,
+                serde_json::json!({
+                    "name": "x",
+                    "directory": dir.path().to_str().unwrap(),
+                    "max_results": 2
+                }),
+            )
+            .await
+            .unwrap();
+
+        let text = result.as_text().unwrap();
+        assert!(text.contains("Found 2 references"));
+        assert!(text.contains("[Results truncated"));
+    }
+}
@@ -0,0 +1,776 @@
+//! Read-only git queries.
+//!
+//! Runs the `git` CLI and parses its machine-readable output into
+//! serializable structs. Revisions that look like options are rejected and
+//! paths always follow `--`, so no argument can turn a query into a write
+//! (e.g. `git diff --output=<file>`).
+
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::terminal::headless_env;
+
+/// Maximum characters of patch or file content returned.
+const MAX_CONTENT_CHARS: usize = 50_000;
+/// Default and maximum number of commits returned by [`log`].
+pub const DEFAULT_LOG_COUNT: usize = 20;
+pub const MAX_LOG_COUNT: usize = 100;
+/// Maximum lines returned by [`blame`].
+const MAX_BLAME_LINES: usize = 500;
+
+/// Git query errors.
+#[derive(Debug, Error)]
+pub enum GitError {
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("git is not installed or not on PATH")]
+    GitNotFound,
+    #[error("Not a git repository: {0}")]
+    NotARepository(String),
+    #[error("Invalid argument: {0}")]
+    InvalidArgument(String),
+    #[error("git {command} failed: {stderr}")]
+    Command { command: String, stderr: String },
+}
+
+/// Run `git` in `repo` and return its stdout.
+fn run_git(repo: &str, args: &[&str]) -> Result<String, GitError> {
+    if !Path::new(repo).is_dir() {
+        return Err(GitError::NotARepository(repo.to_string()));
+    }
+
+    let mut cmd = Command::new("git");
+    cmd.arg("-C")
+        .arg(repo)
+        .args([
+            "--no-pager",
+            "-c",
+            "color.ui=false",
+            "-c",
+            "core.quotepath=false",
+        ])
+        .args(args);
+    for (key, value) in headless_env() {
+        cmd.env(key, value);
+    }
+    // Don't refresh the index in passing; these queries must not write
+    cmd.env("GIT_OPTIONAL_LOCKS", "0");
+
+    let output = match cmd
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .output()
+    {
+        Ok(output) => output,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Err(GitError::GitNotFound),
+        Err(e) => return Err(e.into()),
+    };
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+        if stderr.contains("not a git repository") {
+            return Err(GitError::NotARepository(repo.to_string()));
+        }
+        return Err(GitError::Command {
+            command: args.first().unwrap_or(&"").to_string(),
+            stderr,
+        });
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Reject revisions git would parse as options.
+fn check_rev(rev: &str) -> Result<(), GitError> {
+    if rev.is_empty() || rev.starts_with('-') {
+        return Err(GitError::InvalidArgument(format!(
+            "invalid revision '{}'",
+            rev
+        )));
+    }
+    Ok(())
+}
+
+/// Cut `content` to [`MAX_CONTENT_CHARS`] at a line boundary.
+fn truncate_content(content: String) -> (String, bool) {
+    if content.len() <= MAX_CONTENT_CHARS {
+        return (content, false);
+    }
+    let mut end = MAX_CONTENT_CHARS;
+    while !content.is_char_boundary(end) {
+        end -= 1;
+    }
+    let end = content[..end].rfind('\n').map_or(end, |i| i + 1);
+    (content[..end].to_string(), true)
+}
+
+// ============================================================================
+// Status
+// ============================================================================
+
+/// Working tree status.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GitStatus {
+    /// Current branch; `None` when HEAD is detached.
+    pub branch: Option<String>,
+    pub upstream: Option<String>,
+    pub ahead: usize,
+    pub behind: usize,
+    pub clean: bool,
+    pub files: Vec<StatusEntry>,
+}
+
+/// A changed, untracked or conflicted path.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StatusEntry {
+    pub path: String,
+    /// Source path of a rename or copy.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub orig_path: Option<String>,
+    /// Change in the index: `modified`, `added`, `deleted`, `renamed`, ...
+    pub staged: String,
+    /// Change in the working tree not yet staged.
+    pub unstaged: String,
+}
+
+fn status_word(code: u8) -> &'static str {
+    match code {
+        b' ' => "unmodified",
+        b'M' => "modified",
+        b'T' => "type_changed",
+        b'A' => "added",
+        b'D' => "deleted",
+        b'R' => "renamed",
+        b'C' => "copied",
+        b'U' => "unmerged",
+        b'?' => "untracked",
+        b'!' => "ignored",
+        _ => "unknown",
+    }
+}
+
+/// Parse the `## ...` header of `git status --branch`.
+fn parse_branch_header(header: &str, status: &mut GitStatus) {
+    let (refs, tracking) = match header.split_once(" [") {
+        Some((refs, tracking)) => (refs, tracking.trim_end_matches(']')),
+        None => (header, ""),
+    };
+    let refs = refs
+        .strip_prefix("No commits yet on ")
+        .or_else(|| refs.strip_prefix("Initial commit on "))
+        .unwrap_or(refs);
+    if refs.starts_with("HEAD (no branch)") {
+        return;
+    }
+
+    match refs.split_once("...") {
+        Some((branch, upstream)) => {
+            status.branch = Some(branch.to_string());
+            status.upstream = Some(upstream.to_string());
+        }
+        None => status.branch = Some(refs.to_string()),
+    }
+
+    for part in tracking.split(", ") {
+        if let Some(n) = part.strip_prefix("ahead ") {
+            status.ahead = n.parse().unwrap_or(0);
+        } else if let Some(n) = part.strip_prefix("behind ") {
+            status.behind = n.parse().unwrap_or(0);
+        }
+    }
+}
+
+/// Parse `git status --porcelain=v1 --branch -z`.
+fn parse_status(output: &str) -> GitStatus {
+    let mut status = GitStatus::default();
+    let mut records = output.split('\0');
+
+    while let Some(record) = records.next() {
+        if let Some(header) = record.strip_prefix("## ") {
+            parse_branch_header(header, &mut status);
+            continue;
+        }
+        let (Some(&[x, y, b' ']), Some(path)) = (record.as_bytes().get(..3), record.get(3..))
+        else {
+            continue;
+        };
+        // Renames and copies are followed by their source path
+        let orig_path = if matches!(x, b'R' | b'C') || matches!(y, b'R' | b'C') {
+            records.next().map(str::to_string)
+        } else {
+            None
+        };
+        status.files.push(StatusEntry {
+            path: path.to_string(),
+            orig_path,
+            staged: status_word(x).to_string(),
+            unstaged: status_word(y).to_string(),
+        });
+    }
+
+    status.clean = status.files.is_empty();
+    status
+}
+
+/// Status of the working tree, including untracked files.
+pub fn status(repo: &str) -> Result<GitStatus, GitError> {
+    let output = run_git(
+        repo,
+        &[
+            "status",
+            "--porcelain=v1",
+            "--branch",
+            "--untracked-files=all",
+            "-z",
+        ],
+    )?;
+    Ok(parse_status(&output))
+}
+
+// ============================================================================
+// Diff
+// ============================================================================
+
+/// What to compare in [`diff`].
+#[derive(Debug, Clone, Default)]
+pub struct DiffOptions {
+    /// Compare the index instead of the working tree.
+    pub staged: bool,
+    /// Compare against this revision instead of the index (or HEAD when staged).
+    pub base: Option<String>,
+    /// Limit the diff to these paths.
+    pub paths: Vec<String>,
+    /// Lines of context around each hunk (git's default is 3).
+    pub context_lines: Option<usize>,
+}
+
+/// Line counts for one file in a diff.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiffFile {
+    pub path: String,
+    /// Source path of a rename or copy.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub orig_path: Option<String>,
+    pub additions: usize,
+    pub deletions: usize,
+    pub binary: bool,
+}
+
+/// A diff with per-file stats and the unified patch.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitDiff {
+    pub files: Vec<DiffFile>,
+    pub additions: usize,
+    pub deletions: usize,
+    pub patch: String,
+    /// Whether the patch was cut short.
+    pub truncated: bool,
+}
+
+/// Parse `--numstat -z` output.
+fn parse_numstat(output: &str) -> Vec<DiffFile> {
+    let mut files = Vec::new();
+    let mut records = output.split('\0');
+
+    while let Some(record) = records.next() {
+        let mut fields = record.trim_start_matches('\n').splitn(3, '\t');
+        let (Some(added), Some(deleted), Some(path)) =
+            (fields.next(), fields.next(), fields.next())
+        else {
+            continue;
+        };
+        // Renames leave the path empty and follow with source and destination
+        let (path, orig_path) = if path.is_empty() {
+            let orig_path = records.next().unwrap_or_default();
+            let path = records.next().unwrap_or_default();
+            (path.to_string(), Some(orig_path.to_string()))
+        } else {
+            (path.to_string(), None)
+        };
+        files.push(DiffFile {
+            path,
+            orig_path,
+            additions: added.parse().unwrap_or(0),
+            deletions: deleted.parse().unwrap_or(0),
+            binary: added == "-",
+        });
+    }
+
+    files
+}
+
+/// Diff the working tree, the index or a revision.
+pub fn diff(repo: &str, options: &DiffOptions) -> Result<GitDiff, GitError> {
+    let mut selection: Vec<&str> = Vec::new();
+    if options.staged {
+        selection.push("--cached");
+    }
+    if let Some(base) = &options.base {
+        check_rev(base)?;
+        selection.push(base);
+    }
+    selection.push("--");
+    selection.extend(options.paths.iter().map(String::as_str));
+
+    let mut args = vec!["diff", "--no-ext-diff", "--numstat", "-z"];
+    args.extend(&selection);
+    let files = parse_numstat(&run_git(repo, &args)?);
+
+    let context = format!("-U{}", options.context_lines.unwrap_or(3));
+    let mut args = vec!["diff", "--no-ext-diff", "--patch", context.as_str()];
+    args.extend(&selection);
+    let (patch, truncated) = truncate_content(run_git(repo, &args)?);
+
+    Ok(GitDiff {
+        additions: files.iter().map(|f| f.additions).sum(),
+        deletions: files.iter().map(|f| f.deletions).sum(),
+        files,
+        patch,
+        truncated,
+    })
+}
+
+// ============================================================================
+// Log
+// ============================================================================
+
+/// Filters for [`log`].
+#[derive(Debug, Clone, Default)]
+pub struct LogOptions {
+    /// Number of commits; defaults to [`DEFAULT_LOG_COUNT`].
+    pub max_count: Option<usize>,
+    /// Revision or range to list (e.g. `main..HEAD`); defaults to HEAD.
+    pub rev: Option<String>,
+    /// Only commits touching these paths.
+    pub paths: Vec<String>,
+    pub author: Option<String>,
+    /// Only commits after this date (anything `git log --since` accepts).
+    pub since: Option<String>,
+    /// Only commits whose message matches this regex.
+    pub grep: Option<String>,
+}
+
+/// A commit's metadata.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Commit {
+    pub hash: String,
+    pub short_hash: String,
+    pub author: String,
+    pub email: String,
+    /// Author date, ISO 8601.
+    pub date: String,
+    pub subject: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub body: String,
+}
+
+/// Fields separated by 0x1f, commits terminated by 0x1e.
+const COMMIT_FORMAT: &str = "--format=%H%x1f%h%x1f%an%x1f%ae%x1f%aI%x1f%s%x1f%b%x1e";
+
+fn parse_commits(output: &str) -> Vec<Commit> {
+    output
+        .split('\x1e')
+        .filter_map(|record| {
+            let mut fields = record.trim_start_matches('\n').splitn(7, '\x1f');
+            Some(Commit {
+                hash: fields.next().filter(|h| !h.is_empty())?.to_string(),
+                short_hash: fields.next()?.to_string(),
+                author: fields.next()?.to_string(),
+                email: fields.next()?.to_string(),
+                date: fields.next()?.to_string(),
+                subject: fields.next()?.to_string(),
+                body: fields.next().unwrap_or_default().trim().to_string(),
+            })
+        })
+        .collect()
+}
+
+/// List commits, newest first.
+pub fn log(repo: &str, options: &LogOptions) -> Result<Vec<Commit>, GitError> {
+    let max_count = format!(
+        "--max-count={}",
+        options
+            .max_count
+            .unwrap_or(DEFAULT_LOG_COUNT)
+            .clamp(1, MAX_LOG_COUNT)
+    );
+    let author = options.author.as_ref().map(|a| format!("--author={}", a));
+    let since = options.since.as_ref().map(|s| format!("--since={}", s));
+    let grep = options.grep.as_ref().map(|g| format!("--grep={}", g));
+
+    let mut args = vec!["log", COMMIT_FORMAT, max_count.as_str()];
+    args.extend(
+        [&author, &since, &grep]
+            .into_iter()
+            .flatten()
+            .map(String::as_str),
+    );
+    if let Some(rev) = &options.rev {
+        check_rev(rev)?;
+        args.push(rev);
+    }
+    args.push("--");
+    args.extend(options.paths.iter().map(String::as_str));
+
+    Ok(parse_commits(&run_git(repo, &args)?))
+}
+
+// ============================================================================
+// Blame
+// ============================================================================
+
+/// The commit that last changed a line.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BlameLine {
+    pub line_number: usize,
+    /// Abbreviated commit hash; all zeros for uncommitted changes.
+    pub commit: String,
+    pub author: String,
+    /// Author time, Unix seconds.
+    pub author_time: i64,
+    pub summary: String,
+    pub content: String,
+}
+
+/// Line-by-line authorship of a file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitBlame {
+    pub path: String,
+    pub lines: Vec<BlameLine>,
+    /// Whether more lines exist past the limit.
+    pub truncated: bool,
+}
+
+/// Parse `git blame --line-porcelain`.
+fn parse_blame(output: &str) -> Vec<BlameLine> {
+    let mut lines = Vec::new();
+    let mut current: Option<BlameLine> = None;
+
+    for line in output.lines() {
+        if let Some(content) = line.strip_prefix('\t') {
+            if let Some(mut entry) = current.take() {
+                entry.content = content.to_string();
+                lines.push(entry);
+            }
+        } else if let Some(entry) = current.as_mut() {
+            if let Some(author) = line.strip_prefix("author ") {
+                entry.author = author.to_string();
+            } else if let Some(time) = line.strip_prefix("author-time ") {
+                entry.author_time = time.parse().unwrap_or(0);
+            } else if let Some(summary) = line.strip_prefix("summary ") {
+                entry.summary = summary.to_string();
+            }
+        } else {
+            // "<hash> <original line> <final line> [<lines in group>]"
+            let mut fields = line.split(' ');
+            let (Some(hash), Some(line_number)) = (fields.next(), fields.nth(1)) else {
+                continue;
+            };
+            current = Some(BlameLine {
+                line_number: line_number.parse().unwrap_or(0),
+                commit: hash.chars().take(10).collect(),
+                author: String::new(),
+                author_time: 0,
+                summary: String::new(),
+                content: String::new(),
+            });
+        }
+    }
+
+    lines
+}
+
+/// Blame `path`, optionally limited to a line range or as of a revision.
+pub fn blame(
+    repo: &str,
+    path: &str,
+    start_line: Option<usize>,
+    end_line: Option<usize>,
+    rev: Option<&str>,
+) -> Result<GitBlame, GitError> {
+    if path.is_empty() {
+        return Err(GitError::InvalidArgument("path is required".to_string()));
+    }
+
+    let start = start_line.unwrap_or(1);
+    if start == 0 || end_line.is_some_and(|end| end < start) {
+        return Err(GitError::InvalidArgument(format!(
+            "invalid line range {}..{}",
+            start,
+            end_line.map_or(String::new(), |end| end.to_string())
+        )));
+    }
+    if let Some(rev) = rev {
+        check_rev(rev)?;
+    }
+    let run = |range: &str| {
+        let mut args = vec!["blame", "--line-porcelain", range];
+        args.extend(rev);
+        args.extend(["--", path]);
+        run_git(repo, &args)
+    };
+
+    // Ask for one line past the limit to know whether there is more
+    let end = end_line.map_or(start + MAX_BLAME_LINES, |end| {
+        end.min(start + MAX_BLAME_LINES)
+    });
+    let output = match run(&format!("-L{},{}", start, end)) {
+        Ok(output) => output,
+        // The range ran past the end of the file; blame up to the end instead
+        Err(GitError::Command { stderr, .. }) if stderr.contains("has only") => {
+            run(&format!("-L{},", start))?
+        }
+        Err(e) => return Err(e),
+    };
+
+    let mut lines = parse_blame(&output);
+    let truncated = lines.len() > MAX_BLAME_LINES;
+    lines.truncate(MAX_BLAME_LINES);
+
+    Ok(GitBlame {
+        path: path.to_string(),
+        lines,
+        truncated,
+    })
+}
+
+// ============================================================================
+// Show
+// ============================================================================
+
+/// A commit with its changes.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommitDetails {
+    #[serde(flatten)]
+    pub commit: Commit,
+    pub files: Vec<DiffFile>,
+    pub patch: String,
+    /// Whether the patch was cut short.
+    pub truncated: bool,
+}
+
+/// A file's content at a revision.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileAtRevision {
+    pub rev: String,
+    pub path: String,
+    pub content: String,
+    pub binary: bool,
+    /// Whether the content was cut short.
+    pub truncated: bool,
+}
+
+/// Show a commit's metadata, stats and patch.
+pub fn show_commit(repo: &str, rev: &str) -> Result<CommitDetails, GitError> {
+    check_rev(rev)?;
+
+    let commit = parse_commits(&run_git(
+        repo,
+        &["show", "--no-patch", COMMIT_FORMAT, rev, "--"],
+    )?)
+    .into_iter()
+    .next()
+    .ok_or_else(|| GitError::InvalidArgument(format!("'{}' is not a commit", rev)))?;
+    let files = parse_numstat(&run_git(
+        repo,
+        &[
+            "show",
+            "--no-ext-diff",
+            "--format=",
+            "--numstat",
+            "-z",
+            rev,
+            "--",
+        ],
+    )?);
+    let (patch, truncated) = truncate_content(run_git(
+        repo,
+        &["show", "--no-ext-diff", "--format=", "--patch", rev, "--"],
+    )?);
+
+    Ok(CommitDetails {
+        commit,
+        files,
+        patch,
+        truncated,
+    })
+}
+
+/// Show `path` as it was at `rev`.
+pub fn show_file(repo: &str, rev: &str, path: &str) -> Result<FileAtRevision, GitError> {
+    check_rev(rev)?;
+
+    let object = format!("{}:{}", rev, path);
+    let content = run_git(repo, &["show", object.as_str()])?;
+    let binary = content.contains('\0');
+    let (content, truncated) = if binary {
+        (String::new(), false)
+    } else {
+        truncate_content(content)
+    };
+
+    Ok(FileAtRevision {
+        rev: rev.to_string(),
+        path: path.to_string(),
+        content,
+        binary,
+        truncated,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    /// A repository with one commit of `lib.rs`, or `None` without git.
+    fn init_repo() -> Option<tempfile::TempDir> {
+        let dir = tempfile::tempdir().expect("tempdir failed");
+        let git = |args: &[&str]| {
+            Command::new("git")
+                .arg("-C")
+                .arg(dir.path())
+                .args(["-c", "user.name=Test", "-c", "user.email=test@example.com"])
+                .args(["-c", "commit.gpgsign=false"])
+                .args(args)
+                .output()
+                .ok()
+                .filter(|o| o.status.success())
+        };
+        git(&["init", "-q", "-b", "main"])?;
+        fs::write(dir.path().join("lib.rs"), "fn one() {}\nfn two() {}\n").unwrap();
+        git(&["add", "lib.rs"])?;
+        git(&["commit", "-q", "-m", "Add lib\n\nWith two functions."])?;
+        Some(dir)
+    }
+
+    #[test]
+    fn test_parse_status() {
+        let output = "## main...origin/main [ahead 2, behind 1]\0 M src/lib.rs\0R  new.rs\0old.rs\0?? notes.txt\0";
+        let status = parse_status(output);
+
+        assert_eq!(status.branch.as_deref(), Some("main"));
+        assert_eq!(status.upstream.as_deref(), Some("origin/main"));
+        assert_eq!((status.ahead, status.behind), (2, 1));
+        assert!(!status.clean);
+        assert_eq!(status.files.len(), 3);
+
+        assert_eq!(status.files[0].path, "src/lib.rs");
+        assert_eq!(status.files[0].staged, "unmodified");
+        assert_eq!(status.files[0].unstaged, "modified");
+        assert_eq!(status.files[1].path, "new.rs");
+        assert_eq!(status.files[1].orig_path.as_deref(), Some("old.rs"));
+        assert_eq!(status.files[1].staged, "renamed");
+        assert_eq!(status.files[2].unstaged, "untracked");
+    }
+
+    #[test]
+    fn test_parse_branch_header_variants() {
+        let mut status = GitStatus::default();
+        parse_branch_header("No commits yet on main", &mut status);
+        assert_eq!(status.branch.as_deref(), Some("main"));
+        assert_eq!(status.upstream, None);
+
+        let mut status = GitStatus::default();
+        parse_branch_header("HEAD (no branch)", &mut status);
+        assert_eq!(status.branch, None);
+    }
+
+    #[test]
+    fn test_parse_numstat_with_rename_and_binary() {
+        let output = "3\t1\tsrc/a.rs\0-\t-\tlogo.png\00\t0\t\0old.rs\0new.rs\0";
+        let files = parse_numstat(output);
+
+        assert_eq!(files.len(), 3);
+        assert_eq!((files[0].additions, files[0].deletions), (3, 1));
+        assert!(files[1].binary);
+        assert_eq!(files[2].path, "new.rs");
+        assert_eq!(files[2].orig_path.as_deref(), Some("old.rs"));
+    }
+
+    #[test]
+    fn test_parse_blame() {
+        let hash = "a".repeat(40);
+        let output = format!(
+            "{hash} 1 1 2\nauthor Ada\nauthor-mail <ada@example.com>\nauthor-time 1700000000\n\
+             summary Initial\nfilename lib.rs\n\tfn one() {{}}\n\
+             {hash} 2 2\nauthor Ada\nauthor-time 1700000000\nsummary Initial\nfilename lib.rs\n\tfn two() {{}}\n"
+        );
+        let lines = parse_blame(&output);
+
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0].line_number, 1);
+        assert_eq!(lines[0].commit, "aaaaaaaaaa");
+        assert_eq!(lines[0].author, "Ada");
+        assert_eq!(lines[0].author_time, 1_700_000_000);
+        assert_eq!(lines[1].content, "fn two() {}");
+    }
+
+    #[test]
+    fn test_revisions_cannot_be_options() {
+        let dir = tempfile::tempdir().expect("tempdir failed");
+        let repo = dir.path().to_str().unwrap();
+        let options = DiffOptions {
+            base: Some("--output=/tmp/x".to_string()),
+            ..Default::default()
+        };
+
+        assert!(matches!(
+            diff(repo, &options),
+            Err(GitError::InvalidArgument(_))
+        ));
+        assert!(matches!(
+            show_commit(repo, "-p"),
+            Err(GitError::InvalidArgument(_))
+        ));
+    }
+
+    #[test]
+    fn test_queries_against_repository() {
+        let Some(dir) = init_repo() else {
+            return;
+        };
+        let repo = dir.path().to_str().unwrap();
+
+        let status = status(repo).expect("status failed");
+        assert_eq!(status.branch.as_deref(), Some("main"));
+        assert!(status.clean);
+
+        fs::write(dir.path().join("lib.rs"), "fn one() {}\nfn three() {}\n").unwrap();
+        let diff = diff(repo, &DiffOptions::default()).expect("diff failed");
+        assert_eq!(diff.files.len(), 1);
+        assert_eq!((diff.additions, diff.deletions), (1, 1));
+        assert!(diff.patch.contains("+fn three() {}"));
+
+        let commits = log(repo, &LogOptions::default()).expect("log failed");
+        assert_eq!(commits.len(), 1);
+        assert_eq!(commits[0].subject, "Add lib");
+        assert_eq!(commits[0].body, "With two functions.");
+
+        let blame = blame(repo, "lib.rs", None, None, Some("HEAD")).expect("blame failed");
+        assert_eq!(blame.lines.len(), 2);
+        assert_eq!(blame.lines[1].author, "Test");
+        assert!(!blame.truncated);
+
+        let details = show_commit(repo, "HEAD").expect("show failed");
+        assert_eq!(details.files[0].additions, 2);
+
+        let file = show_file(repo, "HEAD", "lib.rs").expect("show file failed");
+        assert_eq!(file.content, "fn one() {}\nfn two() {}\n");
+    }
+
+    #[test]
+    fn test_not_a_repository() {
+        let dir = tempfile::tempdir().expect("tempdir failed");
+        let err = status(dir.path().to_str().unwrap()).unwrap_err();
+        // Without git on PATH this is GitNotFound instead
+        assert!(matches!(
+            err,
+            GitError::NotARepository(_) | GitError::GitNotFound
+        ));
+    }
+}
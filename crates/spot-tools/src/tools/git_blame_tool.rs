@@ -0,0 +1,111 @@
+//! GitBlame tool implementation.
+//!
+//! Provides a serdesAI-compatible tool for finding who last changed each line.
+
+use async_trait::async_trait;
+use serde::Deserialize;
+use serde_json::Value as JsonValue;
+use tracing::{debug, info_span};
+
+use serdes_ai_tools::{RunContext, SchemaBuilder, Tool, ToolDefinition, ToolResult, ToolReturn};
+
+use super::git;
+
+/// Tool for reading `git blame` as JSON.
+#[derive(Debug, Clone, Default)]
+pub struct GitBlameTool;
+
+#[derive(Debug, Deserialize)]
+struct GitBlameArgs {
+    file_path: String,
+    directory: Option<String>,
+    start_line: Option<usize>,
+    end_line: Option<usize>,
+    rev: Option<String>,
+}
+
+#[async_trait]
+impl Tool for GitBlameTool {
+    fn definition(&self) -> ToolDefinition {
+        ToolDefinition::new(
+            "git_blame",
+            "Show which commit last changed each line of a file, as JSON: line number, \
+             commit, author, time, commit summary and line content. Limit to a line range \
+             for large files; at most 500 lines are returned. Read-only.",
+        )
+        .with_parameters(
+            SchemaBuilder::new()
+                .string("file_path", "File to blame, relative to directory.", true)
+                .string(
+                    "directory",
+                    "Directory inside the repository. Defaults to '.'.",
+                    false,
+                )
+                .integer("start_line", "First line to blame (1-based).", false)
+                .integer("end_line", "Last line to blame (inclusive).", false)
+                .string(
+                    "rev",
+                    "Blame the file as of this branch or commit instead of the working tree.",
+                    false,
+                )
+                .build()
+                .expect("schema build failed"),
+        )
+    }
+
+    async fn call(&self, _ctx: &RunContext, args: JsonValue) -> ToolResult {
+        debug!(tool = "git_blame", ?args, "Tool called");
+
+        let args: GitBlameArgs = crate::tools::common::parse_tool_args_lenient(
+            "git_blame",
+            args.clone(),
+            self.definition().parameters(),
+        )?;
+
+        let directory = args.directory.as_deref().unwrap_or(".");
+        let _span = info_span!("git_blame", file_path = %args.file_path).entered();
+
+        match git::blame(
+            directory,
+            &args.file_path,
+            args.start_line,
+            args.end_line,
+            args.rev.as_deref(),
+        ) {
+            Ok(blame) => Ok(ToolReturn::json(serde_json::json!(blame))),
+            Err(e) => Ok(ToolReturn::error(format!("git blame failed: {}", e))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_definition_returns_correct_name() {
+        assert_eq!(GitBlameTool.definition().name(), "git_blame");
+    }
+
+    #[tokio::test]
+    async fn test_call_invalid_range() {
+        let ctx = RunContext::minimal("test");
+        let result = GitBlameTool
+            .call(
+                &ctx,
+                serde_json::json!({ "file_path": "lib.rs", "start_line": 10, "end_line": 2 }),
+            )
+            .await
+            .unwrap();
+
+        assert!(result.is_error());
+        assert!(result.as_text().unwrap().contains("invalid line range"));
+    }
+
+    #[tokio::test]
+    async fn test_call_missing_file_path_returns_error() {
+        let ctx = RunContext::minimal("test");
+        let result = GitBlameTool.call(&ctx, serde_json::json!({})).await;
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,118 @@
+//! GitDiff tool implementation.
+//!
+//! Provides a serdesAI-compatible tool for reading uncommitted or
+//! between-revision changes.
+
+use async_trait::async_trait;
+use serde::Deserialize;
+use serde_json::Value as JsonValue;
+use tracing::{debug, info_span};
+
+use serdes_ai_tools::{RunContext, Tool, ToolDefinition, ToolResult, ToolReturn};
+
+use super::common::one_or_many;
+use super::git::{self, DiffOptions};
+
+/// Tool for reading `git diff` as JSON.
+#[derive(Debug, Clone, Default)]
+pub struct GitDiffTool;
+
+#[derive(Debug, Deserialize)]
+struct GitDiffArgs {
+    directory: Option<String>,
+    staged: Option<bool>,
+    base: Option<String>,
+    #[serde(default, deserialize_with = "one_or_many")]
+    paths: Vec<String>,
+    context_lines: Option<usize>,
+}
+
+#[async_trait]
+impl Tool for GitDiffTool {
+    fn definition(&self) -> ToolDefinition {
+        ToolDefinition::new(
+            "git_diff",
+            "Show git changes as JSON: per-file addition/deletion counts and the unified \
+             patch. By default shows unstaged working tree changes; set staged to see what \
+             will be committed, or base to compare against a branch or commit. Untracked \
+             files are not included (see git_status). Patches over 50,000 chars are truncated. \
+             Read-only.",
+        )
+        .with_parameters(serde_json::json!({
+            "type": "object",
+            "properties": {
+                "directory": {
+                    "type": "string",
+                    "description": "Directory inside the repository. Defaults to '.'."
+                },
+                "staged": {
+                    "type": "boolean",
+                    "description": "Show staged changes (the index) instead of unstaged ones. Defaults to false."
+                },
+                "base": {
+                    "type": "string",
+                    "description": "Branch, tag or commit to compare against, e.g. 'main' or 'HEAD~3'."
+                },
+                "paths": {
+                    "type": "array",
+                    "items": { "type": "string" },
+                    "description": "Only show changes to these files or directories."
+                },
+                "context_lines": {
+                    "type": "integer",
+                    "description": "Lines of context around each change. Defaults to 3."
+                }
+            }
+        }))
+    }
+
+    async fn call(&self, _ctx: &RunContext, args: JsonValue) -> ToolResult {
+        debug!(tool = "git_diff", ?args, "Tool called");
+
+        let args: GitDiffArgs = crate::tools::common::parse_tool_args_lenient(
+            "git_diff",
+            args.clone(),
+            self.definition().parameters(),
+        )?;
+
+        let directory = args.directory.as_deref().unwrap_or(".");
+        let _span = info_span!("git_diff", directory = %directory).entered();
+
+        let options = DiffOptions {
+            staged: args.staged.unwrap_or(false),
+            base: args.base,
+            paths: args.paths,
+            context_lines: args.context_lines,
+        };
+        match git::diff(directory, &options) {
+            Ok(diff) => Ok(ToolReturn::json(serde_json::json!(diff))),
+            Err(e) => Ok(ToolReturn::error(format!("git diff failed: {}", e))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_definition_has_parameters() {
+        let def = GitDiffTool.definition();
+        assert_eq!(def.name(), "git_diff");
+        let schema_str = serde_json::to_string(def.parameters()).unwrap();
+        assert!(schema_str.contains("staged"));
+        assert!(schema_str.contains("paths"));
+    }
+
+    #[tokio::test]
+    async fn test_call_rejects_option_as_base() {
+        let ctx = RunContext::minimal("test");
+        let result = GitDiffTool
+            .call(&ctx, serde_json::json!({ "base": "--output=diff.txt" }))
+            .await
+            .unwrap();
+
+        assert!(result.is_error());
+        assert!(result.as_text().unwrap().contains("invalid revision"));
+    }
+}
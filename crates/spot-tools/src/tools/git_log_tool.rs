@@ -0,0 +1,131 @@
+//! GitLog tool implementation.
+//!
+//! Provides a serdesAI-compatible tool for listing commit history.
+
+use async_trait::async_trait;
+use serde::Deserialize;
+use serde_json::Value as JsonValue;
+use tracing::{debug, info_span};
+
+use serdes_ai_tools::{RunContext, Tool, ToolDefinition, ToolResult, ToolReturn};
+
+use super::common::one_or_many;
+use super::git::{self, LogOptions};
+
+/// Tool for reading `git log` as JSON.
+#[derive(Debug, Clone, Default)]
+pub struct GitLogTool;
+
+#[derive(Debug, Deserialize)]
+struct GitLogArgs {
+    directory: Option<String>,
+    max_count: Option<usize>,
+    rev: Option<String>,
+    #[serde(default, deserialize_with = "one_or_many")]
+    paths: Vec<String>,
+    author: Option<String>,
+    since: Option<String>,
+    grep: Option<String>,
+}
+
+#[async_trait]
+impl Tool for GitLogTool {
+    fn definition(&self) -> ToolDefinition {
+        ToolDefinition::new(
+            "git_log",
+            "List git commits as JSON, newest first: hash, author, date, subject and body. \
+             Filter by revision range, paths, author, date or message. Read-only.",
+        )
+        .with_parameters(serde_json::json!({
+            "type": "object",
+            "properties": {
+                "directory": {
+                    "type": "string",
+                    "description": "Directory inside the repository. Defaults to '.'."
+                },
+                "max_count": {
+                    "type": "integer",
+                    "description": "Number of commits to return. Defaults to 20, max 100."
+                },
+                "rev": {
+                    "type": "string",
+                    "description": "Branch, commit or range to list, e.g. 'main' or 'main..HEAD'. Defaults to HEAD."
+                },
+                "paths": {
+                    "type": "array",
+                    "items": { "type": "string" },
+                    "description": "Only commits touching these files or directories."
+                },
+                "author": {
+                    "type": "string",
+                    "description": "Only commits whose author name or email matches this pattern."
+                },
+                "since": {
+                    "type": "string",
+                    "description": "Only commits after this date, e.g. '2 weeks ago' or '2024-01-31'."
+                },
+                "grep": {
+                    "type": "string",
+                    "description": "Only commits whose message matches this regex."
+                }
+            }
+        }))
+    }
+
+    async fn call(&self, _ctx: &RunContext, args: JsonValue) -> ToolResult {
+        debug!(tool = "git_log", ?args, "Tool called");
+
+        let args: GitLogArgs = crate::tools::common::parse_tool_args_lenient(
+            "git_log",
+            args.clone(),
+            self.definition().parameters(),
+        )?;
+
+        let directory = args.directory.as_deref().unwrap_or(".");
+        let _span = info_span!("git_log", directory = %directory).entered();
+
+        let options = LogOptions {
+            max_count: args.max_count,
+            rev: args.rev,
+            paths: args.paths,
+            author: args.author,
+            since: args.since,
+            grep: args.grep,
+        };
+        match git::log(directory, &options) {
+            Ok(commits) => Ok(ToolReturn::json(serde_json::json!({
+                "count": commits.len(),
+                "commits": commits,
+            }))),
+            Err(e) => Ok(ToolReturn::error(format!("git log failed: {}", e))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_definition_has_parameters() {
+        let def = GitLogTool.definition();
+        assert_eq!(def.name(), "git_log");
+        let schema_str = serde_json::to_string(def.parameters()).unwrap();
+        assert!(schema_str.contains("max_count"));
+        assert!(schema_str.contains("since"));
+    }
+
+    #[tokio::test]
+    async fn test_call_invalid_directory() {
+        let ctx = RunContext::minimal("test");
+        let result = GitLogTool
+            .call(
+                &ctx,
+                serde_json::json!({ "directory": "/nonexistent/path/xyz123abc" }),
+            )
+            .await
+            .unwrap();
+
+        assert!(result.is_error());
+    }
+}
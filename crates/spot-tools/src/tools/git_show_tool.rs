@@ -0,0 +1,100 @@
+//! GitShow tool implementation.
+//!
+//! Provides a serdesAI-compatible tool for inspecting a commit, or a file as
+//! it was at a revision.
+
+use async_trait::async_trait;
+use serde::Deserialize;
+use serde_json::Value as JsonValue;
+use tracing::{debug, info_span};
+
+use serdes_ai_tools::{RunContext, SchemaBuilder, Tool, ToolDefinition, ToolResult, ToolReturn};
+
+use super::git;
+
+/// Tool for reading `git show` as JSON.
+#[derive(Debug, Clone, Default)]
+pub struct GitShowTool;
+
+#[derive(Debug, Deserialize)]
+struct GitShowArgs {
+    rev: Option<String>,
+    file_path: Option<String>,
+    directory: Option<String>,
+}
+
+#[async_trait]
+impl Tool for GitShowTool {
+    fn definition(&self) -> ToolDefinition {
+        ToolDefinition::new(
+            "git_show",
+            "Show a git commit as JSON: metadata, per-file addition/deletion counts and \
+             the patch. With file_path, return that file's content at the revision instead. \
+             Content over 50,000 chars is truncated. Read-only.",
+        )
+        .with_parameters(
+            SchemaBuilder::new()
+                .string(
+                    "rev",
+                    "Commit, branch or tag to show. Defaults to HEAD.",
+                    false,
+                )
+                .string(
+                    "file_path",
+                    "Show this file as of rev, relative to the repository root.",
+                    false,
+                )
+                .string(
+                    "directory",
+                    "Directory inside the repository. Defaults to '.'.",
+                    false,
+                )
+                .build()
+                .expect("schema build failed"),
+        )
+    }
+
+    async fn call(&self, _ctx: &RunContext, args: JsonValue) -> ToolResult {
+        debug!(tool = "git_show", ?args, "Tool called");
+
+        let args: GitShowArgs = crate::tools::common::parse_tool_args_lenient(
+            "git_show",
+            args.clone(),
+            self.definition().parameters(),
+        )?;
+
+        let directory = args.directory.as_deref().unwrap_or(".");
+        let rev = args.rev.as_deref().unwrap_or("HEAD");
+        let _span = info_span!("git_show", rev = %rev).entered();
+
+        let result = match &args.file_path {
+            Some(path) => git::show_file(directory, rev, path).map(|file| serde_json::json!(file)),
+            None => git::show_commit(directory, rev).map(|commit| serde_json::json!(commit)),
+        };
+        match result {
+            Ok(value) => Ok(ToolReturn::json(value)),
+            Err(e) => Ok(ToolReturn::error(format!("git show failed: {}", e))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_definition_returns_correct_name() {
+        assert_eq!(GitShowTool.definition().name(), "git_show");
+    }
+
+    #[tokio::test]
+    async fn test_call_rejects_option_as_rev() {
+        let ctx = RunContext::minimal("test");
+        let result = GitShowTool
+            .call(&ctx, serde_json::json!({ "rev": "--output=x" }))
+            .await
+            .unwrap();
+
+        assert!(result.is_error());
+    }
+}
@@ -0,0 +1,87 @@
+//! GitStatus tool implementation.
+//!
+//! Provides a serdesAI-compatible tool for reading the working tree status.
+
+use async_trait::async_trait;
+use serde::Deserialize;
+use serde_json::Value as JsonValue;
+use tracing::{debug, info_span};
+
+use serdes_ai_tools::{RunContext, SchemaBuilder, Tool, ToolDefinition, ToolResult, ToolReturn};
+
+use super::git;
+
+/// Tool for reading `git status` as JSON.
+#[derive(Debug, Clone, Default)]
+pub struct GitStatusTool;
+
+#[derive(Debug, Deserialize)]
+struct GitStatusArgs {
+    directory: Option<String>,
+}
+
+#[async_trait]
+impl Tool for GitStatusTool {
+    fn definition(&self) -> ToolDefinition {
+        ToolDefinition::new(
+            "git_status",
+            "Show the git working tree status as JSON: current branch, upstream, \
+             commits ahead/behind, and each changed, staged, untracked or conflicted \
+             file. Read-only.",
+        )
+        .with_parameters(
+            SchemaBuilder::new()
+                .string(
+                    "directory",
+                    "Directory inside the repository. Defaults to '.'.",
+                    false,
+                )
+                .build()
+                .expect("schema build failed"),
+        )
+    }
+
+    async fn call(&self, _ctx: &RunContext, args: JsonValue) -> ToolResult {
+        debug!(tool = "git_status", ?args, "Tool called");
+
+        let args: GitStatusArgs = crate::tools::common::parse_tool_args_lenient(
+            "git_status",
+            args.clone(),
+            self.definition().parameters(),
+        )?;
+
+        let directory = args.directory.as_deref().unwrap_or(".");
+        let _span = info_span!("git_status", directory = %directory).entered();
+
+        match git::status(directory) {
+            Ok(status) => Ok(ToolReturn::json(serde_json::json!(status))),
+            Err(e) => Ok(ToolReturn::error(format!("git status failed: {}", e))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_definition_returns_correct_name() {
+        assert_eq!(GitStatusTool.definition().name(), "git_status");
+    }
+
+    #[tokio::test]
+    async fn test_call_outside_repository_returns_error() {
+        let dir = tempfile::tempdir().expect("tempdir failed");
+        let ctx = RunContext::minimal("test");
+        let result = GitStatusTool
+            .call(
+                &ctx,
+                serde_json::json!({ "directory": dir.path().to_str().unwrap() }),
+            )
+            .await
+            .unwrap();
+
+        assert!(result.is_error());
+        assert!(result.as_text().unwrap().contains("git status failed"));
+    }
+}
@@ -7,9 +7,10 @@ use serde::Deserialize;
 use serde_json::Value as JsonValue;
 use tracing::{debug, info_span};
 
-use serdes_ai_tools::{RunContext, SchemaBuilder, Tool, ToolDefinition, ToolResult, ToolReturn};
+use serdes_ai_tools::{RunContext, Tool, ToolDefinition, ToolResult, ToolReturn};
 
-use super::file_ops;
+use super::common::{one_or_many, IgnoreRules};
+use super::file_ops::{self, GrepLine, GrepOptions, GrepOutputMode, GrepResult};
 
 /// Tool for searching text patterns across files.
 #[derive(Debug, Clone, Default)]
@@ -20,6 +21,130 @@ struct GrepArgs {
     pattern: String,
     directory: Option<String>,
     max_results: Option<usize>,
+    max_per_file: Option<usize>,
+    #[serde(default, deserialize_with = "one_or_many")]
+    include: Vec<String>,
+    #[serde(default, deserialize_with = "one_or_many")]
+    exclude: Vec<String>,
+    #[serde(default, deserialize_with = "one_or_many")]
+    file_types: Vec<String>,
+    context: Option<usize>,
+    before_context: Option<usize>,
+    after_context: Option<usize>,
+    case_insensitive: Option<bool>,
+    word: Option<bool>,
+    multiline: Option<bool>,
+    output_mode: Option<GrepOutputMode>,
+    include_ignored: Option<bool>,
+}
+
+impl GrepArgs {
+    fn options(&self) -> GrepOptions {
+        GrepOptions {
+            max_results: self.max_results,
+            max_per_file: self.max_per_file,
+            include: self.include.clone(),
+            exclude: self.exclude.clone(),
+            file_types: self.file_types.clone(),
+            before_context: self.before_context.or(self.context).unwrap_or(0),
+            after_context: self.after_context.or(self.context).unwrap_or(0),
+            case_insensitive: self.case_insensitive.unwrap_or(false),
+            word: self.word.unwrap_or(false),
+            multiline: self.multiline.unwrap_or(false),
+            output_mode: self.output_mode.unwrap_or_default(),
+            ignore: IgnoreRules::from_global_context()
+                .with_include_ignored(self.include_ignored.unwrap_or(false)),
+        }
+    }
+}
+
+/// Format results the way ripgrep prints them: `path:line:text` for
+/// matches, `path-line-text` for context and `--` between separate groups.
+fn format_result(
+    result: &GrepResult,
+    mode: GrepOutputMode,
+    pattern: &str,
+    directory: &str,
+) -> String {
+    let mut output = match mode {
+        GrepOutputMode::Content => format!(
+            "Found {} matches for '{}' in {}:\n",
+            result.total_matches, pattern, directory
+        ),
+        GrepOutputMode::FilesWithMatches => format!(
+            "Found {} files matching '{}' in {}:\n",
+            result.files.len(),
+            pattern,
+            directory
+        ),
+        GrepOutputMode::Count => format!(
+            "Found {} matches for '{}' in {} files in {}:\n",
+            result.total_matches,
+            pattern,
+            result.files.len(),
+            directory
+        ),
+    };
+
+    match mode {
+        GrepOutputMode::Content => {
+            let push_context = |output: &mut String, path: &str, line: &GrepLine| {
+                output.push_str(&format!("\n{}-{}-{}", path, line.line_number, line.content));
+            };
+            let has_context = result
+                .matches
+                .iter()
+                .any(|m| !m.before.is_empty() || !m.after.is_empty());
+            // Last line printed, to separate non-adjacent groups
+            let mut last: Option<(&str, usize)> = None;
+
+            for m in &result.matches {
+                let first = m.before.first().map_or(m.line_number, |l| l.line_number);
+                if has_context {
+                    if let Some((path, line_number)) = last {
+                        if path != m.path || first > line_number + 1 {
+                            output.push_str("\n--");
+                        }
+                    }
+                }
+
+                for line in &m.before {
+                    push_context(&mut output, &m.path, line);
+                }
+                output.push_str(&format!("\n{}:{}:{}", m.path, m.line_number, m.content));
+                for line in &m.after {
+                    push_context(&mut output, &m.path, line);
+                }
+
+                let end = m
+                    .after
+                    .last()
+                    .map_or(m.line_number + m.content.matches('\n').count(), |l| {
+                        l.line_number
+                    });
+                last = Some((m.path.as_str(), end));
+            }
+        }
+        GrepOutputMode::FilesWithMatches => {
+            for file in &result.files {
+                output.push_str(&format!("\n{}", file.path));
+            }
+        }
+        GrepOutputMode::Count => {
+            for file in &result.files {
+                output.push_str(&format!("\n{}:{}", file.path, file.count));
+            }
+        }
+    }
+
+    if result.truncated {
+        output.push_str(
+            "\n\n[Results truncated - narrow the search with include/exclude/file_types \
+             or use output_mode=\"count\" to see where matches are]",
+        );
+    }
+
+    output
 }
 
 #[async_trait]
@@ -27,30 +152,82 @@ impl Tool for GrepTool {
     fn definition(&self) -> ToolDefinition {
         ToolDefinition::new(
             "grep",
-            "Recursively search for text patterns across files. \
-             Searches across recognized text file types while limiting results for performance. \
-             Safety rails: max 50 matches total, max 10 per file, lines truncated at 512 chars, files over 5MB skipped.",
-        )
-        .with_parameters(
-            SchemaBuilder::new()
-                .string(
-                    "pattern",
-                    "The text pattern to search for. Supports regex patterns.",
-                    true,
-                )
-                .string(
-                    "directory",
-                    "Root directory to start the recursive search. Defaults to '.'.",
-                    false,
-                )
-                .integer(
-                    "max_results",
-                    "Maximum number of matches to return. Defaults to 100.",
-                    false,
-                )
-                .build()
-                .expect("schema build failed"),
+            "Recursively search for a regex across files, skipping binary files and files \
+             matched by .gitignore/.ignore rules. Filter files with include/exclude globs or \
+             file_types, show context lines, or list matching files / per-file counts instead \
+             of lines. Safety rails: max 50 matches total, 10 per file by default, \
+             lines truncated at 512 chars, files over 5MB skipped.",
         )
+        .with_parameters(serde_json::json!({
+            "type": "object",
+            "properties": {
+                "pattern": {
+                    "type": "string",
+                    "description": "The regex to search for. Invalid regexes are searched for literally."
+                },
+                "directory": {
+                    "type": "string",
+                    "description": "Root directory to start the recursive search. Defaults to '.'."
+                },
+                "include": {
+                    "type": "array",
+                    "items": { "type": "string" },
+                    "description": "Only search files matching one of these globs, e.g. [\"*.rs\", \"src/**/*.ts\"]. Globs without '/' match file names."
+                },
+                "exclude": {
+                    "type": "array",
+                    "items": { "type": "string" },
+                    "description": "Skip files matching any of these globs, e.g. [\"tests/**\"]."
+                },
+                "file_types": {
+                    "type": "array",
+                    "items": { "type": "string" },
+                    "description": "Only search these ripgrep file types, e.g. [\"rust\", \"py\", \"ts\"]."
+                },
+                "context": {
+                    "type": "integer",
+                    "description": "Lines of context to show before and after each match (max 10)."
+                },
+                "before_context": {
+                    "type": "integer",
+                    "description": "Lines of context before each match; overrides `context`."
+                },
+                "after_context": {
+                    "type": "integer",
+                    "description": "Lines of context after each match; overrides `context`."
+                },
+                "case_insensitive": {
+                    "type": "boolean",
+                    "description": "Match case-insensitively. Defaults to false."
+                },
+                "word": {
+                    "type": "boolean",
+                    "description": "Only match whole words. Defaults to false."
+                },
+                "multiline": {
+                    "type": "boolean",
+                    "description": "Allow matches to span lines (`.` also matches newlines). Defaults to false."
+                },
+                "output_mode": {
+                    "type": "string",
+                    "enum": ["content", "files_with_matches", "count"],
+                    "description": "`content` shows matching lines (default), `files_with_matches` only the file paths, `count` the number of matching lines per file."
+                },
+                "max_results": {
+                    "type": "integer",
+                    "description": "Maximum number of matches (or files, in the other modes) to return. Defaults to 50."
+                },
+                "max_per_file": {
+                    "type": "integer",
+                    "description": "Maximum matches to show per file. Defaults to 10."
+                },
+                "include_ignored": {
+                    "type": "boolean",
+                    "description": "Also search files excluded by .gitignore/.ignore and default ignore rules. Defaults to false."
+                }
+            },
+            "required": ["pattern"]
+        }))
     }
 
     async fn call(&self, _ctx: &RunContext, args: JsonValue) -> ToolResult {
@@ -66,9 +243,10 @@ impl Tool for GrepTool {
         let _span =
             info_span!("grep_tool", pattern = %args.pattern, directory = %directory).entered();
 
-        match file_ops::grep(&args.pattern, directory, args.max_results) {
+        let options = args.options();
+        match file_ops::grep(&args.pattern, directory, &options) {
             Ok(result) => {
-                if result.matches.is_empty() {
+                if result.total_matches == 0 {
                     debug!(match_count = 0, "Grep found no matches");
                     return Ok(ToolReturn::text(format!(
                         "No matches found for pattern '{}' in {}",
@@ -78,16 +256,12 @@ impl Tool for GrepTool {
 
                 debug!(match_count = result.total_matches, "Grep completed");
 
-                let mut output = format!(
-                    "Found {} matches for '{}' in {}:\n",
-                    result.total_matches, args.pattern, directory
-                );
-
-                for m in &result.matches {
-                    output.push_str(&format!("\n{}:{}:{}", m.path, m.line_number, m.content));
-                }
-
-                Ok(ToolReturn::text(output))
+                Ok(ToolReturn::text(format_result(
+                    &result,
+                    options.output_mode,
+                    &args.pattern,
+                    directory,
+                )))
             }
             Err(e) => Ok(ToolReturn::error(format!("Grep failed: {}", e))),
         }
@@ -247,6 +421,89 @@ mod tests {
         assert!(text.contains("Found"));
     }
 
+    #[tokio::test]
+    async fn test_call_include_ignored() {
+        let dir = tempfile::tempdir().expect("tempdir failed");
+        fs::write(dir.path().join(".gitignore"), "secret.txt\n").expect("write failed");
+        fs::write(dir.path().join("secret.txt"), "hello").expect("write failed");
+
+        let tool = GrepTool;
+        let ctx = RunContext::minimal("test");
+        let directory = dir.path().to_str().unwrap();
+
+        let ret = tool
+            .call(
+                &ctx,
+                serde_json::json!({ "pattern": "hello", "directory": directory }),
+            )
+            .await
+            .unwrap();
+        assert!(ret.as_text().unwrap().contains("No matches found"));
+
+        let ret = tool
+            .call(
+                &ctx,
+                serde_json::json!({
+                    "pattern": "hello",
+                    "directory": directory,
+                    "include_ignored": true
+                }),
+            )
+            .await
+            .unwrap();
+        assert!(ret.as_text().unwrap().contains("secret.txt"));
+    }
+
+    #[tokio::test]
+    async fn test_call_with_context_separates_groups() {
+        let dir = tempfile::tempdir().expect("tempdir failed");
+        let content = "foo\na\nb\nc\nd\nfoo\n";
+        fs::write(dir.path().join("test.txt"), content).expect("write failed");
+
+        let tool = GrepTool;
+        let ctx = RunContext::minimal("test");
+        let ret = tool
+            .call(
+                &ctx,
+                serde_json::json!({
+                    "pattern": "foo",
+                    "directory": dir.path().to_str().unwrap(),
+                    "context": 1
+                }),
+            )
+            .await
+            .unwrap();
+
+        let text = ret.as_text().unwrap();
+        assert!(text.ends_with("test.txt:1:foo\ntest.txt-2-a\n--\ntest.txt-5-d\ntest.txt:6:foo"));
+    }
+
+    #[tokio::test]
+    async fn test_call_count_mode_with_single_string_include() {
+        let dir = tempfile::tempdir().expect("tempdir failed");
+        fs::write(dir.path().join("a.rs"), "foo\nfoo\n").expect("write failed");
+        fs::write(dir.path().join("b.md"), "foo\n").expect("write failed");
+
+        let tool = GrepTool;
+        let ctx = RunContext::minimal("test");
+        let ret = tool
+            .call(
+                &ctx,
+                serde_json::json!({
+                    "pattern": "foo",
+                    "directory": dir.path().to_str().unwrap(),
+                    "include": "*.rs",
+                    "output_mode": "count"
+                }),
+            )
+            .await
+            .unwrap();
+
+        let text = ret.as_text().unwrap();
+        assert!(text.contains("Found 2 matches for 'foo' in 1 files"));
+        assert!(text.ends_with("\na.rs:2"));
+    }
+
     #[tokio::test]
     async fn test_call_missing_pattern_returns_error() {
         let tool = GrepTool;
@@ -9,6 +9,7 @@ use tracing::debug;
 
 use serdes_ai_tools::{RunContext, SchemaBuilder, Tool, ToolDefinition, ToolResult, ToolReturn};
 
+use super::common::IgnoreRules;
 use super::file_ops;
 
 /// Maximum characters in list_files output to protect context window
@@ -24,6 +25,7 @@ struct ListFilesArgs {
     recursive: Option<bool>,
     max_depth: Option<usize>,
     max_entries: Option<usize>,
+    include_ignored: Option<bool>,
 }
 
 #[async_trait]
@@ -32,8 +34,8 @@ impl Tool for ListFilesTool {
         ToolDefinition::new(
             "list_files",
             "List files and directories with intelligent filtering. \
-             Automatically ignores common build artifacts, cache directories, \
-             and other noise while providing rich file metadata.",
+             Skips files matched by .gitignore/.ignore rules, common build artifacts \
+             and cache directories while providing rich file metadata.",
         )
         .with_parameters(
             SchemaBuilder::new()
@@ -58,6 +60,12 @@ impl Tool for ListFilesTool {
                     "Maximum number of entries to return. Defaults to 2000 (hard cap: 10000).",
                     false,
                 )
+                .boolean(
+                    "include_ignored",
+                    "Also list files excluded by .gitignore/.ignore and default ignore rules. \
+                     Defaults to false.",
+                    false,
+                )
                 .build()
                 .expect("schema build failed"),
         )
@@ -76,8 +84,10 @@ impl Tool for ListFilesTool {
         let recursive = args.recursive.unwrap_or(true);
         let max_depth = args.max_depth;
         let max_entries = args.max_entries;
+        let rules = IgnoreRules::from_global_context()
+            .with_include_ignored(args.include_ignored.unwrap_or(false));
 
-        match file_ops::list_files(directory, recursive, max_depth, max_entries) {
+        match file_ops::list_files(directory, recursive, max_depth, max_entries, &rules) {
             Ok(result) => {
                 // Format as a readable summary with file tree
                 let mut output =
@@ -8,6 +8,7 @@ use tracing::debug;
 
 use serdes_ai_tools::{RunContext, SchemaBuilder, Tool, ToolDefinition, ToolResult, ToolReturn};
 
+use super::common::sanitize_terminal_output;
 use super::tool_context::get_global_context;
 
 /// Tool for listing active terminal processes.
@@ -87,17 +88,6 @@ impl Tool for ListProcessesTool {
     }
 }
 
-/// Strip ANSI escape sequences and control characters from terminal output
-fn sanitize_terminal_output(text: &str) -> String {
-    let bytes = text.as_bytes();
-    let stripped = strip_ansi_escapes::strip(bytes);
-    let clean = String::from_utf8_lossy(&stripped);
-    clean
-        .chars()
-        .filter(|c| !c.is_control() || *c == '\n' || *c == '\t')
-        .collect()
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,6 +1,7 @@
 //! Tool implementations for Spot agents.
 //!
-//! Provides file operations, shell execution, and process management tools.
+//! Provides file operations, code navigation, git queries, shell execution,
+//! and process management tools.
 //!
 //! ## serdesAI Tool Integration
 //!
@@ -18,19 +19,31 @@
 pub mod common;
 pub mod diff;
 mod file_ops;
+mod git;
 mod shell;
+mod symbols;
 
 // Tool implementations (serdesAI wrappers)
 mod delete_file_tool;
 mod edit_file_tool;
+mod find_definition_tool;
+mod find_references_tool;
+mod git_blame_tool;
+mod git_diff_tool;
+mod git_log_tool;
+mod git_show_tool;
+mod git_status_tool;
 mod grep_tool;
 mod kill_process_tool;
 mod list_files_tool;
 mod list_processes_tool;
+mod outline_file_tool;
 mod read_file_tool;
 mod read_process_output_tool;
 mod shell_tool;
 mod tool_context;
+mod wait_for_output_tool;
+mod write_process_input_tool;
 
 // Registry
 pub mod registry;
@@ -39,7 +52,7 @@ pub mod registry;
 pub use registry::SpotToolRegistry;
 
 // Re-export tool context
-pub use tool_context::{get_global_context, set_global_context, ToolContext};
+pub use tool_context::{get_global_context, set_global_context, ProcessScope, ToolContext};
 
 // Re-export lenient JSON parsing utilities
 pub use common::{coerce_json_types, parse_tool_args_lenient};
@@ -0,0 +1,154 @@
+//! OutlineFile tool implementation.
+//!
+//! Provides a serdesAI-compatible tool for listing the definitions in a file.
+
+use async_trait::async_trait;
+use serde::Deserialize;
+use serde_json::Value as JsonValue;
+use tracing::{debug, info_span};
+
+use serdes_ai_tools::{RunContext, SchemaBuilder, Tool, ToolDefinition, ToolResult, ToolReturn};
+
+use super::symbols::{self, FileOutline};
+
+/// Tool for listing the symbols defined in a source file.
+#[derive(Debug, Clone, Default)]
+pub struct OutlineFileTool;
+
+#[derive(Debug, Deserialize)]
+struct OutlineFileArgs {
+    file_path: String,
+}
+
+/// One indented line per symbol: `kind name (lines a-b): signature`.
+fn format_outline(outline: &FileOutline) -> String {
+    let mut output = format!(
+        "{} ({}, {} symbols):",
+        outline.path,
+        outline.language,
+        outline.symbols.len()
+    );
+    for symbol in &outline.symbols {
+        output.push_str(&format!(
+            "\n{}{} {} (lines {}-{}): {}",
+            "  ".repeat(symbol.depth),
+            symbol.kind,
+            symbol.name,
+            symbol.line,
+            symbol.end_line,
+            symbol.signature
+        ));
+    }
+    output
+}
+
+#[async_trait]
+impl Tool for OutlineFileTool {
+    fn definition(&self) -> ToolDefinition {
+        ToolDefinition::new(
+            "outline_file",
+            "List the functions, types, classes and other definitions in a source file \
+             with their line ranges and signatures, nested by container. Much cheaper \
+             than reading the whole file. Supports Rust, Python, JavaScript, TypeScript, \
+             Go, Java and C.",
+        )
+        .with_parameters(
+            SchemaBuilder::new()
+                .string(
+                    "file_path",
+                    "Path to the source file. Can be relative or absolute.",
+                    true,
+                )
+                .build()
+                .expect("schema build failed"),
+        )
+    }
+
+    async fn call(&self, _ctx: &RunContext, args: JsonValue) -> ToolResult {
+        debug!(tool = "outline_file", ?args, "Tool called");
+
+        let args: OutlineFileArgs = crate::tools::common::parse_tool_args_lenient(
+            "outline_file",
+            args.clone(),
+            self.definition().parameters(),
+        )?;
+
+        let _span = info_span!("outline_file", file_path = %args.file_path).entered();
+
+        match symbols::outline(&args.file_path) {
+            Ok(outline) if outline.symbols.is_empty() => Ok(ToolReturn::text(format!(
+                "No definitions found in {}",
+                outline.path
+            ))),
+            Ok(outline) => {
+                debug!(symbol_count = outline.symbols.len(), "Outline completed");
+                Ok(ToolReturn::text(format_outline(&outline)))
+            }
+            Err(e) => Ok(ToolReturn::error(format!("Failed to outline file: {}", e))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_definition_returns_correct_name() {
+        let def = OutlineFileTool.definition();
+        assert_eq!(def.name(), "outline_file");
+        let schema_str = serde_json::to_string(def.parameters()).unwrap();
+        assert!(schema_str.contains("file_path"));
+    }
+
+    #[tokio::test]
+    async fn test_call_lists_nested_symbols() {
+        let dir = tempfile::tempdir().expect("tempdir failed");
+        let path = dir.path().join("lib.rs");
+        fs::write(
+            &path,
+            "struct Config;\n\nimpl Config {\n    fn load() -> Self {\n        Config\n    }\n}\n",
+        )
+        .unwrap();
+
+        let ctx = RunContext::minimal("test");
+        let result = OutlineFileTool
+            .call(
+                &ctx,
+                serde_json::json!({ "file_path": path.to_str().unwrap() }),
+            )
+            .await
+            .unwrap();
+
+        let text = result.as_text().unwrap();
+        assert!(text.contains("Rust, 3 symbols"));
+        assert!(text.contains("\nstruct Config (lines 1-1): struct Config;"));
+        assert!(text.contains("\n  method load (lines 4-6): fn load() -> Self"));
+    }
+
+    #[tokio::test]
+    async fn test_call_unsupported_language() {
+        let dir = tempfile::tempdir().expect("tempdir failed");
+        let path = dir.path().join("notes.txt");
+        fs::write(&path, "hello").unwrap();
+
+        let ctx = RunContext::minimal("test");
+        let result = OutlineFileTool
+            .call(
+                &ctx,
+                serde_json::json!({ "file_path": path.to_str().unwrap() }),
+            )
+            .await
+            .unwrap();
+
+        assert!(result.as_text().unwrap().contains("Unsupported language"));
+    }
+
+    #[tokio::test]
+    async fn test_call_missing_file_path_returns_error() {
+        let ctx = RunContext::minimal("test");
+        let result = OutlineFileTool.call(&ctx, serde_json::json!({})).await;
+        assert!(result.is_err());
+    }
+}
@@ -9,6 +9,7 @@ use tracing::debug;
 
 use serdes_ai_tools::{RunContext, SchemaBuilder, Tool, ToolDefinition, ToolResult, ToolReturn};
 
+use super::common::sanitize_terminal_output;
 use super::tool_context::get_global_context;
 
 /// Tool for reading process output.
@@ -48,20 +49,6 @@ fn truncate_output(output: &str, max_chars: usize) -> String {
     truncated
 }
 
-/// Strip ANSI escape sequences and control characters from terminal output
-fn sanitize_terminal_output(text: &str) -> String {
-    // Strip ANSI escape sequences
-    let bytes = text.as_bytes();
-    let stripped = strip_ansi_escapes::strip(bytes);
-    let clean = String::from_utf8_lossy(&stripped);
-
-    // Also remove other control characters that could break JSON
-    clean
-        .chars()
-        .filter(|c| !c.is_control() || *c == '\n' || *c == '\t')
-        .collect()
-}
-
 #[async_trait]
 impl Tool for ReadProcessOutputTool {
     fn definition(&self) -> ToolDefinition {
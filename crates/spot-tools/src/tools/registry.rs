@@ -9,13 +9,23 @@ use serdes_ai_tools::Tool;
 
 use super::delete_file_tool::DeleteFileTool;
 use super::edit_file_tool::EditFileTool;
+use super::find_definition_tool::FindDefinitionTool;
+use super::find_references_tool::FindReferencesTool;
+use super::git_blame_tool::GitBlameTool;
+use super::git_diff_tool::GitDiffTool;
+use super::git_log_tool::GitLogTool;
+use super::git_show_tool::GitShowTool;
+use super::git_status_tool::GitStatusTool;
 use super::grep_tool::GrepTool;
 use super::kill_process_tool::KillProcessTool;
 use super::list_files_tool::ListFilesTool;
 use super::list_processes_tool::ListProcessesTool;
+use super::outline_file_tool::OutlineFileTool;
 use super::read_file_tool::ReadFileTool;
 use super::read_process_output_tool::ReadProcessOutputTool;
 use super::shell_tool::RunShellCommandTool;
+use super::wait_for_output_tool::WaitForOutputTool;
+use super::write_process_input_tool::WriteProcessInputTool;
 
 /// Arc-wrapped tool for shared ownership.
 pub type ArcTool = Arc<dyn Tool + Send + Sync>;
@@ -46,11 +56,23 @@ pub struct SpotToolRegistry {
     pub edit_file: EditFileTool,
     pub delete_file: DeleteFileTool,
     pub grep: GrepTool,
+    // Code navigation tools
+    pub outline_file: OutlineFileTool,
+    pub find_definition: FindDefinitionTool,
+    pub find_references: FindReferencesTool,
+    // Git tools (read-only)
+    pub git_status: GitStatusTool,
+    pub git_diff: GitDiffTool,
+    pub git_log: GitLogTool,
+    pub git_blame: GitBlameTool,
+    pub git_show: GitShowTool,
     pub run_shell_command: RunShellCommandTool,
     // Process management tools
     pub list_processes: ListProcessesTool,
     pub read_process_output: ReadProcessOutputTool,
     pub kill_process: KillProcessTool,
+    pub write_process_input: WriteProcessInputTool,
+    pub wait_for_output: WaitForOutputTool,
     /// Additional tools added externally (e.g., agent tools from spot-agents).
     extra_tools: Vec<ArcTool>,
 }
@@ -63,10 +85,20 @@ impl std::fmt::Debug for SpotToolRegistry {
             .field("edit_file", &self.edit_file)
             .field("delete_file", &self.delete_file)
             .field("grep", &self.grep)
+            .field("outline_file", &self.outline_file)
+            .field("find_definition", &self.find_definition)
+            .field("find_references", &self.find_references)
+            .field("git_status", &self.git_status)
+            .field("git_diff", &self.git_diff)
+            .field("git_log", &self.git_log)
+            .field("git_blame", &self.git_blame)
+            .field("git_show", &self.git_show)
             .field("run_shell_command", &self.run_shell_command)
             .field("list_processes", &self.list_processes)
             .field("read_process_output", &self.read_process_output)
             .field("kill_process", &self.kill_process)
+            .field("write_process_input", &self.write_process_input)
+            .field("wait_for_output", &self.wait_for_output)
             .field("extra_tools_count", &self.extra_tools.len())
             .finish()
     }
@@ -99,11 +131,23 @@ impl SpotToolRegistry {
             Arc::new(self.edit_file.clone()),
             Arc::new(self.delete_file.clone()),
             Arc::new(self.grep.clone()),
+            // Code navigation tools
+            Arc::new(self.outline_file.clone()),
+            Arc::new(self.find_definition.clone()),
+            Arc::new(self.find_references.clone()),
+            // Git tools
+            Arc::new(self.git_status.clone()),
+            Arc::new(self.git_diff.clone()),
+            Arc::new(self.git_log.clone()),
+            Arc::new(self.git_blame.clone()),
+            Arc::new(self.git_show.clone()),
             Arc::new(self.run_shell_command.clone()),
             // Process management tools
             Arc::new(self.list_processes.clone()),
             Arc::new(self.read_process_output.clone()),
             Arc::new(self.kill_process.clone()),
+            Arc::new(self.write_process_input.clone()),
+            Arc::new(self.wait_for_output.clone()),
         ];
         // Include any externally-added tools
         tools.extend(self.extra_tools.clone());
@@ -138,6 +182,14 @@ impl SpotToolRegistry {
             Arc::new(self.list_files.clone()),
             Arc::new(self.read_file.clone()),
             Arc::new(self.grep.clone()),
+            Arc::new(self.outline_file.clone()),
+            Arc::new(self.find_definition.clone()),
+            Arc::new(self.find_references.clone()),
+            Arc::new(self.git_status.clone()),
+            Arc::new(self.git_diff.clone()),
+            Arc::new(self.git_log.clone()),
+            Arc::new(self.git_blame.clone()),
+            Arc::new(self.git_show.clone()),
         ]
     }
 
@@ -165,14 +217,14 @@ mod tests {
     #[test]
     fn test_registry_creation() {
         let registry = SpotToolRegistry::new();
-        assert_eq!(registry.all_tools().len(), 9);
-        assert_eq!(registry.definitions().len(), 9);
+        assert_eq!(registry.all_tools().len(), 19);
+        assert_eq!(registry.definitions().len(), 19);
     }
 
     #[test]
     fn test_registry_default_trait() {
         let registry = SpotToolRegistry::default();
-        assert_eq!(registry.all_tools().len(), 9);
+        assert_eq!(registry.all_tools().len(), 19);
     }
 
     #[test]
@@ -201,7 +253,7 @@ mod tests {
     #[test]
     fn test_all_tools_returns_correct_count() {
         let registry = SpotToolRegistry::new();
-        assert_eq!(registry.all_tools().len(), 9);
+        assert_eq!(registry.all_tools().len(), 19);
     }
 
     #[test]
@@ -219,10 +271,20 @@ mod tests {
             "edit_file",
             "delete_file",
             "grep",
+            "outline_file",
+            "find_definition",
+            "find_references",
+            "git_status",
+            "git_diff",
+            "git_log",
+            "git_blame",
+            "git_show",
             "run_shell_command",
             "list_processes",
             "read_process_output",
             "kill_process",
+            "write_process_input",
+            "wait_for_output",
         ];
 
         for name in expected {
@@ -280,7 +342,7 @@ mod tests {
     #[test]
     fn test_definitions_returns_correct_count() {
         let registry = SpotToolRegistry::new();
-        assert_eq!(registry.definitions().len(), 9);
+        assert_eq!(registry.definitions().len(), 19);
     }
 
     #[test]
@@ -349,14 +411,24 @@ mod tests {
             "edit_file",
             "delete_file",
             "grep",
+            "outline_file",
+            "find_definition",
+            "find_references",
+            "git_status",
+            "git_diff",
+            "git_log",
+            "git_blame",
+            "git_show",
             "run_shell_command",
             "list_processes",
             "read_process_output",
             "kill_process",
+            "write_process_input",
+            "wait_for_output",
         ];
 
         let tools = registry.tools_by_name(&names);
-        assert_eq!(tools.len(), 9);
+        assert_eq!(tools.len(), 19);
     }
 
     #[test]
@@ -421,7 +493,20 @@ mod tests {
         for tool in &tools {
             let name = tool.definition().name;
             assert!(
-                name == "list_files" || name == "read_file" || name == "grep",
+                matches!(
+                    name.as_str(),
+                    "list_files"
+                        | "read_file"
+                        | "grep"
+                        | "outline_file"
+                        | "find_definition"
+                        | "find_references"
+                        | "git_status"
+                        | "git_diff"
+                        | "git_log"
+                        | "git_blame"
+                        | "git_show"
+                ),
                 "Unexpected tool in read_only: {}",
                 name
             );
@@ -432,7 +517,7 @@ mod tests {
     fn test_read_only_tools_count() {
         let registry = SpotToolRegistry::new();
         let tools = registry.read_only_tools();
-        assert_eq!(tools.len(), 3);
+        assert_eq!(tools.len(), 11);
     }
 
     #[test]
@@ -448,6 +533,14 @@ mod tests {
             "list_files".to_string(),
             "read_file".to_string(),
             "grep".to_string(),
+            "outline_file".to_string(),
+            "find_definition".to_string(),
+            "find_references".to_string(),
+            "git_status".to_string(),
+            "git_diff".to_string(),
+            "git_log".to_string(),
+            "git_blame".to_string(),
+            "git_show".to_string(),
         ]
         .into_iter()
         .collect();
@@ -467,6 +560,7 @@ mod tests {
         assert!(!tool_names.contains("edit_file"));
         assert!(!tool_names.contains("delete_file"));
         assert!(!tool_names.contains("run_shell_command"));
+        assert!(!tool_names.contains("write_process_input"));
     }
 
     // =========================================================================
@@ -623,6 +717,30 @@ mod tests {
         assert_eq!(registry.grep.definition().name, "grep");
     }
 
+    #[test]
+    fn test_code_navigation_tools_exist() {
+        let registry = SpotToolRegistry::new();
+        assert_eq!(registry.outline_file.definition().name, "outline_file");
+        assert_eq!(
+            registry.find_definition.definition().name,
+            "find_definition"
+        );
+        assert_eq!(
+            registry.find_references.definition().name,
+            "find_references"
+        );
+    }
+
+    #[test]
+    fn test_git_tools_exist() {
+        let registry = SpotToolRegistry::new();
+        assert_eq!(registry.git_status.definition().name, "git_status");
+        assert_eq!(registry.git_diff.definition().name, "git_diff");
+        assert_eq!(registry.git_log.definition().name, "git_log");
+        assert_eq!(registry.git_blame.definition().name, "git_blame");
+        assert_eq!(registry.git_show.definition().name, "git_show");
+    }
+
     #[test]
     fn test_run_shell_command_tool_exists() {
         let registry = SpotToolRegistry::new();
@@ -632,6 +750,19 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_process_input_tools_exist() {
+        let registry = SpotToolRegistry::new();
+        assert_eq!(
+            registry.write_process_input.definition().name,
+            "write_process_input"
+        );
+        assert_eq!(
+            registry.wait_for_output.definition().name,
+            "wait_for_output"
+        );
+    }
+
     // =========================================================================
     // add_tool / add_tools Tests
     // =========================================================================
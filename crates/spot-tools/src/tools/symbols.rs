@@ -0,0 +1,762 @@
+//! Symbol-aware code navigation backed by tree-sitter.
+//!
+//! [`outline`] lists the definitions in a file, [`find_definitions`] finds
+//! where a name is defined across a directory and [`find_references`] finds
+//! identifiers with that name, skipping strings and comments. Definitions are
+//! recognized by node kind rather than by query, so a grammar update that
+//! renames a node only drops those symbols instead of failing to load.
+
+use std::fs;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tree_sitter::{Language, Node, Parser, Tree};
+
+use super::common::IgnoreRules;
+
+/// Files larger than this are not parsed.
+const MAX_FILE_SIZE_BYTES: u64 = 2 * 1024 * 1024;
+/// Directory depth searched by `find_definitions` and `find_references`.
+const MAX_SEARCH_DEPTH: usize = 20;
+/// Characters of a signature or source line kept in results.
+const MAX_LINE_LENGTH: usize = 200;
+
+/// Symbol navigation errors.
+#[derive(Debug, Error)]
+pub enum SymbolError {
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Path not found: {0}")]
+    NotFound(String),
+    #[error("File too large: {0} bytes (max: {1})")]
+    TooLarge(u64, u64),
+    #[error("Unsupported language for {0}")]
+    UnsupportedLanguage(String),
+    #[error("Parse error: {0}")]
+    Parse(String),
+}
+
+/// Languages the navigation tools can parse.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CodeLanguage {
+    Rust,
+    Python,
+    JavaScript,
+    TypeScript,
+    Tsx,
+    Go,
+    Java,
+    C,
+}
+
+impl CodeLanguage {
+    /// Detect the language from a file extension.
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let ext = path.extension()?.to_str()?.to_lowercase();
+        Some(match ext.as_str() {
+            "rs" => Self::Rust,
+            "py" | "pyi" => Self::Python,
+            "js" | "jsx" | "mjs" | "cjs" => Self::JavaScript,
+            "ts" | "mts" | "cts" => Self::TypeScript,
+            "tsx" => Self::Tsx,
+            "go" => Self::Go,
+            "java" => Self::Java,
+            "c" | "h" => Self::C,
+            _ => return None,
+        })
+    }
+
+    /// Display name.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Rust => "Rust",
+            Self::Python => "Python",
+            Self::JavaScript => "JavaScript",
+            Self::TypeScript => "TypeScript",
+            Self::Tsx => "TSX",
+            Self::Go => "Go",
+            Self::Java => "Java",
+            Self::C => "C",
+        }
+    }
+
+    fn grammar(self) -> Language {
+        match self {
+            Self::Rust => tree_sitter_rust::LANGUAGE.into(),
+            Self::Python => tree_sitter_python::LANGUAGE.into(),
+            Self::JavaScript => tree_sitter_javascript::LANGUAGE.into(),
+            Self::TypeScript => tree_sitter_typescript::LANGUAGE_TYPESCRIPT.into(),
+            Self::Tsx => tree_sitter_typescript::LANGUAGE_TSX.into(),
+            Self::Go => tree_sitter_go::LANGUAGE.into(),
+            Self::Java => tree_sitter_java::LANGUAGE.into(),
+            Self::C => tree_sitter_c::LANGUAGE.into(),
+        }
+    }
+
+    /// Symbol kind of a definition node, if `node` is one.
+    fn symbol_kind(self, node: Node<'_>) -> Option<&'static str> {
+        let kind = match (self, node.kind()) {
+            (Self::Rust, "function_item" | "function_signature_item") => "function",
+            (Self::Rust, "struct_item") => "struct",
+            (Self::Rust, "enum_item") => "enum",
+            (Self::Rust, "union_item") => "union",
+            (Self::Rust, "trait_item") => "trait",
+            (Self::Rust, "impl_item") => "impl",
+            (Self::Rust, "mod_item") => "module",
+            (Self::Rust, "const_item") => "const",
+            (Self::Rust, "static_item") => "static",
+            (Self::Rust, "type_item") => "type",
+            (Self::Rust, "macro_definition") => "macro",
+
+            (Self::Python, "function_definition") => "function",
+            (Self::Python, "class_definition") => "class",
+
+            (
+                Self::JavaScript | Self::TypeScript | Self::Tsx,
+                "function_declaration" | "generator_function_declaration" | "function_signature",
+            ) => "function",
+            (
+                Self::JavaScript | Self::TypeScript | Self::Tsx,
+                "class_declaration" | "abstract_class_declaration" | "class",
+            ) => "class",
+            (
+                Self::JavaScript | Self::TypeScript | Self::Tsx,
+                "method_definition" | "method_signature" | "abstract_method_signature",
+            ) => "method",
+            (Self::JavaScript | Self::TypeScript | Self::Tsx, "variable_declarator") => {
+                let value = node.child_by_field_name("value")?;
+                match value.kind() {
+                    "arrow_function" | "function_expression" | "function" => "function",
+                    _ => return None,
+                }
+            }
+            (Self::TypeScript | Self::Tsx, "interface_declaration") => "interface",
+            (Self::TypeScript | Self::Tsx, "type_alias_declaration") => "type",
+            (Self::TypeScript | Self::Tsx, "enum_declaration") => "enum",
+            (Self::TypeScript | Self::Tsx, "internal_module" | "module") => "namespace",
+
+            (Self::Go, "function_declaration") => "function",
+            (Self::Go, "method_declaration") => "method",
+            (Self::Go, "type_spec" | "type_alias") => {
+                match node.child_by_field_name("type").map(|t| t.kind()) {
+                    Some("struct_type") => "struct",
+                    Some("interface_type") => "interface",
+                    _ => "type",
+                }
+            }
+
+            (Self::Java, "class_declaration") => "class",
+            (Self::Java, "interface_declaration") => "interface",
+            (Self::Java, "enum_declaration") => "enum",
+            (Self::Java, "record_declaration") => "record",
+            (Self::Java, "annotation_type_declaration") => "annotation",
+            (Self::Java, "method_declaration") => "method",
+            (Self::Java, "constructor_declaration") => "constructor",
+
+            (Self::C, "function_definition") => "function",
+            (Self::C, "type_definition") => "typedef",
+            // Only specifiers with a body define the type
+            (Self::C, "struct_specifier" | "union_specifier" | "enum_specifier") => {
+                node.child_by_field_name("body")?;
+                match node.kind() {
+                    "struct_specifier" => "struct",
+                    "union_specifier" => "union",
+                    _ => "enum",
+                }
+            }
+            _ => return None,
+        };
+        Some(kind)
+    }
+}
+
+/// A definition found in a file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Symbol {
+    pub name: String,
+    /// `function`, `method`, `struct`, `class`, `impl`, ...
+    pub kind: String,
+    pub path: String,
+    /// 1-based first and last line.
+    pub line: usize,
+    pub end_line: usize,
+    /// Nesting level within other symbols.
+    pub depth: usize,
+    /// Name of the enclosing symbol (e.g. the class of a method).
+    pub container: Option<String>,
+    /// First line of the definition.
+    pub signature: String,
+}
+
+/// Definitions in a file, in source order.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileOutline {
+    pub path: String,
+    pub language: String,
+    pub symbols: Vec<Symbol>,
+}
+
+/// An identifier with the searched name.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Reference {
+    pub path: String,
+    /// 1-based line and column.
+    pub line: usize,
+    pub column: usize,
+    /// The source line.
+    pub content: String,
+    /// Whether this is the name of a definition rather than a use.
+    pub is_definition: bool,
+}
+
+/// Result of a directory-wide search.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SymbolSearch<T> {
+    pub results: Vec<T>,
+    pub files_searched: usize,
+    /// Whether the result limit was hit, so more results may exist.
+    pub truncated: bool,
+}
+
+fn truncate(s: &str) -> String {
+    if s.chars().count() <= MAX_LINE_LENGTH {
+        s.to_string()
+    } else {
+        let mut truncated: String = s.chars().take(MAX_LINE_LENGTH).collect();
+        truncated.push('…');
+        truncated
+    }
+}
+
+fn parse(language: CodeLanguage, source: &str) -> Result<Tree, SymbolError> {
+    let mut parser = Parser::new();
+    parser
+        .set_language(&language.grammar())
+        .map_err(|e| SymbolError::Parse(e.to_string()))?;
+    parser
+        .parse(source, None)
+        .ok_or_else(|| SymbolError::Parse("parser returned no tree".to_string()))
+}
+
+fn node_text<'a>(node: Node<'_>, source: &'a str) -> &'a str {
+    source.get(node.byte_range()).unwrap_or("")
+}
+
+/// Follow C `declarator` fields down to the declared name.
+fn declarator_name(mut node: Node<'_>) -> Option<Node<'_>> {
+    loop {
+        match node.kind() {
+            "identifier" | "type_identifier" | "field_identifier" => return Some(node),
+            _ => node = node.child_by_field_name("declarator")?,
+        }
+    }
+}
+
+/// The node holding a definition's name.
+fn name_node(node: Node<'_>) -> Option<Node<'_>> {
+    match node.kind() {
+        // `impl Trait for Type` is named after the type, without generics
+        "impl_item" => {
+            let ty = node.child_by_field_name("type")?;
+            match ty.kind() {
+                "generic_type" => ty.child_by_field_name("type"),
+                _ => Some(ty),
+            }
+        }
+        "function_definition" | "type_definition" if node.child_by_field_name("name").is_none() => {
+            declarator_name(node.child_by_field_name("declarator")?)
+        }
+        _ => node.child_by_field_name("name"),
+    }
+}
+
+/// Collect the definitions in a parsed file.
+fn collect_symbols(language: CodeLanguage, tree: &Tree, source: &str, path: &str) -> Vec<Symbol> {
+    let mut symbols: Vec<Symbol> = Vec::new();
+    // (node, index of the enclosing symbol); children are pushed in reverse
+    // so that symbols come out in source order
+    let mut stack: Vec<(Node<'_>, Option<usize>)> = vec![(tree.root_node(), None)];
+
+    while let Some((node, parent)) = stack.pop() {
+        let mut enclosing = parent;
+
+        if let Some(kind) = language.symbol_kind(node) {
+            if let Some(name) = name_node(node).map(|n| node_text(n, source)) {
+                let container = parent.map(|i| &symbols[i]);
+                // Functions inside impls, traits and classes are methods
+                let kind = match (kind, container.map(|c| c.kind.as_str())) {
+                    ("function", Some("impl" | "trait" | "class")) => "method",
+                    _ => kind,
+                };
+                let depth = container.map_or(0, |c| c.depth + 1);
+                let container = container.map(|c| c.name.clone());
+                let signature = node_text(node, source)
+                    .lines()
+                    .next()
+                    .unwrap_or("")
+                    .trim()
+                    .trim_end_matches('{')
+                    .trim_end();
+
+                symbols.push(Symbol {
+                    name: name.to_string(),
+                    kind: kind.to_string(),
+                    path: path.to_string(),
+                    line: node.start_position().row + 1,
+                    end_line: node.end_position().row + 1,
+                    depth,
+                    container,
+                    signature: truncate(signature),
+                });
+                enclosing = Some(symbols.len() - 1);
+            }
+        }
+
+        let mut cursor = node.walk();
+        let children: Vec<Node<'_>> = node.named_children(&mut cursor).collect();
+        for child in children.into_iter().rev() {
+            stack.push((child, enclosing));
+        }
+    }
+
+    symbols
+}
+
+/// Read a source file, checking its size and language.
+fn read_source(path: &Path) -> Result<(CodeLanguage, String), SymbolError> {
+    let display = path.display().to_string();
+    if !path.exists() {
+        return Err(SymbolError::NotFound(display));
+    }
+    let language =
+        CodeLanguage::from_path(path).ok_or(SymbolError::UnsupportedLanguage(display))?;
+    let size = fs::metadata(path)?.len();
+    if size > MAX_FILE_SIZE_BYTES {
+        return Err(SymbolError::TooLarge(size, MAX_FILE_SIZE_BYTES));
+    }
+    Ok((language, fs::read_to_string(path)?))
+}
+
+/// List the definitions in a file.
+pub fn outline(path: &str) -> Result<FileOutline, SymbolError> {
+    let (language, source) = read_source(Path::new(path))?;
+    let tree = parse(language, &source)?;
+    Ok(FileOutline {
+        path: path.to_string(),
+        language: language.name().to_string(),
+        symbols: collect_symbols(language, &tree, &source, path),
+    })
+}
+
+/// Split `Type::method` or `Class.method` into container and name.
+fn split_qualified(query: &str) -> (Option<&str>, &str) {
+    query
+        .rsplit_once("::")
+        .or_else(|| query.rsplit_once('.'))
+        .filter(|(container, name)| !container.is_empty() && !name.is_empty())
+        .map_or((None, query), |(container, name)| (Some(container), name))
+}
+
+/// Parse every supported file under `directory` that mentions `name`.
+///
+/// `visit` returns false to stop the walk.
+fn search_files(
+    name: &str,
+    directory: &str,
+    rules: &IgnoreRules,
+    mut visit: impl FnMut(CodeLanguage, &Tree, &str, &str) -> bool,
+) -> Result<usize, SymbolError> {
+    let root = Path::new(directory);
+    if !root.exists() {
+        return Err(SymbolError::NotFound(directory.to_string()));
+    }
+
+    let walker = rules
+        .walker(root)
+        .max_depth(Some(MAX_SEARCH_DEPTH))
+        .max_filesize(Some(MAX_FILE_SIZE_BYTES))
+        .sort_by_file_name(|a, b| a.cmp(b))
+        .build();
+
+    let mut files_searched = 0;
+    for entry in walker.flatten() {
+        if !entry.file_type().is_some_and(|ft| ft.is_file()) {
+            continue;
+        }
+        let path = entry.path();
+        let Some(language) = CodeLanguage::from_path(path) else {
+            continue;
+        };
+        let Ok(source) = fs::read_to_string(path) else {
+            continue;
+        };
+        files_searched += 1;
+        if !source.contains(name) {
+            continue;
+        }
+        let Ok(tree) = parse(language, &source) else {
+            continue;
+        };
+
+        let relative = path
+            .strip_prefix(root)
+            .unwrap_or(path)
+            .to_string_lossy()
+            .into_owned();
+        if !visit(language, &tree, source.as_str(), relative.as_str()) {
+            break;
+        }
+    }
+
+    Ok(files_searched)
+}
+
+/// Find where `query` is defined under `directory`.
+///
+/// `query` is a name, optionally qualified by its container
+/// (`Type::method` or `Class.method`). `kind` limits results to one symbol kind.
+pub fn find_definitions(
+    query: &str,
+    kind: Option<&str>,
+    directory: &str,
+    rules: &IgnoreRules,
+    max_results: usize,
+) -> Result<SymbolSearch<Symbol>, SymbolError> {
+    let (container, name) = split_qualified(query);
+    let mut results = Vec::new();
+    let mut truncated = false;
+
+    let files_searched = search_files(name, directory, rules, |language, tree, source, path| {
+        for symbol in collect_symbols(language, tree, source, path) {
+            if symbol.name != name
+                || container.is_some_and(|c| symbol.container.as_deref() != Some(c))
+                || kind.is_some_and(|k| symbol.kind != k)
+            {
+                continue;
+            }
+            if results.len() >= max_results {
+                truncated = true;
+                return false;
+            }
+            results.push(symbol);
+        }
+        true
+    })?;
+
+    Ok(SymbolSearch {
+        results,
+        files_searched,
+        truncated,
+    })
+}
+
+/// Find identifiers named `name` under `directory`.
+///
+/// Unlike a text search this skips strings, comments and longer
+/// identifiers that merely contain `name`.
+pub fn find_references(
+    name: &str,
+    directory: &str,
+    rules: &IgnoreRules,
+    max_results: usize,
+) -> Result<SymbolSearch<Reference>, SymbolError> {
+    let mut results = Vec::new();
+    let mut truncated = false;
+
+    let files_searched = search_files(name, directory, rules, |language, tree, source, path| {
+        let lines: Vec<&str> = source.lines().collect();
+        let mut stack = vec![tree.root_node()];
+
+        while let Some(node) = stack.pop() {
+            if node.child_count() > 0 {
+                let mut cursor = node.walk();
+                let children: Vec<Node<'_>> = node.children(&mut cursor).collect();
+                stack.extend(children.into_iter().rev());
+                continue;
+            }
+            if !node.kind().contains("identifier") || node_text(node, source) != name {
+                continue;
+            }
+
+            if results.len() >= max_results {
+                truncated = true;
+                return false;
+            }
+
+            let is_definition = node.parent().is_some_and(|parent| {
+                language.symbol_kind(parent).is_some()
+                    && name_node(parent).is_some_and(|n| n.id() == node.id())
+            });
+            let position = node.start_position();
+            results.push(Reference {
+                path: path.to_string(),
+                line: position.row + 1,
+                column: position.column + 1,
+                content: truncate(lines.get(position.row).unwrap_or(&"").trim()),
+                is_definition,
+            });
+        }
+        true
+    })?;
+
+    Ok(SymbolSearch {
+        results,
+        files_searched,
+        truncated,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write(dir: &Path, name: &str, content: &str) -> String {
+        let path = dir.join(name);
+        fs::write(&path, content).expect("write failed");
+        path.to_string_lossy().to_string()
+    }
+
+    fn names_and_kinds(outline: &FileOutline) -> Vec<(&str, &str, Option<&str>)> {
+        outline
+            .symbols
+            .iter()
+            .map(|s| (s.name.as_str(), s.kind.as_str(), s.container.as_deref()))
+            .collect()
+    }
+
+    #[test]
+    fn test_language_detection() {
+        assert_eq!(
+            CodeLanguage::from_path(Path::new("src/main.rs")),
+            Some(CodeLanguage::Rust)
+        );
+        assert_eq!(
+            CodeLanguage::from_path(Path::new("App.TSX")),
+            Some(CodeLanguage::Tsx)
+        );
+        assert_eq!(CodeLanguage::from_path(Path::new("README.md")), None);
+    }
+
+    #[test]
+    fn test_outline_rust() {
+        let dir = tempfile::tempdir().expect("tempdir failed");
+        let path = write(
+            dir.path(),
+            "lib.rs",
+            r#"
+pub struct Parser<T> {
+    items: Vec<T>,
+}
+
+impl<T> Parser<T> {
+    pub fn new() -> Self {
+        Self { items: Vec::new() }
+    }
+}
+
+pub trait Parse {
+    fn parse(&self) -> bool;
+}
+
+fn helper() {}
+"#,
+        );
+
+        let outline = outline(&path).expect("outline failed");
+        assert_eq!(outline.language, "Rust");
+        assert_eq!(
+            names_and_kinds(&outline),
+            vec![
+                ("Parser", "struct", None),
+                ("Parser", "impl", None),
+                ("new", "method", Some("Parser")),
+                ("Parse", "trait", None),
+                ("parse", "method", Some("Parse")),
+                ("helper", "function", None),
+            ]
+        );
+
+        let new = &outline.symbols[2];
+        assert_eq!((new.line, new.end_line, new.depth), (7, 9, 1));
+        assert_eq!(new.signature, "pub fn new() -> Self");
+    }
+
+    #[test]
+    fn test_outline_python() {
+        let dir = tempfile::tempdir().expect("tempdir failed");
+        let path = write(
+            dir.path(),
+            "app.py",
+            "class Service:\n    def run(self):\n        pass\n\n\ndef main():\n    Service().run()\n",
+        );
+
+        let outline = outline(&path).expect("outline failed");
+        assert_eq!(
+            names_and_kinds(&outline),
+            vec![
+                ("Service", "class", None),
+                ("run", "method", Some("Service")),
+                ("main", "function", None),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_outline_typescript() {
+        let dir = tempfile::tempdir().expect("tempdir failed");
+        let path = write(
+            dir.path(),
+            "api.ts",
+            r#"
+export interface User {
+    name: string;
+}
+
+export class Client {
+    fetch(id: number): User {
+        return { name: "" };
+    }
+}
+
+export const load = async (id: number) => new Client().fetch(id);
+"#,
+        );
+
+        let outline = outline(&path).expect("outline failed");
+        assert_eq!(
+            names_and_kinds(&outline),
+            vec![
+                ("User", "interface", None),
+                ("Client", "class", None),
+                ("fetch", "method", Some("Client")),
+                ("load", "function", None),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_outline_go_java_and_c() {
+        let dir = tempfile::tempdir().expect("tempdir failed");
+
+        let go = write(
+            dir.path(),
+            "main.go",
+            "package main\n\ntype Server struct{}\n\nfunc (s *Server) Start() {}\n\nfunc main() {}\n",
+        );
+        assert_eq!(
+            names_and_kinds(&outline(&go).expect("outline failed")),
+            vec![
+                ("Server", "struct", None),
+                ("Start", "method", None),
+                ("main", "function", None),
+            ]
+        );
+
+        let java = write(
+            dir.path(),
+            "App.java",
+            "public class App {\n    public App() {}\n    void run() {}\n}\n",
+        );
+        assert_eq!(
+            names_and_kinds(&outline(&java).expect("outline failed")),
+            vec![
+                ("App", "class", None),
+                ("App", "constructor", Some("App")),
+                ("run", "method", Some("App")),
+            ]
+        );
+
+        let c = write(
+            dir.path(),
+            "util.c",
+            "struct point { int x; };\n\nstatic int *make(int n) { return 0; }\n",
+        );
+        assert_eq!(
+            names_and_kinds(&outline(&c).expect("outline failed")),
+            vec![("point", "struct", None), ("make", "function", None)]
+        );
+    }
+
+    #[test]
+    fn test_outline_rejects_unsupported_files() {
+        let dir = tempfile::tempdir().expect("tempdir failed");
+        let path = write(dir.path(), "notes.md", "# Notes\n");
+        assert!(matches!(
+            outline(&path),
+            Err(SymbolError::UnsupportedLanguage(_))
+        ));
+    }
+
+    #[test]
+    fn test_find_definitions_qualified_and_by_kind() {
+        let dir = tempfile::tempdir().expect("tempdir failed");
+        write(
+            dir.path(),
+            "a.rs",
+            "struct Cache;\nimpl Cache {\n    fn get(&self) {}\n}\n",
+        );
+        write(
+            dir.path(),
+            "b.py",
+            "class Store:\n    def get(self):\n        pass\n",
+        );
+        let root = dir.path().to_str().unwrap();
+        let rules = IgnoreRules::default();
+
+        let found = find_definitions("get", None, root, &rules, 50).expect("search failed");
+        let paths: Vec<&str> = found.results.iter().map(|s| s.path.as_str()).collect();
+        assert_eq!(paths, vec!["a.rs", "b.py"]);
+        assert_eq!(found.files_searched, 2);
+
+        let found = find_definitions("Store.get", None, root, &rules, 50).expect("search failed");
+        assert_eq!(found.results.len(), 1);
+        assert_eq!(found.results[0].path, "b.py");
+
+        let found =
+            find_definitions("Cache", Some("struct"), root, &rules, 50).expect("search failed");
+        assert_eq!(found.results.len(), 1);
+        assert_eq!(found.results[0].line, 1);
+
+        let found = find_definitions("get", None, root, &rules, 1).expect("search failed");
+        assert_eq!(found.results.len(), 1);
+        assert!(found.truncated);
+    }
+
+    #[test]
+    fn test_find_references_skips_strings_comments_and_longer_names() {
+        let dir = tempfile::tempdir().expect("tempdir failed");
+        write(
+            dir.path(),
+            "lib.rs",
+            r#"// connect is documented here
+fn connect() {}
+
+fn main() {
+    connect();
+    let msg = "connect";
+    reconnect();
+}
+"#,
+        );
+        let root = dir.path().to_str().unwrap();
+
+        let found =
+            find_references("connect", root, &IgnoreRules::default(), 50).expect("search failed");
+        let refs: Vec<(usize, usize, bool)> = found
+            .results
+            .iter()
+            .map(|r| (r.line, r.column, r.is_definition))
+            .collect();
+        assert_eq!(refs, vec![(2, 4, true), (5, 5, false)]);
+        assert_eq!(found.results[1].content, "connect();");
+    }
+
+    #[test]
+    fn test_split_qualified() {
+        assert_eq!(split_qualified("Foo::bar"), (Some("Foo"), "bar"));
+        assert_eq!(split_qualified("Foo.bar"), (Some("Foo"), "bar"));
+        assert_eq!(split_qualified("bar"), (None, "bar"));
+        assert_eq!(split_qualified("::bar"), (None, "::bar"));
+    }
+}
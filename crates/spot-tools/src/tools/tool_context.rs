@@ -6,13 +6,14 @@
 use std::sync::Arc;
 use tokio::sync::mpsc;
 
-use crate::terminal::{SystemExecRequest, SystemExecResponse, SystemExecStore};
+use crate::terminal::{ProposedFileChange, SystemExecRequest, SystemExecResponse, SystemExecStore};
 
 /// Context for tool execution with terminal integration.
 ///
 /// This provides tools with access to:
 /// - Process store for tracking running terminals
-/// - Channel to request terminal spawning from UI
+/// - Channel to request terminal spawning and file-change approval from UI
+/// - Extra ignore patterns for `list_files` and `grep` from the user's config
 #[derive(Clone)]
 pub struct ToolContext {
     /// Process store for tracking terminals
@@ -21,6 +22,8 @@ pub struct ToolContext {
     pub request_tx: mpsc::UnboundedSender<SystemExecRequest>,
     /// Counter for generating request IDs
     next_request_id: Arc<std::sync::atomic::AtomicU64>,
+    /// Extra `.gitignore`-style patterns for directory walks
+    ignore_patterns: Arc<Vec<String>>,
 }
 
 impl ToolContext {
@@ -33,9 +36,21 @@ impl ToolContext {
             store,
             request_tx,
             next_request_id: Arc::new(std::sync::atomic::AtomicU64::new(1)),
+            ignore_patterns: Arc::new(Vec::new()),
         }
     }
 
+    /// Set extra ignore patterns for `list_files` and `grep`.
+    pub fn with_ignore_patterns(mut self, patterns: Vec<String>) -> Self {
+        self.ignore_patterns = Arc::new(patterns);
+        self
+    }
+
+    /// Extra ignore patterns for `list_files` and `grep`.
+    pub fn ignore_patterns(&self) -> &[String] {
+        &self.ignore_patterns
+    }
+
     /// Generate a unique request ID.
     pub fn next_request_id(&self) -> u64 {
         self.next_request_id
@@ -50,6 +65,8 @@ impl ToolContext {
         let request_id = match &request {
             SystemExecRequest::ExecuteShell { request_id, .. } => *request_id,
             SystemExecRequest::KillProcess { request_id, .. } => *request_id,
+            SystemExecRequest::ApproveFileChange { request_id, .. } => *request_id,
+            SystemExecRequest::ApproveProcessInput { request_id, .. } => *request_id,
         };
 
         let (tx, rx) = tokio::sync::oneshot::channel();
@@ -78,9 +95,18 @@ impl ToolContext {
         let rx = self.send_request(request)?;
 
         match rx.await {
-            Ok(SystemExecResponse::Started { process_id }) => Ok(process_id),
+            Ok(SystemExecResponse::Started { process_id }) => {
+                ProcessScope::record_current(&process_id);
+                Ok(process_id)
+            }
             Ok(SystemExecResponse::Error { message }) => Err(message),
             Ok(SystemExecResponse::Killed { .. }) => Err("Unexpected kill response".to_string()),
+            Ok(SystemExecResponse::FileChangeApproved { .. }) => {
+                Err("Unexpected file approval response".to_string())
+            }
+            Ok(SystemExecResponse::InputApproved) => {
+                Err("Unexpected input approval response".to_string())
+            }
             Err(_) => Err("Response channel closed".to_string()),
         }
     }
@@ -99,6 +125,58 @@ impl ToolContext {
             Ok(SystemExecResponse::Killed { .. }) => Ok(()),
             Ok(SystemExecResponse::Error { message }) => Err(message),
             Ok(SystemExecResponse::Started { .. }) => Err("Unexpected start response".to_string()),
+            Ok(SystemExecResponse::FileChangeApproved { .. }) => {
+                Err("Unexpected file approval response".to_string())
+            }
+            Ok(SystemExecResponse::InputApproved) => {
+                Err("Unexpected input approval response".to_string())
+            }
+            Err(_) => Err("Response channel closed".to_string()),
+        }
+    }
+
+    /// Ask the UI whether a file write or delete may go ahead.
+    ///
+    /// Returns the user's edited content, if they changed the proposal, or
+    /// the reason the change was refused.
+    pub async fn approve_file_change(
+        &self,
+        change: ProposedFileChange,
+    ) -> Result<Option<String>, String> {
+        let request_id = self.next_request_id();
+        let request = SystemExecRequest::ApproveFileChange { request_id, change };
+
+        let rx = self.send_request(request)?;
+
+        match rx.await {
+            Ok(SystemExecResponse::FileChangeApproved { edited }) => Ok(edited),
+            Ok(SystemExecResponse::Error { message }) => Err(message),
+            Ok(_) => Err("Unexpected response to file approval".to_string()),
+            Err(_) => Err("Response channel closed".to_string()),
+        }
+    }
+
+    /// Ask the UI whether text may be typed into a running process.
+    ///
+    /// Returns the reason the input was refused.
+    pub async fn approve_process_input(
+        &self,
+        process_id: String,
+        input: String,
+    ) -> Result<(), String> {
+        let request_id = self.next_request_id();
+        let request = SystemExecRequest::ApproveProcessInput {
+            request_id,
+            process_id,
+            input,
+        };
+
+        let rx = self.send_request(request)?;
+
+        match rx.await {
+            Ok(SystemExecResponse::InputApproved) => Ok(()),
+            Ok(SystemExecResponse::Error { message }) => Err(message),
+            Ok(_) => Err("Unexpected response to input approval".to_string()),
             Err(_) => Err("Response channel closed".to_string()),
         }
     }
@@ -143,10 +221,72 @@ impl std::fmt::Debug for ToolContext {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("ToolContext")
             .field("next_request_id", &self.next_request_id)
+            .field("ignore_patterns", &self.ignore_patterns)
             .finish_non_exhaustive()
     }
 }
 
+tokio::task_local! {
+    /// Scope of the tool call being polled, if it runs in one.
+    static PROCESS_SCOPE: ProcessScope;
+}
+
+/// Records the processes started by tool calls run in it.
+///
+/// An agent run wraps its tool calls in a scope so that cancelling the run
+/// kills the processes it started, and not those of other runs. Processes
+/// started in a child scope are also recorded in its parents.
+#[derive(Debug, Clone, Default)]
+pub struct ProcessScope {
+    started: Arc<std::sync::Mutex<Vec<String>>>,
+    parent: Option<Box<ProcessScope>>,
+}
+
+impl ProcessScope {
+    /// Create an empty scope.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a scope whose processes are also recorded in this one.
+    pub fn child(&self) -> Self {
+        Self {
+            started: Arc::default(),
+            parent: Some(Box::new(self.clone())),
+        }
+    }
+
+    /// Run `future`, recording the processes it starts in this scope.
+    pub async fn scope<F: std::future::Future>(&self, future: F) -> F::Output {
+        PROCESS_SCOPE.scope(self.clone(), future).await
+    }
+
+    /// IDs of the processes started in this scope and its children.
+    pub fn process_ids(&self) -> Vec<String> {
+        self.started
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    fn record(&self, process_id: &str) {
+        let mut scope = Some(self);
+        while let Some(current) = scope {
+            current
+                .started
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .push(process_id.to_string());
+            scope = current.parent.as_deref();
+        }
+    }
+
+    /// Record a process in the scope of the current tool call, if any.
+    fn record_current(process_id: &str) {
+        let _ = PROCESS_SCOPE.try_with(|scope| scope.record(process_id));
+    }
+}
+
 /// Global tool context holder.
 ///
 /// Tools can access this via thread-local storage or pass it through RunContext.
@@ -161,3 +301,30 @@ pub fn set_global_context(ctx: ToolContext) -> Result<(), ToolContext> {
 pub fn get_global_context() -> Option<&'static ToolContext> {
     TOOL_CONTEXT.get()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_process_scope_records_into_parents() {
+        let run = ProcessScope::new();
+        let sub_agent = run.child();
+        let other_run = ProcessScope::new();
+
+        run.scope(async { ProcessScope::record_current("run-1") })
+            .await;
+        sub_agent
+            .scope(async { ProcessScope::record_current("sub-1") })
+            .await;
+        other_run
+            .scope(async { ProcessScope::record_current("other-1") })
+            .await;
+        // Outside any scope nothing is recorded
+        ProcessScope::record_current("stray");
+
+        assert_eq!(run.process_ids(), vec!["run-1", "sub-1"]);
+        assert_eq!(sub_agent.process_ids(), vec!["sub-1"]);
+        assert_eq!(other_run.process_ids(), vec!["other-1"]);
+    }
+}
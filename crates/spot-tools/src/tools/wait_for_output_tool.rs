@@ -0,0 +1,414 @@
+//! WaitForOutput tool implementation.
+//!
+//! Blocks until a running terminal process prints a line matching a regex.
+
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use regex::{Regex, RegexBuilder};
+use serde::Deserialize;
+use serde_json::Value as JsonValue;
+use tracing::debug;
+
+use serdes_ai_tools::{RunContext, Tool, ToolDefinition, ToolResult, ToolReturn};
+
+use super::common::sanitize_terminal_output;
+use super::tool_context::get_global_context;
+use crate::terminal::{ProcessSnapshot, SystemExecStore};
+
+/// Default and maximum time to wait, in seconds.
+const DEFAULT_TIMEOUT_SECS: u64 = 30;
+const MAX_TIMEOUT_SECS: u64 = 300;
+
+/// Maximum characters of new output included in the result.
+const WAIT_OUTPUT_MAX_CHARS: usize = 10_000;
+
+/// Tool for waiting on process output.
+#[derive(Debug, Clone, Default)]
+pub struct WaitForOutputTool;
+
+#[derive(Debug, Deserialize)]
+struct WaitForOutputArgs {
+    process_id: String,
+    pattern: String,
+    since_offset: Option<usize>,
+    timeout_secs: Option<u64>,
+}
+
+/// How a wait ended.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum WaitOutcome {
+    /// The pattern matched; holds the matched text.
+    Matched(String),
+    /// The process exited before the pattern appeared.
+    Exited(Option<i32>),
+    /// The timeout passed first.
+    TimedOut,
+    /// The process was removed from the store.
+    Gone,
+}
+
+/// Result of [`wait_for_pattern`].
+#[derive(Debug)]
+struct WaitResult {
+    outcome: WaitOutcome,
+    /// Sanitized output produced since the starting offset.
+    output: String,
+    /// Output offset when the wait ended.
+    offset: usize,
+}
+
+/// The part of the kept output from `offset` on, backing up to a char
+/// boundary. Offsets in output that was already dropped return all of it.
+fn output_since(snapshot: &ProcessSnapshot, offset: usize) -> &str {
+    let output = &snapshot.output;
+    let mut start = offset
+        .saturating_sub(snapshot.output_start())
+        .min(output.len());
+    while !output.is_char_boundary(start) {
+        start -= 1;
+    }
+    &output[start..]
+}
+
+/// Keep the last `max_chars` characters of `text`.
+fn tail(text: &str, max_chars: usize) -> String {
+    let count = text.chars().count();
+    if count <= max_chars {
+        return text.to_string();
+    }
+    let kept: String = text.chars().skip(count - max_chars).collect();
+    format!(
+        "[EARLIER OUTPUT OMITTED: showing last {} of {} chars]\n{}",
+        max_chars, count, kept
+    )
+}
+
+/// Wait until `pattern` matches output written after `since_offset`, the
+/// process exits, or `timeout` passes.
+async fn wait_for_pattern(
+    store: &SystemExecStore,
+    process_id: &str,
+    pattern: &Regex,
+    since_offset: usize,
+    timeout: Duration,
+) -> WaitResult {
+    let deadline = Instant::now() + timeout;
+
+    loop {
+        let Some(snapshot) = store.snapshot(process_id) else {
+            return WaitResult {
+                outcome: WaitOutcome::Gone,
+                output: String::new(),
+                offset: since_offset,
+            };
+        };
+
+        let output = sanitize_terminal_output(output_since(&snapshot, since_offset));
+        let outcome = if let Some(m) = pattern.find(&output) {
+            Some(WaitOutcome::Matched(m.as_str().to_string()))
+        } else if snapshot.exit_code.is_some() {
+            Some(WaitOutcome::Exited(snapshot.exit_code))
+        } else if Instant::now() >= deadline {
+            Some(WaitOutcome::TimedOut)
+        } else {
+            None
+        };
+        if let Some(outcome) = outcome {
+            return WaitResult {
+                outcome,
+                output,
+                offset: snapshot.output_total,
+            };
+        }
+
+        // Wait for update or short sleep
+        tokio::select! {
+            _ = store.wait_for_update(process_id) => {}
+            _ = tokio::time::sleep(Duration::from_millis(100)) => {}
+        }
+    }
+}
+
+#[async_trait]
+impl Tool for WaitForOutputTool {
+    fn definition(&self) -> ToolDefinition {
+        ToolDefinition::new(
+            "wait_for_output",
+            "Wait until a terminal process prints output matching a regex, the process exits, \
+             or the timeout passes. Use this to wait for a dev server to report it is \
+             listening, a REPL to show its prompt, or an installer to ask a question. \
+             Returns the matched text, the output since since_offset and a new offset to pass \
+             to the next call.",
+        )
+        .with_parameters(serde_json::json!({
+            "type": "object",
+            "properties": {
+                "process_id": {
+                    "type": "string",
+                    "description": "The process ID (e.g., 'proc-1') or terminal name (e.g., 'dev-server') to watch."
+                },
+                "pattern": {
+                    "type": "string",
+                    "description": "Regex to wait for, e.g. 'Listening on|ready in' or '^>>> $'. `^` and `$` match at line boundaries; ANSI escape codes are stripped before matching."
+                },
+                "since_offset": {
+                    "type": "integer",
+                    "description": "Only match output after this offset, as returned by write_process_input or a previous wait_for_output. Defaults to 0 (all output)."
+                },
+                "timeout_secs": {
+                    "type": "integer",
+                    "description": "Seconds to wait before giving up. Defaults to 30, max 300."
+                }
+            },
+            "required": ["process_id", "pattern"]
+        }))
+    }
+
+    async fn call(&self, _ctx: &RunContext, args: JsonValue) -> ToolResult {
+        debug!(tool = "wait_for_output", ?args, "Tool called");
+
+        let args: WaitForOutputArgs = crate::tools::common::parse_tool_args_lenient(
+            "wait_for_output",
+            args.clone(),
+            self.definition().parameters(),
+        )?;
+
+        let pattern = match RegexBuilder::new(&args.pattern).multi_line(true).build() {
+            Ok(pattern) => pattern,
+            Err(e) => return Ok(ToolReturn::error(format!("Invalid pattern: {}", e))),
+        };
+        let timeout = Duration::from_secs(
+            args.timeout_secs
+                .unwrap_or(DEFAULT_TIMEOUT_SECS)
+                .clamp(1, MAX_TIMEOUT_SECS),
+        );
+        let since_offset = args.since_offset.unwrap_or(0);
+
+        let Some(tool_ctx) = get_global_context() else {
+            return Ok(ToolReturn::error("Terminal system not initialized"));
+        };
+
+        // Look up by process_id first, then by name
+        let Some(snapshot) = tool_ctx
+            .store
+            .snapshot(&args.process_id)
+            .or_else(|| tool_ctx.store.find_by_name(&args.process_id))
+        else {
+            return Ok(ToolReturn::error(format!(
+                "Process not found: {} (tried as both ID and name)",
+                args.process_id
+            )));
+        };
+        let process_id = snapshot.process_id;
+
+        let result = wait_for_pattern(
+            &tool_ctx.store,
+            &process_id,
+            &pattern,
+            since_offset,
+            timeout,
+        )
+        .await;
+        Ok(format_result(
+            &process_id,
+            &args.pattern,
+            since_offset,
+            timeout,
+            result,
+        ))
+    }
+}
+
+fn format_result(
+    process_id: &str,
+    pattern: &str,
+    since_offset: usize,
+    timeout: Duration,
+    result: WaitResult,
+) -> ToolReturn {
+    let status = match &result.outcome {
+        WaitOutcome::Matched(text) => {
+            format!("Pattern matched in process {}: {:?}", process_id, text)
+        }
+        WaitOutcome::Exited(code) => format!(
+            "Process {} exited (exit: {}) before /{}/ appeared",
+            process_id,
+            code.map_or_else(|| "unknown".to_string(), |c| c.to_string()),
+            pattern
+        ),
+        WaitOutcome::TimedOut => format!(
+            "Timed out after {}s waiting for /{}/ in process {} (still running)",
+            timeout.as_secs(),
+            pattern,
+            process_id
+        ),
+        WaitOutcome::Gone => {
+            return ToolReturn::error(format!("Process {} is no longer tracked", process_id))
+        }
+    };
+
+    let mut text = format!("{}\nOutput offset: {}\n\n", status, result.offset);
+    text.push_str(&format!("--- Output since offset {} ---\n", since_offset));
+    if result.output.is_empty() {
+        text.push_str("(no new output)");
+    } else {
+        text.push_str(&tail(&result.output, WAIT_OUTPUT_MAX_CHARS));
+    }
+    ToolReturn::text(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    use crate::terminal::{ProcessKind, MAX_OUTPUT_CHARS};
+
+    fn store_with_process(output: &str) -> Arc<SystemExecStore> {
+        let store = Arc::new(SystemExecStore::new());
+        store.upsert_process(ProcessSnapshot {
+            process_id: "proc-1".to_string(),
+            name: None,
+            kind: ProcessKind::Llm,
+            visible: true,
+            output: output.to_string(),
+            output_total: output.len(),
+            exit_code: None,
+            started_at_ms: 0,
+            finished_at_ms: None,
+        });
+        store
+    }
+
+    fn regex(pattern: &str) -> Regex {
+        RegexBuilder::new(pattern).multi_line(true).build().unwrap()
+    }
+
+    #[test]
+    fn test_definition() {
+        let def = WaitForOutputTool.definition();
+        assert_eq!(def.name(), "wait_for_output");
+        let schema_str = serde_json::to_string(def.parameters()).unwrap();
+        assert!(schema_str.contains("since_offset"));
+    }
+
+    fn snapshot(output: &str, output_total: usize) -> ProcessSnapshot {
+        ProcessSnapshot {
+            output_total,
+            ..store_with_process(output).snapshot("proc-1").unwrap()
+        }
+    }
+
+    #[test]
+    fn test_output_since() {
+        assert_eq!(output_since(&snapshot("hello", 5), 2), "llo");
+        assert_eq!(output_since(&snapshot("hello", 5), 99), "");
+        // Offsets inside a multi-byte char back up to its start
+        assert_eq!(output_since(&snapshot("aé", 3), 2), "é");
+        // Offsets count from the start of the process, not of the kept output
+        assert_eq!(output_since(&snapshot("hello", 105), 102), "llo");
+        assert_eq!(output_since(&snapshot("hello", 105), 10), "hello");
+    }
+
+    #[tokio::test]
+    async fn test_wait_matches_new_output() {
+        let store = store_with_process(">>> ");
+        let writer = store.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            writer.append_output("proc-1", "1 + 1\r\n2\r\n>>> ");
+        });
+
+        let result = wait_for_pattern(
+            &store,
+            "proc-1",
+            &regex(r"^>>> $"),
+            4,
+            Duration::from_secs(5),
+        )
+        .await;
+        assert_eq!(result.outcome, WaitOutcome::Matched(">>> ".to_string()));
+        assert_eq!(result.output, "1 + 1\n2\n>>> ");
+        assert_eq!(result.offset, 18);
+    }
+
+    #[tokio::test]
+    async fn test_wait_offsets_survive_output_trimming() {
+        let store = store_with_process("");
+        store.append_output("proc-1", &"x".repeat(MAX_OUTPUT_CHARS + 1000));
+        let offset = store.snapshot("proc-1").unwrap().output_total;
+        assert_eq!(offset, MAX_OUTPUT_CHARS + 1000);
+
+        let writer = store.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            writer.append_output("proc-1", "done\r\n");
+        });
+
+        let result = wait_for_pattern(
+            &store,
+            "proc-1",
+            &regex("done"),
+            offset,
+            Duration::from_secs(5),
+        )
+        .await;
+        assert_eq!(result.outcome, WaitOutcome::Matched("done".to_string()));
+        assert_eq!(result.output, "done\n");
+        assert_eq!(result.offset, offset + 6);
+    }
+
+    #[tokio::test]
+    async fn test_wait_times_out() {
+        let store = store_with_process("ready");
+        let result = wait_for_pattern(
+            &store,
+            "proc-1",
+            &regex("ready"),
+            5,
+            Duration::from_millis(150),
+        )
+        .await;
+        assert_eq!(result.outcome, WaitOutcome::TimedOut);
+    }
+
+    #[tokio::test]
+    async fn test_wait_reports_exit() {
+        let store = store_with_process("error: port in use\n");
+        store.mark_finished("proc-1", Some(1));
+        let result = wait_for_pattern(
+            &store,
+            "proc-1",
+            &regex("Listening"),
+            0,
+            Duration::from_secs(5),
+        )
+        .await;
+        assert_eq!(result.outcome, WaitOutcome::Exited(Some(1)));
+        assert!(result.output.contains("port in use"));
+    }
+
+    #[tokio::test]
+    async fn test_call_invalid_pattern() {
+        let ctx = RunContext::minimal("test");
+        let result = WaitForOutputTool
+            .call(
+                &ctx,
+                serde_json::json!({ "process_id": "proc-1", "pattern": "(" }),
+            )
+            .await
+            .unwrap();
+        assert!(result.is_error());
+        assert!(result.as_text().unwrap().contains("Invalid pattern"));
+    }
+
+    #[tokio::test]
+    async fn test_call_missing_pattern() {
+        let ctx = RunContext::minimal("test");
+        let result = WaitForOutputTool
+            .call(&ctx, serde_json::json!({ "process_id": "proc-1" }))
+            .await;
+        assert!(result.is_err());
+    }
+}
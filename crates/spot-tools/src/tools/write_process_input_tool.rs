@@ -0,0 +1,246 @@
+//! WriteProcessInput tool implementation.
+//!
+//! Sends text and key presses to a running terminal process.
+
+use async_trait::async_trait;
+use serde::Deserialize;
+use serde_json::Value as JsonValue;
+use tracing::debug;
+
+use serdes_ai_tools::{RunContext, Tool, ToolDefinition, ToolResult, ToolReturn};
+
+use super::common::one_or_many;
+use super::tool_context::get_global_context;
+use crate::terminal::ProcessKind;
+
+/// Named keys accepted in `keys`, besides `ctrl-<letter>`.
+const NAMED_KEYS: &[(&str, &[u8])] = &[
+    ("enter", b"\r"),
+    ("return", b"\r"),
+    ("tab", b"\t"),
+    ("space", b" "),
+    ("backspace", b"\x7f"),
+    ("escape", b"\x1b"),
+    ("esc", b"\x1b"),
+    ("up", b"\x1b[A"),
+    ("down", b"\x1b[B"),
+    ("right", b"\x1b[C"),
+    ("left", b"\x1b[D"),
+    ("home", b"\x1b[H"),
+    ("end", b"\x1b[F"),
+    ("delete", b"\x1b[3~"),
+    ("pageup", b"\x1b[5~"),
+    ("pagedown", b"\x1b[6~"),
+];
+
+/// Tool for writing input to a running process.
+#[derive(Debug, Clone, Default)]
+pub struct WriteProcessInputTool;
+
+#[derive(Debug, Deserialize)]
+struct WriteProcessInputArgs {
+    process_id: String,
+    input: Option<String>,
+    #[serde(default, deserialize_with = "one_or_many")]
+    keys: Vec<String>,
+}
+
+/// Terminal byte sequence for a key name like `enter`, `up` or `ctrl-c`.
+fn key_bytes(name: &str) -> Option<Vec<u8>> {
+    let name = name.trim().to_ascii_lowercase().replace(['_', '+'], "-");
+
+    if let Some(letter) = name
+        .strip_prefix("ctrl-")
+        .or_else(|| name.strip_prefix("c-"))
+        .or_else(|| name.strip_prefix('^'))
+    {
+        return match letter.as_bytes() {
+            [c @ b'a'..=b'z'] => Some(vec![c & 0x1f]),
+            _ => None,
+        };
+    }
+
+    let name = name.replace('-', "");
+    NAMED_KEYS
+        .iter()
+        .find(|(key, _)| *key == name)
+        .map(|(_, bytes)| bytes.to_vec())
+}
+
+#[async_trait]
+impl Tool for WriteProcessInputTool {
+    fn definition(&self) -> ToolDefinition {
+        ToolDefinition::new(
+            "write_process_input",
+            "Send input to a running terminal process, as if typed at its terminal. Use this to \
+             answer prompts, drive REPLs and interactive installers, or stop a dev server with \
+             Ctrl-C. Text is sent as-is, followed by any special keys; add 'enter' to submit a \
+             line. Text is checked against the command policy like a shell command and may \
+             need the user's approval; keys alone are sent directly. Returns the output \
+             offset at the time of writing - pass it to wait_for_output as since_offset to \
+             wait for the response.",
+        )
+        .with_parameters(serde_json::json!({
+            "type": "object",
+            "properties": {
+                "process_id": {
+                    "type": "string",
+                    "description": "The process ID (e.g., 'proc-1') or terminal name (e.g., 'dev-server') to send input to."
+                },
+                "input": {
+                    "type": "string",
+                    "description": "Text to type into the process."
+                },
+                "keys": {
+                    "type": "array",
+                    "items": { "type": "string" },
+                    "description": "Special keys to press after the text, in order: 'enter', 'tab', 'space', 'backspace', 'escape', 'up', 'down', 'left', 'right', 'home', 'end', 'delete', 'pageup', 'pagedown' or 'ctrl-<letter>' (e.g. 'ctrl-c', 'ctrl-d')."
+                }
+            },
+            "required": ["process_id"]
+        }))
+    }
+
+    async fn call(&self, _ctx: &RunContext, args: JsonValue) -> ToolResult {
+        debug!(tool = "write_process_input", ?args, "Tool called");
+
+        let args: WriteProcessInputArgs = crate::tools::common::parse_tool_args_lenient(
+            "write_process_input",
+            args.clone(),
+            self.definition().parameters(),
+        )?;
+
+        let input = args.input.unwrap_or_default();
+        let mut data = input.clone().into_bytes();
+        for key in &args.keys {
+            let Some(bytes) = key_bytes(key) else {
+                return Ok(ToolReturn::error(format!(
+                    "Unknown key '{}'. Use one of: {}, or ctrl-<letter>",
+                    key,
+                    NAMED_KEYS
+                        .iter()
+                        .map(|(name, _)| *name)
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                )));
+            };
+            data.extend(bytes);
+        }
+        if data.is_empty() {
+            return Ok(ToolReturn::error("Provide input text, keys, or both"));
+        }
+
+        let Some(tool_ctx) = get_global_context() else {
+            return Ok(ToolReturn::error("Terminal system not initialized"));
+        };
+
+        // Look up by process_id first, then by name
+        let Some(snapshot) = tool_ctx
+            .store
+            .snapshot(&args.process_id)
+            .or_else(|| tool_ctx.store.find_by_name(&args.process_id))
+        else {
+            return Ok(ToolReturn::error(format!(
+                "Process not found: {} (tried as both ID and name)",
+                args.process_id
+            )));
+        };
+
+        if snapshot.kind == ProcessKind::User {
+            return Ok(ToolReturn::error(format!(
+                "Process {} is a user terminal; only commands started with \
+                 run_shell_command accept input",
+                snapshot.process_id
+            )));
+        }
+
+        // Text typed into a shell or interpreter runs like a command, so it
+        // goes through the same approval as run_shell_command
+        if !input.is_empty() {
+            if let Err(message) = tool_ctx
+                .approve_process_input(snapshot.process_id.clone(), input)
+                .await
+            {
+                return Ok(ToolReturn::error(format!(
+                    "Input to process {} was not sent: {}",
+                    snapshot.process_id, message
+                )));
+            }
+        }
+
+        // Taken right before writing, as approval may have taken a while
+        let offset = tool_ctx
+            .store
+            .snapshot(&snapshot.process_id)
+            .map_or(snapshot.output_total, |s| s.output_total);
+        let bytes = data.len();
+        match tool_ctx.store.write_input(&snapshot.process_id, data) {
+            Ok(()) => Ok(ToolReturn::text(format!(
+                "Sent {} bytes to process {}\nOutput offset: {}",
+                bytes, snapshot.process_id, offset
+            ))),
+            Err(e) => Ok(ToolReturn::error(format!(
+                "Failed to write to process {}: {}",
+                snapshot.process_id, e
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_definition() {
+        let def = WriteProcessInputTool.definition();
+        assert_eq!(def.name(), "write_process_input");
+        let schema_str = serde_json::to_string(def.parameters()).unwrap();
+        assert!(schema_str.contains("keys"));
+    }
+
+    #[test]
+    fn test_key_bytes() {
+        assert_eq!(key_bytes("enter"), Some(b"\r".to_vec()));
+        assert_eq!(key_bytes("Ctrl-C"), Some(vec![0x03]));
+        assert_eq!(key_bytes("ctrl+d"), Some(vec![0x04]));
+        assert_eq!(key_bytes("^z"), Some(vec![0x1a]));
+        assert_eq!(key_bytes("page_down"), Some(b"\x1b[6~".to_vec()));
+        assert_eq!(key_bytes("Up"), Some(b"\x1b[A".to_vec()));
+        assert_eq!(key_bytes("ctrl-1"), None);
+        assert_eq!(key_bytes("hyper"), None);
+    }
+
+    #[tokio::test]
+    async fn test_call_unknown_key() {
+        let ctx = RunContext::minimal("test");
+        let result = WriteProcessInputTool
+            .call(
+                &ctx,
+                serde_json::json!({ "process_id": "proc-1", "keys": ["enter", "meta-x"] }),
+            )
+            .await
+            .unwrap();
+        assert!(result.is_error());
+        assert!(result.as_text().unwrap().contains("Unknown key 'meta-x'"));
+    }
+
+    #[tokio::test]
+    async fn test_call_without_input() {
+        let ctx = RunContext::minimal("test");
+        let result = WriteProcessInputTool
+            .call(&ctx, serde_json::json!({ "process_id": "proc-1" }))
+            .await
+            .unwrap();
+        assert!(result.is_error());
+    }
+
+    #[tokio::test]
+    async fn test_call_missing_process_id() {
+        let ctx = RunContext::minimal("test");
+        let result = WriteProcessInputTool
+            .call(&ctx, serde_json::json!({ "input": "y" }))
+            .await;
+        assert!(result.is_err());
+    }
+}
@@ -29,10 +29,20 @@ fn test_registry_exposes_all_expected_tools() {
         "edit_file",
         "delete_file",
         "grep",
+        "outline_file",
+        "find_definition",
+        "find_references",
+        "git_status",
+        "git_diff",
+        "git_log",
+        "git_blame",
+        "git_show",
         "run_shell_command",
         "list_processes",
         "read_process_output",
         "kill_process",
+        "write_process_input",
+        "wait_for_output",
     ];
 
     for name in expected {
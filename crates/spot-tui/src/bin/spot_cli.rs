@@ -2,9 +2,11 @@
 //!
 //! This binary always launches the terminal user interface.
 //! Use this when you explicitly want the TUI, or in environments without a display.
+//! With `--prompt`, runs a single prompt headlessly and exits.
 
 use anyhow::Result;
 use clap::Parser;
+use spot_core::headless::{HeadlessOptions, OutputFormat};
 use spot_core::runner::AppConfig;
 
 /// Spot CLI - Precision computer control 👁️
@@ -28,6 +30,26 @@ struct Args {
     /// Skip the automatic update check on startup
     #[arg(long)]
     skip_update_check: bool,
+
+    /// Resume a saved session (the most recent one if no name is given)
+    #[arg(long, value_name = "NAME", conflicts_with = "prompt")]
+    resume: Option<Option<String>>,
+
+    /// Run a single prompt without a UI and exit (`-` reads from stdin)
+    #[arg(short = 'p', long)]
+    prompt: Option<String>,
+
+    /// Agent to use for --prompt
+    #[arg(long, requires = "prompt")]
+    agent: Option<String>,
+
+    /// Model to use for --prompt
+    #[arg(long, requires = "prompt")]
+    model: Option<String>,
+
+    /// Output format for --prompt: text or json (NDJSON)
+    #[arg(long, default_value = "text", requires = "prompt")]
+    output: OutputFormat,
 }
 
 fn main() -> Result<()> {
@@ -42,7 +64,19 @@ fn main() -> Result<()> {
         debug: args.debug,
         verbose: args.verbose,
         skip_update_check: args.skip_update_check,
+        resume: args.resume,
     };
 
+    if let Some(prompt) = args.prompt {
+        let options = HeadlessOptions {
+            prompt,
+            agent: args.agent,
+            model: args.model,
+            output: args.output,
+        };
+        let code = spot_core::headless::run_headless(config, options)?;
+        std::process::exit(code);
+    }
+
     spot_tui::tui::run_tui(config)
 }
@@ -9,7 +9,7 @@
 use serde_json::Value;
 
 use super::{Activity, DiffLine, FileAction};
-use spot_core::messaging::{ToolMessage, ToolStatus};
+use spot_core::messaging::{DiffLineType, DiffMessage, ToolMessage, ToolStatus};
 
 // ─────────────────────────────────────────────────────────────────────────────
 // Converter
@@ -68,20 +68,8 @@ impl ActivityConverter {
                     vec![]
                 }
             }
-            "edit_file" | "cp_edit_file" => {
-                if matches!(tool.status, ToolStatus::Completed | ToolStatus::Failed) {
-                    Self::handle_edit_file(tool)
-                } else {
-                    vec![]
-                }
-            }
-            "delete_file" | "cp_delete_file" => {
-                if matches!(tool.status, ToolStatus::Completed | ToolStatus::Failed) {
-                    Self::handle_delete_file(tool)
-                } else {
-                    vec![]
-                }
-            }
+            // File changes arrive as Message::Diff (see `process_diff`)
+            "edit_file" | "cp_edit_file" | "delete_file" | "cp_delete_file" => vec![],
             "invoke_agent" | "cp_invoke_agent" => {
                 if matches!(tool.status, ToolStatus::Completed | ToolStatus::Failed) {
                     Self::handle_invoke_agent(tool)
@@ -109,6 +97,32 @@ impl ActivityConverter {
         result
     }
 
+    /// Convert a diff published by a file-modifying tool into an Edited activity.
+    pub fn process_diff(&self, diff: &DiffMessage) -> Vec<Activity> {
+        let diff_lines = diff
+            .lines
+            .iter()
+            .filter_map(|line| {
+                let number = line.line_number.unwrap_or_default();
+                // Strip the +/-/space prefix; the activity renders its own
+                let content = line.content.get(1..).unwrap_or_default().to_string();
+                match line.line_type {
+                    DiffLineType::Added => Some(DiffLine::Added(number, content)),
+                    DiffLineType::Removed => Some(DiffLine::Removed(number, content)),
+                    DiffLineType::Context => Some(DiffLine::Context(number, content)),
+                    DiffLineType::Header => None,
+                }
+            })
+            .collect();
+
+        vec![Activity::edited(
+            &diff.path,
+            diff.additions() as i32,
+            diff.deletions() as i32,
+            diff_lines,
+        )]
+    }
+
     // ─────────────────────────────────────────────────────────────────────────
     // Tool Handlers
     // ─────────────────────────────────────────────────────────────────────────
@@ -160,29 +174,6 @@ impl ActivityConverter {
         vec![Activity::ran(command, output, notes)]
     }
 
-    fn handle_edit_file(tool: &ToolMessage) -> Vec<Activity> {
-        let file_path = extract_file_path(&tool.args)
-            .or_else(|| extract_payload_file_path(&tool.args))
-            .unwrap_or_else(|| "unknown".to_string());
-
-        // Parse diff from result
-        let (additions, deletions, diff_lines) = tool
-            .result
-            .as_ref()
-            .map(|r| parse_diff_output(r))
-            .unwrap_or((0, 0, vec![]));
-
-        vec![Activity::edited(
-            file_path, additions, deletions, diff_lines,
-        )]
-    }
-
-    fn handle_delete_file(tool: &ToolMessage) -> Vec<Activity> {
-        let file_path = extract_file_path(&tool.args).unwrap_or_else(|| "unknown".to_string());
-        // Show deletion as an edit with only deletions
-        vec![Activity::edited(file_path, 0, 1, vec![])]
-    }
-
     fn handle_invoke_agent(tool: &ToolMessage) -> Vec<Activity> {
         let agent_name = extract_string(&tool.args, "agent_name").unwrap_or_default();
         let display_name = agent_name.clone();
@@ -246,15 +237,6 @@ fn extract_file_path(args: &Option<Value>) -> Option<String> {
     args.as_ref()?.get("file_path")?.as_str().map(String::from)
 }
 
-/// Extract file path from nested payload (for edit_file)
-fn extract_payload_file_path(args: &Option<Value>) -> Option<String> {
-    args.as_ref()?
-        .get("payload")?
-        .get("file_path")?
-        .as_str()
-        .map(String::from)
-}
-
 /// Extract directory from tool args
 fn extract_directory(args: &Option<Value>) -> Option<String> {
     args.as_ref()?
@@ -271,40 +253,6 @@ fn extract_string(args: &Option<Value>, field: &str) -> Option<String> {
         .map(String::from)
 }
 
-/// Parse diff output into (additions, deletions, diff_lines)
-fn parse_diff_output(result: &str) -> (i32, i32, Vec<DiffLine>) {
-    let mut additions = 0i32;
-    let mut deletions = 0i32;
-    let mut diff_lines = Vec::new();
-    let mut line_num = 1u32;
-
-    for line in result.lines() {
-        // Skip header lines
-        if line.starts_with("---") || line.starts_with("+++") || line.starts_with("@@") {
-            continue;
-        }
-
-        if let Some(content) = line.strip_prefix('+') {
-            additions += 1;
-            diff_lines.push(DiffLine::Added(line_num, content.to_string()));
-            line_num += 1;
-        } else if let Some(content) = line.strip_prefix('-') {
-            deletions += 1;
-            diff_lines.push(DiffLine::Removed(line_num, content.to_string()));
-            // Don't increment line_num for removed lines
-        } else if let Some(content) = line.strip_prefix(' ') {
-            diff_lines.push(DiffLine::Context(line_num, content.to_string()));
-            line_num += 1;
-        } else if !line.is_empty() {
-            // Treat as context if no prefix
-            diff_lines.push(DiffLine::Context(line_num, line.to_string()));
-            line_num += 1;
-        }
-    }
-
-    (additions, deletions, diff_lines)
-}
-
 // ─────────────────────────────────────────────────────────────────────────────
 // Message Conversion Helpers
 // ─────────────────────────────────────────────────────────────────────────────
@@ -334,13 +282,64 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_parse_diff_simple() {
-        let diff = "+added line\n-removed line\n context line";
-        let (add, del, lines) = parse_diff_output(diff);
+    fn test_process_diff_builds_edited_activity() {
+        let diff = DiffMessage {
+            path: "src/main.rs".to_string(),
+            lines: vec![
+                spot_core::messaging::DiffLine {
+                    content: "@@ -1,2 +1,2 @@".to_string(),
+                    line_type: DiffLineType::Header,
+                    line_number: None,
+                },
+                spot_core::messaging::DiffLine {
+                    content: " keep".to_string(),
+                    line_type: DiffLineType::Context,
+                    line_number: Some(1),
+                },
+                spot_core::messaging::DiffLine {
+                    content: "-old".to_string(),
+                    line_type: DiffLineType::Removed,
+                    line_number: Some(2),
+                },
+                spot_core::messaging::DiffLine {
+                    content: "+new".to_string(),
+                    line_type: DiffLineType::Added,
+                    line_number: Some(2),
+                },
+            ],
+            agent_name: None,
+            checkpoint: None,
+        };
+
+        let activities = ActivityConverter::new().process_diff(&diff);
+        assert_eq!(activities.len(), 1);
+        match &activities[0] {
+            Activity::Edited {
+                file_path,
+                additions,
+                deletions,
+                diff_lines,
+                ..
+            } => {
+                assert_eq!(file_path, "src/main.rs");
+                assert_eq!((*additions, *deletions), (1, 1));
+                assert_eq!(diff_lines.len(), 3);
+                assert!(matches!(&diff_lines[0], DiffLine::Context(1, s) if s == "keep"));
+                assert!(matches!(&diff_lines[1], DiffLine::Removed(2, s) if s == "old"));
+                assert!(matches!(&diff_lines[2], DiffLine::Added(2, s) if s == "new"));
+            }
+            other => panic!("expected Edited, got {:?}", other),
+        }
+    }
 
-        assert_eq!(add, 1);
-        assert_eq!(del, 1);
-        assert_eq!(lines.len(), 3);
+    #[test]
+    fn test_edit_file_tool_message_has_no_activity() {
+        let tool = ToolMessage {
+            tool_name: "edit_file".to_string(),
+            status: ToolStatus::Completed,
+            ..Default::default()
+        };
+        assert!(ActivityConverter::new().process_tool(&tool).is_empty());
     }
 
     #[test]
@@ -95,6 +95,14 @@ pub enum Activity {
         diff_lines: Vec<DiffLine>,
     },
 
+    /// Summary of the files a turn changed
+    Changed {
+        id: String,
+        timestamp: DateTime<Local>,
+        turn: usize,
+        files: Vec<String>,
+    },
+
     /// Streaming content (live generation)
     Streaming {
         id: String,
@@ -204,6 +212,16 @@ impl Activity {
         }
     }
 
+    /// Create a new Changed activity
+    pub fn changed(turn: usize, files: Vec<String>) -> Self {
+        Self::Changed {
+            id: generate_id(),
+            timestamp: Local::now(),
+            turn,
+            files,
+        }
+    }
+
     /// Create a new Task activity
     pub fn task(description: impl Into<String>) -> Self {
         Self::Task {
@@ -265,6 +283,7 @@ impl Activity {
             Self::Explored { id, .. } => id,
             Self::Ran { id, .. } => id,
             Self::Edited { id, .. } => id,
+            Self::Changed { id, .. } => id,
             Self::Streaming { id, .. } => id,
             Self::Task { id, .. } => id,
             Self::Thinking { id, .. } => id,
@@ -280,6 +299,7 @@ impl Activity {
             Self::Explored { timestamp, .. } => timestamp,
             Self::Ran { timestamp, .. } => timestamp,
             Self::Edited { timestamp, .. } => timestamp,
+            Self::Changed { timestamp, .. } => timestamp,
             Self::Streaming { timestamp, .. } => timestamp,
             Self::Task { timestamp, .. } => timestamp,
             Self::Thinking { timestamp, .. } => timestamp,
@@ -302,6 +322,9 @@ impl Activity {
             Self::Edited { diff_lines, .. } => {
                 1 + diff_lines.len().min(20) // Header + diff lines (capped)
             }
+            Self::Changed { files, .. } => {
+                1 + files.len() // Header + one line per file
+            }
             Self::Streaming { content, .. } => {
                 1 + content.lines().count().max(1) // Header + content lines
             }
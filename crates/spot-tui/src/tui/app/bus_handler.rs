@@ -177,6 +177,12 @@ impl TuiApp {
 
                 self.message_list_state.scroll_to_bottom();
             }
+            Message::Diff(diff) => {
+                // Activity feed: file-modifying tools report their changes as diffs
+                let new_activities = self.activity_converter.process_diff(&diff);
+                self.activities.extend(new_activities);
+                self.activity_scroll_to_bottom();
+            }
             Message::Agent(agent) => match agent.event {
                 AgentEvent::Started => {
                     if self.active_agent_stack.is_empty() {
@@ -212,6 +218,7 @@ impl TuiApp {
                         }
                     }
                     if self.active_agent_stack.is_empty() {
+                        self.append_turn_summary();
                         self.conversation.finish_current_message();
                         self.is_generating = false;
                         self.stream_start = None;
@@ -235,6 +242,7 @@ impl TuiApp {
                     if self.active_agent_stack.is_empty() {
                         self.conversation
                             .append_to_current(&format!("\n\n❌ Error: {}", message));
+                        self.append_turn_summary();
                         self.conversation.finish_current_message();
                         self.is_generating = false;
                         self.stream_start = None;
@@ -247,6 +255,30 @@ impl TuiApp {
                     }
                     self.activities.push(error_task);
                 }
+                AgentEvent::Cancelled => {
+                    // Nested runs may report after their parent already unwound them
+                    if self.active_agent_stack.contains(&agent.agent_name) {
+                        // Pop all agents down to (and including) the cancelled one
+                        while let Some(agent_name) = self.active_agent_stack.pop() {
+                            if let Some(section_id) = self.active_section_ids.remove(&agent_name) {
+                                self.conversation
+                                    .append_to_nested_agent(&section_id, "\n\n⏹ Cancelled");
+                                self.conversation.finish_nested_agent(&section_id);
+                            }
+                            if agent_name == agent.agent_name {
+                                break;
+                            }
+                        }
+                    }
+
+                    if self.active_agent_stack.is_empty() && self.is_generating {
+                        self.conversation.append_to_current("\n\n⏹ Cancelled");
+                        self.append_turn_summary();
+                        self.conversation.finish_current_message();
+                        self.is_generating = false;
+                        self.stream_start = None;
+                    }
+                }
             },
             Message::HistoryUpdate(history_update) => {
                 // Update message history from executor result
@@ -256,6 +288,7 @@ impl TuiApp {
                         history_len = self.message_history.len(),
                         "Updated message history from executor"
                     );
+                    self.autosave_session();
                 }
             }
             Message::ContextInfo(info) => {
@@ -282,6 +315,26 @@ impl TuiApp {
                 );
                 self.context_tokens_used = compressed.compressed_tokens;
             }
+            Message::Retry(retry) => {
+                tracing::warn!(
+                    provider = %retry.provider,
+                    attempt = retry.attempt,
+                    reason = %retry.reason,
+                    "Retrying model request"
+                );
+                self.copy_feedback = Some((std::time::Instant::now(), retry.summary()));
+            }
+            Message::McpStatus(status) => {
+                tracing::info!(
+                    server = %status.server,
+                    state = status.state.label(),
+                    error = ?status.error,
+                    "MCP server status changed"
+                );
+                if status.state.is_down() {
+                    self.copy_feedback = Some((std::time::Instant::now(), status.summary()));
+                }
+            }
             _ => {}
         }
     }
@@ -0,0 +1,104 @@
+//! File checkpoints: `/undo`, `/revert`, `/edits` and per-turn change summaries.
+
+use std::path::Path;
+use std::time::Instant;
+
+use super::TuiApp;
+use crate::tui::activity::Activity;
+
+impl TuiApp {
+    /// Undo the last turn's file changes, or turn `turn` and everything after it.
+    pub(super) fn undo_turn(&mut self, turn: Option<&str>) {
+        if self.is_generating {
+            self.error_message = Some("Wait for the agent to finish before undoing".to_string());
+            return;
+        }
+        let turn = match turn.map(str::parse::<usize>) {
+            None => None,
+            Some(Ok(turn)) => Some(turn),
+            Some(Err(_)) => {
+                self.error_message = Some("Usage: /undo [turn]".to_string());
+                return;
+            }
+        };
+        match self.checkpoints.undo(turn) {
+            Ok(report) => {
+                self.activities
+                    .push(Activity::task(format!("↩ {}", report)));
+                self.activity_scroll_to_bottom();
+                self.copy_feedback = Some((Instant::now(), report.to_string()));
+            }
+            Err(e) => self.error_message = Some(e.to_string()),
+        }
+    }
+
+    /// Revert the edit with id `target` as listed by `/edits`, or else the
+    /// most recent edit, optionally the most recent one to path `target`.
+    pub(super) fn revert_last_edit(&mut self, target: Option<&str>) {
+        if self.is_generating {
+            self.error_message = Some("Wait for the agent to finish before reverting".to_string());
+            return;
+        }
+        let key = target
+            .and_then(|id| self.checkpoints.edit_by_id(id))
+            .or_else(|| self.checkpoints.last_edit(target.map(Path::new)));
+        let Some(key) = key else {
+            self.error_message = Some("No edit to revert".to_string());
+            return;
+        };
+        match self.checkpoints.revert(&key) {
+            Ok(path) => {
+                let message = format!("Reverted {}", path.display());
+                self.activities
+                    .push(Activity::task(format!("↩ {}", message)));
+                self.activity_scroll_to_bottom();
+                self.copy_feedback = Some((Instant::now(), message));
+            }
+            Err(e) => self.error_message = Some(e.to_string()),
+        }
+    }
+
+    /// List the edits that can be reverted with `/revert <id>`.
+    pub(super) fn show_edits(&mut self) {
+        let edits = self.checkpoints.edits();
+        let text = if edits.is_empty() {
+            "No edits to revert.".to_string()
+        } else {
+            let lines: Vec<String> = edits.iter().map(|edit| format!("  {}", edit)).collect();
+            format!("Revert an edit with /revert <id>.\n{}", lines.join("\n"))
+        };
+
+        self.conversation.start_assistant_message();
+        self.conversation.append_to_current(&text);
+        self.conversation.finish_current_message();
+        self.activities.push(Activity::assistant_message(text));
+        self.message_list_state.scroll_to_bottom();
+    }
+
+    /// Summarize the files the current turn touched, if any.
+    pub(super) fn append_turn_summary(&mut self) {
+        let Some(summary) = self
+            .checkpoints
+            .current_turn()
+            .and_then(|turn| self.checkpoints.turn_summary(turn))
+        else {
+            return;
+        };
+        self.conversation
+            .append_to_current(&format!("\n\n{}", summary));
+
+        let files = summary
+            .files
+            .iter()
+            .map(|file| {
+                let mut line = format!("{} {}", file.change, file.path.display());
+                if file.edits > 1 {
+                    line.push_str(&format!(" ({} edits)", file.edits));
+                }
+                line
+            })
+            .collect();
+        self.activities.push(Activity::changed(summary.turn, files));
+        self.activity_scroll_to_bottom();
+    }
+}
@@ -5,12 +5,15 @@ use std::time::Instant;
 use anyhow::Result;
 use tui_textarea::Input;
 
+use super::file_approval::FileApprovalChoice;
+use super::system_exec::ShellApprovalChoice;
 use super::TuiApp;
 use crate::tui::activity::Activity;
 use crate::tui::event::AppEvent;
 use crate::tui::execution::execute_agent;
 use crate::tui::hit_test::ClickTarget;
 use crate::tui::widgets;
+use spot_core::agents::CancelHandle;
 use spot_core::config::Settings;
 
 impl TuiApp {
@@ -36,10 +39,65 @@ impl TuiApp {
                         self.should_quit = true;
                         return Ok(());
                     }
+                    // Shell approval modal takes priority over everything else.
+                    // Only `y` approves, so Enter pressed while typing can't.
+                    (_, KeyCode::Char('y' | 'Y')) if self.system_exec.current().is_some() => {
+                        self.resolve_shell_approval(ShellApprovalChoice::Approve);
+                        return Ok(());
+                    }
+                    (_, KeyCode::Char('a' | 'A'))
+                        if self.system_exec.current().is_some_and(|p| !p.is_input()) =>
+                    {
+                        self.resolve_shell_approval(ShellApprovalChoice::AlwaysAllow);
+                        return Ok(());
+                    }
+                    (_, KeyCode::Char('n' | 'N') | KeyCode::Esc)
+                        if self.system_exec.current().is_some() =>
+                    {
+                        self.resolve_shell_approval(ShellApprovalChoice::Deny);
+                        return Ok(());
+                    }
+                    _ if self.system_exec.current().is_some() => {
+                        return Ok(());
+                    }
+                    // File review modal; while editing, keys go to the editor
+                    (KeyModifiers::CONTROL, KeyCode::Char('s'))
+                        if self.file_approval.editor.is_some() =>
+                    {
+                        self.resolve_file_approval(FileApprovalChoice::Accept);
+                        return Ok(());
+                    }
+                    (_, KeyCode::Esc) if self.file_approval.editor.is_some() => {
+                        self.file_approval.editor = None;
+                        return Ok(());
+                    }
+                    _ if self.file_approval.editor.is_some() => {
+                        if let Some(editor) = self.file_approval.editor.as_mut() {
+                            editor.input(Input::from(key));
+                        }
+                        return Ok(());
+                    }
+                    // As with commands, only `y` accepts
+                    (_, KeyCode::Char('y' | 'Y')) if self.file_approval.current().is_some() => {
+                        self.resolve_file_approval(FileApprovalChoice::Accept);
+                        return Ok(());
+                    }
+                    (_, KeyCode::Char('e' | 'E')) if self.file_approval.current().is_some() => {
+                        self.edit_file_approval();
+                        return Ok(());
+                    }
+                    (_, KeyCode::Char('n' | 'N') | KeyCode::Esc)
+                        if self.file_approval.current().is_some() =>
+                    {
+                        self.resolve_file_approval(FileApprovalChoice::Reject);
+                        return Ok(());
+                    }
+                    _ if self.file_approval.current().is_some() => {
+                        return Ok(());
+                    }
                     (KeyModifiers::CONTROL, KeyCode::Char('c')) => {
                         if self.is_generating {
-                            // Cancel generation?
-                            // TODO: implement cancellation
+                            self.cancel_generation();
                         } else if let Some(selected) = self.get_selected_text_from_activities() {
                             self.clipboard.copy(&selected);
                             self.copy_feedback = Some((Instant::now(), "Copied!".to_string()));
@@ -74,6 +132,7 @@ impl TuiApp {
                         self.activities.clear();
                         self.rendered_lines.clear();
                         self.message_history.clear();
+                        self.current_session = None;
                         self.input = Self::build_input();
                         self.selection.clear();
                         self.activity_state = widgets::ActivityFeedState::default();
@@ -103,16 +162,23 @@ impl TuiApp {
                             self.show_settings = false;
                         } else if self.show_folder_modal {
                             self.close_folder_modal();
+                        } else if self.show_session_picker {
+                            self.close_session_picker();
                         } else if self.show_oauth_dialog {
                             // Cancel OAuth - just close dialog (flow continues in background)
                             self.show_oauth_dialog = false;
                             self.oauth_dialog_url = None;
                             self.oauth_dialog_port = None;
                             self.oauth_dialog_provider = None;
-                        } else {
+                        } else if self.show_agent_dropdown
+                            || self.show_model_dropdown
+                            || self.show_help
+                        {
                             self.show_agent_dropdown = false;
                             self.show_model_dropdown = false;
                             self.show_help = false;
+                        } else if self.is_generating {
+                            self.cancel_generation();
                         }
                         return Ok(());
                     }
@@ -140,6 +206,16 @@ impl TuiApp {
                         // Key pool absorbs all events when active
                         return Ok(());
                     }
+                    // Remote MCP server form absorbs all events when active
+                    _ if self.settings_state.mcp_remote_form.active => {
+                        let clipboard_text = self.clipboard.paste();
+                        crate::tui::settings::handle_mcp_remote_form_event(
+                            &mut self.settings_state.mcp_remote_form,
+                            key,
+                            clipboard_text.as_deref(),
+                        );
+                        return Ok(());
+                    }
                     // Settings navigation when settings is open
                     (KeyModifiers::NONE, KeyCode::Tab) if self.show_settings => {
                         self.handle_settings_tab_key(false);
@@ -227,6 +303,31 @@ impl TuiApp {
                     _ if self.show_folder_modal => {
                         return Ok(());
                     }
+                    // Session picker navigation
+                    (KeyModifiers::NONE, KeyCode::Up) if self.show_session_picker => {
+                        self.session_picker_selected =
+                            self.session_picker_selected.saturating_sub(1);
+                        return Ok(());
+                    }
+                    (KeyModifiers::NONE, KeyCode::Down) if self.show_session_picker => {
+                        let max_index = self.session_picker_entries.len().saturating_sub(1);
+                        if self.session_picker_selected < max_index {
+                            self.session_picker_selected += 1;
+                        }
+                        return Ok(());
+                    }
+                    (KeyModifiers::NONE, KeyCode::Enter) if self.show_session_picker => {
+                        self.session_picker_confirm(self.session_picker_selected);
+                        return Ok(());
+                    }
+                    (KeyModifiers::NONE, KeyCode::Delete) if self.show_session_picker => {
+                        self.session_picker_delete_selected();
+                        return Ok(());
+                    }
+                    // Absorb all other keys when session picker is open
+                    _ if self.show_session_picker => {
+                        return Ok(());
+                    }
                     // Absorb all other keys when OAuth dialog is open
                     _ if self.show_oauth_dialog => {
                         return Ok(());
@@ -250,6 +351,23 @@ impl TuiApp {
                         self.handle_settings_key_pool_open();
                         return Ok(());
                     }
+                    // 'a' / 'e' add or edit a remote server in the MCP Servers tab
+                    (KeyModifiers::NONE, KeyCode::Char('a'))
+                        if self.show_settings
+                            && self.settings_state.active_tab
+                                == crate::tui::settings::SettingsTab::McpServers =>
+                    {
+                        self.handle_settings_mcp_remote_open(false);
+                        return Ok(());
+                    }
+                    (KeyModifiers::NONE, KeyCode::Char('e'))
+                        if self.show_settings
+                            && self.settings_state.active_tab
+                                == crate::tui::settings::SettingsTab::McpServers =>
+                    {
+                        self.handle_settings_mcp_remote_open(true);
+                        return Ok(());
+                    }
                     // ─────────────────────────────────────────────────────────────
                     // Model settings editing handlers
                     // ─────────────────────────────────────────────────────────────
@@ -300,7 +418,10 @@ impl TuiApp {
                 if self.show_settings
                     || self.show_agent_dropdown
                     || self.show_folder_modal
+                    || self.show_session_picker
                     || self.show_help
+                    || self.system_exec.current().is_some()
+                    || self.file_approval.current().is_some()
                 {
                     return Ok(());
                 }
@@ -332,6 +453,7 @@ impl TuiApp {
                 if self.show_settings
                     || self.show_agent_dropdown
                     || self.show_folder_modal
+                    || self.show_session_picker
                     || self.show_help
                 {
                     return Ok(());
@@ -392,6 +514,11 @@ impl TuiApp {
             } => {
                 self.last_mouse_pos = Some((col, row));
 
+                // The approval modals only take keyboard input
+                if self.system_exec.current().is_some() || self.file_approval.current().is_some() {
+                    return Ok(());
+                }
+
                 // Check hit test first
                 // Note: col, row order in hit_test(x, y)
                 let target = self.hit_registry.hit_test(col, row).cloned();
@@ -424,6 +551,16 @@ impl TuiApp {
                     }
                 }
 
+                if self.show_session_picker {
+                    if let Some(ClickTarget::SessionItem(index)) = &target {
+                        self.session_picker_confirm(*index);
+                    } else {
+                        // Click outside session picker - close without loading
+                        self.close_session_picker();
+                    }
+                    return Ok(());
+                }
+
                 // Handle settings clicks when settings is open
                 if self.show_settings {
                     use crate::tui::settings::{McpPanel, PinnedAgentsPanel, SettingsTab};
@@ -633,6 +770,8 @@ impl TuiApp {
         // Handle slash commands
         if content.starts_with('/') {
             let parts: Vec<&str> = content.split_whitespace().collect();
+            // Everything after the command, so a name with spaces is validated whole
+            let rest = content[parts[0].len()..].trim();
             match parts[0] {
                 "/attach" => {
                     if let Some(path_str) = parts.get(1) {
@@ -651,6 +790,48 @@ impl TuiApp {
                     self.input = Self::build_input();
                     return Ok(());
                 }
+                "/sessions" => {
+                    self.input = Self::build_input();
+                    self.open_session_picker();
+                    return Ok(());
+                }
+                "/save" => {
+                    if !rest.is_empty() {
+                        self.input = Self::build_input();
+                        self.save_session_as(rest);
+                    } else {
+                        self.error_message = Some("Usage: /save <name>".to_string());
+                    }
+                    return Ok(());
+                }
+                "/undo" => {
+                    self.input = Self::build_input();
+                    self.undo_turn(parts.get(1).copied());
+                    return Ok(());
+                }
+                "/revert" => {
+                    self.input = Self::build_input();
+                    self.revert_last_edit(parts.get(1).copied());
+                    return Ok(());
+                }
+                "/edits" => {
+                    self.input = Self::build_input();
+                    self.show_edits();
+                    return Ok(());
+                }
+                "/prompts" | "/resources" => {
+                    self.input = Self::build_input();
+                    self.show_mcp_catalog(parts[0]).await;
+                    return Ok(());
+                }
+                "/load" => {
+                    self.input = Self::build_input();
+                    match parts.get(1) {
+                        Some(_) => self.load_session(Some(rest)),
+                        None => self.open_session_picker(),
+                    }
+                    return Ok(());
+                }
                 _ => {} // Continue as message
             }
         }
@@ -692,10 +873,14 @@ impl TuiApp {
         let tool_registry = self.tool_registry.clone();
         let mcp_manager = self.mcp_manager.clone();
         let sender = self.message_bus.sender();
+        let cancel = CancelHandle::new();
+        self.checkpoints.begin_turn();
+        let checkpoints = self.checkpoints.clone();
 
         // Mark as generating
         self.is_generating = true;
         self.stream_start = Some(Instant::now());
+        self.cancel_handle = Some(cancel.clone());
 
         // Spawn agent execution as local task (not Send-safe due to Database)
         // Results come back via MessageBus which the main loop processes
@@ -711,6 +896,8 @@ impl TuiApp {
                 tool_registry,
                 mcp_manager,
                 sender,
+                cancel,
+                checkpoints,
             )
             .await;
         });
@@ -718,6 +905,17 @@ impl TuiApp {
         Ok(())
     }
 
+    /// Cancel the running agent and refuse the approvals it is waiting on;
+    /// the bus reports when it has stopped.
+    fn cancel_generation(&mut self) {
+        if let Some(cancel) = self.cancel_handle.take() {
+            cancel.cancel();
+            self.clear_pending_approvals();
+            self.activities.push(Activity::task("⏹ Cancelling..."));
+            self.activity_scroll_to_bottom();
+        }
+    }
+
     /// Add a user message to activities
     pub(super) fn add_user_activity(&mut self, content: &str) {
         self.activities.push(Activity::user_message(content));
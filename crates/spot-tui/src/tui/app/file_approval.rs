@@ -0,0 +1,145 @@
+//! Approval of file writes and deletes requested by tools.
+//!
+//! `edit_file` and `delete_file` send an `ApproveFileChange` request through
+//! the global `ToolContext`. Each one is checked against the file rules of
+//! the [`CommandPolicy`]: allowed changes go ahead, denied ones fail, and the
+//! rest are queued for the review modal, where the proposed content can also
+//! be edited before it is written.
+
+use std::collections::VecDeque;
+
+use ratatui::style::{Modifier, Style};
+use tracing::info;
+use tui_textarea::TextArea;
+
+use super::TuiApp;
+use crate::tui::theme::Theme;
+use spot_core::config::CommandAction;
+use spot_core::terminal::{ProposedFileChange, SystemExecResponse};
+use spot_core::tools::CommandPolicy;
+
+/// File change awaiting user approval
+#[derive(Debug)]
+pub struct PendingFileApproval {
+    pub request_id: u64,
+    pub change: ProposedFileChange,
+}
+
+/// User's answer in the review modal
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileApprovalChoice {
+    Accept,
+    Reject,
+}
+
+/// State for file changes requested by tools
+#[derive(Default)]
+pub struct FileApprovalState {
+    /// Changes waiting for approval; the front one is shown
+    pub pending: VecDeque<PendingFileApproval>,
+    /// Editor for the front change's content, while the user edits it
+    pub editor: Option<TextArea<'static>>,
+}
+
+impl FileApprovalState {
+    /// The change currently shown in the review modal.
+    pub fn current(&self) -> Option<&PendingFileApproval> {
+        self.pending.front()
+    }
+}
+
+impl TuiApp {
+    /// Handle a file-change approval request from `edit_file` / `delete_file`.
+    pub(super) fn handle_file_change_request(
+        &mut self,
+        request_id: u64,
+        change: ProposedFileChange,
+    ) {
+        let decision =
+            CommandPolicy::load(&self.db).evaluate_file_change(&change.path, change.operation());
+        info!(
+            request_id,
+            path = %change.path,
+            action = ?decision.action,
+            reason = %decision.reason,
+            "Evaluated file change against policy"
+        );
+
+        match decision.action {
+            CommandAction::Allow => self.system_exec.store.respond(
+                request_id,
+                SystemExecResponse::FileChangeApproved { edited: None },
+            ),
+            CommandAction::Deny => {
+                let message = format!("File change denied by policy: {}", decision.reason);
+                self.error_message = Some(format!("{}: {}", message, change.path));
+                self.system_exec
+                    .store
+                    .respond(request_id, SystemExecResponse::Error { message });
+            }
+            CommandAction::Ask => {
+                self.file_approval
+                    .pending
+                    .push_back(PendingFileApproval { request_id, change });
+            }
+        }
+    }
+
+    /// Resolve the change shown in the review modal.
+    ///
+    /// Accepting while the editor is open writes the edited content.
+    pub(super) fn resolve_file_approval(&mut self, choice: FileApprovalChoice) {
+        let editor = self.file_approval.editor.take();
+        let Some(pending) = self.file_approval.pending.pop_front() else {
+            return;
+        };
+
+        let response = match choice {
+            FileApprovalChoice::Reject => {
+                info!(request_id = pending.request_id, "User rejected file change");
+                SystemExecResponse::Error {
+                    message: "File change rejected by user".to_string(),
+                }
+            }
+            FileApprovalChoice::Accept => {
+                let proposed = pending.change.content.as_deref();
+                let edited = editor
+                    .map(|editor| {
+                        // The editor works in lines; keep the trailing newline
+                        let mut text = editor.lines().join("\n");
+                        if proposed.is_some_and(|c| c.ends_with('\n')) {
+                            text.push('\n');
+                        }
+                        text
+                    })
+                    .filter(|edited| proposed != Some(edited.as_str()));
+                info!(
+                    request_id = pending.request_id,
+                    edited = edited.is_some(),
+                    "User accepted file change"
+                );
+                SystemExecResponse::FileChangeApproved { edited }
+            }
+        };
+        self.system_exec.store.respond(pending.request_id, response);
+    }
+
+    /// Open the proposed content of the shown change for editing.
+    ///
+    /// Deletes have no content to edit.
+    pub(super) fn edit_file_approval(&mut self) {
+        let Some(content) = self
+            .file_approval
+            .current()
+            .and_then(|pending| pending.change.content.as_deref())
+        else {
+            return;
+        };
+
+        let mut editor = TextArea::from(content.lines());
+        editor.set_cursor_line_style(Style::default());
+        editor.set_cursor_style(Style::default().add_modifier(Modifier::REVERSED));
+        editor.set_style(Style::default().fg(Theme::TEXT).bg(Theme::INPUT_BG));
+        self.file_approval.editor = Some(editor);
+    }
+}
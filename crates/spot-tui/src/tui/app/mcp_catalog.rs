@@ -0,0 +1,45 @@
+//! `/prompts` and `/resources`: list what running MCP servers offer.
+
+use spot_core::mcp::{prompt_catalog, resource_catalog};
+
+use super::TuiApp;
+use crate::tui::activity::Activity;
+
+impl TuiApp {
+    /// Show the MCP prompts (`/prompts`) or resources (`/resources`) that
+    /// can be used from the input.
+    pub(super) async fn show_mcp_catalog(&mut self, command: &str) {
+        let (entries, hint) = if command == "/prompts" {
+            let prompts = self.mcp_manager.list_all_prompts().await;
+            (
+                prompt_catalog(&prompts),
+                "Run a prompt by sending its command.",
+            )
+        } else {
+            let resources = self.mcp_manager.list_all_resources().await;
+            (
+                resource_catalog(&resources),
+                "Mention a resource in a message to attach it.",
+            )
+        };
+
+        let text = if entries.is_empty() {
+            format!("No MCP {} available.", command.trim_start_matches('/'))
+        } else {
+            let lines: Vec<String> = entries
+                .into_iter()
+                .map(|(usage, description)| match description {
+                    Some(description) => format!("  {}  {}", usage, description),
+                    None => format!("  {}", usage),
+                })
+                .collect();
+            format!("{}\n{}", hint, lines.join("\n"))
+        };
+
+        self.conversation.start_assistant_message();
+        self.conversation.append_to_current(&text);
+        self.conversation.finish_current_message();
+        self.activities.push(Activity::assistant_message(text));
+        self.message_list_state.scroll_to_bottom();
+    }
+}
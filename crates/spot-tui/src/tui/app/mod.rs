@@ -1,11 +1,16 @@
 //! Main TUI application state and logic
 
 mod bus_handler;
+mod checkpoints;
 mod context;
 mod event_handling;
+mod file_approval;
 mod folder_modal;
+mod mcp_catalog;
 pub mod oauth;
+mod sessions;
 mod settings_keys;
+mod system_exec;
 mod throughput;
 
 use std::collections::HashMap;
@@ -37,13 +42,17 @@ use super::state::TuiConversation;
 use super::theme::Theme;
 use super::ui;
 use super::widgets::{self, ActivityFeedState};
-use spot_core::agents::{AgentManager, UserMode};
+use file_approval::FileApprovalState;
+use spot_core::agents::{AgentManager, CancelHandle, UserMode};
+use spot_core::checkpoints::CheckpointStore;
 use spot_core::config::Settings;
 use spot_core::db::Database;
-use spot_core::mcp::McpManager;
+use spot_core::mcp::{McpManager, McpSupervisorConfig};
 use spot_core::messaging::MessageBus;
 use spot_core::models::ModelRegistry;
+use spot_core::session::{SessionManager, SessionMeta};
 use spot_core::tools::SpotToolRegistry;
+use system_exec::SystemExecState;
 
 /// Main TUI application
 pub struct TuiApp {
@@ -77,6 +86,10 @@ pub struct TuiApp {
     pub user_mode: UserMode,
     /// Whether we're currently generating
     pub is_generating: bool,
+    /// Cancels the running agent (Ctrl+C / Esc while generating)
+    cancel_handle: Option<CancelHandle>,
+    /// File checkpoints for `/undo` and `/revert`
+    checkpoints: CheckpointStore,
     /// Text input area
     pub input: TextArea<'static>,
     /// Selected text state
@@ -101,6 +114,8 @@ pub struct TuiApp {
     pub active_section_ids: HashMap<String, String>,
     /// Request history for context
     pub message_history: Vec<ModelRequest>,
+    /// Name of the session the conversation is auto-saved to
+    pub current_session: Option<String>,
 
     /// File attachments
     pub attachments: AttachmentManager,
@@ -114,6 +129,10 @@ pub struct TuiApp {
     pub show_settings: bool,
     /// Settings panel state
     pub settings_state: SettingsState,
+    /// Shell commands requested by tools, and their approval queue
+    pub system_exec: SystemExecState,
+    /// File writes and deletes awaiting review
+    pub file_approval: FileApprovalState,
 
     // ─────────────────────────────────────────────────────────────────────────
     // Folder modal state
@@ -129,6 +148,16 @@ pub struct TuiApp {
     /// Scroll offset for folder modal
     pub folder_modal_scroll: usize,
 
+    // ─────────────────────────────────────────────────────────────────────────
+    // Session picker state
+    // ─────────────────────────────────────────────────────────────────────────
+    /// Whether session picker is visible
+    pub show_session_picker: bool,
+    /// Saved sessions listed in the picker (most recent first)
+    pub session_picker_entries: Vec<SessionMeta>,
+    /// Selected index in session picker
+    pub session_picker_selected: usize,
+
     // ─────────────────────────────────────────────────────────────────────────
     // OAuth dialog state
     // ─────────────────────────────────────────────────────────────────────────
@@ -207,6 +236,9 @@ impl TuiApp {
         #[allow(clippy::arc_with_non_send_sync)]
         let db = Arc::new(Database::open()?);
         db.migrate()?;
+        if let Err(e) = SessionManager::new(&db).import_legacy_sessions() {
+            tracing::warn!("Failed to import saved sessions: {}", e);
+        }
 
         // Load settings
         let settings = Settings::new(&db);
@@ -218,8 +250,9 @@ impl TuiApp {
         let agents = Arc::new(AgentManager::new());
         let current_agent = agents.current_name();
         let tool_registry = Arc::new(SpotToolRegistry::new());
-        let mcp_manager = Arc::new(McpManager::new());
         let message_bus = MessageBus::new();
+        let mcp_manager = Arc::new(McpManager::new().with_bus(message_bus.sender()));
+        let system_exec = SystemExecState::install();
 
         // OAuth completion channel
         let (oauth_completion_tx, oauth_completion_rx) = tokio::sync::mpsc::unbounded_channel();
@@ -246,6 +279,8 @@ impl TuiApp {
             current_model,
             user_mode,
             is_generating: false,
+            cancel_handle: None,
+            checkpoints: CheckpointStore::new(),
             input,
             selection: SelectionState::default(),
             conversation: TuiConversation::new(),
@@ -257,17 +292,23 @@ impl TuiApp {
             active_agent_stack: Vec::new(),
             active_section_ids: HashMap::new(),
             message_history: Vec::new(),
+            current_session: None,
             attachments: AttachmentManager::default(),
             context_tokens_used: 0,
             context_window_size: 128000, // Default for GPT-4o
             show_help: false,
             show_settings: false,
             settings_state: SettingsState::default(),
+            system_exec,
+            file_approval: FileApprovalState::default(),
             show_folder_modal: false,
             current_working_dir: std::env::current_dir().unwrap_or_default(),
             folder_modal_entries: Vec::new(),
             folder_modal_selected: 0,
             folder_modal_scroll: 0,
+            show_session_picker: false,
+            session_picker_entries: Vec::new(),
+            session_picker_selected: 0,
             // OAuth dialog
             show_oauth_dialog: false,
             oauth_dialog_provider: None,
@@ -372,17 +413,22 @@ impl TuiApp {
 
     /// Run the main event loop
     pub async fn run(&mut self) -> Result<()> {
-        // Start MCP servers in background
+        // Start MCP servers in background, then keep them running
         let mcp = self.mcp_manager.clone();
         tokio::spawn(async move {
             if let Err(e) = mcp.start_all().await {
                 tracing::error!("Failed to start MCP servers: {}", e);
             }
+            mcp.supervise(McpSupervisorConfig::default());
         });
 
         // Take events out of self to avoid borrow conflicts in select! loop
         let mut events = self.events.take().expect("Events not initialized");
         let mut bus_receiver = self.message_bus.subscribe();
+        let mut exec_requests = self
+            .system_exec
+            .take_request_rx()
+            .expect("Exec request receiver not initialized");
 
         // Auto-scroll constants
         let auto_scroll_interval = Duration::from_millis(50);
@@ -503,6 +549,10 @@ impl TuiApp {
                     }
                     needs_render = true;
                 }
+                Some(request) = exec_requests.recv() => {
+                    self.handle_exec_request(request);
+                    needs_render = true;
+                }
                 maybe_event = events.next() => {
                     if let Some(event) = maybe_event {
                         self.handle_event(event).await?;
@@ -0,0 +1,183 @@
+//! Session persistence: auto-save, `/save`, `/load` and the session picker.
+
+use std::time::Instant;
+
+use spot_core::session::{transcript, SessionData, SessionManager, TranscriptEntry};
+
+use super::TuiApp;
+use crate::tui::activity::Activity;
+use crate::tui::widgets;
+
+/// Prefix for auto-generated session names.
+const AUTO_SESSION_PREFIX: &str = "chat";
+
+impl TuiApp {
+    /// Save the conversation to the active session after a completed turn.
+    ///
+    /// The first turn of a new conversation starts an auto-named session.
+    pub(super) fn autosave_session(&mut self) {
+        if self.message_history.is_empty() {
+            return;
+        }
+
+        let manager = SessionManager::new(&self.db);
+        let name = self
+            .current_session
+            .get_or_insert_with(|| manager.generate_name(AUTO_SESSION_PREFIX))
+            .clone();
+        let model = self.effective_model_for_agent(&self.current_agent);
+
+        if let Err(e) = manager.save(&name, &self.message_history, &self.current_agent, &model) {
+            tracing::warn!("Failed to auto-save session '{}': {}", name, e);
+        }
+    }
+
+    /// Save the conversation under a new name (`/save <name>`).
+    ///
+    /// Later turns are auto-saved under this name.
+    pub(super) fn save_session_as(&mut self, name: &str) {
+        if self.message_history.is_empty() {
+            self.error_message = Some("Nothing to save yet".to_string());
+            return;
+        }
+
+        let manager = SessionManager::new(&self.db);
+        let model = self.effective_model_for_agent(&self.current_agent);
+
+        match manager.save(name, &self.message_history, &self.current_agent, &model) {
+            Ok(meta) => {
+                self.copy_feedback = Some((Instant::now(), format!("Saved {}", meta.name)));
+                self.current_session = Some(meta.name);
+            }
+            Err(e) => {
+                self.error_message = Some(format!("Failed to save session: {}", e));
+            }
+        }
+    }
+
+    /// Load a saved session, or the most recent one when `name` is `None`.
+    pub fn load_session(&mut self, name: Option<&str>) {
+        if self.is_generating {
+            self.error_message = Some("Cannot load a session while generating".to_string());
+            return;
+        }
+
+        let manager = SessionManager::new(&self.db);
+        let result = match name {
+            Some(name) => manager.load(name),
+            None => manager.load_latest(),
+        };
+
+        match result {
+            Ok(session) => self.restore_session(session),
+            Err(e) => {
+                self.error_message = Some(format!("Failed to load session: {}", e));
+            }
+        }
+    }
+
+    /// Replace the current conversation with a loaded session.
+    fn restore_session(&mut self, session: SessionData) {
+        let SessionData { meta, messages } = session;
+
+        self.conversation.clear();
+        self.activities.clear();
+        self.rendered_lines.clear();
+        self.selection.clear();
+        self.active_agent_stack.clear();
+        self.active_section_ids.clear();
+        self.activity_state = widgets::ActivityFeedState::default();
+
+        for entry in transcript(&messages) {
+            match entry {
+                TranscriptEntry::User(text) => {
+                    self.conversation.add_user_message(text.clone());
+                    self.activities.push(Activity::user_message(text));
+                }
+                TranscriptEntry::Assistant(text) => {
+                    self.conversation.start_assistant_message();
+                    self.conversation.append_to_current(&text);
+                    self.conversation.finish_current_message();
+                    self.activities.push(Activity::assistant_message(text));
+                }
+            }
+        }
+
+        // Restore the agent and model if they still exist. The model is only
+        // applied for this run; the saved default model is left untouched.
+        if self.agents.get(&meta.agent).is_some() {
+            self.current_agent = meta.agent.clone();
+        }
+        if self.model_registry.get(&meta.model).is_some() {
+            self.current_model = meta.model.clone();
+        }
+        self.update_context_usage();
+        self.context_tokens_used = meta.token_estimate;
+
+        self.message_history = messages;
+        self.copy_feedback = Some((Instant::now(), format!("Loaded {}", meta.name)));
+        self.current_session = Some(meta.name);
+
+        self.message_list_state.scroll_to_bottom();
+        self.activity_scroll_to_bottom();
+    }
+
+    /// Open the session picker and load the saved session list
+    pub fn open_session_picker(&mut self) {
+        match SessionManager::new(&self.db).list() {
+            Ok(sessions) => {
+                self.session_picker_entries = sessions;
+                self.session_picker_selected = 0;
+                self.show_session_picker = true;
+            }
+            Err(e) => {
+                self.error_message = Some(format!("Failed to list sessions: {}", e));
+            }
+        }
+    }
+
+    /// Close the session picker
+    pub fn close_session_picker(&mut self) {
+        self.show_session_picker = false;
+        self.session_picker_entries.clear();
+        self.session_picker_selected = 0;
+    }
+
+    /// Load the session at `index` in the picker and close it
+    pub fn session_picker_confirm(&mut self, index: usize) {
+        let Some(name) = self
+            .session_picker_entries
+            .get(index)
+            .map(|meta| meta.name.clone())
+        else {
+            return;
+        };
+        self.close_session_picker();
+        self.load_session(Some(name.as_str()));
+    }
+
+    /// Delete the selected session from the picker
+    pub fn session_picker_delete_selected(&mut self) {
+        let Some(meta) = self
+            .session_picker_entries
+            .get(self.session_picker_selected)
+        else {
+            return;
+        };
+
+        let name = meta.name.clone();
+        if let Err(e) = SessionManager::new(&self.db).delete(&name) {
+            self.error_message = Some(format!("Failed to delete session: {}", e));
+            return;
+        }
+        if self.current_session.as_deref() == Some(name.as_str()) {
+            self.current_session = None;
+        }
+
+        self.session_picker_entries
+            .remove(self.session_picker_selected);
+        self.session_picker_selected = self
+            .session_picker_selected
+            .min(self.session_picker_entries.len().saturating_sub(1));
+    }
+}
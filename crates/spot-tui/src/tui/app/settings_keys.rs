@@ -759,6 +759,29 @@ impl TuiApp {
         }
     }
 
+    /// Handle 'a' / 'e' keys to add or edit a remote server in the MCP Servers tab
+    pub(super) fn handle_settings_mcp_remote_open(&mut self, edit: bool) {
+        use crate::tui::settings::{McpPanel, SettingsTab};
+
+        if self.settings_state.active_tab != SettingsTab::McpServers
+            || self.settings_state.mcp_panel != McpPanel::Servers
+        {
+            return;
+        }
+
+        if !edit {
+            self.settings_state.mcp_remote_form.open(None);
+            return;
+        }
+
+        // Only remote servers are editable through the form
+        if let Some(name) = crate::tui::settings::mcp_servers::get_remote_server_name(
+            self.settings_state.mcp_server_index,
+        ) {
+            self.settings_state.mcp_remote_form.open(Some(name));
+        }
+    }
+
     /// Handle 'k' key to open key pool management in Models tab
     pub(super) fn handle_settings_key_pool_open(&mut self) {
         use crate::tui::settings::SettingsTab;
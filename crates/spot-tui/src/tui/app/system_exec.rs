@@ -0,0 +1,284 @@
+//! Shell command execution and approval for tool calls.
+//!
+//! Installs the global `ToolContext` so `run_shell_command` routes through
+//! the TUI instead of running commands directly. Each `ExecuteShell` request
+//! is checked against the [`CommandPolicy`]: allowed commands run at once,
+//! denied ones fail, and the rest are queued for the approval modal. Text
+//! typed into a running process (`ApproveProcessInput`) goes through the same
+//! checks and modal.
+//! File-change requests are handed to `file_approval`.
+
+use std::collections::VecDeque;
+use std::sync::Arc;
+
+use tokio::sync::mpsc;
+use tracing::{info, warn};
+
+use super::TuiApp;
+use spot_core::config::{CommandAction, SpotConfig};
+use spot_core::terminal::{
+    spawn_llm_command, CommandValidation, SystemExecRequest, SystemExecResponse, SystemExecStore,
+};
+use spot_core::tools::{set_global_context, CommandPolicy, ToolContext};
+
+/// Command awaiting user approval
+#[derive(Debug)]
+pub struct PendingShellApproval {
+    pub request_id: u64,
+    pub command: String,
+    pub cwd: Option<String>,
+    /// Set when the command is input for this running process
+    pub process_id: Option<String>,
+    pub validation: CommandValidation,
+}
+
+impl PendingShellApproval {
+    /// Whether this is input for a running process rather than a command.
+    ///
+    /// Input can only be approved once; always-allow is for commands.
+    pub fn is_input(&self) -> bool {
+        self.process_id.is_some()
+    }
+}
+
+/// User's answer in the approval modal
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShellApprovalChoice {
+    Approve,
+    AlwaysAllow,
+    Deny,
+}
+
+/// State for LLM-requested shell commands
+pub struct SystemExecState {
+    /// Process store shared with the tool context
+    pub store: Arc<SystemExecStore>,
+    /// Receiver for tool requests (taken by the event loop)
+    request_rx: Option<mpsc::UnboundedReceiver<SystemExecRequest>>,
+    /// Commands waiting for approval; the front one is shown
+    pub pending: VecDeque<PendingShellApproval>,
+    /// Counter for generating unique process IDs
+    next_process_id: u64,
+}
+
+impl SystemExecState {
+    /// Create the state and install the global tool context.
+    pub fn install() -> Self {
+        let store = Arc::new(SystemExecStore::new());
+        let (request_tx, request_rx) = mpsc::unbounded_channel();
+
+        let ignore_patterns = SpotConfig::load_without_db().files.ignore;
+        let tool_ctx =
+            ToolContext::new(store.clone(), request_tx).with_ignore_patterns(ignore_patterns);
+        if set_global_context(tool_ctx).is_err() {
+            tracing::warn!("Tool context already initialized");
+        }
+
+        Self {
+            store,
+            request_rx: Some(request_rx),
+            pending: VecDeque::new(),
+            next_process_id: 1,
+        }
+    }
+
+    /// Take the request receiver (for use in the event loop).
+    pub fn take_request_rx(&mut self) -> Option<mpsc::UnboundedReceiver<SystemExecRequest>> {
+        self.request_rx.take()
+    }
+
+    /// The command currently shown in the approval modal.
+    pub fn current(&self) -> Option<&PendingShellApproval> {
+        self.pending.front()
+    }
+
+    /// Generate a unique process ID
+    fn generate_process_id(&mut self) -> String {
+        let id = format!("proc-{}", self.next_process_id);
+        self.next_process_id += 1;
+        id
+    }
+}
+
+impl TuiApp {
+    /// Handle a system execution request from tools
+    pub(super) fn handle_exec_request(&mut self, request: SystemExecRequest) {
+        match request {
+            SystemExecRequest::ExecuteShell {
+                request_id,
+                command,
+                cwd,
+            } => {
+                let decision = CommandPolicy::load(&self.db).evaluate(&command, cwd.as_deref());
+                info!(
+                    request_id,
+                    risk_level = ?decision.validation.risk_level,
+                    action = ?decision.action,
+                    reason = %decision.reason,
+                    "Evaluated command against policy"
+                );
+
+                match decision.action {
+                    CommandAction::Allow => self.run_approved_command(request_id, command, cwd),
+                    CommandAction::Deny => {
+                        let message = format!("Command denied by policy: {}", decision.reason);
+                        self.error_message = Some(format!("{}: {}", message, command.trim()));
+                        self.system_exec
+                            .store
+                            .respond(request_id, SystemExecResponse::Error { message });
+                    }
+                    CommandAction::Ask => {
+                        self.system_exec.pending.push_back(PendingShellApproval {
+                            request_id,
+                            command,
+                            cwd,
+                            process_id: None,
+                            validation: decision.validation,
+                        });
+                    }
+                }
+            }
+            SystemExecRequest::ApproveProcessInput {
+                request_id,
+                process_id,
+                input,
+            } => {
+                let decision = CommandPolicy::load(&self.db).evaluate_input(&input);
+                info!(
+                    request_id,
+                    process_id = %process_id,
+                    action = ?decision.action,
+                    reason = %decision.reason,
+                    "Evaluated process input against policy"
+                );
+
+                match decision.action {
+                    CommandAction::Allow => self
+                        .system_exec
+                        .store
+                        .respond(request_id, SystemExecResponse::InputApproved),
+                    CommandAction::Deny => {
+                        let message = format!("Input denied by policy: {}", decision.reason);
+                        self.error_message = Some(format!("{}: {}", message, input.trim()));
+                        self.system_exec
+                            .store
+                            .respond(request_id, SystemExecResponse::Error { message });
+                    }
+                    CommandAction::Ask => {
+                        self.system_exec.pending.push_back(PendingShellApproval {
+                            request_id,
+                            command: input,
+                            cwd: None,
+                            process_id: Some(process_id),
+                            validation: decision.validation,
+                        });
+                    }
+                }
+            }
+            SystemExecRequest::KillProcess {
+                request_id,
+                process_id,
+            } => {
+                info!(request_id, process_id = %process_id, "Handling KillProcess request");
+                self.system_exec.store.kill(&process_id);
+                self.system_exec
+                    .store
+                    .respond(request_id, SystemExecResponse::Killed { process_id });
+            }
+            SystemExecRequest::ApproveFileChange { request_id, change } => {
+                self.handle_file_change_request(request_id, change);
+            }
+        }
+    }
+
+    /// Resolve the command shown in the approval modal.
+    pub(super) fn resolve_shell_approval(&mut self, choice: ShellApprovalChoice) {
+        let Some(pending) = self.system_exec.pending.pop_front() else {
+            return;
+        };
+
+        match choice {
+            ShellApprovalChoice::Deny => {
+                info!(request_id = pending.request_id, "User rejected command");
+                self.system_exec.store.respond(
+                    pending.request_id,
+                    SystemExecResponse::Error {
+                        message: "Command rejected by user".to_string(),
+                    },
+                );
+            }
+            ShellApprovalChoice::AlwaysAllow if pending.is_input() => {
+                info!(
+                    request_id = pending.request_id,
+                    "User approved process input"
+                );
+                self.run_approved(pending);
+            }
+            ShellApprovalChoice::AlwaysAllow => {
+                info!(
+                    request_id = pending.request_id,
+                    "User always-allowed command"
+                );
+                if let Err(e) = CommandPolicy::remember(&self.db, &pending.command) {
+                    warn!(error = %e, "Failed to save always-allowed command");
+                }
+                let command = pending.command.trim().to_string();
+                self.run_approved(pending);
+
+                // Release queued duplicates of the same command (not input)
+                let (ready, waiting): (Vec<_>, Vec<_>) = self
+                    .system_exec
+                    .pending
+                    .drain(..)
+                    .partition(|p| !p.is_input() && p.command.trim() == command);
+                self.system_exec.pending = waiting.into();
+                for p in ready {
+                    self.run_approved(p);
+                }
+            }
+            ShellApprovalChoice::Approve => {
+                info!(request_id = pending.request_id, "User approved command");
+                self.run_approved(pending);
+            }
+        }
+    }
+
+    /// Refuse every command, input and file change still awaiting approval.
+    ///
+    /// Used when the agent run that asked for them is cancelled.
+    pub(super) fn clear_pending_approvals(&mut self) {
+        self.file_approval.editor = None;
+        let commands = self.system_exec.pending.drain(..).map(|p| p.request_id);
+        let changes = self.file_approval.pending.drain(..).map(|p| p.request_id);
+        for request_id in commands.chain(changes) {
+            self.system_exec.store.respond(
+                request_id,
+                SystemExecResponse::Error {
+                    message: "Agent run was cancelled".to_string(),
+                },
+            );
+        }
+    }
+
+    /// Run an approved command, or let approved input through to its process.
+    fn run_approved(&mut self, pending: PendingShellApproval) {
+        match pending.process_id {
+            Some(_) => self
+                .system_exec
+                .store
+                .respond(pending.request_id, SystemExecResponse::InputApproved),
+            None => self.run_approved_command(pending.request_id, pending.command, pending.cwd),
+        }
+    }
+
+    /// Spawn an approved command in a headless PTY and report its process ID.
+    fn run_approved_command(&mut self, request_id: u64, command: String, cwd: Option<String>) {
+        let process_id = self.system_exec.generate_process_id();
+        let store = self.system_exec.store.clone();
+
+        spawn_llm_command(store.clone(), process_id.clone(), command, cwd);
+
+        // Send response so tool gets process_id
+        store.respond(request_id, SystemExecResponse::Started { process_id });
+    }
+}
@@ -1,7 +1,8 @@
 //! Agent execution for TUI mode
 
 use serdes_ai_core::ModelRequest;
-use spot_core::agents::{AgentExecutor, AgentManager, RetryHandler};
+use spot_core::agents::{AgentExecutor, AgentManager, CancelHandle, RetryHandler};
+use spot_core::checkpoints::CheckpointStore;
 use spot_core::db::Database;
 use spot_core::mcp::McpManager;
 use spot_core::messaging::{HistoryUpdateMessage, Message, MessageSender};
@@ -10,6 +11,10 @@ use spot_core::tools::SpotToolRegistry;
 use std::sync::Arc;
 
 /// Execute an agent with the given parameters
+///
+/// MCP prompt commands and resource mentions in `prompt` are expanded
+/// first. The run stops early when `cancel` is cancelled. File changes are
+/// snapshotted into `checkpoints` so they can be undone.
 #[allow(clippy::too_many_arguments)]
 pub async fn execute_agent(
     agent_name: String,
@@ -22,6 +27,8 @@ pub async fn execute_agent(
     tool_registry: Arc<SpotToolRegistry>,
     mcp_manager: Arc<McpManager>,
     sender: MessageSender,
+    cancel: CancelHandle,
+    checkpoints: CheckpointStore,
 ) {
     // Get the agent
     let agent = match agent_manager.get(&agent_name) {
@@ -32,13 +39,24 @@ pub async fn execute_agent(
         }
     };
 
+    // Run `/server:prompt` commands and attach `@server:resource` mentions
+    let prompt = match mcp_manager.expand_input(&prompt).await {
+        Ok(prompt) => prompt,
+        Err(e) => {
+            sender.error(format!("MCP: {}", e));
+            return;
+        }
+    };
+
     // Create retry handler for automatic key rotation on 429s
     let retry_handler = RetryHandler::new(Arc::clone(&db));
 
     // Create executor with references and retry handler
     let executor = AgentExecutor::new(&db, &model_registry)
         .with_retry_handler(retry_handler)
-        .with_bus(sender.clone());
+        .with_bus(sender.clone())
+        .with_cancel(cancel)
+        .with_checkpoints(checkpoints);
 
     // Execute and get result with updated messages
     let result = executor
@@ -61,6 +79,10 @@ pub async fn execute_agent(
                 }));
             }
         }
+        Err(e) if e.is_cancelled() => {
+            // Cancelled before anything was streamed; history is unchanged
+            let _ = sender.send(Message::agent_cancelled(agent.name(), agent.display_name()));
+        }
         Err(e) => {
             sender.error(format!("Execution failed: {}", e));
         }
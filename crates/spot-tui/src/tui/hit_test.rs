@@ -19,6 +19,8 @@ pub enum ClickTarget {
     ModelItem(String),
     /// Folder dropdown item (index in list)
     FolderItem(usize),
+    /// Session picker item (index in list)
+    SessionItem(usize),
     /// Collapse/expand toggle for a section
     SectionToggle(String), // section_id
     /// Message content (for text selection)
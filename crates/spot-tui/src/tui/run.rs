@@ -47,6 +47,9 @@ pub fn run_tui(config: AppConfig) -> Result<()> {
     let local = tokio::task::LocalSet::new();
     local.block_on(&runtime, async {
         let mut app = TuiApp::new().await?;
+        if let Some(name) = &config.resume {
+            app.load_session(name.as_deref());
+        }
         app.run().await
     })
 }
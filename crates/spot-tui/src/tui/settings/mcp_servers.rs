@@ -1,50 +1,52 @@
 //! MCP Servers settings tab
 //!
-//! Two-panel layout with MCP server list and agent-MCP assignments.
+//! Two-panel layout with MCP server list (including each running server's
+//! state) and agent-MCP assignments, plus an overlay form for adding and
+//! editing remote servers.
 
+use std::collections::HashMap;
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     style::{Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, List, ListItem, Paragraph},
+    widgets::{Block, Borders, Clear, List, ListItem, Paragraph},
     Frame,
 };
 
 use crate::tui::app::TuiApp;
 use crate::tui::hit_test::{ClickTarget, HitTestRegistry};
-use crate::tui::theme::Theme;
+use crate::tui::theme::{dim_background, Theme};
 use spot_core::config::Settings;
-use spot_core::mcp::McpConfig;
+use spot_core::mcp::{
+    McpConfig, McpServerState, McpServerStatus, McpTransportKind, RemoteServerForm,
+};
 
 use super::McpPanel;
 
-/// Server info: (name, enabled, description, command_preview)
+/// Server info: (name, enabled, description, command_preview, is_remote)
 #[derive(Debug, Clone)]
 pub struct ServerInfo {
     pub name: String,
     pub enabled: bool,
     pub description: Option<String>,
     pub command_preview: String,
+    pub is_remote: bool,
 }
 
 /// Load servers from MCP config
 pub fn load_servers() -> Vec<ServerInfo> {
-    let mcp_config = McpConfig::load_or_default();
+    let mcp_config = McpConfig::load_for_edit();
     let mut servers: Vec<ServerInfo> = mcp_config
         .servers
         .iter()
-        .map(|(name, entry)| {
-            let cmd_preview = if entry.args.is_empty() {
-                entry.command.clone()
-            } else {
-                format!("{} {}", entry.command, entry.args.join(" "))
-            };
-            ServerInfo {
-                name: name.clone(),
-                enabled: entry.enabled,
-                description: entry.description.clone(),
-                command_preview: truncate_str(&cmd_preview, 35),
-            }
+        .map(|(name, entry)| ServerInfo {
+            name: name.clone(),
+            enabled: entry.enabled,
+            description: entry.description.clone(),
+            command_preview: truncate_str(&entry.display_target(), 35),
+            is_remote: entry.is_remote(),
         })
         .collect();
     servers.sort_by(|a, b| a.name.cmp(&b.name));
@@ -87,6 +89,7 @@ pub fn render_mcp_servers_tab(
         frame,
         chunks[0],
         &servers,
+        &app.mcp_manager.server_statuses(),
         app.settings_state.mcp_server_index,
         app.settings_state.mcp_panel == McpPanel::Servers,
     );
@@ -180,6 +183,7 @@ fn render_server_list(
     frame: &mut Frame,
     area: Rect,
     servers: &[ServerInfo],
+    statuses: &HashMap<String, McpServerStatus>,
     selected_index: usize,
     is_focused: bool,
 ) {
@@ -220,6 +224,12 @@ fn render_server_list(
                 "  ~/.spot/mcp_servers.json",
                 Style::default().fg(Theme::ACCENT),
             )),
+            Line::from(""),
+            Line::from(vec![
+                Span::styled("  or press ", Style::default().fg(Theme::MUTED)),
+                Span::styled("a", Style::default().fg(Theme::ACCENT)),
+                Span::styled(" to add a remote server", Style::default().fg(Theme::MUTED)),
+            ]),
         ]);
         frame.render_widget(msg, inner);
         return;
@@ -238,7 +248,11 @@ fn render_server_list(
         Span::styled("  Enter", Style::default().fg(Theme::ACCENT)),
         Span::styled(": toggle  ", Style::default().fg(Theme::MUTED)),
         Span::styled("Del", Style::default().fg(Theme::ACCENT)),
-        Span::styled(": remove", Style::default().fg(Theme::MUTED)),
+        Span::styled(": remove  ", Style::default().fg(Theme::MUTED)),
+        Span::styled("a", Style::default().fg(Theme::ACCENT)),
+        Span::styled(": add remote  ", Style::default().fg(Theme::MUTED)),
+        Span::styled("e", Style::default().fg(Theme::ACCENT)),
+        Span::styled(": edit", Style::default().fg(Theme::MUTED)),
     ])]);
     frame.render_widget(header, header_area);
 
@@ -248,7 +262,7 @@ fn render_server_list(
         .enumerate()
         .map(|(idx, server)| {
             let is_selected = idx == selected_index && is_focused;
-            render_server_item(server, is_selected)
+            render_server_item(server, statuses.get(&server.name), is_selected)
         })
         .collect();
 
@@ -257,7 +271,11 @@ fn render_server_list(
 }
 
 /// Render a single server item (multiple lines)
-fn render_server_item(server: &ServerInfo, is_selected: bool) -> ListItem<'static> {
+fn render_server_item(
+    server: &ServerInfo,
+    status: Option<&McpServerStatus>,
+    is_selected: bool,
+) -> ListItem<'static> {
     let selector = if is_selected { "▶ " } else { "  " };
     let (status_icon, status_text, status_color) = if server.enabled {
         ("✓", "enabled", Theme::GREEN)
@@ -273,6 +291,19 @@ fn render_server_item(server: &ServerInfo, is_selected: bool) -> ListItem<'stati
         Style::default().fg(Theme::TEXT)
     };
 
+    // Config status, then runtime state once the server has been started
+    let mut status_line = vec![
+        Span::raw("    "),
+        Span::styled(
+            format!("{} ", status_icon),
+            Style::default().fg(status_color),
+        ),
+        Span::styled(status_text, Style::default().fg(status_color)),
+    ];
+    if let Some(status) = status {
+        status_line.extend(render_runtime_status(status));
+    }
+
     ListItem::new(vec![
         // Server name
         Line::from(vec![
@@ -280,14 +311,7 @@ fn render_server_item(server: &ServerInfo, is_selected: bool) -> ListItem<'stati
             Span::styled(server.name.clone(), name_style),
         ]),
         // Status
-        Line::from(vec![
-            Span::raw("    "),
-            Span::styled(
-                format!("{} ", status_icon),
-                Style::default().fg(status_color),
-            ),
-            Span::styled(status_text, Style::default().fg(status_color)),
-        ]),
+        Line::from(status_line),
         // Command preview
         Line::from(vec![
             Span::raw("    "),
@@ -301,6 +325,29 @@ fn render_server_item(server: &ServerInfo, is_selected: bool) -> ListItem<'stati
     ])
 }
 
+/// Spans for a started server's state: tool count or last error, and when it
+/// will next be restarted.
+fn render_runtime_status(status: &McpServerStatus) -> Vec<Span<'static>> {
+    let color = match status.state {
+        McpServerState::Ready => Theme::GREEN,
+        McpServerState::Starting => Theme::YELLOW,
+        McpServerState::Unhealthy | McpServerState::Failed => Theme::RED,
+        McpServerState::Stopped => Theme::MUTED,
+    };
+
+    let mut spans = vec![
+        Span::styled("  ● ", Style::default().fg(color)),
+        Span::styled(status.summary(), Style::default().fg(color)),
+    ];
+    if let Some(secs) = status.restart_in_secs() {
+        spans.push(Span::styled(
+            format!(" (retry in {}s)", secs),
+            Style::default().fg(Theme::MUTED),
+        ));
+    }
+    spans
+}
+
 /// Render the right panel with agent-MCP assignments
 #[allow(clippy::too_many_arguments)]
 fn render_agent_assignments(
@@ -560,7 +607,7 @@ pub fn enabled_server_count() -> usize {
 
 /// Toggle enabled state of a server
 pub fn toggle_server_enabled(server_index: usize) {
-    let mut mcp_config = McpConfig::load_or_default();
+    let mut mcp_config = McpConfig::load_for_edit();
     let servers = load_servers();
 
     if let Some(server) = servers.get(server_index) {
@@ -573,7 +620,7 @@ pub fn toggle_server_enabled(server_index: usize) {
 
 /// Remove a server from config
 pub fn remove_server(server_index: usize) {
-    let mut mcp_config = McpConfig::load_or_default();
+    let mut mcp_config = McpConfig::load_for_edit();
     let servers = load_servers();
 
     if let Some(server) = servers.get(server_index) {
@@ -590,3 +637,274 @@ pub fn get_enabled_server_name(checkbox_index: usize) -> Option<String> {
         .nth(checkbox_index)
         .map(|s| s.name.clone())
 }
+
+/// Name of the server at `server_index` if it is a remote server
+pub fn get_remote_server_name(server_index: usize) -> Option<String> {
+    load_servers()
+        .into_iter()
+        .nth(server_index)
+        .filter(|s| s.is_remote)
+        .map(|s| s.name)
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Remote Server Form
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Field of the remote server form
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RemoteFormField {
+    #[default]
+    Name,
+    Url,
+    Transport,
+    Token,
+    Headers,
+}
+
+impl RemoteFormField {
+    const ALL: [Self; 5] = [
+        Self::Name,
+        Self::Url,
+        Self::Transport,
+        Self::Token,
+        Self::Headers,
+    ];
+
+    fn label(&self) -> &'static str {
+        match self {
+            Self::Name => "Name",
+            Self::Url => "URL",
+            Self::Transport => "Transport",
+            Self::Token => "Token",
+            Self::Headers => "Headers",
+        }
+    }
+
+    fn next(self) -> Self {
+        let idx = Self::ALL.iter().position(|f| *f == self).unwrap_or(0);
+        Self::ALL[(idx + 1) % Self::ALL.len()]
+    }
+
+    fn prev(self) -> Self {
+        let idx = Self::ALL.iter().position(|f| *f == self).unwrap_or(0);
+        Self::ALL[(idx + Self::ALL.len() - 1) % Self::ALL.len()]
+    }
+}
+
+/// State for the add/edit remote server overlay
+#[derive(Debug, Default)]
+pub struct McpRemoteFormState {
+    /// Whether the form is open
+    pub active: bool,
+    /// Name of the server being edited, or `None` when adding
+    pub editing: Option<String>,
+    /// Current field values
+    pub form: RemoteServerForm,
+    /// Focused field
+    pub field: RemoteFormField,
+    /// Validation or save error from the last Enter
+    pub error: Option<String>,
+}
+
+impl McpRemoteFormState {
+    /// Open the form, prefilled from the named server when editing
+    pub fn open(&mut self, editing: Option<String>) {
+        self.form = editing
+            .as_deref()
+            .and_then(|name| {
+                McpConfig::load_for_edit()
+                    .get_server(name)
+                    .map(|entry| RemoteServerForm::from_entry(name, entry))
+            })
+            .unwrap_or_default();
+        self.editing = editing;
+        self.field = RemoteFormField::Name;
+        self.error = None;
+        self.active = true;
+    }
+
+    /// Close the form without saving
+    pub fn close(&mut self) {
+        *self = Self::default();
+    }
+
+    /// The text field being edited, if the focused field is one
+    fn focused_text(&mut self) -> Option<&mut String> {
+        match self.field {
+            RemoteFormField::Name => Some(&mut self.form.name),
+            RemoteFormField::Url => Some(&mut self.form.url),
+            RemoteFormField::Token => Some(&mut self.form.bearer_token),
+            RemoteFormField::Headers => Some(&mut self.form.headers),
+            RemoteFormField::Transport => None,
+        }
+    }
+
+    fn toggle_transport(&mut self) {
+        self.form.transport = match self.form.transport {
+            McpTransportKind::Sse => McpTransportKind::Http,
+            _ => McpTransportKind::Sse,
+        };
+    }
+
+    /// Validate and write the form to the MCP config; closes on success
+    fn save(&mut self) {
+        let mut config = McpConfig::load_for_edit();
+        let result = self
+            .form
+            .save_into(&mut config, self.editing.as_deref())
+            .and_then(|()| config.save_default());
+        match result {
+            Ok(()) => self.close(),
+            Err(e) => self.error = Some(e.to_string()),
+        }
+    }
+}
+
+/// Render the remote server form overlay
+pub fn render_mcp_remote_form(frame: &mut Frame, area: Rect, state: &McpRemoteFormState) {
+    if !state.active {
+        return;
+    }
+
+    dim_background(frame, area);
+
+    // Centered overlay, 70% wide and tall enough for the fields
+    let width = area.width.saturating_mul(7) / 10;
+    let height = 14.min(area.height);
+    let overlay_area = Rect::new(
+        area.x + (area.width - width) / 2,
+        area.y + (area.height - height) / 2,
+        width,
+        height,
+    );
+    frame.render_widget(Clear, overlay_area);
+
+    let title = match &state.editing {
+        Some(name) => format!(" 🌐 Edit Remote Server: {} ", name),
+        None => " 🌐 Add Remote MCP Server ".to_string(),
+    };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Theme::ACCENT))
+        .title(Span::styled(
+            title,
+            Style::default()
+                .fg(Theme::HEADER)
+                .add_modifier(Modifier::BOLD),
+        ))
+        .style(Style::default().bg(Theme::PANEL_BG));
+    let inner = block.inner(overlay_area);
+    frame.render_widget(block, overlay_area);
+
+    let mut lines = vec![Line::from("")];
+    for field in RemoteFormField::ALL {
+        let is_focused = field == state.field;
+        let value = match field {
+            RemoteFormField::Name => state.form.name.clone(),
+            RemoteFormField::Url => state.form.url.clone(),
+            RemoteFormField::Transport => match state.form.transport {
+                McpTransportKind::Sse => "( ) Streamable HTTP  (•) SSE".to_string(),
+                _ => "(•) Streamable HTTP  ( ) SSE".to_string(),
+            },
+            // Show ${VAR} references, mask literal tokens
+            RemoteFormField::Token if state.form.bearer_token.starts_with("${") => {
+                state.form.bearer_token.clone()
+            }
+            RemoteFormField::Token => "•".repeat(state.form.bearer_token.chars().count().min(40)),
+            RemoteFormField::Headers => state.form.headers.clone(),
+        };
+        let cursor = if is_focused && field != RemoteFormField::Transport {
+            "│"
+        } else {
+            ""
+        };
+        let style = if is_focused {
+            Style::default().fg(Theme::ACCENT)
+        } else {
+            Style::default().fg(Theme::TEXT)
+        };
+        lines.push(Line::from(vec![
+            Span::styled(
+                if is_focused { " ▶ " } else { "   " },
+                Style::default().fg(Theme::ACCENT),
+            ),
+            Span::styled(
+                format!("{:<10}", format!("{}:", field.label())),
+                Style::default().fg(Theme::MUTED),
+            ),
+            Span::styled(format!("{}{}", value, cursor), style),
+        ]));
+    }
+
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        "   Headers: 'Name: value; Name: value'. ${VAR} is expanded when connecting.",
+        Style::default().fg(Theme::MUTED),
+    )));
+    if let Some(error) = &state.error {
+        lines.push(Line::from(Span::styled(
+            format!("   {}", error),
+            Style::default().fg(Theme::ERROR),
+        )));
+    } else {
+        lines.push(Line::from(""));
+    }
+    lines.push(Line::from(vec![
+        Span::styled("   Tab/↑↓", Style::default().fg(Theme::ACCENT)),
+        Span::styled(":field  ", Style::default().fg(Theme::MUTED)),
+        Span::styled("Space/←→", Style::default().fg(Theme::ACCENT)),
+        Span::styled(":transport  ", Style::default().fg(Theme::MUTED)),
+        Span::styled("Ctrl+V", Style::default().fg(Theme::ACCENT)),
+        Span::styled(":paste  ", Style::default().fg(Theme::MUTED)),
+        Span::styled("Enter", Style::default().fg(Theme::ACCENT)),
+        Span::styled(":save  ", Style::default().fg(Theme::MUTED)),
+        Span::styled("Esc", Style::default().fg(Theme::ACCENT)),
+        Span::styled(":cancel", Style::default().fg(Theme::MUTED)),
+    ]));
+
+    frame.render_widget(Paragraph::new(lines), inner);
+}
+
+/// Handle a key event in the remote server form overlay
+pub fn handle_mcp_remote_form_event(
+    state: &mut McpRemoteFormState,
+    key: KeyEvent,
+    clipboard_text: Option<&str>,
+) {
+    if !state.active {
+        return;
+    }
+
+    match (key.modifiers, key.code) {
+        (_, KeyCode::Esc) => state.close(),
+        (_, KeyCode::Enter) => state.save(),
+        (KeyModifiers::NONE, KeyCode::Tab) | (KeyModifiers::NONE, KeyCode::Down) => {
+            state.field = state.field.next();
+        }
+        (KeyModifiers::SHIFT, KeyCode::BackTab) | (KeyModifiers::NONE, KeyCode::Up) => {
+            state.field = state.field.prev();
+        }
+        (KeyModifiers::NONE, KeyCode::Left | KeyCode::Right | KeyCode::Char(' '))
+            if state.field == RemoteFormField::Transport =>
+        {
+            state.toggle_transport();
+        }
+        (KeyModifiers::CONTROL, KeyCode::Char('v')) => {
+            if let (Some(text), Some(value)) = (clipboard_text, state.focused_text()) {
+                value.push_str(text.lines().next().unwrap_or("").trim());
+            }
+        }
+        (KeyModifiers::NONE, KeyCode::Backspace) => {
+            if let Some(value) = state.focused_text() {
+                value.pop();
+            }
+        }
+        (KeyModifiers::NONE | KeyModifiers::SHIFT, KeyCode::Char(c)) => {
+            if let Some(value) = state.focused_text() {
+                value.push(c);
+            }
+        }
+        _ => {}
+    }
+}
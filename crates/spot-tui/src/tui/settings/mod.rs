@@ -33,7 +33,10 @@ pub use api_keys::{
     KeyPoolInputMode, KeyPoolState,
 };
 pub use general::render_general_tab;
-pub use mcp_servers::render_mcp_servers_tab;
+pub use mcp_servers::{
+    handle_mcp_remote_form_event, render_mcp_remote_form, render_mcp_servers_tab,
+    McpRemoteFormState,
+};
 pub use models::render_models_tab;
 pub use pinned_agents::render_pinned_agents_tab;
 
@@ -305,6 +308,12 @@ pub struct SettingsState {
     // ─────────────────────────────────────────────────────────────────────────
     /// State for the key pool management overlay
     pub key_pool: KeyPoolState,
+
+    // ─────────────────────────────────────────────────────────────────────────
+    // Remote MCP server form state
+    // ─────────────────────────────────────────────────────────────────────────
+    /// State for the add/edit remote MCP server overlay
+    pub mcp_remote_form: McpRemoteFormState,
 }
 
 impl SettingsState {
@@ -522,6 +531,13 @@ pub fn render_settings(frame: &mut Frame, area: Rect, app: &mut TuiApp) {
     if app.settings_state.key_pool.active {
         render_key_pool_overlay(frame, area, &app.settings_state.key_pool);
     }
+
+    // ─────────────────────────────────────────────────────────────────────────
+    // Render remote MCP server form (on top of settings if active)
+    // ─────────────────────────────────────────────────────────────────────────
+    if app.settings_state.mcp_remote_form.active {
+        render_mcp_remote_form(frame, area, &app.settings_state.mcp_remote_form);
+    }
 }
 
 /// Render the tab bar at the top of the settings panel
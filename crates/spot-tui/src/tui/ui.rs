@@ -54,6 +54,9 @@ pub fn render(frame: &mut Frame, app: &mut TuiApp) {
     if app.show_folder_modal {
         render_folder_modal(frame, app, layout.header_area);
     }
+    if app.show_session_picker {
+        render_session_picker(frame, app);
+    }
 
     // Help overlay
     if app.show_help {
@@ -65,10 +68,20 @@ pub fn render(frame: &mut Frame, app: &mut TuiApp) {
         render_settings(frame, frame.area(), app);
     }
 
-    // OAuth dialog overlay (highest priority - shows on top of settings)
+    // OAuth dialog overlay (shows on top of settings)
     if app.show_oauth_dialog {
         render_oauth_dialog(frame, app);
     }
+
+    // File change review overlay (blocks the running edit_file / delete_file call)
+    if app.file_approval.current().is_some() {
+        render_file_approval(frame, app);
+    }
+
+    // Shell approval overlay (highest priority - blocks the running tool call)
+    if app.system_exec.current().is_some() {
+        render_shell_approval(frame, app);
+    }
 }
 
 fn render_header(frame: &mut Frame, app: &mut TuiApp, area: Rect) {
@@ -455,6 +468,105 @@ fn render_folder_modal(frame: &mut Frame, app: &mut TuiApp, header_area: Rect) {
     frame.render_widget(paragraph, modal_area);
 }
 
+fn render_session_picker(frame: &mut Frame, app: &mut TuiApp) {
+    use spot_core::session::format_relative_time;
+
+    // Dim background for modal effect
+    dim_background(frame, frame.area());
+
+    let modal_width: u16 = 72.min(frame.area().width.saturating_sub(4));
+    let visible_entries: usize = 10;
+    // Borders (2) + entries + footer (2)
+    let modal_height: u16 = (visible_entries as u16 + 4).min(frame.area().height);
+
+    // Center the modal
+    let modal_x = (frame.area().width.saturating_sub(modal_width)) / 2;
+    let modal_y = (frame.area().height.saturating_sub(modal_height)) / 2;
+    let modal_area = Rect::new(modal_x, modal_y, modal_width, modal_height);
+
+    frame.render_widget(Clear, modal_area);
+
+    let inner_width = modal_width.saturating_sub(2) as usize;
+    let mut lines: Vec<Line> = Vec::new();
+
+    if app.session_picker_entries.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "  No saved sessions yet",
+            Style::default().fg(Theme::MUTED),
+        )));
+    }
+
+    // Keep the selection visible
+    let scroll = app
+        .session_picker_selected
+        .saturating_sub(visible_entries.saturating_sub(1));
+
+    for (i, meta) in app
+        .session_picker_entries
+        .iter()
+        .enumerate()
+        .skip(scroll)
+        .take(visible_entries)
+    {
+        let is_selected = app.session_picker_selected == i;
+        let is_current = app.current_session.as_deref() == Some(meta.name.as_str());
+        let selector = if is_selected { "▶ " } else { "  " };
+        let name_style = if is_selected {
+            Style::default()
+                .fg(Theme::ACCENT)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(Theme::TEXT)
+        };
+
+        let details = format!(
+            "  {} · {} msgs · {}{}",
+            meta.agent,
+            meta.message_count,
+            format_relative_time(meta.updated_at),
+            if is_current { " · current" } else { "" }
+        );
+        let name_width = inner_width.saturating_sub(details.chars().count() + 2);
+        let name: String = meta.name.chars().take(name_width).collect();
+
+        lines.push(Line::from(vec![
+            Span::styled(selector, Style::default().fg(Theme::ACCENT)),
+            Span::styled(name, name_style),
+            Span::styled(details, Style::default().fg(Theme::MUTED)),
+        ]));
+
+        let entry_y = modal_area.y + 1 + (i - scroll) as u16;
+        let entry_rect = Rect::new(modal_area.x + 1, entry_y, inner_width as u16, 1);
+        app.hit_registry
+            .register(entry_rect, ClickTarget::SessionItem(i));
+    }
+
+    // Pad so the footer stays at the bottom
+    while lines.len() < visible_entries {
+        lines.push(Line::from(""));
+    }
+    lines.push(Line::from(Span::styled(
+        "─".repeat(inner_width),
+        Style::default().fg(Theme::BORDER),
+    )));
+    lines.push(Line::from(Span::styled(
+        "Enter to load · Del to delete · Esc to close",
+        Style::default().fg(Theme::MUTED),
+    )));
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Theme::ACCENT))
+        .title(Span::styled(
+            " Sessions ",
+            Style::default().fg(Theme::ACCENT),
+        ))
+        .style(Style::default().bg(Theme::INPUT_BG));
+
+    let paragraph = Paragraph::new(lines).block(block);
+    frame.render_widget(paragraph, modal_area);
+}
+
 fn render_oauth_dialog(frame: &mut Frame, app: &TuiApp) {
     // Dim background for modal effect
     dim_background(frame, frame.area());
@@ -535,6 +647,198 @@ fn render_oauth_dialog(frame: &mut Frame, app: &TuiApp) {
     frame.render_widget(paragraph, modal_area);
 }
 
+fn render_shell_approval(frame: &mut Frame, app: &TuiApp) {
+    use spot_core::terminal::RiskLevel;
+
+    let Some(pending) = app.system_exec.current() else {
+        return;
+    };
+    let validation = &pending.validation;
+
+    // Dim background for modal effect
+    dim_background(frame, frame.area());
+
+    let modal_width: u16 = 72.min(frame.area().width.saturating_sub(4));
+    let inner_width = modal_width.saturating_sub(4) as usize;
+
+    let risk_color = match validation.risk_level {
+        RiskLevel::Low => Theme::SUCCESS,
+        RiskLevel::Medium => Theme::WARNING,
+        RiskLevel::High => Theme::ERROR,
+    };
+
+    let mut lines: Vec<Line> = Vec::new();
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        format!(
+            "{} {}",
+            validation.risk_emoji(),
+            validation.risk_description()
+        ),
+        Style::default().fg(risk_color).add_modifier(Modifier::BOLD),
+    )));
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        "Command:",
+        Style::default().fg(Theme::MUTED),
+    )));
+    // Show at most 6 wrapped lines of the command
+    for line in textwrap::wrap(&pending.command, inner_width.max(1))
+        .into_iter()
+        .take(6)
+    {
+        lines.push(Line::from(Span::styled(
+            line.into_owned(),
+            Style::default()
+                .fg(Theme::COMMAND)
+                .add_modifier(Modifier::BOLD),
+        )));
+    }
+    if let Some(ref process_id) = pending.process_id {
+        lines.push(Line::from(Span::styled(
+            format!("typed into {}", process_id),
+            Style::default().fg(Theme::PATH),
+        )));
+    } else if let Some(ref cwd) = pending.cwd {
+        lines.push(Line::from(Span::styled(
+            format!("in {}", cwd),
+            Style::default().fg(Theme::PATH),
+        )));
+    }
+    for warning in &validation.warnings {
+        lines.push(Line::from(Span::styled(
+            format!("⚠ {}", warning),
+            Style::default().fg(Theme::ERROR),
+        )));
+    }
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        "─".repeat(inner_width),
+        Style::default().fg(Theme::BORDER),
+    )));
+    let keys = if pending.is_input() {
+        "y approve · n/Esc deny"
+    } else {
+        "y approve · a always allow · n/Esc deny"
+    };
+    lines.push(Line::from(Span::styled(
+        keys,
+        Style::default().fg(Theme::MUTED),
+    )));
+
+    // Borders (2) + content, centered
+    let modal_height: u16 = (lines.len() as u16 + 2).min(frame.area().height);
+    let modal_x = (frame.area().width.saturating_sub(modal_width)) / 2;
+    let modal_y = (frame.area().height.saturating_sub(modal_height)) / 2;
+    let modal_area = Rect::new(modal_x, modal_y, modal_width, modal_height);
+
+    frame.render_widget(Clear, modal_area);
+
+    let queued = app.system_exec.pending.len() - 1;
+    let question = if pending.process_id.is_some() {
+        "Send input to process?"
+    } else {
+        "Run shell command?"
+    };
+    let title = if queued > 0 {
+        format!(" {} (+{} queued) ", question, queued)
+    } else {
+        format!(" {} ", question)
+    };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(risk_color))
+        .title(Span::styled(title, Style::default().fg(risk_color)))
+        .style(Style::default().bg(Theme::INPUT_BG));
+
+    let paragraph = Paragraph::new(lines).block(block);
+    frame.render_widget(paragraph, modal_area);
+}
+
+fn render_file_approval(frame: &mut Frame, app: &TuiApp) {
+    use spot_core::terminal::FileOperation;
+
+    let Some(pending) = app.file_approval.current() else {
+        return;
+    };
+    let change = &pending.change;
+
+    // Dim background for modal effect
+    dim_background(frame, frame.area());
+
+    let modal_width: u16 = 100.min(frame.area().width.saturating_sub(4));
+    let modal_height: u16 = 30.min(frame.area().height.saturating_sub(2));
+    let modal_x = (frame.area().width.saturating_sub(modal_width)) / 2;
+    let modal_y = (frame.area().height.saturating_sub(modal_height)) / 2;
+    let modal_area = Rect::new(modal_x, modal_y, modal_width, modal_height);
+
+    frame.render_widget(Clear, modal_area);
+
+    let verb = match change.operation() {
+        FileOperation::Write => "Write",
+        FileOperation::Delete => "Delete",
+    };
+    let queued = app.file_approval.pending.len() - 1;
+    let title = if queued > 0 {
+        format!(" {} {}? (+{} queued) ", verb, change.path, queued)
+    } else {
+        format!(" {} {}? ", verb, change.path)
+    };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Theme::WARNING))
+        .title(Span::styled(title, Style::default().fg(Theme::WARNING)))
+        .style(Style::default().bg(Theme::INPUT_BG));
+    let inner = block.inner(modal_area);
+    frame.render_widget(block, modal_area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(1), Constraint::Length(2)])
+        .split(inner);
+    let (body_area, footer_area) = (chunks[0], chunks[1]);
+
+    let hint = if let Some(ref editor) = app.file_approval.editor {
+        frame.render_widget(editor, body_area);
+        "Ctrl+S accept edited content · Esc back to diff"
+    } else {
+        let lines: Vec<Line> = change
+            .diff
+            .lines()
+            .take(body_area.height as usize)
+            .map(|line| {
+                let color = if line.starts_with("+++") || line.starts_with("---") {
+                    Theme::MUTED
+                } else if line.starts_with('+') {
+                    Theme::SUCCESS
+                } else if line.starts_with('-') {
+                    Theme::ERROR
+                } else if line.starts_with("@@") {
+                    Theme::ACCENT
+                } else {
+                    Theme::TEXT
+                };
+                Line::from(Span::styled(line.to_string(), Style::default().fg(color)))
+            })
+            .collect();
+        frame.render_widget(Paragraph::new(lines), body_area);
+
+        match change.operation() {
+            FileOperation::Write => "y accept · e edit · n/Esc reject",
+            FileOperation::Delete => "y accept · n/Esc reject",
+        }
+    };
+
+    let footer = vec![
+        Line::from(Span::styled(
+            "─".repeat(footer_area.width as usize),
+            Style::default().fg(Theme::BORDER),
+        )),
+        Line::from(Span::styled(hint, Style::default().fg(Theme::MUTED))),
+    ];
+    frame.render_widget(Paragraph::new(footer), footer_area);
+}
+
 fn render_help(frame: &mut Frame, area: Rect) {
     let help_lines = vec![
         Line::from(vec![Span::styled(
@@ -592,6 +896,43 @@ fn render_help(frame: &mut Frame, area: Rect) {
             Span::styled(" /attach     ", Style::default().fg(Theme::ACCENT)),
             Span::styled("Attach file command", Style::default().fg(Theme::TEXT)),
         ]),
+        Line::from(vec![
+            Span::styled(" /sessions   ", Style::default().fg(Theme::ACCENT)),
+            Span::styled("Browse saved sessions", Style::default().fg(Theme::TEXT)),
+        ]),
+        Line::from(vec![
+            Span::styled(" /save NAME  ", Style::default().fg(Theme::ACCENT)),
+            Span::styled(
+                "Save conversation as NAME",
+                Style::default().fg(Theme::TEXT),
+            ),
+        ]),
+        Line::from(vec![
+            Span::styled(" /load NAME  ", Style::default().fg(Theme::ACCENT)),
+            Span::styled("Load a saved session", Style::default().fg(Theme::TEXT)),
+        ]),
+        Line::from(vec![
+            Span::styled(" /edits      ", Style::default().fg(Theme::ACCENT)),
+            Span::styled("List file edits by id", Style::default().fg(Theme::TEXT)),
+        ]),
+        Line::from(vec![
+            Span::styled(" /revert ID  ", Style::default().fg(Theme::ACCENT)),
+            Span::styled("Revert one file edit", Style::default().fg(Theme::TEXT)),
+        ]),
+        Line::from(vec![
+            Span::styled(" /prompts    ", Style::default().fg(Theme::ACCENT)),
+            Span::styled(
+                "List MCP prompts (/server:prompt)",
+                Style::default().fg(Theme::TEXT),
+            ),
+        ]),
+        Line::from(vec![
+            Span::styled(" /resources  ", Style::default().fg(Theme::ACCENT)),
+            Span::styled(
+                "List MCP resources (@server:name)",
+                Style::default().fg(Theme::TEXT),
+            ),
+        ]),
     ];
 
     let block = Block::default()
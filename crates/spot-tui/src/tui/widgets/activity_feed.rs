@@ -260,6 +260,10 @@ fn render_header(activity: &Activity) -> (LineData, Color) {
             let suffix = format!("{} (+{} -{})", file_path, additions, deletions);
             ("Edited", suffix, Theme::ACCENT)
         }
+        Activity::Changed { turn, .. } => {
+            let suffix = format!("turn {} · /undo to revert", turn);
+            ("Changed", suffix, Theme::GREEN)
+        }
         Activity::Streaming { title, elapsed, .. } => {
             let elapsed_secs = elapsed.as_secs();
             let suffix = if elapsed_secs > 0 {
@@ -490,6 +494,10 @@ fn build_activity_lines(activity: &Activity) -> Vec<LineData> {
                 lines.push(line.with_indent(INDENT_WIDTH));
             }
         }
+        Activity::Changed { files, .. } => {
+            let len = files.len();
+            lines.extend(render_tree_items(files, |i| i == len - 1));
+        }
         Activity::Streaming { content, .. } => {
             let text_style = Style::default().fg(Theme::TEXT);
             lines.extend(render_content_lines(content, text_style));
@@ -1,9 +1,11 @@
 //! Spot CLI - Auto-detecting GUI/TUI launcher
 //!
 //! Automatically selects GUI or TUI based on environment and available features.
+//! With `--prompt`, runs a single prompt headlessly and exits.
 
 use anyhow::Result;
 use clap::Parser;
+use spot_core::headless::{HeadlessOptions, OutputFormat};
 
 #[derive(Parser, Debug)]
 #[command(name = "spot")]
@@ -15,7 +17,7 @@ struct Args {
     cwd: Option<String>,
 
     /// Force TUI mode even if GUI is available
-    #[arg(long)]
+    #[arg(long, visible_alias = "no-gui")]
     tui: bool,
 
     /// Force GUI mode
@@ -37,6 +39,26 @@ struct Args {
     /// Run the render performance test (GUI only)
     #[arg(long)]
     render_test: bool,
+
+    /// Resume a saved session (the most recent one if no name is given)
+    #[arg(long, value_name = "NAME", conflicts_with = "prompt")]
+    resume: Option<Option<String>>,
+
+    /// Run a single prompt without a UI and exit (`-` reads from stdin)
+    #[arg(short = 'p', long)]
+    prompt: Option<String>,
+
+    /// Agent to use for --prompt
+    #[arg(long, requires = "prompt")]
+    agent: Option<String>,
+
+    /// Model to use for --prompt
+    #[arg(long, requires = "prompt")]
+    model: Option<String>,
+
+    /// Output format for --prompt: text or json (NDJSON)
+    #[arg(long, default_value = "text", requires = "prompt")]
+    output: OutputFormat,
 }
 
 fn main() -> Result<()> {
@@ -52,8 +74,21 @@ fn main() -> Result<()> {
         debug: args.debug,
         verbose: args.verbose,
         skip_update_check: args.skip_update_check,
+        resume: args.resume,
     };
 
+    // Headless one-shot prompt
+    if let Some(prompt) = args.prompt {
+        let options = HeadlessOptions {
+            prompt,
+            agent: args.agent,
+            model: args.model,
+            output: args.output,
+        };
+        let code = spot_core::headless::run_headless(config, options)?;
+        std::process::exit(code);
+    }
+
     // Handle render test specially (GUI only)
     if args.render_test {
         #[cfg(feature = "gui")]